@@ -2,20 +2,40 @@ use std::collections::HashMap;
 use syn::{Lit, Meta, MetaNameValue, NestedMeta};
 
 use crate::constants::{
-    DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
+    DEFAULT_MAX_BLOB_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE,
+    DEFAULT_STACK_SIZE,
 };
+use crate::rv_trace::MemoryConfig;
 
 pub struct Attributes {
     pub wasm: bool,
+    /// Whether the guest's outputs start with a checkpoint journal (see `jolt::commit_checkpoint`).
+    pub checkpoints: bool,
     pub memory_size: u64,
     pub stack_size: u64,
     pub max_input_size: u64,
     pub max_output_size: u64,
+    pub max_blob_size: u64,
+    pub max_cycles: Option<u64>,
+}
+
+impl Attributes {
+    pub fn memory_config(&self) -> MemoryConfig {
+        MemoryConfig {
+            max_input_size: self.max_input_size,
+            max_output_size: self.max_output_size,
+            max_blob_size: self.max_blob_size,
+            stack_size: self.stack_size,
+            memory_size: self.memory_size,
+            max_cycles: self.max_cycles,
+        }
+    }
 }
 
 pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let mut attributes = HashMap::<_, u64>::new();
     let mut wasm = false;
+    let mut checkpoints = false;
 
     for attr in attr {
         match attr {
@@ -30,12 +50,17 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
                     "stack_size" => attributes.insert("stack_size", value),
                     "max_input_size" => attributes.insert("max_input_size", value),
                     "max_output_size" => attributes.insert("max_output_size", value),
+                    "max_blob_size" => attributes.insert("max_blob_size", value),
+                    "max_cycles" => attributes.insert("max_cycles", value),
                     _ => panic!("invalid attribute"),
                 };
             }
             NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wasm") => {
                 wasm = true;
             }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("checkpoints") => {
+                checkpoints = true;
+            }
             _ => panic!("expected integer literal"),
         }
     }
@@ -50,12 +75,19 @@ pub fn parse_attributes(attr: &Vec<NestedMeta>) -> Attributes {
     let max_output_size = *attributes
         .get("max_output_size")
         .unwrap_or(&DEFAULT_MAX_OUTPUT_SIZE);
+    let max_blob_size = *attributes
+        .get("max_blob_size")
+        .unwrap_or(&DEFAULT_MAX_BLOB_SIZE);
+    let max_cycles = attributes.get("max_cycles").copied();
 
     Attributes {
         wasm,
+        checkpoints,
         memory_size,
         stack_size,
         max_input_size,
         max_output_size,
+        max_blob_size,
+        max_cycles,
     }
 }
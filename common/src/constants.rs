@@ -12,6 +12,18 @@ pub const DEFAULT_MEMORY_SIZE: u64 = 10 * 1024 * 1024;
 pub const DEFAULT_STACK_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_INPUT_SIZE: u64 = 4096;
 pub const DEFAULT_MAX_OUTPUT_SIZE: u64 = 4096;
+pub const DEFAULT_MAX_BLOB_SIZE: u64 = 0;
+
+/// Value of `a7` for the environment call with which a panicking guest reports its panic message
+/// and location to the host.
+pub const PANIC_ECALL: u32 = 0x4A4F_4C50;
+/// Value of `a7` for the environment call with which a guest appends to its debug log (see
+/// `jolt::println!`). The log is collected by the host, and is not part of the program I/O.
+pub const LOG_ECALL: u32 = 0x4A4F_4C4C;
+/// Value of `a7` for the environment call with which the guest allocator reports its heap usage
+/// (`a0`: peak bytes allocated, `a1`: number of allocations, `a2`: heap size) when the guest returns,
+/// or, with the size of the allocation that failed in `a3`, when it runs out of memory.
+pub const HEAP_ECALL: u32 = 0x4A4F_4C48;
 
 pub const fn memory_address_to_witness_index(address: u64, ram_witness_offset: u64) -> usize {
     (address + ram_witness_offset - RAM_START_ADDRESS) as usize
@@ -1,8 +1,16 @@
+use std::fmt;
 use std::str::FromStr;
 
-use crate::constants::{MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use crate::constants::{
+    DEFAULT_MAX_BLOB_SIZE, DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE,
+    DEFAULT_STACK_SIZE, MEMORY_OPS_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT,
+};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
 use strum::EnumCount;
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter, FromRepr};
 
@@ -45,6 +53,19 @@ fn sum_u64_i32(a: u64, b: i32) -> u64 {
 
 impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
     fn from(val: &RVTraceRow) -> Self {
+        // Environment calls are traced as no-ops, which don't access any registers or memory
+        if val.instruction.opcode == RV32IM::UNIMPL {
+            return [
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_write(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+            ];
+        }
+
         let instruction_type = val.instruction.opcode.instruction_type();
 
         let rs1_read = || MemoryOp::Read(val.instruction.rs1.unwrap());
@@ -116,7 +137,16 @@ impl From<&RVTraceRow> for [MemoryOp; MEMORY_OPS_PER_INSTRUCTION] {
                 | RV32IM::SLTIU
                 | RV32IM::JALR
                 | RV32IM::VIRTUAL_MOVE
-                | RV32IM::VIRTUAL_MOVSIGN => [
+                | RV32IM::VIRTUAL_MOVSIGN
+                | RV32IM::CLZ
+                | RV32IM::CTZ
+                | RV32IM::CPOP
+                | RV32IM::SEXT_B
+                | RV32IM::SEXT_H
+                | RV32IM::ZEXT_H
+                | RV32IM::RORI
+                | RV32IM::ORC_B
+                | RV32IM::REV8 => [
                     rs1_read(),
                     MemoryOp::noop_read(),
                     rd_write(),
@@ -365,7 +395,18 @@ impl ELFInstruction {
             | RV32IM::VIRTUAL_ASSERT_LTE
             | RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER
             | RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER
-            | RV32IM::VIRTUAL_ASSERT_VALID_DIV0,
+            | RV32IM::VIRTUAL_ASSERT_VALID_DIV0
+            | RV32IM::ANDN
+            | RV32IM::XNOR
+            | RV32IM::CLZ
+            | RV32IM::CTZ
+            | RV32IM::CPOP
+            | RV32IM::ORC_B
+            | RV32IM::REV8
+            // Custom instructions' lookup queries are only constrained to match their
+            // operands if they are concatenated, so custom instructions must chunk their
+            // operands with `chunk_and_concatenate_operands`.
+            | RV32IM::CUSTOM_0,
         );
 
         flags[CircuitFlags::Virtual as usize] = self.virtual_sequence_remaining.is_some();
@@ -388,6 +429,15 @@ impl ELFInstruction {
 
         flags
     }
+
+    /// For a `custom-0` instruction, returns its `(funct3, funct7)` fields. These identify which
+    /// custom instruction it is, and are carried in `imm` as `funct7 << 3 | funct3`.
+    pub fn custom_funct(&self) -> Option<(u32, u32)> {
+        match (self.opcode, self.imm) {
+            (RV32IM::CUSTOM_0, Some(imm)) => Some((imm & 0b111, imm >> 3)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -482,6 +532,27 @@ pub enum RV32IM {
     VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER,
     VIRTUAL_ASSERT_EQ,
     VIRTUAL_ASSERT_VALID_DIV0,
+    // Application-defined instructions in the `custom-0` opcode space
+    CUSTOM_0,
+    // Zbb (basic bit-manipulation) extension
+    ANDN,
+    ORN,
+    XNOR,
+    CLZ,
+    CTZ,
+    CPOP,
+    MAX,
+    MAXU,
+    MIN,
+    MINU,
+    SEXT_B,
+    SEXT_H,
+    ZEXT_H,
+    ROL,
+    ROR,
+    RORI,
+    ORC_B,
+    REV8,
 }
 
 impl FromStr for RV32IM {
@@ -539,6 +610,25 @@ impl FromStr for RV32IM {
             "REMU" => Ok(Self::REMU),
             "FENCE" => Ok(Self::FENCE),
             "UNIMPL" => Ok(Self::UNIMPL),
+            "CUSTOM_0" => Ok(Self::CUSTOM_0),
+            "ANDN" => Ok(Self::ANDN),
+            "ORN" => Ok(Self::ORN),
+            "XNOR" => Ok(Self::XNOR),
+            "CLZ" => Ok(Self::CLZ),
+            "CTZ" => Ok(Self::CTZ),
+            "CPOP" => Ok(Self::CPOP),
+            "MAX" => Ok(Self::MAX),
+            "MAXU" => Ok(Self::MAXU),
+            "MIN" => Ok(Self::MIN),
+            "MINU" => Ok(Self::MINU),
+            "SEXT.B" => Ok(Self::SEXT_B),
+            "SEXT.H" => Ok(Self::SEXT_H),
+            "ZEXT.H" => Ok(Self::ZEXT_H),
+            "ROL" => Ok(Self::ROL),
+            "ROR" => Ok(Self::ROR),
+            "RORI" => Ok(Self::RORI),
+            "ORC.B" => Ok(Self::ORC_B),
+            "REV8" => Ok(Self::REV8),
             _ => Err("Could not match instruction to RV32IM set.".to_string()),
         }
     }
@@ -576,7 +666,17 @@ impl RV32IM {
             RV32IM::DIV    |
             RV32IM::DIVU   |
             RV32IM::REM    |
-            RV32IM::REMU => RV32InstructionFormat::R,
+            RV32IM::REMU   |
+            RV32IM::CUSTOM_0 |
+            RV32IM::ANDN   |
+            RV32IM::ORN    |
+            RV32IM::XNOR   |
+            RV32IM::MAX    |
+            RV32IM::MAXU   |
+            RV32IM::MIN    |
+            RV32IM::MINU   |
+            RV32IM::ROL    |
+            RV32IM::ROR    => RV32InstructionFormat::R,
 
             RV32IM::ADDI         |
             RV32IM::XORI         |
@@ -591,6 +691,17 @@ impl RV32IM {
             RV32IM::VIRTUAL_MOVE |
             RV32IM::VIRTUAL_MOVSIGN=> RV32InstructionFormat::I,
 
+            // Zbb instructions with a single source register
+            RV32IM::CLZ    |
+            RV32IM::CTZ    |
+            RV32IM::CPOP   |
+            RV32IM::SEXT_B |
+            RV32IM::SEXT_H |
+            RV32IM::ZEXT_H |
+            RV32IM::RORI   |
+            RV32IM::ORC_B  |
+            RV32IM::REV8   => RV32InstructionFormat::I,
+
             RV32IM::LB  |
             RV32IM::LH  |
             RV32IM::LW  |
@@ -638,16 +749,23 @@ pub struct JoltDevice {
     pub inputs: Vec<u8>,
     pub outputs: Vec<u8>,
     pub panic: bool,
+    /// Set to the guest's `max_cycles` if it was halted for exceeding it, in which case the
+    /// outputs are whatever the guest had written by then. See [`MemoryConfig::max_cycles`].
+    pub exceeded_max_cycles: Option<u64>,
+    /// Read-only data mapped into the guest's blob region, of which only the hashes are public.
+    pub blobs: Blobs,
     pub memory_layout: MemoryLayout,
 }
 
 impl JoltDevice {
-    pub fn new(max_input_size: u64, max_output_size: u64) -> Self {
+    pub fn new(memory_config: &MemoryConfig) -> Self {
         Self {
             inputs: Vec::new(),
             outputs: Vec::new(),
             panic: false,
-            memory_layout: MemoryLayout::new(max_input_size, max_output_size),
+            exceeded_max_cycles: None,
+            blobs: Blobs::default(),
+            memory_layout: MemoryLayout::new(memory_config),
         }
     }
 
@@ -700,6 +818,274 @@ impl JoltDevice {
     }
 }
 
+pub const BLOB_HASH_SIZE: usize = 32;
+const BLOB_DIRECTORY_ENTRY_SIZE: usize = core::mem::size_of::<u32>();
+
+/// Large read-only byte blobs registered by the host, which the guest reads from its blob region
+/// (see [`MemoryLayout::blob_start`]) with `jolt::read_blob`.
+///
+/// Only the blobs' Keccak-256 hashes are part of the program I/O: they are what gets serialized
+/// with a [`JoltDevice`] and bound to its proof. A deserialized device has no blob data, which
+/// the verifier, who needs it to check the guest's initial memory, must [`attach`](Self::attach)
+/// before verifying.
+///
+/// The blob region holds a directory of the blobs' lengths, followed by the blobs back to back:
+///
+/// ```text
+/// [ count: u32 LE | len_0: u32 LE | ... | len_{count-1}: u32 LE | blob_0 | ... | blob_{count-1} ]
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Blobs {
+    hashes: Vec<[u8; BLOB_HASH_SIZE]>,
+    #[serde(skip)]
+    data: Vec<Vec<u8>>,
+}
+
+impl Blobs {
+    pub fn new(data: Vec<Vec<u8>>) -> Self {
+        Self {
+            hashes: data.iter().map(|blob| blob_hash(blob)).collect(),
+            data,
+        }
+    }
+
+    /// Adds `blob`, returning its id.
+    ///
+    /// # Panics
+    ///
+    /// If the data of the existing blobs isn't attached.
+    pub fn push(&mut self, blob: Vec<u8>) -> usize {
+        assert!(self.is_attached(), "blob data isn't attached");
+        self.hashes.push(blob_hash(&blob));
+        self.data.push(blob);
+        self.hashes.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    pub fn hashes(&self) -> &[[u8; BLOB_HASH_SIZE]] {
+        &self.hashes
+    }
+
+    /// The data of blob `id`, if it exists and is attached.
+    pub fn get(&self, id: usize) -> Option<&[u8]> {
+        self.data.get(id).map(Vec::as_slice)
+    }
+
+    /// Whether the data of every blob is available, as opposed to only their hashes.
+    pub fn is_attached(&self) -> bool {
+        self.data.len() == self.hashes.len()
+    }
+
+    /// Attaches the blobs' data, e.g. to a device deserialized from a proof. Returns whether
+    /// `data` matches the blobs' hashes; if it doesn't, nothing is attached.
+    pub fn attach(&mut self, data: Vec<Vec<u8>>) -> bool {
+        let matches = data.len() == self.hashes.len()
+            && data
+                .iter()
+                .zip(&self.hashes)
+                .all(|(blob, hash)| blob_hash(blob) == *hash);
+        if matches {
+            self.data = data;
+        }
+        matches
+    }
+
+    /// Size of the blob region's contents: the directory and the blobs.
+    pub fn region_size(&self) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+        let directory_size = (self.len() + 1) * BLOB_DIRECTORY_ENTRY_SIZE;
+        (directory_size + self.data.iter().map(Vec::len).sum::<usize>()) as u64
+    }
+
+    /// The contents of the blob region, which is left zeroed if there are no blobs.
+    ///
+    /// # Panics
+    ///
+    /// If the data of the blobs isn't attached.
+    pub fn region(&self) -> Vec<u8> {
+        assert!(self.is_attached(), "blob data isn't attached");
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut region = Vec::with_capacity(self.region_size() as usize);
+        region.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        for blob in self.data.iter() {
+            let len = u32::try_from(blob.len()).expect("blobs are at most 4 GiB");
+            region.extend_from_slice(&len.to_le_bytes());
+        }
+        for blob in self.data.iter() {
+            region.extend_from_slice(blob);
+        }
+        region
+    }
+}
+
+/// Blobs are equal if their hashes are, whether or not their data is attached.
+impl PartialEq for Blobs {
+    fn eq(&self, other: &Self) -> bool {
+        self.hashes == other.hashes
+    }
+}
+
+impl CanonicalSerialize for Blobs {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.hashes.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.hashes.serialized_size(compress)
+    }
+}
+
+impl Valid for Blobs {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for Blobs {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            hashes: Vec::deserialize_with_mode(reader, compress, validate)?,
+            data: vec![],
+        })
+    }
+}
+
+fn blob_hash(blob: &[u8]) -> [u8; BLOB_HASH_SIZE] {
+    Keccak256::digest(blob).into()
+}
+
+/// A guest panic, as reported to the host by the guest's panic handler. Unlike the panic bit in
+/// [`JoltDevice`], this is not part of the program I/O, and is only known to whoever traced the
+/// program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestPanic {
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    /// Set if the guest panicked because an allocation didn't fit in its heap.
+    pub out_of_memory: Option<OutOfMemory>,
+}
+
+impl fmt::Display for GuestPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "guest panicked at {}:{}:{}:\n{}",
+            self.file, self.line, self.column, self.message
+        )?;
+        if let Some(out_of_memory) = &self.out_of_memory {
+            write!(f, "\n{out_of_memory}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GuestPanic {}
+
+/// The guest's heap usage, as reported to the host by the jolt-sdk allocator when the guest
+/// returns or runs out of memory. Like [`GuestPanic`], this is not part of the program I/O.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeapStats {
+    /// Most bytes allocated at once, including alignment padding. The allocator only frees
+    /// memory when the guest leaves a `jolt::arena::scope`, so without arenas this is the total.
+    pub peak_bytes: u64,
+    /// Number of allocations.
+    pub allocations: u64,
+    /// Size of the heap, from the end of the stack to the end of RAM.
+    pub capacity: u64,
+}
+
+impl fmt::Display for HeapStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} heap bytes used by {} allocations",
+            self.peak_bytes, self.capacity, self.allocations
+        )
+    }
+}
+
+/// An allocation that didn't fit in the guest's heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutOfMemory {
+    /// Size of the allocation that failed, in bytes.
+    pub size: u64,
+    /// Heap usage before the allocation.
+    pub heap: HeapStats,
+}
+
+impl fmt::Display for OutOfMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "guest ran out of heap allocating {} bytes ({}); increase `memory_size`",
+            self.size, self.heap
+        )
+    }
+}
+
+/// An error that stopped the guest without it panicking, because it left the memory the tracer
+/// emulates. Like [`GuestPanic`], this is not part of the program I/O.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuestFault {
+    /// The instruction at `pc` accessed `address`, beyond the end of the guest's RAM of
+    /// `memory_size` bytes.
+    MemoryOutOfBounds {
+        pc: u64,
+        address: u64,
+        memory_size: u64,
+    },
+    /// The instruction at `pc` moved the stack pointer to `sp`, below the bottom of the stack.
+    StackOverflow { pc: u64, sp: u64, stack_bottom: u64 },
+}
+
+impl fmt::Display for GuestFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MemoryOutOfBounds {
+                pc,
+                address,
+                memory_size,
+            } => write!(
+                f,
+                "Guest accessed address {address:#X} at PC {pc:#X}, beyond the end of its RAM; \
+                 increase `memory_size` (currently {memory_size} bytes)"
+            ),
+            Self::StackOverflow {
+                pc,
+                sp,
+                stack_bottom,
+            } => write!(
+                f,
+                "Guest stack overflow at PC {pc:#X}: stack pointer {sp:#X} is below the bottom of \
+                 the stack ({stack_bottom:#X}); increase `stack_size`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GuestFault {}
+
 #[derive(
     Debug, Clone, PartialEq, Serialize, Deserialize, CanonicalSerialize, CanonicalDeserialize,
 )]
@@ -712,10 +1098,19 @@ pub struct MemoryLayout {
     pub output_start: u64,
     pub output_end: u64,
     pub panic: u64,
+    /// Size of the guest's RAM (program, data, stack, and heap), starting at `RAM_START_ADDRESS`.
+    pub memory_size: u64,
+    pub stack_size: u64,
+    pub max_blob_size: u64,
+    /// Start of the blob region (see [`Blobs`]), which follows the rest of RAM.
+    pub blob_start: u64,
+    /// One past the last addressable byte of RAM, including the blob region.
+    pub ram_end: u64,
 }
 
 impl MemoryLayout {
-    pub fn new(max_input_size: u64, max_output_size: u64) -> Self {
+    pub fn new(config: &MemoryConfig) -> Self {
+        let (max_input_size, max_output_size) = (config.max_input_size, config.max_output_size);
         Self {
             ram_witness_offset: ram_witness_offset(max_input_size, max_output_size),
             max_input_size,
@@ -725,6 +1120,54 @@ impl MemoryLayout {
             output_start: output_start(max_input_size, max_output_size),
             output_end: output_end(max_input_size, max_output_size),
             panic: panic_address(max_input_size, max_output_size),
+            memory_size: config.memory_size,
+            stack_size: config.stack_size,
+            max_blob_size: config.max_blob_size,
+            blob_start: RAM_START_ADDRESS + config.memory_size,
+            ram_end: RAM_START_ADDRESS + config.memory_size + config.max_blob_size,
+        }
+    }
+
+    /// Number of entries in the memory-checking polynomials needed to cover every address in
+    /// this layout: registers, program I/O, and all of RAM.
+    pub fn max_witness_size(&self) -> u64 {
+        self.ram_witness_offset + self.memory_size + self.max_blob_size
+    }
+
+    pub fn is_ram(&self, address: u64) -> bool {
+        (RAM_START_ADDRESS..self.ram_end).contains(&address)
+    }
+
+    pub fn is_blob(&self, address: u64) -> bool {
+        (self.blob_start..self.ram_end).contains(&address)
+    }
+}
+
+/// Sizes of the guest's memory regions, and its cycle limit, as configured via
+/// `#[jolt::provable(...)]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryConfig {
+    pub max_input_size: u64,
+    pub max_output_size: u64,
+    pub stack_size: u64,
+    pub memory_size: u64,
+    /// Size of the read-only blob region after RAM, which must fit the [`Blobs`] the guest is
+    /// run with.
+    pub max_blob_size: u64,
+    /// Number of RISC-V instructions after which the guest is halted, if any. A halted guest
+    /// is recorded in [`JoltDevice::exceeded_max_cycles`].
+    pub max_cycles: Option<u64>,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            max_input_size: DEFAULT_MAX_INPUT_SIZE,
+            max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            stack_size: DEFAULT_STACK_SIZE,
+            memory_size: DEFAULT_MEMORY_SIZE,
+            max_blob_size: DEFAULT_MAX_BLOB_SIZE,
+            max_cycles: None,
         }
     }
 }
@@ -0,0 +1,72 @@
+//! Batched HyperKZG openings of many large polynomials, as in the final opening proof of a long
+//! trace. Each batch is opened with `BatchType::Big` on a single thread (the serial baseline) and
+//! on all threads, and with `BatchType::Huge` on all threads. The largest batch holds 4 GiB of
+//! evaluations.
+
+use ark_bn254::{Bn254, Fr};
+use ark_std::UniformRand;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use jolt_core::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
+use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+use jolt_core::poly::dense_mlpoly::DensePolynomial;
+use jolt_core::utils::transcript::ProofTranscript;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use rayon::prelude::*;
+use std::hint::black_box;
+
+#[allow(clippy::upper_case_acronyms)]
+type PCS = HyperKZG<Bn254>;
+
+const LOG_POLY_SIZE: usize = 20;
+const NUM_POLYS: [usize; 3] = [8, 32, 128];
+
+fn batch_prove(c: &mut Criterion) {
+    let n = 1 << LOG_POLY_SIZE;
+    let setup = PCS::setup(&[CommitShape::new(n, BatchType::Big)]);
+    let serial = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("hyperkzg_batch_prove");
+    group.sample_size(10);
+    for num_polys in NUM_POLYS {
+        let polys: Vec<DensePolynomial<Fr>> = (0..num_polys)
+            .into_par_iter()
+            .map(|i| {
+                let mut rng = ChaCha20Rng::seed_from_u64(i as u64);
+                DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect())
+            })
+            .collect();
+        let mut rng = ChaCha20Rng::seed_from_u64(num_polys as u64);
+        let point: Vec<Fr> = (0..LOG_POLY_SIZE).map(|_| Fr::rand(&mut rng)).collect();
+        let evals: Vec<Fr> = polys.par_iter().map(|poly| poly.evaluate(&point)).collect();
+        let polys: Vec<&DensePolynomial<Fr>> = polys.iter().collect();
+
+        let prove = |batch_type| {
+            let mut transcript = ProofTranscript::new(b"bench");
+            black_box(PCS::batch_prove(
+                &setup,
+                &polys,
+                &point,
+                &evals,
+                batch_type,
+                &mut transcript,
+            ))
+        };
+        group.bench_function(BenchmarkId::new("big_serial", num_polys), |b| {
+            b.iter(|| serial.install(|| prove(BatchType::Big)))
+        });
+        group.bench_function(BenchmarkId::new("big", num_polys), |b| {
+            b.iter(|| prove(BatchType::Big))
+        });
+        group.bench_function(BenchmarkId::new("huge", num_polys), |b| {
+            b.iter(|| prove(BatchType::Huge))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch_prove);
+criterion_main!(benches);
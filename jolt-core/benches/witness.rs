@@ -0,0 +1,124 @@
+//! Scaling of witness generation with the number of threads. Each witness is generated for the
+//! same random trace in thread pools of 1 to 32 threads (as many as the machine has), so the
+//! reported throughputs should grow close to linearly with the thread count.
+
+use ark_bn254::{Bn254, Fr};
+use common::rv_trace::MemoryOp;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jolt_core::jolt::vm::bytecode::{BytecodePreprocessing, BytecodeProof, BytecodeRow};
+use jolt_core::jolt::vm::instruction_lookups::{
+    InstructionLookupsPreprocessing, InstructionLookupsProof,
+};
+use jolt_core::jolt::vm::rv32i_vm::{RV32ISubtables, C, M, RV32I};
+use jolt_core::jolt::vm::JoltTraceStep;
+use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+use jolt_core::r1cs::inputs::{JoltR1CSInputs, R1CSPolynomials};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::hint::black_box;
+
+#[allow(clippy::upper_case_acronyms)]
+type PCS = HyperKZG<Bn254>;
+
+const LOG_NUM_STEPS: usize = 20;
+const BYTECODE_SIZE: usize = 1 << 12;
+const THREAD_COUNTS: [usize; 6] = [1, 2, 4, 8, 16, 32];
+
+fn random_trace(
+    num_steps: usize,
+    rng: &mut StdRng,
+) -> (BytecodePreprocessing<Fr>, Vec<JoltTraceStep<RV32I>>) {
+    let bytecode: Vec<BytecodeRow> = (0..BYTECODE_SIZE)
+        .map(|i| BytecodeRow::random(i, rng))
+        .collect();
+    let trace = (0..num_steps)
+        .map(|_| JoltTraceStep {
+            instruction_lookup: Some(RV32I::random_instruction(rng)),
+            bytecode_row: bytecode[rng.next_u64() as usize % BYTECODE_SIZE].clone(),
+            memory_ops: [
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_write(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+                MemoryOp::noop_read(),
+            ],
+            circuit_flags: std::array::from_fn(|_| rng.next_u32() % 2 == 0),
+        })
+        .collect();
+    (BytecodePreprocessing::preprocess(bytecode), trace)
+}
+
+fn witness_generation(c: &mut Criterion) {
+    let num_steps = 1 << LOG_NUM_STEPS;
+    let mut rng = StdRng::seed_from_u64(0);
+    let (bytecode_preprocessing, trace) = random_trace(num_steps, &mut rng);
+    let instruction_lookups_preprocessing =
+        InstructionLookupsPreprocessing::<C, Fr>::preprocess::<M, RV32I, RV32ISubtables<Fr>>();
+    let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    let mut group = c.benchmark_group("witness_generation");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(num_steps as u64));
+    for num_threads in THREAD_COUNTS.into_iter().filter(|&n| n <= available) {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("instruction_lookups", num_threads),
+            &trace,
+            |b, trace| {
+                b.iter(|| {
+                    pool.install(|| {
+                        black_box(InstructionLookupsProof::<
+                            C,
+                            M,
+                            Fr,
+                            PCS,
+                            RV32I,
+                            RV32ISubtables<Fr>,
+                        >::generate_witness(
+                            &instruction_lookups_preprocessing, trace
+                        ))
+                    })
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("bytecode", num_threads),
+            &trace,
+            |b, trace| {
+                // Bytecode witness generation rewrites the trace's addresses in place
+                b.iter_batched(
+                    || trace.clone(),
+                    |mut trace| {
+                        pool.install(|| {
+                            black_box(BytecodeProof::<Fr, PCS>::generate_witness(
+                                &bytecode_preprocessing,
+                                &mut trace,
+                            ))
+                        })
+                    },
+                    criterion::BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("r1cs", num_threads), &trace, |b, trace| {
+            b.iter(|| {
+                pool.install(|| {
+                    black_box(R1CSPolynomials::<Fr>::new::<C, M, RV32I, JoltR1CSInputs>(
+                        trace,
+                        trace.len(),
+                    ))
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, witness_generation);
+criterion_main!(benches);
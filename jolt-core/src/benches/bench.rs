@@ -7,7 +7,7 @@ use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::poly::commitment::hyrax::HyraxScheme;
 use crate::poly::commitment::zeromorph::Zeromorph;
 use ark_bn254::{Bn254, Fr, G1Projective};
-use serde::Serialize;
+use tracer::Profile;
 
 #[derive(Debug, Copy, Clone, clap::ValueEnum)]
 pub enum PCSType {
@@ -22,6 +22,8 @@ pub enum BenchType {
     Sha2,
     Sha3,
     Sha2Chain,
+    MerkleVerify,
+    JsonParse,
 }
 
 #[allow(unreachable_patterns)] // good errors on new BenchTypes
@@ -38,6 +40,8 @@ pub fn benchmarks(
             BenchType::Sha3 => sha3::<Fr, HyraxScheme<G1Projective>>(),
             BenchType::Sha2Chain => sha2chain::<Fr, HyraxScheme<G1Projective>>(),
             BenchType::Fibonacci => fibonacci::<Fr, HyraxScheme<G1Projective>>(),
+            BenchType::MerkleVerify => merkle_verify::<Fr, HyraxScheme<G1Projective>>(),
+            BenchType::JsonParse => json_parse::<Fr, HyraxScheme<G1Projective>>(),
             _ => panic!("BenchType does not have a mapping"),
         },
         PCSType::Zeromorph => match bench_type {
@@ -45,6 +49,8 @@ pub fn benchmarks(
             BenchType::Sha3 => sha3::<Fr, Zeromorph<Bn254>>(),
             BenchType::Sha2Chain => sha2chain::<Fr, Zeromorph<Bn254>>(),
             BenchType::Fibonacci => fibonacci::<Fr, Zeromorph<Bn254>>(),
+            BenchType::MerkleVerify => merkle_verify::<Fr, Zeromorph<Bn254>>(),
+            BenchType::JsonParse => json_parse::<Fr, Zeromorph<Bn254>>(),
             _ => panic!("BenchType does not have a mapping"),
         },
         PCSType::HyperKZG => match bench_type {
@@ -52,6 +58,8 @@ pub fn benchmarks(
             BenchType::Sha3 => sha3::<Fr, HyperKZG<Bn254>>(),
             BenchType::Sha2Chain => sha2chain::<Fr, HyperKZG<Bn254>>(),
             BenchType::Fibonacci => fibonacci::<Fr, HyperKZG<Bn254>>(),
+            BenchType::MerkleVerify => merkle_verify::<Fr, HyperKZG<Bn254>>(),
+            BenchType::JsonParse => json_parse::<Fr, HyperKZG<Bn254>>(),
             _ => panic!("BenchType does not have a mapping"),
         },
         _ => panic!("PCS Type does not have a mapping"),
@@ -63,7 +71,7 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
-    prove_example::<u32, PCS, F>("fibonacci-guest", &9u32)
+    prove_example::<PCS, F>(guest_program(BenchType::Fibonacci))
 }
 
 fn sha2<F, PCS>() -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
@@ -71,7 +79,7 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
-    prove_example::<Vec<u8>, PCS, F>("sha2-guest", &vec![5u8; 2048])
+    prove_example::<PCS, F>(guest_program(BenchType::Sha2))
 }
 
 fn sha3<F, PCS>() -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
@@ -79,31 +87,110 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
-    prove_example::<Vec<u8>, PCS, F>("sha3-guest", &vec![5u8; 2048])
+    prove_example::<PCS, F>(guest_program(BenchType::Sha3))
 }
 
-#[allow(dead_code)]
-fn serialize_and_print_size(name: &str, item: &impl ark_serialize::CanonicalSerialize) {
-    use std::fs::File;
-    let mut file = File::create("temp_file").unwrap();
-    item.serialize_compressed(&mut file).unwrap();
-    let file_size_bytes = file.metadata().unwrap().len();
-    let file_size_kb = file_size_bytes as f64 / 1024.0;
-    let file_size_mb = file_size_kb / 1024.0;
-    println!("{:<30} : {:.3} MB", name, file_size_mb);
+fn merkle_verify<F, PCS>() -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    prove_example::<PCS, F>(guest_program(BenchType::MerkleVerify))
+}
+
+fn json_parse<F, PCS>() -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    prove_example::<PCS, F>(guest_program(BenchType::JsonParse))
 }
 
-fn prove_example<T: Serialize, PCS, F>(
-    example_name: &str,
-    input: &T,
-) -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
+/// Traces the guest program used by `bench_type` and attributes its cycles to
+/// functions, source lines, and call stacks.
+pub fn profile(bench_type: BenchType) -> Profile {
+    guest_program(bench_type)
+        .trace_analyze::<Fr>()
+        .profile()
+        .expect("failed to profile the guest")
+}
+
+fn guest_program(bench_type: BenchType) -> host::Program {
+    match bench_type {
+        BenchType::Fibonacci => {
+            let mut program = host::Program::new("fibonacci-guest");
+            program.set_input(&9u32);
+            program
+        }
+        BenchType::Sha2 => {
+            let mut program = host::Program::new("sha2-guest");
+            program.set_input(&vec![5u8; 2048]);
+            program
+        }
+        BenchType::Sha3 => {
+            let mut program = host::Program::new("sha3-guest");
+            program.set_input(&vec![5u8; 2048]);
+            program
+        }
+        BenchType::Sha2Chain => {
+            let mut program = host::Program::new("sha2-chain-guest");
+            program.set_input(&[5u8; 32]);
+            program.set_input(&1000u32);
+            program
+        }
+        // The guests below are from `jolt-examples`, whose tests check their outputs
+        BenchType::MerkleVerify => {
+            use sha2::{Digest, Sha256};
+
+            // Opens a leaf of a tree of depth 24; only the path to it is needed to compute the root
+            let leaf = [5u8; 32];
+            let index = 0xabcdefu32;
+            let path: Vec<u8> = (0..24u8).flat_map(|depth| [depth; 32]).collect();
+            let root = path
+                .chunks_exact(32)
+                .enumerate()
+                .fold(leaf, |node, (depth, sibling)| {
+                    let (left, right) = if (index >> depth) & 1 == 0 {
+                        (&node[..], sibling)
+                    } else {
+                        (sibling, &node[..])
+                    };
+                    Sha256::new()
+                        .chain_update(left)
+                        .chain_update(right)
+                        .finalize()
+                        .into()
+                });
+
+            let mut program = host::Program::new("jolt-examples-guest");
+            program.set_func("merkle_verify");
+            program.set_input(&root);
+            program.set_input(&leaf);
+            program.set_input(&index);
+            program.set_input(&path);
+            program
+        }
+        BenchType::JsonParse => {
+            let transfers: Vec<String> = (0..40)
+                .map(|i| format!(r#"{{"to": "account-{i}", "amount": {}}}"#, 100 + i))
+                .collect();
+            let json = format!(r#"{{"transfers": [{}]}}"#, transfers.join(", "));
+
+            let mut program = host::Program::new("jolt-examples-guest");
+            program.set_func("json_total");
+            program.set_stack_size(65536);
+            program.set_input(&json);
+            program
+        }
+    }
+}
+
+fn prove_example<PCS, F>(mut program: host::Program) -> Vec<(tracing::Span, Box<dyn FnOnce()>)>
 where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
     let mut tasks = Vec::new();
-    let mut program = host::Program::new(example_name);
-    program.set_input(input);
 
     let task = move || {
         let (bytecode, memory_init) = program.decode();
@@ -113,20 +200,14 @@ where
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
 
         let (jolt_proof, jolt_commitments, _) =
-            <RV32IJoltVM as Jolt<_, PCS, C, M>>::prove(io_device, trace, preprocessing.clone());
-
-        println!("Proof sizing:");
-        serialize_and_print_size("jolt_commitments", &jolt_commitments);
-        serialize_and_print_size("jolt_proof", &jolt_proof);
-        serialize_and_print_size(" jolt_proof.r1cs", &jolt_proof.r1cs);
-        serialize_and_print_size(" jolt_proof.bytecode", &jolt_proof.bytecode);
-        serialize_and_print_size(
-            " jolt_proof.read_write_memory",
-            &jolt_proof.read_write_memory,
-        );
-        serialize_and_print_size(
-            " jolt_proof.instruction_lookups",
-            &jolt_proof.instruction_lookups,
+            <RV32IJoltVM as Jolt<_, PCS, C, M>>::prove(io_device, trace, preprocessing.clone())
+                .unwrap();
+
+        let breakdown = jolt_proof.size_breakdown(&jolt_commitments);
+        println!("Proof sizing:\n{breakdown}");
+        println!(
+            "Estimated EVM verification gas: {:?}",
+            breakdown.estimate_evm_gas()
         );
 
         let verification_result =
@@ -152,9 +233,7 @@ where
     PCS: CommitmentScheme<Field = F>,
 {
     let mut tasks = Vec::new();
-    let mut program = host::Program::new("sha2-chain-guest");
-    program.set_input(&[5u8; 32]);
-    program.set_input(&1000u32);
+    let mut program = guest_program(BenchType::Sha2Chain);
 
     let task = move || {
         let (bytecode, memory_init) = program.decode();
@@ -164,7 +243,8 @@ where
             RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 22);
 
         let (jolt_proof, jolt_commitments, _) =
-            <RV32IJoltVM as Jolt<_, PCS, C, M>>::prove(io_device, trace, preprocessing.clone());
+            <RV32IJoltVM as Jolt<_, PCS, C, M>>::prove(io_device, trace, preprocessing.clone())
+                .unwrap();
         let verification_result =
             RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, None);
         assert!(
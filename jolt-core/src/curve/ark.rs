@@ -0,0 +1,99 @@
+use ark_ec::{CurveGroup, Group};
+use ark_std::UniformRand;
+
+use super::JoltCurve;
+use crate::field::JoltField;
+use crate::msm::VariableBaseMSM;
+
+/// Every arkworks curve whose scalar field is a [`JoltField`], with MSMs computed by
+/// [`crate::msm`].
+impl<G> JoltCurve for G
+where
+    G: CurveGroup,
+    G::ScalarField: JoltField,
+{
+    type Scalar = G::ScalarField;
+    type Affine = G::Affine;
+
+    fn generator() -> Self {
+        <G as Group>::generator()
+    }
+
+    fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
+        <G as UniformRand>::rand(rng)
+    }
+
+    fn double(&self) -> Self {
+        <G as Group>::double(self)
+    }
+
+    fn to_affine(&self) -> Self::Affine {
+        self.into_affine()
+    }
+
+    fn from_affine(point: &Self::Affine) -> Self {
+        (*point).into()
+    }
+
+    fn normalize_batch(points: &[Self]) -> Vec<Self::Affine> {
+        <G as CurveGroup>::normalize_batch(points)
+    }
+
+    fn msm(bases: &[Self::Affine], scalars: &[Self::Scalar]) -> Result<Self, usize> {
+        <G as VariableBaseMSM>::msm(bases, scalars)
+    }
+
+    fn transcript_bytes(&self, buf: &mut Vec<u8>) {
+        crate::utils::transcript::point_bytes(self, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::test_rng;
+
+    use crate::utils::transcript::ProofTranscript;
+
+    /// Exercises the operations through the trait alone, as code generic over a backend would.
+    fn check_group_laws<G: JoltCurve>() {
+        let mut rng = test_rng();
+        let (p, q) = (G::random(&mut rng), G::random(&mut rng));
+        let (a, b) = (G::Scalar::random(&mut rng), G::Scalar::random(&mut rng));
+
+        assert_eq!(p + q, q + p);
+        assert_eq!(p.double(), p + p);
+        assert_eq!(p + q - q, p);
+        assert_eq!(p * a + p * b, p * (a + b));
+        assert_eq!(G::from_affine(&p.to_affine()), p);
+        assert_eq!(
+            G::normalize_batch(&[p, q]),
+            vec![p.to_affine(), q.to_affine()]
+        );
+        assert_eq!(
+            G::msm(&G::normalize_batch(&[p, q]), &[a, b]).unwrap(),
+            p * a + q * b
+        );
+        assert_eq!(G::msm(&[p.to_affine()], &[a, b]), Err(1));
+    }
+
+    #[test]
+    fn ark_curves() {
+        check_group_laws::<G1Projective>();
+        check_group_laws::<ark_bls12_381::G1Projective>();
+    }
+
+    #[test]
+    fn transcript_bytes_match_append_point() {
+        let point = <G1Projective as JoltCurve>::random(&mut test_rng());
+        let mut via_trait = ProofTranscript::new(b"test");
+        via_trait.append_curve_point(&point);
+        let mut direct = ProofTranscript::new(b"test");
+        direct.append_point(&point);
+        assert_eq!(
+            via_trait.challenge_scalar::<Fr>(),
+            direct.challenge_scalar::<Fr>()
+        );
+    }
+}
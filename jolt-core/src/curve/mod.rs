@@ -0,0 +1,65 @@
+//! The elliptic curve operations Jolt's commitment schemes use, so that a backend other than
+//! arkworks (e.g. halo2curves, or constantine through FFI) can be slotted in by implementing
+//! [`JoltCurve`] for its group, and [`JoltField`] for its scalar field, instead of rewriting every
+//! module that names arkworks types. [`ark`] implements both for every arkworks curve whose
+//! scalar field is a [`JoltField`].
+//!
+//! Code that only needs these operations should be generic over [`JoltCurve`] rather than
+//! `ark_ec::CurveGroup`. Pedersen commitments and Hyrax are; the pairing-based schemes still
+//! name arkworks' `Pairing` directly.
+
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+
+use crate::field::JoltField;
+
+/// A prime-order group of elliptic curve points, in a representation where addition is cheap
+/// (e.g. projective coordinates), with scalars in [`Self::Scalar`].
+pub trait JoltCurve:
+    'static
+    + Sized
+    + Copy
+    + Eq
+    + Send
+    + Sync
+    + Debug
+    + Default
+    + Zero
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + for<'a> Add<&'a Self, Output = Self>
+    + Sub<Output = Self>
+    + AddAssign
+    + SubAssign
+    + Mul<Self::Scalar, Output = Self>
+    + for<'a> Mul<&'a Self::Scalar, Output = Self>
+    + Sum
+    + CanonicalSerialize
+    + CanonicalDeserialize
+{
+    type Scalar: JoltField;
+    /// The compact representation of a point (e.g. affine coordinates), used for MSM bases.
+    type Affine: Copy + Eq + Send + Sync + Debug + CanonicalSerialize + CanonicalDeserialize;
+
+    fn generator() -> Self;
+    fn random<R: rand_core::RngCore>(rng: &mut R) -> Self;
+    fn double(&self) -> Self;
+    fn to_affine(&self) -> Self::Affine;
+    fn from_affine(point: &Self::Affine) -> Self;
+    /// Converts `points` to their compact representation, sharing work between them where the
+    /// backend can (e.g. a single batched inversion).
+    fn normalize_batch(points: &[Self]) -> Vec<Self::Affine>;
+    /// `sum_i scalars[i] * bases[i]`. Returns the length of the shorter input if the lengths
+    /// differ.
+    fn msm(bases: &[Self::Affine], scalars: &[Self::Scalar]) -> Result<Self, usize>;
+    /// Appends the encoding of the point that a transcript absorbs: its affine coordinates x
+    /// then y, each big-endian, or zeros for the identity. This must match the Solidity
+    /// verifier, so backends can't choose their own.
+    fn transcript_bytes(&self, buf: &mut Vec<u8>);
+}
+
+pub mod ark;
@@ -17,11 +17,17 @@ impl BiniusConstructable for BinaryField128bPolyval {
     }
 }
 
-impl BiniusSpecific for BinaryField128b {}
-impl BiniusSpecific for BinaryField128bPolyval {}
+impl BiniusSpecific for BinaryField128b {
+    const NAME: &'static str = "BinaryField128b";
+}
+impl BiniusSpecific for BinaryField128bPolyval {
+    const NAME: &'static str = "BinaryField128bPolyval";
+}
 
 /// Trait for BiniusField functionality specific to each impl.
-pub trait BiniusSpecific: binius_field::TowerField + BiniusConstructable + bytemuck::Pod {}
+pub trait BiniusSpecific: binius_field::TowerField + BiniusConstructable + bytemuck::Pod {
+    const NAME: &'static str;
+}
 
 pub trait BiniusConstructable {
     fn new(n: u64) -> Self;
@@ -105,6 +111,7 @@ impl<'b, F: BiniusSpecific> Div<&'b BiniusField<F>> for BiniusField<F> {
 /// Wrapper for all generic BiniusField functionality.
 impl<F: BiniusSpecific> JoltField for BiniusField<F> {
     const NUM_BYTES: usize = 16;
+    const NAME: &'static str = F::NAME;
 
     fn random<R: rand_core::RngCore>(rng: &mut R) -> Self {
         Self(F::random(rng))
@@ -38,6 +38,9 @@ pub trait JoltField:
     + CanonicalDeserialize
 {
     const NUM_BYTES: usize;
+    /// Identifies the field in file headers. Unlike `std::any::type_name`, it doesn't change
+    /// between compiler versions.
+    const NAME: &'static str;
     fn random<R: rand_core::RngCore>(rng: &mut R) -> Self;
 
     fn from_u64(n: u64) -> Option<Self>;
@@ -91,4 +94,5 @@ where
 }
 
 pub mod ark;
+#[cfg(feature = "binius")]
 pub mod binius;
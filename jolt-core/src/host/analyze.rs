@@ -1,15 +1,44 @@
-use std::{collections::HashMap, fs::File, io, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
+use ark_bn254::Fr;
 use serde::{Deserialize, Serialize};
-use tracer::{ELFInstruction, JoltDevice, RVTraceRow, RV32IM};
+use tracer::{
+    ELFInstruction, HeapStats, JoltDevice, Profile, Profiler, RVTraceRow, SampledTrace, Sampling,
+    RV32IM,
+};
 
+use super::{
+    trace_export::{write_trace, TraceFormat},
+    virtual_trace,
+};
 use crate::{
     field::JoltField,
-    jolt::vm::{rv32i_vm::RV32I, JoltTraceStep},
+    jolt::{
+        instruction::{
+            div::DIVInstruction, divu::DIVUInstruction, max::MAXInstruction, maxu::MAXUInstruction,
+            min::MINInstruction, minu::MINUInstruction, mulh::MULHInstruction,
+            mulhsu::MULHSUInstruction, orn::ORNInstruction, rem::REMInstruction,
+            remu::REMUInstruction, rol::ROLInstruction, ror::RORInstruction, rori::RORIInstruction,
+            sext_b::SEXTBInstruction, sext_h::SEXTHInstruction, zext_h::ZEXTHInstruction,
+            JoltInstruction, JoltInstructionSet, VirtualInstructionSequence,
+        },
+        vm::{
+            rv32i_vm::{C, M, RV32I},
+            JoltTraceStep,
+        },
+    },
 };
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ProgramSummary {
+    /// Path of the guest's ELF, which [`Self::profile`] reads the symbols and debug info from.
+    pub elf: PathBuf,
     pub raw_trace: Vec<RVTraceRow>,
 
     pub bytecode: Vec<ELFInstruction>,
@@ -17,6 +46,11 @@ pub struct ProgramSummary {
 
     pub io_device: JoltDevice,
     pub processed_trace: Vec<JoltTraceStep<RV32I>>,
+    /// Written by the guest with `jolt::println!`.
+    pub guest_log: String,
+    /// Heap usage reported by the guest's allocator, unless the guest uses its own (e.g. with
+    /// `std`).
+    pub heap: Option<HeapStats>,
 }
 
 impl ProgramSummary {
@@ -42,6 +76,35 @@ impl ProgramSummary {
         counts
     }
 
+    /// Attributes the cycles of the (virtual-sequence-expanded) trace to the guest's
+    /// functions, source lines, and call stacks. Use [`Profile::folded`] to produce
+    /// flamegraph input. Fails if the ELF can't be read or parsed.
+    pub fn profile(&self) -> Result<Profile, Box<dyn std::error::Error>> {
+        let mut profiler = Profiler::new(&fs::read(&self.elf)?)?;
+        for row in self.raw_trace.iter() {
+            profiler.record(&row.instruction, jolt_cycles(row.instruction.opcode));
+        }
+        Ok(profiler.finish())
+    }
+
+    /// Writes the trace to `path` in `format`, one record per Jolt cycle, for external analysis
+    /// tools. Returns the number of records written. See [`trace_export`](super::trace_export)
+    /// for the formats.
+    pub fn export_trace(&self, format: TraceFormat, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.export_trace_range(format, path, 0..u64::MAX)
+    }
+
+    /// Like [`Self::export_trace`], but only writes the cycles whose PC is in `pcs`, e.g. the
+    /// address range of a function. Cycles keep their index in the full trace.
+    pub fn export_trace_range(
+        &self,
+        format: TraceFormat,
+        path: impl AsRef<Path>,
+        pcs: Range<u64>,
+    ) -> io::Result<usize> {
+        write_trace(&self.raw_trace, format, pcs, File::create(path)?)
+    }
+
     pub fn write_to_file(self, path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         let mut file = File::create(path)?;
         let data = bincode::serialize(&self)?;
@@ -49,3 +112,133 @@ impl ProgramSummary {
         Ok(())
     }
 }
+
+/// A program's cycle, instruction and lookup counts, extrapolated from a sampled trace. Produced
+/// by [`Program::trace_sampled`](super::Program::trace_sampled).
+#[derive(Clone, Debug)]
+pub struct SampledSummary {
+    pub sampling: Sampling,
+    /// Number of RISC-V instructions the program executed. Exact.
+    pub total_instructions: u64,
+    /// Number of RISC-V instructions that were recorded.
+    pub sampled_instructions: usize,
+    /// Estimated cycles by function, source line and call stack; `total_cycles` estimates the
+    /// length of the Jolt trace. Call stacks are less accurate than with
+    /// [`ProgramSummary::profile`], since calls and returns between samples are missed.
+    pub profile: Profile,
+    /// Estimated executions of each RISC-V instruction, by descending count.
+    pub instructions: Vec<(RV32IM, usize)>,
+    /// Estimated lookups into each instruction's lookup table, by descending count.
+    pub lookups: Vec<(String, usize)>,
+    /// Estimated lookups into each subtable, by descending count.
+    pub subtable_lookups: Vec<(String, usize)>,
+    pub io_device: JoltDevice,
+    /// Written by the guest with `jolt::println!`.
+    pub guest_log: String,
+    /// Heap usage reported by the guest's allocator. Exact, like `total_instructions`.
+    pub heap: Option<HeapStats>,
+}
+
+impl SampledSummary {
+    pub(crate) fn new(
+        elf_contents: &[u8],
+        trace: SampledTrace,
+        io_device: JoltDevice,
+        guest_log: String,
+        heap: Option<HeapStats>,
+    ) -> Self {
+        let sampled_instructions = trace.rows.len();
+        let scale = trace.total_instructions as f64 / sampled_instructions.max(1) as f64;
+        let estimate = |count: usize| (count as f64 * scale).round() as usize;
+
+        let mut profiler =
+            Profiler::new(elf_contents).expect("the ELF was already loaded by the tracer");
+        let mut instruction_counts = HashMap::<RV32IM, usize>::new();
+        let mut lookup_counts = HashMap::<usize, (RV32I, usize)>::new();
+        for row in trace.rows {
+            profiler.record(&row.instruction, jolt_cycles(row.instruction.opcode));
+            *instruction_counts
+                .entry(row.instruction.opcode)
+                .or_default() += 1;
+            for row in virtual_trace(row) {
+                if let Ok(instruction) = RV32I::try_from(&row) {
+                    lookup_counts
+                        .entry(RV32I::enum_index(&instruction))
+                        .or_insert((instruction, 0))
+                        .1 += 1;
+                }
+            }
+        }
+
+        // Which subtables an instruction looks up depends only on the instruction, not on its
+        // operands.
+        let mut subtable_counts = HashMap::<String, usize>::new();
+        for (instruction, count) in lookup_counts.values() {
+            for (subtable, indices) in instruction.subtables::<Fr>(C, M) {
+                *subtable_counts.entry(subtable.name()).or_default() += count * indices.len();
+            }
+        }
+
+        let mut profile = profiler.finish();
+        profile.total_cycles = estimate(profile.total_cycles);
+        for (_, cycles) in profile.functions.iter_mut().chain(profile.lines.iter_mut()) {
+            *cycles = estimate(*cycles);
+        }
+        for (_, cycles) in profile.stacks.iter_mut() {
+            *cycles = estimate(*cycles);
+        }
+
+        let lookups = lookup_counts.into_values().map(|(instruction, count)| {
+            let name = format!("{instruction:?}");
+            (name.split('(').next().unwrap().to_string(), count)
+        });
+        Self {
+            sampling: trace.sampling,
+            total_instructions: trace.total_instructions,
+            sampled_instructions,
+            profile,
+            instructions: by_descending_count(instruction_counts, estimate),
+            lookups: by_descending_count(lookups, estimate),
+            subtable_lookups: by_descending_count(subtable_counts, estimate),
+            io_device,
+            guest_log,
+            heap,
+        }
+    }
+}
+
+fn by_descending_count<K>(
+    counts: impl IntoIterator<Item = (K, usize)>,
+    estimate: impl Fn(usize) -> usize,
+) -> Vec<(K, usize)> {
+    let mut counts: Vec<_> = counts
+        .into_iter()
+        .map(|(key, count)| (key, estimate(count)))
+        .collect();
+    counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    counts
+}
+
+/// The number of Jolt trace steps a single RISC-V instruction expands to.
+fn jolt_cycles(opcode: RV32IM) -> usize {
+    match opcode {
+        RV32IM::MULH => MULHInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MULHSU => MULHSUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::DIV => DIVInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::DIVU => DIVUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::REM => REMInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::REMU => REMUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::ORN => ORNInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MIN => MINInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MAX => MAXInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MINU => MINUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::MAXU => MAXUInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::SEXT_B => SEXTBInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::SEXT_H => SEXTHInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::ZEXT_H => ZEXTHInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::ROL => ROLInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::ROR => RORInstruction::<32>::SEQUENCE_LENGTH,
+        RV32IM::RORI => RORIInstruction::<32>::SEQUENCE_LENGTH,
+        _ => 1,
+    }
+}
@@ -0,0 +1,328 @@
+//! Shared cache for preprocessing, SRS and subtable artifacts.
+//!
+//! Artifacts are expensive to generate but identical for every prover of the same program, so a
+//! prover fleet can generate them once and share them through an [`ArtifactCache`]. The cache
+//! stores byte blobs under `/`-separated keys in an [`ArtifactStore`]: a local directory
+//! ([`LocalDirStore`], by default `~/.jolt/artifacts`) or, with the `s3` feature, any
+//! S3-compatible object store ([`S3Store`](super::s3::S3Store)), which includes Google Cloud
+//! Storage through its XML API and HMAC keys.
+//!
+//! Every artifact is stored with the SHA-256 digest of its contents, which is checked on every
+//! fetch, so a truncated upload or a corrupted file is reported instead of deserialized. Stores
+//! write atomically: a concurrent reader sees either the previous artifact or the complete new
+//! one.
+
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use eyre::{bail, eyre, Result, WrapErr};
+use sha2::{Digest, Sha256};
+
+use super::toolchain::jolt_dir;
+use crate::jolt::vm::preprocessing::ProgramId;
+use crate::jolt::vm::scheduler::ProofProfile;
+
+/// Prefix of every stored artifact, followed by the SHA-256 digest of the contents and then the
+/// contents themselves.
+const ARTIFACT_MAGIC: [u8; 8] = *b"JOLTART1";
+const HEADER_LEN: usize = ARTIFACT_MAGIC.len() + 32;
+
+/// A key-value store of byte blobs that backs an [`ArtifactCache`].
+pub trait ArtifactStore: Send + Sync {
+    /// Returns the blob stored under `key`, or `None` if there is none.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores `bytes` under `key`, replacing any existing blob. The write must be atomic: `get`
+    /// never returns a partially written blob.
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()>;
+}
+
+/// Digest-verified artifact storage on top of an [`ArtifactStore`].
+pub struct ArtifactCache {
+    store: Box<dyn ArtifactStore>,
+}
+
+impl ArtifactCache {
+    pub fn new(store: impl ArtifactStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+        }
+    }
+
+    /// A cache in `~/.jolt/artifacts`.
+    pub fn local() -> Self {
+        Self::new(LocalDirStore::new(jolt_dir().join("artifacts")))
+    }
+
+    /// Returns the artifact stored under `key`, or `None` if there is none. Fails if the stored
+    /// blob is not a complete artifact or its contents don't match its digest.
+    pub fn fetch(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        validate_key(key)?;
+        match self.store.get(key)? {
+            Some(blob) => open_envelope(key, blob).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Like [`Self::fetch`], but also fails if the artifact's digest is not `expected`, e.g. a
+    /// digest pinned in a deployment's configuration.
+    pub fn fetch_pinned(&self, key: &str, expected: &[u8; 32]) -> Result<Option<Vec<u8>>> {
+        let Some(bytes) = self.fetch(key)? else {
+            return Ok(None);
+        };
+        let actual: [u8; 32] = Sha256::digest(&bytes).into();
+        if &actual != expected {
+            bail!(
+                "artifact {key} has digest {}, expected {}",
+                hex::encode(actual),
+                hex::encode(expected)
+            );
+        }
+        Ok(Some(bytes))
+    }
+
+    /// Stores `bytes` under `key` and returns their SHA-256 digest.
+    pub fn store(&self, key: &str, bytes: &[u8]) -> Result<[u8; 32]> {
+        validate_key(key)?;
+        let digest: [u8; 32] = Sha256::digest(bytes).into();
+        let mut blob = Vec::with_capacity(HEADER_LEN + bytes.len());
+        blob.extend_from_slice(&ARTIFACT_MAGIC);
+        blob.extend_from_slice(&digest);
+        blob.extend_from_slice(bytes);
+        self.store
+            .put(key, &blob)
+            .wrap_err_with(|| format!("failed to store artifact {key}"))?;
+        Ok(digest)
+    }
+
+    /// Returns the artifact stored under `key`, generating and storing it with `generate` if
+    /// there is none.
+    pub fn get_or_insert_with(
+        &self,
+        key: &str,
+        generate: impl FnOnce() -> Result<Vec<u8>>,
+    ) -> Result<Vec<u8>> {
+        if let Some(bytes) = self.fetch(key)? {
+            return Ok(bytes);
+        }
+        let bytes = generate()?;
+        self.store(key, &bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Key of the preprocessing for the guest `program_id`. Loading the preprocessing checks that it
+/// was generated with the verifier's configuration, so different configurations of the same
+/// guest should use separate caches.
+pub fn preprocessing_key(program_id: &ProgramId) -> String {
+    format!("preprocessing/{program_id}")
+}
+
+/// Key of an SRS for polynomials of up to `2^num_vars` coefficients. `pcs` names the commitment
+/// scheme and curve, e.g. `hyperkzg-bn254`.
+pub fn srs_key(pcs: &str, num_vars: usize) -> String {
+    format!("srs/{pcs}/{num_vars}")
+}
+
+/// Key of the proof made with `profile` on `inputs`, the guest's serialized inputs (as in
+/// `JoltDevice::inputs`), and the blobs with `blob_hashes` (as in `Blobs::hashes`), for the
+/// preprocessing with digest `preprocessing_digest` (see
+/// [`JoltPreprocessing::digest`](crate::jolt::vm::JoltPreprocessing::digest)). The digest covers
+/// the program, the namespace, `max_cycles` and the memory layout. Proving is deterministic (see
+/// [`Jolt::prove`](crate::jolt::vm::Jolt::prove)), so a cached proof is bit-for-bit the proof a
+/// prover would make.
+pub fn proof_key(
+    preprocessing_digest: &[u8; 32],
+    profile: ProofProfile,
+    inputs: &[u8],
+    blob_hashes: &[[u8; 32]],
+) -> String {
+    let profile = match profile {
+        ProofProfile::FastProver => "fast-prover",
+        ProofProfile::SmallProof => "small-proof",
+    };
+    let mut hasher = Sha256::new();
+    hasher.update((inputs.len() as u64).to_le_bytes());
+    hasher.update(inputs);
+    for hash in blob_hashes {
+        hasher.update(hash);
+    }
+    format!(
+        "proofs/{}/{profile}/{}",
+        hex::encode(preprocessing_digest),
+        hex::encode(hasher.finalize())
+    )
+}
+
+/// Keys are non-empty, `/`-separated paths of ASCII letters, digits, `.`, `_` and `-`, with no
+/// empty, `.` or `..` segments, so they are safe as both file paths and object names.
+fn validate_key(key: &str) -> Result<()> {
+    let valid_segment = |segment: &str| {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && segment
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+    };
+    if !key.split('/').all(valid_segment) {
+        bail!("invalid artifact key {key:?}");
+    }
+    Ok(())
+}
+
+fn open_envelope(key: &str, mut blob: Vec<u8>) -> Result<Vec<u8>> {
+    if blob.len() < HEADER_LEN || blob[..ARTIFACT_MAGIC.len()] != ARTIFACT_MAGIC {
+        bail!("artifact {key} is truncated or not a Jolt artifact");
+    }
+    let expected = &blob[ARTIFACT_MAGIC.len()..HEADER_LEN];
+    let actual = Sha256::digest(&blob[HEADER_LEN..]);
+    if actual[..] != *expected {
+        bail!(
+            "artifact {key} is corrupted: its contents have digest {}, expected {}",
+            hex::encode(actual),
+            hex::encode(expected)
+        );
+    }
+    blob.drain(..HEADER_LEN);
+    Ok(blob)
+}
+
+/// An [`ArtifactStore`] in a local directory, with one file per key.
+pub struct LocalDirStore {
+    root: PathBuf,
+}
+
+impl LocalDirStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+impl ArtifactStore for LocalDirStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        let dir = path
+            .parent()
+            .ok_or_else(|| eyre!("artifact key {key:?} has no parent directory"))?;
+        fs::create_dir_all(dir)?;
+
+        // Write to a temporary file in the same directory, so the rename is atomic.
+        let temp = temp_path(&path);
+        let result = write_synced(&temp, bytes).and_then(|_| fs::rename(&temp, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        Ok(result?)
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.file_name().unwrap().to_os_string();
+    name.push(format!(
+        ".tmp-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache() -> (PathBuf, ArtifactCache) {
+        let root = std::env::temp_dir().join(format!(
+            "jolt-artifacts-test-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        (root.clone(), ArtifactCache::new(LocalDirStore::new(root)))
+    }
+
+    #[test]
+    fn local_round_trip() {
+        let (root, cache) = temp_cache();
+        let key = srs_key("hyperkzg-bn254", 20);
+
+        assert_eq!(cache.fetch(&key).unwrap(), None);
+        let digest = cache.store(&key, b"srs bytes").unwrap();
+        assert_eq!(cache.fetch(&key).unwrap().unwrap(), b"srs bytes");
+        assert!(cache.fetch_pinned(&key, &digest).unwrap().is_some());
+        assert!(cache.fetch_pinned(&key, &[0; 32]).is_err());
+
+        let bytes = cache
+            .get_or_insert_with(&key, || panic!("artifact should be cached"))
+            .unwrap();
+        assert_eq!(bytes, b"srs bytes");
+
+        // Only the artifact itself is left behind, no temporary files.
+        let entries: Vec<_> = fs::read_dir(root.join("srs/hyperkzg-bn254"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, ["20"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn corrupted_artifact() {
+        let (root, cache) = temp_cache();
+        cache.store("subtables/eq", &[1, 2, 3, 4]).unwrap();
+
+        let path = root.join("subtables/eq");
+        let mut blob = fs::read(&path).unwrap();
+        *blob.last_mut().unwrap() ^= 1;
+        fs::write(&path, &blob).unwrap();
+        assert!(cache.fetch("subtables/eq").is_err());
+
+        fs::write(&path, &blob[..HEADER_LEN - 1]).unwrap();
+        assert!(cache.fetch("subtables/eq").is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn invalid_keys() {
+        for key in [
+            "", "/srs", "srs/", "srs//20", "../srs", "srs/./20", "srs 20",
+        ] {
+            assert!(validate_key(key).is_err(), "{key:?}");
+        }
+        validate_key("preprocessing/ab01/cd23").unwrap();
+
+        let digest = [0xab; 32];
+        let key = proof_key(&digest, ProofProfile::FastProver, &[1, 2, 3], &[]);
+        validate_key(&key).unwrap();
+        for other in [
+            proof_key(&[0xcd; 32], ProofProfile::FastProver, &[1, 2, 3], &[]),
+            proof_key(&digest, ProofProfile::SmallProof, &[1, 2, 3], &[]),
+            proof_key(&digest, ProofProfile::FastProver, &[1, 2, 4], &[]),
+            proof_key(&digest, ProofProfile::FastProver, &[1, 2, 3], &[[0; 32]]),
+        ] {
+            assert_ne!(key, other);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Diagnoses proofs whose I/O does not match the program.
+//!
+//! The most common reason for a proof to be rejected is that the inputs, outputs, or panic bit
+//! it claims are not the ones the guest produces, e.g. because the outputs were edited after
+//! proving or the verifier was built from a different guest. [`Program::diff_final_state`]
+//! re-runs the guest on the claimed inputs and reports where its final I/O disagrees with the
+//! claimed one, along with the guest source location that last wrote each disagreeing range.
+
+use std::{collections::HashMap, fmt, fs, ops::Range};
+
+use common::rv_trace::{GuestFault, GuestPanic, JoltDevice, MemoryState, RV32IM};
+use tracer::SourceLines;
+
+use super::Program;
+
+/// A run of consecutive output bytes that differ between the claimed and the actual outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputMismatch {
+    /// Guest addresses of the differing bytes.
+    pub addresses: Range<u64>,
+    pub claimed: Vec<u8>,
+    pub actual: Vec<u8>,
+    /// Address of the store instruction that last wrote to the range, if the guest wrote to it.
+    pub last_writer: Option<u64>,
+    /// Source location of `last_writer`, if the guest was built with debug info.
+    pub last_writer_location: Option<String>,
+}
+
+/// The differences between the I/O claimed by a proof and the I/O the guest actually produces
+/// on the claimed inputs. See [`Program::diff_final_state`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FinalStateDiff {
+    /// (claimed, actual) `(max_input_size, max_output_size)`, if they differ.
+    pub io_sizes: Option<((u64, u64), (u64, u64))>,
+    pub outputs: Vec<OutputMismatch>,
+    /// (claimed, actual) panic bit, if they differ.
+    pub panic: Option<(bool, bool)>,
+    /// The panic reported by the guest while re-running it.
+    pub guest_panic: Option<GuestPanic>,
+    /// The fault that stopped the guest while re-running it.
+    pub guest_fault: Option<GuestFault>,
+}
+
+impl FinalStateDiff {
+    /// Whether the claimed I/O matches the guest's, in which case the proof was rejected for
+    /// some other reason.
+    pub fn is_empty(&self) -> bool {
+        self.io_sizes.is_none() && self.outputs.is_empty() && self.panic.is_none()
+    }
+}
+
+impl fmt::Display for FinalStateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Claimed I/O matches the guest's final state");
+        }
+        writeln!(f, "Claimed I/O does not match the guest's final state:")?;
+        if let Some(((claimed_input, claimed_output), (input, output))) = self.io_sizes {
+            writeln!(
+                f,
+                "  max_input_size/max_output_size: claimed {claimed_input}/{claimed_output}, guest has {input}/{output}"
+            )?;
+        }
+        for mismatch in self.outputs.iter() {
+            writeln!(
+                f,
+                "  output bytes {:#X}..{:#X}: claimed {:02x?}, guest wrote {:02x?}",
+                mismatch.addresses.start, mismatch.addresses.end, mismatch.claimed, mismatch.actual
+            )?;
+            match (mismatch.last_writer, &mismatch.last_writer_location) {
+                (Some(address), Some(location)) => {
+                    writeln!(f, "    last written at {location} ({address:#X})")?
+                }
+                (Some(address), None) => writeln!(f, "    last written by {address:#X}")?,
+                (None, _) => writeln!(f, "    never written by the guest")?,
+            }
+        }
+        if let Some((claimed, actual)) = self.panic {
+            writeln!(f, "  panic bit: claimed {claimed}, guest set {actual}")?;
+        }
+        if let Some(guest_panic) = &self.guest_panic {
+            writeln!(f, "  {guest_panic}")?;
+        }
+        if let Some(guest_fault) = &self.guest_fault {
+            writeln!(f, "  {guest_fault}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Program {
+    /// Re-runs the guest on the inputs in `claimed` and compares its final I/O against
+    /// `claimed`, e.g. the `program_io` of a proof that failed to verify. This traces the whole
+    /// program, so it is meant for debugging, not for use alongside every verification.
+    pub fn diff_final_state(mut self, claimed: &JoltDevice) -> FinalStateDiff {
+        self.build();
+        let elf = self.elf.clone().unwrap();
+        // A deserialized proof's blobs are only hashes, unless their data was attached
+        let blobs = if claimed.blobs.is_attached() {
+            &claimed.blobs
+        } else {
+            &self.blobs
+        };
+        let (raw_trace, actual, guest_panic, guest_fault, _, _) =
+            tracer::trace(&elf, &claimed.inputs, blobs, &self.memory_config);
+
+        let mut diff = FinalStateDiff {
+            guest_panic,
+            guest_fault,
+            ..Default::default()
+        };
+        let (claimed_layout, layout) = (&claimed.memory_layout, &actual.memory_layout);
+        let claimed_sizes = (
+            claimed_layout.max_input_size,
+            claimed_layout.max_output_size,
+        );
+        let sizes = (layout.max_input_size, layout.max_output_size);
+        if claimed_sizes != sizes {
+            diff.io_sizes = Some((claimed_sizes, sizes));
+        }
+        if claimed.panic != actual.panic {
+            diff.panic = Some((claimed.panic, actual.panic));
+        }
+
+        // The last store to each byte of the output region
+        let mut last_writers = HashMap::new();
+        for row in raw_trace.iter() {
+            if let Some(MemoryState::Write { address, .. }) = row.memory_state {
+                let width = match row.instruction.opcode {
+                    RV32IM::SB => 1,
+                    RV32IM::SH => 2,
+                    _ => 4,
+                };
+                for byte_address in address..address + width {
+                    if (layout.output_start..layout.output_end).contains(&byte_address) {
+                        last_writers.insert(byte_address, row.instruction.address);
+                    }
+                }
+            }
+        }
+
+        let source_lines = fs::read(&elf)
+            .ok()
+            .and_then(|elf_contents| SourceLines::new(&elf_contents));
+        let output_len = claimed.outputs.len().max(actual.outputs.len());
+        let byte = |outputs: &[u8], i: usize| outputs.get(i).copied().unwrap_or(0);
+        let mut i = 0;
+        while i < output_len {
+            if byte(&claimed.outputs, i) == byte(&actual.outputs, i) {
+                i += 1;
+                continue;
+            }
+            // Extend the mismatch over consecutive differing bytes written by the same store
+            let address = |i: usize| layout.output_start + i as u64;
+            let last_writer = last_writers.get(&address(i)).copied();
+            let start = i;
+            while i < output_len
+                && byte(&claimed.outputs, i) != byte(&actual.outputs, i)
+                && last_writers.get(&address(i)).copied() == last_writer
+            {
+                i += 1;
+            }
+            diff.outputs.push(OutputMismatch {
+                addresses: address(start)..address(i),
+                claimed: (start..i).map(|i| byte(&claimed.outputs, i)).collect(),
+                actual: (start..i).map(|i| byte(&actual.outputs, i)).collect(),
+                last_writer,
+                last_writer_location: last_writer.and_then(|address| {
+                    source_lines
+                        .as_ref()
+                        .and_then(|source_lines| source_lines.find(address))
+                }),
+            });
+        }
+
+        diff
+    }
+}
@@ -0,0 +1,308 @@
+//! Hermetic guest builds.
+//!
+//! By default, [`Program::build`](super::Program::build) compiles the guest with whichever
+//! `cargo` and `rustc` are on the `PATH`, under the user's cargo configuration and environment.
+//! The resulting ELF, and hence the preprocessing and proofs derived from it, can then differ
+//! from one machine to the next. In hermetic mode the guest is instead built:
+//!
+//! - by a pinned toolchain (the channel in Jolt's `rust-toolchain.toml`), downloaded from
+//!   `static.rust-lang.org` into the Jolt artifact cache and checked against its published
+//!   SHA-256 digests;
+//! - with an isolated `CARGO_HOME` and a cleared environment, so user-wide cargo configuration,
+//!   `RUSTFLAGS`, `RUSTC_WRAPPER`, etc. are ignored;
+//! - with `--locked`, so dependencies are exactly those in the guest's `Cargo.lock`;
+//! - with host-specific paths (the workspace, `CARGO_HOME`, and the toolchain itself) remapped
+//!   out of panic messages and debug info.
+//!
+//! Together these make the guest ELF bit-identical across hosts. Build scripts and proc macros
+//! still run natively and are linked by the host's C linker, which does not affect the guest.
+//! Configuration files inside the guest's own workspace (e.g. `.cargo/config.toml`) are honored,
+//! since they are part of its source. Cargo also reads configuration from every parent directory
+//! of the workspace, which cannot be disabled; hermetic builds warn about any such files.
+//!
+//! Hermetic mode is enabled with [`Program::set_hermetic`](super::Program::set_hermetic), or for
+//! every build by setting the `JOLT_HERMETIC_BUILD` environment variable.
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use eyre::{bail, eyre, Result};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use tokio::runtime::Runtime;
+
+use super::toolchain::{download_file, jolt_dir, retry_times, DELAY_BASE_MS, DOWNLOAD_RETRIES};
+
+const RUST_TOOLCHAIN: &str = include_str!("../../../rust-toolchain.toml");
+const DIST_SERVER: &str = "https://static.rust-lang.org";
+const NO_STD_TARGET: &str = "riscv32im-unknown-none-elf";
+const INSTALLED_MARKER: &str = ".installed";
+
+/// A pinned Rust toolchain in the Jolt artifact cache.
+#[derive(Clone, Debug)]
+pub struct HermeticToolchain {
+    channel: String,
+    root: PathBuf,
+}
+
+impl HermeticToolchain {
+    /// Downloads and installs the pinned toolchain, if it is not already installed.
+    pub fn install() -> Result<Self> {
+        let channel = pinned_channel()?;
+        let toolchain = Self {
+            root: jolt_dir().join("hermetic").join(&channel),
+            channel,
+        };
+        if toolchain.root.join(INSTALLED_MARKER).exists() {
+            return Ok(toolchain);
+        }
+
+        println!("Installing hermetic toolchain {}", toolchain.channel);
+        let download_dir = toolchain.root.join("downloads");
+        fs::create_dir_all(&download_dir)?;
+        let client = Client::builder().user_agent("Mozilla/5.0").build()?;
+        let rt = Runtime::new()?;
+
+        let host = target_lexicon::HOST.to_string();
+        for (component, target) in [
+            ("rustc", host.as_str()),
+            ("cargo", host.as_str()),
+            // Build scripts and proc macros are compiled for the host
+            ("rust-std", host.as_str()),
+            ("rust-std", NO_STD_TARGET),
+        ] {
+            let (url, name) = toolchain.component_url(component, target);
+            let archive = download_dir.join(format!("{name}.tar.gz"));
+            rt.block_on(retry_times(DOWNLOAD_RETRIES, DELAY_BASE_MS, || {
+                download_verified(&client, &url, &archive)
+            }))?;
+            install_component(&archive, &download_dir.join(&name), &toolchain.root)?;
+        }
+
+        fs::remove_dir_all(&download_dir)?;
+        File::create(toolchain.root.join(INSTALLED_MARKER))?;
+        Ok(toolchain)
+    }
+
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    pub fn cargo(&self) -> PathBuf {
+        self.root
+            .join("bin")
+            .join(format!("cargo{}", env::consts::EXE_SUFFIX))
+    }
+
+    pub fn rustc(&self) -> PathBuf {
+        self.root
+            .join("bin")
+            .join(format!("rustc{}", env::consts::EXE_SUFFIX))
+    }
+
+    /// The isolated `CARGO_HOME` used by hermetic builds. Downloaded crates are cached here.
+    pub fn cargo_home(&self) -> PathBuf {
+        self.root.join("cargo-home")
+    }
+
+    /// A `cargo` command using this toolchain, with `rustc` as the compiler, an isolated
+    /// `CARGO_HOME`, and an otherwise empty environment. Only `PATH` is retained (after the
+    /// toolchain's own `bin` directory), so that build scripts can find the host linker.
+    pub fn cargo_command(&self, rustc: &Path) -> Command {
+        let mut path = vec![self.root.join("bin")];
+        if let Some(system_path) = env::var_os("PATH") {
+            path.extend(env::split_paths(&system_path));
+        }
+
+        let mut command = Command::new(self.cargo());
+        command
+            .env_clear()
+            .env(
+                "PATH",
+                env::join_paths(path).unwrap_or_else(|_| OsString::new()),
+            )
+            .env("CARGO_HOME", self.cargo_home())
+            .env("RUSTC", rustc)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TERM_COLOR", "never");
+        command
+    }
+
+    /// `rustc` flags that replace host-specific path prefixes in the guest with fixed ones.
+    pub fn remap_path_flags(&self, workspace: &Path) -> Vec<String> {
+        // When several prefixes match a path, rustc applies the last one, so more specific
+        // prefixes (e.g. `CARGO_HOME`, which is inside the toolchain root) come later.
+        [
+            (jolt_dir(), "/jolt/cache"),
+            (self.root.clone(), "/jolt/toolchain"),
+            (self.cargo_home(), "/jolt/cargo"),
+            (workspace.to_path_buf(), "/jolt/guest"),
+        ]
+        .into_iter()
+        .flat_map(|(from, to)| {
+            [
+                "--remap-path-prefix".to_string(),
+                format!("{}={to}", from.display()),
+            ]
+        })
+        .collect()
+    }
+
+    /// Cargo configuration files outside of `workspace` that cargo would still read, since it
+    /// searches every parent directory of the workspace.
+    pub fn ambient_cargo_configs(workspace: &Path) -> Vec<PathBuf> {
+        workspace
+            .ancestors()
+            .skip(1)
+            .flat_map(|dir| [dir.join(".cargo/config"), dir.join(".cargo/config.toml")])
+            .filter(|config| config.is_file())
+            .collect()
+    }
+
+    fn component_url(&self, component: &str, target: &str) -> (String, String) {
+        // Nightlies are published by date, under the version "nightly"
+        let (dist_dir, version) = match self.channel.strip_prefix("nightly-") {
+            Some(date) => (format!("dist/{date}"), "nightly"),
+            None => ("dist".to_string(), self.channel.as_str()),
+        };
+        let name = format!("{component}-{version}-{target}");
+        (format!("{DIST_SERVER}/{dist_dir}/{name}.tar.gz"), name)
+    }
+}
+
+/// The toolchain channel pinned in Jolt's `rust-toolchain.toml`.
+fn pinned_channel() -> Result<String> {
+    parse_channel(RUST_TOOLCHAIN).ok_or_else(|| eyre!("rust-toolchain.toml does not pin a channel"))
+}
+
+fn parse_channel(rust_toolchain: &str) -> Option<String> {
+    rust_toolchain.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "channel").then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Downloads `url` to `path`, and checks it against the SHA-256 digest published at
+/// `{url}.sha256`.
+async fn download_verified(client: &Client, url: &str, path: &Path) -> Result<()> {
+    download_file(client, url, path).await?;
+
+    let published = client
+        .get(format!("{url}.sha256"))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let expected = published
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| eyre!("empty digest for {url}"))?;
+
+    let mut hasher = Sha256::new();
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    let actual = hex::encode(hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected) {
+        fs::remove_file(path)?;
+        bail!("SHA-256 mismatch for {url}: expected {expected}, got {actual}");
+    }
+    Ok(())
+}
+
+/// Unpacks a rustup component archive and installs it under `prefix`.
+fn install_component(archive: &Path, unpacked: &Path, prefix: &Path) -> Result<()> {
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(archive.parent().unwrap())
+        .output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8(output.stderr)?);
+    }
+
+    let output = Command::new("sh")
+        .arg(unpacked.join("install.sh"))
+        .arg(format!("--prefix={}", prefix.display()))
+        .arg("--disable-ldconfig")
+        .output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8(output.stderr)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_channel() {
+        assert_eq!(
+            parse_channel("[toolchain]\nchannel = \"nightly-2024-08-01\"\ntargets = []\n"),
+            Some("nightly-2024-08-01".to_string())
+        );
+        assert_eq!(parse_channel("[toolchain]\n"), None);
+        assert!(super::pinned_channel().is_ok());
+    }
+
+    #[test]
+    fn component_urls() {
+        let toolchain = HermeticToolchain {
+            channel: "nightly-2024-08-01".to_string(),
+            root: PathBuf::new(),
+        };
+        assert_eq!(
+            toolchain.component_url("rust-std", NO_STD_TARGET),
+            (
+                "https://static.rust-lang.org/dist/2024-08-01/rust-std-nightly-riscv32im-unknown-none-elf.tar.gz".to_string(),
+                "rust-std-nightly-riscv32im-unknown-none-elf".to_string()
+            )
+        );
+
+        let toolchain = HermeticToolchain {
+            channel: "1.80.0".to_string(),
+            root: PathBuf::new(),
+        };
+        assert_eq!(
+            toolchain
+                .component_url("cargo", "x86_64-unknown-linux-gnu")
+                .0,
+            "https://static.rust-lang.org/dist/cargo-1.80.0-x86_64-unknown-linux-gnu.tar.gz"
+        );
+    }
+
+    #[test]
+    fn remapped_paths() {
+        let toolchain = HermeticToolchain {
+            channel: "nightly-2024-08-01".to_string(),
+            root: PathBuf::from("/cache/hermetic/nightly-2024-08-01"),
+        };
+        let flags = toolchain.remap_path_flags(Path::new("/home/user/project"));
+        assert_eq!(flags.len(), 8);
+        assert_eq!(flags[2], "--remap-path-prefix");
+        assert_eq!(
+            flags[3],
+            "/cache/hermetic/nightly-2024-08-01=/jolt/toolchain"
+        );
+        assert_eq!(
+            flags[5],
+            "/cache/hermetic/nightly-2024-08-01/cargo-home=/jolt/cargo"
+        );
+        assert_eq!(flags[7], "/home/user/project=/jolt/guest");
+    }
+}
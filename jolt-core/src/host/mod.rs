@@ -12,44 +12,60 @@ use postcard;
 use rayon::prelude::*;
 use serde::Serialize;
 
-use common::{
-    constants::{
-        DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MEMORY_SIZE, DEFAULT_STACK_SIZE,
-    },
-    rv_trace::JoltDevice,
-};
-pub use tracer::ELFInstruction;
+use common::rv_trace::{Blobs, JoltDevice, MemoryConfig};
+pub use tracer::{ELFInstruction, NondeterminismSource, ReplayDivergence, Sampling};
 
 use crate::{
     field::JoltField,
     jolt::{
         instruction::{
-            div::DIVInstruction, divu::DIVUInstruction, mulh::MULHInstruction,
-            mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction,
-            VirtualInstructionSequence,
+            div::DIVInstruction, divu::DIVUInstruction, max::MAXInstruction, maxu::MAXUInstruction,
+            min::MINInstruction, minu::MINUInstruction, mulh::MULHInstruction,
+            mulhsu::MULHSUInstruction, orn::ORNInstruction, rem::REMInstruction,
+            remu::REMUInstruction, rol::ROLInstruction, ror::RORInstruction, rori::RORIInstruction,
+            sext_b::SEXTBInstruction, sext_h::SEXTHInstruction, zext_h::ZEXTHInstruction,
+            JoltInstructionSet, VirtualInstructionSequence,
+        },
+        vm::{
+            bytecode::BytecodeRow, expand_virtual_sequences, preprocessing::ProgramId,
+            rv32i_vm::RV32I, JoltTraceStep,
         },
-        vm::{bytecode::BytecodeRow, rv32i_vm::RV32I, JoltTraceStep},
     },
+    utils::errors::JoltError,
 };
 
-use self::analyze::ProgramSummary;
+use self::analyze::{ProgramSummary, SampledSummary};
 #[cfg(not(target_arch = "wasm32"))]
-use self::toolchain::{install_no_std_toolchain, install_toolchain};
+use self::hermetic::HermeticToolchain;
+#[cfg(not(target_arch = "wasm32"))]
+use self::toolchain::{install_no_std_toolchain, install_toolchain, jolt_toolchain_rustc};
 
 pub mod analyze;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod artifacts;
+pub mod final_state;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod hermetic;
+#[cfg(all(feature = "s3", not(target_arch = "wasm32")))]
+pub mod s3;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod toolchain;
+pub mod trace_export;
+
+/// When set (to anything but `0`), every [`Program`] is built in hermetic mode. See
+/// [`hermetic`].
+pub const HERMETIC_BUILD_ENV: &str = "JOLT_HERMETIC_BUILD";
 
 #[derive(Clone)]
 pub struct Program {
     guest: String,
     func: Option<String>,
     input: Vec<u8>,
-    memory_size: u64,
-    stack_size: u64,
-    max_input_size: u64,
-    max_output_size: u64,
+    blobs: Blobs,
+    memory_config: MemoryConfig,
     std: bool,
+    zbb: bool,
+    hermetic: bool,
     pub elf: Option<PathBuf>,
 }
 
@@ -59,11 +75,11 @@ impl Program {
             guest: guest.to_string(),
             func: None,
             input: Vec::new(),
-            memory_size: DEFAULT_MEMORY_SIZE,
-            stack_size: DEFAULT_STACK_SIZE,
-            max_input_size: DEFAULT_MAX_INPUT_SIZE,
-            max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+            blobs: Blobs::default(),
+            memory_config: MemoryConfig::default(),
             std: false,
+            zbb: false,
+            hermetic: std::env::var_os(HERMETIC_BUILD_ENV).is_some_and(|value| value != "0"),
             elf: None,
         }
     }
@@ -72,6 +88,18 @@ impl Program {
         self.std = std;
     }
 
+    /// Lets the compiler emit instructions of the Zbb (basic bit-manipulation) extension, such
+    /// as `clz`, `cpop` and `ror`, in place of longer shift and mask sequences.
+    pub fn set_zbb(&mut self, zbb: bool) {
+        self.zbb = zbb;
+    }
+
+    /// Builds the guest with a pinned toolchain in an isolated environment, so that the ELF is
+    /// bit-identical on every host. See [`hermetic`].
+    pub fn set_hermetic(&mut self, hermetic: bool) {
+        self.hermetic = hermetic;
+    }
+
     pub fn set_func(&mut self, func: &str) {
         self.func = Some(func.to_string())
     }
@@ -81,20 +109,51 @@ impl Program {
         self.input.append(&mut serialized);
     }
 
+    /// Appends already-serialized input, e.g. arguments serialized with `postcard` elsewhere.
+    pub fn set_input_bytes(&mut self, input: &[u8]) {
+        self.input.extend_from_slice(input);
+    }
+
+    /// Registers `blob` as read-only data for the guest, which it reads with
+    /// `jolt::read_blob(id)`, and returns its id. Unlike the input, only the blob's hash is part
+    /// of the program I/O, so a verifier must be given the blob separately (see [`Blobs`]). The
+    /// blobs must fit in `max_blob_size`.
+    pub fn add_blob(&mut self, blob: Vec<u8>) -> usize {
+        self.blobs.push(blob)
+    }
+
+    pub fn blobs(&self) -> &Blobs {
+        &self.blobs
+    }
+
     pub fn set_memory_size(&mut self, len: u64) {
-        self.memory_size = len;
+        self.memory_config.memory_size = len;
     }
 
     pub fn set_stack_size(&mut self, len: u64) {
-        self.stack_size = len;
+        self.memory_config.stack_size = len;
     }
 
     pub fn set_max_input_size(&mut self, size: u64) {
-        self.max_input_size = size;
+        self.memory_config.max_input_size = size;
     }
 
     pub fn set_max_output_size(&mut self, size: u64) {
-        self.max_output_size = size;
+        self.memory_config.max_output_size = size;
+    }
+
+    pub fn set_max_blob_size(&mut self, size: u64) {
+        self.memory_config.max_blob_size = size;
+    }
+
+    /// Halts the guest after `max_cycles` RISC-V instructions when tracing it. See
+    /// [`JoltDevice::exceeded_max_cycles`].
+    pub fn set_max_cycles(&mut self, max_cycles: u64) {
+        self.memory_config.max_cycles = Some(max_cycles);
+    }
+
+    pub fn memory_config(&self) -> MemoryConfig {
+        self.memory_config
     }
 
     #[tracing::instrument(skip_all, name = "Program::build")]
@@ -103,18 +162,27 @@ impl Program {
             #[cfg(not(target_arch = "wasm32"))]
             install_toolchain().unwrap();
             #[cfg(not(target_arch = "wasm32"))]
-            install_no_std_toolchain().unwrap();
+            if !self.hermetic {
+                install_no_std_toolchain().unwrap();
+            }
 
             self.save_linker();
 
-            let rust_flags = [
-                "-C",
-                &format!("link-arg=-T{}", self.linker_path()),
-                "-C",
-                "passes=lower-atomic",
-                "-C",
-                "panic=abort",
+            let mut rust_flags = vec![
+                "-C".to_string(),
+                format!("link-arg=-T{}", self.linker_path()),
+                "-C".to_string(),
+                "passes=lower-atomic".to_string(),
+                "-C".to_string(),
+                "panic=abort".to_string(),
+                // Code generation must not depend on the host's core count or build history,
+                // so that the same source always yields the same program ID
+                "-C".to_string(),
+                "codegen-units=1".to_string(),
             ];
+            if self.zbb {
+                rust_flags.extend(["-C".to_string(), "target-feature=+zbb".to_string()]);
+            }
 
             let toolchain = if self.std {
                 "riscv32im-jolt-zkvm-elf"
@@ -122,38 +190,63 @@ impl Program {
                 "riscv32im-unknown-none-elf"
             };
 
-            let mut envs = vec![("CARGO_ENCODED_RUSTFLAGS", rust_flags.join("\x1f"))];
+            let target = format!(
+                "/tmp/jolt-guest-target-{}-{}{}{}",
+                self.guest,
+                self.func.as_ref().unwrap_or(&"".to_string()),
+                if self.zbb { "-zbb" } else { "" },
+                if self.hermetic { "-hermetic" } else { "" }
+            );
+
+            let mut args = vec![
+                "build",
+                "--release",
+                "--features",
+                "guest",
+                "-p",
+                &self.guest,
+                "--target-dir",
+                &target,
+                "--target",
+                toolchain,
+            ];
 
+            let mut command = Command::new("cargo");
             if self.std {
-                envs.push(("RUSTUP_TOOLCHAIN", toolchain.to_string()));
+                command.env("RUSTUP_TOOLCHAIN", toolchain);
             }
 
-            if let Some(func) = &self.func {
-                envs.push(("JOLT_FUNC_NAME", func.to_string()));
+            #[cfg(not(target_arch = "wasm32"))]
+            if self.hermetic {
+                let hermetic_toolchain =
+                    HermeticToolchain::install().expect("failed to install hermetic toolchain");
+                let rustc = if self.std {
+                    jolt_toolchain_rustc()
+                } else {
+                    hermetic_toolchain.rustc()
+                };
+                let workspace = std::env::current_dir().unwrap();
+                for config in HermeticToolchain::ambient_cargo_configs(&workspace) {
+                    eprintln!(
+                        "warning: hermetic guest build reads cargo configuration from {}",
+                        config.display()
+                    );
+                }
+
+                command = hermetic_toolchain.cargo_command(&rustc);
+                rust_flags.extend(hermetic_toolchain.remap_path_flags(&workspace));
+                args.push("--locked");
+            } else {
+                rust_flags.extend(remap_path_flags(&std::env::current_dir().unwrap()));
+                command.env("CARGO_INCREMENTAL", "0");
             }
 
-            let target = format!(
-                "/tmp/jolt-guest-target-{}-{}",
-                self.guest,
-                self.func.as_ref().unwrap_or(&"".to_string())
-            );
+            command.env("CARGO_ENCODED_RUSTFLAGS", rust_flags.join("\x1f"));
+            if let Some(func) = &self.func {
+                command.env("JOLT_FUNC_NAME", func);
+            }
 
-            let output = Command::new("cargo")
-                .envs(envs)
-                .args([
-                    "build",
-                    "--release",
-                    "--features",
-                    "guest",
-                    "-p",
-                    &self.guest,
-                    "--target-dir",
-                    &target,
-                    "--target",
-                    toolchain,
-                ])
-                .output()
-                .expect("failed to build guest");
+            let output = command.args(args).output().expect("failed to build guest");
 
             if !output.status.success() {
                 io::stderr().write_all(&output.stderr).unwrap();
@@ -165,6 +258,17 @@ impl Program {
         }
     }
 
+    /// The ID of the guest program, as recorded in its preprocessing by
+    /// [`Jolt::preprocess`](crate::jolt::vm::Jolt::preprocess).
+    pub fn program_id(&mut self) -> ProgramId {
+        let (bytecode, memory_init) = self.decode();
+        let bytecode_rows: Vec<BytecodeRow> = expand_virtual_sequences(bytecode)
+            .iter()
+            .map(BytecodeRow::from_instruction::<RV32I>)
+            .collect();
+        ProgramId::new(&bytecode_rows, &memory_init)
+    }
+
     pub fn decode(&mut self) -> (Vec<ELFInstruction>, Vec<(u64, u8)>) {
         self.build();
         let elf = self.elf.as_ref().unwrap();
@@ -174,60 +278,145 @@ impl Program {
         tracer::decode(&elf_contents)
     }
 
-    // TODO(moodlezoup): Make this generic over InstructionSet
+    /// Runs the guest under a GDB server listening on `address` (e.g. `"localhost:1234"`),
+    /// so it can be debugged with `target remote` before it is traced and proven.
+    pub fn debug(mut self, address: &str) -> io::Result<JoltDevice> {
+        self.build();
+        let elf = self.elf.unwrap();
+        tracer::debug(&elf, &self.input, &self.blobs, &self.memory_config, address)
+    }
+
+    /// Runs the program twice with the same inputs and reports the first difference between
+    /// the runs, e.g. a host call returning a different value, with its cycle and call site.
+    /// A nondeterministic program's proofs fail whenever its outputs are checked against a
+    /// separate run. Returns the number of instructions executed.
+    pub fn check_replay(self) -> Result<u64, Box<ReplayDivergence>> {
+        self.check_replay_with_instruction_set::<RV32I>()
+    }
+
+    /// Like [`Self::check_replay`], but executes `custom-0` instructions with those of `I`.
+    #[tracing::instrument(skip_all, name = "Program::check_replay")]
+    pub fn check_replay_with_instruction_set<I: JoltInstructionSet>(
+        mut self,
+    ) -> Result<u64, Box<ReplayDivergence>> {
+        self.build();
+        let elf = self.elf.unwrap();
+        tracer::check_replay(
+            &elf,
+            &self.input,
+            &self.blobs,
+            &self.memory_config,
+            Some(I::execute_custom_instruction),
+        )
+    }
+
+    /// Traces the program. Anything the guest logs with `jolt::println!` is written to stdout.
+    ///
+    /// # Panics
+    ///
+    /// If the guest panics, with the guest's panic message, or if it accesses memory beyond the
+    /// end of its RAM or overflows its stack. Use [`Self::try_trace`] to handle these instead.
     #[tracing::instrument(skip_all, name = "Program::trace")]
-    pub fn trace(mut self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
+    pub fn trace(self) -> (JoltDevice, Vec<JoltTraceStep<RV32I>>) {
+        self.trace_with_instruction_set::<RV32I>()
+    }
+
+    /// Like [`Self::trace`], but returns the guest's panic or fault as an error.
+    pub fn try_trace(self) -> Result<(JoltDevice, Vec<JoltTraceStep<RV32I>>), JoltError> {
+        self.try_trace_with_instruction_set::<RV32I>()
+    }
+
+    /// Traces the program for a VM whose instruction set is `I`. Any `custom-0` instructions
+    /// in `I` are emulated using their lookups.
+    ///
+    /// # Panics
+    ///
+    /// If the guest panics or faults, like [`Self::trace`].
+    #[tracing::instrument(skip_all, name = "Program::trace_with_instruction_set")]
+    pub fn trace_with_instruction_set<I: JoltInstructionSet>(
+        self,
+    ) -> (JoltDevice, Vec<JoltTraceStep<I>>) {
+        self.try_trace_with_instruction_set()
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Self::trace_with_instruction_set`], but returns the guest's panic or fault as an
+    /// error ([`JoltError::GuestPanic`], [`JoltError::MemoryOutOfBounds`] or
+    /// [`JoltError::StackOverflow`]). A panicking guest's trace cannot be proven, since the
+    /// environment call the guest makes to report its panic is not traced.
+    pub fn try_trace_with_instruction_set<I: JoltInstructionSet>(
+        mut self,
+    ) -> Result<(JoltDevice, Vec<JoltTraceStep<I>>), JoltError> {
         self.build();
         let elf = self.elf.unwrap();
-        let (raw_trace, io_device) =
-            tracer::trace(&elf, &self.input, self.max_input_size, self.max_output_size);
+        let (raw_trace, io_device, guest_panic, guest_fault, guest_log, _) =
+            tracer::trace_with_custom_instructions(
+                &elf,
+                &self.input,
+                &self.blobs,
+                &self.memory_config,
+                I::execute_custom_instruction,
+            );
+        // Written by `jolt::println!`, for debugging
+        print!("{guest_log}");
+        if let Some(guest_panic) = guest_panic {
+            return Err(guest_panic.into());
+        }
+        if let Some(guest_fault) = guest_fault {
+            return Err(guest_fault.into());
+        }
 
         let trace: Vec<_> = raw_trace
             .into_par_iter()
-            .flat_map(|row| match row.instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REM => REMInstruction::<32>::virtual_trace(row),
-                tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_trace(row),
-                _ => vec![row],
-            })
+            .flat_map(virtual_trace)
             .map(|row| {
-                let instruction_lookup = if let Ok(jolt_instruction) = RV32I::try_from(&row) {
-                    Some(jolt_instruction)
-                } else {
-                    // Instruction does not use lookups
-                    None
-                };
+                // None if the instruction does not use lookups
+                let instruction_lookup = I::try_from(&row).ok();
 
                 JoltTraceStep {
                     instruction_lookup,
-                    bytecode_row: BytecodeRow::from_instruction::<RV32I>(&row.instruction),
+                    bytecode_row: BytecodeRow::from_instruction::<I>(&row.instruction),
                     memory_ops: (&row).into(),
                     circuit_flags: row.instruction.to_circuit_flags(),
                 }
             })
             .collect();
 
-        (io_device, trace)
+        Ok((io_device, trace))
+    }
+
+    /// Runs the program, but only records the instructions selected by `sampling`, and
+    /// extrapolates the program's cycle and lookup counts from them. Much cheaper than
+    /// [`Self::trace_analyze`] for long-running programs, at the cost of precision.
+    #[tracing::instrument(skip_all, name = "Program::trace_sampled")]
+    pub fn trace_sampled(mut self, sampling: Sampling) -> SampledSummary {
+        self.build();
+        let elf = self.elf.as_ref().unwrap();
+        let (trace, io_device, _, _, guest_log, heap) =
+            tracer::trace_sampled(elf, &self.input, &self.blobs, &self.memory_config, sampling);
+        let elf_contents = fs::read(elf).unwrap();
+        SampledSummary::new(&elf_contents, trace, io_device, guest_log, heap)
     }
 
     pub fn trace_analyze<F: JoltField>(mut self) -> ProgramSummary {
         self.build();
         let elf = self.elf.as_ref().unwrap();
-        let (raw_trace, _) =
-            tracer::trace(elf, &self.input, self.max_input_size, self.max_output_size);
+        let (raw_trace, _, _, _, guest_log, heap) =
+            tracer::trace(elf, &self.input, &self.blobs, &self.memory_config);
 
+        let elf = elf.clone();
         let (bytecode, memory_init) = self.decode();
         let (io_device, processed_trace) = self.trace();
 
         ProgramSummary {
+            elf,
             raw_trace,
             bytecode,
             memory_init,
             io_device,
             processed_trace,
+            guest_log,
+            heap,
         }
     }
 
@@ -238,8 +427,8 @@ impl Program {
         }
 
         let linker_script = LINKER_SCRIPT_TEMPLATE
-            .replace("{MEMORY_SIZE}", &self.memory_size.to_string())
-            .replace("{STACK_SIZE}", &self.stack_size.to_string());
+            .replace("{MEMORY_SIZE}", &self.memory_config.memory_size.to_string())
+            .replace("{STACK_SIZE}", &self.memory_config.stack_size.to_string());
 
         let mut file = File::create(linker_path).expect("could not create linker file");
         file.write_all(linker_script.as_bytes())
@@ -251,6 +440,51 @@ impl Program {
     }
 }
 
+/// Expands `row` into the rows of its virtual instruction sequence, if it has one.
+pub(crate) fn virtual_trace(row: tracer::RVTraceRow) -> Vec<tracer::RVTraceRow> {
+    match row.instruction.opcode {
+        tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::REM => REMInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::ORN => ORNInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MIN => MINInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MAX => MAXInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MINU => MINUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::MAXU => MAXUInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SEXT_B => SEXTBInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::SEXT_H => SEXTHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::ZEXT_H => ZEXTHInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::ROL => ROLInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::ROR => RORInstruction::<32>::virtual_trace(row),
+        tracer::RV32IM::RORI => RORIInstruction::<32>::virtual_trace(row),
+        _ => vec![row],
+    }
+}
+
+/// `rustc` flags that replace the paths of the guest's workspace and of `CARGO_HOME` (where the
+/// sources of its dependencies live) with fixed ones, so that they don't end up in panic messages
+/// in the guest. Hermetic builds remap the paths of their own toolchain and `CARGO_HOME` instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn remap_path_flags(workspace: &std::path::Path) -> Vec<String> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+    cargo_home
+        .map(|cargo_home| (cargo_home, "/jolt/cargo"))
+        .into_iter()
+        .chain([(workspace.to_path_buf(), "/jolt/guest")])
+        .flat_map(|(from, to)| {
+            [
+                "--remap-path-prefix".to_string(),
+                format!("{}={to}", from.display()),
+            ]
+        })
+        .collect()
+}
+
 const LINKER_SCRIPT_TEMPLATE: &str = r#"
 MEMORY {
   program (rwx) : ORIGIN = 0x80000000, LENGTH = {MEMORY_SIZE}
@@ -278,5 +512,6 @@ SECTIONS {
   _STACK_PTR = .;
   . = ALIGN(8);
   _HEAP_PTR = .;
+  _HEAP_END = ORIGIN(program) + LENGTH(program);
 }
 "#;
@@ -2,7 +2,7 @@ use std::{
     fs::{self, read_to_string, File},
     future::Future,
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use dirs::home_dir;
@@ -13,8 +13,8 @@ use reqwest::Client;
 use tokio::runtime::Runtime;
 
 const TOOLCHAIN_TAG: &str = include_str!("../../../guest-toolchain-tag");
-const DOWNLOAD_RETRIES: usize = 5;
-const DELAY_BASE_MS: u64 = 500;
+pub(crate) const DOWNLOAD_RETRIES: usize = 5;
+pub(crate) const DELAY_BASE_MS: u64 = 500;
 
 #[cfg(not(target_arch = "wasm32"))]
 /// Installs the toolchain if it is not already
@@ -44,7 +44,7 @@ pub fn install_no_std_toolchain() -> Result<()> {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-async fn retry_times<F, T, E>(times: usize, base_ms: u64, f: F) -> Result<T>
+pub(crate) async fn retry_times<F, T, E>(times: usize, base_ms: u64, f: F) -> Result<T>
 where
     F: Fn() -> E,
     E: Future<Output = Result<T>>,
@@ -115,6 +115,11 @@ async fn download_toolchain(client: &Client, url: &str) -> Result<()> {
     }
 
     println!("Downloading toolchain");
+    download_file(client, url, &output_path).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn download_file(client: &Client, url: &str, output_path: &Path) -> Result<()> {
     let mut response = client.get(url).send().await?;
     if response.status().is_success() {
         let mut file = File::create(output_path)?;
@@ -140,8 +145,8 @@ async fn download_toolchain(client: &Client, url: &str) -> Result<()> {
         Ok(())
     } else {
         Err(match response.error_for_status() {
-            Ok(_) => eyre!("failed to download toolchain"),
-            Err(err) => eyre!("failed to download toolchain: {}", err),
+            Ok(_) => eyre!("failed to download {}", url),
+            Err(err) => eyre!("failed to download {}: {}", url, err),
         })
     }
 }
@@ -163,7 +168,12 @@ fn has_toolchain() -> bool {
     }
 }
 
-fn jolt_dir() -> PathBuf {
+/// The `rustc` of the Jolt toolchain installed by [`install_toolchain`].
+pub(crate) fn jolt_toolchain_rustc() -> PathBuf {
+    jolt_dir().join("rust/build/host/stage2/bin/rustc")
+}
+
+pub(crate) fn jolt_dir() -> PathBuf {
     home_dir().unwrap().join(".jolt")
 }
 
@@ -0,0 +1,322 @@
+//! Export of execution traces for external analysis tools, with one record per Jolt cycle
+//! (i.e. after expanding virtual instruction sequences). See
+//! [`ProgramSummary::export_trace`](super::analyze::ProgramSummary::export_trace).
+//!
+//! [`TraceFormat::JsonLines`] writes each [`TraceRecord`] as a JSON object on its own line.
+//! [`TraceFormat::Binary`] is a compact little-endian encoding, read back by [`read_binary`]:
+//!
+//! ```text
+//! header: "JOLTTRCE" | version: u32
+//! record: cycle: u64 | pc: u64 | opcode: u8 | flags: u8
+//!         | [rs1: u8 | value: u64]        if flags & 0b00001
+//!         | [rs2: u8 | value: u64]        if flags & 0b00010
+//!         | [rd: u8 | value: u64]         if flags & 0b00100
+//!         | [address: u64 | value: u64]   if flags & 0b01000 (read) or 0b10000 (write)
+//! ```
+//!
+//! Records follow the header until the end of the file. `opcode` is the discriminant of
+//! [`RV32IM`], and the value of `rd` and of a memory write is the value after the instruction.
+
+use std::{
+    io::{self, BufWriter, Read, Write},
+    ops::Range,
+};
+
+use serde::{Deserialize, Serialize};
+use tracer::{MemoryState, RVTraceRow, RV32IM};
+
+use super::virtual_trace;
+
+const MAGIC: &[u8; 8] = b"JOLTTRCE";
+const VERSION: u32 = 1;
+
+const RS1: u8 = 1;
+const RS2: u8 = 1 << 1;
+const RD: u8 = 1 << 2;
+const MEMORY_READ: u8 = 1 << 3;
+const MEMORY_WRITE: u8 = 1 << 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// One JSON object per line, as serialized from [`TraceRecord`].
+    JsonLines,
+    /// The compact encoding described in the [module documentation](self).
+    Binary,
+}
+
+/// A register and its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterAccess {
+    /// 0-31 for the RISC-V registers, and above for the virtual registers used by virtual
+    /// instruction sequences.
+    pub register: u8,
+    pub value: u64,
+}
+
+/// One Jolt cycle of a trace.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceRecord {
+    pub cycle: u64,
+    pub pc: u64,
+    pub opcode: RV32IM,
+    pub rs1: Option<RegisterAccess>,
+    pub rs2: Option<RegisterAccess>,
+    /// The destination register and the value written to it.
+    pub rd: Option<RegisterAccess>,
+    pub memory: Option<MemoryState>,
+}
+
+impl TraceRecord {
+    fn new(cycle: u64, row: &RVTraceRow) -> Self {
+        let access = |register: Option<u64>, value: Option<u64>| {
+            Some(RegisterAccess {
+                register: register? as u8,
+                value: value?,
+            })
+        };
+        let state = &row.register_state;
+        Self {
+            cycle,
+            pc: row.instruction.address,
+            opcode: row.instruction.opcode,
+            rs1: access(row.instruction.rs1, state.rs1_val),
+            rs2: access(row.instruction.rs2, state.rs2_val),
+            rd: access(row.instruction.rd, state.rd_post_val),
+            memory: row.memory_state.clone(),
+        }
+    }
+}
+
+/// Writes the Jolt cycles of `raw_trace` whose PC is in `pcs` to `writer` in `format`,
+/// returning the number of records written.
+pub(crate) fn write_trace<W: Write>(
+    raw_trace: &[RVTraceRow],
+    format: TraceFormat,
+    pcs: Range<u64>,
+    writer: W,
+) -> io::Result<usize> {
+    let mut writer = BufWriter::new(writer);
+    if format == TraceFormat::Binary {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+    }
+
+    let mut num_records = 0;
+    let cycles = raw_trace.iter().cloned().flat_map(virtual_trace);
+    for (cycle, row) in cycles.enumerate() {
+        if !pcs.contains(&row.instruction.address) {
+            continue;
+        }
+        let record = TraceRecord::new(cycle as u64, &row);
+        match format {
+            TraceFormat::JsonLines => {
+                serde_json::to_writer(&mut writer, &record)?;
+                writer.write_all(b"\n")?;
+            }
+            TraceFormat::Binary => write_binary_record(&record, &mut writer)?,
+        }
+        num_records += 1;
+    }
+    writer.flush()?;
+    Ok(num_records)
+}
+
+fn write_binary_record<W: Write>(record: &TraceRecord, writer: &mut W) -> io::Result<()> {
+    let registers = [(record.rs1, RS1), (record.rs2, RS2), (record.rd, RD)];
+    let mut flags = registers
+        .iter()
+        .filter(|(access, _)| access.is_some())
+        .fold(0, |flags, (_, flag)| flags | flag);
+    flags |= match record.memory {
+        Some(MemoryState::Read { .. }) => MEMORY_READ,
+        Some(MemoryState::Write { .. }) => MEMORY_WRITE,
+        None => 0,
+    };
+
+    writer.write_all(&record.cycle.to_le_bytes())?;
+    writer.write_all(&record.pc.to_le_bytes())?;
+    writer.write_all(&[record.opcode as u8, flags])?;
+    for access in registers.iter().filter_map(|(access, _)| access.as_ref()) {
+        writer.write_all(&[access.register])?;
+        writer.write_all(&access.value.to_le_bytes())?;
+    }
+    if let Some(MemoryState::Read { address, value })
+    | Some(MemoryState::Write {
+        address,
+        post_value: value,
+    }) = record.memory
+    {
+        writer.write_all(&address.to_le_bytes())?;
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads a trace written in [`TraceFormat::Binary`].
+pub fn read_binary<R: Read>(reader: R) -> io::Result<Vec<TraceRecord>> {
+    let mut reader = io::BufReader::new(reader);
+    let mut header = [0; 12];
+    reader.read_exact(&mut header)?;
+    if &header[..8] != MAGIC {
+        return Err(invalid_data("not a Jolt trace"));
+    }
+    let version = u32::from_le_bytes(header[8..].try_into().unwrap());
+    if version != VERSION {
+        return Err(invalid_data("unsupported Jolt trace version"));
+    }
+
+    let mut records = vec![];
+    let mut fixed = [0; 18];
+    loop {
+        // The trace ends at the end of the file, which must be between records
+        match reader.read(&mut fixed[..1])? {
+            0 => return Ok(records),
+            _ => reader.read_exact(&mut fixed[1..])?,
+        }
+        let flags = fixed[17];
+        let opcode = RV32IM::from_repr(fixed[16]).ok_or_else(|| invalid_data("unknown opcode"))?;
+        let mut register = |flag| -> io::Result<Option<RegisterAccess>> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            let mut bytes = [0; 9];
+            reader.read_exact(&mut bytes)?;
+            Ok(Some(RegisterAccess {
+                register: bytes[0],
+                value: u64::from_le_bytes(bytes[1..].try_into().unwrap()),
+            }))
+        };
+        let (rs1, rs2, rd) = (register(RS1)?, register(RS2)?, register(RD)?);
+        let memory = if flags & (MEMORY_READ | MEMORY_WRITE) == 0 {
+            None
+        } else {
+            let mut bytes = [0; 16];
+            reader.read_exact(&mut bytes)?;
+            let address = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+            let value = u64::from_le_bytes(bytes[8..].try_into().unwrap());
+            Some(if flags & MEMORY_READ != 0 {
+                MemoryState::Read { address, value }
+            } else {
+                MemoryState::Write {
+                    address,
+                    post_value: value,
+                }
+            })
+        };
+        records.push(TraceRecord {
+            cycle: u64::from_le_bytes(fixed[..8].try_into().unwrap()),
+            pc: u64::from_le_bytes(fixed[8..16].try_into().unwrap()),
+            opcode,
+            rs1,
+            rs2,
+            rd,
+            memory,
+        });
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracer::{ELFInstruction, RegisterState};
+
+    fn row(
+        address: u64,
+        opcode: RV32IM,
+        registers: [Option<(u64, u64)>; 3],
+        memory_state: Option<MemoryState>,
+    ) -> RVTraceRow {
+        let [rs1, rs2, rd] = registers;
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address,
+                opcode,
+                rs1: rs1.map(|(register, _)| register),
+                rs2: rs2.map(|(register, _)| register),
+                rd: rd.map(|(register, _)| register),
+                imm: None,
+                virtual_sequence_remaining: None,
+            },
+            register_state: RegisterState {
+                rs1_val: rs1.map(|(_, value)| value),
+                rs2_val: rs2.map(|(_, value)| value),
+                rd_post_val: rd.map(|(_, value)| value),
+            },
+            memory_state,
+            advice_value: None,
+        }
+    }
+
+    fn trace() -> Vec<RVTraceRow> {
+        vec![
+            row(
+                0x8000_0000,
+                RV32IM::ADD,
+                [Some((1, 2)), Some((2, 3)), Some((3, 5))],
+                None,
+            ),
+            row(
+                0x8000_0004,
+                RV32IM::LW,
+                [Some((3, 0x8000_1000)), None, Some((4, 42))],
+                Some(MemoryState::Read {
+                    address: 0x8000_1000,
+                    value: 42,
+                }),
+            ),
+            row(
+                0x8000_0008,
+                RV32IM::SW,
+                [Some((3, 0x8000_1000)), Some((4, 7)), None],
+                Some(MemoryState::Write {
+                    address: 0x8000_1000,
+                    post_value: 7,
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let trace = trace();
+        let mut bytes = vec![];
+        let num_records =
+            write_trace(&trace, TraceFormat::Binary, 0..u64::MAX, &mut bytes).unwrap();
+        assert_eq!(num_records, 3);
+        let records = read_binary(bytes.as_slice()).unwrap();
+        let expected: Vec<_> = trace
+            .iter()
+            .enumerate()
+            .map(|(cycle, row)| TraceRecord::new(cycle as u64, row))
+            .collect();
+        assert_eq!(records, expected);
+
+        assert!(read_binary(&bytes[..bytes.len() - 1]).is_err());
+        bytes[0] = b'X';
+        assert!(read_binary(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn json_lines_filtered_by_pc() {
+        let mut bytes = vec![];
+        let pcs = 0x8000_0004..0x8000_0008;
+        let num_records = write_trace(&trace(), TraceFormat::JsonLines, pcs, &mut bytes).unwrap();
+        assert_eq!(num_records, 1);
+        let lines: Vec<&str> = std::str::from_utf8(&bytes).unwrap().lines().collect();
+        assert_eq!(lines.len(), 1);
+        let record: TraceRecord = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(record.cycle, 1);
+        assert_eq!(record.opcode, RV32IM::LW);
+        assert_eq!(
+            record.rd,
+            Some(RegisterAccess {
+                register: 4,
+                value: 42
+            })
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::field::JoltField;
+use crate::jolt::subtable::{popcount::PopcountSubtable, LassoSubtable};
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+/// Zbb's `cpop`: the number of set bits of x.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CPOPInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for CPOPInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, _: usize) -> F {
+        assert_eq!(vals.len(), C);
+        let mut sum = F::zero();
+        for val in vals {
+            sum += *val;
+        }
+        sum
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![(
+            Box::new(PopcountSubtable::new()),
+            SubtableIndices::from(0..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        // This is the same for 32-bit and 64-bit word sizes
+        self.0.count_ones() as u64
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::CPOPInstruction;
+
+    #[test]
+    fn cpop_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            jolt_instruction_test!(CPOPInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            CPOPInstruction::<WORD_SIZE>(0),
+            CPOPInstruction::<WORD_SIZE>(1),
+            CPOPInstruction::<WORD_SIZE>(1 << 8),
+            CPOPInstruction::<WORD_SIZE>(1 << 31),
+            CPOPInstruction::<WORD_SIZE>(0xff00),
+            CPOPInstruction::<WORD_SIZE>(u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn cpop_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            jolt_instruction_test!(CPOPInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let instructions = vec![
+            CPOPInstruction::<WORD_SIZE>(0),
+            CPOPInstruction::<WORD_SIZE>(1),
+            CPOPInstruction::<WORD_SIZE>(1 << 32),
+            CPOPInstruction::<WORD_SIZE>(1 << 63),
+            CPOPInstruction::<WORD_SIZE>(u64::MAX),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::field::JoltField;
+use crate::jolt::subtable::{
+    left_is_zero::LeftIsZeroSubtable, trailing_zeros::TrailingZerosSubtable, LassoSubtable,
+};
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+/// Zbb's `ctz`: the number of trailing zero bits of x, or `WORD_SIZE` if x is zero.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CTZInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for CTZInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        let vals_by_subtable = self.slice_values(vals, C, M);
+        let trailing_zeros = vals_by_subtable[0];
+        let is_zero = vals_by_subtable[1];
+
+        // A chunk's trailing zeros only count if all the chunks after it are zero
+        let mut sum = F::zero();
+        let mut suffix_is_zero = F::one();
+        for i in (0..C).rev() {
+            sum += trailing_zeros[i] * suffix_is_zero;
+            suffix_is_zero *= is_zero[i];
+        }
+        sum
+    }
+
+    fn g_poly_degree(&self, C: usize) -> usize {
+        C
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        _: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        vec![
+            (
+                Box::new(TrailingZerosSubtable::new()),
+                SubtableIndices::from(0..C),
+            ),
+            (
+                Box::new(LeftIsZeroSubtable::new()),
+                SubtableIndices::from(0..C),
+            ),
+        ]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if WORD_SIZE == 32 {
+            (self.0 as u32).trailing_zeros() as u64
+        } else if WORD_SIZE == 64 {
+            self.0.trailing_zeros() as u64
+        } else {
+            panic!("CTZ is only implemented for 32-bit or 64-bit word sizes")
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::CTZInstruction;
+
+    #[test]
+    fn ctz_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            jolt_instruction_test!(CTZInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            CTZInstruction::<WORD_SIZE>(0),
+            CTZInstruction::<WORD_SIZE>(1),
+            CTZInstruction::<WORD_SIZE>(1 << 8),
+            CTZInstruction::<WORD_SIZE>(1 << 31),
+            CTZInstruction::<WORD_SIZE>(0xff00),
+            CTZInstruction::<WORD_SIZE>(u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn ctz_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            jolt_instruction_test!(CTZInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let instructions = vec![
+            CTZInstruction::<WORD_SIZE>(0),
+            CTZInstruction::<WORD_SIZE>(1),
+            CTZInstruction::<WORD_SIZE>(1 << 32),
+            CTZInstruction::<WORD_SIZE>(1 << 63),
+            CTZInstruction::<WORD_SIZE>(u64::MAX),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Application-defined instructions.
+//!
+//! Guests can use the RISC-V `custom-0` opcode space (`0b0001011`, R-type encoding) for
+//! instructions that are not part of RV32IM. Each such instruction is proven with a lookup,
+//! just like the built-in ones: implement [`JoltInstruction`] and [`CustomInstruction`] for it,
+//! then register it with [`custom_instruction_set!`](crate::custom_instruction_set) (and any
+//! new subtables it uses with [`custom_subtable_set!`](crate::custom_subtable_set)).
+
+use ark_std::log2;
+
+use super::JoltInstruction;
+use crate::jolt::vm::rv32i_vm::{C, M};
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+#[doc(hidden)]
+pub use common::rv_trace::{ELFInstruction, RVTraceRow, NUM_CIRCUIT_FLAGS};
+
+/// # Soundness
+///
+/// The only constraint tying a custom instruction's lookup to the values of its source
+/// registers is that each chunk of the lookup query is the concatenation of the corresponding
+/// chunks of `rs1_val` and `rs2_val` (`CircuitFlags::ConcatLookupQueryChunks`, which is set for
+/// every `custom-0` instruction). So [`JoltInstruction::operands`] must return
+/// `(rs1_val, rs2_val)`, and [`JoltInstruction::to_indices`] must chunk them with
+/// [`chunk_and_concatenate_operands`]; the instruction's subtables and `combine_lookups` have
+/// to be designed around that. An instruction that chunks its operands any other way can't be
+/// proven. Debug builds check both as instructions are traced (see [`check_operands`]).
+pub trait CustomInstruction: JoltInstruction + Default {
+    /// Returns whether a `custom-0` instruction with the given `funct3` and `funct7` fields
+    /// decodes to this instruction.
+    fn matches(funct3: u32, funct7: u32) -> bool;
+    /// Constructs the instruction's lookup from the values of its source registers.
+    fn from_operands(rs1_val: u64, rs2_val: u64) -> Self;
+}
+
+/// Checks, in debug builds, that `instruction`, traced with the given source register values,
+/// meets the requirements of [`CustomInstruction`]. Called by the decoders generated by
+/// [`custom_instruction_set!`](crate::custom_instruction_set).
+pub fn check_operands<I: CustomInstruction>(instruction: &I, rs1_val: u64, rs2_val: u64) {
+    debug_assert_eq!(
+        instruction.operands(),
+        (rs1_val, rs2_val),
+        "the operands of a custom instruction must be the values of its source registers"
+    );
+    debug_assert_eq!(
+        instruction.to_indices(C, log2(M) as usize),
+        chunk_and_concatenate_operands(rs1_val, rs2_val, C, log2(M) as usize),
+        "custom instructions must chunk their operands with chunk_and_concatenate_operands"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::log2;
+    use common::rv_trace::{RegisterState, RV32IM};
+    use rand::prelude::StdRng;
+    use rand::RngCore;
+    use serde::{Deserialize, Serialize};
+    use strum::EnumCount;
+
+    use super::*;
+    use crate::field::JoltField;
+    use crate::jolt::instruction::{JoltInstructionSet, SubtableIndices};
+    use crate::jolt::subtable::{xor::XorSubtable, LassoSubtable};
+    use crate::jolt::vm::rv32i_vm::RV32I;
+    use crate::utils::instruction_utils::{chunk_and_concatenate_operands, concatenate_lookups};
+
+    #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct TestXORInstruction(pub u64, pub u64);
+
+    impl JoltInstruction for TestXORInstruction {
+        fn operands(&self) -> (u64, u64) {
+            (self.0, self.1)
+        }
+
+        fn combine_lookups<F: JoltField>(&self, vals: &[F], c: usize, m: usize) -> F {
+            concatenate_lookups(vals, c, log2(m) as usize / 2)
+        }
+
+        fn g_poly_degree(&self, _: usize) -> usize {
+            1
+        }
+
+        fn subtables<F: JoltField>(
+            &self,
+            c: usize,
+            _: usize,
+        ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+            vec![(Box::new(XorSubtable::new()), SubtableIndices::from(0..c))]
+        }
+
+        fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+            chunk_and_concatenate_operands(self.0, self.1, c, log_m)
+        }
+
+        fn lookup_entry(&self) -> u64 {
+            self.0 ^ self.1
+        }
+
+        fn random(&self, rng: &mut StdRng) -> Self {
+            Self(rng.next_u32() as u64, rng.next_u32() as u64)
+        }
+    }
+
+    impl CustomInstruction for TestXORInstruction {
+        fn matches(funct3: u32, funct7: u32) -> bool {
+            funct3 == 0b100 && funct7 == 0
+        }
+
+        fn from_operands(rs1_val: u64, rs2_val: u64) -> Self {
+            Self(rs1_val, rs2_val)
+        }
+    }
+
+    crate::custom_instruction_set!(TestInstructionSet, TEST_XOR: TestXORInstruction);
+
+    /// XOR with the operands' chunks concatenated the wrong way round, which the R1CS can't
+    /// prove.
+    #[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct TestSwappedXORInstruction(pub TestXORInstruction);
+
+    impl JoltInstruction for TestSwappedXORInstruction {
+        fn operands(&self) -> (u64, u64) {
+            self.0.operands()
+        }
+
+        fn combine_lookups<F: JoltField>(&self, vals: &[F], c: usize, m: usize) -> F {
+            self.0.combine_lookups(vals, c, m)
+        }
+
+        fn g_poly_degree(&self, c: usize) -> usize {
+            self.0.g_poly_degree(c)
+        }
+
+        fn subtables<F: JoltField>(
+            &self,
+            c: usize,
+            m: usize,
+        ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+            self.0.subtables(c, m)
+        }
+
+        fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+            chunk_and_concatenate_operands(self.0 .1, self.0 .0, c, log_m)
+        }
+
+        fn lookup_entry(&self) -> u64 {
+            self.0.lookup_entry()
+        }
+
+        fn random(&self, rng: &mut StdRng) -> Self {
+            Self(self.0.random(rng))
+        }
+    }
+
+    impl CustomInstruction for TestSwappedXORInstruction {
+        fn matches(funct3: u32, funct7: u32) -> bool {
+            TestXORInstruction::matches(funct3, funct7)
+        }
+
+        fn from_operands(rs1_val: u64, rs2_val: u64) -> Self {
+            Self(TestXORInstruction(rs1_val, rs2_val))
+        }
+    }
+
+    crate::custom_instruction_set!(
+        TestSwappedInstructionSet,
+        TEST_SWAPPED_XOR: TestSwappedXORInstruction
+    );
+
+    fn instruction(opcode: RV32IM, imm: Option<u32>) -> ELFInstruction {
+        ELFInstruction {
+            address: 0x8000_0000,
+            opcode,
+            rs1: Some(1),
+            rs2: Some(2),
+            rd: Some(3),
+            imm,
+            virtual_sequence_remaining: None,
+        }
+    }
+
+    fn row(instruction: ELFInstruction, rs1_val: u64, rs2_val: u64) -> RVTraceRow {
+        RVTraceRow {
+            instruction,
+            register_state: RegisterState {
+                rs1_val: Some(rs1_val),
+                rs2_val: Some(rs2_val),
+                rd_post_val: None,
+            },
+            memory_state: None,
+            advice_value: None,
+        }
+    }
+
+    #[test]
+    fn custom_instruction_set() {
+        assert_eq!(TestInstructionSet::COUNT, RV32I::COUNT + 1);
+
+        let custom = instruction(RV32IM::CUSTOM_0, Some(0b100));
+        assert_eq!(
+            TestInstructionSet::try_from(&custom),
+            Ok(TestInstructionSet::TEST_XOR(TestXORInstruction::default()))
+        );
+        assert_eq!(
+            TestInstructionSet::try_from(&row(custom.clone(), 12, 10)),
+            Ok(TestInstructionSet::TEST_XOR(TestXORInstruction(12, 10)))
+        );
+        assert_eq!(
+            TestInstructionSet::execute_custom_instruction(&custom, 12, 10),
+            Some(6)
+        );
+
+        // Unregistered funct3/funct7
+        let unknown = instruction(RV32IM::CUSTOM_0, Some(1 << 3));
+        assert!(TestInstructionSet::try_from(&unknown).is_err());
+        assert_eq!(
+            TestInstructionSet::execute_custom_instruction(&unknown, 12, 10),
+            None
+        );
+        assert_eq!(RV32I::execute_custom_instruction(&custom, 12, 10), None);
+
+        // Built-in instructions decode as in RV32I
+        assert_eq!(
+            TestInstructionSet::try_from(&row(instruction(RV32IM::ADD, None), 12, 10)),
+            Ok(TestInstructionSet::ADD(
+                crate::jolt::instruction::add::ADDInstruction(12, 10)
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "chunk_and_concatenate_operands")]
+    fn custom_instruction_with_other_chunking() {
+        let custom = instruction(RV32IM::CUSTOM_0, Some(0b100));
+        let _ = TestSwappedInstructionSet::try_from(&row(custom, 12, 10));
+    }
+}
@@ -0,0 +1,131 @@
+use common::constants::virtual_register_index;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    add::ADDInstruction, mul::MULInstruction, slt::SLTInstruction, sub::SUBInstruction,
+    JoltInstruction,
+};
+
+/// The smaller of x and y, as signed integers
+pub struct MINInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for MINInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 4;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::MIN);
+        // MIN source registers
+        let r_x = trace_row.instruction.rs1;
+        let r_y = trace_row.instruction.rs2;
+        // Virtual registers used in sequence
+        let v_0 = Some(virtual_register_index(0));
+        let v_1 = Some(virtual_register_index(1));
+        let v_2 = Some(virtual_register_index(2));
+        // MIN operands
+        let x = trace_row.register_state.rs1_val.unwrap();
+        let y = trace_row.register_state.rs2_val.unwrap();
+
+        let mut virtual_trace = vec![];
+
+        let select_x = SLTInstruction::<WORD_SIZE>(x, y).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SLT,
+                rs1: r_x,
+                rs2: r_y,
+                rd: v_0,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: Some(y),
+                rd_post_val: Some(select_x),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let difference = SUBInstruction::<WORD_SIZE>(x, y).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SUB,
+                rs1: r_x,
+                rs2: r_y,
+                rd: v_1,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: Some(y),
+                rd_post_val: Some(difference),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let offset = MULInstruction::<WORD_SIZE>(select_x, difference).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::MUL,
+                rs1: v_0,
+                rs2: v_1,
+                rd: v_2,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(select_x),
+                rs2_val: Some(difference),
+                rd_post_val: Some(offset),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let result = ADDInstruction::<WORD_SIZE>(y, offset).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::ADD,
+                rs1: r_y,
+                rs2: v_2,
+                rd: trace_row.instruction.rd,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(y),
+                rs2_val: Some(offset),
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => (x as i32).min(y as i32) as u32 as u64,
+            64 => (x as i64).min(y as i64) as u64,
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+
+    #[test]
+    fn min_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(MINInstruction::<32>, RV32IM::MIN);
+    }
+}
@@ -1,19 +1,16 @@
-use enum_dispatch::enum_dispatch;
 use fixedbitset::*;
 use rand::prelude::StdRng;
 use serde::Serialize;
 use std::marker::Sync;
 use std::ops::Range;
 use strum::{EnumCount, IntoEnumIterator};
-use tracer::{RVTraceRow, RegisterState};
 
 use crate::field::JoltField;
 use crate::jolt::subtable::LassoSubtable;
 use crate::utils::instruction_utils::chunk_operand;
-use common::rv_trace::ELFInstruction;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState};
 use std::fmt::Debug;
 
-#[enum_dispatch]
 pub trait JoltInstruction: Clone + Debug + Send + Sync + Serialize {
     fn operands(&self) -> (u64, u64);
     /// Combines `vals` according to the instruction's "collation" polynomial `g`.
@@ -71,13 +68,50 @@ pub trait JoltInstruction: Clone + Debug + Send + Sync + Serialize {
 }
 
 pub trait JoltInstructionSet:
-    JoltInstruction + IntoEnumIterator + EnumCount + for<'a> TryFrom<&'a ELFInstruction> + Send + Sync
+    JoltInstruction
+    + Copy
+    + PartialEq
+    + Default
+    + IntoEnumIterator
+    + EnumCount
+    + for<'a> TryFrom<&'a ELFInstruction>
+    + for<'a> TryFrom<&'a RVTraceRow>
+    + Send
+    + Sync
+    + 'static
 {
+    /// Identifies the instruction set in file headers: the name of the enum followed by those
+    /// of its variants.
+    const NAME: &'static str;
+
     fn enum_index(instruction: &Self) -> usize {
         // Discriminant: https://doc.rust-lang.org/reference/items/enumerations.html#pointer-casting
         let byte = unsafe { *(instruction as *const Self as *const u8) };
         byte as usize
     }
+
+    /// Emulates a `custom-0` instruction using its lookup. Passed to the tracer as its
+    /// `CustomInstructionHandler`; returns `None` if the instruction is not part of
+    /// this instruction set.
+    fn execute_custom_instruction(
+        instruction: &ELFInstruction,
+        rs1_val: u64,
+        rs2_val: u64,
+    ) -> Option<u64> {
+        let row = RVTraceRow {
+            instruction: instruction.clone(),
+            register_state: RegisterState {
+                rs1_val: Some(rs1_val),
+                rs2_val: Some(rs2_val),
+                rd_post_val: None,
+            },
+            memory_state: None,
+            advice_value: None,
+        };
+        Self::try_from(&row)
+            .ok()
+            .map(|instruction| instruction.lookup_entry())
+    }
 }
 
 #[derive(Clone)]
@@ -149,23 +183,40 @@ pub trait VirtualInstructionSequence {
 
 pub mod add;
 pub mod and;
+pub mod andn;
 pub mod beq;
 pub mod bge;
 pub mod bgeu;
 pub mod bne;
+pub mod clz;
+pub mod cpop;
+pub mod ctz;
+pub mod custom;
 pub mod div;
 pub mod divu;
 pub mod lb;
 pub mod lh;
+pub mod max;
+pub mod maxu;
+pub mod min;
+pub mod minu;
 pub mod mul;
 pub mod mulh;
 pub mod mulhsu;
 pub mod mulhu;
 pub mod mulu;
 pub mod or;
+pub mod orc_b;
+pub mod orn;
 pub mod rem;
 pub mod remu;
+pub mod rev8;
+pub mod rol;
+pub mod ror;
+pub mod rori;
 pub mod sb;
+pub mod sext_b;
+pub mod sext_h;
 pub mod sh;
 pub mod sll;
 pub mod slt;
@@ -181,7 +232,9 @@ pub mod virtual_assert_valid_signed_remainder;
 pub mod virtual_assert_valid_unsigned_remainder;
 pub mod virtual_move;
 pub mod virtual_movsign;
+pub mod xnor;
 pub mod xor;
+pub mod zext_h;
 
 #[cfg(test)]
 pub mod test;
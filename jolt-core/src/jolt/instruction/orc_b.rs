@@ -0,0 +1,133 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::field::JoltField;
+use crate::jolt::subtable::{left_is_zero::LeftIsZeroSubtable, LassoSubtable};
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+/// Zbb's `orc.b`: sets each byte of x to 0xff if it's nonzero, and 0 otherwise.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ORCBInstruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for ORCBInstruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        // Each chunk is a byte, and becomes 0xff unless it's zero
+        assert!(M == 1 << 16);
+        assert_eq!(vals.len(), C);
+        let mut sum = F::zero();
+        for (i, is_zero) in vals.iter().enumerate() {
+            let byte = F::from_u64(0xff << (8 * (C - i - 1))).unwrap();
+            sum += byte * (F::one() - *is_zero);
+        }
+        sum
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        assert!(M == 1 << 16);
+        vec![(
+            Box::new(LeftIsZeroSubtable::new()),
+            SubtableIndices::from(0..C),
+        )]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        // This is the same for 32-bit and 64-bit word sizes
+        (0..8).fold(0, |result, i| match (self.0 >> (8 * i)) & 0xff {
+            0 => result,
+            _ => result | (0xff << (8 * i)),
+        })
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::ORCBInstruction;
+
+    #[test]
+    fn orc_b_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            jolt_instruction_test!(ORCBInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            ORCBInstruction::<WORD_SIZE>(0),
+            ORCBInstruction::<WORD_SIZE>(1),
+            ORCBInstruction::<WORD_SIZE>(1 << 8),
+            ORCBInstruction::<WORD_SIZE>(1 << 31),
+            ORCBInstruction::<WORD_SIZE>(0xff00),
+            ORCBInstruction::<WORD_SIZE>(u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn orc_b_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            jolt_instruction_test!(ORCBInstruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let instructions = vec![
+            ORCBInstruction::<WORD_SIZE>(0),
+            ORCBInstruction::<WORD_SIZE>(1),
+            ORCBInstruction::<WORD_SIZE>(1 << 32),
+            ORCBInstruction::<WORD_SIZE>(1 << 63),
+            ORCBInstruction::<WORD_SIZE>(u64::MAX),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
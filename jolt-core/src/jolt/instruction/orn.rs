@@ -0,0 +1,86 @@
+use common::constants::virtual_register_index;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{or::ORInstruction, xor::XORInstruction, JoltInstruction};
+
+/// x | !y
+pub struct ORNInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for ORNInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 2;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::ORN);
+        // ORN source registers
+        let r_x = trace_row.instruction.rs1;
+        let r_y = trace_row.instruction.rs2;
+        // Virtual registers used in sequence
+        let v_0 = Some(virtual_register_index(0));
+        // ORN operands
+        let x = trace_row.register_state.rs1_val.unwrap();
+        let y = trace_row.register_state.rs2_val.unwrap();
+
+        let mut virtual_trace = vec![];
+
+        let not_y = XORInstruction::<WORD_SIZE>(y, u32::MAX as u64).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::XORI,
+                rs1: r_y,
+                rs2: None,
+                rd: v_0,
+                imm: Some(u32::MAX),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(y),
+                rs2_val: None,
+                rd_post_val: Some(not_y),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let result = ORInstruction::<WORD_SIZE>(x, not_y).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::OR,
+                rs1: r_x,
+                rs2: v_0,
+                rd: trace_row.instruction.rd,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: Some(not_y),
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => (x | !y) as u32 as u64,
+            64 => x | !y,
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+
+    #[test]
+    fn orn_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(ORNInstruction::<32>, RV32IM::ORN);
+    }
+}
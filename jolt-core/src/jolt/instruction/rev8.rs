@@ -0,0 +1,132 @@
+use rand::prelude::StdRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{JoltInstruction, SubtableIndices};
+use crate::field::JoltField;
+use crate::jolt::subtable::{or::OrSubtable, LassoSubtable};
+use crate::utils::instruction_utils::chunk_and_concatenate_operands;
+
+/// Zbb's `rev8`: reverses the order of the bytes of x.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct REV8Instruction<const WORD_SIZE: usize>(pub u64);
+
+impl<const WORD_SIZE: usize> JoltInstruction for REV8Instruction<WORD_SIZE> {
+    fn operands(&self) -> (u64, u64) {
+        (self.0, 0)
+    }
+
+    fn combine_lookups<F: JoltField>(&self, vals: &[F], C: usize, M: usize) -> F {
+        // Each chunk is a byte, so concatenating the chunks in reverse order reverses the bytes
+        assert!(M == 1 << 16);
+        assert_eq!(vals.len(), C);
+        let mut sum = F::zero();
+        for (i, byte) in vals.iter().enumerate() {
+            sum += F::from_u64(1u64 << (8 * i)).unwrap() * *byte;
+        }
+        sum
+    }
+
+    fn g_poly_degree(&self, _: usize) -> usize {
+        1
+    }
+
+    fn subtables<F: JoltField>(
+        &self,
+        C: usize,
+        M: usize,
+    ) -> Vec<(Box<dyn LassoSubtable<F>>, SubtableIndices)> {
+        assert!(M == 1 << 16);
+        // The second operand is zero, so x | y is the byte itself
+        vec![(Box::new(OrSubtable::new()), SubtableIndices::from(0..C))]
+    }
+
+    fn to_indices(&self, C: usize, log_M: usize) -> Vec<usize> {
+        chunk_and_concatenate_operands(self.0, 0, C, log_M)
+    }
+
+    fn lookup_entry(&self) -> u64 {
+        if WORD_SIZE == 32 {
+            (self.0 as u32).swap_bytes() as u64
+        } else if WORD_SIZE == 64 {
+            self.0.swap_bytes()
+        } else {
+            panic!("REV8 is only implemented for 32-bit or 64-bit word sizes")
+        }
+    }
+
+    fn random(&self, rng: &mut StdRng) -> Self {
+        if WORD_SIZE == 32 {
+            Self(rng.next_u32() as u64)
+        } else if WORD_SIZE == 64 {
+            Self(rng.next_u64())
+        } else {
+            panic!("Only 32-bit and 64-bit word sizes are supported")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    use crate::{jolt::instruction::JoltInstruction, jolt_instruction_test};
+
+    use super::REV8Instruction;
+
+    #[test]
+    fn rev8_instruction_32_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 4;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 32;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u32() as u64;
+            jolt_instruction_test!(REV8Instruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let u32_max: u64 = u32::MAX as u64;
+        let instructions = vec![
+            REV8Instruction::<WORD_SIZE>(0),
+            REV8Instruction::<WORD_SIZE>(1),
+            REV8Instruction::<WORD_SIZE>(1 << 8),
+            REV8Instruction::<WORD_SIZE>(1 << 31),
+            REV8Instruction::<WORD_SIZE>(0xff00),
+            REV8Instruction::<WORD_SIZE>(u32_max),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+
+    #[test]
+    fn rev8_instruction_64_e2e() {
+        let mut rng = test_rng();
+        const C: usize = 8;
+        const M: usize = 1 << 16;
+        const WORD_SIZE: usize = 64;
+
+        // Random
+        for _ in 0..256 {
+            let x = rng.next_u64();
+            jolt_instruction_test!(REV8Instruction::<WORD_SIZE>(x));
+        }
+
+        // Edge cases
+        let instructions = vec![
+            REV8Instruction::<WORD_SIZE>(0),
+            REV8Instruction::<WORD_SIZE>(1),
+            REV8Instruction::<WORD_SIZE>(1 << 32),
+            REV8Instruction::<WORD_SIZE>(1 << 63),
+            REV8Instruction::<WORD_SIZE>(u64::MAX),
+        ];
+        for instruction in instructions {
+            jolt_instruction_test!(instruction);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use common::constants::virtual_register_index;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    add::ADDInstruction, or::ORInstruction, sll::SLLInstruction, srl::SRLInstruction,
+    xor::XORInstruction, JoltInstruction,
+};
+
+/// Rotate x right by y bits
+pub struct RORInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for RORInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 5;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::ROR);
+        // ROR source registers
+        let r_x = trace_row.instruction.rs1;
+        let r_y = trace_row.instruction.rs2;
+        // Virtual registers used in sequence
+        let v_0 = Some(virtual_register_index(0));
+        let v_1 = Some(virtual_register_index(1));
+        let v_2 = Some(virtual_register_index(2));
+        // ROR operands
+        let x = trace_row.register_state.rs1_val.unwrap();
+        let y = trace_row.register_state.rs2_val.unwrap();
+
+        let mut virtual_trace = vec![];
+
+        let x_shifted = SRLInstruction::<WORD_SIZE>(x, y).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SRL,
+                rs1: r_x,
+                rs2: r_y,
+                rd: v_0,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: Some(y),
+                rd_post_val: Some(x_shifted),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        // -y = !y + 1, whose low bits are the shift amount of the bits that wrap around
+        let not_y = XORInstruction::<WORD_SIZE>(y, u32::MAX as u64).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::XORI,
+                rs1: r_y,
+                rs2: None,
+                rd: v_1,
+                imm: Some(u32::MAX),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(y),
+                rs2_val: None,
+                rd_post_val: Some(not_y),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let neg_y = ADDInstruction::<WORD_SIZE>(not_y, 1).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::ADDI,
+                rs1: v_1,
+                rs2: None,
+                rd: v_1,
+                imm: Some(1),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(not_y),
+                rs2_val: None,
+                rd_post_val: Some(neg_y),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let x_wrapped = SLLInstruction::<WORD_SIZE>(x, neg_y).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SLL,
+                rs1: r_x,
+                rs2: v_1,
+                rd: v_2,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: Some(neg_y),
+                rd_post_val: Some(x_wrapped),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let result = ORInstruction::<WORD_SIZE>(x_shifted, x_wrapped).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::OR,
+                rs1: v_0,
+                rs2: v_2,
+                rd: trace_row.instruction.rd,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x_shifted),
+                rs2_val: Some(x_wrapped),
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => (x as u32).rotate_right(y as u32 % 32) as u64,
+            64 => x.rotate_right(y as u32 % 64),
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+
+    #[test]
+    fn ror_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(RORInstruction::<32>, RV32IM::ROR);
+    }
+}
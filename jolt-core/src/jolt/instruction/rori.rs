@@ -0,0 +1,151 @@
+use common::constants::virtual_register_index;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{
+    or::ORInstruction, sll::SLLInstruction, srl::SRLInstruction, JoltInstruction,
+};
+
+/// Rotate x right by the shift amount in `imm`
+pub struct RORIInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for RORIInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 3;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::RORI);
+        // RORI source register
+        let r_x = trace_row.instruction.rs1;
+        // Virtual registers used in sequence
+        let v_0 = Some(virtual_register_index(0));
+        let v_1 = Some(virtual_register_index(1));
+        // RORI operand
+        let x = trace_row.register_state.rs1_val.unwrap();
+        // The upper bits of `imm` are part of the opcode
+        let shamt = trace_row.instruction.imm.unwrap() as u64 % WORD_SIZE as u64;
+        let wrapped_shamt = (WORD_SIZE as u64 - shamt) % WORD_SIZE as u64;
+
+        let mut virtual_trace = vec![];
+
+        let x_shifted = SRLInstruction::<WORD_SIZE>(x, shamt).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SRLI,
+                rs1: r_x,
+                rs2: None,
+                rd: v_0,
+                imm: Some(shamt as u32),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: None,
+                rd_post_val: Some(x_shifted),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let x_wrapped = SLLInstruction::<WORD_SIZE>(x, wrapped_shamt).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SLLI,
+                rs1: r_x,
+                rs2: None,
+                rd: v_1,
+                imm: Some(wrapped_shamt as u32),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: None,
+                rd_post_val: Some(x_wrapped),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let result = ORInstruction::<WORD_SIZE>(x_shifted, x_wrapped).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::OR,
+                rs1: v_0,
+                rs2: v_1,
+                rd: trace_row.instruction.rd,
+                imm: None,
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x_shifted),
+                rs2_val: Some(x_wrapped),
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    /// `y` is the shift amount, i.e. the `imm` of the RORI instruction
+    fn sequence_output(x: u64, y: u64) -> u64 {
+        match WORD_SIZE {
+            32 => (x as u32).rotate_right(y as u32 % 32) as u64,
+            64 => x.rotate_right(y as u32 % 64),
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jolt::{instruction::JoltInstruction, vm::rv32i_vm::RV32I};
+    use ark_std::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    #[test]
+    fn rori_virtual_sequence_32() {
+        let mut rng = test_rng();
+        for shamt in 0..32 {
+            let x = rng.next_u32() as u64;
+            let trace_row = RVTraceRow {
+                instruction: ELFInstruction {
+                    address: rng.next_u64(),
+                    opcode: RV32IM::RORI,
+                    rs1: Some(1),
+                    rs2: None,
+                    rd: Some(2),
+                    // As decoded by the tracer, i.e. including RORI's funct7
+                    imm: Some(0x600 | shamt),
+                    virtual_sequence_remaining: None,
+                },
+                register_state: RegisterState {
+                    rs1_val: Some(x),
+                    rs2_val: None,
+                    rd_post_val: Some(RORIInstruction::<32>::sequence_output(x, shamt as u64)),
+                },
+                memory_state: None,
+                advice_value: None,
+            };
+
+            let virtual_trace = RORIInstruction::<32>::virtual_trace(trace_row);
+            assert_eq!(virtual_trace.len(), RORIInstruction::<32>::SEQUENCE_LENGTH);
+            for row in virtual_trace.iter() {
+                let lookup = RV32I::try_from(row).unwrap();
+                assert_eq!(
+                    lookup.lookup_entry(),
+                    row.register_state.rd_post_val.unwrap()
+                );
+            }
+            let last = virtual_trace.last().unwrap();
+            assert_eq!(last.instruction.rd, Some(2));
+            assert_eq!(
+                last.register_state.rd_post_val,
+                Some((x as u32).rotate_right(shamt) as u64)
+            );
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use common::constants::virtual_register_index;
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{sll::SLLInstruction, sra::SRAInstruction, JoltInstruction};
+
+/// Sign-extend the low byte of x
+pub struct SEXTBInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for SEXTBInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 2;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::SEXT_B);
+        // SEXT_B source register
+        let r_x = trace_row.instruction.rs1;
+        // Virtual registers used in sequence
+        let v_0 = Some(virtual_register_index(0));
+        // SEXT_B operand
+        let x = trace_row.register_state.rs1_val.unwrap();
+        // Shift the low 8 bits to the top of the word, and arithmetic shift them back
+        let shift = (WORD_SIZE - 8) as u64;
+
+        let mut virtual_trace = vec![];
+
+        let x_shifted = SLLInstruction::<WORD_SIZE>(x, shift).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SLLI,
+                rs1: r_x,
+                rs2: None,
+                rd: v_0,
+                imm: Some(shift as u32),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: None,
+                rd_post_val: Some(x_shifted),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+
+        let result = SRAInstruction::<WORD_SIZE>(x_shifted, shift).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::SRAI,
+                rs1: v_0,
+                rs2: None,
+                rd: trace_row.instruction.rd,
+                imm: Some(shift as u32),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x_shifted),
+                rs2_val: None,
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    fn sequence_output(x: u64, _: u64) -> u64 {
+        match WORD_SIZE {
+            32 => x as i8 as i32 as u32 as u64,
+            64 => x as i8 as i64 as u64,
+            _ => panic!("Unsupported WORD_SIZE: {}", WORD_SIZE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+
+    #[test]
+    fn sext_b_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(SEXTBInstruction::<32>, RV32IM::SEXT_B);
+    }
+}
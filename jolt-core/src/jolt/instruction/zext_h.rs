@@ -0,0 +1,57 @@
+use common::rv_trace::{ELFInstruction, RVTraceRow, RegisterState, RV32IM};
+
+use super::VirtualInstructionSequence;
+use crate::jolt::instruction::{and::ANDInstruction, JoltInstruction};
+
+/// Zero-extend the low 16 bits of x
+pub struct ZEXTHInstruction<const WORD_SIZE: usize>;
+
+impl<const WORD_SIZE: usize> VirtualInstructionSequence for ZEXTHInstruction<WORD_SIZE> {
+    const SEQUENCE_LENGTH: usize = 1;
+
+    fn virtual_trace(trace_row: RVTraceRow) -> Vec<RVTraceRow> {
+        assert_eq!(trace_row.instruction.opcode, RV32IM::ZEXT_H);
+        // ZEXT_H source register
+        let r_x = trace_row.instruction.rs1;
+        // ZEXT_H operand
+        let x = trace_row.register_state.rs1_val.unwrap();
+
+        let mut virtual_trace = vec![];
+
+        let result = ANDInstruction::<WORD_SIZE>(x, 0xffff).lookup_entry();
+        virtual_trace.push(RVTraceRow {
+            instruction: ELFInstruction {
+                address: trace_row.instruction.address,
+                opcode: RV32IM::ANDI,
+                rs1: r_x,
+                rs2: None,
+                rd: trace_row.instruction.rd,
+                imm: Some(0xffff),
+                virtual_sequence_remaining: Some(Self::SEQUENCE_LENGTH - virtual_trace.len() - 1),
+            },
+            register_state: RegisterState {
+                rs1_val: Some(x),
+                rs2_val: None,
+                rd_post_val: Some(result),
+            },
+            memory_state: None,
+            advice_value: None,
+        });
+        virtual_trace
+    }
+
+    fn sequence_output(x: u64, _: u64) -> u64 {
+        x & 0xffff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{jolt::instruction::JoltInstruction, jolt_virtual_sequence_test};
+
+    #[test]
+    fn zext_h_virtual_sequence_32() {
+        jolt_virtual_sequence_test!(ZEXTHInstruction::<32>, RV32IM::ZEXT_H);
+    }
+}
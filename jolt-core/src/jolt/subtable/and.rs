@@ -51,16 +51,19 @@ impl<F: JoltField> LassoSubtable<F> for AndSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{and::AndSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
 
     subtable_materialize_mle_parity_test!(and_materialize_mle_parity, AndSubtable<Fr>, Fr, 256);
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         and_binius_materialize_mle_parity,
         AndSubtable<BiniusField<BinaryField128b>>,
@@ -49,11 +49,13 @@ impl<F: JoltField> LassoSubtable<F> for DivByZeroSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{div_by_zero::DivByZeroSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -64,6 +66,7 @@ mod test {
         Fr,
         256
     );
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         div_by_zero_binius_materialize_mle_parity,
         DivByZeroSubtable<BiniusField<BinaryField128b>>,
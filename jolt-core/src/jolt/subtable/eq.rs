@@ -48,16 +48,19 @@ impl<F: JoltField> LassoSubtable<F> for EqSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{eq::EqSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
 
     subtable_materialize_mle_parity_test!(eq_materialize_mle_parity, EqSubtable<Fr>, Fr, 256);
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         eq_binius_materialize_mle_parity,
         EqSubtable<BiniusField<BinaryField128b>>,
@@ -57,11 +57,13 @@ impl<F: JoltField> LassoSubtable<F> for EqAbsSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{eq_abs::EqAbsSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -73,6 +75,7 @@ mod test {
         256
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         eq_abs_binius_materialize_mle_parity,
         EqAbsSubtable<BiniusField<BinaryField128b>>,
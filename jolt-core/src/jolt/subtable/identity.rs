@@ -34,11 +34,13 @@ impl<F: JoltField> LassoSubtable<F> for IdentitySubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{identity::IdentitySubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -50,6 +52,7 @@ mod test {
         256
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         iden_binius_materialize_mle_parity,
         IdentitySubtable<BiniusField<BinaryField128b>>,
@@ -0,0 +1,71 @@
+use crate::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+/// The number of leading zeros of the first operand, which is `log2(M) / 2` if it's zero.
+#[derive(Default)]
+pub struct LeadingZerosSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> LeadingZerosSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for LeadingZerosSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        // table[x | y] = leading zeros of the (log2(M) / 2)-bit x
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            let leading_zeros = (0..bits_per_operand)
+                .take_while(|&i| (x >> (bits_per_operand - i - 1)) & 1 == 0)
+                .count();
+            entries.push(F::from_u64(leading_zeros as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // \sum_k \prod_{i <= k} (1 - x_i), where the k-th term is 1 iff the first k + 1 bits
+        // of x are zero
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let (x, _) = point.split_at(b);
+
+        let mut result = F::zero();
+        let mut prefix_is_zero = F::one();
+        for x_i in x {
+            prefix_is_zero *= F::one() - *x_i;
+            result += prefix_is_zero;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{leading_zeros::LeadingZerosSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    // Counts don't embed into binary fields, so there's no Binius parity test
+    subtable_materialize_mle_parity_test!(
+        leading_zeros_materialize_mle_parity,
+        LeadingZerosSubtable<Fr>,
+        Fr,
+        256
+    );
+}
@@ -45,11 +45,13 @@ impl<F: JoltField> LassoSubtable<F> for LeftIsZeroSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{left_is_zero::LeftIsZeroSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -60,6 +62,7 @@ mod test {
         Fr,
         256
     );
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         left_is_zero_binius_materialize_mle_parity,
         LeftIsZeroSubtable<BiniusField<BinaryField128b>>,
@@ -48,11 +48,13 @@ impl<F: JoltField> LassoSubtable<F> for LeftMSBSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{left_msb::LeftMSBSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -63,6 +65,7 @@ mod test {
         Fr,
         256
     );
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         left_msb_binius_materialize_mle_parity,
         LeftMSBSubtable<BiniusField<BinaryField128b>>,
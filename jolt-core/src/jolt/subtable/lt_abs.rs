@@ -58,11 +58,13 @@ impl<F: JoltField> LassoSubtable<F> for LtAbsSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{lt_abs::LtAbsSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -74,6 +76,7 @@ mod test {
         256
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         lt_abs_binius_materialize_mle_parity,
         LtAbsSubtable<BiniusField<BinaryField128b>>,
@@ -51,16 +51,19 @@ impl<F: JoltField> LassoSubtable<F> for LtuSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{ltu::LtuSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
 
     subtable_materialize_mle_parity_test!(ltu_ark_materialize_mle_parity, LtuSubtable<Fr>, Fr, 256);
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         ltu_binius_materialize_mle_parity,
         LtuSubtable<BiniusField<BinaryField128b>>,
@@ -1,10 +1,8 @@
 use crate::field::JoltField;
-use enum_dispatch::enum_dispatch;
 use std::any::TypeId;
 use std::marker::Sync;
 use strum::{EnumCount, IntoEnumIterator};
 
-#[enum_dispatch]
 pub trait LassoSubtable<F: JoltField>: 'static + Sync {
     /// Returns the TypeId of this subtable.
     /// The `Jolt` trait has associated enum types `InstructionSet` and `Subtables`.
@@ -18,33 +16,75 @@ pub trait LassoSubtable<F: JoltField>: 'static + Sync {
     /// Evaluates the multilinear extension polynomial for this subtable at the given `point`,
     /// interpreted to be of size log_2(M), where M is the size of the subtable.
     fn evaluate_mle(&self, point: &[F]) -> F;
+    /// The subtable's type name, without paths or the field parameter, e.g. `EqSubtable` or
+    /// `SignExtendSubtable<8>`.
+    fn name(&self) -> String {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// Strips the paths from `type_name`, and its first generic argument (the field).
+fn short_type_name(type_name: &str) -> String {
+    let Some((name, args)) = type_name.split_once('<') else {
+        return type_name.rsplit("::").next().unwrap().to_string();
+    };
+    let name = name.rsplit("::").next().unwrap();
+    let args = args.strip_suffix('>').unwrap();
+
+    // Skip the first top-level argument
+    let mut depth = 0;
+    let rest = args
+        .char_indices()
+        .find_map(|(i, c)| {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => return Some(args[i + 1..].trim()),
+                _ => {}
+            }
+            None
+        })
+        .unwrap_or("");
+    if rest.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name}<{rest}>")
+    }
 }
 
 pub type SubtableId = TypeId;
 pub trait JoltSubtableSet<F: JoltField>:
     LassoSubtable<F> + IntoEnumIterator + EnumCount + From<SubtableId> + Into<usize> + Send + Sync
 {
+    /// Identifies the subtable set in file headers: the name of the enum followed by those of
+    /// its variants.
+    const NAME: &'static str;
+
     fn enum_index(subtable: Box<dyn LassoSubtable<F>>) -> usize {
         Self::from(subtable.subtable_id()).into()
     }
 }
 
 pub mod and;
+pub mod andn;
 pub mod div_by_zero;
 pub mod eq;
 pub mod eq_abs;
 pub mod identity;
+pub mod leading_zeros;
 pub mod left_is_zero;
 pub mod left_msb;
 pub mod lt_abs;
 pub mod ltu;
 pub mod or;
+pub mod popcount;
 pub mod right_is_zero;
 pub mod right_msb;
 pub mod sign_extend;
 pub mod sll;
 pub mod sra_sign;
 pub mod srl;
+pub mod trailing_zeros;
 pub mod truncate_overflow;
 pub mod xor;
 pub mod zero_lsb;
@@ -51,16 +51,19 @@ impl<F: JoltField> LassoSubtable<F> for OrSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128bPolyval;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{or::OrSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
 
     subtable_materialize_mle_parity_test!(or_ark_materialize_mle_parity, OrSubtable<Fr>, Fr, 256);
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         or_binius_materialize_mle_parity,
         OrSubtable<BiniusField<BinaryField128bPolyval>>,
@@ -50,11 +50,13 @@ impl<F: JoltField, const WIDTH: usize> LassoSubtable<F> for SignExtendSubtable<F
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{sign_extend::SignExtendSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -73,6 +75,7 @@ mod test {
         1 << 16
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         sign_extend_16_binius_materialize_mle_parity,
         SignExtendSubtable<BiniusField<BinaryField128b>, 16>,
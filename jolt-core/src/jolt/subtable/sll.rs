@@ -103,11 +103,13 @@ impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtab
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{sll::SllSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -126,6 +128,7 @@ mod test {
     subtable_materialize_mle_parity_test!(sll_materialize_mle_parity6_64, SllSubtable<Fr, 6, 64>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(sll_materialize_mle_parity7_64, SllSubtable<Fr, 7, 64>, Fr, 1 << 10);
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         sll_binius_materialize_mle_parity3_32,
         SllSubtable<BiniusField<BinaryField128b>, 3, 32>,
@@ -133,6 +136,7 @@ mod test {
         1 << 10
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         sll_binius_materialize_mle_parity3_64,
         SllSubtable<BiniusField<BinaryField128b>, 0, 64>,
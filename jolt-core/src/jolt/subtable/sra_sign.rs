@@ -93,11 +93,13 @@ impl<F: JoltField, const WORD_SIZE: usize> LassoSubtable<F> for SraSignSubtable<
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{sra_sign::SraSignSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -109,6 +111,7 @@ mod test {
       256
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         sra_sign_binius_materialize_mle_parity,
         SraSignSubtable<BiniusField<BinaryField128b>, 32>,
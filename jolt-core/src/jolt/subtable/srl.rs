@@ -100,11 +100,13 @@ impl<F: JoltField, const CHUNK_INDEX: usize, const WORD_SIZE: usize> LassoSubtab
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{srl::SrlSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -114,6 +116,7 @@ mod test {
     subtable_materialize_mle_parity_test!(srl_materialize_mle_parity2, SrlSubtable<Fr, 2, 32>, Fr, 1 << 10);
     subtable_materialize_mle_parity_test!(srl_materialize_mle_parity3, SrlSubtable<Fr, 3, 32>, Fr, 1 << 10);
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         srl_binius_materialize_mle_parity3,
         SrlSubtable<BiniusField<BinaryField128b>, 3, 32>,
@@ -0,0 +1,71 @@
+use crate::field::JoltField;
+use ark_std::log2;
+use std::marker::PhantomData;
+
+use super::LassoSubtable;
+use crate::utils::split_bits;
+
+/// The number of trailing zeros of the first operand, which is `log2(M) / 2` if it's zero.
+#[derive(Default)]
+pub struct TrailingZerosSubtable<F: JoltField> {
+    _field: PhantomData<F>,
+}
+
+impl<F: JoltField> TrailingZerosSubtable<F> {
+    pub fn new() -> Self {
+        Self {
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: JoltField> LassoSubtable<F> for TrailingZerosSubtable<F> {
+    fn materialize(&self, M: usize) -> Vec<F> {
+        // table[x | y] = trailing zeros of the (log2(M) / 2)-bit x
+        let mut entries: Vec<F> = Vec::with_capacity(M);
+        let bits_per_operand = (log2(M) / 2) as usize;
+
+        for idx in 0..M {
+            let (x, _) = split_bits(idx, bits_per_operand);
+            let trailing_zeros = (0..bits_per_operand)
+                .take_while(|&i| (x >> i) & 1 == 0)
+                .count();
+            entries.push(F::from_u64(trailing_zeros as u64).unwrap());
+        }
+        entries
+    }
+
+    fn evaluate_mle(&self, point: &[F]) -> F {
+        // \sum_k \prod_{i <= k} (1 - x_{b - i - 1}), where the k-th term is 1 iff the last
+        // k + 1 bits of x are zero
+        debug_assert!(point.len() % 2 == 0);
+        let b = point.len() / 2;
+        let (x, _) = point.split_at(b);
+
+        let mut result = F::zero();
+        let mut suffix_is_zero = F::one();
+        for x_i in x.iter().rev() {
+            suffix_is_zero *= F::one() - *x_i;
+            result += suffix_is_zero;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fr;
+
+    use crate::{
+        jolt::subtable::{trailing_zeros::TrailingZerosSubtable, LassoSubtable},
+        subtable_materialize_mle_parity_test,
+    };
+
+    // Counts don't embed into binary fields, so there's no Binius parity test
+    subtable_materialize_mle_parity_test!(
+        trailing_zeros_materialize_mle_parity,
+        TrailingZerosSubtable<Fr>,
+        Fr,
+        256
+    );
+}
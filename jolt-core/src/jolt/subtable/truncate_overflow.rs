@@ -54,11 +54,13 @@ impl<F: JoltField, const WORD_SIZE: usize> LassoSubtable<F>
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{truncate_overflow::TruncateOverflowSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -70,6 +72,7 @@ mod test {
       256
     );
 
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         truncate_overflow_binius_materialize_mle_parity,
         TruncateOverflowSubtable<BiniusField<BinaryField128b>, 32>,
@@ -51,16 +51,19 @@ impl<F: JoltField> LassoSubtable<F> for XorSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{xor::XorSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
 
     subtable_materialize_mle_parity_test!(xor_materialize_mle_parity, XorSubtable<Fr>, Fr, 256);
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         xor_binius_materialize_mle_parity,
         XorSubtable<BiniusField<BinaryField128b>>,
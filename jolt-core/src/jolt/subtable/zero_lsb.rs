@@ -38,11 +38,13 @@ impl<F: JoltField> LassoSubtable<F> for ZeroLSBSubtable<F> {
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "binius")]
+    use crate::field::binius::BiniusField;
     use ark_bn254::Fr;
+    #[cfg(feature = "binius")]
     use binius_field::BinaryField128b;
 
     use crate::{
-        field::binius::BiniusField,
         jolt::subtable::{zero_lsb::ZeroLSBSubtable, LassoSubtable},
         subtable_materialize_mle_parity_test,
     };
@@ -53,6 +55,7 @@ mod test {
         Fr,
         256
     );
+    #[cfg(feature = "binius")]
     subtable_materialize_mle_parity_test!(
         zero_lsb_binius_materialize_mle_parity,
         ZeroLSBSubtable<BiniusField<BinaryField128b>>,
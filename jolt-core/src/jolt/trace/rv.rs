@@ -1,14 +1,20 @@
 use crate::jolt::instruction::and::ANDInstruction;
+use crate::jolt::instruction::andn::ANDNInstruction;
 use crate::jolt::instruction::beq::BEQInstruction;
 use crate::jolt::instruction::bge::BGEInstruction;
 use crate::jolt::instruction::bgeu::BGEUInstruction;
 use crate::jolt::instruction::bne::BNEInstruction;
+use crate::jolt::instruction::clz::CLZInstruction;
+use crate::jolt::instruction::cpop::CPOPInstruction;
+use crate::jolt::instruction::ctz::CTZInstruction;
 use crate::jolt::instruction::lb::LBInstruction;
 use crate::jolt::instruction::lh::LHInstruction;
 use crate::jolt::instruction::mul::MULInstruction;
 use crate::jolt::instruction::mulhu::MULHUInstruction;
 use crate::jolt::instruction::mulu::MULUInstruction;
 use crate::jolt::instruction::or::ORInstruction;
+use crate::jolt::instruction::orc_b::ORCBInstruction;
+use crate::jolt::instruction::rev8::REV8Instruction;
 use crate::jolt::instruction::sb::SBInstruction;
 use crate::jolt::instruction::sh::SHInstruction;
 use crate::jolt::instruction::sll::SLLInstruction;
@@ -24,140 +30,172 @@ use crate::jolt::instruction::virtual_assert_valid_div0::AssertValidDiv0Instruct
 use crate::jolt::instruction::virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction;
 use crate::jolt::instruction::virtual_assert_valid_unsigned_remainder::AssertValidUnsignedRemainderInstruction;
 use crate::jolt::instruction::virtual_move::MOVEInstruction;
+use crate::jolt::instruction::xnor::XNORInstruction;
 use crate::jolt::instruction::xor::XORInstruction;
 use crate::jolt::instruction::{add::ADDInstruction, virtual_movsign::MOVSIGNInstruction};
-use crate::jolt::vm::rv32i_vm::RV32I;
+use crate::jolt::vm::rv32i_vm::{RV32IInstructionSet, RV32I};
 use common::rv_trace::{ELFInstruction, MemoryState, RVTraceRow, RV32IM};
 
 impl TryFrom<&ELFInstruction> for RV32I {
     type Error = &'static str;
 
-    #[rustfmt::skip] // keep matches pretty
     fn try_from(instruction: &ELFInstruction) -> Result<Self, Self::Error> {
-        match instruction.opcode {
-            RV32IM::ADD  => Ok(ADDInstruction::default().into()),
-            RV32IM::SUB  => Ok(SUBInstruction::default().into()),
-            RV32IM::XOR  => Ok(XORInstruction::default().into()),
-            RV32IM::OR   => Ok(ORInstruction::default().into()),
-            RV32IM::AND  => Ok(ANDInstruction::default().into()),
-            RV32IM::SLL  => Ok(SLLInstruction::default().into()),
-            RV32IM::SRL  => Ok(SRLInstruction::default().into()),
-            RV32IM::SRA  => Ok(SRAInstruction::default().into()),
-            RV32IM::SLT  => Ok(SLTInstruction::default().into()),
-            RV32IM::SLTU => Ok(SLTUInstruction::default().into()),
-
-            RV32IM::ADDI  => Ok(ADDInstruction::default().into()),
-            RV32IM::XORI  => Ok(XORInstruction::default().into()),
-            RV32IM::ORI   => Ok(ORInstruction::default().into()),
-            RV32IM::ANDI  => Ok(ANDInstruction::default().into()),
-            RV32IM::SLLI  => Ok(SLLInstruction::default().into()),
-            RV32IM::SRLI  => Ok(SRLInstruction::default().into()),
-            RV32IM::SRAI  => Ok(SRAInstruction::default().into()),
-            RV32IM::SLTI  => Ok(SLTInstruction::default().into()),
-            RV32IM::SLTIU => Ok(SLTUInstruction::default().into()),
-
-            RV32IM::BEQ  => Ok(BEQInstruction::default().into()),
-            RV32IM::BNE  => Ok(BNEInstruction::default().into()),
-            RV32IM::BLT  => Ok(SLTInstruction::default().into()),
-            RV32IM::BLTU => Ok(SLTUInstruction::default().into()),
-            RV32IM::BGE  => Ok(BGEInstruction::default().into()),
-            RV32IM::BGEU => Ok(BGEUInstruction::default().into()),
-
-            RV32IM::JAL   => Ok(ADDInstruction::default().into()),
-            RV32IM::JALR  => Ok(ADDInstruction::default().into()),
-            RV32IM::AUIPC => Ok(ADDInstruction::default().into()),
-
-            RV32IM::SB => Ok(SBInstruction::default().into()),
-            RV32IM::SH => Ok(SHInstruction::default().into()),
-            RV32IM::SW => Ok(SWInstruction::default().into()),
-
-            RV32IM::LB => Ok(LBInstruction::default().into()),
-            RV32IM::LH => Ok(LHInstruction::default().into()),
-            RV32IM::LW => Ok(SWInstruction::default().into()),
-            RV32IM::LBU => Ok(SBInstruction::default().into()),
-            RV32IM::LHU => Ok(SHInstruction::default().into()),
-
-            RV32IM::MUL => Ok(MULInstruction::default().into()),
-            RV32IM::MULU => Ok(MULUInstruction::default().into()),
-            RV32IM::MULHU => Ok(MULHUInstruction::default().into()),
-
-            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction::default().into()),
-            RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction::default().into()),
-            RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction::default().into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction::default().into()),
-
-            _ => Err("No corresponding RV32I instruction")
-        }
+        rv32i_instruction_from_elf(instruction)
     }
 }
 
 impl TryFrom<&RVTraceRow> for RV32I {
     type Error = &'static str;
 
-    #[rustfmt::skip] // keep matches pretty
     fn try_from(row: &RVTraceRow) -> Result<Self, Self::Error> {
-        match row.instruction.opcode {
-            RV32IM::ADD => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SUB => Ok(SUBInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::XOR => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::OR  => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::AND => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLL => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SRL => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SRA => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::ADDI  => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::XORI  => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::ORI   => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::ANDI  => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::SLLI  => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::SRLI  => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::SRAI  => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::SLTI  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::SLTIU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-
-            RV32IM::BEQ  => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BNE  => Ok(BNEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BGE  => Ok(BGEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::BGEU => Ok(BGEUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::JAL  => Ok(ADDInstruction(row.instruction.address, row.imm_u64()).into()),
-            RV32IM::JALR => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
-            RV32IM::AUIPC => Ok(ADDInstruction(row.instruction.address, row.imm_u64()).into()),
-
-            RV32IM::SB => Ok(SBInstruction(row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SH => Ok(SHInstruction(row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::SW => Ok(SWInstruction(row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::LB => Ok(LBInstruction(load_value(row)).into()),
-            RV32IM::LH => Ok(LHInstruction(load_value(row)).into()),
-            RV32IM::LW => Ok(SWInstruction(load_value(row)).into()),
-            RV32IM::LBU => Ok(SBInstruction(load_value(row)).into()),
-            RV32IM::LHU => Ok(SHInstruction(load_value(row)).into()),
-
-            RV32IM::MUL => Ok(MULInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::MULU => Ok(MULUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::MULHU => Ok(MULHUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction(row.advice_value.unwrap()).into()),
-            RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction(row.register_state.rs1_val.unwrap()).into()),
-            RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction(row.register_state.rs1_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-            RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
-
-            _ => Err("No corresponding RV32I instruction")
-        }
+        rv32i_instruction_from_row(row)
+    }
+}
+
+/// Maps an RV32IM instruction to the (operand-less) RV32I lookup instruction it is proven with.
+#[rustfmt::skip] // keep matches pretty
+pub fn rv32i_instruction_from_elf<I: RV32IInstructionSet>(
+    instruction: &ELFInstruction,
+) -> Result<I, &'static str> {
+    match instruction.opcode {
+        RV32IM::ADD  => Ok(ADDInstruction::default().into()),
+        RV32IM::SUB  => Ok(SUBInstruction::default().into()),
+        RV32IM::XOR  => Ok(XORInstruction::default().into()),
+        RV32IM::OR   => Ok(ORInstruction::default().into()),
+        RV32IM::AND  => Ok(ANDInstruction::default().into()),
+        RV32IM::SLL  => Ok(SLLInstruction::default().into()),
+        RV32IM::SRL  => Ok(SRLInstruction::default().into()),
+        RV32IM::SRA  => Ok(SRAInstruction::default().into()),
+        RV32IM::SLT  => Ok(SLTInstruction::default().into()),
+        RV32IM::SLTU => Ok(SLTUInstruction::default().into()),
+
+        RV32IM::ADDI  => Ok(ADDInstruction::default().into()),
+        RV32IM::XORI  => Ok(XORInstruction::default().into()),
+        RV32IM::ORI   => Ok(ORInstruction::default().into()),
+        RV32IM::ANDI  => Ok(ANDInstruction::default().into()),
+        RV32IM::SLLI  => Ok(SLLInstruction::default().into()),
+        RV32IM::SRLI  => Ok(SRLInstruction::default().into()),
+        RV32IM::SRAI  => Ok(SRAInstruction::default().into()),
+        RV32IM::SLTI  => Ok(SLTInstruction::default().into()),
+        RV32IM::SLTIU => Ok(SLTUInstruction::default().into()),
+
+        RV32IM::BEQ  => Ok(BEQInstruction::default().into()),
+        RV32IM::BNE  => Ok(BNEInstruction::default().into()),
+        RV32IM::BLT  => Ok(SLTInstruction::default().into()),
+        RV32IM::BLTU => Ok(SLTUInstruction::default().into()),
+        RV32IM::BGE  => Ok(BGEInstruction::default().into()),
+        RV32IM::BGEU => Ok(BGEUInstruction::default().into()),
+
+        RV32IM::JAL   => Ok(ADDInstruction::default().into()),
+        RV32IM::JALR  => Ok(ADDInstruction::default().into()),
+        RV32IM::AUIPC => Ok(ADDInstruction::default().into()),
+
+        RV32IM::SB => Ok(SBInstruction::default().into()),
+        RV32IM::SH => Ok(SHInstruction::default().into()),
+        RV32IM::SW => Ok(SWInstruction::default().into()),
+
+        RV32IM::LB => Ok(LBInstruction::default().into()),
+        RV32IM::LH => Ok(LHInstruction::default().into()),
+        RV32IM::LW => Ok(SWInstruction::default().into()),
+        RV32IM::LBU => Ok(SBInstruction::default().into()),
+        RV32IM::LHU => Ok(SHInstruction::default().into()),
+
+        RV32IM::MUL => Ok(MULInstruction::default().into()),
+        RV32IM::MULU => Ok(MULUInstruction::default().into()),
+        RV32IM::MULHU => Ok(MULHUInstruction::default().into()),
+
+        RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction::default().into()),
+        RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction::default().into()),
+        RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction::default().into()),
+        RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction::default().into()),
+        RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction::default().into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction::default().into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction::default().into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction::default().into()),
+
+        RV32IM::ANDN  => Ok(ANDNInstruction::default().into()),
+        RV32IM::XNOR  => Ok(XNORInstruction::default().into()),
+        RV32IM::CLZ   => Ok(CLZInstruction::default().into()),
+        RV32IM::CTZ   => Ok(CTZInstruction::default().into()),
+        RV32IM::CPOP  => Ok(CPOPInstruction::default().into()),
+        RV32IM::ORC_B => Ok(ORCBInstruction::default().into()),
+        RV32IM::REV8  => Ok(REV8Instruction::default().into()),
+
+        _ => Err("No corresponding RV32I instruction")
+    }
+}
+
+/// Maps an RV32IM trace row to the RV32I lookup instruction it is proven with, including its
+/// operands.
+#[rustfmt::skip] // keep matches pretty
+pub fn rv32i_instruction_from_row<I: RV32IInstructionSet>(
+    row: &RVTraceRow,
+) -> Result<I, &'static str> {
+    match row.instruction.opcode {
+        RV32IM::ADD => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SUB => Ok(SUBInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::XOR => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::OR  => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::AND => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SLL => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SRL => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SRA => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+
+        RV32IM::ADDI  => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::XORI  => Ok(XORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::ORI   => Ok(ORInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::ANDI  => Ok(ANDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::SLLI  => Ok(SLLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::SRLI  => Ok(SRLInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::SRAI  => Ok(SRAInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::SLTI  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::SLTIU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+
+        RV32IM::BEQ  => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::BNE  => Ok(BNEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::BLT  => Ok(SLTInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::BLTU => Ok(SLTUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::BGE  => Ok(BGEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::BGEU => Ok(BGEUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+
+        RV32IM::JAL  => Ok(ADDInstruction(row.instruction.address, row.imm_u64()).into()),
+        RV32IM::JALR => Ok(ADDInstruction(row.register_state.rs1_val.unwrap(), row.imm_u64()).into()),
+        RV32IM::AUIPC => Ok(ADDInstruction(row.instruction.address, row.imm_u64()).into()),
+
+        RV32IM::SB => Ok(SBInstruction(row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SH => Ok(SHInstruction(row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::SW => Ok(SWInstruction(row.register_state.rs2_val.unwrap()).into()),
+
+        RV32IM::LB => Ok(LBInstruction(load_value(row)).into()),
+        RV32IM::LH => Ok(LHInstruction(load_value(row)).into()),
+        RV32IM::LW => Ok(SWInstruction(load_value(row)).into()),
+        RV32IM::LBU => Ok(SBInstruction(load_value(row)).into()),
+        RV32IM::LHU => Ok(SHInstruction(load_value(row)).into()),
+
+        RV32IM::MUL => Ok(MULInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::MULU => Ok(MULUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::MULHU => Ok(MULHUInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+
+        RV32IM::VIRTUAL_ADVICE => Ok(ADVICEInstruction(row.advice_value.unwrap()).into()),
+        RV32IM::VIRTUAL_MOVE => Ok(MOVEInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::VIRTUAL_MOVSIGN => Ok(MOVSIGNInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::VIRTUAL_ASSERT_EQ => Ok(BEQInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::VIRTUAL_ASSERT_LTE => Ok(ASSERTLTEInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER => Ok(AssertValidUnsignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER => Ok(AssertValidSignedRemainderInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::VIRTUAL_ASSERT_VALID_DIV0 => Ok(AssertValidDiv0Instruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+
+        RV32IM::ANDN  => Ok(ANDNInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::XNOR  => Ok(XNORInstruction(row.register_state.rs1_val.unwrap(), row.register_state.rs2_val.unwrap()).into()),
+        RV32IM::CLZ   => Ok(CLZInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::CTZ   => Ok(CTZInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::CPOP  => Ok(CPOPInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::ORC_B => Ok(ORCBInstruction(row.register_state.rs1_val.unwrap()).into()),
+        RV32IM::REV8  => Ok(REV8Instruction(row.register_state.rs1_val.unwrap()).into()),
+
+        _ => Err("No corresponding RV32I instruction")
     }
 }
 
@@ -3,9 +3,12 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rand::rngs::StdRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use serde_with::serde_as;
 #[cfg(test)]
 use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::mem::size_of;
 
 use crate::field::JoltField;
 use crate::jolt::instruction::JoltInstructionSet;
@@ -14,6 +17,8 @@ use crate::lasso::memory_checking::{
 };
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use crate::poly::eq_poly::EqPolynomial;
+use crate::subprotocols::grand_product::ConfigurableGrandProduct;
+use crate::utils::json::Hex;
 use common::constants::{BYTES_PER_INSTRUCTION, RAM_START_ADDRESS, REGISTER_COUNT};
 use common::rv_trace::ELFInstruction;
 use common::to_ram_address;
@@ -25,24 +30,36 @@ use crate::{
     poly::{dense_mlpoly::DensePolynomial, identity_poly::IdentityPolynomial},
 };
 
-use super::{JoltPolynomials, JoltTraceStep};
+use super::stage::{JoltStage, StageProver, StageVerifier};
+use super::witness::{memory_counters, par_columns};
+use super::{commit_bytecode_final, commit_read_write_and_final};
+use super::{JoltCommitments, JoltPolynomials, JoltTraceStep};
+use crate::utils::errors::ProofVerifyError;
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct BytecodeStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     /// Read/write addresses for offline memory-checking.
     /// For offline memory-checking, each read is paired with a "virtual" write,
     /// so the read addresses and write addresses are the same.
+    #[serde_as(as = "Hex")]
     pub(crate) a_read_write: T,
     /// Read/write values for offline memory-checking.
     /// For offline memory-checking, each read is paired with a "virtual" write,
     /// so the read values and write values are the same. There are six values
     /// (address, bitflags, rd, rs1, rs2, imm) associated with each memory address.
+    #[serde_as(as = "[Hex; 6]")]
     pub(crate) v_read_write: [T; 6],
     /// Read timestamps for offline memory-checking
+    #[serde_as(as = "Hex")]
     pub(crate) t_read: T,
     /// Final timestamps for offline memory-checking
+    #[serde_as(as = "Hex")]
     pub(crate) t_final: T,
+    #[serde_as(as = "Option<Hex>")]
     a_init_final: VerifierComputedOpening<T>,
+    #[serde_as(as = "Option<[Hex; 6]>")]
     v_init_final: VerifierComputedOpening<[T; 6]>,
 }
 
@@ -118,6 +135,27 @@ pub struct BytecodeRow {
 }
 
 impl BytecodeRow {
+    /// Canonical little-endian encoding of the row, hashed into the
+    /// [`ProgramId`](super::preprocessing::ProgramId).
+    pub(crate) fn canonical_bytes(&self) -> [u8; 56] {
+        let virtual_sequence_remaining = self
+            .virtual_sequence_remaining
+            .map_or(u64::MAX, |remaining| remaining as u64);
+        let mut bytes = [0u8; 56];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip([
+            self.address as u64,
+            self.bitflags,
+            self.rd,
+            self.rs1,
+            self.rs2,
+            self.imm,
+            virtual_sequence_remaining,
+        ]) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
     pub fn new(address: usize, bitflags: u64, rd: u64, rs1: u64, rs2: u64, imm: u64) -> Self {
         Self {
             address,
@@ -210,83 +248,182 @@ pub fn random_bytecode_trace(
     trace
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BytecodePreprocessing<F: JoltField> {
     /// Size of the (padded) bytecode.
     code_size: usize,
-    /// MLE of init/final values. Bytecode is read-only data, so the final memory values are unchanged from
-    /// the initial memory values. There are six values (address, bitflags, rd, rs1, rs2, imm)
-    /// associated with each memory address, so `v_init_final` comprises six polynomials.
-    v_init_final: [DensePolynomial<F>; 6],
-    /// Maps the memory address of each instruction in the bytecode to its "virtual" address.
-    /// See Section 6.1 of the Jolt paper, "Reflecting the program counter". The virtual address
-    /// is the one used to keep track of the next (potentially virtual) instruction to execute.
-    /// Key: (ELF address, virtual sequence index or 0)
-    virtual_address_map: BTreeMap<(usize, usize), usize>,
+    /// Compressed ELF address of each row of the bytecode, starting with the prepended no-op.
+    /// Rows past the end, up to `code_size`, are padding no-ops.
+    addresses: Vec<u32>,
+    /// Index into `distinct_rows` of each row of the bytecode.
+    row_indices: Vec<u32>,
+    /// The (bitflags, rd, rs1, rs2, imm) values of each distinct instruction in the bytecode.
+    /// Jump tables, vtables, and the data words in the text section repeat the same few
+    /// instructions many times, so the bytecode's init/final values (the MLEs of the address
+    /// and these five values, see Section 6.1 of the Jolt paper) are computed from this table
+    /// rather than stored as six dense polynomials of `code_size` field elements.
+    distinct_rows: Vec<[u64; 5]>,
+    /// The virtual address of the last instruction at each compressed ELF address. See Section
+    /// 6.1 of the Jolt paper, "Reflecting the program counter". The virtual address is the one
+    /// used to keep track of the next (potentially virtual) instruction to execute; the
+    /// instructions of a virtual sequence occupy consecutive virtual addresses, ending with the
+    /// one with no instructions remaining.
+    last_virtual_addresses: Vec<u32>,
+    _field: PhantomData<F>,
 }
 
 impl<F: JoltField> BytecodePreprocessing<F> {
     #[tracing::instrument(skip_all, name = "BytecodePreprocessing::preprocess")]
-    pub fn preprocess(mut bytecode: Vec<BytecodeRow>) -> Self {
-        let mut virtual_address_map = BTreeMap::new();
-        let mut virtual_address = 1; // Account for no-op instruction prepended to bytecode
-        for instruction in bytecode.iter_mut() {
+    pub fn preprocess(bytecode: Vec<BytecodeRow>) -> Self {
+        // Account for no-op instruction prepended to bytecode
+        let num_rows = bytecode.len() + 1;
+        assert!(num_rows <= u32::MAX as usize);
+
+        // Bytecode: Prepend a single no-op instruction
+        let mut addresses = Vec::with_capacity(num_rows);
+        let mut row_indices = Vec::with_capacity(num_rows);
+        let mut distinct_rows = vec![[0; 5]];
+        let mut distinct_indices = HashMap::from([([0; 5], 0)]);
+        let mut last_virtual_addresses = vec![0];
+        addresses.push(0);
+        row_indices.push(0);
+
+        // (address, remaining) of the last instruction, if it's in an unfinished virtual sequence
+        let mut sequence: Option<(usize, usize)> = None;
+        for instruction in bytecode {
             assert!(instruction.address >= RAM_START_ADDRESS as usize);
             assert!(instruction.address % BYTES_PER_INSTRUCTION == 0);
             // Compress instruction address for more efficient commitment:
-            instruction.address =
+            let address =
                 1 + (instruction.address - RAM_START_ADDRESS as usize) / BYTES_PER_INSTRUCTION;
-            assert_eq!(
-                virtual_address_map.insert(
-                    (
-                        instruction.address,
-                        instruction.virtual_sequence_remaining.unwrap_or(0)
-                    ),
-                    virtual_address
-                ),
-                None
-            );
-            virtual_address += 1;
+            let remaining = instruction.virtual_sequence_remaining.unwrap_or(0);
+            if let Some(previous) = sequence {
+                assert_eq!(
+                    previous,
+                    (address, remaining + 1),
+                    "broken virtual sequence"
+                );
+            }
+            sequence = (remaining > 0).then_some((address, remaining));
+
+            if remaining == 0 {
+                if address >= last_virtual_addresses.len() {
+                    last_virtual_addresses.resize(address + 1, 0);
+                }
+                assert_eq!(last_virtual_addresses[address], 0, "duplicate bytecode row");
+                last_virtual_addresses[address] = addresses.len() as u32;
+            }
+            addresses.push(address as u32);
+
+            let values = [
+                instruction.bitflags,
+                instruction.rd,
+                instruction.rs1,
+                instruction.rs2,
+                instruction.imm,
+            ];
+            let index = *distinct_indices.entry(values).or_insert_with(|| {
+                distinct_rows.push(values);
+                distinct_rows.len() as u32 - 1
+            });
+            row_indices.push(index);
         }
+        assert_eq!(sequence, None, "unterminated virtual sequence");
 
-        // Bytecode: Prepend a single no-op instruction
-        bytecode.insert(0, BytecodeRow::no_op(0));
-        assert_eq!(virtual_address_map.insert((0, 0), 0), None);
+        Self {
+            // Bytecode: Pad to nearest power of 2
+            code_size: num_rows.next_power_of_two(),
+            addresses,
+            row_indices,
+            distinct_rows,
+            last_virtual_addresses,
+            _field: PhantomData,
+        }
+    }
 
-        // Bytecode: Pad to nearest power of 2
-        let code_size = bytecode.len().next_power_of_two();
-        bytecode.resize(code_size, BytecodeRow::no_op(0));
+    /// Size of the (padded) bytecode, i.e. of the bytecode's init/final polynomials.
+    pub fn code_size(&self) -> usize {
+        self.code_size
+    }
 
-        let mut address = vec![];
-        let mut bitflags = vec![];
-        let mut rd = vec![];
-        let mut rs1 = vec![];
-        let mut rs2 = vec![];
-        let mut imm = vec![];
+    /// Number of rows of the bytecode, i.e. of instructions after expanding virtual sequences,
+    /// including the no-op prepended by [`Self::preprocess`].
+    pub fn num_rows(&self) -> usize {
+        self.addresses.len()
+    }
 
-        for instruction in bytecode {
-            address.push(F::from_u64(instruction.address as u64).unwrap());
-            bitflags.push(F::from_u64(instruction.bitflags).unwrap());
-            rd.push(F::from_u64(instruction.rd).unwrap());
-            rs1.push(F::from_u64(instruction.rs1).unwrap());
-            rs2.push(F::from_u64(instruction.rs2).unwrap());
-            imm.push(F::from_u64(instruction.imm).unwrap());
+    /// Number of distinct instructions in the bytecode, ignoring their addresses.
+    pub fn num_distinct_rows(&self) -> usize {
+        self.distinct_rows.len()
+    }
+
+    /// Approximate number of bytes of memory used by this preprocessing.
+    pub fn size_in_bytes(&self) -> usize {
+        size_of::<u32>()
+            * (self.addresses.len() + self.row_indices.len() + self.last_virtual_addresses.len())
+            + size_of::<[u64; 5]>() * self.distinct_rows.len()
+    }
+
+    /// The virtual address of the instruction at compressed ELF address `address` with
+    /// `remaining` instructions after it in its virtual sequence, if there is one.
+    fn virtual_address(&self, address: usize, remaining: usize) -> Option<usize> {
+        let last = *self.last_virtual_addresses.get(address)? as usize;
+        let virtual_address = last.checked_sub(remaining)?;
+        (self.addresses[virtual_address] as usize == address).then_some(virtual_address)
+    }
+
+    /// The (address, bitflags, rd, rs1, rs2, imm) values of the bytecode at virtual address `i`.
+    #[cfg(test)]
+    fn row(&self, i: usize) -> [u64; 6] {
+        match self.addresses.get(i) {
+            Some(&address) => {
+                let [bitflags, rd, rs1, rs2, imm] =
+                    self.distinct_rows[self.row_indices[i] as usize];
+                [address as u64, bitflags, rd, rs1, rs2, imm]
+            }
+            None => [0; 6],
         }
+    }
 
-        let v_init_final = [
-            DensePolynomial::new(address),
-            DensePolynomial::new(bitflags),
-            DensePolynomial::new(rd),
-            DensePolynomial::new(rs1),
-            DensePolynomial::new(rs2),
-            DensePolynomial::new(imm),
-        ];
+    /// The init/final values of the distinct instructions, as field elements.
+    fn distinct_values(&self) -> Vec<[F; 5]> {
+        self.distinct_rows
+            .par_iter()
+            .map(|values| values.map(|value| F::from_u64(value).unwrap()))
+            .collect()
+    }
 
-        Self {
-            v_init_final,
-            code_size,
-            virtual_address_map,
+    /// Evaluates the MLEs of the init/final values (address, bitflags, rd, rs1, rs2, imm) at the
+    /// point whose `EqPolynomial::evals` are `chis`. Rather than a dot product per value, each row
+    /// adds its `chi` to the weight of its distinct instruction, whose values are then only
+    /// multiplied once. The padding no-ops are zero, so they don't contribute.
+    fn evaluate_init_final(&self, chis: &[F]) -> [F; 6] {
+        assert_eq!(chis.len(), self.code_size);
+        let (address, weights) = rayon::join(
+            || {
+                self.addresses
+                    .par_iter()
+                    .zip(chis.par_iter())
+                    .map(|(&address, chi)| *chi * F::from_u64(address as u64).unwrap())
+                    .sum::<F>()
+            },
+            || {
+                let mut weights = vec![F::zero(); self.distinct_rows.len()];
+                for (&index, chi) in self.row_indices.iter().zip(chis) {
+                    weights[index as usize] += *chi;
+                }
+                weights
+            },
+        );
+
+        let mut evals = [F::zero(); 6];
+        evals[0] = address;
+        for (weight, values) in weights.iter().zip(self.distinct_values()) {
+            for (eval, value) in evals[1..].iter_mut().zip(values) {
+                *eval += *weight * value;
+            }
         }
+        evals
     }
 }
 
@@ -298,11 +435,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BytecodeProof<F, PCS> {
     ) -> BytecodePolynomials<F> {
         let num_ops = trace.len();
 
-        let mut a_read_write_usize: Vec<usize> = vec![0; num_ops];
-        let mut read_cts: Vec<usize> = vec![0; num_ops];
-        let mut final_cts: Vec<usize> = vec![0; preprocessing.code_size];
-
-        for (step_index, step) in trace.iter_mut().enumerate() {
+        trace.par_iter_mut().for_each(|step| {
             if !step.bytecode_row.address.is_zero() {
                 assert!(step.bytecode_row.address >= RAM_START_ADDRESS as usize);
                 assert!(step.bytecode_row.address % BYTES_PER_INSTRUCTION == 0);
@@ -311,46 +444,46 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BytecodeProof<F, PCS> {
                     + (step.bytecode_row.address - RAM_START_ADDRESS as usize)
                         / BYTES_PER_INSTRUCTION;
             }
-
-            let virtual_address = preprocessing
-                .virtual_address_map
-                .get(&(
-                    step.bytecode_row.address,
-                    step.bytecode_row.virtual_sequence_remaining.unwrap_or(0),
-                ))
-                .unwrap();
-            a_read_write_usize[step_index] = *virtual_address;
-            let counter = final_cts[*virtual_address];
-            read_cts[step_index] = counter;
-            final_cts[*virtual_address] = counter + 1;
-        }
+        });
+
+        let a_read_write_usize: Vec<usize> = trace
+            .par_iter()
+            .map(|step| {
+                preprocessing
+                    .virtual_address(
+                        step.bytecode_row.address,
+                        step.bytecode_row.virtual_sequence_remaining.unwrap_or(0),
+                    )
+                    .unwrap()
+            })
+            .collect();
+        let (read_cts, final_cts) = memory_counters(num_ops, preprocessing.code_size, |j| {
+            Some(a_read_write_usize[j])
+        });
 
         let a_read_write = DensePolynomial::from_usize(&a_read_write_usize);
 
-        let mut address = vec![];
-        let mut bitflags = vec![];
-        let mut rd = vec![];
-        let mut rs1 = vec![];
-        let mut rs2 = vec![];
-        let mut imm = vec![];
-
-        for step in trace {
-            address.push(F::from_u64(step.bytecode_row.address as u64).unwrap());
-            bitflags.push(F::from_u64(step.bytecode_row.bitflags).unwrap());
-            rd.push(F::from_u64(step.bytecode_row.rd).unwrap());
-            rs1.push(F::from_u64(step.bytecode_row.rs1).unwrap());
-            rs2.push(F::from_u64(step.bytecode_row.rs2).unwrap());
-            imm.push(F::from_u64(step.bytecode_row.imm).unwrap());
-        }
+        let v_read_write: [DensePolynomial<F>; 6] = par_columns(num_ops, 6, |steps, columns| {
+            for step in &trace[steps] {
+                let row = &step.bytecode_row;
+                for (column, value) in columns.iter_mut().zip([
+                    row.address as u64,
+                    row.bitflags,
+                    row.rd,
+                    row.rs1,
+                    row.rs2,
+                    row.imm,
+                ]) {
+                    column.push(F::from_u64(value).unwrap());
+                }
+            }
+        })
+        .into_iter()
+        .map(DensePolynomial::new)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
 
-        let v_read_write = [
-            DensePolynomial::new(address),
-            DensePolynomial::new(bitflags),
-            DensePolynomial::new(rd),
-            DensePolynomial::new(rs1),
-            DensePolynomial::new(rs2),
-            DensePolynomial::new(imm),
-        ];
         let t_read: DensePolynomial<F> = DensePolynomial::from_usize(&read_cts);
         let t_final: DensePolynomial<F> = DensePolynomial::from_usize(&final_cts);
 
@@ -361,30 +494,8 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BytecodeProof<F, PCS> {
 
         #[cfg(test)]
         for (a, t) in t_final.Z.iter().enumerate() {
-            init_tuples.insert((
-                a as u64,
-                [
-                    preprocessing.v_init_final[0][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[1][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[2][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[3][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[4][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[5][a].to_u64().unwrap(),
-                ],
-                0,
-            ));
-            final_tuples.insert((
-                a as u64,
-                [
-                    preprocessing.v_init_final[0][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[1][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[2][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[3][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[4][a].to_u64().unwrap(),
-                    preprocessing.v_init_final[5][a].to_u64().unwrap(),
-                ],
-                t.to_u64().unwrap(),
-            ));
+            init_tuples.insert((a as u64, preprocessing.row(a), 0));
+            final_tuples.insert((a as u64, preprocessing.row(a), t.to_u64().unwrap()));
         }
 
         #[cfg(test)]
@@ -474,9 +585,13 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
+    type ReadWriteGrandProduct = ConfigurableGrandProduct<F>;
+    type InitFinalGrandProduct = ConfigurableGrandProduct<F>;
+
     type Polynomials = BytecodePolynomials<F>;
     type Openings = BytecodeOpenings<F>;
     type Commitments = BytecodeCommitments<PCS>;
+    type ExogenousOpenings = NoExogenousOpenings;
     type Preprocessing = BytecodePreprocessing<F>;
 
     // [virtual_address, elf_address, opcode, rd, rs1, rs2, imm, t]
@@ -501,7 +616,7 @@ where
         tau: &F,
     ) -> (Vec<Vec<F>>, Vec<Vec<F>>) {
         let num_ops = polynomials.a_read_write.len();
-        let bytecode_size = preprocessing.v_init_final[0].len();
+        let bytecode_size = preprocessing.code_size;
 
         let read_leaves = (0..num_ops)
             .into_par_iter()
@@ -523,26 +638,6 @@ where
             })
             .collect();
 
-        let init_leaves = (0..bytecode_size)
-            .into_par_iter()
-            .map(|i| {
-                Self::fingerprint(
-                    &[
-                        F::from_u64(i as u64).unwrap(),
-                        preprocessing.v_init_final[0][i],
-                        preprocessing.v_init_final[1][i],
-                        preprocessing.v_init_final[2][i],
-                        preprocessing.v_init_final[3][i],
-                        preprocessing.v_init_final[4][i],
-                        preprocessing.v_init_final[5][i],
-                        F::zero(),
-                    ],
-                    gamma,
-                    tau,
-                )
-            })
-            .collect();
-
         // TODO(moodlezoup): Compute write_leaves from read_leaves
         let write_leaves = (0..num_ops)
             .into_par_iter()
@@ -565,25 +660,34 @@ where
             .collect();
 
         // TODO(moodlezoup): Compute final_leaves from init_leaves
-        let final_leaves = (0..bytecode_size)
+        let distinct_values = preprocessing.distinct_values();
+        let (init_leaves, final_leaves): (Vec<F>, Vec<F>) = (0..bytecode_size)
             .into_par_iter()
             .map(|i| {
-                Self::fingerprint(
-                    &[
-                        F::from_u64(i as u64).unwrap(),
-                        preprocessing.v_init_final[0][i],
-                        preprocessing.v_init_final[1][i],
-                        preprocessing.v_init_final[2][i],
-                        preprocessing.v_init_final[3][i],
-                        preprocessing.v_init_final[4][i],
-                        preprocessing.v_init_final[5][i],
-                        polynomials.t_final[i],
-                    ],
-                    gamma,
-                    tau,
-                )
+                let (address, [bitflags, rd, rs1, rs2, imm]) = match preprocessing.addresses.get(i)
+                {
+                    Some(&address) => (
+                        F::from_u64(address as u64).unwrap(),
+                        distinct_values[preprocessing.row_indices[i] as usize],
+                    ),
+                    // Padding no-op
+                    None => (F::zero(), [F::zero(); 5]),
+                };
+                let mut tuple = [
+                    F::from_u64(i as u64).unwrap(),
+                    address,
+                    bitflags,
+                    rd,
+                    rs1,
+                    rs2,
+                    imm,
+                    F::zero(),
+                ];
+                let init_leaf = Self::fingerprint(&tuple, gamma, tau);
+                tuple[7] = polynomials.t_final[i];
+                (init_leaf, Self::fingerprint(&tuple, gamma, tau))
             })
-            .collect();
+            .unzip();
 
         (
             vec![read_leaves, write_leaves],
@@ -596,6 +700,61 @@ where
     }
 }
 
+impl<F, PCS> JoltStage<F, PCS> for BytecodeProof<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    type Preprocessing = BytecodePreprocessing<F>;
+    const NAME: &'static str = "Bytecode";
+
+    fn commit(
+        polynomials: &JoltPolynomials<F>,
+        commitments: &mut JoltCommitments<PCS>,
+        setup: &PCS::Setup,
+    ) {
+        let polynomials = &polynomials.bytecode;
+        let t_final = commit_read_write_and_final::<PCS, _>(
+            polynomials.read_write_values(),
+            commitments.bytecode.read_write_values_mut(),
+            &[&polynomials.t_final],
+            || commit_bytecode_final::<PCS>(polynomials, setup),
+            setup,
+        );
+        commitments.bytecode.t_final = t_final;
+    }
+
+    #[tracing::instrument(skip_all, name = "BytecodeProof::prove")]
+    fn prove(preprocessing: &BytecodePreprocessing<F>, prover: &mut StageProver<F, PCS>) -> Self {
+        Self::prove_memory_checking(
+            prover.generators,
+            preprocessing,
+            &prover.polynomials.bytecode,
+            prover.polynomials,
+            prover.opening_accumulator,
+            prover.transcript,
+            prover.grand_product,
+        )
+    }
+
+    #[tracing::instrument(skip_all, name = "BytecodeProof::verify")]
+    fn verify(
+        self,
+        preprocessing: &BytecodePreprocessing<F>,
+        verifier: &mut StageVerifier<F, PCS>,
+    ) -> Result<(), ProofVerifyError> {
+        Self::verify_memory_checking(
+            preprocessing,
+            verifier.generators,
+            self,
+            &verifier.commitments.bytecode,
+            verifier.commitments,
+            verifier.opening_accumulator,
+            verifier.transcript,
+        )
+    }
+}
+
 impl<F, PCS> MemoryCheckingVerifier<F, PCS> for BytecodeProof<F, PCS>
 where
     F: JoltField,
@@ -611,15 +770,7 @@ where
             Some(IdentityPolynomial::new(r_init_final.len()).evaluate(r_init_final));
 
         let chis = EqPolynomial::evals(r_init_final);
-        openings.v_init_final = Some(
-            preprocessing
-                .v_init_final
-                .par_iter()
-                .map(|poly| poly.evaluate_at_chi(&chis))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        );
+        openings.v_init_final = Some(preprocessing.evaluate_init_final(&chis));
     }
 
     fn read_tuples(
@@ -729,6 +880,56 @@ mod tests {
         BytecodeOpenings::<Fr>::test_ordering_consistency(&preprocessing);
     }
 
+    #[test]
+    fn bytecode_preprocessing_deduplicates_rows() {
+        let mut program = vec![
+            BytecodeRow::new(to_ram_address(0), 2u64, 2u64, 2u64, 2u64, 2u64),
+            BytecodeRow::new(to_ram_address(1), 4u64, 4u64, 4u64, 4u64, 4u64),
+            BytecodeRow::new(to_ram_address(2), 2u64, 2u64, 2u64, 2u64, 2u64),
+            BytecodeRow::new(to_ram_address(3), 4u64, 4u64, 4u64, 4u64, 4u64),
+        ];
+        // A virtual sequence of two instructions
+        for remaining in [1, 0] {
+            let mut row = BytecodeRow::new(to_ram_address(4), 8u64, 8u64, 8u64, 8u64, 8u64);
+            row.virtual_sequence_remaining = Some(remaining);
+            program.push(row);
+        }
+        let preprocessing = BytecodePreprocessing::<Fr>::preprocess(program);
+        assert_eq!(preprocessing.num_rows(), 7);
+        // The prepended no-op and three distinct instructions
+        assert_eq!(preprocessing.num_distinct_rows(), 4);
+        assert_eq!(preprocessing.code_size(), 8);
+        assert_eq!(preprocessing.row(3), [3, 2, 2, 2, 2, 2]);
+        assert_eq!(preprocessing.row(7), [0; 6]);
+
+        // Compressed addresses start at 1
+        assert_eq!(preprocessing.virtual_address(0, 0), Some(0));
+        assert_eq!(preprocessing.virtual_address(3, 0), Some(3));
+        assert_eq!(preprocessing.virtual_address(5, 1), Some(5));
+        assert_eq!(preprocessing.virtual_address(5, 0), Some(6));
+        assert_eq!(preprocessing.virtual_address(5, 2), None);
+        assert_eq!(preprocessing.virtual_address(4, 1), None);
+        assert_eq!(preprocessing.virtual_address(6, 0), None);
+
+        let r = [Fr::from(3u64), Fr::from(5u64), Fr::from(7u64)];
+        let evals = preprocessing.evaluate_init_final(&EqPolynomial::evals(&r));
+        for (k, eval) in evals.into_iter().enumerate() {
+            let column = (0..8).map(|i| Fr::from(preprocessing.row(i)[k])).collect();
+            assert_eq!(DensePolynomial::new(column).evaluate(&r), eval);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "broken virtual sequence")]
+    fn bytecode_preprocessing_broken_virtual_sequence() {
+        let mut program = vec![
+            BytecodeRow::new(to_ram_address(0), 2u64, 2u64, 2u64, 2u64, 2u64),
+            BytecodeRow::new(to_ram_address(1), 4u64, 4u64, 4u64, 4u64, 4u64),
+        ];
+        program[0].virtual_sequence_remaining = Some(1);
+        BytecodePreprocessing::<Fr>::preprocess(program);
+    }
+
     #[test]
     fn bytecode_poly_leaf_construction() {
         let program = vec![
@@ -0,0 +1,61 @@
+//! Binds the point at which an execution was halted for exceeding its cycle limit.
+//!
+//! An execution halted at `max_cycles` (see [`JoltDevice::exceeded_max_cycles`]) must have
+//! executed exactly `max_cycles` RISC-V instructions. A virtual sequence is a single
+//! instruction spread over several steps of the trace, and the trace is padded with no-ops, so
+//! its length alone doesn't bound the number of instructions. Instead, the R1CS counts down the
+//! `RemainingCycles` from each step to the end of the trace, skipping the steps in the middle of
+//! a virtual sequence and the padding (which the verifier knows from the trace length, see
+//! `RealStep`), and the count at the first step is opened with `max_cycles` as its claim.
+
+use common::rv_trace::JoltDevice;
+
+use super::{JoltCommitments, JoltPolynomials};
+use crate::field::JoltField;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::dense_mlpoly::DensePolynomial;
+use crate::poly::eq_poly::EqPolynomial;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::utils::transcript::ProofTranscript;
+
+/// Opens the first step's `RemainingCycles`, if the execution was halted at its cycle limit.
+pub(crate) fn prove_halt<F: JoltField>(
+    polynomials: &JoltPolynomials<F>,
+    program_io: &JoltDevice,
+    opening_accumulator: &mut ProverOpeningAccumulator<F>,
+    transcript: &mut ProofTranscript,
+) {
+    if program_io.exceeded_max_cycles.is_none() {
+        return;
+    }
+    let remaining_cycles = &polynomials.r1cs.remaining_cycles;
+    let opening_point = vec![F::zero(); remaining_cycles.get_num_vars()];
+    let eq_poly = DensePolynomial::new(EqPolynomial::evals(&opening_point));
+    opening_accumulator.append(
+        &[remaining_cycles],
+        eq_poly,
+        opening_point,
+        &[&remaining_cycles[0]],
+        transcript,
+    );
+}
+
+/// The verifier side of [`prove_halt`].
+pub(crate) fn verify_halt<F: JoltField, PCS: CommitmentScheme<Field = F>>(
+    commitments: &JoltCommitments<PCS>,
+    program_io: &JoltDevice,
+    trace_length: usize,
+    opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
+    transcript: &mut ProofTranscript,
+) {
+    let Some(max_cycles) = program_io.exceeded_max_cycles else {
+        return;
+    };
+    let num_vars = trace_length.next_power_of_two().ilog2() as usize;
+    opening_accumulator.append(
+        &[&commitments.r1cs.remaining_cycles],
+        vec![F::zero(); num_vars],
+        &[&F::from_u64(max_cycles).unwrap()],
+        transcript,
+    );
+}
@@ -1,9 +1,13 @@
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
-use crate::subprotocols::grand_product::{BatchedGrandProduct, ToggledBatchedGrandProduct};
+use crate::subprotocols::grand_product::{
+    BatchedGrandProduct, GrandProductConfig, ToggledBatchedGrandProduct,
+};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::{interleave, Itertools};
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::marker::PhantomData;
 use tracing::trace_span;
 
@@ -27,41 +31,56 @@ use crate::{
     subprotocols::sumcheck::SumcheckInstanceProof,
     utils::{
         errors::ProofVerifyError,
+        json::Hex,
         math::Math,
         transcript::{AppendToTranscript, ProofTranscript},
     },
 };
 
+use super::stage::{JoltStage, StageProver, StageVerifier};
+use super::witness::{memory_counters, par_columns};
+use super::{commit_instruction_lookups_final, commit_read_write_and_final};
 use super::{JoltCommitments, JoltPolynomials, JoltTraceStep};
 
-#[derive(Debug, Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Debug, Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct InstructionLookupStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     /// `C`-sized vector of polynomials/commitments/openings corresponding to the
     /// indices at which subtables are queried.
+    #[serde_as(as = "Vec<Hex>")]
     pub(crate) dim: Vec<T>,
     /// `num_memories`-sized vector of polynomials/commitments/openings corresponding to
     /// the read access counts for each memory.
+    #[serde_as(as = "Vec<Hex>")]
     read_cts: Vec<T>,
     /// `num_memories`-sized vector of polynomials/commitments/openings corresponding to
     /// the final access counts for each memory.
+    #[serde_as(as = "Vec<Hex>")]
     pub(crate) final_cts: Vec<T>,
     /// `num_memories`-sized vector of polynomials/commitments/openings corresponding to
     /// the values read from each memory.
+    #[serde_as(as = "Vec<Hex>")]
     pub(crate) E_polys: Vec<T>,
     /// `NUM_INSTRUCTIONS`-sized vector of polynomials/commitments/openings corresponding
     /// to the indicator bitvectors designating which lookup to perform at each step of
     /// the execution trace.
+    #[serde_as(as = "Vec<Hex>")]
     pub(crate) instruction_flags: Vec<T>,
     /// The polynomial/commitment/opening corresponding to the lookup output for each
     /// step of the execution trace.
+    #[serde_as(as = "Hex")]
     pub(crate) lookup_outputs: T,
 
     /// Hack: This is only populated for `InstructionLookupPolynomials`, where
     /// the instruction flags are kept in u64 representation for efficient conversion
     /// to memory flags.
+    #[serde(skip)]
     instruction_flag_bitvectors: Option<Vec<Vec<u64>>>,
 
+    #[serde_as(as = "Option<Hex>")]
     a_init_final: VerifierComputedOpening<T>,
+    #[serde_as(as = "Option<Vec<Hex>>")]
     v_init_final: VerifierComputedOpening<Vec<T>>,
 }
 
@@ -140,17 +159,22 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T>
     }
 }
 
-#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 /// Polynomial openings associated with the "primary sumcheck" of Jolt instruction lookups.
 struct PrimarySumcheckOpenings<F>
 where
     F: JoltField,
 {
     /// Evaluations of the E_i polynomials at the opening point. Vector is of length NUM_MEMORIES.
+    #[serde_as(as = "Vec<Hex>")]
     E_poly_openings: Vec<F>,
     /// Evaluations of the flag polynomials at the opening point. Vector is of length NUM_INSTRUCTIONS.
+    #[serde_as(as = "Vec<Hex>")]
     flag_openings: Vec<F>,
     /// Evaluation of the lookup_outputs polynomial at the opening point.
+    #[serde_as(as = "Hex")]
     lookup_outputs_opening: F,
 }
 
@@ -167,6 +191,7 @@ where
     type Polynomials = InstructionLookupPolynomials<F>;
     type Openings = InstructionLookupOpenings<F>;
     type Commitments = InstructionLookupCommitments<PCS>;
+    type ExogenousOpenings = NoExogenousOpenings;
 
     type Preprocessing = InstructionLookupsPreprocessing<C, F>;
 
@@ -451,7 +476,8 @@ where
 }
 
 /// Proof of instruction lookups for a single Jolt program execution.
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct InstructionLookupsProof<
     const C: usize,
     const M: usize,
@@ -465,13 +491,16 @@ pub struct InstructionLookupsProof<
     Subtables: JoltSubtableSet<F>,
     InstructionSet: JoltInstructionSet,
 {
+    #[serde(skip)]
     _instructions: PhantomData<InstructionSet>,
+    #[serde(skip)]
     _subtables: PhantomData<Subtables>,
     primary_sumcheck: PrimarySumcheck<F>,
     memory_checking: MemoryCheckingProof<F, PCS, InstructionLookupOpenings<F>, NoExogenousOpenings>,
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct PrimarySumcheck<F: JoltField> {
     sumcheck_proof: SumcheckInstanceProof<F>,
     num_rounds: usize,
@@ -479,7 +508,7 @@ pub struct PrimarySumcheck<F: JoltField> {
     // opening_proof: PCS::BatchedProof,
 }
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct InstructionLookupsPreprocessing<const C: usize, F: JoltField> {
     subtable_to_memory_indices: Vec<Vec<usize>>, // Vec<Range<usize>>?
     instruction_to_memory_indices: Vec<Vec<usize>>,
@@ -560,6 +589,65 @@ impl<const C: usize, F: JoltField> InstructionLookupsPreprocessing<C, F> {
     }
 }
 
+impl<F, PCS, InstructionSet, Subtables, const C: usize, const M: usize> JoltStage<F, PCS>
+    for InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+    Subtables: JoltSubtableSet<F>,
+{
+    type Preprocessing = InstructionLookupsPreprocessing<C, F>;
+    const NAME: &'static str = "Instruction lookups";
+
+    fn commit(
+        polynomials: &JoltPolynomials<F>,
+        commitments: &mut JoltCommitments<PCS>,
+        setup: &PCS::Setup,
+    ) {
+        let polynomials = &polynomials.instruction_lookups;
+        let final_polys: Vec<_> = polynomials.final_cts.iter().collect();
+        let final_cts = commit_read_write_and_final::<PCS, _>(
+            polynomials.read_write_values(),
+            commitments.instruction_lookups.read_write_values_mut(),
+            &final_polys,
+            || commit_instruction_lookups_final::<PCS>(polynomials, setup),
+            setup,
+        );
+        commitments.instruction_lookups.final_cts = final_cts;
+    }
+
+    fn prove(
+        preprocessing: &InstructionLookupsPreprocessing<C, F>,
+        prover: &mut StageProver<F, PCS>,
+    ) -> Self {
+        Self::prove(
+            prover.generators,
+            prover.polynomials,
+            preprocessing,
+            prover.opening_accumulator,
+            prover.transcript,
+            prover.grand_product,
+        )
+    }
+
+    #[tracing::instrument(skip_all, name = "InstructionLookupsProof::verify")]
+    fn verify(
+        self,
+        preprocessing: &InstructionLookupsPreprocessing<C, F>,
+        verifier: &mut StageVerifier<F, PCS>,
+    ) -> Result<(), ProofVerifyError> {
+        Self::verify(
+            preprocessing,
+            verifier.generators,
+            self,
+            verifier.commitments,
+            verifier.opening_accumulator,
+            verifier.transcript,
+        )
+    }
+}
+
 impl<F, PCS, InstructionSet, Subtables, const C: usize, const M: usize>
     InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables>
 where
@@ -578,6 +666,7 @@ where
         preprocessing: &InstructionLookupsPreprocessing<C, F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
+        grand_product: GrandProductConfig,
     ) -> InstructionLookupsProof<C, M, F, PCS, InstructionSet, Subtables> {
         transcript.append_protocol_name(Self::protocol_name());
 
@@ -644,6 +733,7 @@ where
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product,
         );
 
         InstructionLookupsProof {
@@ -742,26 +832,25 @@ where
                 let subtable_index = preprocessing.memory_to_subtable_index[memory_index];
                 let access_sequence: &Vec<usize> = &subtable_lookup_indices[dim_index];
 
-                let mut final_cts_i = vec![0usize; M];
-                let mut read_cts_i = vec![0usize; m];
-                let mut subtable_lookups = vec![F::zero(); m];
-
-                for (j, op) in ops.iter().enumerate() {
-                    if let Some(instr) = &op.instruction_lookup {
-                        let memories_used = &preprocessing.instruction_to_memory_indices
-                            [InstructionSet::enum_index(instr)];
-                        if memories_used.contains(&memory_index) {
-                            let memory_address = access_sequence[j];
-                            debug_assert!(memory_address < M);
-
-                            let counter = final_cts_i[memory_address];
-                            read_cts_i[j] = counter;
-                            final_cts_i[memory_address] = counter + 1;
-                            subtable_lookups[j] = preprocessing.materialized_subtables
-                                [subtable_index][memory_address];
+                let memory_address = |j: usize| {
+                    let instr = ops.get(j)?.instruction_lookup.as_ref()?;
+                    let memories_used = &preprocessing.instruction_to_memory_indices
+                        [InstructionSet::enum_index(instr)];
+                    memories_used
+                        .contains(&memory_index)
+                        .then(|| access_sequence[j])
+                };
+                let (read_cts_i, final_cts_i) = memory_counters(m, M, memory_address);
+                let subtable_lookups: Vec<F> = (0..m)
+                    .into_par_iter()
+                    .map(|j| match memory_address(j) {
+                        Some(address) => {
+                            debug_assert!(address < M);
+                            preprocessing.materialized_subtables[subtable_index][address]
                         }
-                    }
-                }
+                        None => F::zero(),
+                    })
+                    .collect();
 
                 (
                     DensePolynomial::from_usize(&read_cts_i),
@@ -794,13 +883,17 @@ where
             })
             .collect();
 
-        let mut instruction_flag_bitvectors: Vec<Vec<u64>> =
-            vec![vec![0u64; m]; Self::NUM_INSTRUCTIONS];
-        for (j, op) in ops.iter().enumerate() {
-            if let Some(instr) = &op.instruction_lookup {
-                instruction_flag_bitvectors[InstructionSet::enum_index(instr)][j] = 1;
-            }
-        }
+        let instruction_flag_bitvectors: Vec<Vec<u64>> =
+            par_columns(m, Self::NUM_INSTRUCTIONS, |steps, columns| {
+                for column in columns.iter_mut() {
+                    column.resize(steps.len(), 0);
+                }
+                for j in steps.clone() {
+                    if let Some(instr) = ops.get(j).and_then(|op| op.instruction_lookup.as_ref()) {
+                        columns[InstructionSet::enum_index(instr)][j - steps.start] = 1;
+                    }
+                }
+            });
 
         let instruction_flag_polys: Vec<DensePolynomial<F>> = instruction_flag_bitvectors
             .par_iter()
@@ -1163,7 +1256,7 @@ where
         let m = ops.len().next_power_of_two();
         let log_M = M.log_2();
         let chunked_indices: Vec<Vec<usize>> = ops
-            .iter()
+            .par_iter()
             .map(|op| {
                 if let Some(instr) = &op.instruction_lookup {
                     instr.to_indices(C, log_M)
@@ -1173,14 +1266,15 @@ where
             })
             .collect();
 
-        let mut subtable_lookup_indices: Vec<Vec<usize>> = Vec::with_capacity(C);
-        for i in 0..C {
-            let mut access_sequence: Vec<usize> =
-                chunked_indices.iter().map(|chunks| chunks[i]).collect();
-            access_sequence.resize(m, 0);
-            subtable_lookup_indices.push(access_sequence);
-        }
-        subtable_lookup_indices
+        (0..C)
+            .into_par_iter()
+            .map(|i| {
+                let mut access_sequence: Vec<usize> =
+                    chunked_indices.par_iter().map(|chunks| chunks[i]).collect();
+                access_sequence.resize(m, 0);
+                access_sequence
+            })
+            .collect()
     }
 
     /// Computes the shape of all commitments.
@@ -5,46 +5,61 @@ use crate::field::JoltField;
 use crate::poly::opening_proof::{
     ProverOpeningAccumulator, ReducedOpeningProof, VerifierOpeningAccumulator,
 };
+use crate::r1cs::builder::CombinedUniformBuilder;
 use crate::r1cs::constraints::R1CSConstraints;
-use crate::r1cs::spartan::{self, UniformSpartanProof};
+use crate::r1cs::spartan::{self, R1CSPreprocessing, UniformSpartanProof};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use common::constants::RAM_START_ADDRESS;
 use common::rv_trace::NUM_CIRCUIT_FLAGS;
 use serde::{Deserialize, Serialize};
-use strum::EnumCount;
+use strum::{EnumCount, IntoEnumIterator};
 use timestamp_range_check::TimestampRangeCheckStuff;
 
 use crate::jolt::{
     instruction::{
-        div::DIVInstruction, divu::DIVUInstruction, mulh::MULHInstruction,
-        mulhsu::MULHSUInstruction, rem::REMInstruction, remu::REMUInstruction,
-        VirtualInstructionSequence,
+        div::DIVInstruction, divu::DIVUInstruction, max::MAXInstruction, maxu::MAXUInstruction,
+        min::MINInstruction, minu::MINUInstruction, mulh::MULHInstruction,
+        mulhsu::MULHSUInstruction, orn::ORNInstruction, rem::REMInstruction, remu::REMUInstruction,
+        rol::ROLInstruction, ror::RORInstruction, rori::RORIInstruction, sext_b::SEXTBInstruction,
+        sext_h::SEXTHInstruction, zext_h::ZEXTHInstruction, VirtualInstructionSequence,
     },
     subtable::JoltSubtableSet,
     vm::timestamp_range_check::TimestampValidityProof,
 };
-use crate::lasso::memory_checking::{
-    Initializable, MemoryCheckingProver, MemoryCheckingVerifier, StructuredPolynomialData,
-};
+use crate::lasso::memory_checking::{Initializable, StructuredPolynomialData};
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
 use crate::poly::dense_mlpoly::DensePolynomial;
-use crate::r1cs::inputs::{ConstraintInput, R1CSPolynomials, R1CSProof, R1CSStuff};
-use crate::utils::errors::ProofVerifyError;
-use crate::utils::thread::drop_in_background_thread;
-use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use crate::r1cs::inputs::{ConstraintInput, R1CSPolynomials, R1CSStuff};
+use crate::utils::errors::{JoltError, PreprocessingError};
+use crate::utils::thread::{drop_in_background_thread, join_weighted};
+use crate::utils::transcript::{
+    AppendToTranscript, ProofTranscript, TranscriptNamespace, TranscriptVersion,
+};
 use common::{
     constants::MEMORY_OPS_PER_INSTRUCTION,
-    rv_trace::{ELFInstruction, JoltDevice, MemoryOp},
+    rv_trace::{ELFInstruction, JoltDevice, MemoryLayout, MemoryOp, RV32IM},
 };
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::Arc;
 
-use self::bytecode::{BytecodePreprocessing, BytecodeProof, BytecodeRow, BytecodeStuff};
+use self::bytecode::{
+    BytecodePolynomials, BytecodePreprocessing, BytecodeProof, BytecodeRow, BytecodeStuff,
+};
 use self::instruction_lookups::{
-    InstructionLookupStuff, InstructionLookupsPreprocessing, InstructionLookupsProof,
+    InstructionLookupPolynomials, InstructionLookupStuff, InstructionLookupsPreprocessing,
+    InstructionLookupsProof,
 };
+use self::preprocessing::{PreprocessingMetadata, ProgramId};
 use self::read_write_memory::{
     ReadWriteMemoryPolynomials, ReadWriteMemoryPreprocessing, ReadWriteMemoryProof,
     ReadWriteMemoryStuff,
 };
+use self::scheduler::{MetricsRecorder, ProverMetrics, SchedulerConfig, TaskGraph};
+use self::stage::{prove_forked, verify_forked, JoltStage};
+use self::streaming::read_proof_section;
+use self::witness_audit::WitnessAudit;
 
 use super::instruction::JoltInstructionSet;
 
@@ -54,6 +69,7 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
+    pub metadata: PreprocessingMetadata,
     pub generators: PCS::Setup,
     pub instruction_lookups: InstructionLookupsPreprocessing<C, F>,
     pub bytecode: BytecodePreprocessing<F>,
@@ -98,7 +114,14 @@ impl<InstructionSet: JoltInstructionSet> JoltTraceStep<InstructionSet> {
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+/// Besides its canonical serialization, a proof can be encoded as JSON with
+/// [`to_json`](crate::utils::json::to_json).
+///
+/// Proofs are not zero-knowledge, and there is no zero-knowledge mode yet: `program_io` holds the
+/// guest's inputs and outputs in the clear, and the sumchecks and opening proofs reveal
+/// evaluations of the witness polynomials.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct JoltProof<const C: usize, const M: usize, I, F, PCS, InstructionSet, Subtables>
 where
     I: ConstraintInput,
@@ -107,6 +130,11 @@ where
     InstructionSet: JoltInstructionSet,
     Subtables: JoltSubtableSet<F>,
 {
+    /// See [`JoltPreprocessing::with_namespace`].
+    pub namespace: Option<TranscriptNamespace>,
+    /// The [digest](JoltPreprocessing::digest) of the preprocessing the proof was generated
+    /// with, which the verifier checks against its own.
+    pub preprocessing_digest: [u8; 32],
     pub trace_length: usize,
     pub program_io: JoltDevice,
     pub bytecode: BytecodeProof<F, PCS>,
@@ -116,7 +144,8 @@ where
     pub opening_proof: ReducedOpeningProof<F, PCS>,
 }
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct JoltStuff<T: CanonicalSerialize + CanonicalDeserialize + Sync> {
     pub(crate) bytecode: BytecodeStuff<T>,
     pub(crate) read_write_memory: ReadWriteMemoryStuff<T>,
@@ -213,34 +242,97 @@ impl<F: JoltField> JoltPolynomials<F> {
     ) -> JoltCommitments<PCS> {
         let mut commitments = JoltCommitments::<PCS>::initialize(preprocessing);
 
-        let trace_polys = self.read_write_values();
-        let trace_comitments =
-            PCS::batch_commit_polys_ref(&trace_polys, &preprocessing.generators, BatchType::Big);
-        commitments
-            .read_write_values_mut()
-            .into_iter()
-            .zip(trace_comitments.into_iter())
-            .for_each(|(dest, src)| *dest = src);
-
-        commitments.bytecode.t_final =
-            PCS::commit(&self.bytecode.t_final, &preprocessing.generators);
+        commit_in_batches::<PCS>(
+            self.read_write_values(),
+            commitments.read_write_values_mut(),
+            &preprocessing.generators,
+        );
+        let setup = &preprocessing.generators;
+        commitments.bytecode.t_final = commit_bytecode_final::<PCS>(&self.bytecode, setup);
         (
             commitments.read_write_memory.v_final,
             commitments.read_write_memory.t_final,
-        ) = rayon::join(
-            || PCS::commit(&self.read_write_memory.v_final, &preprocessing.generators),
-            || PCS::commit(&self.read_write_memory.t_final, &preprocessing.generators),
-        );
-        commitments.instruction_lookups.final_cts = PCS::batch_commit_polys(
-            &self.instruction_lookups.final_cts,
-            &preprocessing.generators,
-            BatchType::Big,
-        );
+        ) = commit_memory_final::<PCS>(&self.read_write_memory, setup);
+        commitments.instruction_lookups.final_cts =
+            commit_instruction_lookups_final::<PCS>(&self.instruction_lookups, setup);
 
         commitments
     }
 }
 
+/// Commits to `polys` in batches of `PCS::commit_batch_size()` polynomials, or fewer under a
+/// memory budget (see [`SchedulerConfig::max_memory_bytes`]), writing each commitment to the
+/// corresponding entry of `commitments`.
+#[tracing::instrument(skip_all)]
+pub(crate) fn commit_in_batches<PCS: CommitmentScheme>(
+    polys: Vec<&DensePolynomial<PCS::Field>>,
+    mut commitments: Vec<&mut PCS::Commitment>,
+    setup: &PCS::Setup,
+) {
+    assert_eq!(polys.len(), commitments.len());
+    let max_poly_bytes =
+        polys.iter().map(|poly| poly.len()).max().unwrap_or(0) * std::mem::size_of::<PCS::Field>();
+    let batch_size = scheduler::budgeted_batch_size(PCS::commit_batch_size(), max_poly_bytes);
+    for (poly_batch, commitment_batch) in polys
+        .chunks(batch_size)
+        .zip(commitments.chunks_mut(batch_size))
+    {
+        let batch_commitments =
+            PCS::batch_commit_polys_ref(poly_batch, setup, BatchType::SmallValues);
+        commitment_batch
+            .iter_mut()
+            .zip(batch_commitments)
+            .for_each(|(dest, src)| **dest = src);
+    }
+}
+
+/// Commits to the read/write columns `read_write_polys` (in batches, see [`commit_in_batches`])
+/// concurrently with `commit_final`, which commits to a component's final-value columns
+/// `final_polys`. The two are independent, so each runs on its own slice of the thread pool,
+/// sized by the total number of evaluations (i.e. MSM size) it commits to.
+fn commit_read_write_and_final<PCS: CommitmentScheme, R: Send>(
+    read_write_polys: Vec<&DensePolynomial<PCS::Field>>,
+    read_write_commitments: Vec<&mut PCS::Commitment>,
+    final_polys: &[&DensePolynomial<PCS::Field>],
+    commit_final: impl FnOnce() -> R + Send,
+    setup: &PCS::Setup,
+) -> R {
+    let msm_size = |polys: &[&DensePolynomial<PCS::Field>]| -> usize {
+        polys.iter().map(|poly| poly.len()).sum()
+    };
+    let (_, final_commitments) = join_weighted(
+        msm_size(&read_write_polys),
+        || commit_in_batches::<PCS>(read_write_polys, read_write_commitments, setup),
+        msm_size(final_polys),
+        commit_final,
+    );
+    final_commitments
+}
+
+fn commit_bytecode_final<PCS: CommitmentScheme>(
+    polynomials: &BytecodePolynomials<PCS::Field>,
+    setup: &PCS::Setup,
+) -> PCS::Commitment {
+    PCS::commit(&polynomials.t_final, setup)
+}
+
+fn commit_memory_final<PCS: CommitmentScheme>(
+    polynomials: &ReadWriteMemoryPolynomials<PCS::Field>,
+    setup: &PCS::Setup,
+) -> (PCS::Commitment, PCS::Commitment) {
+    rayon::join(
+        || PCS::commit(&polynomials.v_final, setup),
+        || PCS::commit(&polynomials.t_final, setup),
+    )
+}
+
+fn commit_instruction_lookups_final<PCS: CommitmentScheme>(
+    polynomials: &InstructionLookupPolynomials<PCS::Field>,
+    setup: &PCS::Setup,
+) -> Vec<PCS::Commitment> {
+    PCS::batch_commit_polys(&polynomials.final_cts, setup, BatchType::SmallValues)
+}
+
 pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, const M: usize> {
     type InstructionSet: JoltInstructionSet;
     type Subtables: JoltSubtableSet<F>;
@@ -278,21 +370,13 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             Self::Subtables,
         >();
 
-        let read_write_memory_preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init);
-
-        let bytecode_rows: Vec<BytecodeRow> = bytecode
-            .into_iter()
-            .flat_map(|instruction| match instruction.opcode {
-                tracer::RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::DIV => DIVInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::DIVU => DIVUInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::REM => REMInstruction::<32>::virtual_sequence(instruction),
-                tracer::RV32IM::REMU => REMUInstruction::<32>::virtual_sequence(instruction),
-                _ => vec![instruction],
-            })
-            .map(|instruction| BytecodeRow::from_instruction::<Self::InstructionSet>(&instruction))
+        let bytecode_rows: Vec<BytecodeRow> = expand_virtual_sequences(bytecode)
+            .iter()
+            .map(BytecodeRow::from_instruction::<Self::InstructionSet>)
             .collect();
+        let program_id = ProgramId::new(&bytecode_rows, &memory_init);
+
+        let read_write_memory_preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init);
         let bytecode_preprocessing = BytecodePreprocessing::<F>::preprocess(bytecode_rows);
 
         let commitment_shapes = [
@@ -304,7 +388,16 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         .concat();
         let generators = PCS::setup(&commitment_shapes);
 
+        let metadata =
+            PreprocessingMetadata::new::<C, M, F, PCS, Self::InstructionSet, Self::Subtables>(
+                program_id,
+                max_bytecode_size,
+                max_memory_address,
+                max_trace_length,
+            );
+
         JoltPreprocessing {
+            metadata,
             generators,
             instruction_lookups: instruction_lookups_preprocessing,
             bytecode: bytecode_preprocessing,
@@ -312,34 +405,491 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         }
     }
 
-    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    /// Test-only "tiny" preprocessing, sized to fit one particular execution of the program as
+    /// tightly as possible: the bytecode, memory-checking, and Spartan (trace length) parameters
+    /// are the smallest powers of two that accommodate `program_io` and `trace`. This shrinks the
+    /// SRS and every commitment shape, so the full prover and verifier run in seconds.
+    ///
+    /// The instruction lookup parameters `C` and `M` are fixed by the VM, since lookups must
+    /// cover 64-bit operand pairs (`C * log2(M) = 64`); for RV32I, `M = 2^16` is already the
+    /// smallest subtable size that supports every instruction.
+    ///
+    /// The result is not reusable across inputs: a longer trace, or one that touches more
+    /// memory, is rejected by [`Jolt::prove`], so this is only compiled for tests. Applications
+    /// should size [`Jolt::preprocess`] for the largest execution they expect.
+    #[cfg(test)]
+    fn preprocess_tiny(
+        bytecode: Vec<ELFInstruction>,
+        memory_init: Vec<(u64, u8)>,
+        program_io: &JoltDevice,
+        trace: &[JoltTraceStep<Self::InstructionSet>],
+    ) -> Result<JoltPreprocessing<C, F, PCS>, JoltError> {
+        let max_bytecode_size = expand_virtual_sequences(bytecode.clone())
+            .len()
+            .next_power_of_two();
+        let max_memory_address =
+            read_write_memory::memory_witness_size(&program_io.memory_layout, trace)?;
+        let max_trace_length = trace.len().next_power_of_two();
+        Ok(Self::preprocess(
+            bytecode,
+            memory_init,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+        ))
+    }
+
+    /// Loads preprocessing written by [`JoltPreprocessing::serialize_to_writer`], refusing
+    /// files whose metadata doesn't match this VM.
+    #[tracing::instrument(skip_all, name = "Jolt::load_preprocessing")]
+    fn load_preprocessing<R: Read>(
+        mut reader: R,
+    ) -> Result<JoltPreprocessing<C, F, PCS>, PreprocessingError> {
+        let metadata = PreprocessingMetadata::read(&mut reader)?;
+        metadata.check::<C, M, F, PCS, Self::InstructionSet, Self::Subtables>()?;
+        JoltPreprocessing::deserialize_after_metadata(metadata, reader)
+    }
+
+    fn load_preprocessing_from_file<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<JoltPreprocessing<C, F, PCS>, PreprocessingError> {
+        Self::load_preprocessing(BufReader::new(File::open(path)?))
+    }
+
+    /// Proves that `trace` is an execution of the preprocessed program with I/O `program_io`.
+    ///
+    /// The prover draws no randomness: Jolt's proofs aren't zero-knowledge, so nothing is blinded,
+    /// and every challenge is derived from the transcript. A proof is a deterministic function of
+    /// the preprocessing, `program_io` and `trace` (and of the proof profile in
+    /// [`Self::prove_with_metrics`]), so proving the same program on the same inputs gives
+    /// bit-for-bit the same proof, however many threads are used and however the stages are
+    /// scheduled. Proofs can therefore be cached by preprocessing, profile and inputs, e.g. under
+    /// `host::artifacts::proof_key`.
     fn prove(
+        program_io: JoltDevice,
+        trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        preprocessing: JoltPreprocessing<C, F, PCS>,
+    ) -> Result<
+        (
+            JoltProof<
+                C,
+                M,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                PCS,
+                Self::InstructionSet,
+                Self::Subtables,
+            >,
+            JoltCommitments<PCS>,
+            Option<ProverDebugInfo<F>>,
+        ),
+        JoltError,
+    > {
+        let (proof, commitments, debug_info, _) = Self::prove_with_metrics(
+            program_io,
+            trace,
+            preprocessing,
+            &SchedulerConfig::default(),
+        )?;
+        Ok((proof, commitments, debug_info))
+    }
+
+    /// Like [`Self::prove`], but scheduled according to `config`, and also returning how long
+    /// each part of the proof took. Progress is reported to `config.observer`; if
+    /// `config.cancellation` is cancelled, proving stops after the steps in flight and returns
+    /// [`JoltError::Cancelled`].
+    #[tracing::instrument(skip_all, name = "Jolt::prove")]
+    fn prove_with_metrics(
         program_io: JoltDevice,
         mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
         preprocessing: JoltPreprocessing<C, F, PCS>,
-    ) -> (
-        JoltProof<
-            C,
-            M,
-            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
-            F,
-            PCS,
-            Self::InstructionSet,
-            Self::Subtables,
-        >,
-        JoltCommitments<PCS>,
-        Option<ProverDebugInfo<F>>,
-    ) {
+        config: &SchedulerConfig,
+    ) -> Result<
+        (
+            JoltProof<
+                C,
+                M,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+                PCS,
+                Self::InstructionSet,
+                Self::Subtables,
+            >,
+            JoltCommitments<PCS>,
+            Option<ProverDebugInfo<F>>,
+            ProverMetrics,
+        ),
+        JoltError,
+    > {
         let trace_length = trace.len();
         let padded_trace_length = trace_length.next_power_of_two();
         println!("Trace length: {}", trace_length);
 
+        let max_trace_length = preprocessing.metadata.max_trace_length.next_power_of_two();
+        if padded_trace_length > max_trace_length {
+            return Err(JoltError::TraceTooLong {
+                trace_length,
+                max_trace_length,
+            });
+        }
+        let memory_layout = &program_io.memory_layout;
+        if program_io.inputs.len() > memory_layout.max_input_size as usize {
+            return Err(JoltError::InvalidInputSize {
+                size: program_io.inputs.len(),
+                max_size: memory_layout.max_input_size,
+            });
+        }
+        if program_io.outputs.len() > memory_layout.max_output_size as usize {
+            return Err(JoltError::InvalidOutputSize {
+                size: program_io.outputs.len(),
+                max_size: memory_layout.max_output_size,
+            });
+        }
+        let memory_size = read_write_memory::memory_witness_size(memory_layout, &trace)?;
+        let required_srs_size = padded_trace_length.max(memory_size).max(M);
+        let srs_size = PCS::srs_size(&preprocessing.generators);
+        if required_srs_size > srs_size {
+            return Err(JoltError::SrsTooSmall {
+                required: required_srs_size,
+                available: srs_size,
+            });
+        }
+
+        config.install(move || {
+            let metrics = MetricsRecorder::new(config);
+            JoltTraceStep::pad(&mut trace);
+
+            let namespace = preprocessing.metadata.namespace.clone();
+            let preprocessing_digest = preprocessing.digest(&program_io.memory_layout);
+            let mut transcript = new_transcript(
+                namespace.as_ref(),
+                preprocessing.metadata.transcript_version,
+            );
+            Self::fiat_shamir_preamble(&mut transcript, &program_io, trace_length);
+
+            // Each component's witness columns are committed as soon as they are generated,
+            // overlapping with generation of the next component's witness. Commitments are
+            // appended to the transcript in canonical order once all of them exist.
+            let mut jolt_polynomials = JoltPolynomials::<F>::default();
+            let mut jolt_commitments = JoltCommitments::<PCS>::initialize(&preprocessing);
+            let setup = &preprocessing.generators;
+
+            jolt_polynomials.instruction_lookups =
+                metrics.time("Instruction lookups witness", || {
+                    InstructionLookupsProof::<
+                        C,
+                        M,
+                        F,
+                        PCS,
+                        Self::InstructionSet,
+                        Self::Subtables,
+                    >::generate_witness(
+                        &preprocessing.instruction_lookups, &trace
+                    )
+                });
+
+            metrics.check_cancelled()?;
+
+            let load_store_flags = &jolt_polynomials.instruction_lookups.instruction_flags[5..10];
+            let ((memory_polynomials, range_check_polys), _) = rayon::join(
+                || {
+                    metrics.time("Read-write memory witness", || {
+                        let (memory_polynomials, read_timestamps) =
+                            ReadWriteMemoryPolynomials::generate_witness(
+                                &program_io,
+                                load_store_flags,
+                                &preprocessing.read_write_memory,
+                                &trace,
+                            );
+                        let range_check_polys =
+                            TimestampValidityProof::<F, PCS>::generate_witness(&read_timestamps);
+                        (memory_polynomials, range_check_polys)
+                    })
+                },
+                || {
+                    metrics.time("Instruction lookups commitment", || {
+                        InstructionLookupsProof::<
+                            C,
+                            M,
+                            F,
+                            PCS,
+                            Self::InstructionSet,
+                            Self::Subtables,
+                        >::commit(
+                            &jolt_polynomials, &mut jolt_commitments, setup
+                        )
+                    })
+                },
+            );
+            jolt_polynomials.read_write_memory = memory_polynomials;
+            jolt_polynomials.timestamp_range_check = range_check_polys;
+            metrics.check_cancelled()?;
+
+            let (bytecode_polynomials, _) = rayon::join(
+                || {
+                    metrics.time("Bytecode witness", || {
+                        BytecodeProof::<F, PCS>::generate_witness(
+                            &preprocessing.bytecode,
+                            &mut trace,
+                        )
+                    })
+                },
+                || {
+                    metrics.time("Read-write memory commitment", || {
+                        ReadWriteMemoryProof::<F, PCS>::commit(
+                            &jolt_polynomials,
+                            &mut jolt_commitments,
+                            setup,
+                        )
+                    })
+                },
+            );
+            jolt_polynomials.bytecode = bytecode_polynomials;
+            metrics.check_cancelled()?;
+
+            let r1cs_builder = Self::Constraints::construct_constraints(
+                padded_trace_length,
+                RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
+            );
+            let spartan_key = spartan::UniformSpartanProof::<
+                C,
+                <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                F,
+            >::setup(&r1cs_builder, padded_trace_length);
+            let r1cs_preprocessing = R1CSPreprocessing {
+                builder: r1cs_builder,
+                key: spartan_key,
+            };
+
+            let (r1cs_polynomials, _) = rayon::join(
+                || {
+                    metrics.time("R1CS witness", || {
+                        R1CSPolynomials::new::<
+                            C,
+                            M,
+                            Self::InstructionSet,
+                            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                        >(&trace, trace_length)
+                    })
+                },
+                || {
+                    metrics.time("Bytecode commitment", || {
+                        BytecodeProof::<F, PCS>::commit(
+                            &jolt_polynomials,
+                            &mut jolt_commitments,
+                            setup,
+                        )
+                    })
+                },
+            );
+            jolt_polynomials.r1cs = r1cs_polynomials;
+            // The trace is no longer needed once every witness column has been generated.
+            drop(trace);
+            metrics.check_cancelled()?;
+
+            metrics.time("R1CS aux witness", || {
+                r1cs_preprocessing
+                    .builder
+                    .compute_aux(&mut jolt_polynomials)
+            });
+            metrics.check_cancelled()?;
+            metrics.time("R1CS commitment", || {
+                <UniformSpartanProof<
+                    C,
+                    <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+                    F,
+                > as JoltStage<F, PCS>>::commit(
+                    &jolt_polynomials, &mut jolt_commitments, setup
+                )
+            });
+            metrics.check_cancelled()?;
+
+            transcript.append_scalar(&r1cs_preprocessing.key.vk_digest);
+
+            jolt_commitments
+                .read_write_values()
+                .iter()
+                .for_each(|value| value.append_to_transcript(&mut transcript));
+            jolt_commitments
+                .init_final_values()
+                .iter()
+                .for_each(|value| value.append_to_transcript(&mut transcript));
+
+            // The stages are independent given the commitments, so they are proven as a task
+            // graph over forks of the transcript, which are then joined back in stage order.
+            let (polynomials, base_transcript) = (&jolt_polynomials, &transcript);
+            let mut bytecode = None;
+            let mut instruction_lookups = None;
+            let mut read_write_memory = None;
+            let mut r1cs = None;
+            let mut stages = TaskGraph::new();
+            stages.add_task("Bytecode proof", &[], || {
+                bytecode = Some(prove_forked::<F, PCS, BytecodeProof<F, PCS>>(
+                    &preprocessing.bytecode,
+                    setup,
+                    polynomials,
+                    &program_io,
+                    trace_length,
+                    base_transcript,
+                    config.grand_product,
+                ))
+            });
+            stages.add_task("Instruction lookups proof", &[], || {
+                instruction_lookups = Some(prove_forked::<
+                    F,
+                    PCS,
+                    InstructionLookupsProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
+                >(
+                    &preprocessing.instruction_lookups,
+                    setup,
+                    polynomials,
+                    &program_io,
+                    trace_length,
+                    base_transcript,
+                    config.grand_product,
+                ))
+            });
+            stages.add_task("Read-write memory proof", &[], || {
+                read_write_memory = Some(prove_forked::<F, PCS, ReadWriteMemoryProof<F, PCS>>(
+                    &preprocessing.read_write_memory,
+                    setup,
+                    polynomials,
+                    &program_io,
+                    trace_length,
+                    base_transcript,
+                    config.grand_product,
+                ))
+            });
+            stages.add_task("R1CS proof", &[], || {
+                r1cs = Some(prove_forked::<
+                    F,
+                    PCS,
+                    UniformSpartanProof<C, <Self::Constraints as R1CSConstraints<C, F>>::Inputs, F>,
+                >(
+                    &r1cs_preprocessing,
+                    setup,
+                    polynomials,
+                    &program_io,
+                    trace_length,
+                    base_transcript,
+                    config.grand_product,
+                ))
+            });
+            let witness_bytes = jolt_polynomials
+                .read_write_values()
+                .into_iter()
+                .chain(jolt_polynomials.init_final_values())
+                .map(|poly| poly.len() * std::mem::size_of::<F>())
+                .sum();
+            stages.run(config.parallel_stages(witness_bytes), &metrics);
+            metrics.check_cancelled()?;
+
+            let (bytecode_proof, bytecode_openings, bytecode_transcript) = bytecode.unwrap();
+            let (instruction_proof, instruction_openings, instruction_transcript) =
+                instruction_lookups.unwrap();
+            let (memory_proof, memory_openings, memory_transcript) = read_write_memory.unwrap();
+            let (spartan_proof, r1cs_openings, r1cs_transcript) = r1cs.unwrap();
+
+            let mut opening_accumulator: ProverOpeningAccumulator<F> =
+                ProverOpeningAccumulator::new();
+            for (openings, stage_transcript) in [
+                (bytecode_openings, bytecode_transcript),
+                (instruction_openings, instruction_transcript),
+                (memory_openings, memory_transcript),
+                (r1cs_openings, r1cs_transcript),
+            ] {
+                opening_accumulator.join(openings);
+                transcript.join(&stage_transcript);
+            }
+            halt::prove_halt(
+                &jolt_polynomials,
+                &program_io,
+                &mut opening_accumulator,
+                &mut transcript,
+            );
+
+            // Batch-prove all openings
+            let opening_proof = metrics.time("Opening proof", || {
+                opening_accumulator
+                    .reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript)
+            });
+
+            drop_in_background_thread(jolt_polynomials);
+
+            let jolt_proof = JoltProof {
+                namespace,
+                preprocessing_digest,
+                trace_length,
+                program_io,
+                bytecode: bytecode_proof,
+                read_write_memory: memory_proof,
+                instruction_lookups: instruction_proof,
+                r1cs: spartan_proof,
+                opening_proof,
+            };
+
+            #[cfg(test)]
+            let debug_info = Some(ProverDebugInfo {
+                transcript,
+                opening_accumulator,
+            });
+            #[cfg(not(test))]
+            let debug_info = None;
+            Ok((jolt_proof, jolt_commitments, debug_info, metrics.finish()))
+        })
+    }
+
+    /// Generates the witness for `trace` like [`Self::prove`] does, but instead of committing to
+    /// it, reports the range of values observed in each committed column. Needs no SRS, so it
+    /// is cheap to run on real executions.
+    #[tracing::instrument(skip_all, name = "Jolt::audit_witness")]
+    fn audit_witness(
+        bytecode: Vec<ELFInstruction>,
+        memory_init: Vec<(u64, u8)>,
+        program_io: &JoltDevice,
+        trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+    ) -> WitnessAudit {
+        let trace_length = trace.len();
+        let (polynomials, _) =
+            Self::witness(bytecode, memory_init, program_io, trace, trace_length);
+
+        let instruction_names: Vec<String> = Self::InstructionSet::iter()
+            .map(|instruction| {
+                let name = format!("{instruction:?}");
+                name.split('(').next().unwrap().to_string()
+            })
+            .collect();
+        WitnessAudit::new(&polynomials, &instruction_names, trace_length)
+    }
+
+    /// Generates the witness for `trace` like [`Self::prove`] does, for a prover claiming that
+    /// the first `trace_length` steps are the execution and the rest is padding, along with the
+    /// R1CS it has to satisfy. Needs no SRS.
+    fn witness(
+        bytecode: Vec<ELFInstruction>,
+        memory_init: Vec<(u64, u8)>,
+        program_io: &JoltDevice,
+        mut trace: Vec<JoltTraceStep<Self::InstructionSet>>,
+        trace_length: usize,
+    ) -> (
+        JoltPolynomials<F>,
+        CombinedUniformBuilder<C, F, <Self::Constraints as R1CSConstraints<C, F>>::Inputs>,
+    ) {
         JoltTraceStep::pad(&mut trace);
 
-        let mut transcript = ProofTranscript::new(b"Jolt transcript");
-        Self::fiat_shamir_preamble(&mut transcript, &program_io, trace_length);
+        let instruction_lookups_preprocessing = InstructionLookupsPreprocessing::preprocess::<
+            M,
+            Self::InstructionSet,
+            Self::Subtables,
+        >();
+        let bytecode_rows: Vec<BytecodeRow> = expand_virtual_sequences(bytecode)
+            .iter()
+            .map(BytecodeRow::from_instruction::<Self::InstructionSet>)
+            .collect();
+        let read_write_memory_preprocessing = ReadWriteMemoryPreprocessing::preprocess(memory_init);
+        let bytecode_preprocessing = BytecodePreprocessing::<F>::preprocess(bytecode_rows);
 
-        let instruction_polynomials = InstructionLookupsProof::<
+        let instruction_lookups = InstructionLookupsProof::<
             C,
             M,
             F,
@@ -347,132 +897,69 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             Self::InstructionSet,
             Self::Subtables,
         >::generate_witness(
-            &preprocessing.instruction_lookups, &trace
+            &instruction_lookups_preprocessing, &trace
         );
-
-        let load_store_flags = &instruction_polynomials.instruction_flags[5..10];
-        let (memory_polynomials, read_timestamps) = ReadWriteMemoryPolynomials::generate_witness(
-            &program_io,
+        let load_store_flags = &instruction_lookups.instruction_flags[5..10];
+        let (read_write_memory, read_timestamps) = ReadWriteMemoryPolynomials::generate_witness(
+            program_io,
             load_store_flags,
-            &preprocessing.read_write_memory,
+            &read_write_memory_preprocessing,
             &trace,
         );
-
-        let (bytecode_polynomials, range_check_polys) = rayon::join(
-            || BytecodeProof::<F, PCS>::generate_witness(&preprocessing.bytecode, &mut trace),
-            || TimestampValidityProof::<F, PCS>::generate_witness(&read_timestamps),
-        );
-
-        let r1cs_builder = Self::Constraints::construct_constraints(
-            padded_trace_length,
-            RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
-        );
-        let spartan_key = spartan::UniformSpartanProof::<
-            C,
-            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
-            F,
-        >::setup(&r1cs_builder, padded_trace_length);
-
-        let r1cs_polynomials = R1CSPolynomials::new::<
+        let timestamp_range_check =
+            TimestampValidityProof::<F, PCS>::generate_witness(&read_timestamps);
+        let bytecode =
+            BytecodeProof::<F, PCS>::generate_witness(&bytecode_preprocessing, &mut trace);
+        let r1cs = R1CSPolynomials::new::<
             C,
             M,
             Self::InstructionSet,
             <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
-        >(&trace);
-
-        let mut jolt_polynomials = JoltPolynomials {
-            bytecode: bytecode_polynomials,
-            read_write_memory: memory_polynomials,
-            timestamp_range_check: range_check_polys,
-            instruction_lookups: instruction_polynomials,
-            r1cs: r1cs_polynomials,
+        >(&trace, trace_length);
+        let mut polynomials = JoltPolynomials {
+            bytecode,
+            read_write_memory,
+            instruction_lookups,
+            timestamp_range_check,
+            r1cs,
         };
-
-        r1cs_builder.compute_aux(&mut jolt_polynomials);
-
-        let jolt_commitments = jolt_polynomials.commit::<C, PCS>(&preprocessing);
-
-        transcript.append_scalar(&spartan_key.vk_digest);
-
-        jolt_commitments
-            .read_write_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-        jolt_commitments
-            .init_final_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-
-        let mut opening_accumulator: ProverOpeningAccumulator<F> = ProverOpeningAccumulator::new();
-
-        let bytecode_proof = BytecodeProof::prove_memory_checking(
-            &preprocessing.generators,
-            &preprocessing.bytecode,
-            &jolt_polynomials.bytecode,
-            &jolt_polynomials,
-            &mut opening_accumulator,
-            &mut transcript,
-        );
-
-        let instruction_proof = InstructionLookupsProof::prove(
-            &preprocessing.generators,
-            &jolt_polynomials,
-            &preprocessing.instruction_lookups,
-            &mut opening_accumulator,
-            &mut transcript,
-        );
-
-        let memory_proof = ReadWriteMemoryProof::prove(
-            &preprocessing.generators,
-            &preprocessing.read_write_memory,
-            &jolt_polynomials,
-            &program_io,
-            &mut opening_accumulator,
-            &mut transcript,
+        let r1cs_builder = Self::Constraints::construct_constraints(
+            trace.len(),
+            RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
         );
+        r1cs_builder.compute_aux(&mut polynomials);
+        (polynomials, r1cs_builder)
+    }
 
-        let spartan_proof = UniformSpartanProof::<
+    #[tracing::instrument(skip_all)]
+    fn verify(
+        preprocessing: JoltPreprocessing<C, F, PCS>,
+        proof: JoltProof<
             C,
+            M,
             <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
             F,
-        >::prove::<PCS>(
-            &r1cs_builder,
-            &spartan_key,
-            &jolt_polynomials,
-            &mut opening_accumulator,
-            &mut transcript,
+            PCS,
+            Self::InstructionSet,
+            Self::Subtables,
+        >,
+        commitments: JoltCommitments<PCS>,
+        debug_info: Option<ProverDebugInfo<F>>,
+    ) -> Result<(), JoltError> {
+        Self::verify_with(
+            &preprocessing,
+            proof,
+            commitments,
+            debug_info,
+            |program_io, trace_length| Arc::new(Self::r1cs_preprocessing(program_io, trace_length)),
         )
-        .expect("r1cs proof failed");
-
-        // Batch-prove all openings
-        let opening_proof =
-            opening_accumulator.reduce_and_prove::<PCS>(&preprocessing.generators, &mut transcript);
-
-        drop_in_background_thread(jolt_polynomials);
-
-        let jolt_proof = JoltProof {
-            trace_length,
-            program_io,
-            bytecode: bytecode_proof,
-            read_write_memory: memory_proof,
-            instruction_lookups: instruction_proof,
-            r1cs: spartan_proof,
-            opening_proof,
-        };
-
-        #[cfg(test)]
-        let debug_info = Some(ProverDebugInfo {
-            transcript,
-            opening_accumulator,
-        });
-        #[cfg(not(test))]
-        let debug_info = None;
-        (jolt_proof, jolt_commitments, debug_info)
     }
 
-    #[tracing::instrument(skip_all)]
-    fn verify(
-        mut preprocessing: JoltPreprocessing<C, F, PCS>,
+    /// Like [`Self::verify`], but borrows the preprocessing, and gets the R1CS preprocessing for
+    /// the proof's program I/O and trace length from `r1cs_preprocessing` instead of regenerating
+    /// it, so that it can be shared between proofs. See [`verifier::Verifier`].
+    fn verify_with(
+        preprocessing: &JoltPreprocessing<C, F, PCS>,
         proof: JoltProof<
             C,
             M,
@@ -484,8 +971,18 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         >,
         commitments: JoltCommitments<PCS>,
         _debug_info: Option<ProverDebugInfo<F>>,
-    ) -> Result<(), ProofVerifyError> {
-        let mut transcript = ProofTranscript::new(b"Jolt transcript");
+        r1cs_preprocessing: impl FnOnce(
+            &JoltDevice,
+            usize,
+        ) -> Arc<
+            R1CSPreprocessing<C, <Self::Constraints as R1CSConstraints<C, F>>::Inputs, F>,
+        >,
+    ) -> Result<(), JoltError> {
+        check_namespace(preprocessing, proof.namespace.as_ref())?;
+        let mut transcript = new_transcript(
+            proof.namespace.as_ref(),
+            preprocessing.metadata.transcript_version,
+        );
         let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS> =
             VerifierOpeningAccumulator::new();
 
@@ -495,146 +992,254 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
             opening_accumulator
                 .compare_to(debug_info.opening_accumulator, &preprocessing.generators);
         }
-        Self::fiat_shamir_preamble(&mut transcript, &proof.program_io, proof.trace_length);
+        check_public_inputs(preprocessing, proof.trace_length, &proof.program_io)?;
+        check_preprocessing_digest(
+            preprocessing,
+            &proof.preprocessing_digest,
+            &proof.program_io.memory_layout,
+        )?;
+        let r1cs_preprocessing = r1cs_preprocessing(&proof.program_io, proof.trace_length);
+        Self::verifier_preamble(
+            &mut transcript,
+            &proof.program_io,
+            proof.trace_length,
+            &r1cs_preprocessing,
+            &commitments,
+        );
+        // See `ReadWriteMemoryPreprocessing::program_io`
+        let mut read_write_memory = preprocessing.read_write_memory.clone();
+        read_write_memory.program_io = Some(proof.program_io.clone());
+
+        let (generators, program_io) = (&preprocessing.generators, &proof.program_io);
+        let stage_transcripts = [
+            verify_forked(
+                proof.bytecode,
+                &preprocessing.bytecode,
+                generators,
+                &commitments,
+                program_io,
+                proof.trace_length,
+                &mut opening_accumulator,
+                &transcript,
+            )?,
+            verify_forked(
+                proof.instruction_lookups,
+                &preprocessing.instruction_lookups,
+                generators,
+                &commitments,
+                program_io,
+                proof.trace_length,
+                &mut opening_accumulator,
+                &transcript,
+            )?,
+            verify_forked(
+                proof.read_write_memory,
+                &read_write_memory,
+                generators,
+                &commitments,
+                program_io,
+                proof.trace_length,
+                &mut opening_accumulator,
+                &transcript,
+            )?,
+            verify_forked(
+                proof.r1cs,
+                &*r1cs_preprocessing,
+                generators,
+                &commitments,
+                program_io,
+                proof.trace_length,
+                &mut opening_accumulator,
+                &transcript,
+            )?,
+        ];
+        for stage_transcript in &stage_transcripts {
+            transcript.join(stage_transcript);
+        }
+        halt::verify_halt(
+            &commitments,
+            program_io,
+            proof.trace_length,
+            &mut opening_accumulator,
+            &mut transcript,
+        );
 
-        // Regenerate the uniform Spartan key
-        let padded_trace_length = proof.trace_length.next_power_of_two();
-        let memory_start = RAM_START_ADDRESS - proof.program_io.memory_layout.ram_witness_offset;
-        let r1cs_builder =
-            Self::Constraints::construct_constraints(padded_trace_length, memory_start);
-        let spartan_key = spartan::UniformSpartanProof::setup(&r1cs_builder, padded_trace_length);
-        transcript.append_scalar(&spartan_key.vk_digest);
+        // Batch-verify all openings
+        opening_accumulator
+            .reduce_and_verify(
+                &preprocessing.generators,
+                proof.opening_proof,
+                &mut transcript,
+            )
+            .map_err(JoltError::Pcs)?;
 
-        let r1cs_proof = R1CSProof {
-            key: spartan_key,
-            proof: proof.r1cs,
-        };
+        Ok(())
+    }
 
-        commitments
-            .read_write_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
-        commitments
-            .init_final_values()
-            .iter()
-            .for_each(|value| value.append_to_transcript(&mut transcript));
+    /// Verifies a proof written by [`JoltProof::serialize_streaming`], deserializing each
+    /// stage's section of the proof only once the previous stage has been verified and dropped.
+    /// Peak verifier memory is bounded by the largest section rather than the whole proof.
+    ///
+    /// Proofs of programs run with blobs are rejected with [`JoltError::MissingBlobs`], since
+    /// their data can't be attached to the streamed program I/O.
+    #[tracing::instrument(skip_all, name = "Jolt::verify_streaming")]
+    fn verify_streaming<R: Read>(
+        mut preprocessing: JoltPreprocessing<C, F, PCS>,
+        mut reader: R,
+        commitments: JoltCommitments<PCS>,
+    ) -> Result<(), JoltError> {
+        let namespace: Option<TranscriptNamespace> = read_proof_section(&mut reader)?;
+        check_namespace(&preprocessing, namespace.as_ref())?;
+        let mut transcript = new_transcript(
+            namespace.as_ref(),
+            preprocessing.metadata.transcript_version,
+        );
+        let mut opening_accumulator: VerifierOpeningAccumulator<F, PCS> =
+            VerifierOpeningAccumulator::new();
 
-        Self::verify_bytecode(
+        let preprocessing_digest: [u8; 32] = read_proof_section(&mut reader)?;
+        let trace_length: usize = read_proof_section(&mut reader)?;
+        let program_io: JoltDevice = read_proof_section(&mut reader)?;
+        check_public_inputs(&preprocessing, trace_length, &program_io)?;
+        check_preprocessing_digest(
+            &preprocessing,
+            &preprocessing_digest,
+            &program_io.memory_layout,
+        )?;
+        let r1cs_preprocessing = Self::r1cs_preprocessing(&program_io, trace_length);
+        Self::verifier_preamble(
+            &mut transcript,
+            &program_io,
+            trace_length,
+            &r1cs_preprocessing,
+            &commitments,
+        );
+        preprocessing.read_write_memory.program_io = Some(program_io.clone());
+
+        let generators = &preprocessing.generators;
+        let mut stage_transcripts = Vec::with_capacity(4);
+        let bytecode_proof: BytecodeProof<F, PCS> = read_proof_section(&mut reader)?;
+        stage_transcripts.push(verify_forked(
+            bytecode_proof,
             &preprocessing.bytecode,
-            &preprocessing.generators,
-            proof.bytecode,
+            generators,
             &commitments,
+            &program_io,
+            trace_length,
             &mut opening_accumulator,
-            &mut transcript,
-        )?;
-        Self::verify_instruction_lookups(
+            &transcript,
+        )?);
+
+        let instruction_proof: InstructionLookupsProof<
+            C,
+            M,
+            F,
+            PCS,
+            Self::InstructionSet,
+            Self::Subtables,
+        > = read_proof_section(&mut reader)?;
+        stage_transcripts.push(verify_forked(
+            instruction_proof,
             &preprocessing.instruction_lookups,
-            &preprocessing.generators,
-            proof.instruction_lookups,
+            generators,
             &commitments,
+            &program_io,
+            trace_length,
             &mut opening_accumulator,
-            &mut transcript,
-        )?;
-        Self::verify_memory(
-            &mut preprocessing.read_write_memory,
-            &preprocessing.generators,
-            proof.read_write_memory,
+            &transcript,
+        )?);
+
+        let memory_proof: ReadWriteMemoryProof<F, PCS> = read_proof_section(&mut reader)?;
+        stage_transcripts.push(verify_forked(
+            memory_proof,
+            &preprocessing.read_write_memory,
+            generators,
             &commitments,
-            proof.program_io,
+            &program_io,
+            trace_length,
             &mut opening_accumulator,
-            &mut transcript,
-        )?;
+            &transcript,
+        )?);
 
-        Self::verify_r1cs(
+        let r1cs_proof: UniformSpartanProof<
+            C,
+            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+            F,
+        > = read_proof_section(&mut reader)?;
+        stage_transcripts.push(verify_forked(
             r1cs_proof,
+            &r1cs_preprocessing,
+            generators,
             &commitments,
+            &program_io,
+            trace_length,
+            &mut opening_accumulator,
+            &transcript,
+        )?);
+        for stage_transcript in &stage_transcripts {
+            transcript.join(stage_transcript);
+        }
+        halt::verify_halt(
+            &commitments,
+            &program_io,
+            trace_length,
             &mut opening_accumulator,
             &mut transcript,
-        )?;
+        );
 
-        // Batch-verify all openings
-        opening_accumulator.reduce_and_verify(
-            &preprocessing.generators,
-            proof.opening_proof,
-            &mut transcript,
-        )?;
+        opening_accumulator
+            .reduce_and_verify(
+                &preprocessing.generators,
+                read_proof_section(&mut reader)?,
+                &mut transcript,
+            )
+            .map_err(JoltError::Pcs)?;
 
         Ok(())
     }
 
-    #[tracing::instrument(skip_all)]
-    fn verify_instruction_lookups<'a>(
-        preprocessing: &InstructionLookupsPreprocessing<C, F>,
-        generators: &PCS::Setup,
-        proof: InstructionLookupsProof<C, M, F, PCS, Self::InstructionSet, Self::Subtables>,
-        commitments: &'a JoltCommitments<PCS>,
-        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
-        transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
-        InstructionLookupsProof::verify(
-            preprocessing,
-            generators,
-            proof,
-            commitments,
-            opening_accumulator,
-            transcript,
-        )
-    }
-
-    #[tracing::instrument(skip_all)]
-    fn verify_bytecode<'a>(
-        preprocessing: &BytecodePreprocessing<F>,
-        generators: &PCS::Setup,
-        proof: BytecodeProof<F, PCS>,
-        commitments: &'a JoltCommitments<PCS>,
-        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
-        transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
-        BytecodeProof::verify_memory_checking(
-            preprocessing,
-            generators,
-            proof,
-            &commitments.bytecode,
-            commitments,
-            opening_accumulator,
-            transcript,
-        )
+    /// Regenerates the R1CS constraints and uniform Spartan key for an execution with
+    /// `program_io` and `trace_length`. They only depend on the padded trace length and the memory
+    /// layout.
+    fn r1cs_preprocessing(
+        program_io: &JoltDevice,
+        trace_length: usize,
+    ) -> R1CSPreprocessing<C, <Self::Constraints as R1CSConstraints<C, F>>::Inputs, F> {
+        let padded_trace_length = trace_length.next_power_of_two();
+        let memory_start = RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset;
+        let r1cs_builder =
+            Self::Constraints::construct_constraints(padded_trace_length, memory_start);
+        let spartan_key = spartan::UniformSpartanProof::setup(&r1cs_builder, padded_trace_length);
+        R1CSPreprocessing {
+            builder: r1cs_builder,
+            key: spartan_key,
+        }
     }
 
-    #[tracing::instrument(skip_all)]
-    fn verify_memory<'a>(
-        preprocessing: &mut ReadWriteMemoryPreprocessing,
-        generators: &PCS::Setup,
-        proof: ReadWriteMemoryProof<F, PCS>,
-        commitment: &'a JoltCommitments<PCS>,
-        program_io: JoltDevice,
-        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
+    /// Absorbs the public parameters, program I/O, Spartan key digest, and commitments into the
+    /// transcript.
+    fn verifier_preamble(
         transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
-        assert!(program_io.inputs.len() <= program_io.memory_layout.max_input_size as usize);
-        assert!(program_io.outputs.len() <= program_io.memory_layout.max_output_size as usize);
-        preprocessing.program_io = Some(program_io);
-
-        ReadWriteMemoryProof::verify(
-            proof,
-            generators,
-            preprocessing,
-            commitment,
-            opening_accumulator,
-            transcript,
-        )
-    }
+        program_io: &JoltDevice,
+        trace_length: usize,
+        r1cs_preprocessing: &R1CSPreprocessing<
+            C,
+            <Self::Constraints as R1CSConstraints<C, F>>::Inputs,
+            F,
+        >,
+        commitments: &JoltCommitments<PCS>,
+    ) {
+        Self::fiat_shamir_preamble(transcript, program_io, trace_length);
+        transcript.append_scalar(&r1cs_preprocessing.key.vk_digest);
 
-    #[tracing::instrument(skip_all)]
-    fn verify_r1cs<'a>(
-        proof: R1CSProof<C, <Self::Constraints as R1CSConstraints<C, F>>::Inputs, F>,
-        commitments: &'a JoltCommitments<PCS>,
-        opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
-        transcript: &mut ProofTranscript,
-    ) -> Result<(), ProofVerifyError> {
-        proof
-            .verify(commitments, opening_accumulator, transcript)
-            .map_err(|e| ProofVerifyError::SpartanError(e.to_string()))
+        commitments
+            .read_write_values()
+            .iter()
+            .for_each(|value| value.append_to_transcript(transcript));
+        commitments
+            .init_final_values()
+            .iter()
+            .for_each(|value| value.append_to_transcript(transcript));
     }
 
     fn fiat_shamir_preamble(
@@ -649,14 +1254,176 @@ pub trait Jolt<F: JoltField, PCS: CommitmentScheme<Field = F>, const C: usize, c
         transcript.append_u64(Self::Subtables::COUNT as u64);
         transcript.append_u64(program_io.memory_layout.max_input_size);
         transcript.append_u64(program_io.memory_layout.max_output_size);
+        transcript.append_u64(program_io.memory_layout.max_blob_size);
         transcript.append_bytes(&program_io.inputs);
+        // The blobs themselves are bound through their hashes
+        transcript.append_u64(program_io.blobs.len() as u64);
+        for hash in program_io.blobs.hashes() {
+            transcript.append_bytes(hash);
+        }
         transcript.append_bytes(&program_io.outputs);
         transcript.append_u64(program_io.panic as u64);
+        transcript.append_u64(program_io.exceeded_max_cycles.is_some() as u64);
+        transcript.append_u64(program_io.exceeded_max_cycles.unwrap_or(0));
+    }
+}
+
+/// Replaces each instruction with its virtual sequence, if it has one.
+pub(crate) fn expand_virtual_sequences(bytecode: Vec<ELFInstruction>) -> Vec<ELFInstruction> {
+    bytecode
+        .into_iter()
+        .flat_map(|instruction| match instruction.opcode {
+            RV32IM::MULH => MULHInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::MULHSU => MULHSUInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::DIV => DIVInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::DIVU => DIVUInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::REM => REMInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::REMU => REMUInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::ORN => ORNInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::MIN => MINInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::MAX => MAXInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::MINU => MINUInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::MAXU => MAXUInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::SEXT_B => SEXTBInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::SEXT_H => SEXTHInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::ZEXT_H => ZEXTHInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::ROL => ROLInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::ROR => RORInstruction::<32>::virtual_sequence(instruction),
+            RV32IM::RORI => RORIInstruction::<32>::virtual_sequence(instruction),
+            _ => vec![instruction],
+        })
+        .collect()
+}
+
+fn new_transcript(
+    namespace: Option<&TranscriptNamespace>,
+    version: TranscriptVersion,
+) -> ProofTranscript {
+    let mut transcript = ProofTranscript::new(b"Jolt transcript").with_version(version);
+    if let Some(namespace) = namespace {
+        namespace.append_to_transcript(&mut transcript);
+    }
+    transcript
+}
+
+/// Checks that a proof was generated for the application the verifier's preprocessing is bound
+/// to. A proof claiming the wrong namespace would fail verification anyway, since the namespace
+/// is absorbed into the transcript, but this gives a more useful error.
+fn check_namespace<const C: usize, F, PCS>(
+    preprocessing: &JoltPreprocessing<C, F, PCS>,
+    namespace: Option<&TranscriptNamespace>,
+) -> Result<(), JoltError>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let expected = preprocessing.metadata.namespace.as_ref();
+    if namespace == expected {
+        return Ok(());
+    }
+    let describe = |namespace: Option<&TranscriptNamespace>| match namespace {
+        Some(namespace) => format!("application {namespace}"),
+        None => "no application namespace".to_string(),
+    };
+    Err(JoltError::TranscriptMismatch(format!(
+        "{}, but the verifier expects {}",
+        describe(namespace),
+        describe(expected)
+    )))
+}
+
+/// Checks that a proof was generated with the same preprocessing as the verifier's, for a guest
+/// with the same memory layout. See [`JoltPreprocessing::digest`].
+fn check_preprocessing_digest<const C: usize, F, PCS>(
+    preprocessing: &JoltPreprocessing<C, F, PCS>,
+    digest: &[u8; 32],
+    memory_layout: &MemoryLayout,
+) -> Result<(), JoltError>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let expected = preprocessing.digest(memory_layout);
+    if *digest == expected {
+        return Ok(());
+    }
+    Err(JoltError::TranscriptMismatch(format!(
+        "preprocessing with digest {}, but the verifier's is {}",
+        hex::encode(digest),
+        hex::encode(expected)
+    )))
+}
+
+/// Checks the public inputs claimed by a proof against the verifier's preprocessing, before any
+/// of them are absorbed into the transcript.
+fn check_public_inputs<const C: usize, F, PCS>(
+    preprocessing: &JoltPreprocessing<C, F, PCS>,
+    trace_length: usize,
+    program_io: &JoltDevice,
+) -> Result<(), JoltError>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    let max_trace_length = preprocessing.metadata.max_trace_length.next_power_of_two();
+    if trace_length.next_power_of_two() > max_trace_length {
+        return Err(JoltError::TranscriptMismatch(format!(
+            "trace length {trace_length}, but the preprocessing supports at most {max_trace_length}"
+        )));
+    }
+    if let Some(max_cycles) = program_io.exceeded_max_cycles {
+        if preprocessing.metadata.max_cycles != Some(max_cycles) {
+            return Err(JoltError::TranscriptMismatch(format!(
+                "an execution halted after {max_cycles} cycles, but the verifier's max_cycles is {:?}",
+                preprocessing.metadata.max_cycles
+            )));
+        }
+        // Every RISC-V instruction is at least one row of the trace. The exact number of
+        // instructions is bound by the proof, see `halt`.
+        if (trace_length as u64) < max_cycles {
+            return Err(JoltError::TranscriptMismatch(format!(
+                "an execution halted after {max_cycles} cycles, but its trace length is {trace_length}"
+            )));
+        }
+    }
+    let memory_layout = &program_io.memory_layout;
+    if program_io.inputs.len() > memory_layout.max_input_size as usize {
+        return Err(JoltError::InvalidInputSize {
+            size: program_io.inputs.len(),
+            max_size: memory_layout.max_input_size,
+        });
+    }
+    if program_io.outputs.len() > memory_layout.max_output_size as usize {
+        return Err(JoltError::InvalidOutputSize {
+            size: program_io.outputs.len(),
+            max_size: memory_layout.max_output_size,
+        });
+    }
+    // The guest's initial memory includes the blobs, so the verifier needs their data
+    if !program_io.blobs.is_attached() {
+        return Err(JoltError::MissingBlobs);
+    }
+    if program_io.blobs.region_size() > memory_layout.max_blob_size {
+        return Err(JoltError::InvalidBlobSize {
+            size: program_io.blobs.region_size(),
+            max_size: memory_layout.max_blob_size,
+        });
     }
+    Ok(())
 }
 
 pub mod bytecode;
+pub mod halt;
 pub mod instruction_lookups;
+pub mod preprocessing;
+pub mod progress;
+pub mod proof_size;
 pub mod read_write_memory;
 pub mod rv32i_vm;
+pub mod scheduler;
+pub mod stage;
+pub mod streaming;
 pub mod timestamp_range_check;
+pub mod verifier;
+pub mod witness;
+pub mod witness_audit;
@@ -0,0 +1,401 @@
+//! Self-describing serialization for [`JoltPreprocessing`].
+//!
+//! A preprocessing file starts with [`PREPROCESSING_MAGIC`] and a [`PreprocessingMetadata`]
+//! header recording how it was generated. Loading checks the header against the VM it is being
+//! loaded into before deserializing the (large) remainder of the file, so a file produced by a
+//! different crate version, curve, commitment scheme, or instruction set is rejected with a
+//! descriptive error instead of surfacing later as an opaque verification failure.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::MemoryLayout;
+use sha3::{Digest, Keccak256, Sha3_256};
+
+use super::bytecode::BytecodeRow;
+use super::JoltPreprocessing;
+use crate::field::JoltField;
+use crate::jolt::instruction::JoltInstructionSet;
+use crate::jolt::subtable::JoltSubtableSet;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::utils::errors::PreprocessingError;
+use crate::utils::transcript::{TranscriptNamespace, TranscriptVersion};
+
+pub const PREPROCESSING_MAGIC: [u8; 8] = *b"JOLTPREP";
+
+/// Fingerprint of a guest program: a SHA3-256 digest of its bytecode (as committed to by the
+/// preprocessing, i.e. after expanding virtual sequences) and initial memory image. Unlike a
+/// hash of the ELF file, it doesn't depend on symbols, debug info, or section layout, so two
+/// ELFs with the same code and data have the same ID.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProgramId(pub [u8; 32]);
+
+impl ProgramId {
+    pub fn new(bytecode: &[BytecodeRow], memory_init: &[(u64, u8)]) -> Self {
+        let mut memory_init = memory_init.to_vec();
+        memory_init.sort_unstable();
+
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"Jolt program id v1");
+        hasher.update((bytecode.len() as u64).to_le_bytes());
+        for row in bytecode {
+            hasher.update(row.canonical_bytes());
+        }
+        hasher.update((memory_init.len() as u64).to_le_bytes());
+        for (address, byte) in memory_init {
+            hasher.update(address.to_le_bytes());
+            hasher.update([byte]);
+        }
+        Self(hasher.finalize().into())
+    }
+}
+
+impl fmt::Display for ProgramId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ProgramId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProgramId({self})")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PreprocessingMetadata {
+    /// Version of `jolt-core` that generated the preprocessing.
+    pub crate_version: String,
+    /// The scalar field's [`JoltField::NAME`], which also determines the curve.
+    pub field: String,
+    /// The polynomial commitment scheme's [`CommitmentScheme::NAME`].
+    pub pcs: String,
+    /// The instruction set's [`JoltInstructionSet::NAME`].
+    pub instruction_set: String,
+    /// The subtable set's [`JoltSubtableSet::NAME`].
+    pub subtables: String,
+    /// Number of chunks each lookup operand is decomposed into (`C`).
+    pub num_chunks: usize,
+    /// Size of each subtable (`M`).
+    pub subtable_size: usize,
+    pub max_bytecode_size: usize,
+    pub max_memory_address: usize,
+    pub max_trace_length: usize,
+    /// The guest program the preprocessing was generated for.
+    pub program_id: ProgramId,
+    /// The application the preprocessing's proofs are bound to, if any. Set with
+    /// [`JoltPreprocessing::with_namespace`].
+    pub namespace: Option<TranscriptNamespace>,
+    /// The guest's cycle limit, if any. Set with [`JoltPreprocessing::with_max_cycles`].
+    pub max_cycles: Option<u64>,
+    /// How the proofs' transcripts absorb vectors. Set with
+    /// [`JoltPreprocessing::with_transcript_version`].
+    pub transcript_version: TranscriptVersion,
+    /// SHA3-256 digest of all of the above, used to detect corrupted headers.
+    pub config_digest: [u8; 32],
+}
+
+impl PreprocessingMetadata {
+    pub fn new<const C: usize, const M: usize, F, PCS, InstructionSet, Subtables>(
+        program_id: ProgramId,
+        max_bytecode_size: usize,
+        max_memory_address: usize,
+        max_trace_length: usize,
+    ) -> Self
+    where
+        F: JoltField,
+        PCS: CommitmentScheme,
+        InstructionSet: JoltInstructionSet,
+        Subtables: JoltSubtableSet<F>,
+    {
+        let mut metadata = Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            field: F::NAME.to_string(),
+            pcs: PCS::NAME.to_string(),
+            instruction_set: InstructionSet::NAME.to_string(),
+            subtables: Subtables::NAME.to_string(),
+            num_chunks: C,
+            subtable_size: M,
+            max_bytecode_size,
+            max_memory_address,
+            max_trace_length,
+            program_id,
+            namespace: None,
+            max_cycles: None,
+            transcript_version: TranscriptVersion::V1,
+            config_digest: [0; 32],
+        };
+        metadata.config_digest = metadata.compute_digest();
+        metadata
+    }
+
+    fn compute_digest(&self) -> [u8; 32] {
+        let mut unhashed = self.clone();
+        unhashed.config_digest = [0; 32];
+        let mut bytes = Vec::new();
+        unhashed.serialize_compressed(&mut bytes).unwrap();
+        Sha3_256::digest(&bytes).into()
+    }
+
+    /// Checks that preprocessing described by this header can be used by a VM with the given
+    /// parameters.
+    pub fn check<const C: usize, const M: usize, F, PCS, InstructionSet, Subtables>(
+        &self,
+    ) -> Result<(), PreprocessingError>
+    where
+        F: JoltField,
+        PCS: CommitmentScheme,
+        InstructionSet: JoltInstructionSet,
+        Subtables: JoltSubtableSet<F>,
+    {
+        if self.compute_digest() != self.config_digest {
+            return Err(PreprocessingError::CorruptedMetadata);
+        }
+
+        let expected = Self::new::<C, M, F, PCS, InstructionSet, Subtables>(
+            self.program_id,
+            self.max_bytecode_size,
+            self.max_memory_address,
+            self.max_trace_length,
+        );
+        let parameters = [
+            (
+                "crate version",
+                &expected.crate_version,
+                &self.crate_version,
+            ),
+            ("field", &expected.field, &self.field),
+            ("commitment scheme", &expected.pcs, &self.pcs),
+            (
+                "instruction set",
+                &expected.instruction_set,
+                &self.instruction_set,
+            ),
+            ("subtables", &expected.subtables, &self.subtables),
+        ];
+        for (parameter, expected, found) in parameters {
+            if expected != found {
+                return Err(PreprocessingError::Incompatible {
+                    parameter,
+                    expected: expected.clone(),
+                    found: found.clone(),
+                });
+            }
+        }
+        let table_parameters = [
+            ("C", expected.num_chunks, self.num_chunks),
+            ("M", expected.subtable_size, self.subtable_size),
+        ];
+        for (parameter, expected, found) in table_parameters {
+            if expected != found {
+                return Err(PreprocessingError::Incompatible {
+                    parameter,
+                    expected: expected.to_string(),
+                    found: found.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads and validates the magic bytes and metadata header, leaving `reader` positioned at
+    /// the start of the preprocessing itself.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, PreprocessingError> {
+        let mut magic = [0u8; PREPROCESSING_MAGIC.len()];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| PreprocessingError::InvalidMagic)?;
+        if magic != PREPROCESSING_MAGIC {
+            return Err(PreprocessingError::InvalidMagic);
+        }
+        Ok(Self::deserialize_compressed(reader)?)
+    }
+}
+
+/// How much of the bytecode capacity of a preprocessing its program uses. See
+/// [`JoltPreprocessing::bytecode_report`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BytecodeReport {
+    /// Number of instructions in the bytecode after expanding virtual sequences, including the
+    /// no-op the preprocessing prepends.
+    pub num_rows: usize,
+    /// Number of distinct instructions among them, ignoring their addresses. Jump tables and
+    /// vtables make this much smaller than `num_rows`.
+    pub num_distinct_rows: usize,
+    /// `num_rows` padded to a power of two, i.e. the size of the bytecode polynomials.
+    pub code_size: usize,
+    /// The bytecode size the generators were set up for.
+    pub max_bytecode_size: usize,
+    /// Approximate number of bytes of memory used by the bytecode preprocessing.
+    pub size_in_bytes: usize,
+}
+
+impl BytecodeReport {
+    /// The largest `code_size` the generators can commit to.
+    pub fn capacity(&self) -> usize {
+        // Account for no-op prepended to bytecode
+        (self.max_bytecode_size + 1).next_power_of_two()
+    }
+
+    /// Whether the program fits in the bytecode size the generators were set up for. A program
+    /// that doesn't may fail to prove; preprocess it with a larger `max_bytecode_size`.
+    pub fn fits(&self) -> bool {
+        self.code_size <= self.capacity()
+    }
+}
+
+impl<const C: usize, F, PCS> JoltPreprocessing<C, F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    /// The guest program this preprocessing was generated for. A verifier should check it
+    /// against the ID of the program it expects, e.g. one computed with `Program::program_id`
+    /// (with the `host` feature) from a reproducible build.
+    pub fn program_id(&self) -> ProgramId {
+        self.metadata.program_id
+    }
+
+    /// The size of the program's bytecode and the limit it is preprocessed for.
+    pub fn bytecode_report(&self) -> BytecodeReport {
+        BytecodeReport {
+            num_rows: self.bytecode.num_rows(),
+            num_distinct_rows: self.bytecode.num_distinct_rows(),
+            code_size: self.bytecode.code_size(),
+            max_bytecode_size: self.metadata.max_bytecode_size,
+            size_in_bytes: self.bytecode.size_in_bytes(),
+        }
+    }
+
+    /// Short identifier of the verifier's view of this preprocessing for a guest with
+    /// `memory_layout`, for registering the guest with a smart contract. Proofs carry it (see
+    /// [`JoltProof::preprocessing_digest`](super::JoltProof::preprocessing_digest)), and
+    /// [`Jolt::verify`](super::Jolt::verify) rejects those generated with another.
+    ///
+    /// It is the Keccak-256 hash of:
+    ///
+    /// ```text
+    /// "Jolt verifier preprocessing v1" | program ID | C: u64 | M: u64 | max_bytecode_size: u64
+    /// | max_memory_address: u64 | max_trace_length: u64 | namespace | max_cycles
+    /// | transcript version | PCS name length: u64 | PCS name | verifier key length: u64
+    /// | verifier key | memory layout
+    /// ```
+    ///
+    /// where integers are little-endian, the program ID (a hash of the bytecode and initial
+    /// memory) stands in for a commitment to the bytecode, the verifier key is encoded by
+    /// [`CommitmentScheme::verifier_key_bytes`], and the remaining fields have their compressed
+    /// arkworks encoding. Unlike [`PreprocessingMetadata::config_digest`], it doesn't depend on
+    /// the crate version or Rust type names.
+    pub fn digest(&self, memory_layout: &MemoryLayout) -> [u8; 32] {
+        let metadata = &self.metadata;
+        let mut hasher = Keccak256::new();
+        hasher.update(b"Jolt verifier preprocessing v1");
+        hasher.update(metadata.program_id.0);
+        for parameter in [
+            metadata.num_chunks,
+            metadata.subtable_size,
+            metadata.max_bytecode_size,
+            metadata.max_memory_address,
+            metadata.max_trace_length,
+        ] {
+            hasher.update((parameter as u64).to_le_bytes());
+        }
+
+        let mut bytes = vec![];
+        metadata.namespace.serialize_compressed(&mut bytes).unwrap();
+        metadata
+            .max_cycles
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        metadata
+            .transcript_version
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        hasher.update(&bytes);
+
+        for bytes in [
+            PCS::protocol_name().to_vec(),
+            PCS::verifier_key_bytes(&self.generators),
+        ] {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(&bytes);
+        }
+
+        let mut bytes = vec![];
+        memory_layout.serialize_compressed(&mut bytes).unwrap();
+        hasher.update(&bytes);
+        hasher.finalize().into()
+    }
+
+    /// Binds the proofs generated and verified with this preprocessing to `namespace`. The
+    /// prover and verifier must use the same namespace.
+    pub fn with_namespace(mut self, namespace: TranscriptNamespace) -> Self {
+        self.metadata.namespace = Some(namespace);
+        self.metadata.config_digest = self.metadata.compute_digest();
+        self
+    }
+
+    /// Accepts proofs of executions that were halted after `max_cycles` RISC-V instructions,
+    /// which claim so in `JoltDevice::exceeded_max_cycles`.
+    /// Must match the `max_cycles` the guest was traced with.
+    pub fn with_max_cycles(mut self, max_cycles: u64) -> Self {
+        self.metadata.max_cycles = Some(max_cycles);
+        self.metadata.config_digest = self.metadata.compute_digest();
+        self
+    }
+
+    /// Absorbs vectors into the transcripts of the proofs generated and verified with this
+    /// preprocessing according to `version`. [`TranscriptVersion::V2`] makes absorbing long
+    /// vectors cheaper, but the Solidity verifier only supports [`TranscriptVersion::V1`].
+    pub fn with_transcript_version(mut self, version: TranscriptVersion) -> Self {
+        self.metadata.transcript_version = version;
+        self.metadata.config_digest = self.metadata.compute_digest();
+        self
+    }
+
+    pub fn serialize_to_writer<W: Write>(&self, mut writer: W) -> Result<(), PreprocessingError> {
+        writer.write_all(&PREPROCESSING_MAGIC)?;
+        self.metadata.serialize_compressed(&mut writer)?;
+        self.generators.serialize_compressed(&mut writer)?;
+        self.instruction_lookups.serialize_compressed(&mut writer)?;
+        self.bytecode.serialize_compressed(&mut writer)?;
+        self.read_write_memory.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn serialize_to_bytes(&self) -> Result<Vec<u8>, PreprocessingError> {
+        let mut bytes = Vec::new();
+        self.serialize_to_writer(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), PreprocessingError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.serialize_to_writer(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Deserializes the preprocessing that follows a header which has already been read (and
+    /// checked) with [`PreprocessingMetadata::read`].
+    pub(crate) fn deserialize_after_metadata<R: Read>(
+        metadata: PreprocessingMetadata,
+        mut reader: R,
+    ) -> Result<Self, PreprocessingError> {
+        Ok(Self {
+            metadata,
+            generators: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            instruction_lookups: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            bytecode: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+            read_write_memory: CanonicalDeserialize::deserialize_compressed(&mut reader)?,
+        })
+    }
+}
@@ -0,0 +1,225 @@
+//! Prover progress reporting and cancellation.
+//!
+//! A [`ProverObserver`] registered on the [`SchedulerConfig`](super::scheduler::SchedulerConfig)
+//! is notified as each step of the proof (witness generation, commitments, stage proofs, and the
+//! opening proof) starts and finishes, along with an estimate of how much of the proof is done.
+//! A [`CancellationToken`] lets another thread stop the prover: the step in flight runs to
+//! completion, no further steps are started, and proving returns
+//! [`JoltError::Cancelled`](crate::utils::errors::JoltError::Cancelled).
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::utils::errors::JoltError;
+
+/// Rough share of proving time taken by each step of a proof, used to estimate the fraction of
+/// the proof that is complete. Measured on RV32I traces of 2^20 to 2^24 steps with HyperKZG.
+pub(crate) const PROVER_STEP_WEIGHTS: &[(&str, u32)] = &[
+    ("Instruction lookups witness", 8),
+    ("Read-write memory witness", 5),
+    ("Instruction lookups commitment", 12),
+    ("Bytecode witness", 2),
+    ("Read-write memory commitment", 8),
+    ("R1CS witness", 4),
+    ("Bytecode commitment", 3),
+    ("R1CS aux witness", 4),
+    ("R1CS commitment", 6),
+    ("Bytecode proof", 5),
+    ("Instruction lookups proof", 15),
+    ("Read-write memory proof", 12),
+    ("R1CS proof", 10),
+    ("Opening proof", 6),
+];
+
+/// A step of the proof starting or finishing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProverEvent {
+    StageStarted {
+        name: &'static str,
+        /// Estimated fraction of the proof complete, in `[0, 1]`.
+        fraction_complete: f64,
+    },
+    StageFinished {
+        name: &'static str,
+        duration: Duration,
+        /// Estimated fraction of the proof complete, in `[0, 1]`.
+        fraction_complete: f64,
+    },
+}
+
+impl ProverEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::StageStarted { name, .. } | Self::StageFinished { name, .. } => name,
+        }
+    }
+
+    pub fn fraction_complete(&self) -> f64 {
+        match self {
+            Self::StageStarted {
+                fraction_complete, ..
+            }
+            | Self::StageFinished {
+                fraction_complete, ..
+            } => *fraction_complete,
+        }
+    }
+}
+
+/// Receives [`ProverEvent`]s. Independent stages are proven concurrently, so events may arrive
+/// from several threads at once, and should be handled quickly.
+pub trait ProverObserver: Send + Sync {
+    fn on_event(&self, event: &ProverEvent);
+}
+
+impl<F: Fn(&ProverEvent) + Send + Sync> ProverObserver for F {
+    fn on_event(&self, event: &ProverEvent) {
+        self(event)
+    }
+}
+
+/// A shared flag that asks the prover to stop. Clones refer to the same flag.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CancellationToken")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Emits [`ProverEvent`]s for the steps of a proof and answers whether it has been cancelled.
+#[derive(Default)]
+pub(crate) struct ProgressReporter {
+    observer: Option<Arc<dyn ProverObserver>>,
+    cancellation: Option<CancellationToken>,
+    /// Total weight of the finished steps
+    completed: AtomicU32,
+}
+
+impl ProgressReporter {
+    pub fn new(
+        observer: Option<Arc<dyn ProverObserver>>,
+        cancellation: Option<CancellationToken>,
+    ) -> Self {
+        Self {
+            observer,
+            cancellation,
+            completed: AtomicU32::new(0),
+        }
+    }
+
+    fn fraction(completed: u32) -> f64 {
+        let total: u32 = PROVER_STEP_WEIGHTS.iter().map(|(_, weight)| weight).sum();
+        (completed as f64 / total as f64).min(1.0)
+    }
+
+    pub fn started(&self, name: &'static str) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(&ProverEvent::StageStarted {
+                name,
+                fraction_complete: Self::fraction(self.completed.load(Ordering::Acquire)),
+            });
+        }
+    }
+
+    pub fn finished(&self, name: &'static str, duration: Duration) {
+        let weight = PROVER_STEP_WEIGHTS
+            .iter()
+            .find(|(step, _)| *step == name)
+            .map_or(0, |(_, weight)| *weight);
+        let completed = self.completed.fetch_add(weight, Ordering::AcqRel) + weight;
+        if let Some(observer) = &self.observer {
+            observer.on_event(&ProverEvent::StageFinished {
+                name,
+                duration,
+                fraction_complete: Self::fraction(completed),
+            });
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Returns [`JoltError::Cancelled`] if the proof has been cancelled.
+    pub fn check_cancelled(&self) -> Result<(), JoltError> {
+        if self.is_cancelled() {
+            return Err(JoltError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn fraction_complete() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let observer = {
+            let events = events.clone();
+            move |event: &ProverEvent| events.lock().unwrap().push(event.clone())
+        };
+        let progress = ProgressReporter::new(Some(Arc::new(observer)), None);
+        for &(name, _) in PROVER_STEP_WEIGHTS {
+            progress.started(name);
+            progress.finished(name, Duration::ZERO);
+        }
+        progress.finished("Not a step", Duration::ZERO);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2 * PROVER_STEP_WEIGHTS.len() + 1);
+        assert_eq!(events[0].fraction_complete(), 0.0);
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].fraction_complete() <= pair[1].fraction_complete()));
+        assert_eq!(events.last().unwrap().fraction_complete(), 1.0);
+        assert!(matches!(
+            events[events.len() - 2],
+            ProverEvent::StageFinished {
+                name: "Opening proof",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn cancellation_is_shared() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert!(!CancellationToken::new().is_cancelled());
+
+        let progress = ProgressReporter::new(None, Some(token));
+        assert!(matches!(
+            progress.check_cancelled(),
+            Err(JoltError::Cancelled)
+        ));
+        assert!(ProgressReporter::default().check_cancelled().is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+//! Size of a Jolt proof by component, and a rough estimate of the gas an EVM verifier would
+//! spend on it.
+//!
+//! [`JoltProof::size_breakdown`] reports the compressed and uncompressed size of the commitments,
+//! of each instance's sumchecks and claimed openings, and of the batched opening proof, along
+//! with the calldata gas of passing them uncompressed (as the Solidity verifiers take them).
+//! [`ProofSizeBreakdown::estimate_evm_gas`] adds the precompile costs of checking the opening
+//! proof, so the on-chain cost of a program can be judged before deploying anything.
+
+use std::fmt;
+
+use ark_serialize::CanonicalSerialize;
+
+use super::{JoltCommitments, JoltProof};
+use crate::field::JoltField;
+use crate::jolt::instruction::JoltInstructionSet;
+use crate::jolt::subtable::JoltSubtableSet;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::r1cs::inputs::ConstraintInput;
+
+/// Base cost of a transaction.
+pub const TRANSACTION_GAS: u64 = 21_000;
+/// `ECADD` precompile (EIP-1108).
+pub const ECADD_GAS: u64 = 150;
+/// `ECMUL` precompile (EIP-1108).
+pub const ECMUL_GAS: u64 = 6_000;
+/// `ECPAIRING` precompile (EIP-1108): a base cost plus a cost per pair.
+pub const PAIRING_BASE_GAS: u64 = 45_000;
+pub const PAIRING_PER_PAIR_GAS: u64 = 34_000;
+/// Pairs in the final check of a HyperKZG opening.
+const HYPERKZG_PAIRS: u64 = 2;
+/// Rough allowance per 32-byte word of the proof for absorbing it into the Keccak transcript and
+/// for the field arithmetic of the sumcheck and grand product verifiers that consume it.
+pub const EXECUTION_GAS_PER_WORD: u64 = 250;
+
+/// The size of a part of a [`JoltProof`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentSize {
+    pub name: &'static str,
+    pub compressed_bytes: usize,
+    pub uncompressed_bytes: usize,
+    /// Calldata gas (EIP-2028) of the uncompressed serialization: 4 per zero byte and 16 per
+    /// non-zero byte.
+    pub calldata_gas: u64,
+}
+
+impl ComponentSize {
+    fn of(name: &'static str, item: &impl CanonicalSerialize) -> Self {
+        let mut uncompressed = Vec::with_capacity(item.uncompressed_size());
+        item.serialize_uncompressed(&mut uncompressed).unwrap();
+        Self {
+            name,
+            compressed_bytes: item.compressed_size(),
+            uncompressed_bytes: uncompressed.len(),
+            calldata_gas: uncompressed
+                .iter()
+                .map(|&byte| if byte == 0 { 4 } else { 16 })
+                .sum(),
+        }
+    }
+
+    fn plus(self, other: Self) -> Self {
+        Self {
+            compressed_bytes: self.compressed_bytes + other.compressed_bytes,
+            uncompressed_bytes: self.uncompressed_bytes + other.uncompressed_bytes,
+            calldata_gas: self.calldata_gas + other.calldata_gas,
+            ..self
+        }
+    }
+
+    fn minus(self, other: &Self) -> Self {
+        Self {
+            compressed_bytes: self.compressed_bytes - other.compressed_bytes,
+            uncompressed_bytes: self.uncompressed_bytes - other.uncompressed_bytes,
+            calldata_gas: self.calldata_gas - other.calldata_gas,
+            ..self
+        }
+    }
+
+    /// The number of curve points, assuming they are BN254 G1 points, which take 32 bytes
+    /// compressed and 64 uncompressed, and that everything else takes the same space either way.
+    pub fn num_points(&self) -> usize {
+        (self.uncompressed_bytes - self.compressed_bytes) / 32
+    }
+}
+
+/// Sizes of the parts of a [`JoltProof`] and its commitments, in the order the verifier reads
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofSizeBreakdown {
+    pub commitments: ComponentSize,
+    /// The namespace, preprocessing digest, trace length, and program inputs and outputs.
+    pub program_io: ComponentSize,
+    pub bytecode: ComponentSize,
+    pub instruction_lookups: ComponentSize,
+    pub read_write_memory: ComponentSize,
+    pub r1cs: ComponentSize,
+    /// The sumcheck reducing every claimed opening to a single one, and its claims.
+    pub opening_sumcheck: ComponentSize,
+    /// The PCS proof of the reduced opening.
+    pub pcs_opening: ComponentSize,
+}
+
+impl ProofSizeBreakdown {
+    pub fn components(&self) -> [&ComponentSize; 8] {
+        [
+            &self.commitments,
+            &self.program_io,
+            &self.bytecode,
+            &self.instruction_lookups,
+            &self.read_write_memory,
+            &self.r1cs,
+            &self.opening_sumcheck,
+            &self.pcs_opening,
+        ]
+    }
+
+    pub fn compressed_bytes(&self) -> usize {
+        self.components().iter().map(|c| c.compressed_bytes).sum()
+    }
+
+    pub fn uncompressed_bytes(&self) -> usize {
+        self.components().iter().map(|c| c.uncompressed_bytes).sum()
+    }
+
+    /// Estimates the gas of verifying the proof in a transaction to a Solidity verifier using
+    /// HyperKZG over BN254. The precompile costs are exact given the number of points: the
+    /// opening proof is checked with one pairing check of two pairs, and every commitment and
+    /// point of the opening proof costs an `ECMUL` and an `ECADD` when they are combined. The
+    /// remaining execution is estimated at [`EXECUTION_GAS_PER_WORD`] per word of the proof.
+    pub fn estimate_evm_gas(&self) -> EvmGasEstimate {
+        let num_points = (self.commitments.num_points() + self.pcs_opening.num_points()) as u64;
+        let words = self
+            .components()
+            .iter()
+            .map(|c| c.uncompressed_bytes.div_ceil(32) as u64)
+            .sum::<u64>();
+        EvmGasEstimate {
+            transaction: TRANSACTION_GAS,
+            calldata: self.components().iter().map(|c| c.calldata_gas).sum(),
+            pairing: PAIRING_BASE_GAS + HYPERKZG_PAIRS * PAIRING_PER_PAIR_GAS,
+            elliptic_curve: num_points * (ECMUL_GAS + ECADD_GAS),
+            execution: words * EXECUTION_GAS_PER_WORD,
+        }
+    }
+}
+
+impl fmt::Display for ProofSizeBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<22} {:>12} {:>14} {:>12}",
+            "component", "compressed", "uncompressed", "calldata gas"
+        )?;
+        for c in self.components() {
+            writeln!(
+                f,
+                "{:<22} {:>12} {:>14} {:>12}",
+                c.name, c.compressed_bytes, c.uncompressed_bytes, c.calldata_gas
+            )?;
+        }
+        write!(
+            f,
+            "{:<22} {:>12} {:>14}",
+            "total",
+            self.compressed_bytes(),
+            self.uncompressed_bytes()
+        )
+    }
+}
+
+/// Gas of verifying a proof on-chain, by kind of cost. See
+/// [`ProofSizeBreakdown::estimate_evm_gas`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvmGasEstimate {
+    pub transaction: u64,
+    pub calldata: u64,
+    pub pairing: u64,
+    /// `ECMUL` and `ECADD` precompiles.
+    pub elliptic_curve: u64,
+    /// Transcript hashing and field arithmetic.
+    pub execution: u64,
+}
+
+impl EvmGasEstimate {
+    pub fn total(&self) -> u64 {
+        self.transaction + self.calldata + self.pairing + self.elliptic_curve + self.execution
+    }
+}
+
+impl<const C: usize, const M: usize, I, F, PCS, InstructionSet, Subtables>
+    JoltProof<C, M, I, F, PCS, InstructionSet, Subtables>
+where
+    I: ConstraintInput,
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+    Subtables: JoltSubtableSet<F>,
+{
+    /// Sizes of the proof's components, including the `commitments` it is verified against.
+    pub fn size_breakdown(&self, commitments: &JoltCommitments<PCS>) -> ProofSizeBreakdown {
+        let pcs_opening = ComponentSize::of("PCS opening", self.opening_proof.pcs_proof());
+        ProofSizeBreakdown {
+            commitments: ComponentSize::of("commitments", commitments),
+            program_io: ComponentSize::of("program I/O", &self.program_io)
+                .plus(ComponentSize::of("", &self.namespace))
+                .plus(ComponentSize::of("", &self.preprocessing_digest))
+                .plus(ComponentSize::of("", &self.trace_length)),
+            bytecode: ComponentSize::of("bytecode", &self.bytecode),
+            instruction_lookups: ComponentSize::of(
+                "instruction lookups",
+                &self.instruction_lookups,
+            ),
+            read_write_memory: ComponentSize::of("read-write memory", &self.read_write_memory),
+            r1cs: ComponentSize::of("R1CS", &self.r1cs),
+            opening_sumcheck: ComponentSize::of("opening sumcheck", &self.opening_proof)
+                .minus(&pcs_opening),
+            pcs_opening,
+        }
+    }
+}
@@ -7,6 +7,8 @@ use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumu
 use rand::rngs::StdRng;
 use rand::RngCore;
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 #[cfg(test)]
 use std::collections::HashSet;
 use std::marker::PhantomData;
@@ -21,8 +23,17 @@ use crate::{
     poly::{
         dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
     },
-    subprotocols::sumcheck::SumcheckInstanceProof,
-    utils::{errors::ProofVerifyError, math::Math, mul_0_optimized, transcript::ProofTranscript},
+    subprotocols::{
+        grand_product::{ConfigurableGrandProduct, GrandProductConfig},
+        sumcheck::SumcheckInstanceProof,
+    },
+    utils::{
+        errors::{JoltError, ProofVerifyError},
+        json::Hex,
+        math::Math,
+        mul_0_optimized,
+        transcript::ProofTranscript,
+    },
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use common::constants::{
@@ -31,10 +42,12 @@ use common::constants::{
 };
 use common::rv_trace::{JoltDevice, MemoryLayout, MemoryOp};
 
+use super::stage::{JoltStage, StageProver, StageVerifier};
+use super::{commit_memory_final, commit_read_write_and_final};
 use super::{timestamp_range_check::TimestampValidityProof, JoltCommitments};
 use super::{JoltPolynomials, JoltStuff, JoltTraceStep};
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ReadWriteMemoryPreprocessing {
     min_bytecode_address: u64,
     pub bytecode_bytes: Vec<u8>,
@@ -89,6 +102,35 @@ fn remap_address(a: u64, memory_layout: &MemoryLayout) -> u64 {
     }
 }
 
+/// Checks that every memory operation in `trace` lies within the address space described by
+/// `memory_layout`, returning the size of the resulting memory-checking polynomials.
+pub fn memory_witness_size<InstructionSet: JoltInstructionSet>(
+    memory_layout: &MemoryLayout,
+    trace: &[JoltTraceStep<InstructionSet>],
+) -> Result<usize, JoltError> {
+    let max_trace_address = trace
+        .par_iter()
+        .flat_map_iter(|step| step.memory_ops.iter())
+        .map(|op| {
+            let a = match op {
+                MemoryOp::Read(a) => *a,
+                MemoryOp::Write(a, _) => *a,
+            };
+            if a < REGISTER_COUNT || (memory_layout.input_start..memory_layout.ram_end).contains(&a)
+            {
+                Ok(remap_address(a, memory_layout))
+            } else {
+                Err(JoltError::MemoryOutOfBounds {
+                    address: a,
+                    memory_size: memory_layout.memory_size,
+                })
+            }
+        })
+        .try_reduce(|| 0, |a, b| Ok(a.max(b)))?;
+
+    Ok((memory_layout.ram_witness_offset + max_trace_address).next_power_of_two() as usize)
+}
+
 fn remap_address_index(remapped_a: u64) -> usize {
     (remapped_a - REGISTER_COUNT) as usize
 }
@@ -105,29 +147,42 @@ const RAM_2_INDEX: usize = RAM_2 - 3;
 const RAM_3_INDEX: usize = RAM_3 - 3;
 const RAM_4_INDEX: usize = RAM_4 - 3;
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ReadWriteMemoryStuff<T: CanonicalSerialize + CanonicalDeserialize> {
     /// Read/write addresses. For offline memory checking, each read is paired with a "virtual" write
     /// and vice versa, so the read addresses and write addresses are the same.
+    #[serde_as(as = "Hex")]
     pub a_ram: T,
     /// Read values (RD, RS1, RS2, 4 bytes of RAM)
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     pub v_read: [T; MEMORY_OPS_PER_INSTRUCTION],
     /// RD write value
+    #[serde_as(as = "Hex")]
     pub v_write_rd: T,
     /// RAM write values
+    #[serde_as(as = "[Hex; 4]")]
     pub v_write_ram: [T; 4],
     /// Final memory state.
+    #[serde_as(as = "Hex")]
     pub v_final: T,
     /// Read timestamps.
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     pub t_read: [T; MEMORY_OPS_PER_INSTRUCTION],
     /// Write timestamps.
+    #[serde_as(as = "[Hex; 4]")]
     pub t_write_ram: [T; 4],
     /// Final timestamps.
+    #[serde_as(as = "Hex")]
     pub t_final: T,
 
+    #[serde_as(as = "Option<Hex>")]
     a_init_final: VerifierComputedOpening<T>,
     /// Initial memory values. RAM is initialized to contain the program bytecode and inputs.
+    #[serde_as(as = "Option<Hex>")]
     v_init: VerifierComputedOpening<T>,
+    #[serde_as(as = "Option<Hex>")]
     identity: VerifierComputedOpening<T>,
 }
 
@@ -186,10 +241,15 @@ impl<T: CanonicalSerialize + CanonicalDeserialize + Default>
 {
 }
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct RegisterAddressOpenings<F: JoltField> {
+    #[serde_as(as = "Hex")]
     pub a_rd: F,
+    #[serde_as(as = "Hex")]
     pub a_rs1: F,
+    #[serde_as(as = "Hex")]
     pub a_rs2: F,
 }
 
@@ -254,19 +314,8 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
         let m = trace.len();
         assert!(m.is_power_of_two());
 
-        let max_trace_address = trace
-            .iter()
-            .flat_map(|step| {
-                step.memory_ops.iter().map(|op| match op {
-                    MemoryOp::Read(a) => remap_address(*a, &program_io.memory_layout),
-                    MemoryOp::Write(a, _) => remap_address(*a, &program_io.memory_layout),
-                })
-            })
-            .max()
-            .unwrap_or(0);
-
-        let memory_size = (program_io.memory_layout.ram_witness_offset + max_trace_address)
-            .next_power_of_two() as usize;
+        let memory_size =
+            memory_witness_size(&program_io.memory_layout, trace).unwrap_or_else(|e| panic!("{e}"));
         let mut v_init: Vec<u64> = vec![0; memory_size];
         // Copy bytecode
         let mut v_init_index = memory_address_to_witness_index(
@@ -286,6 +335,18 @@ impl<F: JoltField> ReadWriteMemoryPolynomials<F> {
             v_init[v_init_index] = *byte as u64;
             v_init_index += 1;
         }
+        // Copy blob bytes, as far as the polynomial extends
+        v_init_index = memory_address_to_witness_index(
+            program_io.memory_layout.blob_start,
+            program_io.memory_layout.ram_witness_offset,
+        );
+        for (v, byte) in v_init
+            .iter_mut()
+            .skip(v_init_index)
+            .zip(program_io.blobs.region())
+        {
+            *v = byte as u64;
+        }
 
         #[cfg(test)]
         let mut init_tuples: HashSet<(u64, u64, u64)> = HashSet::new();
@@ -869,6 +930,9 @@ where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
+    type ReadWriteGrandProduct = ConfigurableGrandProduct<F>;
+    type InitFinalGrandProduct = ConfigurableGrandProduct<F>;
+
     type Polynomials = ReadWriteMemoryPolynomials<F>;
     type Openings = ReadWriteMemoryOpenings<F>;
     type Commitments = ReadWriteMemoryCommitments<PCS>;
@@ -1076,6 +1140,18 @@ where
             v_init[v_init_index] = *byte as u64;
             v_init_index += 1;
         }
+        // Copy blob bytes, as far as the polynomial extends
+        v_init_index = memory_address_to_witness_index(
+            memory_layout.blob_start,
+            memory_layout.ram_witness_offset,
+        );
+        for (v, byte) in v_init
+            .iter_mut()
+            .skip(v_init_index)
+            .zip(preprocessing.program_io.as_ref().unwrap().blobs.region())
+        {
+            *v = byte as u64;
+        }
 
         openings.v_init = Some(DensePolynomial::from_u64(&v_init).evaluate(r_init_final));
     }
@@ -1153,17 +1229,21 @@ where
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct OutputSumcheckProof<F, PCS>
 where
     F: JoltField,
     PCS: CommitmentScheme<Field = F>,
 {
+    #[serde(skip)]
     _pcs: PhantomData<PCS>,
     num_rounds: usize,
     /// Sumcheck proof that v_final is equal to the program outputs at the relevant indices.
     sumcheck_proof: SumcheckInstanceProof<F>,
     /// Opening of v_final at the random point chosen over the course of sumcheck
+    #[serde_as(as = "Hex")]
     opening: F,
 }
 
@@ -1324,11 +1404,9 @@ where
             DensePolynomial::from_u64(&v_io).evaluate(&r_sumcheck[..log_nonzero_memory_size]);
         v_io_eval *= r_prod;
 
-        assert_eq!(
-            eq_eval * io_witness_range_eval * (proof.opening - v_io_eval),
-            sumcheck_claim,
-            "Output sumcheck check failed."
-        );
+        if eq_eval * io_witness_range_eval * (proof.opening - v_io_eval) != sumcheck_claim {
+            return Err(ProofVerifyError::OutputMismatch);
+        }
 
         opening_accumulator.append(
             &[&commitment.v_final],
@@ -1341,7 +1419,8 @@ where
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ReadWriteMemoryProof<F, PCS>
 where
     F: JoltField,
@@ -1366,6 +1445,7 @@ where
         program_io: &JoltDevice,
         opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
+        grand_product: GrandProductConfig,
     ) -> Self {
         let memory_checking_proof = ReadWriteMemoryProof::prove_memory_checking(
             generators,
@@ -1374,6 +1454,7 @@ where
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product,
         );
 
         let output_proof = OutputSumcheckProof::prove_outputs(
@@ -1389,6 +1470,7 @@ where
             polynomials,
             opening_accumulator,
             transcript,
+            grand_product,
         );
 
         Self {
@@ -1432,6 +1514,81 @@ where
     }
 }
 
+impl<F, PCS> JoltStage<F, PCS> for ReadWriteMemoryProof<F, PCS>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    type Preprocessing = ReadWriteMemoryPreprocessing;
+    const NAME: &'static str = "Read-write memory";
+
+    /// Commits to the read-write memory columns, as well as the timestamp range-check columns
+    /// proven alongside them.
+    fn commit(
+        polynomials: &JoltPolynomials<F>,
+        commitments: &mut JoltCommitments<PCS>,
+        setup: &PCS::Setup,
+    ) {
+        let memory_polynomials = &polynomials.read_write_memory;
+        let (v_final, t_final) = commit_read_write_and_final::<PCS, _>(
+            memory_polynomials
+                .read_write_values()
+                .into_iter()
+                .chain(polynomials.timestamp_range_check.read_write_values())
+                .collect(),
+            commitments
+                .read_write_memory
+                .read_write_values_mut()
+                .into_iter()
+                .chain(commitments.timestamp_range_check.read_write_values_mut())
+                .collect(),
+            &[&memory_polynomials.v_final, &memory_polynomials.t_final],
+            || commit_memory_final::<PCS>(memory_polynomials, setup),
+            setup,
+        );
+        commitments.read_write_memory.v_final = v_final;
+        commitments.read_write_memory.t_final = t_final;
+    }
+
+    fn prove(
+        preprocessing: &ReadWriteMemoryPreprocessing,
+        prover: &mut StageProver<F, PCS>,
+    ) -> Self {
+        Self::prove(
+            prover.generators,
+            preprocessing,
+            prover.polynomials,
+            prover.program_io,
+            prover.opening_accumulator,
+            prover.transcript,
+            prover.grand_product,
+        )
+    }
+
+    /// Expects `preprocessing.program_io` to have been populated with `verifier.program_io`.
+    #[tracing::instrument(skip_all, name = "ReadWriteMemoryProof::verify")]
+    fn verify(
+        self,
+        preprocessing: &ReadWriteMemoryPreprocessing,
+        verifier: &mut StageVerifier<F, PCS>,
+    ) -> Result<(), ProofVerifyError> {
+        let program_io = verifier.program_io;
+        if program_io.inputs.len() > program_io.memory_layout.max_input_size as usize
+            || program_io.outputs.len() > program_io.memory_layout.max_output_size as usize
+        {
+            return Err(ProofVerifyError::InputTooLarge);
+        }
+        Self::verify(
+            self,
+            verifier.generators,
+            preprocessing,
+            verifier.commitments,
+            verifier.opening_accumulator,
+            verifier.transcript,
+        )
+    }
+}
+
 pub fn random_memory_trace<F: JoltField>(
     memory_init: &Vec<(u64, u8)>,
     max_memory_address: usize,
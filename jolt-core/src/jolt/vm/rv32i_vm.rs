@@ -2,88 +2,171 @@ use crate::field::JoltField;
 use crate::jolt::instruction::virtual_assert_valid_div0::AssertValidDiv0Instruction;
 use crate::jolt::instruction::virtual_assert_valid_unsigned_remainder::AssertValidUnsignedRemainderInstruction;
 use crate::jolt::instruction::virtual_move::MOVEInstruction;
-use crate::jolt::subtable::div_by_zero::DivByZeroSubtable;
-use crate::jolt::subtable::right_is_zero::RightIsZeroSubtable;
 use crate::poly::commitment::hyperkzg::HyperKZG;
 use crate::r1cs::constraints::JoltRV32IMConstraints;
 use crate::r1cs::inputs::JoltR1CSInputs;
 use ark_bn254::{Bn254, Fr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use enum_dispatch::enum_dispatch;
-use rand::{prelude::StdRng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::any::TypeId;
-use strum::{EnumCount, IntoEnumIterator};
-use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 
+use super::verifier::Verifier;
 use super::{Jolt, JoltCommitments, JoltProof};
 use crate::jolt::instruction::{
-    add::ADDInstruction, and::ANDInstruction, beq::BEQInstruction, bge::BGEInstruction,
-    bgeu::BGEUInstruction, bne::BNEInstruction, lb::LBInstruction, lh::LHInstruction,
+    add::ADDInstruction, and::ANDInstruction, andn::ANDNInstruction, beq::BEQInstruction,
+    bge::BGEInstruction, bgeu::BGEUInstruction, bne::BNEInstruction, clz::CLZInstruction,
+    cpop::CPOPInstruction, ctz::CTZInstruction, lb::LBInstruction, lh::LHInstruction,
     mul::MULInstruction, mulhu::MULHUInstruction, mulu::MULUInstruction, or::ORInstruction,
-    sb::SBInstruction, sh::SHInstruction, sll::SLLInstruction, slt::SLTInstruction,
-    sltu::SLTUInstruction, sra::SRAInstruction, srl::SRLInstruction, sub::SUBInstruction,
-    sw::SWInstruction, virtual_advice::ADVICEInstruction, virtual_assert_lte::ASSERTLTEInstruction,
+    orc_b::ORCBInstruction, rev8::REV8Instruction, sb::SBInstruction, sh::SHInstruction,
+    sll::SLLInstruction, slt::SLTInstruction, sltu::SLTUInstruction, sra::SRAInstruction,
+    srl::SRLInstruction, sub::SUBInstruction, sw::SWInstruction, virtual_advice::ADVICEInstruction,
+    virtual_assert_lte::ASSERTLTEInstruction,
     virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction,
-    virtual_movsign::MOVSIGNInstruction, xor::XORInstruction, JoltInstruction, JoltInstructionSet,
-    SubtableIndices,
-};
-use crate::jolt::subtable::{
-    and::AndSubtable, eq::EqSubtable, eq_abs::EqAbsSubtable, identity::IdentitySubtable,
-    left_is_zero::LeftIsZeroSubtable, left_msb::LeftMSBSubtable, lt_abs::LtAbsSubtable,
-    ltu::LtuSubtable, or::OrSubtable, right_msb::RightMSBSubtable, sign_extend::SignExtendSubtable,
-    sll::SllSubtable, sra_sign::SraSignSubtable, srl::SrlSubtable,
-    truncate_overflow::TruncateOverflowSubtable, xor::XorSubtable, JoltSubtableSet, LassoSubtable,
-    SubtableId,
+    virtual_movsign::MOVSIGNInstruction, xnor::XNORInstruction, xor::XORInstruction,
+    JoltInstructionSet,
 };
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 
 /// Generates an enum out of a list of JoltInstruction types. All JoltInstruction methods
-/// are callable on the enum type via enum_dispatch.
+/// are callable on the enum type, and are dispatched to the wrapped instruction.
+///
+/// Crates invoking this macro must depend on `rand`, `serde`, `strum`, and `strum_macros`.
+#[macro_export]
 macro_rules! instruction_set {
-    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
-        #[allow(non_camel_case_types)]
+    ($enum_name:ident, $($alias:ident: $struct:ty),+ $(,)?) => {
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
         #[repr(u8)]
-        #[derive(Copy, Clone, Debug, PartialEq, EnumIter, EnumCountMacro, Serialize, Deserialize)]
-        #[enum_dispatch(JoltInstruction)]
+        #[derive(
+            Copy,
+            Clone,
+            Debug,
+            PartialEq,
+            ::strum_macros::EnumIter,
+            ::strum_macros::EnumCount,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+        )]
         pub enum $enum_name {
             $($alias($struct)),+
         }
-        impl JoltInstructionSet for $enum_name {}
+        $(
+            impl From<$struct> for $enum_name {
+                fn from(instruction: $struct) -> Self {
+                    $enum_name::$alias(instruction)
+                }
+            }
+        )+
+        impl $crate::jolt::instruction::JoltInstruction for $enum_name {
+            fn operands(&self) -> (u64, u64) {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.operands()),+
+                }
+            }
+            fn combine_lookups<F: $crate::field::JoltField>(
+                &self,
+                vals: &[F],
+                c: usize,
+                m: usize,
+            ) -> F {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.combine_lookups(vals, c, m)),+
+                }
+            }
+            fn g_poly_degree(&self, c: usize) -> usize {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.g_poly_degree(c)),+
+                }
+            }
+            fn subtables<F: $crate::field::JoltField>(
+                &self,
+                c: usize,
+                m: usize,
+            ) -> Vec<(
+                Box<dyn $crate::jolt::subtable::LassoSubtable<F>>,
+                $crate::jolt::instruction::SubtableIndices,
+            )> {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.subtables(c, m)),+
+                }
+            }
+            fn to_indices(&self, c: usize, log_m: usize) -> Vec<usize> {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.to_indices(c, log_m)),+
+                }
+            }
+            fn lookup_entry(&self) -> u64 {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.lookup_entry()),+
+                }
+            }
+            fn operand_chunks(&self, c: usize, log_m: usize) -> (Vec<u64>, Vec<u64>) {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.operand_chunks(c, log_m)),+
+                }
+            }
+            fn random(&self, rng: &mut ::rand::prelude::StdRng) -> Self {
+                match self {
+                    $($enum_name::$alias(instruction) => $enum_name::$alias(instruction.random(rng))),+
+                }
+            }
+            fn slice_values<'a, F: $crate::field::JoltField>(
+                &self,
+                vals: &'a [F],
+                c: usize,
+                m: usize,
+            ) -> Vec<&'a [F]> {
+                match self {
+                    $($enum_name::$alias(instruction) => instruction.slice_values(vals, c, m)),+
+                }
+            }
+        }
+        impl $crate::jolt::instruction::JoltInstructionSet for $enum_name {
+            const NAME: &'static str = concat!(stringify!($enum_name), $(" ", stringify!($alias)),+);
+        }
         impl $enum_name {
-            pub fn random_instruction(rng: &mut StdRng) -> Self {
-                let index = rng.next_u64() as usize % $enum_name::COUNT;
-                let instruction = $enum_name::iter()
+            #[allow(dead_code)]
+            pub fn random_instruction(rng: &mut ::rand::prelude::StdRng) -> Self {
+                let index = ::rand::RngCore::next_u64(rng) as usize
+                    % <$enum_name as ::strum::EnumCount>::COUNT;
+                let instruction = <$enum_name as ::strum::IntoEnumIterator>::iter()
                     .enumerate()
                     .filter(|(i, _)| *i == index)
                     .map(|(_, x)| x)
                     .next()
                     .unwrap();
-                instruction.random(rng)
+                $crate::jolt::instruction::JoltInstruction::random(&instruction, rng)
             }
         }
         // Need a default so that we can derive EnumIter on `JoltR1CSInputs`
         impl Default for $enum_name {
             fn default() -> Self {
-                $enum_name::iter().collect::<Vec<_>>()[0]
+                <$enum_name as ::strum::IntoEnumIterator>::iter().collect::<Vec<_>>()[0]
             }
         }
     };
 }
 
 /// Generates an enum out of a list of LassoSubtable types. All LassoSubtable methods
-/// are callable on the enum type via enum_dispatch.
+/// are callable on the enum type, and are dispatched to the wrapped subtable.
+///
+/// Crates invoking this macro must depend on `strum_macros`.
+#[macro_export]
 macro_rules! subtable_enum {
-    ($enum_name:ident, $($alias:ident: $struct:ty),+) => {
+    ($enum_name:ident, $($alias:ident: $struct:ty),+ $(,)?) => {
         #[allow(non_camel_case_types)]
         #[repr(u8)]
-        #[enum_dispatch(LassoSubtable<F>)]
-        #[derive(EnumCountMacro, EnumIter)]
-        pub enum $enum_name<F: JoltField> { $($alias($struct)),+ }
-        impl<F: JoltField> From<SubtableId> for $enum_name<F> {
-          fn from(subtable_id: SubtableId) -> Self {
+        #[derive(::strum_macros::EnumCount, ::strum_macros::EnumIter)]
+        pub enum $enum_name<F: $crate::field::JoltField> { $($alias($struct)),+ }
+        $(
+            impl<F: $crate::field::JoltField> From<$struct> for $enum_name<F> {
+                fn from(subtable: $struct) -> Self {
+                    $enum_name::$alias(subtable)
+                }
+            }
+        )+
+        impl<F: $crate::field::JoltField> From<$crate::jolt::subtable::SubtableId> for $enum_name<F> {
+          fn from(subtable_id: $crate::jolt::subtable::SubtableId) -> Self {
             $(
-              if subtable_id == TypeId::of::<$struct>() {
+              if subtable_id == ::std::any::TypeId::of::<$struct>() {
                 $enum_name::from(<$struct>::new())
               } else
             )+
@@ -91,80 +174,274 @@ macro_rules! subtable_enum {
           }
         }
 
-        impl<F: JoltField> From<$enum_name<F>> for usize {
+        impl<F: $crate::field::JoltField> From<$enum_name<F>> for usize {
             fn from(subtable: $enum_name<F>) -> usize {
                 // Discriminant: https://doc.rust-lang.org/reference/items/enumerations.html#pointer-casting
                 let byte = unsafe { *(&subtable as *const $enum_name<F> as *const u8) };
                 byte as usize
             }
         }
-        impl<F: JoltField> JoltSubtableSet<F> for $enum_name<F> {}
+        impl<F: $crate::field::JoltField> $crate::jolt::subtable::LassoSubtable<F> for $enum_name<F> {
+            fn subtable_id(&self) -> $crate::jolt::subtable::SubtableId {
+                match self {
+                    $($enum_name::$alias(subtable) => subtable.subtable_id()),+
+                }
+            }
+            fn materialize(&self, m: usize) -> Vec<F> {
+                match self {
+                    $($enum_name::$alias(subtable) => subtable.materialize(m)),+
+                }
+            }
+            fn evaluate_mle(&self, point: &[F]) -> F {
+                match self {
+                    $($enum_name::$alias(subtable) => subtable.evaluate_mle(point)),+
+                }
+            }
+            fn name(&self) -> String {
+                match self {
+                    $($enum_name::$alias(subtable) => subtable.name()),+
+                }
+            }
+        }
+        impl<F: $crate::field::JoltField> $crate::jolt::subtable::JoltSubtableSet<F> for $enum_name<F> {
+            const NAME: &'static str = concat!(stringify!($enum_name), $(" ", stringify!($alias)),+);
+        }
+    };
+}
+
+/// Generates an instruction set consisting of every RV32I instruction followed by the given
+/// ones. Used to define [`RV32I`] and by [`custom_instruction_set!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! rv32i_instruction_set {
+    ($enum_name:ident $(, $alias:ident: $struct:ty)* $(,)?) => {
+        $crate::instruction_set!(
+          $enum_name,
+          ADD: $crate::jolt::instruction::add::ADDInstruction<32>,
+          SUB: $crate::jolt::instruction::sub::SUBInstruction<32>,
+          AND: $crate::jolt::instruction::and::ANDInstruction<32>,
+          OR: $crate::jolt::instruction::or::ORInstruction<32>,
+          XOR: $crate::jolt::instruction::xor::XORInstruction<32>,
+          LB: $crate::jolt::instruction::lb::LBInstruction<32>,
+          LH: $crate::jolt::instruction::lh::LHInstruction<32>,
+          SB: $crate::jolt::instruction::sb::SBInstruction<32>,
+          SH: $crate::jolt::instruction::sh::SHInstruction<32>,
+          SW: $crate::jolt::instruction::sw::SWInstruction<32>,
+          BEQ: $crate::jolt::instruction::beq::BEQInstruction<32>,
+          BGE: $crate::jolt::instruction::bge::BGEInstruction<32>,
+          BGEU: $crate::jolt::instruction::bgeu::BGEUInstruction<32>,
+          BNE: $crate::jolt::instruction::bne::BNEInstruction<32>,
+          SLT: $crate::jolt::instruction::slt::SLTInstruction<32>,
+          SLTU: $crate::jolt::instruction::sltu::SLTUInstruction<32>,
+          SLL: $crate::jolt::instruction::sll::SLLInstruction<32>,
+          SRA: $crate::jolt::instruction::sra::SRAInstruction<32>,
+          SRL: $crate::jolt::instruction::srl::SRLInstruction<32>,
+          MOVSIGN: $crate::jolt::instruction::virtual_movsign::MOVSIGNInstruction<32>,
+          MUL: $crate::jolt::instruction::mul::MULInstruction<32>,
+          MULU: $crate::jolt::instruction::mulu::MULUInstruction<32>,
+          MULHU: $crate::jolt::instruction::mulhu::MULHUInstruction<32>,
+          VIRTUAL_ADVICE: $crate::jolt::instruction::virtual_advice::ADVICEInstruction<32>,
+          VIRTUAL_MOVE: $crate::jolt::instruction::virtual_move::MOVEInstruction<32>,
+          VIRTUAL_ASSERT_LTE: $crate::jolt::instruction::virtual_assert_lte::ASSERTLTEInstruction<32>,
+          VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER: $crate::jolt::instruction::virtual_assert_valid_signed_remainder::AssertValidSignedRemainderInstruction<32>,
+          VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER: $crate::jolt::instruction::virtual_assert_valid_unsigned_remainder::AssertValidUnsignedRemainderInstruction<32>,
+          VIRTUAL_ASSERT_VALID_DIV0: $crate::jolt::instruction::virtual_assert_valid_div0::AssertValidDiv0Instruction<32>,
+          ANDN: $crate::jolt::instruction::andn::ANDNInstruction<32>,
+          XNOR: $crate::jolt::instruction::xnor::XNORInstruction<32>,
+          CLZ: $crate::jolt::instruction::clz::CLZInstruction<32>,
+          CTZ: $crate::jolt::instruction::ctz::CTZInstruction<32>,
+          CPOP: $crate::jolt::instruction::cpop::CPOPInstruction<32>,
+          ORC_B: $crate::jolt::instruction::orc_b::ORCBInstruction<32>,
+          REV8: $crate::jolt::instruction::rev8::REV8Instruction<32>
+          $(, $alias: $struct)*
+        );
+        impl $crate::jolt::vm::rv32i_vm::RV32IInstructionSet for $enum_name {}
+    };
+}
+
+/// Generates a subtable enum consisting of every subtable used by RV32I followed by the given
+/// ones. Used to define [`RV32ISubtables`] and by [`custom_subtable_set!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! rv32i_subtable_set {
+    ($enum_name:ident $(, $alias:ident: $struct:ty)* $(,)?) => {
+        $crate::subtable_enum!(
+          $enum_name,
+          AND: $crate::jolt::subtable::and::AndSubtable<F>,
+          EQ_ABS: $crate::jolt::subtable::eq_abs::EqAbsSubtable<F>,
+          EQ: $crate::jolt::subtable::eq::EqSubtable<F>,
+          LEFT_MSB: $crate::jolt::subtable::left_msb::LeftMSBSubtable<F>,
+          RIGHT_MSB: $crate::jolt::subtable::right_msb::RightMSBSubtable<F>,
+          IDENTITY: $crate::jolt::subtable::identity::IdentitySubtable<F>,
+          LT_ABS: $crate::jolt::subtable::lt_abs::LtAbsSubtable<F>,
+          LTU: $crate::jolt::subtable::ltu::LtuSubtable<F>,
+          OR: $crate::jolt::subtable::or::OrSubtable<F>,
+          SIGN_EXTEND_8: $crate::jolt::subtable::sign_extend::SignExtendSubtable<F, 8>,
+          SIGN_EXTEND_16: $crate::jolt::subtable::sign_extend::SignExtendSubtable<F, 16>,
+          SLL0: $crate::jolt::subtable::sll::SllSubtable<F, 0, 32>,
+          SLL1: $crate::jolt::subtable::sll::SllSubtable<F, 1, 32>,
+          SLL2: $crate::jolt::subtable::sll::SllSubtable<F, 2, 32>,
+          SLL3: $crate::jolt::subtable::sll::SllSubtable<F, 3, 32>,
+          SRA_SIGN: $crate::jolt::subtable::sra_sign::SraSignSubtable<F, 32>,
+          SRL0: $crate::jolt::subtable::srl::SrlSubtable<F, 0, 32>,
+          SRL1: $crate::jolt::subtable::srl::SrlSubtable<F, 1, 32>,
+          SRL2: $crate::jolt::subtable::srl::SrlSubtable<F, 2, 32>,
+          SRL3: $crate::jolt::subtable::srl::SrlSubtable<F, 3, 32>,
+          TRUNCATE: $crate::jolt::subtable::truncate_overflow::TruncateOverflowSubtable<F, 32>,
+          TRUNCATE_BYTE: $crate::jolt::subtable::truncate_overflow::TruncateOverflowSubtable<F, 8>,
+          XOR: $crate::jolt::subtable::xor::XorSubtable<F>,
+          LEFT_IS_ZERO: $crate::jolt::subtable::left_is_zero::LeftIsZeroSubtable<F>,
+          RIGHT_IS_ZERO: $crate::jolt::subtable::right_is_zero::RightIsZeroSubtable<F>,
+          DIV_BY_ZERO: $crate::jolt::subtable::div_by_zero::DivByZeroSubtable<F>,
+          ANDN: $crate::jolt::subtable::andn::AndnSubtable<F>,
+          LEADING_ZEROS: $crate::jolt::subtable::leading_zeros::LeadingZerosSubtable<F>,
+          TRAILING_ZEROS: $crate::jolt::subtable::trailing_zeros::TrailingZerosSubtable<F>,
+          POPCOUNT: $crate::jolt::subtable::popcount::PopcountSubtable<F>
+          $(, $alias: $struct)*
+        );
     };
 }
 
+/// Defines an instruction set extending [`RV32I`] with application-defined instructions in the
+/// RISC-V `custom-0` opcode space, each of which implements [`CustomInstruction`]. For example:
+///
+/// ```ignore
+/// custom_instruction_set!(MyInstructionSet, ROTR: RotateRightInstruction);
+/// custom_subtable_set!(MySubtables, ROTR: RotateRightSubtable<F>);
+///
+/// pub enum MyJoltVM {}
+/// impl<F: JoltField, PCS: CommitmentScheme<Field = F>> Jolt<F, PCS, C, M> for MyJoltVM {
+///     type InstructionSet = MyInstructionSet;
+///     type Subtables = MySubtables<F>;
+///     type Constraints = JoltRV32IMConstraints<MyInstructionSet>;
+/// }
+/// ```
+///
+/// The generated instruction set decodes `custom-0` instructions using
+/// [`CustomInstruction::matches`] and all other instructions like [`RV32I`]. Programs using
+/// the custom instructions can be traced with `Program::trace_with_instruction_set`, which
+/// emulates them using their `lookup_entry`. In debug builds, decoding a traced custom
+/// instruction checks that it chunks its operands the way the R1CS requires (see
+/// [`CustomInstruction`]'s `# Soundness` section).
+///
+/// [`CustomInstruction`]: crate::jolt::instruction::custom::CustomInstruction
+/// [`CustomInstruction::matches`]: crate::jolt::instruction::custom::CustomInstruction::matches
+#[macro_export]
+macro_rules! custom_instruction_set {
+    ($enum_name:ident, $($alias:ident: $struct:ty),+ $(,)?) => {
+        $crate::rv32i_instruction_set!($enum_name, $($alias: $struct),+);
+
+        // Instruction flags are packed into the bytecode alongside the circuit flags
+        const _: () = assert!(
+            <$enum_name as ::strum::EnumCount>::COUNT
+                + $crate::jolt::instruction::custom::NUM_CIRCUIT_FLAGS
+                <= 64,
+            "too many custom instructions"
+        );
+
+        impl TryFrom<&$crate::jolt::instruction::custom::ELFInstruction> for $enum_name {
+            type Error = &'static str;
+
+            fn try_from(
+                instruction: &$crate::jolt::instruction::custom::ELFInstruction,
+            ) -> Result<Self, Self::Error> {
+                if let Some((funct3, funct7)) = instruction.custom_funct() {
+                    $(
+                        if <$struct as $crate::jolt::instruction::custom::CustomInstruction>::matches(funct3, funct7) {
+                            return Ok($enum_name::$alias(Default::default()));
+                        }
+                    )+
+                    return Err("No corresponding custom instruction");
+                }
+                $crate::jolt::trace::rv::rv32i_instruction_from_elf(instruction)
+            }
+        }
+
+        impl TryFrom<&$crate::jolt::instruction::custom::RVTraceRow> for $enum_name {
+            type Error = &'static str;
+
+            fn try_from(
+                row: &$crate::jolt::instruction::custom::RVTraceRow,
+            ) -> Result<Self, Self::Error> {
+                if let Some((funct3, funct7)) = row.instruction.custom_funct() {
+                    $(
+                        if <$struct as $crate::jolt::instruction::custom::CustomInstruction>::matches(funct3, funct7) {
+                            let rs1_val = row.register_state.rs1_val.unwrap();
+                            let rs2_val = row.register_state.rs2_val.unwrap();
+                            let instruction = <$struct as $crate::jolt::instruction::custom::CustomInstruction>::from_operands(
+                                rs1_val,
+                                rs2_val,
+                            );
+                            $crate::jolt::instruction::custom::check_operands(&instruction, rs1_val, rs2_val);
+                            return Ok($enum_name::$alias(instruction));
+                        }
+                    )+
+                    return Err("No corresponding custom instruction");
+                }
+                $crate::jolt::trace::rv::rv32i_instruction_from_row(row)
+            }
+        }
+    };
+}
+
+/// Defines a subtable enum containing the subtables used by RV32I as well as the given ones,
+/// for use with an instruction set defined by [`custom_instruction_set!`].
+#[macro_export]
+macro_rules! custom_subtable_set {
+    ($enum_name:ident $(, $alias:ident: $struct:ty)* $(,)?) => {
+        $crate::rv32i_subtable_set!($enum_name $(, $alias: $struct)*);
+    };
+}
+
+/// An instruction set containing every RV32I instruction (and possibly others). The RV32I
+/// decoding logic and [`JoltRV32IMConstraints`] are generic over such instruction sets.
+///
+/// [`JoltRV32IMConstraints`]: crate::r1cs::constraints::JoltRV32IMConstraints
+pub trait RV32IInstructionSet:
+    JoltInstructionSet
+    + From<ADDInstruction<WORD_SIZE>>
+    + From<SUBInstruction<WORD_SIZE>>
+    + From<ANDInstruction<WORD_SIZE>>
+    + From<ORInstruction<WORD_SIZE>>
+    + From<XORInstruction<WORD_SIZE>>
+    + From<LBInstruction<WORD_SIZE>>
+    + From<LHInstruction<WORD_SIZE>>
+    + From<SBInstruction<WORD_SIZE>>
+    + From<SHInstruction<WORD_SIZE>>
+    + From<SWInstruction<WORD_SIZE>>
+    + From<BEQInstruction<WORD_SIZE>>
+    + From<BGEInstruction<WORD_SIZE>>
+    + From<BGEUInstruction<WORD_SIZE>>
+    + From<BNEInstruction<WORD_SIZE>>
+    + From<SLTInstruction<WORD_SIZE>>
+    + From<SLTUInstruction<WORD_SIZE>>
+    + From<SLLInstruction<WORD_SIZE>>
+    + From<SRAInstruction<WORD_SIZE>>
+    + From<SRLInstruction<WORD_SIZE>>
+    + From<MOVSIGNInstruction<WORD_SIZE>>
+    + From<MULInstruction<WORD_SIZE>>
+    + From<MULUInstruction<WORD_SIZE>>
+    + From<MULHUInstruction<WORD_SIZE>>
+    + From<ADVICEInstruction<WORD_SIZE>>
+    + From<MOVEInstruction<WORD_SIZE>>
+    + From<ASSERTLTEInstruction<WORD_SIZE>>
+    + From<AssertValidSignedRemainderInstruction<WORD_SIZE>>
+    + From<AssertValidUnsignedRemainderInstruction<WORD_SIZE>>
+    + From<AssertValidDiv0Instruction<WORD_SIZE>>
+    + From<ANDNInstruction<WORD_SIZE>>
+    + From<XNORInstruction<WORD_SIZE>>
+    + From<CLZInstruction<WORD_SIZE>>
+    + From<CTZInstruction<WORD_SIZE>>
+    + From<CPOPInstruction<WORD_SIZE>>
+    + From<ORCBInstruction<WORD_SIZE>>
+    + From<REV8Instruction<WORD_SIZE>>
+{
+}
+
 const WORD_SIZE: usize = 32;
 
-instruction_set!(
-  RV32I,
-  ADD: ADDInstruction<WORD_SIZE>,
-  SUB: SUBInstruction<WORD_SIZE>,
-  AND: ANDInstruction<WORD_SIZE>,
-  OR: ORInstruction<WORD_SIZE>,
-  XOR: XORInstruction<WORD_SIZE>,
-  LB: LBInstruction<WORD_SIZE>,
-  LH: LHInstruction<WORD_SIZE>,
-  SB: SBInstruction<WORD_SIZE>,
-  SH: SHInstruction<WORD_SIZE>,
-  SW: SWInstruction<WORD_SIZE>,
-  BEQ: BEQInstruction<WORD_SIZE>,
-  BGE: BGEInstruction<WORD_SIZE>,
-  BGEU: BGEUInstruction<WORD_SIZE>,
-  BNE: BNEInstruction<WORD_SIZE>,
-  SLT: SLTInstruction<WORD_SIZE>,
-  SLTU: SLTUInstruction<WORD_SIZE>,
-  SLL: SLLInstruction<WORD_SIZE>,
-  SRA: SRAInstruction<WORD_SIZE>,
-  SRL: SRLInstruction<WORD_SIZE>,
-  MOVSIGN: MOVSIGNInstruction<WORD_SIZE>,
-  MUL: MULInstruction<WORD_SIZE>,
-  MULU: MULUInstruction<WORD_SIZE>,
-  MULHU: MULHUInstruction<WORD_SIZE>,
-  VIRTUAL_ADVICE: ADVICEInstruction<WORD_SIZE>,
-  VIRTUAL_MOVE: MOVEInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_LTE: ASSERTLTEInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_SIGNED_REMAINDER: AssertValidSignedRemainderInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_UNSIGNED_REMAINDER: AssertValidUnsignedRemainderInstruction<WORD_SIZE>,
-  VIRTUAL_ASSERT_VALID_DIV0: AssertValidDiv0Instruction<WORD_SIZE>
-);
-subtable_enum!(
-  RV32ISubtables,
-  AND: AndSubtable<F>,
-  EQ_ABS: EqAbsSubtable<F>,
-  EQ: EqSubtable<F>,
-  LEFT_MSB: LeftMSBSubtable<F>,
-  RIGHT_MSB: RightMSBSubtable<F>,
-  IDENTITY: IdentitySubtable<F>,
-  LT_ABS: LtAbsSubtable<F>,
-  LTU: LtuSubtable<F>,
-  OR: OrSubtable<F>,
-  SIGN_EXTEND_8: SignExtendSubtable<F, 8>,
-  SIGN_EXTEND_16: SignExtendSubtable<F, 16>,
-  SLL0: SllSubtable<F, 0, WORD_SIZE>,
-  SLL1: SllSubtable<F, 1, WORD_SIZE>,
-  SLL2: SllSubtable<F, 2, WORD_SIZE>,
-  SLL3: SllSubtable<F, 3, WORD_SIZE>,
-  SRA_SIGN: SraSignSubtable<F, WORD_SIZE>,
-  SRL0: SrlSubtable<F, 0, WORD_SIZE>,
-  SRL1: SrlSubtable<F, 1, WORD_SIZE>,
-  SRL2: SrlSubtable<F, 2, WORD_SIZE>,
-  SRL3: SrlSubtable<F, 3, WORD_SIZE>,
-  TRUNCATE: TruncateOverflowSubtable<F, WORD_SIZE>,
-  TRUNCATE_BYTE: TruncateOverflowSubtable<F, 8>,
-  XOR: XorSubtable<F>,
-  LEFT_IS_ZERO: LeftIsZeroSubtable<F>,
-  RIGHT_IS_ZERO: RightIsZeroSubtable<F>,
-  DIV_BY_ZERO: DivByZeroSubtable<F>
-);
+rv32i_instruction_set!(RV32I);
+rv32i_subtable_set!(RV32ISubtables);
 
 // ==================== JOLT ====================
 
@@ -184,6 +461,7 @@ where
 }
 
 pub type RV32IJoltProof<F, PCS> = JoltProof<C, M, JoltR1CSInputs, F, PCS, RV32I, RV32ISubtables<F>>;
+pub type RV32IVerifier<F, PCS> = Verifier<C, M, F, PCS, RV32IJoltVM>;
 
 use eyre::Result;
 use std::fs::File;
@@ -226,7 +504,7 @@ pub trait Serializable: CanonicalSerialize + CanonicalDeserialize + Sized {
 }
 
 pub type PCS = HyperKZG<Bn254>;
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
 pub struct JoltHyperKZGProof {
     pub proof: RV32IJoltProof<Fr, PCS>,
     pub commitments: JoltCommitments<PCS>,
@@ -238,26 +516,46 @@ impl Serializable for JoltHyperKZGProof {}
 
 #[cfg(test)]
 mod tests {
+    use ark_bls12_381::Bls12_381;
     use ark_bn254::{Bn254, Fr, G1Projective};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
+    use common::rv_trace::{CircuitFlags, JoltDevice};
     use std::collections::HashSet;
 
     use crate::field::JoltField;
     use crate::host;
     use crate::jolt::instruction::JoltInstruction;
-    use crate::jolt::vm::rv32i_vm::{Jolt, RV32IJoltVM, C, M};
+    use crate::jolt::vm::preprocessing::PREPROCESSING_MAGIC;
+    use crate::jolt::vm::progress::{CancellationToken, ProverEvent};
+    use crate::jolt::vm::rv32i_vm::{
+        Jolt, JoltCommitments, JoltHyperKZGProof, RV32IJoltProof, RV32IJoltVM, RV32IVerifier, C, M,
+        RV32I,
+    };
+    use crate::jolt::vm::scheduler::{ProofProfile, SchedulerConfig};
+    use crate::jolt::vm::witness_audit::CompactType;
+    use crate::jolt::vm::{JoltPreprocessing, JoltTraceStep};
+    use crate::lasso::memory_checking::StructuredPolynomialData;
     use crate::poly::commitment::commitment_scheme::CommitmentScheme;
     use crate::poly::commitment::hyperkzg::HyperKZG;
     use crate::poly::commitment::hyrax::HyraxScheme;
     use crate::poly::commitment::mock::MockCommitScheme;
     use crate::poly::commitment::zeromorph::Zeromorph;
-    use std::sync::Mutex;
+    use crate::r1cs::inputs::{ConstraintInput, JoltR1CSInputs};
+    use crate::subprotocols::grand_product::GrandProductConfig;
+    use crate::testing::fuzz_verifier;
+    use crate::utils::errors::{JoltError, PreprocessingError, ProofVerifyError};
+    use crate::utils::json::{from_json, to_json};
+    use crate::utils::sol_types::JoltProofHeader;
+    use crate::utils::transcript::{TranscriptNamespace, TranscriptVersion};
+    use std::sync::{Arc, Mutex};
     use strum::{EnumCount, IntoEnumIterator};
 
     // If multiple tests try to read the same trace artifacts simultaneously, they will fail
     lazy_static::lazy_static! {
         static ref FIB_FILE_LOCK: Mutex<()> = Mutex::new(());
         static ref SHA3_FILE_LOCK: Mutex<()> = Mutex::new(());
+        static ref MULDIV_FILE_LOCK: Mutex<()> = Mutex::new(());
     }
 
     fn test_instruction_set_subtables<PCS: CommitmentScheme>() {
@@ -284,7 +582,12 @@ mod tests {
         test_instruction_set_subtables::<HyperKZG<Bn254>>();
     }
 
-    fn fib_e2e<F: JoltField, PCS: CommitmentScheme<Field = F>>() {
+    /// Traces the Fibonacci guest on a small input, and preprocesses it as tightly as possible.
+    fn fib_tiny<F: JoltField, PCS: CommitmentScheme<Field = F>>() -> (
+        JoltPreprocessing<C, F, PCS>,
+        JoltDevice,
+        Vec<JoltTraceStep<RV32I>>,
+    ) {
         let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
         let mut program = host::Program::new("fibonacci-guest");
         program.set_input(&9u32);
@@ -293,9 +596,15 @@ mod tests {
         drop(artifact_guard);
 
         let preprocessing =
-            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+            RV32IJoltVM::preprocess_tiny(bytecode, memory_init, &io_device, &trace).unwrap();
+        (preprocessing, io_device, trace)
+    }
+
+    fn fib_e2e<F: JoltField, PCS: CommitmentScheme<Field = F>>() {
+        let (preprocessing, io_device, trace) = fib_tiny::<F, PCS>();
         let (proof, commitments, debug_info) =
-            <RV32IJoltVM as Jolt<F, PCS, C, M>>::prove(io_device, trace, preprocessing.clone());
+            <RV32IJoltVM as Jolt<F, PCS, C, M>>::prove(io_device, trace, preprocessing.clone())
+                .unwrap();
         let verification_result =
             RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info);
         assert!(
@@ -310,6 +619,752 @@ mod tests {
         fib_e2e::<Fr, MockCommitScheme<Fr>>();
     }
 
+    #[test]
+    fn fib_e2e_schedules_agree() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let mut proofs = vec![];
+        for config in [
+            SchedulerConfig::default().with_num_threads(4),
+            SchedulerConfig::sequential().with_num_threads(1),
+            // Small enough to commit one polynomial at a time and prove stages sequentially
+            SchedulerConfig::default()
+                .with_num_threads(2)
+                .with_max_memory_bytes(1 << 12),
+        ] {
+            let (proof, commitments, debug_info, metrics) = RV32IJoltVM::prove_with_metrics(
+                io_device.clone(),
+                trace.clone(),
+                preprocessing.clone(),
+                &config,
+            )
+            .unwrap();
+            assert_eq!(metrics.num_threads, config.num_threads.unwrap());
+            for stage in [
+                "Bytecode",
+                "Instruction lookups",
+                "Read-write memory",
+                "R1CS",
+            ] {
+                let witness = metrics.task(&format!("{stage} witness")).unwrap();
+                let proof = metrics.task(&format!("{stage} proof")).unwrap();
+                assert!(witness.end() <= proof.start);
+            }
+            assert!(metrics.task("Opening proof").unwrap().end() <= metrics.total);
+
+            let mut proof_bytes = vec![];
+            proof.serialize_compressed(&mut proof_bytes).unwrap();
+            proofs.push(proof_bytes);
+            RV32IJoltVM::verify(preprocessing.clone(), proof, commitments, debug_info).unwrap();
+        }
+        // Proving is deterministic, whatever the schedule
+        assert!(proofs.iter().all(|proof| *proof == proofs[0]));
+    }
+
+    #[test]
+    fn fib_e2e_size_breakdown() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+
+        let breakdown = proof.size_breakdown(&commitments);
+        assert_eq!(
+            breakdown.compressed_bytes(),
+            proof.compressed_size() + commitments.compressed_size()
+        );
+        assert_eq!(
+            breakdown.uncompressed_bytes(),
+            proof.uncompressed_size() + commitments.uncompressed_size()
+        );
+        // Every commitment is a single point
+        let num_commitments =
+            commitments.read_write_values().len() + commitments.init_final_values().len();
+        assert_eq!(breakdown.commitments.num_points(), num_commitments);
+        // The HyperKZG proof of an opening of ell variables has ell - 1 + 3 points
+        assert!(breakdown.pcs_opening.num_points() > 3);
+        for component in breakdown.components() {
+            assert!(component.calldata_gas >= 4 * component.uncompressed_bytes as u64);
+            assert!(component.calldata_gas <= 16 * component.uncompressed_bytes as u64);
+        }
+
+        let gas = breakdown.estimate_evm_gas();
+        assert_eq!(gas.pairing, 113_000);
+        assert!(gas.elliptic_curve >= num_commitments as u64 * 6_150);
+        assert!(gas.total() > gas.calldata + gas.elliptic_curve);
+    }
+
+    #[test]
+    fn fib_e2e_quarks_grand_products() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let config = SchedulerConfig::default().with_grand_product(GrandProductConfig::Quarks);
+        let (proof, commitments, debug_info, _) =
+            RV32IJoltVM::prove_with_metrics(io_device, trace, preprocessing.clone(), &config)
+                .unwrap();
+        for grand_product in [
+            &proof.bytecode.read_write_grand_product,
+            &proof.bytecode.init_final_grand_product,
+        ] {
+            assert!(grand_product.quark_proof.is_some());
+        }
+        RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info).unwrap();
+    }
+
+    #[test]
+    fn fib_e2e_proof_profiles() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        for profile in ProofProfile::ALL {
+            let config = SchedulerConfig::profile(profile);
+            let (proof, commitments, debug_info, _) = RV32IJoltVM::prove_with_metrics(
+                io_device.clone(),
+                trace.clone(),
+                preprocessing.clone(),
+                &config,
+            )
+            .unwrap();
+            assert_eq!(
+                proof
+                    .bytecode
+                    .read_write_grand_product
+                    .quark_proof
+                    .is_some(),
+                profile.grand_product() == GrandProductConfig::Quarks,
+                "{profile:?}"
+            );
+            RV32IJoltVM::verify(preprocessing.clone(), proof, commitments, debug_info)
+                .unwrap_or_else(|err| panic!("{profile:?}: {err:?}"));
+        }
+    }
+
+    #[test]
+    fn fib_e2e_json_round_trip() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        // Both grand product arguments, so that Quarks proofs (and their commitments) are covered
+        for profile in ProofProfile::ALL {
+            let config = SchedulerConfig::profile(profile);
+            let (proof, commitments, _, _) = RV32IJoltVM::prove_with_metrics(
+                io_device.clone(),
+                trace.clone(),
+                preprocessing.clone(),
+                &config,
+            )
+            .unwrap();
+            let json = to_json(&JoltHyperKZGProof { proof, commitments }).unwrap();
+            let decoded: JoltHyperKZGProof = from_json(&json).unwrap();
+            assert_eq!(to_json(&decoded).unwrap(), json, "{profile:?}");
+            RV32IJoltVM::verify(
+                preprocessing.clone(),
+                decoded.proof,
+                decoded.commitments,
+                None,
+            )
+            .unwrap_or_else(|err| panic!("{profile:?}: {err:?}"));
+        }
+    }
+
+    #[test]
+    fn fib_e2e_rejects_mutated_proofs() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+        let report = fuzz_verifier::<Fr, _, _>(
+            &JoltHyperKZGProof { proof, commitments },
+            30,
+            0,
+            |mutant: JoltHyperKZGProof| {
+                RV32IJoltVM::verify(
+                    preprocessing.clone(),
+                    mutant.proof,
+                    mutant.commitments,
+                    None,
+                )
+            },
+        );
+        report.assert_sound();
+        assert_eq!(report.num_mutants(), 30);
+    }
+
+    #[test]
+    fn fib_e2e_progress_and_cancellation() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+
+        let events = Arc::new(Mutex::new(vec![]));
+        let config = SchedulerConfig::default().with_observer({
+            let events = events.clone();
+            move |event: &ProverEvent| events.lock().unwrap().push(event.clone())
+        });
+        let (proof, commitments, debug_info, _) = RV32IJoltVM::prove_with_metrics(
+            io_device.clone(),
+            trace.clone(),
+            preprocessing.clone(),
+            &config,
+        )
+        .unwrap();
+        RV32IJoltVM::verify(preprocessing.clone(), proof, commitments, debug_info).unwrap();
+        let events = events.lock().unwrap();
+        assert_eq!(events.first().unwrap().fraction_complete(), 0.0);
+        assert!(matches!(
+            events.last().unwrap(),
+            ProverEvent::StageFinished { name: "Opening proof", fraction_complete, .. }
+                if *fraction_complete == 1.0
+        ));
+
+        // Cancelling once the witness is committed stops the prover before the stage proofs
+        let token = CancellationToken::new();
+        let started = Arc::new(Mutex::new(vec![]));
+        let config = SchedulerConfig::default()
+            .with_cancellation(token.clone())
+            .with_observer({
+                let started = started.clone();
+                move |event: &ProverEvent| match event {
+                    ProverEvent::StageStarted { name, .. } => started.lock().unwrap().push(*name),
+                    ProverEvent::StageFinished { name, .. } if *name == "R1CS commitment" => {
+                        token.cancel()
+                    }
+                    ProverEvent::StageFinished { .. } => {}
+                }
+            });
+        let result =
+            RV32IJoltVM::prove_with_metrics(io_device, trace, preprocessing.clone(), &config);
+        assert!(matches!(result, Err(JoltError::Cancelled)));
+        let started = started.lock().unwrap();
+        assert_eq!(started.last(), Some(&"R1CS commitment"));
+        assert!(!started.contains(&"Opening proof"));
+    }
+
+    #[test]
+    fn fib_e2e_streaming_verify() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let (proof, commitments, _) = <RV32IJoltVM as Jolt<Fr, HyperKZG<Bn254>, C, M>>::prove(
+            io_device,
+            trace,
+            preprocessing.clone(),
+        )
+        .unwrap();
+        let mut proof_bytes = Vec::new();
+        proof.serialize_streaming(&mut proof_bytes).unwrap();
+        assert!(proof.max_streaming_section_size() < proof_bytes.len());
+        let mut commitments_bytes = Vec::new();
+        commitments
+            .serialize_compressed(&mut commitments_bytes)
+            .unwrap();
+
+        let verification_result = RV32IJoltVM::verify_streaming(
+            preprocessing.clone(),
+            proof_bytes.as_slice(),
+            commitments,
+        );
+        assert!(
+            verification_result.is_ok(),
+            "Verification failed with error: {:?}",
+            verification_result.err()
+        );
+
+        let truncated = RV32IJoltVM::verify_streaming(
+            preprocessing,
+            &proof_bytes[..proof_bytes.len() - 1],
+            JoltCommitments::<HyperKZG<Bn254>>::deserialize_compressed(
+                commitments_bytes.as_slice(),
+            )
+            .unwrap(),
+        );
+        assert!(matches!(
+            truncated,
+            Err(JoltError::Verification(
+                ProofVerifyError::DeserializationError(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn fib_e2e_trace_too_long() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let trace_length = trace.len();
+        let preprocessing =
+            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 4);
+        let result = <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::prove(
+            io_device,
+            trace,
+            preprocessing,
+        );
+        let error = result.err().expect("proving should fail");
+        assert!(!error.is_soundness_failure());
+        assert!(matches!(
+            error,
+            JoltError::TraceTooLong {
+                trace_length: t,
+                max_trace_length: 16
+            } if t == trace_length
+        ));
+    }
+
+    #[test]
+    fn fib_e2e_transcript_v2() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode.clone(), memory_init, &io_device, &trace)
+                .unwrap()
+                .with_transcript_version(TranscriptVersion::V2);
+        let (proof, commitments, debug_info) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+        RV32IJoltVM::verify(preprocessing, proof, commitments, debug_info).unwrap();
+    }
+
+    #[test]
+    fn fib_e2e_namespaces() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let app_a = preprocessing
+            .clone()
+            .with_namespace(TranscriptNamespace::new("app-a", 1));
+        let app_b = preprocessing
+            .clone()
+            .with_namespace(TranscriptNamespace::new("app-b", 1));
+        let (proof, commitments, _) = RV32IJoltVM::prove(io_device, trace, app_a.clone()).unwrap();
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        commitments.serialize_compressed(&mut bytes).unwrap();
+        let reload = || {
+            let mut reader = &bytes[..];
+            let proof: RV32IJoltProof<Fr, HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            let commitments: JoltCommitments<HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            (proof, commitments)
+        };
+
+        let (proof, commitments) = reload();
+        RV32IJoltVM::verify(app_a, proof, commitments, None).unwrap();
+
+        for verifier in [app_b.clone(), preprocessing] {
+            let (proof, commitments) = reload();
+            let error = RV32IJoltVM::verify(verifier, proof, commitments, None).unwrap_err();
+            assert!(matches!(error, JoltError::TranscriptMismatch(_)));
+        }
+
+        // Relabeling the proof doesn't get it past the other application's verifier
+        let (mut proof, commitments) = reload();
+        proof.namespace = Some(TranscriptNamespace::new("app-b", 1));
+        proof.preprocessing_digest = app_b.digest(&proof.program_io.memory_layout);
+        let error = RV32IJoltVM::verify(app_b, proof, commitments, None).unwrap_err();
+        assert!(error.is_soundness_failure());
+        assert!(!matches!(error, JoltError::TranscriptMismatch(_)));
+    }
+
+    #[test]
+    fn fib_e2e_preprocessing_digest() {
+        let (preprocessing, io_device, trace) = fib_tiny::<Fr, HyperKZG<Bn254>>();
+        let memory_layout = io_device.memory_layout.clone();
+        let digest = preprocessing.digest(&memory_layout);
+        // The digest doesn't depend on how the preprocessing was obtained
+        let bytes = preprocessing.serialize_to_bytes().unwrap();
+        let reloaded =
+            <RV32IJoltVM as Jolt<Fr, HyperKZG<Bn254>, C, M>>::load_preprocessing(bytes.as_slice())
+                .unwrap();
+        assert_eq!(reloaded.digest(&memory_layout), digest);
+        assert_ne!(
+            preprocessing
+                .clone()
+                .with_max_cycles(100)
+                .digest(&memory_layout),
+            digest
+        );
+        let mut other_layout = memory_layout.clone();
+        other_layout.stack_size += 8;
+        assert_ne!(preprocessing.digest(&other_layout), digest);
+
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+        assert_eq!(proof.preprocessing_digest, digest);
+        let header: JoltProofHeader = (&proof).into();
+        assert_eq!(header.preprocessingDigest.0, digest);
+        assert!(!header.exceededMaxCycles);
+        assert!(!header.hasNamespace);
+        assert!(header.blobHashes.is_empty());
+
+        let mut bad_proof = proof;
+        bad_proof.preprocessing_digest[0] ^= 1;
+        let error = RV32IJoltVM::verify(preprocessing, bad_proof, commitments, None).unwrap_err();
+        assert!(matches!(error, JoltError::TranscriptMismatch(_)));
+    }
+
+    #[test]
+    #[ignore = "requires the Spike simulator on the PATH"]
+    fn fib_matches_spike() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.build();
+        let elf = program.elf.clone().unwrap();
+        let result = tracer::diff_test(&elf, tracer::Reference::spike());
+        drop(artifact_guard);
+        let compared = result.unwrap_or_else(|e| panic!("{}", e));
+        assert!(compared > 0);
+    }
+
+    #[test]
+    fn fib_e2e_max_cycles() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        program.set_max_cycles(100);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+        assert_eq!(io_device.exceeded_max_cycles, Some(100));
+        assert!(trace.len() >= 100);
+
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode, memory_init, &io_device, &trace).unwrap();
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone().with_max_cycles(100))
+                .unwrap();
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        commitments.serialize_compressed(&mut bytes).unwrap();
+        let reload = || {
+            let mut reader = &bytes[..];
+            let proof: RV32IJoltProof<Fr, HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            let commitments: JoltCommitments<HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            (proof, commitments)
+        };
+
+        let (proof, commitments) = reload();
+        RV32IJoltVM::verify(
+            preprocessing.clone().with_max_cycles(100),
+            proof,
+            commitments,
+            None,
+        )
+        .unwrap();
+
+        // Verifiers expecting a different limit, or none, reject the early exit
+        for verifier in [
+            preprocessing.clone().with_max_cycles(50),
+            preprocessing.clone(),
+        ] {
+            let (proof, commitments) = reload();
+            let error = RV32IJoltVM::verify(verifier, proof, commitments, None).unwrap_err();
+            assert!(matches!(error, JoltError::TranscriptMismatch(_)));
+        }
+
+        // Dropping the early exit claim doesn't verify either
+        let (mut proof, commitments) = reload();
+        proof.program_io.exceeded_max_cycles = None;
+        let error = RV32IJoltVM::verify(preprocessing, proof, commitments, None).unwrap_err();
+        assert!(error.is_soundness_failure());
+    }
+
+    #[test]
+    // The PC update into the first no-op doesn't hold, which the prover checks in tests
+    #[should_panic(expected = "Non-uniform constraint 0 violated")]
+    fn fib_e2e_max_cycles_rejects_early_halt() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        program.set_max_cycles(50);
+        let (bytecode, memory_init) = program.decode();
+        let (mut io_device, mut trace) = program.trace();
+        drop(artifact_guard);
+        assert!(trace.len() < 100);
+
+        // Claim to have halted at 100 cycles, padding the trace with no-ops to make up the length
+        io_device.exceeded_max_cycles = Some(100);
+        trace.resize(100, JoltTraceStep::no_op());
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode, memory_init, &io_device, &trace)
+                .unwrap()
+                .with_max_cycles(100);
+        let _ = RV32IJoltVM::prove(io_device, trace, preprocessing);
+    }
+
+    #[test]
+    fn muldiv_e2e_max_cycles_rejects_early_halt() {
+        let artifact_guard = MULDIV_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("muldiv-guest");
+        program.set_input(&123u32);
+        program.set_input(&234u32);
+        program.set_input(&345u32);
+        let (bytecode, memory_init) = program.decode();
+        let (mut io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        // The division is a virtual sequence of several steps, but a single cycle
+        let cycles = trace
+            .iter()
+            .filter(|step| !step.circuit_flags[CircuitFlags::DoNotUpdatePC as usize])
+            .count();
+        assert!(cycles < trace.len());
+
+        // Claim to have halted at as many cycles as there are steps in the trace
+        let max_cycles = trace.len() as u64;
+        io_device.exceeded_max_cycles = Some(max_cycles);
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode, memory_init, &io_device, &trace)
+                .unwrap()
+                .with_max_cycles(max_cycles);
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+        let error = RV32IJoltVM::verify(preprocessing, proof, commitments, None).unwrap_err();
+        assert!(error.is_soundness_failure());
+    }
+
+    #[test]
+    // The first instruction after the claimed end of the trace isn't the no-op
+    #[should_panic(expected = "Uniform constraint")]
+    fn fib_rejects_understated_trace_length() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        // Checks the R1CS witness of a prover claiming the given trace length, which panics in
+        // tests if a constraint doesn't hold
+        let check = |trace_length: usize| {
+            let (polynomials, r1cs_builder) =
+                <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::witness(
+                    bytecode.clone(),
+                    memory_init.clone(),
+                    &io_device,
+                    trace.clone(),
+                    trace_length,
+                );
+            let flattened_polys: Vec<_> = JoltR1CSInputs::<RV32I>::flatten::<C>()
+                .iter()
+                .map(|var| var.get_ref(&polynomials))
+                .collect();
+            r1cs_builder.compute_spartan_Az_Bz_Cz::<MockCommitScheme<Fr>>(&flattened_polys);
+        };
+        check(trace.len());
+        // Leaves the rest of the execution, including its stores to the outputs, in the padding
+        check(trace.len() / 2);
+    }
+
+    #[test]
+    fn fib_e2e_blobs() {
+        let blob: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        program.set_max_blob_size(1 << 12);
+        assert_eq!(program.add_blob(blob.clone()), 0);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+        assert_eq!(io_device.blobs.get(0), Some(blob.as_slice()));
+
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode, memory_init, &io_device, &trace).unwrap();
+        let (proof, commitments, _) =
+            RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        commitments.serialize_compressed(&mut bytes).unwrap();
+        let reload = || {
+            let mut reader = &bytes[..];
+            let proof: RV32IJoltProof<Fr, HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            let commitments: JoltCommitments<HyperKZG<Bn254>> =
+                CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+            (proof, commitments)
+        };
+
+        // Only the blob's hash is in the proof
+        let (proof, commitments) = reload();
+        assert!(!proof.program_io.blobs.is_attached());
+        let error =
+            RV32IJoltVM::verify(preprocessing.clone(), proof, commitments, None).unwrap_err();
+        assert!(matches!(error, JoltError::MissingBlobs));
+
+        let (mut proof, commitments) = reload();
+        let mut other_blob = blob.clone();
+        other_blob[0] ^= 1;
+        assert!(!proof.program_io.blobs.attach(vec![other_blob]));
+        assert!(proof.program_io.blobs.attach(vec![blob]));
+        RV32IJoltVM::verify(preprocessing.clone(), proof, commitments, None).unwrap();
+
+        // Dropping the blob doesn't verify
+        let (mut proof, commitments) = reload();
+        proof.program_io.blobs = Default::default();
+        let error = RV32IJoltVM::verify(preprocessing, proof, commitments, None).unwrap_err();
+        assert!(error.is_soundness_failure());
+    }
+
+    #[test]
+    fn fib_verifier_reuse() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let executions: Vec<_> = [9u32, 8]
+            .iter()
+            .map(|n| {
+                let mut program = host::Program::new("fibonacci-guest");
+                program.set_input(n);
+                let (bytecode, memory_init) = program.decode();
+                let (io_device, trace) = program.trace();
+                (bytecode, memory_init, io_device, trace)
+            })
+            .collect();
+        drop(artifact_guard);
+
+        let (bytecode, memory_init, io_device, trace) = &executions[0];
+        let preprocessing: JoltPreprocessing<C, Fr, HyperKZG<Bn254>> =
+            RV32IJoltVM::preprocess_tiny(bytecode.clone(), memory_init.clone(), io_device, trace)
+                .unwrap();
+        let verifier = RV32IVerifier::<Fr, HyperKZG<Bn254>>::new(preprocessing.clone());
+
+        for (_, _, io_device, trace) in executions {
+            let (proof, commitments, _) =
+                RV32IJoltVM::prove(io_device, trace, preprocessing.clone()).unwrap();
+            let mut bytes = vec![];
+            proof.serialize_compressed(&mut bytes).unwrap();
+            commitments.serialize_compressed(&mut bytes).unwrap();
+            let reload = || {
+                let mut reader = &bytes[..];
+                let proof: RV32IJoltProof<Fr, HyperKZG<Bn254>> =
+                    CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+                let commitments: JoltCommitments<HyperKZG<Bn254>> =
+                    CanonicalDeserialize::deserialize_compressed(&mut reader).unwrap();
+                (proof, commitments)
+            };
+
+            let (proof, commitments) = reload();
+            verifier.verify(proof, commitments).unwrap();
+
+            // The cached R1CS preprocessing doesn't make the verifier accept other outputs
+            let (mut proof, commitments) = reload();
+            proof.program_io.outputs[0] ^= 1;
+            let error = verifier.verify(proof, commitments).unwrap_err();
+            assert!(error.is_soundness_failure());
+        }
+    }
+
+    #[test]
+    fn fib_witness_audit() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let trace_length = trace.len();
+        let audit = <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::audit_witness(
+            bytecode,
+            memory_init,
+            &io_device,
+            trace,
+        );
+        assert_eq!(audit.trace_length, trace_length);
+        let column = |name: &str| audit.columns.iter().find(|c| c.name == name).unwrap();
+
+        let add_flag = column("instruction_lookups.instruction_flags[ADD]");
+        assert_eq!(add_flag.len, trace_length.next_power_of_two());
+        assert_eq!(add_flag.max, Some(1));
+        assert_eq!(add_flag.compact_type(), CompactType::U8);
+        // Timestamps are bounded by the padded trace length, since the padding reads x0 too
+        let t_read = column("read_write_memory.t_read[rs1]");
+        assert!(t_read.max.unwrap() < t_read.len as u64);
+        assert!(t_read.min_negative.is_none());
+        // Backward branches have negative offsets
+        assert!(column("r1cs.imm_signed").min_negative.is_some());
+    }
+
+    #[test]
+    fn fib_trace_sampled() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&1000u32);
+        let summary = program.clone().trace_analyze::<Fr>();
+        let sampled = program.trace_sampled(host::Sampling {
+            window: 16,
+            period: 4,
+        });
+        drop(artifact_guard);
+
+        let total = summary.raw_trace.len();
+        assert_eq!(sampled.total_instructions, total as u64);
+        assert!(sampled.sampled_instructions.abs_diff(total / 4) <= 16);
+        assert_eq!(sampled.io_device, summary.io_device);
+
+        let trace_length = summary.trace_len();
+        let estimated_length = sampled.profile.total_cycles;
+        assert!(estimated_length.abs_diff(trace_length) * 20 < trace_length);
+        let lookups = summary
+            .processed_trace
+            .iter()
+            .filter(|step| step.instruction_lookup.is_some())
+            .count();
+        let estimated_lookups: usize = sampled.lookups.iter().map(|(_, count)| count).sum();
+        assert!(estimated_lookups.abs_diff(lookups) * 10 < lookups);
+        assert!(sampled.subtable_lookups.len() > 1);
+    }
+
+    #[test]
+    fn fib_final_state_diff() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (io_device, _) = program.clone().trace();
+        assert!(program.clone().diff_final_state(&io_device).is_empty());
+
+        let mut claimed = io_device.clone();
+        claimed.outputs[0] ^= 1;
+        claimed.panic = true;
+        let diff = program.diff_final_state(&claimed);
+        drop(artifact_guard);
+
+        assert_eq!(diff.panic, Some((true, false)));
+        assert_eq!(diff.outputs.len(), 1);
+        let mismatch = &diff.outputs[0];
+        let output_start = io_device.memory_layout.output_start;
+        assert_eq!(mismatch.addresses, output_start..output_start + 1);
+        assert_eq!(mismatch.claimed, vec![io_device.outputs[0] ^ 1]);
+        assert_eq!(mismatch.actual, vec![io_device.outputs[0]]);
+        assert!(mismatch.last_writer.is_some());
+    }
+
+    #[test]
+    fn tiny_preprocessing() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        program.set_input(&9u32);
+        let (bytecode, memory_init) = program.decode();
+        let (io_device, trace) = program.trace();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, MockCommitScheme<Fr>> =
+            RV32IJoltVM::preprocess_tiny(bytecode.clone(), memory_init, &io_device, &trace)
+                .unwrap();
+        let metadata = &preprocessing.metadata;
+        assert_eq!(metadata.max_trace_length, trace.len().next_power_of_two());
+        assert!(metadata.max_bytecode_size.is_power_of_two());
+        assert!(metadata.max_bytecode_size >= bytecode.len());
+        let report = preprocessing.bytecode_report();
+        assert!(report.fits());
+        assert!(report.num_distinct_rows < report.num_rows);
+        assert!(metadata.max_memory_address.is_power_of_two());
+        assert!(metadata.max_memory_address < 1 << 20);
+    }
+
     #[ignore = "Opening proof reduction for Hyrax doesn't work right now"]
     #[test]
     fn fib_e2e_hyrax() {
@@ -326,6 +1381,16 @@ mod tests {
         fib_e2e::<Fr, HyperKZG<Bn254>>();
     }
 
+    #[test]
+    fn fib_e2e_zeromorph_bls12_381() {
+        fib_e2e::<ark_bls12_381::Fr, Zeromorph<Bls12_381>>();
+    }
+
+    #[test]
+    fn fib_e2e_hyperkzg_bls12_381() {
+        fib_e2e::<ark_bls12_381::Fr, HyperKZG<Bls12_381>>();
+    }
+
     // TODO(sragss): Finish Binius.
     // #[test]
     // fn fib_e2e_binius() {
@@ -350,7 +1415,8 @@ mod tests {
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
+            )
+            .unwrap();
         let verification_result =
             RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
         assert!(
@@ -378,7 +1444,8 @@ mod tests {
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
+            )
+            .unwrap();
 
         let verification_result =
             RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
@@ -400,13 +1467,15 @@ mod tests {
         drop(guard);
 
         let preprocessing =
-            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+            RV32IJoltVM::preprocess_tiny(bytecode.clone(), memory_init, &io_device, &trace)
+                .unwrap();
         let (jolt_proof, jolt_commitments, debug_info) =
             <RV32IJoltVM as Jolt<_, Zeromorph<Bn254>, C, M>>::prove(
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
+            )
+            .unwrap();
 
         let verification_result =
             RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
@@ -428,13 +1497,15 @@ mod tests {
         drop(guard);
 
         let preprocessing =
-            RV32IJoltVM::preprocess(bytecode.clone(), memory_init, 1 << 20, 1 << 20, 1 << 20);
+            RV32IJoltVM::preprocess_tiny(bytecode.clone(), memory_init, &io_device, &trace)
+                .unwrap();
         let (jolt_proof, jolt_commitments, debug_info) =
             <RV32IJoltVM as Jolt<_, HyperKZG<Bn254>, C, M>>::prove(
                 io_device,
                 trace,
                 preprocessing.clone(),
-            );
+            )
+            .unwrap();
 
         let verification_result =
             RV32IJoltVM::verify(preprocessing, jolt_proof, jolt_commitments, debug_info);
@@ -444,4 +1515,75 @@ mod tests {
             verification_result.err()
         );
     }
+
+    #[test]
+    fn preprocessing_metadata_checks() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut program = host::Program::new("fibonacci-guest");
+        let (bytecode, memory_init) = program.decode();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, MockCommitScheme<Fr>> =
+            RV32IJoltVM::preprocess(bytecode, memory_init, 1 << 20, 1 << 20, 1 << 20);
+        let bytes = preprocessing.serialize_to_bytes().unwrap();
+
+        let loaded = <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::load_preprocessing(
+            bytes.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(loaded.metadata, preprocessing.metadata);
+        assert_eq!(loaded.metadata.field, "bn254::Fr");
+        assert_eq!(loaded.metadata.pcs, "Mock");
+
+        let wrong_pcs =
+            <RV32IJoltVM as Jolt<Fr, Zeromorph<Bn254>, C, M>>::load_preprocessing(bytes.as_slice());
+        assert!(matches!(
+            wrong_pcs,
+            Err(PreprocessingError::Incompatible {
+                parameter: "commitment scheme",
+                ..
+            })
+        ));
+
+        let not_preprocessing =
+            <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::load_preprocessing(&bytes[1..]);
+        assert!(matches!(
+            not_preprocessing,
+            Err(PreprocessingError::InvalidMagic)
+        ));
+
+        // Flip a byte of the crate version string, which follows the magic and a length prefix.
+        let mut corrupted = bytes.clone();
+        corrupted[PREPROCESSING_MAGIC.len() + 8] ^= 1;
+        let corrupted = <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::load_preprocessing(
+            corrupted.as_slice(),
+        );
+        assert!(matches!(
+            corrupted,
+            Err(PreprocessingError::CorruptedMetadata)
+        ));
+    }
+
+    #[test]
+    fn program_ids() {
+        let artifact_guard = FIB_FILE_LOCK.lock().unwrap();
+        let mut fib = host::Program::new("fibonacci-guest");
+        let (bytecode, memory_init) = fib.decode();
+        let fib_id = fib.program_id();
+        drop(artifact_guard);
+        let artifact_guard = SHA3_FILE_LOCK.lock().unwrap();
+        let sha3_id = host::Program::new("sha3-guest").program_id();
+        drop(artifact_guard);
+
+        let preprocessing: JoltPreprocessing<C, Fr, MockCommitScheme<Fr>> =
+            RV32IJoltVM::preprocess(bytecode, memory_init, 1 << 20, 1 << 20, 1 << 20);
+        assert_eq!(preprocessing.program_id(), fib_id);
+        assert_ne!(fib_id, sha3_id);
+
+        let loaded = <RV32IJoltVM as Jolt<Fr, MockCommitScheme<Fr>, C, M>>::load_preprocessing(
+            preprocessing.serialize_to_bytes().unwrap().as_slice(),
+        )
+        .unwrap();
+        assert_eq!(loaded.program_id(), fib_id);
+    }
 }
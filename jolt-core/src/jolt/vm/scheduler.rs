@@ -0,0 +1,600 @@
+//! Prover scheduling.
+//!
+//! The stages of a Jolt proof (see [`JoltStage`](super::stage::JoltStage)) are independent of
+//! one another once the witness has been committed: each one proves its relation over its own
+//! fork of the Fiat-Shamir transcript, and only the final batched opening proof depends on all
+//! of them. The prover runs them as a [`TaskGraph`] on a single rayon pool, so that threads left
+//! idle by a stage with little parallelism (e.g. the sequential rounds of a small sumcheck) are
+//! stolen by the others, rather than each stage having the whole machine to itself in turn.
+//!
+//! The schedule does not affect the proof: a [`SchedulerConfig::sequential`] prover emits
+//! exactly the same bytes, using less memory.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::progress::{CancellationToken, ProgressReporter, ProverObserver};
+use crate::subprotocols::grand_product::GrandProductConfig;
+use crate::utils::errors::JoltError;
+
+/// How the prover uses the machine.
+#[derive(Clone)]
+pub struct SchedulerConfig {
+    /// Number of prover threads. Defaults to the threads of the current rayon pool, and is
+    /// ignored when proving on a pool thread already (see [`Self::install`]).
+    pub num_threads: Option<usize>,
+    /// Whether independent tasks run concurrently. Proving them one at a time lowers peak
+    /// memory, since only one stage's intermediate polynomials are live at once.
+    pub parallel_stages: bool,
+    /// Notified as each task starts and finishes.
+    pub observer: Option<Arc<dyn ProverObserver>>,
+    /// Stops the prover between tasks once cancelled.
+    pub cancellation: Option<CancellationToken>,
+    /// The argument used for memory-checking grand products. Unlike the other options, this
+    /// changes the proof, though either choice verifies.
+    pub grand_product: GrandProductConfig,
+    /// Soft limit on the memory the prover uses, in bytes. It isn't enforced by the allocator:
+    /// rather, within the limit's pool (see [`Self::install`]) the prover commits to fewer
+    /// polynomials at once, splits memory-checking counters into fewer chunks, and proves the
+    /// stages one at a time if the witness leaves too little room to run them concurrently.
+    pub max_memory_bytes: Option<usize>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            num_threads: None,
+            parallel_stages: true,
+            observer: None,
+            cancellation: None,
+            grand_product: GrandProductConfig::default(),
+            max_memory_bytes: None,
+        }
+    }
+}
+
+/// A named bundle of the prover options that trade proof size, prover time and verifier time
+/// against one another, for [`SchedulerConfig::profile`]. Every profile produces proofs that the
+/// same verifier accepts.
+///
+/// The commitment scheme is chosen at compile time, through the `PCS` type parameter, so it is
+/// not part of a profile; each profile notes the scheme that suits it. Nor are opening batching
+/// and padding, which every profile does the same way: all openings are batched into one, and
+/// the trace is padded to a power of two.
+///
+/// There is no profile for a cheaper verifier: [`Self::FastProver`] is already the cheapest to
+/// verify, since its openings are all checked by the batched opening proof, with a single
+/// pairing. Lowering verifier cost further would take a change to the proof, e.g. folding the
+/// Quarks openings into the batched opening, which is out of scope for now.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProofProfile {
+    /// Proves grand products with layered GKR and runs the stages concurrently. Proofs have
+    /// O(log² n) grand product layers, but nothing beyond the witness is committed. This is the
+    /// default configuration. Pairs with HyperKZG or Zeromorph, whose commitments are the
+    /// cheapest to compute.
+    #[default]
+    FastProver,
+    /// Proves grand products with Quarks, for O(log n) proofs. The prover commits to and opens
+    /// an extra polynomial per grand product, and the verifier checks those openings with a few
+    /// extra pairings. Pairs with HyperKZG, whose opening proofs are the smallest.
+    SmallProof,
+}
+
+impl ProofProfile {
+    pub const ALL: [ProofProfile; 2] = [ProofProfile::FastProver, ProofProfile::SmallProof];
+
+    pub fn grand_product(&self) -> GrandProductConfig {
+        match self {
+            ProofProfile::FastProver => GrandProductConfig::Gkr,
+            ProofProfile::SmallProof => GrandProductConfig::Quarks,
+        }
+    }
+}
+
+impl fmt::Debug for SchedulerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchedulerConfig")
+            .field("num_threads", &self.num_threads)
+            .field("parallel_stages", &self.parallel_stages)
+            .field("observer", &self.observer.is_some())
+            .field("cancellation", &self.cancellation)
+            .field("grand_product", &self.grand_product)
+            .field("max_memory_bytes", &self.max_memory_bytes)
+            .finish()
+    }
+}
+
+impl SchedulerConfig {
+    /// Runs one task at a time, each using every prover thread.
+    pub fn sequential() -> Self {
+        Self {
+            parallel_stages: false,
+            ..Default::default()
+        }
+    }
+
+    /// The configuration of `profile`, with every thread of the current pool. The other
+    /// options (threads, observer, cancellation) can still be set on the result.
+    pub fn profile(profile: ProofProfile) -> Self {
+        Self::default().with_grand_product(profile.grand_product())
+    }
+
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        assert!(num_threads > 0, "the prover needs at least one thread");
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    pub fn with_observer(mut self, observer: impl ProverObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    pub fn with_grand_product(mut self, grand_product: GrandProductConfig) -> Self {
+        self.grand_product = grand_product;
+        self
+    }
+
+    pub fn with_max_memory_bytes(mut self, max_memory_bytes: usize) -> Self {
+        self.max_memory_bytes = Some(max_memory_bytes);
+        self
+    }
+
+    /// Runs `op` on a pool of `num_threads` threads, or on the current pool if unset. A
+    /// `max_memory_bytes` limit also needs a pool of its own (of as many threads as the current
+    /// one, unless `num_threads` is set), whose threads see it through [`memory_budget`].
+    ///
+    /// If this is called on a pool thread already, e.g. within
+    /// [`ThrottleConfig::install`](crate::utils::throttle::ThrottleConfig::install), `op` runs
+    /// on that pool instead, since a pool of its own would escape the enclosing pool's limits.
+    /// The memory limit is then set on the enclosing pool's threads until `op` returns.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        if rayon::current_thread_index().is_some() {
+            if self.max_memory_bytes.is_none() {
+                return op();
+            }
+            let previous = rayon::broadcast(|_| {
+                MEMORY_BUDGET.with(|budget| budget.replace(self.max_memory_bytes))
+            });
+            let result = op();
+            rayon::broadcast(|context| {
+                MEMORY_BUDGET.with(|budget| budget.set(previous[context.index()]))
+            });
+            return result;
+        }
+        if self.num_threads.is_none() && self.max_memory_bytes.is_none() {
+            return op();
+        }
+        let max_memory_bytes = self.max_memory_bytes;
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads.unwrap_or_else(rayon::current_num_threads))
+            .start_handler(move |_| MEMORY_BUDGET.with(|budget| budget.set(max_memory_bytes)))
+            .build()
+            .unwrap()
+            .install(op)
+    }
+
+    /// Whether the stages of a proof whose witness takes `witness_bytes` are proven
+    /// concurrently. Each stage's intermediate polynomials take up to about as much memory as
+    /// the part of the witness it proves, so running all of them at once roughly doubles the
+    /// memory used.
+    pub(crate) fn parallel_stages(&self, witness_bytes: usize) -> bool {
+        self.parallel_stages
+            && self.max_memory_bytes.map_or(true, |max_memory_bytes| {
+                2 * witness_bytes <= max_memory_bytes
+            })
+    }
+}
+
+thread_local! {
+    static MEMORY_BUDGET: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// The `max_memory_bytes` of the prover running on the current thread, if any.
+pub(crate) fn memory_budget() -> Option<usize> {
+    MEMORY_BUDGET.with(Cell::get)
+}
+
+/// The number of items of `item_bytes` each to process at once: `default`, or fewer if they
+/// would take more than a quarter of the memory budget, but at least one.
+pub(crate) fn budgeted_batch_size(default: usize, item_bytes: usize) -> usize {
+    match memory_budget() {
+        Some(budget) => (budget / 4 / item_bytes.max(1)).clamp(1, default.max(1)),
+        None => default.max(1),
+    }
+}
+
+/// When a prover task ran, relative to the start of the proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskTiming {
+    pub name: &'static str,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl TaskTiming {
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// Per-task timings of a proof, in the order the tasks finished.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProverMetrics {
+    pub num_threads: usize,
+    pub tasks: Vec<TaskTiming>,
+    pub total: Duration,
+}
+
+impl ProverMetrics {
+    pub fn task(&self, name: &str) -> Option<&TaskTiming> {
+        self.tasks.iter().find(|task| task.name == name)
+    }
+
+    /// Sum of the task durations over the wall-clock time of the proof; above 1 when tasks
+    /// overlapped.
+    pub fn concurrency(&self) -> f64 {
+        let busy: Duration = self.tasks.iter().map(|task| task.duration).sum();
+        busy.as_secs_f64() / self.total.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+impl fmt::Display for ProverMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Proved in {:.3}s on {} threads (concurrency {:.2})",
+            self.total.as_secs_f64(),
+            self.num_threads,
+            self.concurrency()
+        )?;
+        for task in &self.tasks {
+            writeln!(
+                f,
+                "  {:<32} {:>9.3}s .. {:>9.3}s ({:.3}s)",
+                task.name,
+                task.start.as_secs_f64(),
+                task.end().as_secs_f64(),
+                task.duration.as_secs_f64()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects [`TaskTiming`]s from concurrently running tasks, reporting their progress to the
+/// configured observer.
+pub(crate) struct MetricsRecorder {
+    start: Instant,
+    tasks: Mutex<Vec<TaskTiming>>,
+    progress: ProgressReporter,
+}
+
+impl MetricsRecorder {
+    pub fn new(config: &SchedulerConfig) -> Self {
+        Self {
+            start: Instant::now(),
+            tasks: Mutex::new(vec![]),
+            progress: ProgressReporter::new(config.observer.clone(), config.cancellation.clone()),
+        }
+    }
+
+    pub fn time<T>(&self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        self.progress.started(name);
+        let start = self.start.elapsed();
+        let result = f();
+        let duration = self.start.elapsed() - start;
+        self.progress.finished(name, duration);
+        self.tasks.lock().unwrap().push(TaskTiming {
+            name,
+            start,
+            duration,
+        });
+        result
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.progress.is_cancelled()
+    }
+
+    /// Returns [`JoltError::Cancelled`] if the proof has been cancelled.
+    pub fn check_cancelled(&self) -> Result<(), JoltError> {
+        self.progress.check_cancelled()
+    }
+
+    pub fn finish(self) -> ProverMetrics {
+        ProverMetrics {
+            num_threads: rayon::current_num_threads(),
+            total: self.start.elapsed(),
+            tasks: self.tasks.into_inner().unwrap(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TaskId(usize);
+
+struct Task<'a> {
+    name: &'static str,
+    dependencies: Vec<TaskId>,
+    run: Box<dyn FnOnce() + Send + 'a>,
+}
+
+/// A set of tasks with explicit dependencies between them. Tasks pass results to their
+/// dependents through state they capture by reference (e.g. a [`std::sync::OnceLock`]).
+#[derive(Default)]
+pub(crate) struct TaskGraph<'a> {
+    tasks: Vec<Task<'a>>,
+}
+
+impl<'a> TaskGraph<'a> {
+    pub fn new() -> Self {
+        Self { tasks: vec![] }
+    }
+
+    /// Adds a task that runs once all of `dependencies` have finished. Dependencies must
+    /// already be in the graph, which is therefore acyclic.
+    pub fn add_task(
+        &mut self,
+        name: &'static str,
+        dependencies: &[TaskId],
+        run: impl FnOnce() + Send + 'a,
+    ) -> TaskId {
+        assert!(
+            dependencies
+                .iter()
+                .all(|dependency| dependency.0 < self.tasks.len()),
+            "task {name} depends on a task outside of the graph"
+        );
+        self.tasks.push(Task {
+            name,
+            dependencies: dependencies.to_vec(),
+            run: Box::new(run),
+        });
+        TaskId(self.tasks.len() - 1)
+    }
+
+    /// Runs every task, recording its timing. In parallel mode each task is spawned onto the
+    /// current rayon pool as soon as its dependencies have finished; otherwise tasks run one
+    /// after the other, in the order they were added. Once the proof is cancelled no further
+    /// tasks are started, so callers must check for cancellation before using any results.
+    pub fn run(self, parallel: bool, metrics: &MetricsRecorder) {
+        if !parallel || rayon::current_num_threads() == 1 {
+            for task in self.tasks {
+                if metrics.is_cancelled() {
+                    return;
+                }
+                metrics.time(task.name, task.run);
+            }
+            return;
+        }
+
+        let mut dependents = vec![vec![]; self.tasks.len()];
+        let remaining: Vec<_> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| {
+                for dependency in &task.dependencies {
+                    dependents[dependency.0].push(index);
+                }
+                AtomicUsize::new(task.dependencies.len())
+            })
+            .collect();
+        let graph = ScheduledGraph {
+            names: self.tasks.iter().map(|task| task.name).collect(),
+            runs: self
+                .tasks
+                .into_iter()
+                .map(|task| Mutex::new(Some(task.run)))
+                .collect(),
+            dependents,
+            remaining,
+            metrics,
+        };
+
+        rayon::scope(|scope| {
+            for (index, remaining) in graph.remaining.iter().enumerate() {
+                if remaining.load(Ordering::Relaxed) == 0 {
+                    graph.spawn(scope, index);
+                }
+            }
+        });
+    }
+}
+
+struct ScheduledGraph<'a, 'm> {
+    names: Vec<&'static str>,
+    runs: Vec<Mutex<Option<Box<dyn FnOnce() + Send + 'a>>>>,
+    dependents: Vec<Vec<usize>>,
+    /// Number of unfinished dependencies of each task
+    remaining: Vec<AtomicUsize>,
+    metrics: &'m MetricsRecorder,
+}
+
+impl ScheduledGraph<'_, '_> {
+    fn spawn<'s>(&'s self, scope: &rayon::Scope<'s>, index: usize) {
+        scope.spawn(move |scope| {
+            if self.metrics.is_cancelled() {
+                return;
+            }
+            let run = self.runs[index].lock().unwrap().take().unwrap();
+            self.metrics.time(self.names[index], run);
+            for &dependent in &self.dependents[index] {
+                if self.remaining[dependent].fetch_sub(1, Ordering::AcqRel) == 1 {
+                    self.spawn(scope, dependent);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jolt::vm::progress::ProverEvent;
+    use rayon::prelude::*;
+    use std::sync::OnceLock;
+
+    #[test]
+    fn dependencies_run_first() {
+        for parallel in [true, false] {
+            let metrics = MetricsRecorder::new(&SchedulerConfig::default());
+            let order = Mutex::new(vec![]);
+            let a = OnceLock::new();
+            let b = OnceLock::new();
+            let c = OnceLock::new();
+
+            let mut graph = TaskGraph::new();
+            let task_a = graph.add_task("a", &[], || {
+                a.set(1).unwrap();
+                order.lock().unwrap().push("a");
+            });
+            let task_b = graph.add_task("b", &[task_a], || {
+                b.set(a.get().unwrap() + 1).unwrap();
+                order.lock().unwrap().push("b");
+            });
+            let independent = graph.add_task("independent", &[], || {
+                order.lock().unwrap().push("independent");
+            });
+            graph.add_task("c", &[task_a, task_b, independent], || {
+                c.set(a.get().unwrap() + b.get().unwrap()).unwrap();
+                order.lock().unwrap().push("c");
+            });
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(4)
+                .build()
+                .unwrap()
+                .install(|| graph.run(parallel, &metrics));
+
+            assert_eq!(c.get(), Some(&3));
+            let order = order.into_inner().unwrap();
+            let position = |name| order.iter().position(|task| *task == name).unwrap();
+            assert!(position("a") < position("b"));
+            assert!(position("b") < position("c"));
+            assert!(position("independent") < position("c"));
+            if !parallel {
+                assert_eq!(order, vec!["a", "b", "independent", "c"]);
+            }
+
+            let metrics = metrics.finish();
+            assert_eq!(metrics.tasks.len(), 4);
+            let (b, c) = (metrics.task("b").unwrap(), metrics.task("c").unwrap());
+            assert!(b.end() <= c.start);
+            assert!(metrics.total >= c.end());
+        }
+    }
+
+    #[test]
+    fn memory_budget_is_scoped_to_the_pool() {
+        assert_eq!(memory_budget(), None);
+        let config = SchedulerConfig::default()
+            .with_num_threads(2)
+            .with_max_memory_bytes(1 << 20);
+        let (budgets, batch_size) = config.install(|| {
+            let budgets: Vec<_> = (0..16).into_par_iter().map(|_| memory_budget()).collect();
+            (budgets, budgeted_batch_size(64, 1 << 16))
+        });
+        assert!(budgets.iter().all(|budget| *budget == Some(1 << 20)));
+        assert_eq!(batch_size, 4);
+        assert_eq!(budgeted_batch_size(64, 1 << 16), 64);
+
+        assert!(config.parallel_stages(1 << 19));
+        assert!(!config.parallel_stages((1 << 19) + 1));
+        assert!(SchedulerConfig::default().parallel_stages(usize::MAX / 2));
+    }
+
+    #[test]
+    fn cancelled_between_tasks() {
+        for parallel in [true, false] {
+            let token = CancellationToken::new();
+            let events = Arc::new(Mutex::new(vec![]));
+            let config = SchedulerConfig::default()
+                .with_cancellation(token.clone())
+                .with_observer({
+                    let events = events.clone();
+                    move |event: &ProverEvent| events.lock().unwrap().push(event.name())
+                });
+            let metrics = MetricsRecorder::new(&config);
+            let ran_after = OnceLock::new();
+
+            let mut graph = TaskGraph::new();
+            let cancel = graph.add_task("cancel", &[], || token.cancel());
+            graph.add_task("after", &[cancel], || ran_after.set(()).unwrap());
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(4)
+                .build()
+                .unwrap()
+                .install(|| graph.run(parallel, &metrics));
+
+            assert!(ran_after.get().is_none());
+            assert!(matches!(
+                metrics.check_cancelled(),
+                Err(JoltError::Cancelled)
+            ));
+            assert_eq!(*events.lock().unwrap(), vec!["cancel", "cancel"]);
+            assert_eq!(metrics.finish().tasks.len(), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the graph")]
+    fn unknown_dependency() {
+        let mut other = TaskGraph::new();
+        other.add_task("a", &[], || {});
+        let b = other.add_task("b", &[], || {});
+        TaskGraph::new().add_task("c", &[b], || {});
+    }
+
+    #[test]
+    fn thread_budget() {
+        let config = SchedulerConfig::default().with_num_threads(3);
+        assert_eq!(config.install(rayon::current_num_threads), 3);
+        assert!(config.parallel_stages);
+        assert!(!SchedulerConfig::sequential().parallel_stages);
+    }
+
+    #[test]
+    fn install_keeps_the_current_pool() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let config = SchedulerConfig::default()
+            .with_num_threads(3)
+            .with_max_memory_bytes(1 << 20);
+        let (num_threads, budgets) = pool.install(|| {
+            config.install(|| {
+                let budgets: Vec<_> = (0..16).into_par_iter().map(|_| memory_budget()).collect();
+                (rayon::current_num_threads(), budgets)
+            })
+        });
+        assert_eq!(num_threads, 2);
+        assert!(budgets.iter().all(|budget| *budget == Some(1 << 20)));
+        assert!(pool
+            .broadcast(|_| memory_budget())
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn profiles_are_distinct() {
+        for (i, a) in ProofProfile::ALL.iter().enumerate() {
+            for b in &ProofProfile::ALL[i + 1..] {
+                assert_ne!(a.grand_product(), b.grand_product(), "{a:?} and {b:?}");
+            }
+        }
+    }
+}
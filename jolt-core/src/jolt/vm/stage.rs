@@ -0,0 +1,154 @@
+//! Proof stages.
+//!
+//! A Jolt proof is a sequence of stages (bytecode, instruction lookups, read-write memory,
+//! R1CS) that each prove some relation over the committed witness. Every stage reduces its
+//! relation to claimed evaluations of committed polynomials ("opening claims"), which it
+//! records in a shared opening accumulator; all of the claims are then proven with a single
+//! batched opening proof at the end.
+//!
+//! Each stage is a [`JoltStage`], implemented by the stage's proof type. [`Jolt::prove`] and
+//! [`Jolt::verify`] only drive stages through this trait, in the order they appear in
+//! [`JoltProof`], so a new stage (e.g. a precompile gadget) is added by implementing the trait
+//! and inserting it into that sequence, rather than by editing the internals of the others.
+//!
+//! Stages don't depend on each other's claims, so each one runs on its own
+//! [fork](ProofTranscript::fork) of the transcript and its own opening accumulator. The prover
+//! may then prove them concurrently (see [`scheduler`](super::scheduler)); the forks are joined
+//! back in stage order before the batched opening proof.
+//!
+//! [`Jolt::prove`]: super::Jolt::prove
+//! [`Jolt::verify`]: super::Jolt::verify
+//! [`JoltProof`]: super::JoltProof
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use common::rv_trace::JoltDevice;
+
+use super::{JoltCommitments, JoltPolynomials};
+use crate::field::JoltField;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
+use crate::subprotocols::grand_product::GrandProductConfig;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::transcript::ProofTranscript;
+
+/// Prover state shared by every stage.
+pub struct StageProver<'a, F: JoltField, PCS: CommitmentScheme<Field = F>> {
+    pub generators: &'a PCS::Setup,
+    /// The full witness, since a stage may constrain columns owned by other stages.
+    pub polynomials: &'a JoltPolynomials<F>,
+    pub program_io: &'a JoltDevice,
+    /// Length of the trace before padding.
+    pub trace_length: usize,
+    pub opening_accumulator: &'a mut ProverOpeningAccumulator<F>,
+    pub transcript: &'a mut ProofTranscript,
+    /// The argument memory-checking stages prove their grand products with.
+    pub grand_product: GrandProductConfig,
+}
+
+/// Verifier state shared by every stage.
+pub struct StageVerifier<'a, F: JoltField, PCS: CommitmentScheme<Field = F>> {
+    pub generators: &'a PCS::Setup,
+    pub commitments: &'a JoltCommitments<PCS>,
+    pub program_io: &'a JoltDevice,
+    /// Length of the trace before padding.
+    pub trace_length: usize,
+    pub opening_accumulator: &'a mut VerifierOpeningAccumulator<F, PCS>,
+    pub transcript: &'a mut ProofTranscript,
+}
+
+/// A stage of a Jolt proof, implemented by the stage's proof type.
+pub trait JoltStage<F: JoltField, PCS: CommitmentScheme<Field = F>>:
+    CanonicalSerialize + CanonicalDeserialize + Sized
+{
+    /// Preprocessing shared by the prover and verifier of this stage.
+    type Preprocessing;
+
+    /// Label of this stage's transcript fork.
+    const NAME: &'static str;
+
+    /// Commits to the witness columns owned by this stage, writing them into `commitments`.
+    fn commit(
+        polynomials: &JoltPolynomials<F>,
+        commitments: &mut JoltCommitments<PCS>,
+        setup: &PCS::Setup,
+    );
+
+    /// Proves this stage, appending its opening claims to `prover.opening_accumulator`.
+    fn prove(preprocessing: &Self::Preprocessing, prover: &mut StageProver<F, PCS>) -> Self;
+
+    /// Verifies this stage, appending its opening claims to `verifier.opening_accumulator`.
+    /// The claims themselves are only checked by the final batched opening proof.
+    fn verify(
+        self,
+        preprocessing: &Self::Preprocessing,
+        verifier: &mut StageVerifier<F, PCS>,
+    ) -> Result<(), ProofVerifyError>;
+}
+
+/// Proves stage `S` on its own fork of `transcript`, returning the fork and the stage's
+/// openings alongside the proof.
+pub(crate) fn prove_forked<F, PCS, S>(
+    preprocessing: &S::Preprocessing,
+    generators: &PCS::Setup,
+    polynomials: &JoltPolynomials<F>,
+    program_io: &JoltDevice,
+    trace_length: usize,
+    transcript: &ProofTranscript,
+    grand_product: GrandProductConfig,
+) -> (S, ProverOpeningAccumulator<F>, ProofTranscript)
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    S: JoltStage<F, PCS>,
+{
+    let mut opening_accumulator = ProverOpeningAccumulator::new();
+    let mut transcript = transcript.fork(S::NAME.as_bytes());
+    let proof = S::prove(
+        preprocessing,
+        &mut StageProver {
+            generators,
+            polynomials,
+            program_io,
+            trace_length,
+            opening_accumulator: &mut opening_accumulator,
+            transcript: &mut transcript,
+            grand_product,
+        },
+    );
+    (proof, opening_accumulator, transcript)
+}
+
+/// Verifies stage `S` on its own fork of `transcript`, joining its openings into
+/// `opening_accumulator` and returning the fork.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_forked<F, PCS, S>(
+    proof: S,
+    preprocessing: &S::Preprocessing,
+    generators: &PCS::Setup,
+    commitments: &JoltCommitments<PCS>,
+    program_io: &JoltDevice,
+    trace_length: usize,
+    opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
+    transcript: &ProofTranscript,
+) -> Result<ProofTranscript, ProofVerifyError>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    S: JoltStage<F, PCS>,
+{
+    let mut stage_accumulator = opening_accumulator.fork();
+    let mut transcript = transcript.fork(S::NAME.as_bytes());
+    proof.verify(
+        preprocessing,
+        &mut StageVerifier {
+            generators,
+            commitments,
+            program_io,
+            trace_length,
+            opening_accumulator: &mut stage_accumulator,
+            transcript: &mut transcript,
+        },
+    )?;
+    opening_accumulator.join(stage_accumulator);
+    Ok(transcript)
+}
@@ -0,0 +1,77 @@
+//! Section-by-section proof serialization for memory-constrained verifiers.
+//!
+//! [`JoltProof::serialize_streaming`] writes the proof as a sequence of independently
+//! deserializable sections, ordered by the verifier stage that consumes them:
+//!
+//! ```text
+//! [ namespace | preprocessing_digest | trace_length | program_io | bytecode | instruction_lookups | read_write_memory | r1cs | opening_proof ]
+//! ```
+//!
+//! [`Jolt::verify_streaming`](super::Jolt::verify_streaming) reads one section at a time and
+//! drops it once the corresponding stage has been verified, so the full [`JoltProof`] is never
+//! materialized. Note that this differs from the field order used by `JoltProof`'s
+//! `CanonicalSerialize` implementation, so the two formats are not interchangeable.
+
+use std::io::{Read, Write};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+use super::JoltProof;
+use crate::field::JoltField;
+use crate::jolt::instruction::JoltInstructionSet;
+use crate::jolt::subtable::JoltSubtableSet;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::r1cs::inputs::ConstraintInput;
+use crate::utils::errors::ProofVerifyError;
+
+impl<const C: usize, const M: usize, I, F, PCS, InstructionSet, Subtables>
+    JoltProof<C, M, I, F, PCS, InstructionSet, Subtables>
+where
+    I: ConstraintInput,
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    InstructionSet: JoltInstructionSet,
+    Subtables: JoltSubtableSet<F>,
+{
+    /// Writes the proof in the sectioned format read by
+    /// [`Jolt::verify_streaming`](super::Jolt::verify_streaming).
+    pub fn serialize_streaming<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.namespace.serialize_compressed(&mut writer)?;
+        self.preprocessing_digest
+            .serialize_compressed(&mut writer)?;
+        self.trace_length.serialize_compressed(&mut writer)?;
+        self.program_io.serialize_compressed(&mut writer)?;
+        self.bytecode.serialize_compressed(&mut writer)?;
+        self.instruction_lookups.serialize_compressed(&mut writer)?;
+        self.read_write_memory.serialize_compressed(&mut writer)?;
+        self.r1cs.serialize_compressed(&mut writer)?;
+        self.opening_proof.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// Size in bytes of the largest section written by [`Self::serialize_streaming`], i.e. a
+    /// bound on the proof data held in memory at any point during streaming verification.
+    pub fn max_streaming_section_size(&self) -> usize {
+        [
+            self.namespace.compressed_size(),
+            self.preprocessing_digest.compressed_size(),
+            self.trace_length.compressed_size(),
+            self.program_io.compressed_size(),
+            self.bytecode.compressed_size(),
+            self.instruction_lookups.compressed_size(),
+            self.read_write_memory.compressed_size(),
+            self.r1cs.compressed_size(),
+            self.opening_proof.compressed_size(),
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+}
+
+/// Deserializes (and validates) the next section of a streamed proof.
+pub(crate) fn read_proof_section<T: CanonicalDeserialize, R: Read>(
+    reader: R,
+) -> Result<T, ProofVerifyError> {
+    T::deserialize_compressed(reader).map_err(ProofVerifyError::DeserializationError)
+}
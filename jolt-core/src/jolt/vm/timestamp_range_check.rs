@@ -4,14 +4,16 @@ use crate::lasso::memory_checking::{
 };
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::subprotocols::grand_product::{
-    BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductLayer,
-    BatchedGrandProductProof,
+    BatchedGrandProduct, BatchedGrandProductLayer, BatchedGrandProductProof,
+    ConfigurableGrandProduct, GrandProductConfig,
 };
 use crate::utils::thread::drop_in_background_thread;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use common::constants::MEMORY_OPS_PER_INSTRUCTION;
 use itertools::interleave;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 #[cfg(test)]
 use std::collections::HashSet;
 use std::iter::zip;
@@ -25,18 +27,25 @@ use crate::{
     poly::{
         dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
     },
-    utils::{errors::ProofVerifyError, mul_0_1_optimized, transcript::ProofTranscript},
+    utils::{errors::ProofVerifyError, json::Hex, mul_0_1_optimized, transcript::ProofTranscript},
 };
 
 use super::{JoltCommitments, JoltPolynomials, JoltStuff};
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct TimestampRangeCheckStuff<T: CanonicalSerialize + CanonicalDeserialize + Sync> {
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     read_cts_read_timestamp: [T; MEMORY_OPS_PER_INSTRUCTION],
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     read_cts_global_minus_read: [T; MEMORY_OPS_PER_INSTRUCTION],
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     final_cts_read_timestamp: [T; MEMORY_OPS_PER_INSTRUCTION],
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     final_cts_global_minus_read: [T; MEMORY_OPS_PER_INSTRUCTION],
 
+    #[serde_as(as = "Option<Hex>")]
     identity: VerifierComputedOpening<T>,
 }
 
@@ -239,7 +248,12 @@ where
     type Openings = TimestampRangeCheckOpenings<F>;
     type Commitments = TimestampRangeCheckCommitments<PCS>;
     type ExogenousOpenings = ReadTimestampOpenings<F>;
+    type Preprocessing = NoPreprocessing;
 
+    // (a, v, t)
+    type MemoryTuple = (F, F, F);
+
+    type ReadWriteGrandProduct = ConfigurableGrandProduct<F>;
     // Init/final grand products are batched together with read/write grand products
     type InitFinalGrandProduct = NoopGrandProduct;
 
@@ -250,6 +264,7 @@ where
         _: &JoltPolynomials<F>,
         _: &mut ProverOpeningAccumulator<F>,
         _: &mut ProofTranscript,
+        _: GrandProductConfig,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings> {
         unimplemented!("Use TimestampValidityProof::prove instead");
     }
@@ -567,12 +582,14 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
         _claims: &Vec<F>,
         _transcript: &mut ProofTranscript,
         _setup: Option<&PCS::Setup>,
-    ) -> (Vec<F>, Vec<F>) {
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         unimplemented!("init/final grand products are batched with read/write grand products")
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct TimestampValidityProof<F, PCS>
 where
     F: JoltField,
@@ -580,6 +597,7 @@ where
 {
     multiset_hashes: MultisetHashes<F>,
     openings: TimestampRangeCheckOpenings<F>,
+    #[serde_as(as = "[Hex; MEMORY_OPS_PER_INSTRUCTION]")]
     exogenous_openings: ReadTimestampOpenings<F>,
     batched_grand_product: BatchedGrandProductProof<PCS>,
 }
@@ -596,6 +614,7 @@ where
         jolt_polynomials: &'a JoltPolynomials<F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
+        grand_product: GrandProductConfig,
     ) -> Self {
         let (batched_grand_product, multiset_hashes, r_grand_product) =
             TimestampValidityProof::prove_grand_products(
@@ -603,6 +622,7 @@ where
                 jolt_polynomials,
                 transcript,
                 generators,
+                grand_product,
             );
 
         let mut openings = TimestampRangeCheckOpenings::default();
@@ -654,6 +674,7 @@ where
         jolt_polynomials: &JoltPolynomials<F>,
         transcript: &mut ProofTranscript,
         setup: &PCS::Setup,
+        grand_product: GrandProductConfig,
     ) -> (BatchedGrandProductProof<PCS>, MultisetHashes<F>, Vec<F>) {
         // Fiat-Shamir randomness for multiset hashes
         let gamma: F = transcript.challenge_scalar();
@@ -670,10 +691,13 @@ where
         );
 
         let mut batched_circuit =
-            <BatchedDenseGrandProduct<F> as BatchedGrandProduct<F, PCS>>::construct(leaves);
+            <ConfigurableGrandProduct<F> as BatchedGrandProduct<F, PCS>>::construct_with_config(
+                leaves,
+                grand_product,
+            );
 
         let hashes: Vec<F> =
-            <BatchedDenseGrandProduct<F> as BatchedGrandProduct<F, PCS>>::claims(&batched_circuit);
+            <ConfigurableGrandProduct<F> as BatchedGrandProduct<F, PCS>>::claims(&batched_circuit);
         let (read_write_hashes, init_final_hashes) =
             hashes.split_at(4 * MEMORY_OPS_PER_INSTRUCTION);
         let multiset_hashes = TimestampValidityProof::<F, PCS>::uninterleave_hashes(
@@ -722,12 +746,12 @@ where
             );
         let concatenated_hashes = [read_write_hashes, init_final_hashes].concat();
         let (grand_product_claims, r_grand_product) =
-            BatchedDenseGrandProduct::verify_grand_product(
+            ConfigurableGrandProduct::verify_grand_product(
                 &self.batched_grand_product,
                 &concatenated_hashes,
                 transcript,
                 Some(generators),
-            );
+            )?;
 
         opening_accumulator.append(
             &commitments
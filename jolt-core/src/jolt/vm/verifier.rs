@@ -0,0 +1,108 @@
+//! Verification of many proofs against the same preprocessing.
+//!
+//! [`Jolt::verify`] takes its preprocessing by value, so verifying a stream of proofs clones the
+//! whole preprocessing (including the PCS verifier key) for every proof, and regenerates the R1CS
+//! constraints and uniform Spartan key from scratch each time. A [`Verifier`] borrows its
+//! preprocessing for every proof and caches the R1CS preprocessing for each padded trace length,
+//! so that per-proof work is only the verification itself.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use common::constants::RAM_START_ADDRESS;
+use common::rv_trace::JoltDevice;
+
+use super::{Jolt, JoltCommitments, JoltPreprocessing, JoltProof};
+use crate::field::JoltField;
+use crate::poly::commitment::commitment_scheme::CommitmentScheme;
+use crate::r1cs::constraints::R1CSConstraints;
+use crate::r1cs::spartan::R1CSPreprocessing;
+use crate::utils::errors::JoltError;
+
+/// Verifies proofs for the program `preprocessing` was generated for, reusing the work that
+/// doesn't depend on the proof. `verify` takes `&self`, so a `Verifier` can be shared between
+/// threads.
+pub struct Verifier<const C: usize, const M: usize, F, PCS, VM>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    VM: Jolt<F, PCS, C, M>,
+{
+    preprocessing: JoltPreprocessing<C, F, PCS>,
+    /// Keyed by padded trace length and the start of memory in the witness, which are all the
+    /// R1CS preprocessing depends on.
+    r1cs_preprocessing: Mutex<
+        HashMap<
+            (usize, u64),
+            Arc<R1CSPreprocessing<C, <VM::Constraints as R1CSConstraints<C, F>>::Inputs, F>>,
+        >,
+    >,
+    _vm: PhantomData<fn() -> VM>,
+}
+
+impl<const C: usize, const M: usize, F, PCS, VM> Verifier<C, M, F, PCS, VM>
+where
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+    VM: Jolt<F, PCS, C, M>,
+{
+    pub fn new(preprocessing: JoltPreprocessing<C, F, PCS>) -> Self {
+        Self {
+            preprocessing,
+            r1cs_preprocessing: Mutex::new(HashMap::new()),
+            _vm: PhantomData,
+        }
+    }
+
+    pub fn preprocessing(&self) -> &JoltPreprocessing<C, F, PCS> {
+        &self.preprocessing
+    }
+
+    /// Equivalent to `VM::verify(preprocessing.clone(), proof, commitments, None)`.
+    #[tracing::instrument(skip_all, name = "Verifier::verify")]
+    pub fn verify(
+        &self,
+        proof: JoltProof<
+            C,
+            M,
+            <VM::Constraints as R1CSConstraints<C, F>>::Inputs,
+            F,
+            PCS,
+            VM::InstructionSet,
+            VM::Subtables,
+        >,
+        commitments: JoltCommitments<PCS>,
+    ) -> Result<(), JoltError> {
+        VM::verify_with(
+            &self.preprocessing,
+            proof,
+            commitments,
+            None,
+            |program_io, trace_length| self.r1cs_preprocessing(program_io, trace_length),
+        )
+    }
+
+    fn r1cs_preprocessing(
+        &self,
+        program_io: &JoltDevice,
+        trace_length: usize,
+    ) -> Arc<R1CSPreprocessing<C, <VM::Constraints as R1CSConstraints<C, F>>::Inputs, F>> {
+        let key = (
+            trace_length.next_power_of_two(),
+            RAM_START_ADDRESS - program_io.memory_layout.ram_witness_offset,
+        );
+        if let Some(r1cs_preprocessing) = self.r1cs_preprocessing.lock().unwrap().get(&key) {
+            return r1cs_preprocessing.clone();
+        }
+        // Generate without holding the lock, so that proofs of other trace lengths aren't blocked.
+        // Concurrent proofs of a new trace length may each generate it, but only one is kept.
+        let r1cs_preprocessing = Arc::new(VM::r1cs_preprocessing(program_io, trace_length));
+        self.r1cs_preprocessing
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert(r1cs_preprocessing)
+            .clone()
+    }
+}
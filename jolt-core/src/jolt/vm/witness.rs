@@ -0,0 +1,199 @@
+//! Parallel conversion of the execution trace into witness columns.
+//!
+//! The trace is split into contiguous chunks, one or a few per thread, and each chunk is
+//! converted independently. A stitch step then assembles the chunks: [`par_columns`]
+//! concatenates each chunk's columns, and [`memory_counters`], whose read counters depend on
+//! every earlier access, offsets each chunk's counters by the accesses made in the chunks before
+//! it.
+
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use super::scheduler::budgeted_batch_size;
+
+/// Chunks per thread, so that uneven chunks (e.g. steps with and without lookups) balance out.
+const CHUNKS_PER_THREAD: usize = 4;
+/// Below this many steps per chunk, splitting costs more than it saves.
+const MIN_CHUNK_SIZE: usize = 1 << 10;
+
+/// The contiguous chunks `0..num_steps` is split into.
+pub fn trace_chunks(num_steps: usize) -> Vec<Range<usize>> {
+    chunks_of(num_steps, default_chunk_size(num_steps))
+}
+
+fn default_chunk_size(num_steps: usize) -> usize {
+    let num_chunks = rayon::current_num_threads() * CHUNKS_PER_THREAD;
+    num_steps.div_ceil(num_chunks).max(MIN_CHUNK_SIZE)
+}
+
+fn chunks_of(num_steps: usize, chunk_size: usize) -> Vec<Range<usize>> {
+    (0..num_steps)
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(num_steps))
+        .collect()
+}
+
+/// Builds `num_columns` columns of one entry per step. `fill_chunk` is called in parallel with
+/// each chunk of steps and empty columns, and must push exactly one entry per step of the chunk
+/// to each column, in order.
+pub fn par_columns<T, Fill>(num_steps: usize, num_columns: usize, fill_chunk: Fill) -> Vec<Vec<T>>
+where
+    T: Clone + Send + Sync,
+    Fill: Fn(Range<usize>, &mut [Vec<T>]) + Sync,
+{
+    let chunks: Vec<Vec<Vec<T>>> = trace_chunks(num_steps)
+        .into_par_iter()
+        .map(|steps| {
+            let mut columns = vec![Vec::with_capacity(steps.len()); num_columns];
+            fill_chunk(steps.clone(), &mut columns);
+            debug_assert!(columns.iter().all(|column| column.len() == steps.len()));
+            columns
+        })
+        .collect();
+
+    (0..num_columns)
+        .into_par_iter()
+        .map(|i| {
+            let mut column = Vec::with_capacity(num_steps);
+            for chunk in chunks.iter() {
+                column.extend_from_slice(&chunk[i]);
+            }
+            column
+        })
+        .collect()
+}
+
+/// The read and final counters of memory checking for a sequence of accesses to a memory of
+/// `memory_size` cells, where step `j` accesses cell `address(j)`, if any. The read counter of a
+/// step is the number of earlier accesses to its cell (0 for steps without an access), and the
+/// final counter of a cell is the number of accesses to it.
+///
+/// Each chunk counts its accesses from zero, with a dense counter per cell, so the number of
+/// chunks is capped to keep those counters within the size of the trace, and within a quarter
+/// of the memory budget if there is one. Memories as large as the trace are counted
+/// sequentially.
+pub fn memory_counters<Address>(
+    num_steps: usize,
+    memory_size: usize,
+    address: Address,
+) -> (Vec<usize>, Vec<usize>)
+where
+    Address: Fn(usize) -> Option<usize> + Sync,
+{
+    let max_chunks = budgeted_batch_size(
+        num_steps / memory_size.max(1),
+        memory_size * std::mem::size_of::<usize>(),
+    );
+    let chunk_size = default_chunk_size(num_steps).max(num_steps.div_ceil(max_chunks));
+    let chunks = chunks_of(num_steps, chunk_size);
+
+    let mut local: Vec<(Vec<usize>, Vec<usize>)> = chunks
+        .par_iter()
+        .map(|steps| {
+            let mut read_cts = vec![0; steps.len()];
+            let mut counts = vec![0; memory_size];
+            for (j, read_ct) in steps.clone().zip(read_cts.iter_mut()) {
+                if let Some(a) = address(j) {
+                    *read_ct = counts[a];
+                    counts[a] += 1;
+                }
+            }
+            (read_cts, counts)
+        })
+        .collect();
+
+    // Stitch: replace each chunk's counts with the accesses made before the chunk, which its
+    // read counters are offset by.
+    let mut final_cts = vec![0; memory_size];
+    for (_, counts) in local.iter_mut() {
+        for (offset, total) in counts.iter_mut().zip(final_cts.iter_mut()) {
+            let count = *offset;
+            *offset = *total;
+            *total += count;
+        }
+    }
+
+    let read_cts = local
+        .into_par_iter()
+        .zip(chunks.into_par_iter())
+        .flat_map_iter(|((mut read_cts, offsets), steps)| {
+            for (j, read_ct) in steps.zip(read_cts.iter_mut()) {
+                if let Some(a) = address(j) {
+                    *read_ct += offsets[a];
+                }
+            }
+            read_cts
+        })
+        .collect();
+
+    (read_cts, final_cts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequential_counters(
+        addresses: &[Option<usize>],
+        memory_size: usize,
+    ) -> (Vec<usize>, Vec<usize>) {
+        let mut read_cts = vec![0; addresses.len()];
+        let mut final_cts = vec![0; memory_size];
+        for (read_ct, address) in read_cts.iter_mut().zip(addresses) {
+            if let Some(a) = address {
+                *read_ct = final_cts[*a];
+                final_cts[*a] += 1;
+            }
+        }
+        (read_cts, final_cts)
+    }
+
+    #[test]
+    fn chunks_cover_the_trace() {
+        for num_steps in [0, 1, MIN_CHUNK_SIZE + 1, 1 << 20, (1 << 20) + 3] {
+            let chunks = trace_chunks(num_steps);
+            let mut next = 0;
+            for chunk in chunks {
+                assert_eq!(chunk.start, next);
+                assert!(!chunk.is_empty());
+                next = chunk.end;
+            }
+            assert_eq!(next, num_steps);
+        }
+    }
+
+    #[test]
+    fn counters_match_sequential() {
+        let num_steps = 1 << 16;
+        for memory_size in [1, 7, 1 << 10, 1 << 16, 1 << 18] {
+            let addresses: Vec<Option<usize>> = (0..num_steps)
+                .map(|j: usize| {
+                    let hash = j.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 7;
+                    (hash % 5 != 0).then_some(hash % memory_size)
+                })
+                .collect();
+            assert_eq!(
+                memory_counters(num_steps, memory_size, |j| addresses[j]),
+                sequential_counters(&addresses, memory_size),
+                "memory_size = {memory_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn columns_are_stitched_in_order() {
+        let num_steps = (1 << 16) + 5;
+        let columns = par_columns(num_steps, 3, |steps, columns| {
+            for j in steps {
+                for (i, column) in columns.iter_mut().enumerate() {
+                    column.push(j * 3 + i);
+                }
+            }
+        });
+        for (i, column) in columns.iter().enumerate() {
+            assert_eq!(column.len(), num_steps);
+            assert!(column.iter().enumerate().all(|(j, v)| *v == j * 3 + i));
+        }
+    }
+}
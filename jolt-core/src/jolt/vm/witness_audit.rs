@@ -0,0 +1,364 @@
+//! Reports how many bits of each committed witness column are actually used.
+//!
+//! Every column is committed as a vector of field elements, but most of them only ever hold
+//! small values: flags, chunks, timestamps, and so on. [`WitnessAudit`] records the range of
+//! values observed in each column of a real execution, so that columns can be moved to compact
+//! `u8`/`u16`/`u32`/`u64` representations based on data rather than guesswork. Produced by
+//! [`Jolt::audit_witness`](super::Jolt::audit_witness).
+
+use std::fmt;
+
+use common::constants::{MEMORY_OPS_PER_INSTRUCTION, RAM_OPS_PER_INSTRUCTION};
+use common::rv_trace::CircuitFlags;
+use rayon::prelude::*;
+use strum::IntoEnumIterator;
+
+use super::JoltPolynomials;
+use crate::field::JoltField;
+use crate::lasso::memory_checking::StructuredPolynomialData;
+use crate::poly::dense_mlpoly::DensePolynomial;
+
+/// Names of the memory operations of a step, in the order of `JoltTraceStep::memory_ops`.
+const MEMORY_OPS: [&str; MEMORY_OPS_PER_INSTRUCTION] =
+    ["rs1", "rs2", "rd", "ram0", "ram1", "ram2", "ram3"];
+const RAM_OPS: [&str; RAM_OPS_PER_INSTRUCTION] = ["ram0", "ram1", "ram2", "ram3"];
+/// Names of the bytecode row fields, in the order of `BytecodeStuff::v_read_write`.
+const BYTECODE_FIELDS: [&str; 6] = ["elf_address", "bitflags", "rs1", "rs2", "rd", "imm"];
+
+/// The smallest integer type that holds every value of a column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompactType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    /// Some value doesn't fit in 64 bits, so the column needs full field elements.
+    Field,
+}
+
+impl fmt::Display for CompactType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::Field => "field",
+        };
+        f.pad(name)
+    }
+}
+
+/// The values observed in one committed column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnAudit {
+    /// `<component>.<column>`, e.g. `read_write_memory.t_read[rs1]`.
+    pub name: String,
+    /// Number of entries, including padding.
+    pub len: usize,
+    /// Largest non-negative value, if every value fits in 64 bits.
+    pub max: Option<u64>,
+    /// Largest magnitude of a negative value (i.e. of `p - x` for `x > p / 2`), if there are any
+    /// and every value fits in 64 bits.
+    pub min_negative: Option<u64>,
+}
+
+impl ColumnAudit {
+    fn new<F: JoltField>(name: String, column: &DensePolynomial<F>) -> Self {
+        // (max, min_negative), or None if some value doesn't fit in 64 bits
+        let range = column
+            .evals_ref()
+            .par_iter()
+            .map(|value| match value.to_u64() {
+                Some(value) => Some((value, None)),
+                None => (-*value).to_u64().map(|magnitude| (0, Some(magnitude))),
+            })
+            .reduce(
+                || Some((0, None)),
+                |a, b| {
+                    let ((max_a, neg_a), (max_b, neg_b)) = (a?, b?);
+                    Some((max_a.max(max_b), neg_a.max(neg_b)))
+                },
+            );
+        Self {
+            name,
+            len: column.len(),
+            max: range.map(|(max, _)| max),
+            min_negative: range.and_then(|(_, min_negative)| min_negative),
+        }
+    }
+
+    /// Number of bits needed to represent every value of the column (including a sign bit if
+    /// any value is negative), or `None` if some value doesn't fit in 64 bits.
+    pub fn max_bits(&self) -> Option<u32> {
+        let bits = |value: u64| u64::BITS - value.leading_zeros();
+        let max = self.max?;
+        match self.min_negative {
+            // -2^(k-1) fits in k bits, so only magnitudes above that need another one
+            Some(magnitude) => Some(bits(max).max(bits(magnitude - 1)) + 1),
+            None => Some(bits(max)),
+        }
+    }
+
+    pub fn compact_type(&self) -> CompactType {
+        let signed = self.min_negative.is_some();
+        match (self.max_bits(), signed) {
+            (None, _) | (Some(65..), _) => CompactType::Field,
+            (Some(0..=8), false) => CompactType::U8,
+            (Some(9..=16), false) => CompactType::U16,
+            (Some(17..=32), false) => CompactType::U32,
+            (Some(_), false) => CompactType::U64,
+            (Some(0..=8), true) => CompactType::I8,
+            (Some(9..=16), true) => CompactType::I16,
+            (Some(17..=32), true) => CompactType::I32,
+            (Some(_), true) => CompactType::I64,
+        }
+    }
+}
+
+/// The value ranges of every committed witness column of one execution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WitnessAudit {
+    /// Length of the trace before padding.
+    pub trace_length: usize,
+    pub columns: Vec<ColumnAudit>,
+}
+
+impl WitnessAudit {
+    /// Audits the columns of `polynomials`. `instruction_names` are the names of the VM's
+    /// instructions, used to label their flag columns.
+    pub(crate) fn new<F: JoltField>(
+        polynomials: &JoltPolynomials<F>,
+        instruction_names: &[String],
+        trace_length: usize,
+    ) -> Self {
+        let columns = named_columns(polynomials, instruction_names)
+            .into_par_iter()
+            .map(|(name, column)| ColumnAudit::new(name, column))
+            .collect();
+        Self {
+            trace_length,
+            columns,
+        }
+    }
+
+    /// Number of columns (and their total number of entries) that fit in each compact type.
+    pub fn summary(&self) -> Vec<(CompactType, usize, usize)> {
+        let mut summary: Vec<(CompactType, usize, usize)> = vec![];
+        for column in self.columns.iter() {
+            let compact_type = column.compact_type();
+            match summary.iter_mut().find(|(t, _, _)| *t == compact_type) {
+                Some((_, count, entries)) => {
+                    *count += 1;
+                    *entries += column.len;
+                }
+                None => summary.push((compact_type, 1, column.len)),
+            }
+        }
+        summary.sort();
+        summary
+    }
+}
+
+impl fmt::Display for WitnessAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Trace length: {}", self.trace_length)?;
+        let name_width = self
+            .columns
+            .iter()
+            .map(|column| column.name.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "{:name_width$}  {:>10}  {:>8}  {:>20}  {:>20}  type",
+            "column", "len", "max bits", "max", "most negative"
+        )?;
+        for column in self.columns.iter() {
+            let max_bits = column
+                .max_bits()
+                .map_or("-".to_string(), |bits| bits.to_string());
+            let max = column.max.map_or("-".to_string(), |max| max.to_string());
+            let min = column
+                .min_negative
+                .map_or("-".to_string(), |magnitude| format!("-{magnitude}"));
+            writeln!(
+                f,
+                "{:name_width$}  {:>10}  {:>8}  {:>20}  {:>20}  {}",
+                column.name,
+                column.len,
+                max_bits,
+                max,
+                min,
+                column.compact_type()
+            )?;
+        }
+        writeln!(f)?;
+        for (compact_type, count, entries) in self.summary() {
+            writeln!(f, "{compact_type:>5}: {count} columns, {entries} entries")?;
+        }
+        Ok(())
+    }
+}
+
+/// Pairs each committed column with its name, in the order columns are committed.
+fn named_columns<'a, F: JoltField>(
+    polynomials: &'a JoltPolynomials<F>,
+    instruction_names: &[String],
+) -> Vec<(String, &'a DensePolynomial<F>)> {
+    let mut columns = vec![];
+    let indexed = |name: &str, len: usize| -> Vec<String> {
+        (0..len).map(|i| format!("{name}[{i}]")).collect()
+    };
+    let labeled = |name: &str, labels: &[&str]| -> Vec<String> {
+        labels
+            .iter()
+            .map(|label| format!("{name}[{label}]"))
+            .collect()
+    };
+
+    let bytecode = &polynomials.bytecode;
+    let names = [
+        vec!["a_read_write".to_string()],
+        labeled("v_read_write", &BYTECODE_FIELDS),
+        vec!["t_read".to_string(), "t_final".to_string()],
+    ];
+    name_component(&mut columns, "bytecode", names, bytecode);
+
+    let memory = &polynomials.read_write_memory;
+    let names = [
+        vec!["a_ram".to_string()],
+        labeled("v_read", &MEMORY_OPS),
+        vec!["v_write_rd".to_string()],
+        labeled("v_write_ram", &RAM_OPS),
+        labeled("t_read", &MEMORY_OPS),
+        labeled("t_write_ram", &RAM_OPS),
+        vec!["v_final".to_string(), "t_final".to_string()],
+    ];
+    name_component(&mut columns, "read_write_memory", names, memory);
+
+    let lookups = &polynomials.instruction_lookups;
+    let num_memories = lookups.final_cts.len();
+    let names = [
+        indexed("dim", lookups.dim.len()),
+        indexed("read_cts", num_memories),
+        indexed("E_polys", lookups.E_polys.len()),
+        labeled(
+            "instruction_flags",
+            &instruction_names
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        ),
+        vec!["lookup_outputs".to_string()],
+        indexed("final_cts", num_memories),
+    ];
+    name_component(&mut columns, "instruction_lookups", names, lookups);
+
+    let names = [
+        labeled("read_cts_read_timestamp", &MEMORY_OPS),
+        labeled("read_cts_global_minus_read", &MEMORY_OPS),
+        labeled("final_cts_read_timestamp", &MEMORY_OPS),
+        labeled("final_cts_global_minus_read", &MEMORY_OPS),
+    ];
+    let range_check = &polynomials.timestamp_range_check;
+    name_component(&mut columns, "timestamp_range_check", names, range_check);
+
+    let r1cs = &polynomials.r1cs;
+    let flags: Vec<String> = CircuitFlags::iter()
+        .map(|flag| format!("{flag:?}"))
+        .collect();
+    let flags: Vec<&str> = flags.iter().map(String::as_str).collect();
+    let names = [
+        indexed("chunks_x", r1cs.chunks_x.len()),
+        indexed("chunks_y", r1cs.chunks_y.len()),
+        labeled("circuit_flags", &flags),
+        vec!["remaining_cycles".to_string()],
+        [
+            "left_lookup_operand",
+            "right_lookup_operand",
+            "imm_signed",
+            "product",
+        ]
+        .map(String::from)
+        .to_vec(),
+        indexed("relevant_y_chunks", r1cs.aux.relevant_y_chunks.len()),
+        [
+            "write_lookup_output_to_rd",
+            "write_pc_to_rd",
+            "next_pc_jump",
+            "should_branch",
+            "next_pc",
+        ]
+        .map(String::from)
+        .to_vec(),
+    ];
+    name_component(&mut columns, "r1cs", names, r1cs);
+
+    columns
+}
+
+/// Appends the read/write and init/final columns of `component`, named `names` (in the same
+/// order), to `columns`.
+fn name_component<'a, F: JoltField, const N: usize>(
+    columns: &mut Vec<(String, &'a DensePolynomial<F>)>,
+    component: &str,
+    names: [Vec<String>; N],
+    polynomials: &'a impl StructuredPolynomialData<DensePolynomial<F>>,
+) {
+    let polynomials: Vec<_> = polynomials
+        .read_write_values()
+        .into_iter()
+        .chain(polynomials.init_final_values())
+        .collect();
+    let names: Vec<String> = names.into_iter().flatten().collect();
+    assert_eq!(
+        names.len(),
+        polynomials.len(),
+        "column names don't match the {component} columns"
+    );
+    columns.extend(
+        names
+            .into_iter()
+            .map(|name| format!("{component}.{name}"))
+            .zip(polynomials),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(max: Option<u64>, min_negative: Option<u64>) -> ColumnAudit {
+        ColumnAudit {
+            name: String::new(),
+            len: 1,
+            max,
+            min_negative,
+        }
+    }
+
+    #[test]
+    fn compact_types() {
+        assert_eq!(column(Some(0), None).compact_type(), CompactType::U8);
+        assert_eq!(column(Some(255), None).compact_type(), CompactType::U8);
+        assert_eq!(column(Some(256), None).compact_type(), CompactType::U16);
+        assert_eq!(column(Some(1 << 32), None).compact_type(), CompactType::U64);
+        assert_eq!(column(Some(127), Some(128)).compact_type(), CompactType::I8);
+        assert_eq!(column(Some(128), Some(1)).compact_type(), CompactType::I16);
+        assert_eq!(column(Some(0), Some(129)).compact_type(), CompactType::I16);
+        assert_eq!(
+            column(Some(u64::MAX), Some(1)).compact_type(),
+            CompactType::Field
+        );
+        assert_eq!(column(None, None).compact_type(), CompactType::Field);
+    }
+}
@@ -6,12 +6,13 @@ use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::opening_proof::{ProverOpeningAccumulator, VerifierOpeningAccumulator};
 use crate::utils::errors::ProofVerifyError;
+use crate::utils::json::Hex;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::ProofTranscript;
 use crate::{
     poly::commitment::commitment_scheme::CommitmentScheme,
     subprotocols::grand_product::{
-        BatchedDenseGrandProduct, BatchedGrandProduct, BatchedGrandProductProof,
+        BatchedGrandProduct, BatchedGrandProductProof, GrandProductConfig,
     },
 };
 
@@ -19,17 +20,25 @@ use crate::field::JoltField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::interleave;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::iter::zip;
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct MultisetHashes<F: JoltField> {
     /// Multiset hash of "read" tuples
+    #[serde_as(as = "Vec<Hex>")]
     pub read_hashes: Vec<F>,
     /// Multiset hash of "write" tuples
+    #[serde_as(as = "Vec<Hex>")]
     pub write_hashes: Vec<F>,
     /// Multiset hash of "init" tuples
+    #[serde_as(as = "Vec<Hex>")]
     pub init_hashes: Vec<F>,
     /// Multiset hash of "final" tuples
+    #[serde_as(as = "Vec<Hex>")]
     pub final_hashes: Vec<F>,
 }
 
@@ -42,7 +51,11 @@ impl<F: JoltField> MultisetHashes<F> {
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "Openings: Serialize, OtherOpenings: Serialize",
+    deserialize = "Openings: Deserialize<'de>, OtherOpenings: Deserialize<'de>"
+))]
 pub struct MemoryCheckingProof<F, PCS, Openings, OtherOpenings>
 where
     F: JoltField,
@@ -67,6 +80,9 @@ where
 /// field has a corresponding opening but no corrresponding polynomial or commitment ––
 /// the prover doesn't need to compute a witness polynomial or commitment because
 /// the verifier can compute the opening on its own.
+/// Columns that are checked as part of a sumcheck rather than through memory-checking
+/// tuples can be made verifier-computed the same way, using
+/// [`VirtualPolynomial`](crate::poly::virtual_poly::VirtualPolynomial).
 pub type VerifierComputedOpening<T> = Option<T>;
 
 /// This trait is used to capture the relationship between polynomials, commitments, and
@@ -126,7 +142,7 @@ pub trait ExogenousOpenings<F: JoltField>:
     ) -> Vec<&T>;
 }
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
 pub struct NoExogenousOpenings;
 impl<F: JoltField> ExogenousOpenings<F> for NoExogenousOpenings {
     fn openings(&self) -> Vec<&F> {
@@ -204,23 +220,26 @@ where
     PCS: CommitmentScheme<Field = F>,
     Self: Sync,
 {
-    type ReadWriteGrandProduct: BatchedGrandProduct<F, PCS> + Send + 'static =
-        BatchedDenseGrandProduct<F>;
-    type InitFinalGrandProduct: BatchedGrandProduct<F, PCS> + Send + 'static =
-        BatchedDenseGrandProduct<F>;
+    /// Usually `ConfigurableGrandProduct`, so that the prover can choose the argument.
+    type ReadWriteGrandProduct: BatchedGrandProduct<F, PCS> + Send + 'static;
+    type InitFinalGrandProduct: BatchedGrandProduct<F, PCS> + Send + 'static;
 
     type Polynomials: StructuredPolynomialData<DensePolynomial<F>>;
     type Openings: StructuredPolynomialData<F> + Sync + Initializable<F, Self::Preprocessing>;
     type Commitments: StructuredPolynomialData<PCS::Commitment>;
-    type ExogenousOpenings: ExogenousOpenings<F> + Sync = NoExogenousOpenings;
+    /// [`NoExogenousOpenings`] if the memory tuples only depend on `Self::Openings`.
+    type ExogenousOpenings: ExogenousOpenings<F> + Sync;
 
-    type Preprocessing = NoPreprocessing;
+    /// [`NoPreprocessing`] if there is none.
+    type Preprocessing;
 
-    /// The data associated with each memory slot. A triple (a, v, t) by default.
-    type MemoryTuple = (F, F, F);
+    /// The data associated with each memory slot, usually a triple (a, v, t).
+    type MemoryTuple;
 
     #[tracing::instrument(skip_all, name = "MemoryCheckingProver::prove_memory_checking")]
-    /// Generates a memory checking proof for the given committed polynomials.
+    /// Generates a memory checking proof for the given committed polynomials, proving its grand
+    /// products with the argument chosen by `grand_product` where the grand product supports a
+    /// choice.
     fn prove_memory_checking(
         pcs_setup: &PCS::Setup,
         preprocessing: &Self::Preprocessing,
@@ -228,6 +247,7 @@ where
         jolt_polynomials: &JoltPolynomials<F>,
         opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
+        grand_product: GrandProductConfig,
     ) -> MemoryCheckingProof<F, PCS, Self::Openings, Self::ExogenousOpenings> {
         let (
             read_write_grand_product,
@@ -241,6 +261,7 @@ where
             jolt_polynomials,
             transcript,
             pcs_setup,
+            grand_product,
         );
 
         let (openings, exogenous_openings) = Self::compute_openings(
@@ -270,6 +291,7 @@ where
         jolt_polynomials: &JoltPolynomials<F>,
         transcript: &mut ProofTranscript,
         pcs_setup: &PCS::Setup,
+        grand_product: GrandProductConfig,
     ) -> (
         BatchedGrandProductProof<PCS>,
         BatchedGrandProductProof<PCS>,
@@ -285,10 +307,18 @@ where
 
         let (read_write_leaves, init_final_leaves) =
             Self::compute_leaves(preprocessing, polynomials, jolt_polynomials, &gamma, &tau);
-        let (mut read_write_circuit, read_write_hashes) =
-            Self::read_write_grand_product(preprocessing, polynomials, read_write_leaves);
-        let (mut init_final_circuit, init_final_hashes) =
-            Self::init_final_grand_product(preprocessing, polynomials, init_final_leaves);
+        let (mut read_write_circuit, read_write_hashes) = Self::read_write_grand_product(
+            preprocessing,
+            polynomials,
+            read_write_leaves,
+            grand_product,
+        );
+        let (mut init_final_circuit, init_final_hashes) = Self::init_final_grand_product(
+            preprocessing,
+            polynomials,
+            init_final_leaves,
+            grand_product,
+        );
 
         let multiset_hashes =
             Self::uninterleave_hashes(preprocessing, read_write_hashes, init_final_hashes);
@@ -382,8 +412,10 @@ where
         _preprocessing: &Self::Preprocessing,
         _polynomials: &Self::Polynomials,
         read_write_leaves: <Self::ReadWriteGrandProduct as BatchedGrandProduct<F, PCS>>::Leaves,
+        grand_product: GrandProductConfig,
     ) -> (Self::ReadWriteGrandProduct, Vec<F>) {
-        let batched_circuit = Self::ReadWriteGrandProduct::construct(read_write_leaves);
+        let batched_circuit =
+            Self::ReadWriteGrandProduct::construct_with_config(read_write_leaves, grand_product);
         let claims = batched_circuit.claims();
         (batched_circuit, claims)
     }
@@ -395,8 +427,10 @@ where
         _preprocessing: &Self::Preprocessing,
         _polynomials: &Self::Polynomials,
         init_final_leaves: <Self::InitFinalGrandProduct as BatchedGrandProduct<F, PCS>>::Leaves,
+        grand_product: GrandProductConfig,
     ) -> (Self::InitFinalGrandProduct, Vec<F>) {
-        let batched_circuit = Self::InitFinalGrandProduct::construct(init_final_leaves);
+        let batched_circuit =
+            Self::InitFinalGrandProduct::construct_with_config(init_final_leaves, grand_product);
         let claims = batched_circuit.claims();
         (batched_circuit, claims)
     }
@@ -525,13 +559,13 @@ where
             &read_write_hashes,
             transcript,
             Some(pcs_setup),
-        );
+        )?;
         let (claims_init_final, r_init_final) = Self::InitFinalGrandProduct::verify_grand_product(
             &proof.init_final_grand_product,
             &init_final_hashes,
             transcript,
             Some(pcs_setup),
-        );
+        )?;
 
         let read_write_commits: Vec<_> = [
             commitments.read_write_values(),
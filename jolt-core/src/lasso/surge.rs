@@ -19,7 +19,10 @@ use crate::{
         eq_poly::EqPolynomial,
         identity_poly::IdentityPolynomial,
     },
-    subprotocols::sumcheck::SumcheckInstanceProof,
+    subprotocols::{
+        grand_product::{ConfigurableGrandProduct, GrandProductConfig},
+        sumcheck::SumcheckInstanceProof,
+    },
     utils::{errors::ProofVerifyError, math::Math, mul_0_1_optimized, transcript::ProofTranscript},
 };
 
@@ -101,11 +104,18 @@ where
     PCS: CommitmentScheme<Field = F>,
     Instruction: JoltInstruction + Default + Sync,
 {
+    type ReadWriteGrandProduct = ConfigurableGrandProduct<F>;
+    type InitFinalGrandProduct = ConfigurableGrandProduct<F>;
+
     type Polynomials = SurgePolynomials<F>;
     type Openings = SurgeOpenings<F>;
     type Commitments = SurgeCommitments<PCS>;
+    type ExogenousOpenings = NoExogenousOpenings;
     type Preprocessing = SurgePreprocessing<F, Instruction, C, M>;
 
+    // (a, v, t)
+    type MemoryTuple = (F, F, F);
+
     fn fingerprint(inputs: &(F, F, F), gamma: &F, tau: &F) -> F {
         let (a, v, t) = *inputs;
         t * gamma.square() + v * *gamma + a - *tau
@@ -456,6 +466,7 @@ where
             &JoltPolynomials::default(), // Hack: required by the memory-checking trait, but unused in Surge
             &mut opening_accumulator,
             &mut transcript,
+            GrandProductConfig::default(),
         );
 
         let proof = SurgeProof {
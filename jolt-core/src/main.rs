@@ -1,7 +1,7 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use jolt_core::benches::{
-    bench::{benchmarks, BenchType, PCSType},
+    bench::{benchmarks, profile, BenchType, PCSType},
     sum_timer::CumulativeTimingLayer,
 };
 
@@ -40,6 +40,10 @@ struct TraceArgs {
     /// Number of cycles to run the benchmark for
     #[clap(short, long)]
     num_cycles: Option<usize>,
+
+    /// Print the guest's cycle profile as folded stacks (flamegraph input) instead of proving
+    #[clap(long)]
+    profile: bool,
 }
 
 #[derive(Args, Debug)]
@@ -82,6 +86,11 @@ fn main() {
 }
 
 fn trace(args: TraceArgs) {
+    if args.profile {
+        print!("{}", profile(args.name).folded());
+        return;
+    }
+
     let mut layers = Vec::new();
 
     let mut guards: Vec<Box<dyn Any>> = vec![];
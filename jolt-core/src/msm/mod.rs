@@ -51,6 +51,116 @@ pub trait VariableBaseMSM: ScalarMul {
             })
             .ok_or_else(|| bases.len().min(scalars.len()))
     }
+
+    /// Like [`Self::msm`], but faster when most of the scalars are small integers, as in most
+    /// witness polynomials (flags, bytes, register indices, ...), even if a few are large.
+    /// [`Self::msm`] picks its algorithm by the widest scalar; this instead buckets each scalar
+    /// by its own width:
+    /// - zeros are skipped, and ones are simply added up;
+    /// - scalars below 2^16 are added into two tables of 2^8 buckets, one for their low byte
+    ///   and one for their high byte, which are then summed with 2^9 additions each;
+    /// - the remaining scalars are passed to [`Self::msm`].
+    #[tracing::instrument(skip_all, name = "msm_small_values")]
+    fn msm_small_values(
+        bases: &[Self::MulBase],
+        scalars: &[Self::ScalarField],
+    ) -> Result<Self, usize> {
+        if bases.len() != scalars.len() {
+            return Err(bases.len().min(scalars.len()));
+        }
+        let num_chunks = rayon::current_num_threads();
+        let chunk_size = scalars.len().div_ceil(num_chunks).max(1);
+
+        let buckets = bases
+            .par_chunks(chunk_size)
+            .zip(scalars.par_chunks(chunk_size))
+            .map(|(bases, scalars)| {
+                let mut buckets = SmallValueBuckets::<Self>::new();
+                for (base, scalar) in bases.iter().zip(scalars) {
+                    buckets.add(base, scalar);
+                }
+                buckets
+            })
+            .reduce(SmallValueBuckets::new, SmallValueBuckets::merge);
+
+        let mut result = sum_buckets(buckets.high_bytes);
+        for _ in 0..8 {
+            result.double_in_place();
+        }
+        result += sum_buckets(buckets.low_bytes);
+        result += buckets.ones;
+        if !buckets.large_bases.is_empty() {
+            result += Self::msm(&buckets.large_bases, &buckets.large_scalars)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Partial sums for [`VariableBaseMSM::msm_small_values`].
+struct SmallValueBuckets<V: VariableBaseMSM> {
+    /// Sum of the bases whose scalar is one.
+    ones: V,
+    /// `low_bytes[i]` is the sum of the bases whose scalar is below 2^16 and has low byte `i`.
+    low_bytes: Vec<V>,
+    high_bytes: Vec<V>,
+    /// The bases whose scalar is 2^16 or more, and their scalars.
+    large_bases: Vec<V::MulBase>,
+    large_scalars: Vec<V::ScalarField>,
+}
+
+impl<V: VariableBaseMSM> SmallValueBuckets<V> {
+    fn new() -> Self {
+        Self {
+            ones: V::zero(),
+            low_bytes: vec![V::zero(); 1 << 8],
+            high_bytes: vec![V::zero(); 1 << 8],
+            large_bases: vec![],
+            large_scalars: vec![],
+        }
+    }
+
+    fn add(&mut self, base: &V::MulBase, scalar: &V::ScalarField) {
+        if scalar.is_zero() {
+            return;
+        }
+        if scalar.is_one() {
+            self.ones += base;
+            return;
+        }
+        let bigint = scalar.into_bigint();
+        if bigint.num_bits() > 16 {
+            self.large_bases.push(*base);
+            self.large_scalars.push(*scalar);
+            return;
+        }
+        let value = bigint.as_ref()[0] as usize;
+        self.low_bytes[value & 0xff] += base;
+        self.high_bytes[value >> 8] += base;
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.ones += other.ones;
+        for (sum, bucket) in self.low_bytes.iter_mut().zip(other.low_bytes) {
+            *sum += bucket;
+        }
+        for (sum, bucket) in self.high_bytes.iter_mut().zip(other.high_bytes) {
+            *sum += bucket;
+        }
+        self.large_bases.extend(other.large_bases);
+        self.large_scalars.extend(other.large_scalars);
+        self
+    }
+}
+
+/// Computes `sum_i i * buckets[i]` with `2 * buckets.len()` additions.
+fn sum_buckets<V: VariableBaseMSM>(buckets: Vec<V>) -> V {
+    let mut running_sum = V::zero();
+    let mut result = V::zero();
+    buckets.into_iter().skip(1).rev().for_each(|bucket| {
+        running_sum += bucket;
+        result += running_sum;
+    });
+    result
 }
 
 fn map_field_elements_to_u64<V: VariableBaseMSM>(field_elements: &[V::ScalarField]) -> Vec<u64> {
@@ -480,3 +590,33 @@ fn ln_without_floats(a: usize) -> usize {
     // log2(a) * ln(2)
     (ark_std::log2(a) * 69 / 100) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn msm_small_values() {
+        let mut rng = test_rng();
+        let n = 1000;
+        let bases: Vec<G1Affine> = (0..n).map(|_| G1Affine::rand(&mut rng)).collect();
+        // Zeros, ones, bytes, u16s, and a few full-width scalars
+        let scalars: Vec<Fr> = (0..n as u64)
+            .map(|i| match i % 5 {
+                0 => Fr::zero(),
+                1 => Fr::one(),
+                2 => Fr::from(i % 256),
+                3 => Fr::from(i * 65),
+                _ if i % 50 == 4 => Fr::rand(&mut rng),
+                _ => Fr::from(1 << 16),
+            })
+            .collect();
+        assert_eq!(
+            G1Projective::msm_small_values(&bases, &scalars).unwrap(),
+            G1Projective::msm(&bases, &scalars).unwrap()
+        );
+        assert!(G1Projective::msm_small_values(&bases[1..], &scalars).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone)]
 pub struct Binius128Scheme {}
@@ -20,16 +21,17 @@ impl AppendToTranscript for BiniusCommitment {
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
 pub struct BiniusProof {}
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
 pub struct BiniusBatchedProof {}
 
-#[derive(Clone)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct None {}
 
 impl CommitmentScheme for Binius128Scheme {
+    const NAME: &'static str = "Binius128";
     type Field = crate::field::binius::BiniusField<binius_field::BinaryField128bPolyval>;
     type Setup = None;
     type Commitment = BiniusCommitment;
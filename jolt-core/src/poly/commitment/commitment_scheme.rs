@@ -1,9 +1,15 @@
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 use crate::{
     field::JoltField,
-    poly::dense_mlpoly::DensePolynomial,
+    poly::{
+        dense_mlpoly::DensePolynomial,
+        opening_proof::{
+            prove_multi_point_opening, verify_multi_point_opening, ReducedOpeningProof,
+        },
+    },
     utils::{
         errors::ProofVerifyError,
         transcript::{AppendToTranscript, ProofTranscript},
@@ -28,14 +34,50 @@ impl CommitShape {
 #[derive(Clone, Debug)]
 pub enum BatchType {
     Big,
+    /// Like `Big`, for polynomials whose evaluations are mostly small integers, e.g. witness
+    /// columns. Schemes may commit to them with
+    /// [`VariableBaseMSM::msm_small_values`](crate::msm::VariableBaseMSM::msm_small_values).
+    SmallValues,
+    /// Like `Big`, for batches of many (e.g. 100+) large polynomials. Schemes may fold them in
+    /// cache-sized tiles rather than a polynomial at a time.
+    Huge,
     Small,
     SurgeInitFinal,
     SurgeReadWrite,
 }
 
+/// Commitments for which a linear combination of commitments is a commitment to the same
+/// linear combination of the committed polynomials.
+pub trait AdditivelyHomomorphic: Sized {
+    type Scalar: JoltField;
+
+    /// Returns `sum_i scalars[i] * commitments[i]`.
+    fn combine(commitments: &[&Self], scalars: &[Self::Scalar]) -> Self;
+
+    /// Verifier-side counterpart of [`DensePolynomial::linear_combination`]: folds the claims
+    /// that each `commitments[i]` opens to `openings[i]` (all at the same point) into a single
+    /// claim about the combined commitment.
+    fn fold(
+        commitments: &[&Self],
+        openings: &[Self::Scalar],
+        scalars: &[Self::Scalar],
+    ) -> (Self, Self::Scalar) {
+        assert_eq!(commitments.len(), openings.len());
+        let opening = openings
+            .iter()
+            .zip(scalars.iter())
+            .map(|(opening, scalar)| *opening * scalar)
+            .sum();
+        (Self::combine(commitments, scalars), opening)
+    }
+}
+
 pub trait CommitmentScheme: Clone + Sync + Send + 'static {
+    /// Identifies the scheme in file headers. The curve isn't part of it, since it follows from
+    /// `Field`.
+    const NAME: &'static str;
     type Field: JoltField + Sized;
-    type Setup: Clone + Sync + Send;
+    type Setup: Clone + Sync + Send + CanonicalSerialize + CanonicalDeserialize;
     type Commitment: Default
         + Debug
         + Sync
@@ -44,10 +86,33 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         + CanonicalSerialize
         + CanonicalDeserialize
         + AppendToTranscript;
-    type Proof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
-    type BatchedProof: Sync + Send + CanonicalSerialize + CanonicalDeserialize;
+    /// Proofs are also (de)serializable as JSON (see [`crate::utils::json`]), for tooling.
+    type Proof: Sync
+        + Send
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + Serialize
+        + DeserializeOwned;
+    type BatchedProof: Sync
+        + Send
+        + CanonicalSerialize
+        + CanonicalDeserialize
+        + Serialize
+        + DeserializeOwned;
 
     fn setup(shapes: &[CommitShape]) -> Self::Setup;
+    /// The maximum number of evaluations of a polynomial that can be committed to using `setup`.
+    fn srs_size(_setup: &Self::Setup) -> usize {
+        usize::MAX
+    }
+    /// Canonical encoding of the part of `setup` the verifier uses, which identifies it in
+    /// [`JoltPreprocessing::digest`](crate::jolt::vm::JoltPreprocessing::digest). Defaults to
+    /// all of `setup`.
+    fn verifier_key_bytes(setup: &Self::Setup) -> Vec<u8> {
+        let mut bytes = vec![];
+        setup.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment;
     fn batch_commit(
         evals: &[&[Self::Field]],
@@ -72,13 +137,24 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         Self::batch_commit(&slices, setup, batch_type)
     }
 
+    /// The number of polynomials the prover should pass to a single `batch_commit` call.
+    /// Witness columns are committed in batches of this size as soon as they are generated,
+    /// so schemes whose `batch_commit` parallelizes across polynomials should return a
+    /// value that keeps every thread busy.
+    fn commit_batch_size() -> usize {
+        rayon::current_num_threads()
+    }
+
     /// Homomorphically combines multiple commitments into a single commitment, computed as a
-    /// linear combination with the given coefficients.
+    /// linear combination with the given coefficients. Schemes whose commitments implement
+    /// [`AdditivelyHomomorphic`] forward to [`AdditivelyHomomorphic::combine`]; for other schemes
+    /// it panics, which is why the APIs relying on it outside of Jolt's own provers (e.g.
+    /// [`Self::batch_prove_multi_point`]) require that bound.
     fn combine_commitments(
         _commitments: &[&Self::Commitment],
         _coeffs: &[Self::Field],
     ) -> Self::Commitment {
-        todo!("`combine_commitments` should be on a separate `AdditivelyHomomorphic` trait")
+        todo!("`combine_commitments` is only supported by additively homomorphic schemes")
     }
 
     fn prove(
@@ -114,5 +190,43 @@ pub trait CommitmentScheme: Clone + Sync + Send + 'static {
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError>;
 
+    /// Proves the openings of multiple polynomials, each at its own point, with a single proof.
+    /// The openings are reduced to one opening of a random linear combination of the
+    /// polynomials via sumcheck (see [`crate::poly::opening_proof`]), so this is only available
+    /// for schemes whose commitments are [`AdditivelyHomomorphic`].
+    fn batch_prove_multi_point(
+        setup: &Self::Setup,
+        polynomials: &[&DensePolynomial<Self::Field>],
+        opening_points: &[&[Self::Field]],
+        openings: &[Self::Field],
+        transcript: &mut ProofTranscript,
+    ) -> ReducedOpeningProof<Self::Field, Self>
+    where
+        Self::Commitment: AdditivelyHomomorphic<Scalar = Self::Field>,
+    {
+        prove_multi_point_opening(setup, polynomials, opening_points, openings, transcript)
+    }
+
+    fn batch_verify_multi_point(
+        proof: ReducedOpeningProof<Self::Field, Self>,
+        setup: &Self::Setup,
+        opening_points: &[&[Self::Field]],
+        openings: &[Self::Field],
+        commitments: &[&Self::Commitment],
+        transcript: &mut ProofTranscript,
+    ) -> Result<(), ProofVerifyError>
+    where
+        Self::Commitment: AdditivelyHomomorphic<Scalar = Self::Field>,
+    {
+        verify_multi_point_opening(
+            setup,
+            proof,
+            commitments,
+            opening_points,
+            openings,
+            transcript,
+        )
+    }
+
     fn protocol_name() -> &'static [u8];
 }
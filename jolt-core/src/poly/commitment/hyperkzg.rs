@@ -8,11 +8,12 @@
 //! (2) HyperKZG is specialized to use KZG as the univariate commitment scheme, so it includes several optimizations (both during the transformation of multilinear-to-univariate claims
 //! and within the KZG commitment scheme implementation itself).
 use super::{
-    commitment_scheme::{BatchType, CommitmentScheme},
-    kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG},
+    commitment_scheme::{AdditivelyHomomorphic, BatchType, CommitmentScheme},
+    kzg::{sparse_support, KZGProverKey, KZGVerifierKey, UnivariateKZG},
 };
 use crate::field;
 use crate::poly::commitment::commitment_scheme::CommitShape;
+use crate::utils::json::Hex;
 use crate::utils::mul_0_1_optimized;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use crate::{
@@ -24,7 +25,7 @@ use crate::{
     },
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError, Validate};
 use ark_std::{One, Zero};
 use rand_chacha::ChaCha20Rng;
 use rand_core::{CryptoRng, RngCore, SeedableRng};
@@ -32,9 +33,23 @@ use rayon::iter::{
     IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
     IntoParallelRefMutIterator, ParallelIterator,
 };
-use std::{marker::PhantomData, sync::Arc};
+use rayon::slice::ParallelSliceMut;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{io::ErrorKind, marker::PhantomData, path::Path, sync::Arc};
 use tracing::trace_span;
 
+/// Seed of the SRS generated by [`HyperKZG::setup`].
+const SETUP_SEED: [u8; 32] = *b"HyperKZG_POLY_COMMITMENT_SCHEMEE";
+
+/// Below this many coefficients per thread, quotients are computed serially.
+const MIN_WITNESS_CHUNK_SIZE: usize = 1 << 14;
+/// Number of coefficients of the folded polynomial each task computes at a time in a
+/// [`BatchType::Huge`] batch.
+const HUGE_BATCH_TILE_SIZE: usize = 1 << 12;
+/// Number of polynomials folded per pass over a tile in a [`BatchType::Huge`] batch.
+const HUGE_BATCH_GROUP_SIZE: usize = 8;
+
 pub struct HyperKZGSRS<P: Pairing>(Arc<SRS<P>>);
 
 impl<P: Pairing> HyperKZGSRS<P> {
@@ -42,18 +57,101 @@ impl<P: Pairing> HyperKZGSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, 2)))
     }
 
+    /// Memory-maps an SRS saved with [`Self::write_mmap`], validating its G1 powers and then
+    /// paging them in on demand. See [`mmap_srs`](super::mmap_srs).
+    pub fn open_mmap<Q: AsRef<Path>>(path: Q) -> Result<Self, SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        Ok(Self(Arc::new(SRS::open_mmap(path)?)))
+    }
+
+    pub fn write_mmap<Q: AsRef<Path>>(&self, path: Q) -> Result<(), SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        self.0.write_mmap(path)
+    }
+
     pub fn trim(self, max_degree: usize) -> (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>) {
         let (kzg_pk, kzg_vk) = SRS::trim(self.0, max_degree);
         (HyperKZGProverKey { kzg_pk }, HyperKZGVerifierKey { kzg_vk })
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct HyperKZGProverKey<P: Pairing> {
     pub kzg_pk: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl<P: Pairing> HyperKZGProverKey<P> {
+    /// The prover key that [`HyperKZG::setup`] generates for polynomials of up to `max_len`
+    /// coefficients, precomputed once and cached at `cache_path` for every prover that needs it.
+    ///
+    /// If `cache_path` holds enough powers, the ones it needs are memory-mapped rather
+    /// than recomputed (see [`mmap_srs`](super::mmap_srs)), and every process mapping the file
+    /// shares its pages. Starting a prover then still reads and validates those powers, which
+    /// takes time linear in `max_len`, but is much cheaper than generating them. Otherwise the powers are generated, written to
+    /// `cache_path` and then mapped. The file is written under a temporary name and renamed into
+    /// place, so concurrent provers never map a partially written file, and those that mapped a
+    /// smaller one before it was replaced keep using it.
+    ///
+    /// The powers only depend on [`HyperKZG::setup`]'s seed, so a cached key serves any smaller
+    /// `max_len`, and commitments and proofs are the same as with the uncached key. A cached file
+    /// from another seed or curve, or holding invalid points, is rejected, but beyond that its
+    /// powers are not checked to be those of the seed: the cache must only be writable by
+    /// trusted provers.
+    pub fn with_precompute<Q: AsRef<Path>>(
+        cache_path: Q,
+        max_len: usize,
+    ) -> Result<Self, SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        let cache_path = cache_path.as_ref();
+        let srs = match SRS::<P>::open_mmap_prefix(cache_path, max_len + 1) {
+            Ok(srs) if srs.g1_powers.len() > max_len => srs,
+            Ok(_) => Self::precompute(cache_path, max_len)?,
+            Err(SerializationError::IoError(err)) if err.kind() == ErrorKind::NotFound => {
+                Self::precompute(cache_path, max_len)?
+            }
+            Err(err) => return Err(err),
+        };
+
+        // Regenerating the first powers is cheap, and catches a file from another seed
+        let expected = SRS::<P>::setup(&mut ChaCha20Rng::from_seed(SETUP_SEED), 0, 2);
+        if srs.g1_powers.get(0) != expected.g1_powers.get(0) || srs.g2_powers != expected.g2_powers
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(HyperKZGSRS(Arc::new(srs)).trim(max_len).0)
+    }
+
+    /// Generates the SRS for `max_len`, writes it to `cache_path` and maps it.
+    fn precompute(cache_path: &Path, max_len: usize) -> Result<SRS<P>, SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        let _span = trace_span!("HyperKZGProverKey::precompute").entered();
+        let srs = SRS::<P>::setup(&mut ChaCha20Rng::from_seed(SETUP_SEED), max_len, 2);
+        let mut temp_path = cache_path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        srs.write_mmap(&temp_path)?;
+        std::fs::rename(&temp_path, cache_path)?;
+        // The powers were just generated, so they don't need validating again
+        SRS::map(cache_path, usize::MAX, Validate::No)
+    }
+
+    /// The verifier key matching this prover key.
+    pub fn verifier_key(&self) -> HyperKZGVerifierKey<P> {
+        HyperKZGVerifierKey {
+            kzg_vk: self.kzg_pk.verifier_key(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct HyperKZGVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
 }
@@ -67,16 +165,41 @@ impl<P: Pairing> Default for HyperKZGCommitment<P> {
     }
 }
 
+impl<P: Pairing> AdditivelyHomomorphic for HyperKZGCommitment<P>
+where
+    <P as Pairing>::ScalarField: field::JoltField,
+{
+    type Scalar = P::ScalarField;
+
+    fn combine(commitments: &[&Self], scalars: &[Self::Scalar]) -> Self {
+        assert_eq!(commitments.len(), scalars.len());
+        if commitments.is_empty() {
+            return Self::default();
+        }
+        let bases: Vec<P::G1Affine> = commitments.iter().map(|commitment| commitment.0).collect();
+        Self(
+            <P::G1 as VariableBaseMSM>::msm(&bases, scalars)
+                .unwrap()
+                .into_affine(),
+        )
+    }
+}
+
 impl<P: Pairing> AppendToTranscript for HyperKZGCommitment<P> {
     fn append_to_transcript(&self, transcript: &mut ProofTranscript) {
         transcript.append_point(&self.0.into_group());
     }
 }
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Debug)]
+#[serde_as]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize, Debug)]
+#[serde(bound = "")]
 pub struct HyperKZGProof<P: Pairing> {
+    #[serde_as(as = "Vec<Hex>")]
     pub com: Vec<P::G1Affine>,
+    #[serde_as(as = "Vec<Hex>")]
     pub w: Vec<P::G1Affine>,
+    #[serde_as(as = "Vec<Vec<Hex>>")]
     pub v: Vec<Vec<P::ScalarField>>,
 }
 
@@ -114,26 +237,69 @@ where
 {
     let d = f.len();
 
-    // Compute h(x) = f(x)/(x - u)
+    // Compute h(x) = f(x)/(x - u), i.e. h[i - 1] = f[i] + h[i] * u with h[d - 1] = 0
     let mut h = vec![P::ScalarField::zero(); d];
-    for i in (1..d).rev() {
-        h[i - 1] = f[i] + h[i] * u;
+    if d < 2 {
+        return h;
+    }
+
+    // The recurrence is serial, so h[..d - 1] is split into chunks, each of which is first
+    // computed as if the value after it were zero. The actual value after each chunk, h[b], is
+    // then carried back from the end, and the chunk adds u^(b - j) * h[b] to each h[j].
+    let num_chunks = rayon::current_num_threads()
+        .min((d - 1).div_ceil(MIN_WITNESS_CHUNK_SIZE))
+        .max(1);
+    let chunk_size = (d - 1).div_ceil(num_chunks);
+    h[..d - 1]
+        .par_chunks_mut(chunk_size)
+        .enumerate()
+        .for_each(|(chunk_index, chunk)| {
+            let start = chunk_index * chunk_size;
+            let mut next = P::ScalarField::zero();
+            for (j, h_j) in chunk.iter_mut().enumerate().rev() {
+                next = f[start + j + 1] + next * u;
+                *h_j = next;
+            }
+        });
+    if num_chunks == 1 {
+        return h;
     }
 
+    let chunk_starts: Vec<usize> = (0..d - 1).step_by(chunk_size).collect();
+    let mut carries = vec![P::ScalarField::zero(); chunk_starts.len()];
+    let mut carry = P::ScalarField::zero();
+    for (chunk_index, &start) in chunk_starts.iter().enumerate().rev() {
+        carries[chunk_index] = carry;
+        let len = chunk_size.min(d - 1 - start);
+        carry = h[start] + pow::<P>(u, len) * carry;
+    }
+    h[..d - 1]
+        .par_chunks_mut(chunk_size)
+        .zip(carries)
+        .for_each(|(chunk, carry)| {
+            let mut term = carry;
+            for h_j in chunk.iter_mut().rev() {
+                term *= u;
+                *h_j += term;
+            }
+        });
+
     h
 }
 
-fn scalar_vector_muladd<P: Pairing>(
-    a: &mut [P::ScalarField],
-    v: &[P::ScalarField],
-    s: P::ScalarField,
-) where
+fn pow<P: Pairing>(base: P::ScalarField, mut exp: usize) -> P::ScalarField
+where
     <P as Pairing>::ScalarField: field::JoltField,
 {
-    assert!(a.len() >= v.len());
-    for i in 0..v.len() {
-        a[i] += s * v[i];
+    let (mut result, mut base) = (P::ScalarField::one(), base);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base = base * base;
+        exp >>= 1;
     }
+    result
 }
 
 fn kzg_compute_batch_polynomial<P: Pairing>(
@@ -143,17 +309,54 @@ fn kzg_compute_batch_polynomial<P: Pairing>(
 where
     <P as Pairing>::ScalarField: field::JoltField,
 {
-    let k = f.len(); // Number of polynomials we're batching
-
     // Compute B(x) = f[0] + q*f[1] + q^2 * f[2] + ... q^(k-1) * f[k-1]
     let mut B = f[0].clone();
-    for i in 1..k {
-        scalar_vector_muladd::<P>(&mut B, &f[i], q_powers[i]); // B += q_powers[i] * f[i]
-    }
+    B.par_iter_mut().enumerate().for_each(|(j, B_j)| {
+        for (f_i, q_i) in f[1..].iter().zip(&q_powers[1..]) {
+            if let Some(f_ij) = f_i.get(j) {
+                *B_j += *q_i * *f_ij;
+            }
+        }
+    });
 
     B
 }
 
+/// Computes the random linear combination `sum_i rho_powers[i] * polynomials[i]` of a
+/// [`BatchType::Huge`] batch. The result is split into tiles small enough to stay in cache, and
+/// each task folds the polynomials into its tile a group at a time, so that each pass over the
+/// tile accumulates several polynomials.
+fn fold_huge_batch<F: field::JoltField>(
+    polynomials: &[&DensePolynomial<F>],
+    rho_powers: &[F],
+    n: usize,
+) -> Vec<F> {
+    let mut f_batched = unsafe_allocate_zero_vec::<F>(n);
+    f_batched
+        .par_chunks_mut(HUGE_BATCH_TILE_SIZE)
+        .enumerate()
+        .for_each(|(tile_index, tile)| {
+            let tile_range = tile_index * HUGE_BATCH_TILE_SIZE..;
+            for (coeffs, group) in rho_powers
+                .chunks(HUGE_BATCH_GROUP_SIZE)
+                .zip(polynomials.chunks(HUGE_BATCH_GROUP_SIZE))
+            {
+                let evals: Vec<&[F]> = group
+                    .iter()
+                    .map(|poly| &poly.evals_ref()[tile_range.clone()][..tile.len()])
+                    .collect();
+                for (j, rlc) in tile.iter_mut().enumerate() {
+                    *rlc += coeffs
+                        .iter()
+                        .zip(&evals)
+                        .map(|(coeff, evals)| mul_0_1_optimized(&evals[j], coeff))
+                        .sum::<F>();
+                }
+            }
+        });
+    f_batched
+}
+
 fn kzg_open_batch<P: Pairing>(
     f: &[Vec<P::ScalarField>],
     u: &[P::ScalarField],
@@ -178,7 +381,7 @@ where
     });
 
     // TODO(moodlezoup): Avoid cloned()
-    transcript.append_scalars(&v.iter().flatten().cloned().collect::<Vec<P::ScalarField>>());
+    transcript.append_scalars_iter(v.iter().flatten().cloned());
     let q_powers: Vec<P::ScalarField> = transcript.challenge_scalar_powers(f.len());
     let B = kzg_compute_batch_polynomial::<P>(f, q_powers);
 
@@ -196,22 +399,24 @@ where
     (w, v)
 }
 
+/// Reduces a batched KZG opening to the pairing inputs `(L, R)`, which are valid iff
+/// e(L, vk.H) == e(R, vk.tau_H).
 // vk is hashed in transcript already, so we do not add it here
-fn kzg_verify_batch<P: Pairing>(
+fn kzg_batch_pairing_inputs<P: Pairing>(
     vk: &HyperKZGVerifierKey<P>,
     C: &[P::G1Affine],
     W: &[P::G1Affine],
     u: &[P::ScalarField],
     v: &[Vec<P::ScalarField>],
     transcript: &mut ProofTranscript,
-) -> bool
+) -> (P::G1, P::G1)
 where
     <P as Pairing>::ScalarField: field::JoltField,
 {
     let k = C.len();
     let t = u.len();
 
-    transcript.append_scalars(&v.iter().flatten().cloned().collect::<Vec<P::ScalarField>>());
+    transcript.append_scalars_iter(v.iter().flatten().cloned());
     let q_powers: Vec<P::ScalarField> = transcript.challenge_scalar_powers(k);
 
     transcript.append_points(&W.iter().map(|g| g.into_group()).collect::<Vec<P::G1>>());
@@ -274,10 +479,26 @@ where
 
     let R = W[0] + W[1] * d_0 + W[2] * d_1;
 
-    // Check that e(L, vk.H) == e(R, vk.tau_H)
+    (L, R)
+}
+
+/// Checks that e(L, vk.H) == e(R, vk.tau_H)
+fn check_pairing<P: Pairing>(vk: &HyperKZGVerifierKey<P>, L: P::G1, R: P::G1) -> bool {
     P::multi_pairing([L, -R], [vk.kzg_vk.g2, vk.kzg_vk.beta_g2]).is_zero()
 }
 
+/// A single HyperKZG opening, to be checked together with others by
+/// [`HyperKZG::aggregate_verify`].
+pub struct HyperKZGOpening<'a, P: Pairing> {
+    pub commitment: &'a HyperKZGCommitment<P>,
+    pub point: &'a [P::ScalarField],
+    pub eval: P::ScalarField,
+    pub proof: &'a HyperKZGProof<P>,
+    /// The transcript of the protocol this opening belongs to, in the state
+    /// [`HyperKZG::verify`] would be called with.
+    pub transcript: ProofTranscript,
+}
+
 #[derive(Clone)]
 pub struct HyperKZG<P: Pairing> {
     _phantom: PhantomData<P>,
@@ -295,9 +516,9 @@ where
         pp: &HyperKZGProverKey<P>,
         poly: &DensePolynomial<P::ScalarField>,
     ) -> Result<HyperKZGCommitment<P>, ProofVerifyError> {
-        if pp.kzg_pk.g1_powers().len() < poly.Z.len() {
+        if pp.kzg_pk.supported_size() < poly.Z.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pp.kzg_pk.g1_powers().len(),
+                pp.kzg_pk.supported_size(),
                 poly.Z.len(),
             ));
         }
@@ -323,17 +544,42 @@ where
         // also known to verifier, so can be derived on its side as well
         let mut polys: Vec<Vec<P::ScalarField>> = Vec::new();
         polys.push(poly.Z.to_vec());
+        // If the polynomial is sparse, so are all of the Pi (folding never adds nonzero
+        // entries), so they are folded and committed to in time proportional to their number
+        // of nonzero entries.
+        let mut supports: Vec<Option<Vec<usize>>> = vec![sparse_support(&poly.Z)];
         for i in 0..ell - 1 {
             let Pi_len = polys[i].len() / 2;
-            let mut Pi = vec![P::ScalarField::zero(); Pi_len];
-
-            #[allow(clippy::needless_range_loop)]
-            Pi.par_iter_mut().enumerate().for_each(|(j, Pi_j)| {
-                *Pi_j =
-                    point[ell - i - 1] * (polys[i][2 * j + 1] - polys[i][2 * j]) + polys[i][2 * j];
-            });
-
+            let r = point[ell - i - 1];
+            let fold = |j: usize| r * (polys[i][2 * j + 1] - polys[i][2 * j]) + polys[i][2 * j];
+
+            let (Pi, support) = match &supports[i] {
+                Some(indices) => {
+                    let mut Pi = unsafe_allocate_zero_vec(Pi_len);
+                    let mut next_indices = Vec::with_capacity(indices.len());
+                    let mut last_folded = None;
+                    for j in indices.iter().map(|index| index / 2) {
+                        if last_folded == Some(j) {
+                            continue;
+                        }
+                        last_folded = Some(j);
+                        Pi[j] = fold(j);
+                        if !Pi[j].is_zero() {
+                            next_indices.push(j);
+                        }
+                    }
+                    (Pi, Some(next_indices))
+                }
+                None => {
+                    let mut Pi = vec![P::ScalarField::zero(); Pi_len];
+                    Pi.par_iter_mut()
+                        .enumerate()
+                        .for_each(|(j, Pi_j)| *Pi_j = fold(j));
+                    (Pi, None)
+                }
+            };
             polys.push(Pi);
+            supports.push(support);
         }
 
         assert_eq!(polys.len(), ell);
@@ -343,7 +589,12 @@ where
         // Compute commitments in parallel
         let com: Vec<P::G1Affine> = (1..polys.len())
             .into_par_iter()
-            .map(|i| UnivariateKZG::commit_slice(&pk.kzg_pk, &polys[i]).unwrap())
+            .map(|i| match &supports[i] {
+                Some(indices) => {
+                    UnivariateKZG::commit_sparse(&pk.kzg_pk, &polys[i], indices).unwrap()
+                }
+                None => UnivariateKZG::commit_slice(&pk.kzg_pk, &polys[i]).unwrap(),
+            })
             .collect();
 
         // Phase 2
@@ -368,6 +619,76 @@ where
         pi: &HyperKZGProof<P>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
+        let (L, R) = Self::pairing_inputs(vk, C, point, P_of_x, pi, transcript)?;
+        if !check_pairing(vk, L, R) {
+            return Err(ProofVerifyError::InternalError);
+        }
+        Ok(())
+    }
+
+    /// Verifies several independent openings (e.g. the final opening proofs of K Jolt proofs)
+    /// with a single pairing check. Each opening is reduced to its pairing inputs `(L_i, R_i)`
+    /// on its own transcript; the inputs are then absorbed into an aggregation transcript,
+    /// which yields the challenge `gamma` for the check
+    /// e(sum_i gamma^i L_i, vk.H) == e(sum_i gamma^i R_i, vk.tau_H).
+    pub fn aggregate_verify(
+        vk: &HyperKZGVerifierKey<P>,
+        openings: Vec<HyperKZGOpening<'_, P>>,
+    ) -> Result<(), ProofVerifyError> {
+        if openings.is_empty() {
+            return Err(ProofVerifyError::InternalError);
+        }
+        let pairing_inputs = openings
+            .into_iter()
+            .map(|mut opening| {
+                Self::pairing_inputs(
+                    vk,
+                    opening.commitment,
+                    opening.point,
+                    &opening.eval,
+                    opening.proof,
+                    &mut opening.transcript,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut transcript = ProofTranscript::new(Self::aggregation_label());
+        transcript.append_points(
+            &pairing_inputs
+                .iter()
+                .flat_map(|(L, R)| [*L, *R])
+                .collect::<Vec<_>>(),
+        );
+        let gamma: P::ScalarField = transcript.challenge_scalar();
+
+        let (mut L, mut R) = (P::G1::zero(), P::G1::zero());
+        let mut gamma_power = P::ScalarField::one();
+        for (L_i, R_i) in pairing_inputs {
+            L += L_i * gamma_power;
+            R += R_i * gamma_power;
+            gamma_power *= gamma;
+        }
+        if !check_pairing(vk, L, R) {
+            return Err(ProofVerifyError::InternalError);
+        }
+        Ok(())
+    }
+
+    /// Label of the transcript used by [`Self::aggregate_verify`].
+    pub fn aggregation_label() -> &'static [u8] {
+        b"HyperKZG aggregation"
+    }
+
+    /// Checks everything about an opening except the final pairing equation, returning its
+    /// inputs `(L, R)`.
+    fn pairing_inputs(
+        vk: &HyperKZGVerifierKey<P>,
+        C: &HyperKZGCommitment<P>,
+        point: &[P::ScalarField],
+        P_of_x: &P::ScalarField,
+        pi: &HyperKZGProof<P>,
+        transcript: &mut ProofTranscript,
+    ) -> Result<(P::G1, P::G1), ProofVerifyError> {
         let y = P_of_x;
 
         let ell = point.len();
@@ -413,11 +734,9 @@ where
         }
 
         // Check commitments to (Y, ypos, yneg) are valid
-        if !kzg_verify_batch(vk, &com, &pi.w, &u, &pi.v, transcript) {
-            return Err(ProofVerifyError::InternalError);
-        }
-
-        Ok(())
+        Ok(kzg_batch_pairing_inputs(
+            vk, &com, &pi.w, &u, &pi.v, transcript,
+        ))
     }
 
     #[tracing::instrument(skip_all, name = "HyperKZG::batch_open")]
@@ -426,6 +745,7 @@ where
         polynomials: &[&DensePolynomial<P::ScalarField>],
         point: &[P::ScalarField],
         evals: &[P::ScalarField],
+        batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> HyperKZGProof<P> {
         let num_vars = point.len();
@@ -447,23 +767,28 @@ where
 
         let span = trace_span!("f_batched");
         let enter = span.enter();
-        let num_chunks = rayon::current_num_threads().next_power_of_two();
-        let chunk_size = n / num_chunks;
-        let f_batched = (0..num_chunks)
-            .into_par_iter()
-            .flat_map_iter(|chunk_index| {
-                let mut chunk = unsafe_allocate_zero_vec::<P::ScalarField>(chunk_size);
-                for (coeff, poly) in rho_powers.iter().zip(polynomials.iter()) {
-                    for (rlc, poly_eval) in chunk
-                        .iter_mut()
-                        .zip(poly.evals_ref()[chunk_index * chunk_size..].iter())
-                    {
-                        *rlc += mul_0_1_optimized(poly_eval, coeff);
-                    }
-                }
-                chunk
-            })
-            .collect::<Vec<_>>();
+        let f_batched = match batch_type {
+            BatchType::Huge => fold_huge_batch(polynomials, &rho_powers, n),
+            _ => {
+                let num_chunks = rayon::current_num_threads().next_power_of_two().min(n);
+                let chunk_size = n / num_chunks;
+                (0..num_chunks)
+                    .into_par_iter()
+                    .flat_map_iter(|chunk_index| {
+                        let mut chunk = unsafe_allocate_zero_vec::<P::ScalarField>(chunk_size);
+                        for (coeff, poly) in rho_powers.iter().zip(polynomials.iter()) {
+                            for (rlc, poly_eval) in chunk
+                                .iter_mut()
+                                .zip(poly.evals_ref()[chunk_index * chunk_size..].iter())
+                            {
+                                *rlc += mul_0_1_optimized(poly_eval, coeff);
+                            }
+                        }
+                        chunk
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
         drop(enter);
         drop(span);
 
@@ -508,6 +833,7 @@ impl<P: Pairing> CommitmentScheme for HyperKZG<P>
 where
     <P as Pairing>::ScalarField: field::JoltField,
 {
+    const NAME: &'static str = "HyperKZG";
     type Field = P::ScalarField;
     type Setup = (HyperKZGProverKey<P>, HyperKZGVerifierKey<P>);
     type Commitment = HyperKZGCommitment<P>;
@@ -518,18 +844,28 @@ where
         let max_len = shapes.iter().map(|shape| shape.input_length).max().unwrap();
 
         HyperKZGSRS(Arc::new(SRS::setup(
-            &mut ChaCha20Rng::from_seed(*b"HyperKZG_POLY_COMMITMENT_SCHEMEE"),
+            &mut ChaCha20Rng::from_seed(SETUP_SEED),
             max_len,
             2,
         )))
         .trim(max_len)
     }
 
+    fn srs_size(setup: &Self::Setup) -> usize {
+        setup.0.kzg_pk.supported_size()
+    }
+
+    fn verifier_key_bytes(setup: &Self::Setup) -> Vec<u8> {
+        let mut bytes = vec![];
+        setup.1.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
-            setup.0.kzg_pk.g1_powers().len() >= poly.Z.len(),
+            setup.0.kzg_pk.supported_size() >= poly.Z.len(),
             "COMMIT KEY LENGTH ERROR {}, {}",
-            setup.0.kzg_pk.g1_powers().len(),
+            setup.0.kzg_pk.supported_size(),
             poly.Z.len()
         );
         HyperKZGCommitment(UnivariateKZG::commit_slice(&setup.0.kzg_pk, &poly.Z).unwrap())
@@ -538,19 +874,25 @@ where
     fn batch_commit(
         evals: &[&[Self::Field]],
         gens: &Self::Setup,
-        _batch_type: BatchType,
+        batch_type: BatchType,
     ) -> Vec<Self::Commitment> {
         // TODO: assert lengths are valid
         evals
             .par_iter()
             .map(|evals| {
                 assert!(
-                    gens.0.kzg_pk.g1_powers().len() >= evals.len(),
+                    gens.0.kzg_pk.supported_size() >= evals.len(),
                     "COMMIT KEY LENGTH ERROR {}, {}",
-                    gens.0.kzg_pk.g1_powers().len(),
+                    gens.0.kzg_pk.supported_size(),
                     evals.len()
                 );
-                HyperKZGCommitment(UnivariateKZG::commit_slice(&gens.0.kzg_pk, evals).unwrap())
+                let commitment = match batch_type {
+                    BatchType::SmallValues => {
+                        UnivariateKZG::commit_small_values(&gens.0.kzg_pk, evals)
+                    }
+                    _ => UnivariateKZG::commit_slice(&gens.0.kzg_pk, evals),
+                };
+                HyperKZGCommitment(commitment.unwrap())
             })
             .collect::<Vec<_>>()
     }
@@ -574,22 +916,24 @@ where
         polynomials: &[&DensePolynomial<Self::Field>],
         opening_point: &[Self::Field],
         openings: &[Self::Field],
-        _batch_type: BatchType,
+        batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self::BatchedProof {
-        HyperKZG::<P>::batch_open(&setup.0, polynomials, opening_point, openings, transcript)
+        HyperKZG::<P>::batch_open(
+            &setup.0,
+            polynomials,
+            opening_point,
+            openings,
+            batch_type,
+            transcript,
+        )
     }
 
     fn combine_commitments(
         commitments: &[&Self::Commitment],
         coeffs: &[Self::Field],
     ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.0 * coeff)
-            .sum();
-        HyperKZGCommitment(combined_commitment.into_affine())
+        AdditivelyHomomorphic::combine(commitments, coeffs)
     }
 
     fn verify(
@@ -636,6 +980,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::json::{from_json, to_json};
     use ark_bn254::{Bn254, Fr};
     use ark_std::UniformRand;
     use rand_core::SeedableRng;
@@ -691,6 +1036,72 @@ mod tests {
         assert!(test_inner(point, eval).is_err());
     }
 
+    #[test]
+    fn aggregate_verify() {
+        let ell = 4;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+        let srs = HyperKZGSRS::setup(&mut rng, 1 << ell);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(1 << ell);
+
+        let mut commitments = vec![];
+        let mut points = vec![];
+        let mut evals = vec![];
+        let mut proofs = vec![];
+        for _ in 0..4 {
+            let poly = DensePolynomial::new((0..1 << ell).map(|_| Fr::rand(&mut rng)).collect());
+            let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+            let eval = poly.evaluate(&point);
+            let mut tr = ProofTranscript::new(b"TestEval");
+            proofs.push(HyperKZG::open(&pk, &poly, &point, &eval, &mut tr).unwrap());
+            commitments.push(HyperKZG::commit(&pk, &poly).unwrap());
+            points.push(point);
+            evals.push(eval);
+        }
+
+        fn openings<'a>(
+            commitments: &'a [HyperKZGCommitment<Bn254>],
+            points: &'a [Vec<Fr>],
+            evals: &[Fr],
+            proofs: &'a [HyperKZGProof<Bn254>],
+        ) -> Vec<HyperKZGOpening<'a, Bn254>> {
+            (0..commitments.len())
+                .map(|i| HyperKZGOpening {
+                    commitment: &commitments[i],
+                    point: &points[i],
+                    eval: evals[i],
+                    proof: &proofs[i],
+                    transcript: ProofTranscript::new(b"TestEval"),
+                })
+                .collect()
+        }
+        assert!(
+            HyperKZG::aggregate_verify(&vk, openings(&commitments, &points, &evals, &proofs))
+                .is_ok()
+        );
+        assert!(HyperKZG::aggregate_verify(&vk, vec![]).is_err());
+
+        // Wrong claimed evaluation for one of the openings
+        let mut bad_evals = evals.clone();
+        bad_evals[2] += Fr::from(1);
+        assert!(HyperKZG::aggregate_verify(
+            &vk,
+            openings(&commitments, &points, &bad_evals, &proofs)
+        )
+        .is_err());
+
+        // Wrong commitment, which only the pairing check catches
+        let mut bad_commitments: Vec<_> = commitments
+            .iter()
+            .map(|commitment| HyperKZGCommitment(commitment.0))
+            .collect();
+        bad_commitments.swap(0, 3);
+        assert!(HyperKZG::aggregate_verify(
+            &vk,
+            openings(&bad_commitments, &points, &evals, &proofs)
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_hyperkzg_small() {
         let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
@@ -745,6 +1156,71 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn json_round_trip() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let poly = DensePolynomial::new((0..8).map(|_| Fr::rand(&mut rng)).collect());
+        let point: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = poly.evaluate(&point);
+
+        let srs = HyperKZGSRS::setup(&mut rng, 8);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(8);
+        let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+        let mut transcript = ProofTranscript::new(b"TestEval");
+        let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut transcript).unwrap();
+
+        let vk_json = to_json(&vk).unwrap();
+        let proof_json = to_json(&proof).unwrap();
+        let vk: HyperKZGVerifierKey<Bn254> = from_json(&vk_json).unwrap();
+        let proof: HyperKZGProof<Bn254> = from_json(&proof_json).unwrap();
+        assert_eq!(to_json(&vk).unwrap(), vk_json);
+        assert_eq!(to_json(&proof).unwrap(), proof_json);
+
+        let value: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+        assert_eq!(
+            value["data"]["com"].as_array().unwrap().len(),
+            proof.com.len()
+        );
+        assert_eq!(value["data"]["v"].as_array().unwrap().len(), 3);
+
+        let mut transcript = ProofTranscript::new(b"TestEval");
+        assert!(HyperKZG::verify(&vk, &commitment, &point, &eval, &proof, &mut transcript).is_ok());
+    }
+
+    #[test]
+    fn precomputed_prover_key() {
+        let path = std::env::temp_dir().join(format!("jolt-hyperkzg-key-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let shapes = [CommitShape::new(16, BatchType::Small)];
+        let (pk, vk) = HyperKZG::<Bn254>::setup(&shapes);
+
+        // Generated on first use, then mapped, including for a smaller key
+        let cached = HyperKZGProverKey::<Bn254>::with_precompute(&path, 16).unwrap();
+        assert!(path.exists());
+        let mapped = HyperKZGProverKey::<Bn254>::with_precompute(&path, 8).unwrap();
+        assert_eq!(mapped.kzg_pk.supported_size(), 9);
+        assert_eq!(
+            to_json(&cached.verifier_key()).unwrap(),
+            to_json(&vk).unwrap()
+        );
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let poly = DensePolynomial::new((0..16).map(|_| Fr::rand(&mut rng)).collect());
+        let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+        assert_eq!(HyperKZG::commit(&cached, &poly).unwrap(), commitment);
+
+        // A larger key replaces the cached one
+        let larger = HyperKZGProverKey::<Bn254>::with_precompute(&path, 32).unwrap();
+        assert_eq!(larger.kzg_pk.supported_size(), 33);
+        assert_eq!(HyperKZG::commit(&larger, &poly).unwrap(), commitment);
+
+        // Powers from another seed are rejected
+        let other = HyperKZGSRS::<Bn254>::setup(&mut rng, 32);
+        other.write_mmap(&path).unwrap();
+        assert!(HyperKZGProverKey::<Bn254>::with_precompute(&path, 16).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_hyperkzg_large() {
         // test the hyperkzg prover and verifier with random instances (derived from a seed)
@@ -788,4 +1264,194 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn sparse_polynomial() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let ell = 12;
+        let n = 1 << ell;
+
+        // Nonzero at every 64th index, plus a pair of neighbours that fold together
+        let mut evals = vec![Fr::zero(); n];
+        for i in (0..n).step_by(64) {
+            evals[i] = Fr::rand(&mut rng);
+        }
+        evals[10] = Fr::rand(&mut rng);
+        evals[11] = Fr::one();
+        let poly = DensePolynomial::new(evals.clone());
+        assert!(sparse_support(&evals).is_some());
+
+        let srs = HyperKZGSRS::<Bn254>::setup(&mut rng, n);
+        let (pk, vk) = srs.trim(n);
+        let C = HyperKZG::commit(&pk, &poly).unwrap();
+        assert_eq!(C.0, pk.kzg_pk.msm(0, &evals).into_affine());
+
+        // The sparse opening is identical to the dense one
+        let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = poly.evaluate(&point);
+        let mut prover_transcript = ProofTranscript::new(b"TestEval");
+        let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+        let mut verifier_transcript = ProofTranscript::new(b"TestEval");
+        assert!(HyperKZG::verify(&vk, &C, &point, &eval, &proof, &mut verifier_transcript).is_ok());
+
+        let mut dense_polys = vec![evals];
+        for i in 0..ell - 1 {
+            let r = point[ell - i - 1];
+            let Pi = dense_polys[i]
+                .chunks(2)
+                .map(|pair| r * (pair[1] - pair[0]) + pair[0])
+                .collect();
+            dense_polys.push(Pi);
+        }
+        for (com, Pi) in proof.com.iter().zip(&dense_polys[1..]) {
+            assert_eq!(*com, pk.kzg_pk.msm(0, Pi).into_affine());
+        }
+    }
+
+    #[test]
+    fn combine_commitments() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let ell = 4;
+        let n = 1 << ell;
+        let srs = HyperKZGSRS::<Bn254>::setup(&mut rng, n);
+        let (pk, vk) = srs.trim(n);
+
+        let polys: Vec<DensePolynomial<Fr>> = (0..3)
+            .map(|_| DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect()))
+            .collect();
+        let scalars: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let commitments: Vec<_> = polys
+            .iter()
+            .map(|poly| HyperKZG::<Bn254>::commit(&pk, poly).unwrap())
+            .collect();
+        let commitment_refs: Vec<_> = commitments.iter().collect();
+
+        let poly_refs: Vec<_> = polys.iter().collect();
+        let combined_poly = DensePolynomial::linear_combination(&poly_refs, &scalars);
+        assert_eq!(
+            HyperKZGCommitment::<Bn254>::combine(&commitment_refs, &scalars),
+            HyperKZG::<Bn254>::commit(&pk, &combined_poly).unwrap()
+        );
+
+        // The folded claim can be checked with a single opening of the combined polynomial
+        let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let openings: Vec<Fr> = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+        let (combined_commitment, combined_opening) =
+            HyperKZGCommitment::<Bn254>::fold(&commitment_refs, &openings, &scalars);
+        assert_eq!(combined_opening, combined_poly.evaluate(&point));
+
+        let mut prover_transcript = ProofTranscript::new(b"TestEval");
+        let proof = HyperKZG::<Bn254>::open(
+            &pk,
+            &combined_poly,
+            &point,
+            &combined_opening,
+            &mut prover_transcript,
+        )
+        .unwrap();
+        let mut verifier_transcript = ProofTranscript::new(b"TestEval");
+        assert!(HyperKZG::<Bn254>::verify(
+            &vk,
+            &combined_commitment,
+            &point,
+            &combined_opening,
+            &proof,
+            &mut verifier_transcript,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn mmap_srs_open() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let ell = 4;
+        let n = 1 << ell;
+        let path = std::env::temp_dir().join(format!("jolt-hyperkzg-srs-{}", std::process::id()));
+        HyperKZGSRS::<Bn254>::setup(&mut rng, n)
+            .write_mmap(&path)
+            .unwrap();
+        let (pk, vk) = HyperKZGSRS::<Bn254>::open_mmap(&path).unwrap().trim(n);
+
+        let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>());
+        let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = poly.evaluate(&point);
+        let C = HyperKZG::<Bn254>::commit(&pk, &poly).unwrap();
+
+        let mut prover_transcript = ProofTranscript::new(b"TestEval");
+        let proof =
+            HyperKZG::<Bn254>::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+        let mut verifier_transcript = ProofTranscript::new(b"TestEval");
+        assert!(HyperKZG::<Bn254>::verify(
+            &vk,
+            &C,
+            &point,
+            &eval,
+            &proof,
+            &mut verifier_transcript
+        )
+        .is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn witness_polynomial_matches_serial_division() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let u = Fr::rand(&mut rng);
+        // Sizes around and well above a chunk, so that several threads split the recurrence
+        for d in [
+            0,
+            1,
+            2,
+            MIN_WITNESS_CHUNK_SIZE + 1,
+            4 * MIN_WITNESS_CHUNK_SIZE + 3,
+        ] {
+            let f: Vec<Fr> = (0..d).map(|_| Fr::rand(&mut rng)).collect();
+            let mut expected = vec![Fr::zero(); d];
+            for i in (1..d).rev() {
+                expected[i - 1] = f[i] + expected[i] * u;
+            }
+            assert_eq!(compute_witness_polynomial::<Bn254>(&f, u), expected);
+        }
+    }
+
+    #[test]
+    fn huge_batch_matches_big() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        // Several tiles, and a number of polynomials that isn't a multiple of the group size
+        let ell = 13;
+        let n = 1 << ell;
+        let polys: Vec<DensePolynomial<Fr>> = (0..HUGE_BATCH_GROUP_SIZE * 2 + 3)
+            .map(|_| DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect()))
+            .collect();
+        let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let evals: Vec<Fr> = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+        let shapes = [CommitShape::new(n, BatchType::Huge)];
+        let setup = HyperKZG::<Bn254>::setup(&shapes);
+        let commitments = HyperKZG::batch_commit_polys(&polys, &setup, BatchType::Huge);
+
+        let polys: Vec<_> = polys.iter().collect();
+        let prove = |batch_type| {
+            let mut transcript = ProofTranscript::new(b"TestEval");
+            HyperKZG::batch_prove(&setup, &polys, &point, &evals, batch_type, &mut transcript)
+        };
+        let to_bytes = |proof: &HyperKZGProof<Bn254>| {
+            let mut bytes = vec![];
+            proof.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        let proof = prove(BatchType::Huge);
+        assert_eq!(to_bytes(&proof), to_bytes(&prove(BatchType::Big)));
+
+        let commitments: Vec<_> = commitments.iter().collect();
+        let mut transcript = ProofTranscript::new(b"TestEval");
+        assert!(<HyperKZG<Bn254> as CommitmentScheme>::batch_verify(
+            &proof,
+            &setup,
+            &point,
+            &evals,
+            &commitments,
+            &mut transcript
+        )
+        .is_ok());
+    }
 }
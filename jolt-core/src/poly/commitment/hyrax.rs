@@ -2,23 +2,24 @@ use std::marker::PhantomData;
 
 use super::commitment_scheme::{BatchType, CommitShape, CommitmentScheme};
 use super::pedersen::{PedersenCommitment, PedersenGenerators};
+use crate::curve::JoltCurve;
 use crate::field::JoltField;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::utils::errors::ProofVerifyError;
+use crate::utils::json::Hex;
 use crate::utils::math::Math;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use crate::utils::{compute_dotproduct, mul_0_1_optimized};
-use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use num_integer::Roots;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use tracing::trace_span;
 
-use crate::msm::VariableBaseMSM;
-
 #[derive(Clone)]
-pub struct HyraxScheme<G: CurveGroup> {
+pub struct HyraxScheme<G: JoltCurve> {
     marker: PhantomData<G>,
 }
 
@@ -28,7 +29,9 @@ const SURGE_RATIO_FINAL: usize = 4;
 
 pub fn batch_type_to_ratio(batch_type: &BatchType) -> usize {
     match batch_type {
-        BatchType::Big => TRACE_LEN_R1CS_POLYS_BATCH_RATIO,
+        BatchType::Big | BatchType::SmallValues | BatchType::Huge => {
+            TRACE_LEN_R1CS_POLYS_BATCH_RATIO
+        }
         BatchType::Small => 1,
         BatchType::SurgeReadWrite => SURGE_RATIO_READ_WRITE,
         BatchType::SurgeInitFinal => SURGE_RATIO_FINAL,
@@ -47,8 +50,9 @@ pub fn matrix_dimensions(num_vars: usize, ratio: usize) -> (usize, usize) {
     (col_size, row_size)
 }
 
-impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxScheme<G> {
-    type Field = G::ScalarField;
+impl<F: JoltField, G: JoltCurve<Scalar = F>> CommitmentScheme for HyraxScheme<G> {
+    const NAME: &'static str = "Hyrax";
+    type Field = G::Scalar;
     type Setup = PedersenGenerators<G>;
     type Commitment = HyraxCommitment<G>;
     type Proof = HyraxOpeningProof<G>;
@@ -182,33 +186,30 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> CommitmentScheme for HyraxSch
 }
 
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct HyraxGenerators<G: CurveGroup> {
+pub struct HyraxGenerators<G: JoltCurve> {
     pub gens: PedersenGenerators<G>,
 }
 
 #[derive(Default, Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
-pub struct HyraxCommitment<G: CurveGroup> {
+pub struct HyraxCommitment<G: JoltCurve> {
     pub row_commitments: Vec<G>,
 }
 
-impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
+impl<F: JoltField, G: JoltCurve<Scalar = F>> HyraxCommitment<G> {
     #[tracing::instrument(skip_all, name = "HyraxCommitment::commit")]
-    pub fn commit(
-        poly: &DensePolynomial<G::ScalarField>,
-        generators: &PedersenGenerators<G>,
-    ) -> Self {
+    pub fn commit(poly: &DensePolynomial<G::Scalar>, generators: &PedersenGenerators<G>) -> Self {
         Self::commit_slice(poly.evals_ref(), generators)
     }
 
     #[tracing::instrument(skip_all, name = "HyraxCommitment::commit_slice")]
-    pub fn commit_slice(eval_slice: &[G::ScalarField], generators: &PedersenGenerators<G>) -> Self {
+    pub fn commit_slice(eval_slice: &[G::Scalar], generators: &PedersenGenerators<G>) -> Self {
         let n = eval_slice.len();
         let ell = n.log_2();
 
         let (L_size, R_size) = matrix_dimensions(ell, 1);
         assert_eq!(L_size * R_size, n);
 
-        let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
+        let gens = G::normalize_batch(&generators.generators[..R_size]);
         let row_commitments = eval_slice
             .par_chunks(R_size)
             .map(|row| PedersenCommitment::commit_vector(row, &gens))
@@ -218,7 +219,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
 
     #[tracing::instrument(skip_all, name = "HyraxCommitment::batch_commit")]
     pub fn batch_commit(
-        batch: &[&[G::ScalarField]],
+        batch: &[&[G::Scalar]],
         generators: &PedersenGenerators<G>,
         batch_type: BatchType,
     ) -> Vec<Self> {
@@ -231,7 +232,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
         let (L_size, R_size) = matrix_dimensions(ell, ratio);
         assert_eq!(L_size * R_size, n);
 
-        let gens = CurveGroup::normalize_batch(&generators.generators[..R_size]);
+        let gens = G::normalize_batch(&generators.generators[..R_size]);
 
         let rows = batch.par_iter().flat_map(|poly| poly.par_chunks(R_size));
         let row_commitments: Vec<G> = rows
@@ -247,31 +248,34 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxCommitment<G> {
     }
 }
 
-impl<G: CurveGroup> AppendToTranscript for HyraxCommitment<G> {
+impl<G: JoltCurve> AppendToTranscript for HyraxCommitment<G> {
     fn append_to_transcript(&self, transcript: &mut ProofTranscript) {
         transcript.append_message(b"poly_commitment_begin");
         for i in 0..self.row_commitments.len() {
-            transcript.append_point(&self.row_commitments[i]);
+            transcript.append_curve_point(&self.row_commitments[i]);
         }
         transcript.append_message(b"poly_commitment_end");
     }
 }
 
-#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
-pub struct HyraxOpeningProof<G: CurveGroup> {
-    pub vector_matrix_product: Vec<G::ScalarField>,
+#[serde_as]
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HyraxOpeningProof<G: JoltCurve> {
+    #[serde_as(as = "Vec<Hex>")]
+    pub vector_matrix_product: Vec<G::Scalar>,
 }
 
 /// See Section 14.3 of Thaler's Proofs, Arguments, and Zero-Knowledge
-impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
+impl<F: JoltField, G: JoltCurve<Scalar = F>> HyraxOpeningProof<G> {
     fn protocol_name() -> &'static [u8] {
         b"Hyrax opening proof"
     }
 
     #[tracing::instrument(skip_all, name = "HyraxOpeningProof::prove")]
     pub fn prove(
-        poly: &DensePolynomial<G::ScalarField>,
-        opening_point: &[G::ScalarField], // point at which the polynomial is evaluated
+        poly: &DensePolynomial<G::Scalar>,
+        opening_point: &[G::Scalar], // point at which the polynomial is evaluated
         ratio: usize,
         transcript: &mut ProofTranscript,
     ) -> HyraxOpeningProof<G> {
@@ -297,8 +301,8 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
         &self,
         pedersen_generators: &PedersenGenerators<G>,
         transcript: &mut ProofTranscript,
-        opening_point: &[G::ScalarField], // point at which the polynomial is evaluated
-        opening: &G::ScalarField,         // evaluation \widetilde{Z}(r)
+        opening_point: &[G::Scalar], // point at which the polynomial is evaluated
+        opening: &G::Scalar,         // evaluation \widetilde{Z}(r)
         commitment: &HyraxCommitment<G>,
         ratio: usize,
     ) -> Result<(), ProofVerifyError> {
@@ -311,9 +315,9 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
 
         // Verifier-derived commitment to u * a = \prod Com(u_j)^{a_j}
         let homomorphically_derived_commitment: G =
-            VariableBaseMSM::msm(&G::normalize_batch(&commitment.row_commitments), &L).unwrap();
+            G::msm(&G::normalize_batch(&commitment.row_commitments), &L).unwrap();
 
-        let product_commitment = VariableBaseMSM::msm(
+        let product_commitment = G::msm(
             &G::normalize_batch(&pedersen_generators.generators[..R_size]),
             &self.vector_matrix_product,
         )
@@ -330,10 +334,10 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
 
     #[tracing::instrument(skip_all, name = "HyraxOpeningProof::vector_matrix_product")]
     fn vector_matrix_product(
-        poly: &DensePolynomial<G::ScalarField>,
-        L: &[G::ScalarField],
+        poly: &DensePolynomial<G::Scalar>,
+        L: &[G::Scalar],
         ratio: usize,
-    ) -> Vec<G::ScalarField> {
+    ) -> Vec<G::Scalar> {
         let (_, R_size) = matrix_dimensions(poly.get_num_vars(), ratio);
 
         poly.evals_ref()
@@ -342,10 +346,10 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
             .map(|(i, row)| {
                 row.iter()
                     .map(|x| mul_0_1_optimized(&L[i], x))
-                    .collect::<Vec<G::ScalarField>>()
+                    .collect::<Vec<G::Scalar>>()
             })
             .reduce(
-                || vec![G::ScalarField::zero(); R_size],
+                || vec![G::Scalar::zero(); R_size],
                 |mut acc: Vec<_>, row| {
                     acc.iter_mut().zip(row).for_each(|(x, y)| *x += y);
                     acc
@@ -354,19 +358,20 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> HyraxOpeningProof<G> {
     }
 }
 
-#[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
-pub struct BatchedHyraxOpeningProof<G: CurveGroup> {
+#[derive(Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BatchedHyraxOpeningProof<G: JoltCurve> {
     pub joint_proof: HyraxOpeningProof<G>,
     pub ratio: usize,
 }
 
 /// See Section 16.1 of Thaler's Proofs, Arguments, and Zero-Knowledge
-impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
+impl<F: JoltField, G: JoltCurve<Scalar = F>> BatchedHyraxOpeningProof<G> {
     #[tracing::instrument(skip_all, name = "BatchedHyraxOpeningProof::prove")]
     pub fn prove(
-        polynomials: &[&DensePolynomial<G::ScalarField>],
-        opening_point: &[G::ScalarField],
-        openings: &[G::ScalarField],
+        polynomials: &[&DensePolynomial<G::Scalar>],
+        opening_point: &[G::Scalar],
+        openings: &[G::Scalar],
         batch_type: BatchType,
         transcript: &mut ProofTranscript,
     ) -> Self {
@@ -389,7 +394,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
             (0..num_chunks)
                 .into_par_iter()
                 .flat_map_iter(|chunk_index| {
-                    let mut chunk = vec![G::ScalarField::zero(); chunk_size];
+                    let mut chunk = vec![G::Scalar::zero(); chunk_size];
                     for (coeff, poly) in rlc_coefficients.iter().zip(polynomials.iter()) {
                         for (rlc, poly_eval) in chunk
                             .iter_mut()
@@ -407,7 +412,7 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
                 .zip(polynomials.par_iter())
                 .map(|(coeff, poly)| poly.evals_ref().iter().map(|eval| *coeff * *eval).collect())
                 .reduce(
-                    || vec![G::ScalarField::zero(); poly_len],
+                    || vec![G::Scalar::zero(); poly_len],
                     |running, new| {
                         debug_assert_eq!(running.len(), new.len());
                         running
@@ -437,8 +442,8 @@ impl<F: JoltField, G: CurveGroup<ScalarField = F>> BatchedHyraxOpeningProof<G> {
     pub fn verify(
         &self,
         pedersen_generators: &PedersenGenerators<G>,
-        opening_point: &[G::ScalarField],
-        openings: &[G::ScalarField],
+        opening_point: &[G::Scalar],
+        openings: &[G::Scalar],
         commitments: &[&HyraxCommitment<G>],
         transcript: &mut ProofTranscript,
     ) -> Result<(), ProofVerifyError> {
@@ -515,24 +520,24 @@ mod tests {
 
     fn check_polynomial_commit_helper<
         F: JoltField,
-        G: CurveGroup<ScalarField = F>,
+        G: JoltCurve<Scalar = F>,
         const RATIO: usize,
     >() {
         let Z = vec![
-            G::ScalarField::one(),
-            G::ScalarField::from_u64(2u64).unwrap(),
-            G::ScalarField::one(),
-            G::ScalarField::from_u64(4u64).unwrap(),
+            G::Scalar::one(),
+            G::Scalar::from_u64(2u64).unwrap(),
+            G::Scalar::one(),
+            G::Scalar::from_u64(4u64).unwrap(),
         ];
         let poly = DensePolynomial::new(Z);
 
         // r = [4,3]
         let r = vec![
-            G::ScalarField::from_u64(4u64).unwrap(),
-            G::ScalarField::from_u64(3u64).unwrap(),
+            G::Scalar::from_u64(4u64).unwrap(),
+            G::Scalar::from_u64(3u64).unwrap(),
         ];
         let eval = poly.evaluate(&r);
-        assert_eq!(eval, G::ScalarField::from_u64(28u64).unwrap());
+        assert_eq!(eval, G::Scalar::from_u64(28u64).unwrap());
 
         let generators: PedersenGenerators<G> = PedersenGenerators::new(1 << 8, b"test-two");
         let poly_commitment: HyraxCommitment<G> = HyraxCommitment::commit(&poly, &generators);
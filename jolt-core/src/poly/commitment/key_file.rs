@@ -0,0 +1,192 @@
+//! Self-checking files for the prover and verifier keys of the KZG-based commitment schemes.
+//!
+//! A key file starts with [`KEY_MAGIC`], the [kind](CheckedKey::KIND) of the key, and a SHA3-256 digest of the key,
+//! which is stored after them with compressed points. [`CheckedKey::load_checked`] rejects a
+//! file whose digest doesn't match before deserializing it, validates every point, and then
+//! checks the key's structure (e.g. that the SRS powers are powers of a single secret), so a
+//! corrupted or tampered key fails to load instead of producing proofs that don't verify.
+
+use std::io::{Read, Write};
+
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use sha3::{Digest, Sha3_256};
+
+use super::hyperkzg::{HyperKZGProverKey, HyperKZGVerifierKey};
+use super::zeromorph::{ZeromorphProverKey, ZeromorphVerifierKey};
+use crate::utils::errors::KeyError;
+
+pub const KEY_MAGIC: [u8; 8] = *b"JOLTKEY1";
+
+pub trait CheckedKey: CanonicalSerialize + CanonicalDeserialize {
+    /// Name of the type of key, stored in the file so that a key isn't loaded as another type.
+    const KIND: &'static str;
+
+    /// Checks relations between the key's points that hold for any honestly generated key,
+    /// using `rng` for the random linear combinations they are batched with.
+    fn check_structure<R: RngCore>(&self, rng: &mut R) -> Result<(), KeyError>;
+
+    /// Writes the key in the format read by [`Self::load_checked`].
+    fn save_compressed<W: Write>(&self, mut writer: W) -> Result<(), KeyError> {
+        let mut body = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut body)?;
+        writer.write_all(&KEY_MAGIC)?;
+        Self::KIND.to_string().serialize_compressed(&mut writer)?;
+        writer.write_all(&Sha3_256::digest(&body))?;
+        writer.write_all(&body)?;
+        Ok(())
+    }
+
+    /// Reads a key written by [`Self::save_compressed`], checking its digest, its points, and
+    /// its structure. The randomness of the structure checks is derived from the digest, so
+    /// loading is deterministic but a key can't be crafted to pass them.
+    fn load_checked<R: Read>(mut reader: R) -> Result<Self, KeyError> {
+        let mut magic = [0u8; KEY_MAGIC.len()];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| KeyError::InvalidMagic)?;
+        if magic != KEY_MAGIC {
+            return Err(KeyError::InvalidMagic);
+        }
+        let kind = String::deserialize_compressed(&mut reader)?;
+        if kind != Self::KIND {
+            return Err(KeyError::WrongKind {
+                expected: Self::KIND.to_string(),
+                found: kind,
+            });
+        }
+        let mut digest = [0u8; 32];
+        reader.read_exact(&mut digest)?;
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body)?;
+        if <[u8; 32]>::from(Sha3_256::digest(&body)) != digest {
+            return Err(KeyError::DigestMismatch);
+        }
+
+        let key = Self::deserialize_compressed(body.as_slice())?;
+        key.check_structure(&mut ChaCha20Rng::from_seed(digest))?;
+        Ok(key)
+    }
+}
+
+impl<P: Pairing> CheckedKey for HyperKZGProverKey<P> {
+    const KIND: &'static str = "HyperKZGProverKey";
+
+    fn check_structure<R: RngCore>(&self, rng: &mut R) -> Result<(), KeyError> {
+        self.kzg_pk.check_powers(rng)
+    }
+}
+
+impl<P: Pairing> CheckedKey for HyperKZGVerifierKey<P> {
+    const KIND: &'static str = "HyperKZGVerifierKey";
+
+    fn check_structure<R: RngCore>(&self, _rng: &mut R) -> Result<(), KeyError> {
+        self.kzg_vk.check_nondegenerate()
+    }
+}
+
+impl<P: Pairing> CheckedKey for ZeromorphProverKey<P> {
+    const KIND: &'static str = "ZeromorphProverKey";
+
+    fn check_structure<R: RngCore>(&self, rng: &mut R) -> Result<(), KeyError> {
+        self.commit_pp.check_powers(rng)?;
+        self.open_pp.check_powers(rng)
+    }
+}
+
+impl<P: Pairing> CheckedKey for ZeromorphVerifierKey<P> {
+    const KIND: &'static str = "ZeromorphVerifierKey";
+
+    fn check_structure<R: RngCore>(&self, _rng: &mut R) -> Result<(), KeyError> {
+        self.kzg_vk.check_nondegenerate()?;
+        if self.tau_N_max_sub_2_N.is_zero() {
+            return Err(KeyError::Malformed("verifier key point is the identity"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::hyperkzg::HyperKZGSRS;
+    use crate::poly::commitment::kzg::SRS;
+    use crate::poly::commitment::mmap_srs::G1Powers;
+    use crate::poly::commitment::zeromorph::ZeromorphSRS;
+    use ark_bn254::{Bn254, G1Affine};
+    use std::sync::Arc;
+
+    fn saved<K: CheckedKey>(key: &K) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        key.save_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (pk, vk) = HyperKZGSRS::<Bn254>::setup(&mut rng, 64).trim(64);
+        let loaded = HyperKZGProverKey::<Bn254>::load_checked(saved(&pk).as_slice()).unwrap();
+        assert_eq!(saved(&loaded), saved(&pk));
+        let loaded = HyperKZGVerifierKey::<Bn254>::load_checked(saved(&vk).as_slice()).unwrap();
+        assert_eq!(loaded.kzg_vk.beta_g2, vk.kzg_vk.beta_g2);
+
+        let (pk, vk) = ZeromorphSRS::<Bn254>::setup(&mut rng, 64).trim(32);
+        ZeromorphProverKey::<Bn254>::load_checked(saved(&pk).as_slice()).unwrap();
+        ZeromorphVerifierKey::<Bn254>::load_checked(saved(&vk).as_slice()).unwrap();
+
+        // Compressed points take half the space
+        let mut uncompressed = Vec::new();
+        pk.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert!(saved(&pk).len() < uncompressed.len() * 2 / 3);
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (pk, vk) = HyperKZGSRS::<Bn254>::setup(&mut rng, 64).trim(64);
+
+        assert!(matches!(
+            HyperKZGProverKey::<Bn254>::load_checked(&b"JOLTPREP"[..]),
+            Err(KeyError::InvalidMagic)
+        ));
+        assert!(matches!(
+            HyperKZGVerifierKey::<Bn254>::load_checked(saved(&pk).as_slice()),
+            Err(KeyError::WrongKind { .. })
+        ));
+
+        let mut corrupted = saved(&vk);
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            HyperKZGVerifierKey::<Bn254>::load_checked(corrupted.as_slice()),
+            Err(KeyError::DigestMismatch)
+        ));
+    }
+
+    #[test]
+    fn rejects_inconsistent_powers() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let mut srs = SRS::<Bn254>::setup(&mut rng, 64, 2);
+        // Swapping two powers keeps every point valid, but breaks the relations between them
+        if let G1Powers::InMemory(powers) = &mut srs.g1_powers {
+            powers.swap(10, 11);
+        }
+        let (kzg_pk, _) = SRS::trim(Arc::new(srs), 64);
+        let pk = HyperKZGProverKey { kzg_pk };
+        // A well-formed file, so only the structure check can catch it
+        assert!(matches!(
+            HyperKZGProverKey::<Bn254>::load_checked(saved(&pk).as_slice()),
+            Err(KeyError::Malformed(_))
+        ));
+
+        let (_, mut vk) = HyperKZGSRS::<Bn254>::setup(&mut rng, 64).trim(64);
+        vk.kzg_vk.g1 = G1Affine::zero();
+        assert!(matches!(
+            HyperKZGVerifierKey::<Bn254>::load_checked(saved(&vk).as_slice()),
+            Err(KeyError::Malformed(_))
+        ));
+    }
+}
@@ -1,18 +1,27 @@
+use super::mmap_srs::G1Powers;
 use crate::field::JoltField;
 use crate::msm::VariableBaseMSM;
 use crate::poly::unipoly::UniPoly;
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{KeyError, ProofVerifyError};
+use crate::utils::json::Hex;
 use ark_ec::scalar_mul::fixed_base::FixedBase;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{One, UniformRand, Zero};
 use rand_core::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SRS<P: Pairing> {
-    pub g1_powers: Vec<P::G1Affine>,
+    pub g1_powers: G1Powers<P::G1Affine>,
     pub g2_powers: Vec<P::G2Affine>,
 }
 
@@ -62,7 +71,7 @@ impl<P: Pairing> SRS<P> {
         );
 
         Self {
-            g1_powers,
+            g1_powers: G1Powers::InMemory(g1_powers),
             g2_powers,
         }
     }
@@ -73,13 +82,46 @@ impl<P: Pairing> SRS<P> {
             max_degree < params.g1_powers.len(),
             "SRS length is less than size"
         );
-        let g1 = params.g1_powers[0];
-        let g2 = params.g2_powers[0];
-        let beta_g2 = params.g2_powers[1];
         let pk = KZGProverKey::new(params, 0, max_degree + 1);
-        let vk = KZGVerifierKey { g1, g2, beta_g2 };
+        let vk = pk.verifier_key();
         (pk, vk)
     }
+
+    /// Checks that the powers are powers of one secret: with `g1_powers[i] = beta^(i + 1) g1`
+    /// and `g2_powers[i] = beta^(i + 1) g2`, that
+    /// `e(g1_powers[i + 1], g2_powers[0]) = e(g1_powers[i], g2_powers[1])` for every `i`, and
+    /// likewise along the G2 powers. All of the relations are checked at once, as a random
+    /// linear combination drawn from `rng`.
+    pub fn check_powers<R: RngCore>(&self, rng: &mut R) -> Result<(), KeyError> {
+        let (n1, n2) = (self.g1_powers.len(), self.g2_powers.len());
+        if n1 < 2 || n2 < 2 {
+            return Err(KeyError::Malformed(
+                "SRS needs at least two G1 and two G2 powers",
+            ));
+        }
+        let g1 = self.g1_powers.get(0);
+        if g1.is_zero() || self.g2_powers[0].is_zero() {
+            return Err(KeyError::Malformed("SRS generator is the identity"));
+        }
+
+        let r1: Vec<P::ScalarField> = (0..n1 - 1).map(|_| P::ScalarField::rand(rng)).collect();
+        let shifted: P::G1 = self.g1_powers.msm(1, &r1);
+        let unshifted: P::G1 = self.g1_powers.msm(0, &r1);
+        let g1_relation = P::multi_pairing(
+            [shifted, -unshifted],
+            [self.g2_powers[0], self.g2_powers[1]],
+        );
+
+        let r2: Vec<P::ScalarField> = (0..n2 - 1).map(|_| P::ScalarField::rand(rng)).collect();
+        let shifted = <P::G2 as VariableBaseMSM>::msm(&self.g2_powers[1..], &r2).unwrap();
+        let unshifted = <P::G2 as VariableBaseMSM>::msm(&self.g2_powers[..n2 - 1], &r2).unwrap();
+        let g2_relation = P::multi_pairing([g1, self.g1_powers.get(1)], [shifted, -unshifted]);
+
+        if !g1_relation.is_zero() || !g2_relation.is_zero() {
+            return Err(KeyError::Malformed("SRS powers are inconsistent"));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -107,18 +149,159 @@ impl<P: Pairing> KZGProverKey<P> {
         }
     }
 
-    pub fn g1_powers(&self) -> &[P::G1Affine] {
-        &self.srs.g1_powers[self.offset..self.offset + self.supported_size]
+    /// Checks the SRS this key reads from with [`SRS::check_powers`].
+    pub fn check_powers<R: RngCore>(&self, rng: &mut R) -> Result<(), KeyError> {
+        self.srs.check_powers(rng)
+    }
+
+    /// The verifier key for the SRS this key reads from.
+    pub fn verifier_key(&self) -> KZGVerifierKey<P> {
+        KZGVerifierKey {
+            g1: self.srs.g1_powers.get(0),
+            g2: self.srs.g2_powers[0],
+            beta_g2: self.srs.g2_powers[1],
+        }
+    }
+
+    /// Number of G1 powers available to this key.
+    pub fn supported_size(&self) -> usize {
+        self.supported_size
+    }
+
+    /// Computes `sum_i scalars[i] * g1_powers[start + i]`, for this key's G1 powers.
+    pub fn msm(&self, start: usize, scalars: &[P::ScalarField]) -> P::G1 {
+        assert!(start + scalars.len() <= self.supported_size);
+        self.srs.g1_powers.msm(self.offset + start, scalars)
+    }
+
+    /// Like [`Self::msm`], using
+    /// [`VariableBaseMSM::msm_small_values`](crate::msm::VariableBaseMSM::msm_small_values).
+    pub fn msm_small_values(&self, start: usize, scalars: &[P::ScalarField]) -> P::G1 {
+        assert!(start + scalars.len() <= self.supported_size);
+        self.srs
+            .g1_powers
+            .msm_small_values(self.offset + start, scalars)
+    }
+
+    /// Like [`Self::msm`] with `start = 0`, for `scalars` that are zero outside of `indices`.
+    pub fn sparse_msm(&self, indices: &[usize], scalars: &[P::ScalarField]) -> P::G1 {
+        assert!(scalars.len() <= self.supported_size);
+        self.srs.g1_powers.sparse_msm(self.offset, indices, scalars)
+    }
+
+    /// Like [`Self::msm`] with `start = 0`, but over only the nonzero `coeffs` if they are
+    /// sparse (see [`sparse_support`]).
+    fn msm_auto(&self, coeffs: &[P::ScalarField]) -> P::G1
+    where
+        P::ScalarField: JoltField,
+    {
+        match sparse_support(coeffs) {
+            Some(indices) => self.sparse_msm(&indices, coeffs),
+            None => self.msm(0, coeffs),
+        }
+    }
+}
+
+/// Polynomials at least this long with at most a `1 / SPARSE_DENSITY_INV` fraction of nonzero
+/// coefficients are committed to with an MSM over only the nonzero coefficients.
+const SPARSE_MIN_LEN: usize = 1 << 10;
+const SPARSE_DENSITY_INV: usize = 16;
+
+/// The indices of the nonzero entries of `coeffs`, in increasing order, if `coeffs` is sparse
+/// enough for a sparse MSM to be faster than a dense one. Witness polynomials such as
+/// instruction flags are typically mostly zero.
+pub fn sparse_support<F: JoltField>(coeffs: &[F]) -> Option<Vec<usize>> {
+    if coeffs.len() < SPARSE_MIN_LEN {
+        return None;
+    }
+    let num_nonzero = coeffs.par_iter().filter(|coeff| !coeff.is_zero()).count();
+    if num_nonzero * SPARSE_DENSITY_INV > coeffs.len() {
+        return None;
+    }
+    Some(
+        coeffs
+            .par_iter()
+            .enumerate()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(i, _)| i)
+            .collect(),
+    )
+}
+
+// Serialized by hand because the SRS is shared behind an `Arc`.
+impl<P: Pairing> CanonicalSerialize for KZGProverKey<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.srs
+            .as_ref()
+            .serialize_with_mode(&mut writer, compress)?;
+        self.offset.serialize_with_mode(&mut writer, compress)?;
+        self.supported_size
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.srs.as_ref().serialized_size(compress)
+            + self.offset.serialized_size(compress)
+            + self.supported_size.serialized_size(compress)
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl<P: Pairing> Valid for KZGProverKey<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.srs.as_ref().check()
+    }
+}
+
+impl<P: Pairing> CanonicalDeserialize for KZGProverKey<P> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let srs = SRS::deserialize_with_mode(&mut reader, compress, validate)?;
+        let offset = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let supported_size = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let end = offset
+            .checked_add(supported_size)
+            .ok_or(SerializationError::InvalidData)?;
+        if srs.g1_powers.len() < end {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(Self {
+            srs: Arc::new(srs),
+            offset,
+            supported_size,
+        })
+    }
+}
+
+#[serde_as]
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct KZGVerifierKey<P: Pairing> {
+    #[serde_as(as = "Hex")]
     pub g1: P::G1Affine,
+    #[serde_as(as = "Hex")]
     pub g2: P::G2Affine,
+    #[serde_as(as = "Hex")]
     pub beta_g2: P::G2Affine,
 }
 
+impl<P: Pairing> KZGVerifierKey<P> {
+    /// Checks that none of the points is the identity, which would make every pairing check
+    /// involving it trivial.
+    pub fn check_nondegenerate(&self) -> Result<(), KeyError> {
+        if self.g1.is_zero() || self.g2.is_zero() || self.beta_g2.is_zero() {
+            return Err(KeyError::Malformed("verifier key point is the identity"));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct UnivariateKZG<P: Pairing> {
     _phantom: PhantomData<P>,
@@ -134,20 +317,14 @@ where
         poly: &UniPoly<P::ScalarField>,
         offset: usize,
     ) -> Result<P::G1Affine, ProofVerifyError> {
-        if pk.g1_powers().len() < poly.coeffs.len() {
+        if pk.supported_size() < poly.coeffs.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pk.g1_powers().len(),
+                pk.supported_size(),
                 poly.coeffs.len(),
             ));
         }
 
-        let bases = pk.g1_powers();
-        let c = <P::G1 as VariableBaseMSM>::msm(
-            &bases[offset..poly.coeffs.len()],
-            &poly.coeffs[offset..],
-        )
-        .unwrap();
-
+        let c = pk.msm(offset, &poly.coeffs[offset..]);
         Ok(c.into_affine())
     }
 
@@ -156,17 +333,13 @@ where
         pk: &KZGProverKey<P>,
         poly: &UniPoly<P::ScalarField>,
     ) -> Result<P::G1Affine, ProofVerifyError> {
-        if pk.g1_powers().len() < poly.coeffs.len() {
+        if pk.supported_size() < poly.coeffs.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pk.g1_powers().len(),
+                pk.supported_size(),
                 poly.coeffs.len(),
             ));
         }
-        let c = <P::G1 as VariableBaseMSM>::msm(
-            &pk.g1_powers()[..poly.coeffs.len()],
-            poly.coeffs.as_slice(),
-        )
-        .unwrap();
+        let c = pk.msm_auto(poly.coeffs.as_slice());
         Ok(c.into_affine())
     }
 
@@ -175,16 +348,53 @@ where
         pk: &KZGProverKey<P>,
         coeffs: &[P::ScalarField],
     ) -> Result<P::G1Affine, ProofVerifyError> {
-        if pk.g1_powers().len() < coeffs.len() {
+        if pk.supported_size() < coeffs.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pk.g1_powers().len(),
+                pk.supported_size(),
                 coeffs.len(),
             ));
         }
-        let c = <P::G1 as VariableBaseMSM>::msm(&pk.g1_powers()[..coeffs.len()], coeffs).unwrap();
+        let c = pk.msm_auto(coeffs);
         Ok(c.into_affine())
     }
 
+    /// Like [`Self::commit_slice`], but faster if most of `coeffs` are small integers. See
+    /// [`VariableBaseMSM::msm_small_values`](crate::msm::VariableBaseMSM::msm_small_values).
+    #[tracing::instrument(skip_all, name = "KZG::commit_small_values")]
+    pub fn commit_small_values(
+        pk: &KZGProverKey<P>,
+        coeffs: &[P::ScalarField],
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        if pk.supported_size() < coeffs.len() {
+            return Err(ProofVerifyError::KeyLengthError(
+                pk.supported_size(),
+                coeffs.len(),
+            ));
+        }
+        let c = match sparse_support(coeffs) {
+            Some(indices) => pk.sparse_msm(&indices, coeffs),
+            None => pk.msm_small_values(0, coeffs),
+        };
+        Ok(c.into_affine())
+    }
+
+    /// Commits to `coeffs`, which are zero outside of `indices` (as returned by
+    /// [`sparse_support`]), reading only the powers at `indices`.
+    #[tracing::instrument(skip_all, name = "KZG::commit_sparse")]
+    pub fn commit_sparse(
+        pk: &KZGProverKey<P>,
+        coeffs: &[P::ScalarField],
+        indices: &[usize],
+    ) -> Result<P::G1Affine, ProofVerifyError> {
+        if pk.supported_size() < coeffs.len() {
+            return Err(ProofVerifyError::KeyLengthError(
+                pk.supported_size(),
+                coeffs.len(),
+            ));
+        }
+        Ok(pk.sparse_msm(indices, coeffs).into_affine())
+    }
+
     #[tracing::instrument(skip_all, name = "KZG::open")]
     pub fn open(
         pk: &KZGProverKey<P>,
@@ -196,11 +406,7 @@ where
     {
         let divisor = UniPoly::from_coeff(vec![-*point, P::ScalarField::one()]);
         let (witness_poly, _) = poly.divide_with_remainder(&divisor).unwrap();
-        let proof = <P::G1 as VariableBaseMSM>::msm(
-            &pk.g1_powers()[..witness_poly.coeffs.len()],
-            witness_poly.coeffs.as_slice(),
-        )
-        .unwrap();
+        let proof = pk.msm(0, witness_poly.coeffs.as_slice());
         let evaluation = poly.evaluate(point);
         Ok((proof.into_affine(), evaluation))
     }
@@ -0,0 +1,380 @@
+//! Disk-backed SRS.
+//!
+//! A large SRS (e.g. 2^24 G1 powers) takes gigabytes to hold in memory, even though a given
+//! proof may only use a prefix of it. [`SRS::write_mmap`] writes an SRS to a file that
+//! [`SRS::open_mmap`] later memory-maps instead of reading: the G1 powers are only held in memory
+//! (decoded) while an MSM uses them. Trimming a mapped SRS shares the mapping rather than copying
+//! powers out of it.
+//!
+//! File format:
+//!
+//! ```text
+//! [ magic | curve (String) | num_g1_powers: u64 | g1_point_size: u64 | g2_powers (Vec, compressed) |
+//!   g1_powers (uncompressed, back to back) ]
+//! ```
+//!
+//! The curve is recorded as the [`JoltField::NAME`] of its scalar field. Opening a file
+//! validates every G1 power it maps once, so that decoding them later can't fail. This reads
+//! (and subgroup-checks) every mapped power, so opening isn't free: [`SRS::open_mmap`] reads the
+//! whole file, and [`SRS::open_mmap_prefix`] only the powers a smaller setup needs. It is still
+//! much cheaper than generating the powers, and the memory is only held while they are used.
+//! The file must not be modified while it is mapped.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+};
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use super::kzg::SRS;
+use crate::field::JoltField;
+use crate::msm::VariableBaseMSM;
+
+pub const SRS_MAGIC: [u8; 8] = *b"JOLTSRS\0";
+
+/// Number of mapped G1 powers decoded at a time when computing an MSM. Bounds the memory used by
+/// decoded points, at the cost of splitting large MSMs into several smaller ones.
+const MAPPED_MSM_CHUNK_SIZE: usize = 1 << 18;
+
+/// The G1 powers of an [`SRS`]: either held in memory, or memory-mapped from a file written by
+/// [`SRS::write_mmap`].
+#[derive(Clone, Debug)]
+pub enum G1Powers<A: AffineRepr> {
+    InMemory(Vec<A>),
+    Mapped(MappedG1Powers<A>),
+}
+
+#[derive(Clone)]
+pub struct MappedG1Powers<A> {
+    mmap: Arc<Mmap>,
+    /// Byte offset of the first power in `mmap`.
+    data_offset: usize,
+    len: usize,
+    point_size: usize,
+    _marker: PhantomData<A>,
+}
+
+impl<A> fmt::Debug for MappedG1Powers<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedG1Powers")
+            .field("len", &self.len)
+            .field("point_size", &self.point_size)
+            .finish()
+    }
+}
+
+impl<A: AffineRepr> MappedG1Powers<A> {
+    fn point_bytes(&self, index: usize) -> &[u8] {
+        let start = self.data_offset + index * self.point_size;
+        &self.mmap[start..start + self.point_size]
+    }
+
+    /// Checks that every power is a valid point, so that [`Self::decode`] can skip the checks.
+    fn validate(&self) -> Result<(), SerializationError> {
+        (0..self.len).into_par_iter().try_for_each(|i| {
+            A::deserialize_with_mode(self.point_bytes(i), Compress::No, Validate::Yes).map(|_| ())
+        })
+    }
+
+    fn decode(&self, range: Range<usize>) -> Vec<A> {
+        assert!(range.end <= self.len);
+        range
+            .into_par_iter()
+            .map(|i| {
+                A::deserialize_with_mode(self.point_bytes(i), Compress::No, Validate::No)
+                    .expect("mapped G1 powers are validated when the file is opened")
+            })
+            .collect()
+    }
+}
+
+impl<A: AffineRepr> G1Powers<A> {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::InMemory(powers) => powers.len(),
+            Self::Mapped(powers) => powers.len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> A {
+        match self {
+            Self::InMemory(powers) => powers[index],
+            Self::Mapped(powers) => powers.decode(index..index + 1)[0],
+        }
+    }
+
+    /// Computes `sum_i scalars[i] * powers[start + i]`.
+    pub fn msm<G: CurveGroup<Affine = A>>(&self, start: usize, scalars: &[G::ScalarField]) -> G {
+        self.msm_with(start, scalars, <G as VariableBaseMSM>::msm)
+    }
+
+    /// Like [`Self::msm`], using [`VariableBaseMSM::msm_small_values`].
+    pub fn msm_small_values<G: CurveGroup<Affine = A>>(
+        &self,
+        start: usize,
+        scalars: &[G::ScalarField],
+    ) -> G {
+        self.msm_with(start, scalars, <G as VariableBaseMSM>::msm_small_values)
+    }
+
+    fn msm_with<G: CurveGroup<Affine = A>>(
+        &self,
+        start: usize,
+        scalars: &[G::ScalarField],
+        msm: impl Fn(&[A], &[G::ScalarField]) -> Result<G, usize>,
+    ) -> G {
+        match self {
+            Self::InMemory(powers) => msm(&powers[start..start + scalars.len()], scalars).unwrap(),
+            Self::Mapped(powers) => scalars
+                .chunks(MAPPED_MSM_CHUNK_SIZE)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let chunk_start = start + i * MAPPED_MSM_CHUNK_SIZE;
+                    let bases = powers.decode(chunk_start..chunk_start + chunk.len());
+                    msm(&bases, chunk).unwrap()
+                })
+                .sum(),
+        }
+    }
+
+    /// Computes `sum_i scalars[i] * powers[start + i]`, where `scalars` is zero outside of
+    /// `indices`. Only the powers at `indices` are read.
+    pub fn sparse_msm<G: CurveGroup<Affine = A>>(
+        &self,
+        start: usize,
+        indices: &[usize],
+        scalars: &[G::ScalarField],
+    ) -> G {
+        let bases: Vec<A> = indices.par_iter().map(|&i| self.get(start + i)).collect();
+        let scalars: Vec<G::ScalarField> = indices.par_iter().map(|&i| scalars[i]).collect();
+        if bases.is_empty() {
+            return G::zero();
+        }
+        <G as VariableBaseMSM>::msm(&bases, &scalars).unwrap()
+    }
+}
+
+// Serialized exactly like a `Vec<A>`, so mapped and in-memory SRSs are interchangeable.
+impl<A: AffineRepr> CanonicalSerialize for G1Powers<A> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match self {
+            Self::InMemory(powers) => powers.serialize_with_mode(writer, compress),
+            Self::Mapped(powers) => {
+                (powers.len as u64).serialize_with_mode(&mut writer, compress)?;
+                for start in (0..powers.len).step_by(MAPPED_MSM_CHUNK_SIZE) {
+                    let end = (start + MAPPED_MSM_CHUNK_SIZE).min(powers.len);
+                    for point in powers.decode(start..end) {
+                        point.serialize_with_mode(&mut writer, compress)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        match self {
+            Self::InMemory(powers) => powers.serialized_size(compress),
+            Self::Mapped(powers) => {
+                0u64.serialized_size(compress) + powers.len * A::zero().serialized_size(compress)
+            }
+        }
+    }
+}
+
+impl<A: AffineRepr> Valid for G1Powers<A> {
+    fn check(&self) -> Result<(), SerializationError> {
+        match self {
+            Self::InMemory(powers) => powers.check(),
+            // Checked by `SRS::open_mmap`
+            Self::Mapped(_) => Ok(()),
+        }
+    }
+}
+
+impl<A: AffineRepr> CanonicalDeserialize for G1Powers<A> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Vec::<A>::deserialize_with_mode(reader, compress, validate).map(Self::InMemory)
+    }
+}
+
+impl<P: Pairing> SRS<P>
+where
+    P::ScalarField: JoltField,
+{
+    /// Writes the SRS in the format read by [`Self::open_mmap`].
+    pub fn write_mmap<Q: AsRef<Path>>(&self, path: Q) -> Result<(), SerializationError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&SRS_MAGIC)?;
+        <P::ScalarField as JoltField>::NAME
+            .to_string()
+            .serialize_compressed(&mut writer)?;
+        (self.g1_powers.len() as u64).serialize_compressed(&mut writer)?;
+        (g1_point_size::<P>() as u64).serialize_compressed(&mut writer)?;
+        self.g2_powers.serialize_compressed(&mut writer)?;
+        for start in (0..self.g1_powers.len()).step_by(MAPPED_MSM_CHUNK_SIZE) {
+            let end = (start + MAPPED_MSM_CHUNK_SIZE).min(self.g1_powers.len());
+            let chunk = match &self.g1_powers {
+                G1Powers::InMemory(powers) => powers[start..end].to_vec(),
+                G1Powers::Mapped(powers) => powers.decode(start..end),
+            };
+            for point in chunk {
+                point.serialize_uncompressed(&mut writer)?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Memory-maps an SRS written by [`Self::write_mmap`]. The G1 powers are validated, but not
+    /// kept in memory: they are decoded again whenever they are used. Fails with
+    /// [`SerializationError::InvalidData`] if the file is for another curve or holds an invalid
+    /// point.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open_mmap<Q: AsRef<Path>>(path: Q) -> Result<Self, SerializationError> {
+        Self::map(path.as_ref(), usize::MAX, Validate::Yes)
+    }
+
+    /// Like [`Self::open_mmap`], but only maps the first `num_powers` G1 powers (or all of them,
+    /// if the file holds fewer), so that only those are read and validated.
+    pub fn open_mmap_prefix<Q: AsRef<Path>>(
+        path: Q,
+        num_powers: usize,
+    ) -> Result<Self, SerializationError> {
+        Self::map(path.as_ref(), num_powers, Validate::Yes)
+    }
+
+    /// Maps the first `num_powers` G1 powers of the file at `path`. They may only be left
+    /// unvalidated if this process wrote them.
+    pub(super) fn map(
+        path: &Path,
+        num_powers: usize,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let file = File::open(path)?;
+        // Safety: the file is only read, and callers guarantee it isn't concurrently modified.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let mut reader = &mmap[..];
+        let mut magic = [0u8; SRS_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SRS_MAGIC
+            || String::deserialize_compressed(&mut reader)? != <P::ScalarField as JoltField>::NAME
+        {
+            return Err(SerializationError::InvalidData);
+        }
+        let len = u64::deserialize_compressed(&mut reader)? as usize;
+        let point_size = u64::deserialize_compressed(&mut reader)? as usize;
+        if point_size != g1_point_size::<P>() {
+            return Err(SerializationError::InvalidData);
+        }
+        let g2_powers = Vec::<P::G2Affine>::deserialize_compressed(&mut reader)?;
+
+        let data_offset = mmap.len() - reader.len();
+        if len.checked_mul(point_size) != Some(reader.len()) {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let g1_powers = MappedG1Powers {
+            mmap: Arc::new(mmap),
+            data_offset,
+            len: len.min(num_powers),
+            point_size,
+            _marker: PhantomData,
+        };
+        if validate == Validate::Yes {
+            g1_powers
+                .validate()
+                .map_err(|_| SerializationError::InvalidData)?;
+        }
+        Ok(Self {
+            g1_powers: G1Powers::Mapped(g1_powers),
+            g2_powers,
+        })
+    }
+}
+
+fn g1_point_size<P: Pairing>() -> usize {
+    P::G1Affine::zero().uncompressed_size()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::kzg::UnivariateKZG;
+    use crate::poly::unipoly::UniPoly;
+    use ark_bn254::Bn254;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn mmap_srs() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let degree = 100;
+        let srs = Arc::new(SRS::<Bn254>::setup(&mut rng, degree, 2));
+
+        let path = std::env::temp_dir().join(format!("jolt-mmap-srs-{}", std::process::id()));
+        srs.write_mmap(&path).unwrap();
+        let mapped = Arc::new(SRS::<Bn254>::open_mmap(&path).unwrap());
+        assert!(matches!(mapped.g1_powers, G1Powers::Mapped(_)));
+        assert_eq!(mapped.g1_powers.len(), srs.g1_powers.len());
+        assert_eq!(mapped.g2_powers, srs.g2_powers);
+        assert_eq!(mapped.g1_powers.get(degree), srs.g1_powers.get(degree));
+
+        // Mapped and in-memory SRSs serialize identically
+        let mut srs_bytes = vec![];
+        srs.serialize_compressed(&mut srs_bytes).unwrap();
+        let mut mapped_bytes = vec![];
+        mapped.serialize_compressed(&mut mapped_bytes).unwrap();
+        assert_eq!(srs_bytes, mapped_bytes);
+        assert_eq!(mapped.compressed_size(), srs_bytes.len());
+
+        let (pk, _) = SRS::trim(srs.clone(), degree);
+        let (mapped_pk, _) = SRS::trim(mapped, degree);
+        let poly = UniPoly::random::<ChaCha20Rng>(degree, &mut rng);
+        assert_eq!(
+            UnivariateKZG::commit(&mapped_pk, &poly).unwrap(),
+            UnivariateKZG::commit(&pk, &poly).unwrap()
+        );
+
+        // Wrong curve
+        assert!(SRS::<ark_bls12_381::Bls12_381>::open_mmap(&path).is_err());
+
+        // A point that isn't on the curve is rejected when opening, rather than when it's used
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last - 8] ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            SRS::<Bn254>::open_mmap(&path),
+            Err(SerializationError::InvalidData)
+        ));
+        // ... but only if it's mapped
+        let prefix = SRS::<Bn254>::open_mmap_prefix(&path, degree).unwrap();
+        assert_eq!(prefix.g1_powers.len(), degree);
+        assert_eq!(prefix.g1_powers.get(1), srs.g1_powers.get(1));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
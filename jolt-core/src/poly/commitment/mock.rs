@@ -1,12 +1,15 @@
 use std::marker::PhantomData;
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
 use crate::{
     field::JoltField,
     poly::dense_mlpoly::DensePolynomial,
     utils::{
         errors::ProofVerifyError,
+        json::Hex,
         transcript::{AppendToTranscript, ProofTranscript},
     },
 };
@@ -29,12 +32,16 @@ impl<F: JoltField> AppendToTranscript for MockCommitment<F> {
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct MockProof<F: JoltField> {
+    #[serde_as(as = "Vec<Hex>")]
     opening_point: Vec<F>,
 }
 
 impl<F: JoltField> CommitmentScheme for MockCommitScheme<F> {
+    const NAME: &'static str = "Mock";
     type Field = F;
     type Setup = ();
     type Commitment = MockCommitment<F>;
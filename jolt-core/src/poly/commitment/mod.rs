@@ -1,8 +1,11 @@
+#[cfg(feature = "binius")]
 pub mod binius;
 pub mod commitment_scheme;
 pub mod hyperkzg;
 pub mod hyrax;
+pub mod key_file;
 pub mod kzg;
+pub mod mmap_srs;
 pub mod pedersen;
 pub mod zeromorph;
 
@@ -1,4 +1,3 @@
-use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
@@ -6,14 +5,14 @@ use sha3::digest::{ExtendableOutput, Update};
 use sha3::Shake256;
 use std::io::Read;
 
-use crate::msm::VariableBaseMSM;
+use crate::curve::JoltCurve;
 
 #[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
-pub struct PedersenGenerators<G: CurveGroup> {
+pub struct PedersenGenerators<G: JoltCurve> {
     pub generators: Vec<G>,
 }
 
-impl<G: CurveGroup> PedersenGenerators<G> {
+impl<G: JoltCurve> PedersenGenerators<G> {
     #[tracing::instrument(skip_all, name = "PedersenGenerators::new")]
     pub fn new(len: usize, label: &[u8]) -> Self {
         let mut shake = Shake256::default();
@@ -29,7 +28,7 @@ impl<G: CurveGroup> PedersenGenerators<G> {
 
         let mut generators: Vec<G> = Vec::new();
         for _ in 0..len {
-            generators.push(G::rand(&mut rng));
+            generators.push(G::random(&mut rng));
         }
 
         Self { generators }
@@ -49,12 +48,12 @@ impl<G: CurveGroup> PedersenGenerators<G> {
     }
 }
 
-pub trait PedersenCommitment<G: CurveGroup>: Sized {
+pub trait PedersenCommitment<G: JoltCurve>: Sized {
     fn commit(&self, gens: &PedersenGenerators<G>) -> G;
     fn commit_vector(inputs: &[Self], bases: &[G::Affine]) -> G;
 }
 
-impl<G: CurveGroup> PedersenCommitment<G> for G::ScalarField {
+impl<G: JoltCurve> PedersenCommitment<G> for G::Scalar {
     #[tracing::instrument(skip_all, name = "PedersenCommitment::commit")]
     fn commit(&self, gens: &PedersenGenerators<G>) -> G {
         assert_eq!(gens.generators.len(), 1);
@@ -63,6 +62,6 @@ impl<G: CurveGroup> PedersenCommitment<G> for G::ScalarField {
 
     fn commit_vector(inputs: &[Self], bases: &[G::Affine]) -> G {
         assert_eq!(bases.len(), inputs.len());
-        VariableBaseMSM::msm(bases, inputs).unwrap()
+        G::msm(bases, inputs).unwrap()
     }
 }
@@ -1,11 +1,12 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
 
-use std::{iter, marker::PhantomData};
+use std::{iter, marker::PhantomData, path::Path};
 
 use crate::field;
 use crate::msm::VariableBaseMSM;
 use crate::poly::{dense_mlpoly::DensePolynomial, unipoly::UniPoly};
+use crate::utils::json::Hex;
 use crate::utils::mul_0_1_optimized;
 use crate::utils::thread::unsafe_allocate_zero_vec;
 use crate::utils::{
@@ -14,18 +15,20 @@ use crate::utils::{
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::{batch_inversion, Field};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{One, Zero};
 use itertools::izip;
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::sync::Arc;
 use tracing::trace_span;
 
 use rayon::prelude::*;
 
 use super::{
-    commitment_scheme::{BatchType, CommitShape, CommitmentScheme},
+    commitment_scheme::{AdditivelyHomomorphic, BatchType, CommitShape, CommitmentScheme},
     kzg::{KZGProverKey, KZGVerifierKey, UnivariateKZG, SRS},
 };
 
@@ -36,6 +39,22 @@ impl<P: Pairing> ZeromorphSRS<P> {
         Self(Arc::new(SRS::setup(rng, max_degree, max_degree)))
     }
 
+    /// Memory-maps an SRS saved with [`Self::write_mmap`], validating its G1 powers and then
+    /// paging them in on demand. See [`mmap_srs`](super::mmap_srs).
+    pub fn open_mmap<Q: AsRef<Path>>(path: Q) -> Result<Self, SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        Ok(Self(Arc::new(SRS::open_mmap(path)?)))
+    }
+
+    pub fn write_mmap<Q: AsRef<Path>>(&self, path: Q) -> Result<(), SerializationError>
+    where
+        <P as Pairing>::ScalarField: field::JoltField,
+    {
+        self.0.write_mmap(path)
+    }
+
     pub fn trim(self, max_degree: usize) -> (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>) {
         let (commit_pp, kzg_vk) = SRS::trim(self.0.clone(), max_degree);
         let offset = self.0.g1_powers.len() - max_degree;
@@ -52,20 +71,20 @@ impl<P: Pairing> ZeromorphSRS<P> {
 }
 
 //TODO: adapt interface to have prover and verifier key
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphProverKey<P: Pairing> {
     pub commit_pp: KZGProverKey<P>,
     pub open_pp: KZGProverKey<P>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct ZeromorphVerifierKey<P: Pairing> {
     pub kzg_vk: KZGVerifierKey<P>,
     pub tau_N_max_sub_2_N: P::G2Affine,
 }
 
 #[derive(Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
-pub struct ZeromorphCommitment<P: Pairing>(P::G1Affine);
+pub struct ZeromorphCommitment<P: Pairing>(pub P::G1Affine);
 
 impl<P: Pairing> Default for ZeromorphCommitment<P> {
     fn default() -> Self {
@@ -73,16 +92,41 @@ impl<P: Pairing> Default for ZeromorphCommitment<P> {
     }
 }
 
+impl<P: Pairing> AdditivelyHomomorphic for ZeromorphCommitment<P>
+where
+    <P as Pairing>::ScalarField: field::JoltField,
+{
+    type Scalar = P::ScalarField;
+
+    fn combine(commitments: &[&Self], scalars: &[Self::Scalar]) -> Self {
+        assert_eq!(commitments.len(), scalars.len());
+        if commitments.is_empty() {
+            return Self::default();
+        }
+        let bases: Vec<P::G1Affine> = commitments.iter().map(|commitment| commitment.0).collect();
+        Self(
+            <P::G1 as VariableBaseMSM>::msm(&bases, scalars)
+                .unwrap()
+                .into_affine(),
+        )
+    }
+}
+
 impl<P: Pairing> AppendToTranscript for ZeromorphCommitment<P> {
     fn append_to_transcript(&self, transcript: &mut ProofTranscript) {
         transcript.append_point(&self.0.into_group());
     }
 }
 
-#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Debug)]
+#[serde_as]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize, Debug)]
+#[serde(bound = "")]
 pub struct ZeromorphProof<P: Pairing> {
+    #[serde_as(as = "Hex")]
     pub pi: P::G1Affine,
+    #[serde_as(as = "Hex")]
     pub q_hat_com: P::G1Affine,
+    #[serde_as(as = "Vec<Hex>")]
     pub q_k_com: Vec<P::G1Affine>,
 }
 
@@ -238,9 +282,9 @@ where
         pp: &ZeromorphProverKey<P>,
         poly: &DensePolynomial<P::ScalarField>,
     ) -> Result<ZeromorphCommitment<P>, ProofVerifyError> {
-        if pp.commit_pp.g1_powers().len() < poly.Z.len() {
+        if pp.commit_pp.supported_size() < poly.Z.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pp.commit_pp.g1_powers().len(),
+                pp.commit_pp.supported_size(),
                 poly.Z.len(),
             ));
         }
@@ -260,9 +304,9 @@ where
     ) -> Result<ZeromorphProof<P>, ProofVerifyError> {
         transcript.append_protocol_name(Self::protocol_name());
 
-        if pp.commit_pp.g1_powers().len() < poly.Z.len() {
+        if pp.commit_pp.supported_size() < poly.Z.len() {
             return Err(ProofVerifyError::KeyLengthError(
-                pp.commit_pp.g1_powers().len(),
+                pp.commit_pp.supported_size(),
                 poly.Z.len(),
             ));
         }
@@ -478,6 +522,7 @@ impl<P: Pairing> CommitmentScheme for Zeromorph<P>
 where
     <P as Pairing>::ScalarField: field::JoltField,
 {
+    const NAME: &'static str = "Zeromorph";
     type Field = P::ScalarField;
     type Setup = (ZeromorphProverKey<P>, ZeromorphVerifierKey<P>);
     type Commitment = ZeromorphCommitment<P>;
@@ -495,11 +540,21 @@ where
         .trim(max_len)
     }
 
+    fn srs_size(setup: &Self::Setup) -> usize {
+        setup.0.commit_pp.supported_size() - 1
+    }
+
+    fn verifier_key_bytes(setup: &Self::Setup) -> Vec<u8> {
+        let mut bytes = vec![];
+        setup.1.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
     fn commit(poly: &DensePolynomial<Self::Field>, setup: &Self::Setup) -> Self::Commitment {
         assert!(
-            setup.0.commit_pp.g1_powers().len() > poly.Z.len(),
+            setup.0.commit_pp.supported_size() > poly.Z.len(),
             "COMMIT KEY LENGTH ERROR {}, {}",
-            setup.0.commit_pp.g1_powers().len(),
+            setup.0.commit_pp.supported_size(),
             poly.Z.len()
         );
         ZeromorphCommitment(
@@ -518,9 +573,9 @@ where
             .par_iter()
             .map(|evals| {
                 assert!(
-                    gens.0.commit_pp.g1_powers().len() > evals.len(),
+                    gens.0.commit_pp.supported_size() > evals.len(),
                     "COMMIT KEY LENGTH ERROR {}, {}",
-                    gens.0.commit_pp.g1_powers().len(),
+                    gens.0.commit_pp.supported_size(),
                     evals.len()
                 );
                 ZeromorphCommitment(
@@ -563,12 +618,7 @@ where
         commitments: &[&Self::Commitment],
         coeffs: &[Self::Field],
     ) -> Self::Commitment {
-        let combined_commitment: P::G1 = commitments
-            .iter()
-            .zip(coeffs.iter())
-            .map(|(commitment, coeff)| commitment.0 * coeff)
-            .sum();
-        ZeromorphCommitment(combined_commitment.into_affine())
+        AdditivelyHomomorphic::combine(commitments, coeffs)
     }
 
     fn verify(
@@ -989,4 +1039,57 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn combine_commitments() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let ell = 4;
+        let n = 1 << ell;
+        let srs = ZeromorphSRS::<Bn254>::setup(&mut rng, n);
+        let (pk, vk) = srs.trim(n);
+
+        let polys: Vec<DensePolynomial<Fr>> = (0..3)
+            .map(|_| DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect()))
+            .collect();
+        let scalars: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let commitments: Vec<_> = polys
+            .iter()
+            .map(|poly| Zeromorph::<Bn254>::commit(&pk, poly).unwrap())
+            .collect();
+        let commitment_refs: Vec<_> = commitments.iter().collect();
+
+        let poly_refs: Vec<_> = polys.iter().collect();
+        let combined_poly = DensePolynomial::linear_combination(&poly_refs, &scalars);
+        assert_eq!(
+            ZeromorphCommitment::<Bn254>::combine(&commitment_refs, &scalars),
+            Zeromorph::<Bn254>::commit(&pk, &combined_poly).unwrap()
+        );
+
+        // The folded claim can be checked with a single opening of the combined polynomial
+        let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let openings: Vec<Fr> = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+        let (combined_commitment, combined_opening) =
+            ZeromorphCommitment::<Bn254>::fold(&commitment_refs, &openings, &scalars);
+        assert_eq!(combined_opening, combined_poly.evaluate(&point));
+
+        let mut prover_transcript = ProofTranscript::new(b"TestEval");
+        let proof = Zeromorph::<Bn254>::open(
+            &pk,
+            &combined_poly,
+            &point,
+            &combined_opening,
+            &mut prover_transcript,
+        )
+        .unwrap();
+        let mut verifier_transcript = ProofTranscript::new(b"TestEval");
+        assert!(Zeromorph::<Bn254>::verify(
+            &vk,
+            &combined_commitment,
+            &point,
+            &combined_opening,
+            &proof,
+            &mut verifier_transcript,
+        )
+        .is_ok());
+    }
 }
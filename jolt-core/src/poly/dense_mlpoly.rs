@@ -282,6 +282,25 @@ impl<F: JoltField> DensePolynomial<F> {
     }
 
     #[tracing::instrument(skip_all, name = "DensePolynomial::from")]
+    /// Returns `sum_i scalars[i] * polys[i]`, zero-padding shorter polynomials to the length of
+    /// the longest one.
+    pub fn linear_combination(polys: &[&DensePolynomial<F>], scalars: &[F]) -> Self {
+        assert_eq!(polys.len(), scalars.len());
+        let len = polys.iter().map(|poly| poly.len()).max().unwrap_or(1);
+        let evals: Vec<F> = (0..len)
+            .into_par_iter()
+            .map(|i| {
+                polys
+                    .iter()
+                    .zip(scalars.iter())
+                    .filter(|(poly, _)| i < poly.len())
+                    .map(|(poly, scalar)| poly.Z[i] * scalar)
+                    .sum()
+            })
+            .collect();
+        Self::new(evals)
+    }
+
     pub fn from_usize(Z: &[usize]) -> Self {
         DensePolynomial::new(
             (0..Z.len())
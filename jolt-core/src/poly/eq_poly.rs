@@ -14,6 +14,15 @@ impl<F: JoltField> EqPolynomial<F> {
         EqPolynomial { r }
     }
 
+    pub fn num_vars(&self) -> usize {
+        self.r.len()
+    }
+
+    /// The point `r` that this polynomial is EQ(x, r) for.
+    pub fn point(&self) -> &[F] {
+        &self.r
+    }
+
     pub fn evaluate(&self, rx: &[F]) -> F {
         assert_eq!(self.r.len(), rx.len());
         (0..rx.len())
@@ -11,6 +11,10 @@ impl IdentityPolynomial {
         IdentityPolynomial { size_point }
     }
 
+    pub fn num_vars(&self) -> usize {
+        self.size_point
+    }
+
     pub fn evaluate<F: JoltField>(&self, r: &[F]) -> F {
         let len = r.len();
         assert_eq!(len, self.size_point);
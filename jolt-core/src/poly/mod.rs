@@ -4,3 +4,4 @@ pub mod eq_poly;
 pub mod identity_poly;
 pub mod opening_proof;
 pub mod unipoly;
+pub mod virtual_poly;
@@ -5,22 +5,26 @@
 //! necessarily of the same size, each opened at a different point) into a single opening.
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
 use crate::{
     field::{JoltField, OptimizedMul},
     subprotocols::sumcheck::SumcheckInstanceProof,
     utils::{
         errors::ProofVerifyError,
+        json::Hex,
         thread::unsafe_allocate_zero_vec,
         transcript::{AppendToTranscript, ProofTranscript},
     },
 };
 
 use super::{
-    commitment::commitment_scheme::CommitmentScheme,
+    commitment::commitment_scheme::{AdditivelyHomomorphic, CommitmentScheme},
     dense_mlpoly::DensePolynomial,
     eq_poly::EqPolynomial,
     unipoly::{CompressedUniPoly, UniPoly},
+    virtual_poly::VirtualPolynomial,
 };
 
 /// An opening computed by the prover.
@@ -110,13 +114,23 @@ pub struct VerifierOpeningAccumulator<F: JoltField, PCS: CommitmentScheme<Field
     pcs_setup: Option<PCS::Setup>,
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct ReducedOpeningProof<F: JoltField, PCS: CommitmentScheme<Field = F>> {
     sumcheck_proof: SumcheckInstanceProof<F>,
+    #[serde_as(as = "Vec<Hex>")]
     sumcheck_claims: Vec<F>,
     joint_opening_proof: PCS::Proof,
 }
 
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>> ReducedOpeningProof<F, PCS> {
+    /// The PCS proof of the single opening the claims were reduced to.
+    pub fn pcs_proof(&self) -> &PCS::Proof {
+        &self.joint_opening_proof
+    }
+}
+
 impl<F: JoltField> Default for ProverOpeningAccumulator<F> {
     fn default() -> Self {
         Self::new()
@@ -132,6 +146,12 @@ impl<F: JoltField> ProverOpeningAccumulator<F> {
         self.openings.len()
     }
 
+    /// Appends the openings accumulated by a single proof stage, which must be joined in the
+    /// same order as on the verifier side.
+    pub fn join(&mut self, stage: Self) {
+        self.openings.extend(stage.openings);
+    }
+
     /// Adds openings to the accumulator. The given `polynomials` are opened at
     /// `opening_point`, yielding the claimed evaluations `claims`. `eq_poly` is
     /// the multilinear extension EQ(x, opening_point), which is typically an
@@ -215,6 +235,28 @@ impl<F: JoltField> ProverOpeningAccumulator<F> {
         }
     }
 
+    /// The prover side of [`VerifierOpeningAccumulator::append_virtual`]. Virtual polynomials
+    /// are evaluated by the verifier itself, so nothing is accumulated or appended to the
+    /// transcript; in tests, the claims are checked against the polynomials.
+    pub fn append_virtual(
+        &mut self,
+        polynomials: &[&dyn VirtualPolynomial<F>],
+        opening_point: &[F],
+        claims: &[&F],
+    ) {
+        assert_eq!(polynomials.len(), claims.len());
+        #[cfg(test)]
+        for (poly, claim) in polynomials.iter().zip(claims.iter()) {
+            assert_eq!(
+                poly.to_dense().evaluate(opening_point),
+                **claim,
+                "Unexpected virtual claim"
+            );
+        }
+        #[cfg(not(test))]
+        let _ = opening_point;
+    }
+
     /// Reduces the multiple openings accumulated into a single opening proof,
     /// using a single sumcheck.
     #[tracing::instrument(skip_all, name = "ProverOpeningAccumulator::reduce_and_prove")]
@@ -471,6 +513,32 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> VerifierOpeningAccumulator<
         self.openings.len()
     }
 
+    /// Returns an empty accumulator for the openings of a single proof stage, to be appended
+    /// to this one with [`Self::join`] before the next stage is forked.
+    pub fn fork(&mut self) -> Self {
+        #[cfg(not(test))]
+        {
+            Self::new()
+        }
+        #[cfg(test)]
+        {
+            // Hand the stage the prover openings it is expected to reproduce
+            let start = self.openings.len().min(self.prover_openings.len());
+            Self {
+                openings: vec![],
+                prover_openings: self.prover_openings.split_off(start),
+                pcs_setup: self.pcs_setup.clone(),
+            }
+        }
+    }
+
+    /// Appends the openings accumulated by a stage returned by [`Self::fork`].
+    pub fn join(&mut self, stage: Self) {
+        self.openings.extend(stage.openings);
+        #[cfg(test)]
+        self.prover_openings.extend(stage.prover_openings);
+    }
+
     /// Adds openings to the accumulator. The polynomials underlying the given
     /// `commitments` are opened at `opening_point`, yielding the claimed evaluations
     /// `claims`.
@@ -501,8 +569,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> VerifierOpeningAccumulator<
         let joint_commitment = PCS::combine_commitments(commitments, &rho_powers);
 
         #[cfg(test)]
-        {
-            let prover_opening = &self.prover_openings[self.openings.len()];
+        if let Some(prover_opening) = self.prover_openings.get(self.openings.len()) {
             assert_eq!(
                 prover_opening.batch.len(),
                 commitments.len(),
@@ -544,6 +611,30 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> VerifierOpeningAccumulator<
         ));
     }
 
+    /// Checks the claimed evaluations `claims` of virtual polynomials at `opening_point`, in
+    /// place of [`Self::append`]ing openings of committed polynomials. Nothing is accumulated,
+    /// so these polynomials need no commitments and add nothing to the reduced opening proof.
+    pub fn append_virtual(
+        &mut self,
+        polynomials: &[&dyn VirtualPolynomial<F>],
+        opening_point: &[F],
+        claims: &[&F],
+    ) -> Result<(), ProofVerifyError> {
+        assert_eq!(polynomials.len(), claims.len());
+        for (poly, claim) in polynomials.iter().zip(claims.iter()) {
+            if poly.num_vars() != opening_point.len() {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    poly.num_vars(),
+                    opening_point.len(),
+                ));
+            }
+            if poly.evaluate(opening_point) != **claim {
+                return Err(ProofVerifyError::InternalError);
+            }
+        }
+        Ok(())
+    }
+
     /// Verifies that the given `reduced_opening_proof` (consisting of a sumcheck proof
     /// and a single opening proof) indeed proves the openings accumulated.
     pub fn reduce_and_verify(
@@ -660,3 +751,271 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> VerifierOpeningAccumulator<
         sumcheck_proof.verify(combined_claim, num_sumcheck_rounds, 2, transcript)
     }
 }
+
+/// Groups the indices of `opening_points` by point, preserving the order in which each distinct
+/// point first appears.
+fn group_by_opening_point<F: JoltField>(opening_points: &[&[F]]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (i, point) in opening_points.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|group| opening_points[group[0]] == *point)
+        {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+    groups
+}
+
+/// Proves the openings of each of `polynomials` at the corresponding point in `opening_points`
+/// (which need not be distinct) with a single [`ReducedOpeningProof`]. Polynomials opened at the
+/// same point are first batched by random linear combination; the resulting openings are then
+/// reduced to one using the sumcheck above.
+pub fn prove_multi_point_opening<F: JoltField, PCS: CommitmentScheme<Field = F>>(
+    setup: &PCS::Setup,
+    polynomials: &[&DensePolynomial<F>],
+    opening_points: &[&[F]],
+    openings: &[F],
+    transcript: &mut ProofTranscript,
+) -> ReducedOpeningProof<F, PCS>
+where
+    PCS::Commitment: AdditivelyHomomorphic<Scalar = F>,
+{
+    assert_eq!(polynomials.len(), opening_points.len());
+    assert_eq!(polynomials.len(), openings.len());
+
+    let mut accumulator = ProverOpeningAccumulator::new();
+    for group in group_by_opening_point(opening_points) {
+        let opening_point = opening_points[group[0]].to_vec();
+        let polys: Vec<_> = group.iter().map(|&i| polynomials[i]).collect();
+        let claims: Vec<_> = group.iter().map(|&i| &openings[i]).collect();
+        accumulator.append(
+            &polys,
+            DensePolynomial::new(EqPolynomial::evals(&opening_point)),
+            opening_point,
+            &claims,
+            transcript,
+        );
+    }
+    accumulator.reduce_and_prove::<PCS>(setup, transcript)
+}
+
+/// Verifies a proof produced by [`prove_multi_point_opening`].
+pub fn verify_multi_point_opening<F: JoltField, PCS: CommitmentScheme<Field = F>>(
+    setup: &PCS::Setup,
+    proof: ReducedOpeningProof<F, PCS>,
+    commitments: &[&PCS::Commitment],
+    opening_points: &[&[F]],
+    openings: &[F],
+    transcript: &mut ProofTranscript,
+) -> Result<(), ProofVerifyError>
+where
+    PCS::Commitment: AdditivelyHomomorphic<Scalar = F>,
+{
+    if commitments.len() != opening_points.len() {
+        return Err(ProofVerifyError::InvalidInputLength(
+            commitments.len(),
+            opening_points.len(),
+        ));
+    }
+    if commitments.len() != openings.len() {
+        return Err(ProofVerifyError::InvalidInputLength(
+            commitments.len(),
+            openings.len(),
+        ));
+    }
+
+    let mut accumulator: VerifierOpeningAccumulator<F, PCS> = VerifierOpeningAccumulator::new();
+    for group in group_by_opening_point(opening_points) {
+        let group_commitments: Vec<_> = group.iter().map(|&i| commitments[i]).collect();
+        let claims: Vec<_> = group.iter().map(|&i| &openings[i]).collect();
+        accumulator.append(
+            &group_commitments,
+            opening_points[group[0]].to_vec(),
+            &claims,
+            transcript,
+        );
+    }
+    accumulator.reduce_and_verify(setup, proof, transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::commitment::commitment_scheme::{BatchType, CommitShape};
+    use crate::poly::commitment::hyperkzg::HyperKZG;
+    use crate::poly::commitment::zeromorph::Zeromorph;
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_std::test_rng;
+
+    fn multi_point_opening<F: JoltField, PCS: CommitmentScheme<Field = F>>()
+    where
+        PCS::Commitment: AdditivelyHomomorphic<Scalar = F>,
+    {
+        let mut rng = test_rng();
+        let num_vars = 10;
+        let polynomials: Vec<_> = (0..4)
+            .map(|i| {
+                // Polynomials of different sizes
+                let len = 1 << (num_vars - i % 2);
+                DensePolynomial::new((0..len).map(|_| F::random(&mut rng)).collect())
+            })
+            .collect();
+        let points: Vec<Vec<F>> = polynomials
+            .iter()
+            .map(|poly| {
+                (0..poly.get_num_vars())
+                    .map(|_| F::random(&mut rng))
+                    .collect()
+            })
+            .collect();
+        // The first and third polynomials are opened at the same point
+        let opening_points: Vec<&[F]> = vec![&points[0], &points[1], &points[0], &points[3]];
+        let openings: Vec<F> = polynomials
+            .iter()
+            .zip(opening_points.iter())
+            .map(|(poly, point)| poly.evaluate(point))
+            .collect();
+
+        let setup = PCS::setup(&[CommitShape::new(1 << num_vars, BatchType::Small)]);
+        let commitments: Vec<_> = polynomials
+            .iter()
+            .map(|poly| PCS::commit(poly, &setup))
+            .collect();
+        let polynomials: Vec<_> = polynomials.iter().collect();
+        let commitments: Vec<_> = commitments.iter().collect();
+
+        let mut prover_transcript = ProofTranscript::new(b"test_multi_point");
+        let proof = PCS::batch_prove_multi_point(
+            &setup,
+            &polynomials,
+            &opening_points,
+            &openings,
+            &mut prover_transcript,
+        );
+        let mut proof_bytes = vec![];
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+
+        let mut verifier_transcript = ProofTranscript::new(b"test_multi_point");
+        assert!(PCS::batch_verify_multi_point(
+            proof,
+            &setup,
+            &opening_points,
+            &openings,
+            &commitments,
+            &mut verifier_transcript,
+        )
+        .is_ok());
+
+        let mut bad_openings = openings.clone();
+        bad_openings[3] += F::one();
+        let mut verifier_transcript = ProofTranscript::new(b"test_multi_point");
+        assert!(PCS::batch_verify_multi_point(
+            ReducedOpeningProof::deserialize_compressed(proof_bytes.as_slice()).unwrap(),
+            &setup,
+            &opening_points,
+            &bad_openings,
+            &commitments,
+            &mut verifier_transcript,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn multi_point_opening_hyperkzg() {
+        multi_point_opening::<ark_bn254::Fr, HyperKZG<Bn254>>();
+    }
+
+    #[test]
+    fn multi_point_opening_zeromorph() {
+        multi_point_opening::<ark_bn254::Fr, Zeromorph<Bn254>>();
+    }
+
+    #[test]
+    fn multi_point_opening_hyperkzg_bls12_381() {
+        multi_point_opening::<ark_bls12_381::Fr, HyperKZG<Bls12_381>>();
+    }
+
+    #[test]
+    fn multi_point_opening_zeromorph_bls12_381() {
+        multi_point_opening::<ark_bls12_381::Fr, Zeromorph<Bls12_381>>();
+    }
+
+    #[test]
+    fn virtual_openings_are_checked() {
+        use crate::poly::virtual_poly::PrefixPolynomial;
+        use ark_bn254::Fr;
+        use ark_std::One;
+
+        let mut rng = test_rng();
+        let point: Vec<Fr> = (0..6).map(|_| Fr::random(&mut rng)).collect();
+        let prefix = PrefixPolynomial::new(6, 45);
+        let claim = prefix.evaluate(&point);
+
+        let mut accumulator = VerifierOpeningAccumulator::<Fr, HyperKZG<Bn254>>::new();
+        assert!(accumulator
+            .append_virtual(&[&prefix], &point, &[&claim])
+            .is_ok());
+        assert!(accumulator
+            .append_virtual(&[&prefix], &point, &[&(claim + Fr::one())])
+            .is_err());
+        assert!(accumulator
+            .append_virtual(&[&prefix], &point[1..], &[&claim])
+            .is_err());
+        assert_eq!(accumulator.len(), 0);
+    }
+
+    #[test]
+    fn virtual_openings_shrink_proof() {
+        use crate::poly::virtual_poly::PrefixPolynomial;
+        use ark_bn254::Fr;
+
+        let num_vars = 6;
+        let mut rng = test_rng();
+        let witness =
+            DensePolynomial::new((0..1 << num_vars).map(|_| Fr::random(&mut rng)).collect());
+        let r_witness: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let r_prefix: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let prefix = PrefixPolynomial::new(num_vars, 45);
+        let prefix_column: DensePolynomial<Fr> = prefix.to_dense();
+        let setup = HyperKZG::<Bn254>::setup(&[CommitShape::new(1 << num_vars, BatchType::Small)]);
+
+        // Size of the commitments and the reduced opening proof, with the prefix column opened
+        // either as a committed column or as a virtual one
+        let proof_size = |is_virtual: bool| -> usize {
+            let mut transcript = ProofTranscript::new(b"test_virtual");
+            let mut accumulator = ProverOpeningAccumulator::new();
+            let mut commitments = vec![<HyperKZG<Bn254> as CommitmentScheme>::commit(
+                &witness, &setup,
+            )];
+            accumulator.append(
+                &[&witness],
+                DensePolynomial::new(EqPolynomial::evals(&r_witness)),
+                r_witness.clone(),
+                &[&witness.evaluate(&r_witness)],
+                &mut transcript,
+            );
+            let prefix_claim = prefix.evaluate(&r_prefix);
+            if is_virtual {
+                accumulator.append_virtual(&[&prefix], &r_prefix, &[&prefix_claim]);
+            } else {
+                commitments.push(<HyperKZG<Bn254> as CommitmentScheme>::commit(
+                    &prefix_column,
+                    &setup,
+                ));
+                accumulator.append(
+                    &[&prefix_column],
+                    DensePolynomial::new(EqPolynomial::evals(&r_prefix)),
+                    r_prefix.clone(),
+                    &[&prefix_claim],
+                    &mut transcript,
+                );
+            }
+            let proof = accumulator.reduce_and_prove::<HyperKZG<Bn254>>(&setup, &mut transcript);
+            commitments.compressed_size() + proof.compressed_size()
+        };
+        assert!(proof_size(true) < proof_size(false));
+    }
+}
@@ -0,0 +1,139 @@
+//! Virtual columns: witness columns that are fixed by public parameters alone (counters,
+//! selectors derived from `eq`, padding indicators), so the verifier can evaluate their
+//! multilinear extensions at any point in time logarithmic in their size.
+//!
+//! Such a column is never committed to or included in the batched opening proof. The prover
+//! still materializes it with [`VirtualPolynomial::evals`] to run its sumchecks, and where a
+//! committed column's opening would be appended to the opening accumulators, both parties call
+//! `append_virtual` instead: the verifier checks the claimed evaluation against
+//! [`VirtualPolynomial::evaluate`], and nothing is added to the transcript or to the proof.
+
+use crate::field::JoltField;
+use crate::utils::math::Math;
+
+use super::{
+    dense_mlpoly::DensePolynomial, eq_poly::EqPolynomial, identity_poly::IdentityPolynomial,
+};
+
+/// A column whose multilinear extension the verifier evaluates directly, in place of an opening
+/// proof. Variables are ordered as in [`DensePolynomial`], most significant first.
+pub trait VirtualPolynomial<F: JoltField>: Sync {
+    /// The number of variables of the multilinear extension.
+    fn num_vars(&self) -> usize;
+    /// Evaluates the multilinear extension at `r`, without materializing the column.
+    fn evaluate(&self, r: &[F]) -> F;
+    /// The column itself, i.e. the evaluations over the boolean hypercube.
+    fn evals(&self) -> Vec<F>;
+
+    /// The column as a polynomial, for the prover's sumchecks.
+    fn to_dense(&self) -> DensePolynomial<F> {
+        DensePolynomial::new(self.evals())
+    }
+}
+
+impl<F: JoltField> VirtualPolynomial<F> for IdentityPolynomial {
+    fn num_vars(&self) -> usize {
+        self.num_vars()
+    }
+
+    fn evaluate(&self, r: &[F]) -> F {
+        IdentityPolynomial::evaluate(self, r)
+    }
+
+    fn evals(&self) -> Vec<F> {
+        (0..self.num_vars().pow2())
+            .map(|i| F::from_u64(i as u64).unwrap())
+            .collect()
+    }
+}
+
+impl<F: JoltField> VirtualPolynomial<F> for EqPolynomial<F> {
+    fn num_vars(&self) -> usize {
+        self.num_vars()
+    }
+
+    fn evaluate(&self, r: &[F]) -> F {
+        EqPolynomial::evaluate(self, r)
+    }
+
+    fn evals(&self) -> Vec<F> {
+        EqPolynomial::evals(self.point())
+    }
+}
+
+/// The indicator of the first `len` of `2^num_vars` rows, e.g. the rows of a trace before it is
+/// padded to a power of two.
+pub struct PrefixPolynomial {
+    num_vars: usize,
+    len: usize,
+}
+
+impl PrefixPolynomial {
+    pub fn new(num_vars: usize, len: usize) -> Self {
+        assert!(len <= num_vars.pow2());
+        PrefixPolynomial { num_vars, len }
+    }
+}
+
+impl<F: JoltField> VirtualPolynomial<F> for PrefixPolynomial {
+    fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    fn evaluate(&self, r: &[F]) -> F {
+        assert_eq!(r.len(), self.num_vars);
+        if self.len == self.num_vars.pow2() {
+            return F::one();
+        }
+        // Row i < len iff, at the first bit where i and len differ, i has a 0 and len a 1. Sum
+        // eq(prefix of len) * (1 - r_j) over the 1 bits j of len.
+        let mut eval = F::zero();
+        let mut eq_prefix = F::one();
+        for (j, r_j) in r.iter().enumerate() {
+            if (self.len >> (self.num_vars - 1 - j)) & 1 == 1 {
+                eval += eq_prefix * (F::one() - *r_j);
+                eq_prefix *= *r_j;
+            } else {
+                eq_prefix *= F::one() - *r_j;
+            }
+        }
+        eval
+    }
+
+    fn evals(&self) -> Vec<F> {
+        let mut evals = vec![F::zero(); self.num_vars.pow2()];
+        evals[..self.len].fill(F::one());
+        evals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::{test_rng, UniformRand};
+
+    fn assert_consistent(poly: &impl VirtualPolynomial<Fr>) {
+        let mut rng = test_rng();
+        let dense = poly.to_dense();
+        assert_eq!(dense.get_num_vars(), poly.num_vars());
+        for _ in 0..4 {
+            let r: Vec<Fr> = (0..poly.num_vars()).map(|_| Fr::rand(&mut rng)).collect();
+            assert_eq!(poly.evaluate(&r), dense.evaluate(&r));
+        }
+    }
+
+    #[test]
+    fn evaluations_match_columns() {
+        let mut rng = test_rng();
+        for num_vars in 1..8 {
+            assert_consistent(&IdentityPolynomial::new(num_vars));
+            let point: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut rng)).collect();
+            assert_consistent(&EqPolynomial::new(point));
+            let size = num_vars.pow2();
+            for len in [0, 1, size / 2 + 1, size - 1, size] {
+                assert_consistent(&PrefixPolynomial::new(num_vars, len));
+            }
+        }
+    }
+}
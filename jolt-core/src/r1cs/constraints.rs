@@ -1,4 +1,5 @@
 use common::{constants::RAM_OPS_PER_INSTRUCTION, rv_trace::CircuitFlags};
+use std::marker::PhantomData;
 use strum::IntoEnumIterator;
 
 use crate::{
@@ -10,14 +11,14 @@ use crate::{
             sub::SUBInstruction, virtual_move::MOVEInstruction,
             virtual_movsign::MOVSIGNInstruction,
         },
-        vm::rv32i_vm::RV32I,
+        vm::rv32i_vm::{RV32IInstructionSet, RV32I},
     },
 };
 
 use super::{
     builder::{CombinedUniformBuilder, OffsetEqConstraint, R1CSBuilder},
     inputs::{AuxVariable, ConstraintInput, JoltR1CSInputs},
-    ops::Variable,
+    ops::{Variable, LC},
 };
 
 pub const PC_START_ADDRESS: i64 = 0x80000000;
@@ -54,152 +55,157 @@ pub trait R1CSConstraints<const C: usize, F: JoltField> {
     fn non_uniform_constraints() -> Vec<OffsetEqConstraint>;
 }
 
-pub struct JoltRV32IMConstraints;
-impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstraints {
-    type Inputs = JoltR1CSInputs;
+/// Jolt's constraints for RV32IM, over any instruction set extending [`RV32I`].
+pub struct JoltRV32IMConstraints<I = RV32I>(PhantomData<I>);
+impl<const C: usize, F: JoltField, I: RV32IInstructionSet> R1CSConstraints<C, F>
+    for JoltRV32IMConstraints<I>
+{
+    type Inputs = JoltR1CSInputs<I>;
 
     fn uniform_constraints(cs: &mut R1CSBuilder<C, F, Self::Inputs>, memory_start: u64) {
-        for flag in RV32I::iter() {
-            cs.constrain_binary(JoltR1CSInputs::InstructionFlags(flag));
+        for flag in I::iter() {
+            cs.constrain_binary(JoltR1CSInputs::<I>::InstructionFlags(flag));
         }
         for flag in CircuitFlags::iter() {
-            cs.constrain_binary(JoltR1CSInputs::OpFlags(flag));
+            cs.constrain_binary(JoltR1CSInputs::<I>::OpFlags(flag));
         }
 
         let flags = CircuitFlags::iter()
-            .map(|flag| JoltR1CSInputs::OpFlags(flag).into())
-            .chain(RV32I::iter().map(|flag| JoltR1CSInputs::InstructionFlags(flag).into()))
+            .map(|flag| JoltR1CSInputs::<I>::OpFlags(flag).into())
+            .chain(I::iter().map(|flag| JoltR1CSInputs::<I>::InstructionFlags(flag).into()))
             .collect();
-        cs.constrain_pack_be(flags, JoltR1CSInputs::Bytecode_Bitflags, 1);
+        cs.constrain_pack_be(flags, JoltR1CSInputs::<I>::Bytecode_Bitflags, 1);
 
         let real_pc =
-            4i64 * JoltR1CSInputs::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
+            4i64 * JoltR1CSInputs::<I>::Bytecode_ELFAddress + (PC_START_ADDRESS - PC_NOOP_SHIFT);
         let x = cs.allocate_if_else(
-            JoltR1CSInputs::Aux(AuxVariable::LeftLookupOperand),
-            JoltR1CSInputs::OpFlags(CircuitFlags::LeftOperandIsPC),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::LeftLookupOperand),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::LeftOperandIsPC),
             real_pc,
-            JoltR1CSInputs::RS1_Read,
+            JoltR1CSInputs::<I>::RS1_Read,
         );
         let y = cs.allocate_if_else(
-            JoltR1CSInputs::Aux(AuxVariable::RightLookupOperand),
-            JoltR1CSInputs::OpFlags(CircuitFlags::RightOperandIsImm),
-            JoltR1CSInputs::Bytecode_Imm,
-            JoltR1CSInputs::RS2_Read,
+            JoltR1CSInputs::<I>::Aux(AuxVariable::RightLookupOperand),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::RightOperandIsImm),
+            JoltR1CSInputs::<I>::Bytecode_Imm,
+            JoltR1CSInputs::<I>::RS2_Read,
         );
 
         // Converts from unsigned to twos-complement representation
-        let signed_output = JoltR1CSInputs::Bytecode_Imm - (0xffffffffi64 + 1i64);
+        let signed_output = JoltR1CSInputs::<I>::Bytecode_Imm - (0xffffffffi64 + 1i64);
         let imm_signed = cs.allocate_if_else(
-            JoltR1CSInputs::Aux(AuxVariable::ImmSigned),
-            JoltR1CSInputs::OpFlags(CircuitFlags::ImmSignBit),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::ImmSigned),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::ImmSignBit),
             signed_output,
-            JoltR1CSInputs::Bytecode_Imm,
+            JoltR1CSInputs::<I>::Bytecode_Imm,
         );
 
-        let is_load_or_store = JoltR1CSInputs::OpFlags(CircuitFlags::Load)
-            + JoltR1CSInputs::OpFlags(CircuitFlags::Store);
+        let is_load_or_store = JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Load)
+            + JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Store);
         let memory_start: i64 = memory_start.try_into().unwrap();
         cs.constrain_eq_conditional(
             is_load_or_store,
-            JoltR1CSInputs::RS1_Read + imm_signed,
-            JoltR1CSInputs::RAM_A + memory_start,
+            JoltR1CSInputs::<I>::RS1_Read + imm_signed,
+            JoltR1CSInputs::<I>::RAM_A + memory_start,
         );
 
         for i in 0..RAM_OPS_PER_INSTRUCTION {
             cs.constrain_eq_conditional(
-                JoltR1CSInputs::OpFlags(CircuitFlags::Load),
-                JoltR1CSInputs::RAM_Read(i),
-                JoltR1CSInputs::RAM_Write(i),
+                JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Load),
+                JoltR1CSInputs::<I>::RAM_Read(i),
+                JoltR1CSInputs::<I>::RAM_Write(i),
             );
         }
 
         let ram_writes = (0..RAM_OPS_PER_INSTRUCTION)
-            .map(|i| Variable::Input(JoltR1CSInputs::RAM_Write(i).to_index::<C>()))
+            .map(|i| Variable::Input(JoltR1CSInputs::<I>::RAM_Write(i).to_index::<C>()))
             .collect();
-        let packed_load_store = R1CSBuilder::<C, F, JoltR1CSInputs>::pack_le(ram_writes, 8);
+        let packed_load_store = R1CSBuilder::<C, F, JoltR1CSInputs<I>>::pack_le(ram_writes, 8);
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::Store),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Store),
             packed_load_store.clone(),
-            JoltR1CSInputs::LookupOutput,
+            JoltR1CSInputs::<I>::LookupOutput,
         );
 
         let query_chunks: Vec<Variable> = (0..C)
-            .map(|i| Variable::Input(JoltR1CSInputs::ChunksQuery(i).to_index::<C>()))
+            .map(|i| Variable::Input(JoltR1CSInputs::<I>::ChunksQuery(i).to_index::<C>()))
             .collect();
         let packed_query =
-            R1CSBuilder::<C, F, JoltR1CSInputs>::pack_be(query_chunks.clone(), LOG_M);
+            R1CSBuilder::<C, F, JoltR1CSInputs<I>>::pack_be(query_chunks.clone(), LOG_M);
 
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::InstructionFlags(ADDInstruction::default().into()),
+            JoltR1CSInputs::<I>::InstructionFlags(ADDInstruction::default().into()),
             packed_query.clone(),
             x + y,
         );
         // Converts from unsigned to twos-complement representation
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::InstructionFlags(SUBInstruction::default().into()),
+            JoltR1CSInputs::<I>::InstructionFlags(SUBInstruction::default().into()),
             packed_query.clone(),
             x - y + (0xffffffffi64 + 1),
         );
-        let is_mul = JoltR1CSInputs::InstructionFlags(MULInstruction::default().into())
-            + JoltR1CSInputs::InstructionFlags(MULUInstruction::default().into())
-            + JoltR1CSInputs::InstructionFlags(MULHUInstruction::default().into());
-        let product = cs.allocate_prod(JoltR1CSInputs::Aux(AuxVariable::Product), x, y);
+        let is_mul = JoltR1CSInputs::<I>::InstructionFlags(MULInstruction::default().into())
+            + JoltR1CSInputs::<I>::InstructionFlags(MULUInstruction::default().into())
+            + JoltR1CSInputs::<I>::InstructionFlags(MULHUInstruction::default().into());
+        let product = cs.allocate_prod(JoltR1CSInputs::<I>::Aux(AuxVariable::Product), x, y);
         cs.constrain_eq_conditional(is_mul, packed_query.clone(), product);
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::InstructionFlags(MOVSIGNInstruction::default().into())
-                + JoltR1CSInputs::InstructionFlags(MOVEInstruction::default().into()),
+            JoltR1CSInputs::<I>::InstructionFlags(MOVSIGNInstruction::default().into())
+                + JoltR1CSInputs::<I>::InstructionFlags(MOVEInstruction::default().into()),
             packed_query.clone(),
             x,
         );
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::Load),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Load),
             packed_query.clone(),
             packed_load_store,
         );
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::Store),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Store),
             packed_query,
-            JoltR1CSInputs::RS2_Read,
+            JoltR1CSInputs::<I>::RS2_Read,
         );
 
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::Assert),
-            JoltR1CSInputs::LookupOutput,
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Assert),
+            JoltR1CSInputs::<I>::LookupOutput,
             1,
         );
 
         let x_chunks: Vec<Variable> = (0..C)
-            .map(|i| Variable::Input(JoltR1CSInputs::ChunksX(i).to_index::<C>()))
+            .map(|i| Variable::Input(JoltR1CSInputs::<I>::ChunksX(i).to_index::<C>()))
             .collect();
         let y_chunks: Vec<Variable> = (0..C)
-            .map(|i| Variable::Input(JoltR1CSInputs::ChunksY(i).to_index::<C>()))
+            .map(|i| Variable::Input(JoltR1CSInputs::<I>::ChunksY(i).to_index::<C>()))
             .collect();
-        let x_concat = R1CSBuilder::<C, F, JoltR1CSInputs>::pack_be(x_chunks.clone(), OPERAND_SIZE);
-        let y_concat = R1CSBuilder::<C, F, JoltR1CSInputs>::pack_be(y_chunks.clone(), OPERAND_SIZE);
+        let x_concat =
+            R1CSBuilder::<C, F, JoltR1CSInputs<I>>::pack_be(x_chunks.clone(), OPERAND_SIZE);
+        let y_concat =
+            R1CSBuilder::<C, F, JoltR1CSInputs<I>>::pack_be(y_chunks.clone(), OPERAND_SIZE);
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
             x_concat,
             x,
         );
         cs.constrain_eq_conditional(
-            JoltR1CSInputs::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
             y_concat,
             y,
         );
 
         // if is_shift ? chunks_query[i] == zip(chunks_x[i], chunks_y[C-1]) : chunks_query[i] == zip(chunks_x[i], chunks_y[i])
-        let is_shift = JoltR1CSInputs::InstructionFlags(SLLInstruction::default().into())
-            + JoltR1CSInputs::InstructionFlags(SRLInstruction::default().into())
-            + JoltR1CSInputs::InstructionFlags(SRAInstruction::default().into());
+        let is_shift = JoltR1CSInputs::<I>::InstructionFlags(SLLInstruction::default().into())
+            + JoltR1CSInputs::<I>::InstructionFlags(SRLInstruction::default().into())
+            + JoltR1CSInputs::<I>::InstructionFlags(SRAInstruction::default().into());
         for i in 0..C {
             let relevant_chunk_y = cs.allocate_if_else(
-                JoltR1CSInputs::Aux(AuxVariable::RelevantYChunk(i)),
+                JoltR1CSInputs::<I>::Aux(AuxVariable::RelevantYChunk(i)),
                 is_shift.clone(),
                 y_chunks[C - 1],
                 y_chunks[i],
             );
             cs.constrain_eq_conditional(
-                JoltR1CSInputs::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
+                JoltR1CSInputs::<I>::OpFlags(CircuitFlags::ConcatLookupQueryChunks),
                 query_chunks[i],
                 x_chunks[i] * (1i64 << 8) + relevant_chunk_y,
             );
@@ -208,53 +214,77 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         // if (rd != 0 && update_rd_with_lookup_output == 1) constrain(rd_val == LookupOutput)
         // if (rd != 0 && is_jump_instr == 1) constrain(rd_val == 4 * PC)
         let rd_nonzero_and_lookup_to_rd = cs.allocate_prod(
-            JoltR1CSInputs::Aux(AuxVariable::WriteLookupOutputToRD),
-            JoltR1CSInputs::Bytecode_RD,
-            JoltR1CSInputs::OpFlags(CircuitFlags::WriteLookupOutputToRD),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::WriteLookupOutputToRD),
+            JoltR1CSInputs::<I>::Bytecode_RD,
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::WriteLookupOutputToRD),
         );
         cs.constrain_eq_conditional(
             rd_nonzero_and_lookup_to_rd,
-            JoltR1CSInputs::RD_Write,
-            JoltR1CSInputs::LookupOutput,
+            JoltR1CSInputs::<I>::RD_Write,
+            JoltR1CSInputs::<I>::LookupOutput,
         );
         let rd_nonzero_and_jmp = cs.allocate_prod(
-            JoltR1CSInputs::Aux(AuxVariable::WritePCtoRD),
-            JoltR1CSInputs::Bytecode_RD,
-            JoltR1CSInputs::OpFlags(CircuitFlags::Jump),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::WritePCtoRD),
+            JoltR1CSInputs::<I>::Bytecode_RD,
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Jump),
         );
-        let lhs = 4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS; // TODO(moodlezoup): is this right?
-        let rhs = JoltR1CSInputs::RD_Write;
+        let lhs = 4 * JoltR1CSInputs::<I>::Bytecode_ELFAddress + PC_START_ADDRESS; // TODO(moodlezoup): is this right?
+        let rhs = JoltR1CSInputs::<I>::RD_Write;
         cs.constrain_eq_conditional(rd_nonzero_and_jmp, lhs, rhs);
 
         let next_pc_jump = cs.allocate_if_else(
-            JoltR1CSInputs::Aux(AuxVariable::NextPCJump),
-            JoltR1CSInputs::OpFlags(CircuitFlags::Jump),
-            JoltR1CSInputs::LookupOutput + 4,
-            4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS + 4
-                - 4 * JoltR1CSInputs::OpFlags(CircuitFlags::DoNotUpdatePC),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::NextPCJump),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Jump),
+            JoltR1CSInputs::<I>::LookupOutput + 4,
+            4 * JoltR1CSInputs::<I>::Bytecode_ELFAddress + PC_START_ADDRESS + 4
+                - 4 * JoltR1CSInputs::<I>::OpFlags(CircuitFlags::DoNotUpdatePC),
         );
 
         let should_branch = cs.allocate_prod(
-            JoltR1CSInputs::Aux(AuxVariable::ShouldBranch),
-            JoltR1CSInputs::OpFlags(CircuitFlags::Branch),
-            JoltR1CSInputs::LookupOutput,
+            JoltR1CSInputs::<I>::Aux(AuxVariable::ShouldBranch),
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Branch),
+            JoltR1CSInputs::<I>::LookupOutput,
         );
         let _next_pc = cs.allocate_if_else(
-            JoltR1CSInputs::Aux(AuxVariable::NextPC),
+            JoltR1CSInputs::<I>::Aux(AuxVariable::NextPC),
             should_branch,
-            4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS + imm_signed,
+            4 * JoltR1CSInputs::<I>::Bytecode_ELFAddress + PC_START_ADDRESS + imm_signed,
             next_pc_jump,
         );
+
+        // Only steps of the execution can be in the middle of a virtual sequence, so that the
+        // padding doesn't affect the remaining cycles.
+        cs.constrain_eq_conditional(
+            JoltR1CSInputs::<I>::OpFlags(CircuitFlags::DoNotUpdatePC),
+            JoltR1CSInputs::<I>::RealStep,
+            1,
+        );
+
+        // The padding is the no-op at the start of the bytecode, so that the steps after the
+        // claimed trace length can't execute any instructions (whose PC updates aren't checked).
+        // Its flags are zero through `Bytecode_Bitflags`.
+        let is_padding = LC::from(1) - JoltR1CSInputs::<I>::RealStep;
+        for input in [
+            JoltR1CSInputs::<I>::Bytecode_ELFAddress,
+            JoltR1CSInputs::<I>::Bytecode_Bitflags,
+            JoltR1CSInputs::<I>::Bytecode_RD,
+            JoltR1CSInputs::<I>::Bytecode_RS1,
+            JoltR1CSInputs::<I>::Bytecode_RS2,
+            JoltR1CSInputs::<I>::Bytecode_Imm,
+        ] {
+            cs.constrain_eq_conditional(is_padding.clone(), input, 0);
+        }
     }
 
     fn non_uniform_constraints() -> Vec<OffsetEqConstraint> {
-        // If the next instruction's ELF address is not zero (i.e. it's
-        // not padding), then check the PC update.
+        // If the next step is part of the execution (i.e. it's not padding),
+        // then check the PC update. In particular, the execution can't be cut
+        // short with no-ops, whose PC precedes the program.
         let pc_constraint = OffsetEqConstraint::new(
-            (JoltR1CSInputs::Bytecode_ELFAddress, true),
-            (JoltR1CSInputs::Aux(AuxVariable::NextPC), false),
+            (JoltR1CSInputs::<I>::RealStep, true),
+            (JoltR1CSInputs::<I>::Aux(AuxVariable::NextPC), false),
             (
-                4 * JoltR1CSInputs::Bytecode_ELFAddress + PC_START_ADDRESS,
+                4 * JoltR1CSInputs::<I>::Bytecode_ELFAddress + PC_START_ADDRESS,
                 true,
             ),
         );
@@ -267,11 +297,30 @@ impl<const C: usize, F: JoltField> R1CSConstraints<C, F> for JoltRV32IMConstrain
         // trace should always end with some return handling, which shouldn't involve
         // any virtual sequences.
         let virtual_sequence_constraint = OffsetEqConstraint::new(
-            (JoltR1CSInputs::OpFlags(CircuitFlags::Virtual), false),
-            (JoltR1CSInputs::Bytecode_A, true),
-            (JoltR1CSInputs::Bytecode_A + 1, false),
+            (JoltR1CSInputs::<I>::OpFlags(CircuitFlags::Virtual), false),
+            (JoltR1CSInputs::<I>::Bytecode_A, true),
+            (JoltR1CSInputs::<I>::Bytecode_A + 1, false),
+        );
+
+        // Counts down the remaining cycles from each step to the end of the trace, where a
+        // virtual sequence is a single cycle and the padding isn't counted. The next step's
+        // count is zero at the last step, so the first step's count is the number of cycles
+        // executed.
+        let remaining_cycles_constraint = OffsetEqConstraint::new(
+            (1i64, false),
+            (JoltR1CSInputs::<I>::RemainingCycles, true),
+            (
+                JoltR1CSInputs::<I>::RemainingCycles
+                    + JoltR1CSInputs::<I>::OpFlags(CircuitFlags::DoNotUpdatePC)
+                    - JoltR1CSInputs::<I>::RealStep,
+                false,
+            ),
         );
 
-        vec![pc_constraint, virtual_sequence_constraint]
+        vec![
+            pc_constraint,
+            virtual_sequence_constraint,
+            remaining_cycles_constraint,
+        ]
     }
 }
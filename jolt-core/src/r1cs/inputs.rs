@@ -7,12 +7,17 @@
 use crate::impl_r1cs_input_lc_conversions;
 use crate::jolt::instruction::JoltInstructionSet;
 use crate::jolt::vm::rv32i_vm::RV32I;
+use crate::jolt::vm::witness::par_columns;
 use crate::jolt::vm::{JoltCommitments, JoltStuff, JoltTraceStep};
-use crate::lasso::memory_checking::{Initializable, StructuredPolynomialData};
+use crate::lasso::memory_checking::{
+    Initializable, StructuredPolynomialData, VerifierComputedOpening,
+};
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::opening_proof::VerifierOpeningAccumulator;
-use crate::utils::thread::unsafe_allocate_zero_vec;
+use crate::poly::virtual_poly::{PrefixPolynomial, VirtualPolynomial};
+use crate::utils::json::Hex;
+use crate::utils::math::Math;
 use crate::utils::transcript::ProofTranscript;
 
 use super::key::UniformSpartanKey;
@@ -23,22 +28,36 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::log2;
 use common::constants::RAM_OPS_PER_INSTRUCTION;
 use common::rv_trace::{CircuitFlags, NUM_CIRCUIT_FLAGS};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::fmt::Debug;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 /// Auxiliary variables defined in Jolt's R1CS constraints.
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct AuxVariableStuff<T: CanonicalSerialize + CanonicalDeserialize> {
+    #[serde_as(as = "Hex")]
     pub left_lookup_operand: T,
+    #[serde_as(as = "Hex")]
     pub right_lookup_operand: T,
+    #[serde_as(as = "Hex")]
     pub imm_signed: T,
+    #[serde_as(as = "Hex")]
     pub product: T,
+    #[serde_as(as = "Vec<Hex>")]
     pub relevant_y_chunks: Vec<T>,
+    #[serde_as(as = "Hex")]
     pub write_lookup_output_to_rd: T,
+    #[serde_as(as = "Hex")]
     pub write_pc_to_rd: T,
+    #[serde_as(as = "Hex")]
     pub next_pc_jump: T,
+    #[serde_as(as = "Hex")]
     pub should_branch: T,
+    #[serde_as(as = "Hex")]
     pub next_pc: T,
 }
 
@@ -93,11 +112,24 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T>
     }
 }
 
-#[derive(Default, CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(Default, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct R1CSStuff<T: CanonicalSerialize + CanonicalDeserialize> {
+    #[serde_as(as = "Vec<Hex>")]
     pub chunks_x: Vec<T>,
+    #[serde_as(as = "Vec<Hex>")]
     pub chunks_y: Vec<T>,
+    #[serde_as(as = "[Hex; NUM_CIRCUIT_FLAGS]")]
     pub circuit_flags: [T; NUM_CIRCUIT_FLAGS],
+    /// The number of steps from each step to the end of the trace, not counting the steps in
+    /// the middle of a virtual sequence or the padding. See [`crate::jolt::vm::halt`].
+    #[serde_as(as = "Hex")]
+    pub remaining_cycles: T,
+    /// 1 at the steps of the execution and 0 at the padding, i.e. a [`PrefixPolynomial`]. It's
+    /// virtual, so it's neither committed nor opened.
+    #[serde_as(as = "Option<Hex>")]
+    pub real_step: VerifierComputedOpening<T>,
     pub aux: AuxVariableStuff<T>,
 }
 
@@ -109,6 +141,8 @@ impl<T: CanonicalSerialize + CanonicalDeserialize + Default> Initializable<T, us
             chunks_x: std::iter::repeat_with(|| T::default()).take(*C).collect(),
             chunks_y: std::iter::repeat_with(|| T::default()).take(*C).collect(),
             circuit_flags: std::array::from_fn(|_| T::default()),
+            remaining_cycles: T::default(),
+            real_step: None,
             aux: AuxVariableStuff::initialize(C),
         }
     }
@@ -120,6 +154,7 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T> f
             .iter()
             .chain(self.chunks_y.iter())
             .chain(self.circuit_flags.iter())
+            .chain([&self.remaining_cycles])
             .chain(self.aux.read_write_values())
             .collect()
     }
@@ -129,6 +164,7 @@ impl<T: CanonicalSerialize + CanonicalDeserialize> StructuredPolynomialData<T> f
             .iter_mut()
             .chain(self.chunks_y.iter_mut())
             .chain(self.circuit_flags.iter_mut())
+            .chain([&mut self.remaining_cycles])
             .chain(self.aux.read_write_values_mut())
             .collect()
     }
@@ -167,45 +203,47 @@ impl<F: JoltField> R1CSPolynomials<F> {
         I: ConstraintInput,
     >(
         trace: &[JoltTraceStep<InstructionSet>],
+        trace_length: usize,
     ) -> Self {
         let log_M = log2(M) as usize;
 
-        let mut chunks_x = vec![unsafe_allocate_zero_vec(trace.len()); C];
-        let mut chunks_y = vec![unsafe_allocate_zero_vec(trace.len()); C];
-        let mut circuit_flags = vec![unsafe_allocate_zero_vec(trace.len()); NUM_CIRCUIT_FLAGS];
-
-        // TODO(moodlezoup): Can be parallelized
-        for (step_index, step) in trace.iter().enumerate() {
-            if let Some(instr) = &step.instruction_lookup {
-                let (x, y) = instr.operand_chunks(C, log_M);
-                for i in 0..C {
-                    chunks_x[i][step_index] = F::from_u64(x[i]).unwrap();
-                    chunks_y[i][step_index] = F::from_u64(y[i]).unwrap();
+        // Columns are laid out as C chunks of x, C chunks of y, then the circuit flags.
+        let mut columns = par_columns(trace.len(), 2 * C + NUM_CIRCUIT_FLAGS, |steps, columns| {
+            for step in &trace[steps] {
+                let (x, y) = match &step.instruction_lookup {
+                    Some(instr) => instr.operand_chunks(C, log_M),
+                    None => (vec![0; C], vec![0; C]),
+                };
+                for (column, value) in columns.iter_mut().zip(x.into_iter().chain(y)) {
+                    column.push(F::from_u64(value).unwrap());
+                }
+                for (column, flag) in columns[2 * C..].iter_mut().zip(step.circuit_flags) {
+                    column.push(if flag { F::one() } else { F::zero() });
                 }
             }
+        })
+        .into_iter()
+        .map(DensePolynomial::new);
 
-            for j in 0..NUM_CIRCUIT_FLAGS {
-                if step.circuit_flags[j] {
-                    circuit_flags[j][step_index] = F::one();
-                }
+        let mut remaining_cycles = vec![F::zero(); trace.len()];
+        let mut count = 0;
+        for (step, remaining) in trace[..trace_length]
+            .iter()
+            .zip(remaining_cycles[..trace_length].iter_mut())
+            .rev()
+        {
+            if !step.circuit_flags[CircuitFlags::DoNotUpdatePC as usize] {
+                count += 1;
             }
+            *remaining = F::from_u64(count).unwrap();
         }
 
         Self {
-            chunks_x: chunks_x
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
-                .collect(),
-            chunks_y: chunks_y
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
-                .collect(),
-            circuit_flags: circuit_flags
-                .into_iter()
-                .map(|vals| DensePolynomial::new(vals))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
+            chunks_x: columns.by_ref().take(C).collect(),
+            chunks_y: columns.by_ref().take(C).collect(),
+            circuit_flags: columns.collect::<Vec<_>>().try_into().unwrap(),
+            remaining_cycles: DensePolynomial::new(remaining_cycles),
+            real_step: Some(PrefixPolynomial::new(trace.len().log_2(), trace_length).to_dense()),
             // Actual aux variable polynomials will be computed afterwards
             aux: AuxVariableStuff::initialize(&C),
         }
@@ -223,11 +261,17 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> R1CSProof<C, I, F> {
     pub fn verify<PCS: CommitmentScheme<Field = F>>(
         &self,
         commitments: &JoltCommitments<PCS>,
+        trace_length: usize,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), SpartanError> {
-        self.proof
-            .verify(&self.key, commitments, opening_accumulator, transcript)
+        self.proof.verify(
+            &self.key,
+            commitments,
+            trace_length,
+            opening_accumulator,
+            transcript,
+        )
     }
 }
 
@@ -261,6 +305,16 @@ pub trait ConstraintInput: Clone + Copy + Debug + PartialEq + Sync + Send + 'sta
         }
     }
 
+    /// The column of this input in an execution of `trace_length` steps if the input is virtual
+    /// (see [`crate::poly::virtual_poly`]), i.e. evaluated by the verifier instead of opened.
+    /// Virtual inputs have no commitments.
+    fn virtual_polynomial<F: JoltField>(
+        &self,
+        _trace_length: usize,
+    ) -> Option<Box<dyn VirtualPolynomial<F>>> {
+        None
+    }
+
     /// Gets an immutable reference to a Jolt polynomial/commitment/opening
     /// corresponding to the given constraint input.
     fn get_ref<'a, T: CanonicalSerialize + CanonicalDeserialize + Sync>(
@@ -278,7 +332,7 @@ pub trait ConstraintInput: Clone + Copy + Debug + PartialEq + Sync + Send + 'sta
 
 #[allow(non_camel_case_types)]
 #[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
-pub enum JoltR1CSInputs {
+pub enum JoltR1CSInputs<I: JoltInstructionSet = RV32I> {
     Bytecode_A, // Virtual address
     // Bytecode_V
     Bytecode_ELFAddress,
@@ -301,9 +355,11 @@ pub enum JoltR1CSInputs {
     LookupOutput,
     ChunksX(usize),
     ChunksY(usize),
+    RemainingCycles,
+    RealStep,
 
     OpFlags(CircuitFlags),
-    InstructionFlags(RV32I),
+    InstructionFlags(I),
     Aux(AuxVariable),
 }
 
@@ -322,10 +378,10 @@ pub enum AuxVariable {
     NextPC,
 }
 
-impl_r1cs_input_lc_conversions!(JoltR1CSInputs, 4);
-impl ConstraintInput for JoltR1CSInputs {
+impl_r1cs_input_lc_conversions!(<I: JoltInstructionSet> JoltR1CSInputs<I>, 4);
+impl<I: JoltInstructionSet> ConstraintInput for JoltR1CSInputs<I> {
     fn flatten<const C: usize>() -> Vec<Self> {
-        JoltR1CSInputs::iter()
+        Self::iter()
             .flat_map(|variant| match variant {
                 Self::RAM_Read(_) => (0..RAM_OPS_PER_INSTRUCTION).map(Self::RAM_Read).collect(),
                 Self::RAM_Write(_) => (0..RAM_OPS_PER_INSTRUCTION).map(Self::RAM_Write).collect(),
@@ -333,7 +389,7 @@ impl ConstraintInput for JoltR1CSInputs {
                 Self::ChunksX(_) => (0..C).map(Self::ChunksX).collect(),
                 Self::ChunksY(_) => (0..C).map(Self::ChunksY).collect(),
                 Self::OpFlags(_) => CircuitFlags::iter().map(Self::OpFlags).collect(),
-                Self::InstructionFlags(_) => RV32I::iter().map(Self::InstructionFlags).collect(),
+                Self::InstructionFlags(_) => I::iter().map(Self::InstructionFlags).collect(),
                 Self::Aux(_) => AuxVariable::iter()
                     .flat_map(|aux| match aux {
                         AuxVariable::RelevantYChunk(_) => (0..C)
@@ -347,6 +403,19 @@ impl ConstraintInput for JoltR1CSInputs {
             .collect()
     }
 
+    fn virtual_polynomial<F: JoltField>(
+        &self,
+        trace_length: usize,
+    ) -> Option<Box<dyn VirtualPolynomial<F>>> {
+        match self {
+            JoltR1CSInputs::RealStep => Some(Box::new(PrefixPolynomial::new(
+                trace_length.next_power_of_two().log_2(),
+                trace_length,
+            ))),
+            _ => None,
+        }
+    }
+
     fn get_ref<'a, T: CanonicalSerialize + CanonicalDeserialize + Sync>(
         &self,
         jolt: &'a JoltStuff<T>,
@@ -371,9 +440,15 @@ impl ConstraintInput for JoltR1CSInputs {
             JoltR1CSInputs::LookupOutput => &jolt.instruction_lookups.lookup_outputs,
             JoltR1CSInputs::ChunksX(i) => &jolt.r1cs.chunks_x[*i],
             JoltR1CSInputs::ChunksY(i) => &jolt.r1cs.chunks_y[*i],
+            JoltR1CSInputs::RemainingCycles => &jolt.r1cs.remaining_cycles,
+            JoltR1CSInputs::RealStep => jolt
+                .r1cs
+                .real_step
+                .as_ref()
+                .expect("virtual inputs only have polynomials"),
             JoltR1CSInputs::OpFlags(i) => &jolt.r1cs.circuit_flags[*i as usize],
             JoltR1CSInputs::InstructionFlags(i) => {
-                &jolt.instruction_lookups.instruction_flags[RV32I::enum_index(i)]
+                &jolt.instruction_lookups.instruction_flags[I::enum_index(i)]
             }
             Self::Aux(aux) => match aux {
                 AuxVariable::LeftLookupOperand => &aux_polynomials.left_lookup_operand,
@@ -426,13 +501,16 @@ mod tests {
     #[test]
     fn from_index_to_index() {
         const C: usize = 4;
-        for i in 0..JoltR1CSInputs::num_inputs::<C>() {
-            assert_eq!(i, JoltR1CSInputs::from_index::<C>(i).to_index::<C>());
+        for i in 0..JoltR1CSInputs::<RV32I>::num_inputs::<C>() {
+            assert_eq!(
+                i,
+                JoltR1CSInputs::<RV32I>::from_index::<C>(i).to_index::<C>()
+            );
         }
-        for var in JoltR1CSInputs::flatten::<C>() {
+        for var in JoltR1CSInputs::<RV32I>::flatten::<C>() {
             assert_eq!(
                 var,
-                JoltR1CSInputs::from_index::<C>(JoltR1CSInputs::to_index::<C>(&var))
+                JoltR1CSInputs::<RV32I>::from_index::<C>(JoltR1CSInputs::to_index::<C>(&var))
             );
         }
     }
@@ -446,9 +524,9 @@ mod tests {
         for aux in AuxVariable::iter().flat_map(|aux| match aux {
             AuxVariable::RelevantYChunk(_) => (0..C)
                 .into_iter()
-                .map(|i| JoltR1CSInputs::Aux(AuxVariable::RelevantYChunk(i)))
+                .map(|i| JoltR1CSInputs::<RV32I>::Aux(AuxVariable::RelevantYChunk(i)))
                 .collect(),
-            _ => vec![JoltR1CSInputs::Aux(aux)],
+            _ => vec![JoltR1CSInputs::<RV32I>::Aux(aux)],
         }) {
             let ref_ptr = aux.get_ref(&jolt_polys) as *const DensePolynomial<Fr>;
             let ref_mut_ptr = aux.get_ref_mut(&mut jolt_polys) as *const DensePolynomial<Fr>;
@@ -328,17 +328,20 @@ impl std::ops::Mul<Variable> for i64 {
     }
 }
 
-/// Conversions and arithmetic for concrete ConstraintInput
+/// Conversions and arithmetic for concrete ConstraintInput. Generic inputs can be passed as e.g.
+/// `impl_r1cs_input_lc_conversions!(<I: Bound> Input<I>, 4)`.
 #[macro_export]
 macro_rules! impl_r1cs_input_lc_conversions {
-    ($ConcreteInput:ty, $C:expr) => {
-        impl Into<$crate::r1cs::ops::Variable> for $ConcreteInput {
+    // A type can itself start with `<`, so the generics can't be an optional prefix of a type in
+    // one rule
+    (<$($G:ident: $Bound:path),*> $ConcreteInput:ty, $C:expr) => {
+        impl<$($G: $Bound),*> Into<$crate::r1cs::ops::Variable> for $ConcreteInput {
             fn into(self) -> $crate::r1cs::ops::Variable {
                 $crate::r1cs::ops::Variable::Input(self.to_index::<$C>())
             }
         }
 
-        impl Into<$crate::r1cs::ops::Term> for $ConcreteInput {
+        impl<$($G: $Bound),*> Into<$crate::r1cs::ops::Term> for $ConcreteInput {
             fn into(self) -> $crate::r1cs::ops::Term {
                 $crate::r1cs::ops::Term(
                     $crate::r1cs::ops::Variable::Input(self.to_index::<$C>()),
@@ -347,7 +350,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl Into<$crate::r1cs::ops::LC> for $ConcreteInput {
+        impl<$($G: $Bound),*> Into<$crate::r1cs::ops::LC> for $ConcreteInput {
             fn into(self) -> $crate::r1cs::ops::LC {
                 $crate::r1cs::ops::Term(
                     $crate::r1cs::ops::Variable::Input(self.to_index::<$C>()),
@@ -357,7 +360,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl Into<$crate::r1cs::ops::LC> for Vec<$ConcreteInput> {
+        impl<$($G: $Bound),*> Into<$crate::r1cs::ops::LC> for Vec<$ConcreteInput> {
             fn into(self) -> $crate::r1cs::ops::LC {
                 let terms: Vec<$crate::r1cs::ops::Term> =
                     self.into_iter().map(Into::into).collect();
@@ -365,7 +368,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl<T: Into<$crate::r1cs::ops::LC>> std::ops::Add<T> for $ConcreteInput {
+        impl<$($G: $Bound,)* T: Into<$crate::r1cs::ops::LC>> std::ops::Add<T> for $ConcreteInput {
             type Output = $crate::r1cs::ops::LC;
 
             fn add(self, rhs: T) -> Self::Output {
@@ -375,7 +378,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl<T: Into<$crate::r1cs::ops::LC>> std::ops::Sub<T> for $ConcreteInput {
+        impl<$($G: $Bound,)* T: Into<$crate::r1cs::ops::LC>> std::ops::Sub<T> for $ConcreteInput {
             type Output = $crate::r1cs::ops::LC;
 
             fn sub(self, rhs: T) -> Self::Output {
@@ -385,7 +388,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl std::ops::Mul<i64> for $ConcreteInput {
+        impl<$($G: $Bound),*> std::ops::Mul<i64> for $ConcreteInput {
             type Output = $crate::r1cs::ops::Term;
 
             fn mul(self, rhs: i64) -> Self::Output {
@@ -396,7 +399,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
 
-        impl std::ops::Mul<$ConcreteInput> for i64 {
+        impl<$($G: $Bound),*> std::ops::Mul<$ConcreteInput> for i64 {
             type Output = $crate::r1cs::ops::Term;
 
             fn mul(self, rhs: $ConcreteInput) -> Self::Output {
@@ -406,7 +409,7 @@ macro_rules! impl_r1cs_input_lc_conversions {
                 )
             }
         }
-        impl std::ops::Add<$ConcreteInput> for i64 {
+        impl<$($G: $Bound),*> std::ops::Add<$ConcreteInput> for i64 {
             type Output = $crate::r1cs::ops::LC;
 
             fn add(self, rhs: $ConcreteInput) -> Self::Output {
@@ -419,4 +422,8 @@ macro_rules! impl_r1cs_input_lc_conversions {
             }
         }
     };
+
+    ($ConcreteInput:ty, $C:expr) => {
+        $crate::impl_r1cs_input_lc_conversions!(<> $ConcreteInput, $C);
+    };
 }
@@ -3,12 +3,15 @@
 use std::marker::PhantomData;
 
 use crate::field::JoltField;
-use crate::jolt::vm::JoltCommitments;
-use crate::jolt::vm::JoltPolynomials;
+use crate::jolt::vm::stage::{JoltStage, StageProver, StageVerifier};
+use crate::jolt::vm::{commit_in_batches, JoltCommitments, JoltPolynomials};
+use crate::lasso::memory_checking::StructuredPolynomialData;
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::opening_proof::ProverOpeningAccumulator;
 use crate::poly::opening_proof::VerifierOpeningAccumulator;
 use crate::r1cs::key::UniformSpartanKey;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::json::Hex;
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 
@@ -17,6 +20,8 @@ use ark_serialize::CanonicalDeserialize;
 use ark_serialize::CanonicalSerialize;
 
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use thiserror::Error;
 
 use crate::{
@@ -60,17 +65,26 @@ pub enum SpartanError {
     /// returned when an invalid PCS proof is provided
     #[error("InvalidPCSProof")]
     InvalidPCSProof,
+
+    /// returned when the claimed evaluation of a virtual input is wrong
+    #[error("InvalidVirtualInputClaim")]
+    InvalidVirtualInputClaim,
 }
 
 /// A succinct proof of knowledge of a witness to a relaxed R1CS instance
 /// The proof is produced using Spartan's combination of the sum-check and
 /// the commitment to a vector viewed as a polynomial commitment
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct UniformSpartanProof<const C: usize, I: ConstraintInput, F: JoltField> {
+    #[serde(skip)]
     _inputs: PhantomData<I>,
     pub(crate) outer_sumcheck_proof: SumcheckInstanceProof<F>,
+    #[serde_as(as = "(Hex, Hex, Hex)")]
     pub(crate) outer_sumcheck_claims: (F, F, F),
     pub(crate) inner_sumcheck_proof: SumcheckInstanceProof<F>,
+    #[serde_as(as = "Vec<Hex>")]
     pub(crate) claimed_witness_evals: Vec<F>,
 }
 
@@ -92,6 +106,7 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> UniformSpartanProof<C, I,
         constraint_builder: &CombinedUniformBuilder<C, F, I>,
         key: &UniformSpartanKey<C, I, F>,
         polynomials: &JoltPolynomials<F>,
+        trace_length: usize,
         opening_accumulator: &mut ProverOpeningAccumulator<F>,
         transcript: &mut ProofTranscript,
     ) -> Result<Self, SpartanError> {
@@ -189,11 +204,29 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> UniformSpartanProof<C, I,
             .map(|poly| poly.evaluate_at_chi_low_optimized(&chi))
             .collect();
 
+        // The verifier evaluates the virtual inputs itself, so only the rest are opened
+        let mut committed_polys = vec![];
+        let mut committed_evals = vec![];
+        for ((var, poly), eval) in I::flatten::<C>()
+            .iter()
+            .zip(flattened_polys.iter())
+            .zip(claimed_witness_evals.iter())
+        {
+            match var.virtual_polynomial(trace_length) {
+                Some(virtual_poly) => {
+                    opening_accumulator.append_virtual(&[&*virtual_poly], r_col_step, &[eval])
+                }
+                None => {
+                    committed_polys.push(*poly);
+                    committed_evals.push(eval);
+                }
+            }
+        }
         opening_accumulator.append(
-            &flattened_polys,
+            &committed_polys,
             DensePolynomial::new(chi),
             r_col_step.to_vec(),
-            &claimed_witness_evals.iter().collect::<Vec<_>>(),
+            &committed_evals,
             transcript,
         );
 
@@ -217,6 +250,7 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> UniformSpartanProof<C, I,
         &self,
         key: &UniformSpartanKey<C, I, F>,
         commitments: &JoltCommitments<PCS>,
+        trace_length: usize,
         opening_accumulator: &mut VerifierOpeningAccumulator<F, PCS>,
         transcript: &mut ProofTranscript,
     ) -> Result<(), SpartanError> {
@@ -282,15 +316,27 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> UniformSpartanProof<C, I,
             return Err(SpartanError::InvalidInnerSumcheckClaim);
         }
 
-        let flattened_commitments: Vec<_> = I::flatten::<C>()
-            .iter()
-            .map(|var| var.get_ref(commitments))
-            .collect();
         let r_y_point = &inner_sumcheck_r[n_prefix..];
+        let mut committed_commitments = vec![];
+        let mut committed_evals = vec![];
+        for (var, eval) in I::flatten::<C>()
+            .iter()
+            .zip(self.claimed_witness_evals.iter())
+        {
+            match var.virtual_polynomial(trace_length) {
+                Some(virtual_poly) => opening_accumulator
+                    .append_virtual(&[&*virtual_poly], r_y_point, &[eval])
+                    .map_err(|_| SpartanError::InvalidVirtualInputClaim)?,
+                None => {
+                    committed_commitments.push(var.get_ref(commitments));
+                    committed_evals.push(eval);
+                }
+            }
+        }
         opening_accumulator.append(
-            &flattened_commitments,
+            &committed_commitments,
             r_y_point.to_vec(),
-            &self.claimed_witness_evals.iter().collect::<Vec<_>>(),
+            &committed_evals,
             transcript,
         );
 
@@ -298,6 +344,66 @@ impl<const C: usize, I: ConstraintInput, F: JoltField> UniformSpartanProof<C, I,
     }
 }
 
+/// Preprocessing for the R1CS stage: the uniform constraints, and the Spartan key derived from
+/// them.
+pub struct R1CSPreprocessing<const C: usize, I: ConstraintInput, F: JoltField> {
+    pub builder: CombinedUniformBuilder<C, F, I>,
+    pub key: UniformSpartanKey<C, I, F>,
+}
+
+impl<const C: usize, I, F, PCS> JoltStage<F, PCS> for UniformSpartanProof<C, I, F>
+where
+    I: ConstraintInput,
+    F: JoltField,
+    PCS: CommitmentScheme<Field = F>,
+{
+    type Preprocessing = R1CSPreprocessing<C, I, F>;
+    const NAME: &'static str = "R1CS";
+
+    /// Commits to the auxiliary R1CS columns, which must already have been computed with
+    /// [`CombinedUniformBuilder::compute_aux`].
+    fn commit(
+        polynomials: &JoltPolynomials<F>,
+        commitments: &mut JoltCommitments<PCS>,
+        setup: &PCS::Setup,
+    ) {
+        commit_in_batches::<PCS>(
+            polynomials.r1cs.read_write_values(),
+            commitments.r1cs.read_write_values_mut(),
+            setup,
+        );
+    }
+
+    fn prove(preprocessing: &R1CSPreprocessing<C, I, F>, prover: &mut StageProver<F, PCS>) -> Self {
+        Self::prove::<PCS>(
+            &preprocessing.builder,
+            &preprocessing.key,
+            prover.polynomials,
+            prover.trace_length,
+            prover.opening_accumulator,
+            prover.transcript,
+        )
+        .expect("r1cs proof failed")
+    }
+
+    #[tracing::instrument(skip_all, name = "UniformSpartanProof::verify")]
+    fn verify(
+        self,
+        preprocessing: &R1CSPreprocessing<C, I, F>,
+        verifier: &mut StageVerifier<F, PCS>,
+    ) -> Result<(), ProofVerifyError> {
+        Self::verify(
+            &self,
+            &preprocessing.key,
+            verifier.commitments,
+            verifier.trace_length,
+            verifier.opening_accumulator,
+            verifier.transcript,
+        )
+        .map_err(|e| ProofVerifyError::SpartanError(e.to_string()))
+    }
+}
+
 // #[cfg(test)]
 // mod test {
 //     use ark_bn254::Fr;
@@ -1,9 +1,11 @@
-use super::grand_product_quarks::QuarkGrandProductProof;
+use super::grand_product_quarks::{QuarkGrandProduct, QuarkGrandProductProof};
 use super::sumcheck::{BatchedCubicSumcheck, SumcheckInstanceProof};
 use crate::field::{JoltField, OptimizedMul};
 use crate::poly::commitment::commitment_scheme::CommitmentScheme;
 use crate::poly::eq_poly::EqPolynomial;
 use crate::poly::{dense_mlpoly::DensePolynomial, unipoly::UniPoly};
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::json::Hex;
 use crate::utils::math::Math;
 use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::ProofTranscript;
@@ -11,11 +13,17 @@ use ark_ff::Zero;
 use ark_serialize::*;
 use itertools::Itertools;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct BatchedGrandProductLayerProof<F: JoltField> {
     pub proof: SumcheckInstanceProof<F>,
+    #[serde_as(as = "Vec<Hex>")]
     pub left_claims: Vec<F>,
+    #[serde_as(as = "Vec<Hex>")]
     pub right_claims: Vec<F>,
 }
 
@@ -26,25 +34,47 @@ impl<F: JoltField> BatchedGrandProductLayerProof<F> {
         num_rounds: usize,
         degree_bound: usize,
         transcript: &mut ProofTranscript,
-    ) -> (F, Vec<F>) {
+    ) -> Result<(F, Vec<F>), ProofVerifyError> {
         self.proof
             .verify(claim, num_rounds, degree_bound, transcript)
-            .unwrap()
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct BatchedGrandProductProof<PCS: CommitmentScheme> {
     pub layers: Vec<BatchedGrandProductLayerProof<PCS::Field>>,
+    /// Present iff the grand products were proven with [`GrandProductConfig::Quarks`], in which
+    /// case `layers` only covers the bottom of the circuit.
     pub quark_proof: Option<QuarkGrandProductProof<PCS>>,
 }
 
+/// Which argument the prover uses for grand products that support a choice (see
+/// [`ConfigurableGrandProduct`]). The choice is recorded in the proof, so the verifier doesn't
+/// need to know it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GrandProductConfig {
+    /// A layered GKR circuit (Thaler '13), with a sumcheck per layer. No commitments, but the
+    /// proof has O(log² n) field elements.
+    #[default]
+    Gkr,
+    /// The Quarks grand product (Setty-Lee '20, section 5) over the top of the circuit, with GKR
+    /// for its bottom layers. Proofs are O(log n), but the prover commits to and opens an extra
+    /// polynomial per grand product.
+    Quarks,
+}
+
 pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: Sized {
     /// The bottom/input layer of the grand products
     type Leaves;
 
     /// Constructs the grand product circuit(s) from `leaves`
     fn construct(leaves: Self::Leaves) -> Self;
+    /// Constructs the grand product circuit(s) from `leaves`, to be proven with the argument
+    /// chosen by `config` if this grand product supports a choice.
+    fn construct_with_config(leaves: Self::Leaves, _config: GrandProductConfig) -> Self {
+        Self::construct(leaves)
+    }
     /// The number of layers in the grand product.
     fn num_layers(&self) -> usize;
     /// The claimed outputs of the grand products.
@@ -95,14 +125,16 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
         grand_product_claims: &mut Vec<F>,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let layer_proof = &layer_proofs[layer_index];
 
         let expected_sumcheck_claim: F = (0..grand_product_claims.len())
             .map(|i| coeffs[i] * layer_proof.left_claims[i] * layer_proof.right_claims[i] * eq_eval)
             .sum();
 
-        assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+        if expected_sumcheck_claim != sumcheck_claim {
+            return Err(ProofVerifyError::InternalError);
+        }
         // produce a random challenge to condense two claims into a single claim
         let r_layer = transcript.challenge_scalar();
 
@@ -114,6 +146,7 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
             .collect();
 
         r_grand_product.push(r_layer);
+        Ok(())
     }
 
     /// Function used for layer sumchecks in the generic batch verifier as well as the quark layered sumcheck hybrid
@@ -122,7 +155,7 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
         claims: &Vec<F>,
         transcript: &mut ProofTranscript,
         r_start: Vec<F>,
-    ) -> (Vec<F>, Vec<F>) {
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         let mut claims_to_verify = claims.to_owned();
         // We allow a non empty start in this function call because the quark hybrid form provides prespecified random for
         // most of the positions and then we proceed with GKR on the remaining layers using the preset random values.
@@ -141,9 +174,15 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
                 .sum();
 
             let (sumcheck_claim, r_sumcheck) =
-                layer_proof.verify(claim, layer_index + fixed_at_start, 3, transcript);
-            assert_eq!(claims.len(), layer_proof.left_claims.len());
-            assert_eq!(claims.len(), layer_proof.right_claims.len());
+                layer_proof.verify(claim, layer_index + fixed_at_start, 3, transcript)?;
+            for layer_claims in [&layer_proof.left_claims, &layer_proof.right_claims] {
+                if layer_claims.len() != claims.len() {
+                    return Err(ProofVerifyError::InvalidInputLength(
+                        claims.len(),
+                        layer_claims.len(),
+                    ));
+                }
+            }
 
             for (left, right) in layer_proof
                 .left_claims
@@ -173,10 +212,10 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
                 &mut claims_to_verify,
                 &mut r_grand_product,
                 transcript,
-            );
+            )?;
         }
 
-        (claims_to_verify, r_grand_product)
+        Ok((claims_to_verify, r_grand_product))
     }
 
     /// Verifies the given grand product proof.
@@ -185,7 +224,7 @@ pub trait BatchedGrandProduct<F: JoltField, PCS: CommitmentScheme<Field = F>>: S
         claims: &Vec<F>,
         transcript: &mut ProofTranscript,
         _setup: Option<&PCS::Setup>,
-    ) -> (Vec<F>, Vec<F>) {
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         // Pass the inputs to the layer verification function, by default we have no quarks and so we do not
         // use the quark proof fields.
         let r_start = Vec::<F>::new();
@@ -466,6 +505,74 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
     }
 }
 
+/// Batched dense grand products, proven with GKR or Quarks according to a
+/// [`GrandProductConfig`]. The verifier dispatches on [`BatchedGrandProductProof::quark_proof`].
+pub enum ConfigurableGrandProduct<F: JoltField> {
+    Gkr(BatchedDenseGrandProduct<F>),
+    Quarks(QuarkGrandProduct<F>),
+}
+
+impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
+    for ConfigurableGrandProduct<F>
+{
+    type Leaves = Vec<Vec<F>>;
+
+    fn construct(leaves: Self::Leaves) -> Self {
+        Self::Gkr(BatchedGrandProduct::<F, PCS>::construct(leaves))
+    }
+
+    fn construct_with_config(leaves: Self::Leaves, config: GrandProductConfig) -> Self {
+        match config {
+            GrandProductConfig::Gkr => Self::Gkr(BatchedGrandProduct::<F, PCS>::construct(leaves)),
+            GrandProductConfig::Quarks => {
+                Self::Quarks(BatchedGrandProduct::<F, PCS>::construct(leaves))
+            }
+        }
+    }
+
+    fn num_layers(&self) -> usize {
+        match self {
+            Self::Gkr(circuit) => BatchedGrandProduct::<F, PCS>::num_layers(circuit),
+            Self::Quarks(circuit) => BatchedGrandProduct::<F, PCS>::num_layers(circuit),
+        }
+    }
+
+    fn claims(&self) -> Vec<F> {
+        match self {
+            Self::Gkr(circuit) => BatchedGrandProduct::<F, PCS>::claims(circuit),
+            Self::Quarks(circuit) => BatchedGrandProduct::<F, PCS>::claims(circuit),
+        }
+    }
+
+    fn layers(&'_ mut self) -> impl Iterator<Item = &'_ mut dyn BatchedGrandProductLayer<F>> {
+        match self {
+            Self::Gkr(circuit) => BatchedGrandProduct::<F, PCS>::layers(circuit),
+            Self::Quarks(_) => unreachable!("Quarks grand products aren't proven layer by layer"),
+        }
+    }
+
+    fn prove_grand_product(
+        &mut self,
+        transcript: &mut ProofTranscript,
+        setup: Option<&PCS::Setup>,
+    ) -> (BatchedGrandProductProof<PCS>, Vec<F>) {
+        match self {
+            Self::Gkr(circuit) => circuit.prove_grand_product(transcript, setup),
+            Self::Quarks(circuit) => circuit.prove_grand_product(transcript, setup),
+        }
+    }
+
+    fn verify_grand_product(
+        proof: &BatchedGrandProductProof<PCS>,
+        claims: &Vec<F>,
+        transcript: &mut ProofTranscript,
+        setup: Option<&PCS::Setup>,
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
+        // Without a Quarks proof, this is exactly the GKR verifier
+        QuarkGrandProduct::verify_grand_product(proof, claims, transcript, setup)
+    }
+}
+
 /// Represents a single layer of a single grand product circuit using a sparse vector,
 /// i.e. a vector containing (index, value) pairs.
 /// Nodes with value 1 are omitted from the sparse vector.
@@ -1489,7 +1596,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
         grand_product_claims: &mut Vec<F>,
         r_grand_product: &mut Vec<F>,
         transcript: &mut ProofTranscript,
-    ) {
+    ) -> Result<(), ProofVerifyError> {
         let layer_proof = &layer_proofs[layer_index];
         if layer_index != layer_proofs.len() - 1 {
             // Normal grand product layer (multiplication gates)
@@ -1499,7 +1606,9 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
                 })
                 .sum();
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            if expected_sumcheck_claim != sumcheck_claim {
+                return Err(ProofVerifyError::InternalError);
+            }
 
             // produce a random challenge to condense two claims into a single claim
             let r_layer = transcript.challenge_scalar();
@@ -1526,7 +1635,9 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
                 })
                 .sum();
 
-            assert_eq!(expected_sumcheck_claim, sumcheck_claim);
+            if expected_sumcheck_claim != sumcheck_claim {
+                return Err(ProofVerifyError::InternalError);
+            }
 
             *grand_product_claims = layer_proof
                 .left_claims
@@ -1537,6 +1648,7 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
                 })
                 .collect();
         }
+        Ok(())
     }
 }
 
@@ -1581,7 +1693,8 @@ mod grand_product_tests {
 
         let mut transcript: ProofTranscript = ProofTranscript::new(b"test_transcript");
         let (_, r_verifier) =
-            BatchedDenseGrandProduct::verify_grand_product(&proof, &claims, &mut transcript, None);
+            BatchedDenseGrandProduct::verify_grand_product(&proof, &claims, &mut transcript, None)
+                .unwrap();
         assert_eq!(r_prover, r_verifier);
     }
 
@@ -7,20 +7,30 @@ use crate::field::JoltField;
 use crate::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::errors::ProofVerifyError;
+use crate::utils::json::Hex;
 use crate::utils::math::Math;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use ark_serialize::*;
 use ark_std::{One, Zero};
 use itertools::Itertools;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Same};
 use thiserror::Error;
 
-#[derive(CanonicalSerialize, CanonicalDeserialize)]
+#[serde_as]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct QuarkGrandProductProof<PCS: CommitmentScheme> {
     sumcheck_proof: SumcheckInstanceProof<PCS::Field>,
+    #[serde_as(as = "Vec<Hex>")]
     g_commitment: Vec<PCS::Commitment>,
+    #[serde_as(as = "(Vec<Hex>, Same)")]
     claimed_eval_g_r: (Vec<PCS::Field>, PCS::BatchedProof),
+    #[serde_as(as = "(Vec<Hex>, Vec<Hex>, Same)")]
     claimed_eval_g_r_x: (Vec<PCS::Field>, Vec<PCS::Field>, PCS::BatchedProof),
+    #[serde_as(as = "(Vec<Hex>, Vec<Hex>)")]
     helper_values: (Vec<PCS::Field>, Vec<PCS::Field>),
     num_vars: usize,
 }
@@ -149,16 +159,15 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
         claims: &Vec<F>,
         transcript: &mut ProofTranscript,
         setup: Option<&PCS::Setup>,
-    ) -> (Vec<F>, Vec<F>) {
+    ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError> {
         // Here we must also support the case where the number of layers is very small
         let (v_points, rand) = match proof.quark_proof.as_ref() {
             Some(quark) => {
                 // In this case we verify the quark which fixes the first log(n)-4 vars in the random eval point.
                 let v_len = quark.num_vars;
-                // Todo (aleph_v) - bubble up errors
                 quark
                     .verify(claims, transcript, v_len, setup.unwrap())
-                    .unwrap()
+                    .map_err(|_| ProofVerifyError::InternalError)?
             }
             None => {
                 // Otherwise we must check the actual claims and the preset random will be empty.
@@ -166,14 +175,12 @@ impl<F: JoltField, PCS: CommitmentScheme<Field = F>> BatchedGrandProduct<F, PCS>
             }
         };
 
-        let (sumcheck_claims, sumcheck_r) = <Self as BatchedGrandProduct<F, PCS>>::verify_layers(
+        <Self as BatchedGrandProduct<F, PCS>>::verify_layers(
             &proof.layers,
             &v_points,
             transcript,
             rand,
-        );
-
-        (sumcheck_claims, sumcheck_r)
+        )
     }
 }
 
@@ -673,11 +680,12 @@ mod quark_grand_product_tests {
 
         // Note resetting the transcript is important
         transcript = ProofTranscript::new(b"test_transcript");
-        let _ = QuarkGrandProduct::verify_grand_product(
+        QuarkGrandProduct::verify_grand_product(
             &proof,
             &known_products,
             &mut transcript,
             Some(&setup),
-        );
+        )
+        .unwrap();
     }
 }
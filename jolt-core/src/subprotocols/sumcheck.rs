@@ -11,6 +11,7 @@ use crate::utils::thread::drop_in_background_thread;
 use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
 use ark_serialize::*;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Batched cubic sumcheck used in grand products
 pub trait BatchedCubicSumcheck<F: JoltField>: Sync {
@@ -492,7 +493,8 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
     }
 }
 
-#[derive(CanonicalSerialize, CanonicalDeserialize, Debug)]
+#[derive(CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize, Debug)]
+#[serde(bound = "")]
 pub struct SumcheckInstanceProof<F: JoltField> {
     pub compressed_polys: Vec<CompressedUniPoly<F>>,
 }
@@ -526,7 +528,12 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         let mut r: Vec<F> = Vec::new();
 
         // verify that there is a univariate polynomial for each round
-        assert_eq!(self.compressed_polys.len(), num_rounds);
+        if self.compressed_polys.len() != num_rounds {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_rounds,
+                self.compressed_polys.len(),
+            ));
+        }
         for i in 0..self.compressed_polys.len() {
             // verify degree bound
             if self.compressed_polys[i].degree() != degree_bound {
@@ -550,3 +557,272 @@ impl<F: JoltField> SumcheckInstanceProof<F> {
         Ok((e, r))
     }
 }
+
+/// A relation `Σ_{x ∈ {0,1}^n} combine(p_0(x), ..., p_{k-1}(x)) = claim` over multilinear
+/// polynomials `p_i`, for protocols defined outside of Jolt. Proven and verified, possibly
+/// batched with other relations over the same number of variables, by [`RelationSumcheckProof`].
+pub trait SumcheckRelation<F: JoltField>: Sync {
+    /// The number `k` of polynomials `combine` takes.
+    fn num_polys(&self) -> usize;
+    /// The total degree of `combine` in its arguments, which bounds the degree of each round's
+    /// univariate polynomial.
+    fn degree(&self) -> usize;
+    /// Combines the evaluations of `p_0, ..., p_{k-1}` at a point.
+    fn combine(&self, evals: &[F]) -> F;
+}
+
+/// A sumcheck proof of one or more [`SumcheckRelation`]s over polynomials with the same number
+/// of variables, batched with a random linear combination, together with the evaluations of
+/// their polynomials at the final sumcheck point.
+///
+/// The verifier only learns that `final_evals` are consistent with the claims: checking them
+/// against the polynomials (e.g. with a polynomial commitment opening at the returned point) is
+/// up to the caller.
+#[derive(CanonicalSerialize, CanonicalDeserialize, Debug)]
+pub struct RelationSumcheckProof<F: JoltField> {
+    pub sumcheck: SumcheckInstanceProof<F>,
+    /// For each relation, the evaluations of its polynomials at the final point.
+    pub final_evals: Vec<Vec<F>>,
+}
+
+impl<F: JoltField> RelationSumcheckProof<F> {
+    /// Proves `relations`, where `polys[i]` are the polynomials of `relations[i]`, each with
+    /// `2^n` evaluations over the hypercube. Returns the proof, the claim of each relation, and
+    /// the final point `r` of length `n`, at which `final_evals` are the evaluations.
+    #[tracing::instrument(skip_all, name = "RelationSumcheckProof::prove")]
+    pub fn prove(
+        relations: &[&dyn SumcheckRelation<F>],
+        polys: Vec<Vec<DensePolynomial<F>>>,
+        transcript: &mut ProofTranscript,
+    ) -> (Self, Vec<F>, Vec<F>) {
+        assert_eq!(relations.len(), polys.len());
+        let num_vars = polys[0][0].get_num_vars();
+        for (relation, polys) in relations.iter().zip(polys.iter()) {
+            assert_eq!(relation.num_polys(), polys.len());
+            assert!(polys.iter().all(|poly| poly.get_num_vars() == num_vars));
+        }
+
+        let claims: Vec<F> = relations
+            .iter()
+            .zip(polys.iter())
+            .map(|(relation, polys)| {
+                (0..1 << num_vars)
+                    .into_par_iter()
+                    .map(|i| {
+                        let evals: Vec<F> = polys.iter().map(|poly| poly[i]).collect();
+                        relation.combine(&evals)
+                    })
+                    .sum()
+            })
+            .collect();
+        transcript.append_scalars(&claims);
+        let coeffs: Vec<F> = transcript.challenge_vector(relations.len());
+        let combined_claim = combine_claims(&claims, &coeffs);
+
+        let offsets = poly_offsets(relations);
+        let degree = combined_degree(relations);
+        let mut polys: Vec<DensePolynomial<F>> = polys.into_iter().flatten().collect();
+        let (sumcheck, r, evals) = SumcheckInstanceProof::prove_arbitrary(
+            &combined_claim,
+            num_vars,
+            &mut polys,
+            |evals| combine_relations(relations, &coeffs, &offsets, evals),
+            degree,
+            transcript,
+        );
+        transcript.append_scalars(&evals);
+
+        let final_evals = offsets
+            .windows(2)
+            .map(|window| evals[window[0]..window[1]].to_vec())
+            .collect();
+        (
+            Self {
+                sumcheck,
+                final_evals,
+            },
+            claims,
+            r,
+        )
+    }
+
+    /// Verifies that `relations` over `num_vars` variables sum to `claims`, and returns the final
+    /// point at which the polynomials are claimed to evaluate to `self.final_evals`.
+    pub fn verify(
+        &self,
+        relations: &[&dyn SumcheckRelation<F>],
+        claims: &[F],
+        num_vars: usize,
+        transcript: &mut ProofTranscript,
+    ) -> Result<Vec<F>, ProofVerifyError> {
+        if claims.len() != relations.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                relations.len(),
+                claims.len(),
+            ));
+        }
+        if self.final_evals.len() != relations.len() {
+            return Err(ProofVerifyError::InvalidInputLength(
+                relations.len(),
+                self.final_evals.len(),
+            ));
+        }
+        for (relation, evals) in relations.iter().zip(self.final_evals.iter()) {
+            if evals.len() != relation.num_polys() {
+                return Err(ProofVerifyError::InvalidInputLength(
+                    relation.num_polys(),
+                    evals.len(),
+                ));
+            }
+        }
+        if self.sumcheck.compressed_polys.len() != num_vars {
+            return Err(ProofVerifyError::InvalidInputLength(
+                num_vars,
+                self.sumcheck.compressed_polys.len(),
+            ));
+        }
+
+        transcript.append_scalars(claims);
+        let coeffs: Vec<F> = transcript.challenge_vector(relations.len());
+        let (final_claim, r) = self.sumcheck.verify(
+            combine_claims(claims, &coeffs),
+            num_vars,
+            combined_degree(relations),
+            transcript,
+        )?;
+
+        let evals: Vec<F> = self.final_evals.concat();
+        transcript.append_scalars(&evals);
+        let offsets = poly_offsets(relations);
+        if combine_relations(relations, &coeffs, &offsets, &evals) != final_claim {
+            return Err(ProofVerifyError::InternalError);
+        }
+        Ok(r)
+    }
+}
+
+fn combine_claims<F: JoltField>(claims: &[F], coeffs: &[F]) -> F {
+    claims
+        .iter()
+        .zip(coeffs.iter())
+        .map(|(claim, coeff)| *claim * coeff)
+        .sum()
+}
+
+/// The maximum degree of `relations`; at least 1, since each round's polynomial is linear in a
+/// multilinear polynomial's variable.
+fn combined_degree<F: JoltField>(relations: &[&dyn SumcheckRelation<F>]) -> usize {
+    relations
+        .iter()
+        .map(|relation| relation.degree())
+        .max()
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Where each relation's polynomials start in the concatenation of all of them, followed by the
+/// total.
+fn poly_offsets<F: JoltField>(relations: &[&dyn SumcheckRelation<F>]) -> Vec<usize> {
+    let mut offsets = vec![0];
+    for relation in relations {
+        offsets.push(offsets.last().unwrap() + relation.num_polys());
+    }
+    offsets
+}
+
+fn combine_relations<F: JoltField>(
+    relations: &[&dyn SumcheckRelation<F>],
+    coeffs: &[F],
+    offsets: &[usize],
+    evals: &[F],
+) -> F {
+    relations
+        .iter()
+        .zip(coeffs.iter())
+        .zip(offsets.windows(2))
+        .map(|((relation, coeff), window)| *coeff * relation.combine(&evals[window[0]..window[1]]))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::test_rng;
+
+    /// `Σ a(x) * b(x) * c(x)`
+    struct TripleProduct;
+
+    impl SumcheckRelation<Fr> for TripleProduct {
+        fn num_polys(&self) -> usize {
+            3
+        }
+        fn degree(&self) -> usize {
+            3
+        }
+        fn combine(&self, evals: &[Fr]) -> Fr {
+            evals[0] * evals[1] * evals[2]
+        }
+    }
+
+    /// `Σ a(x)^2 - b(x)`
+    struct SquareMinus;
+
+    impl SumcheckRelation<Fr> for SquareMinus {
+        fn num_polys(&self) -> usize {
+            2
+        }
+        fn degree(&self) -> usize {
+            2
+        }
+        fn combine(&self, evals: &[Fr]) -> Fr {
+            evals[0].square() - evals[1]
+        }
+    }
+
+    fn random_polys(num_polys: usize, num_vars: usize) -> Vec<DensePolynomial<Fr>> {
+        let mut rng = test_rng();
+        (0..num_polys)
+            .map(|_| {
+                DensePolynomial::new(
+                    std::iter::repeat_with(|| Fr::random(&mut rng))
+                        .take(1 << num_vars)
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batched_relations() {
+        const NUM_VARS: usize = 6;
+        let relations: [&dyn SumcheckRelation<Fr>; 2] = [&TripleProduct, &SquareMinus];
+        let polys = vec![random_polys(3, NUM_VARS), random_polys(2, NUM_VARS)];
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let (proof, claims, r_prover) =
+            RelationSumcheckProof::prove(&relations, polys.clone(), &mut transcript);
+        let expected_claim: Fr = (0..1 << NUM_VARS)
+            .map(|i| polys[0][0][i] * polys[0][1][i] * polys[0][2][i])
+            .sum();
+        assert_eq!(claims[0], expected_claim);
+        for (polys, evals) in polys.iter().zip(proof.final_evals.iter()) {
+            for (poly, eval) in polys.iter().zip(evals.iter()) {
+                assert_eq!(poly.evaluate(&r_prover), *eval);
+            }
+        }
+
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        let r_verifier = proof
+            .verify(&relations, &claims, NUM_VARS, &mut transcript)
+            .unwrap();
+        assert_eq!(r_prover, r_verifier);
+
+        let mut wrong_claims = claims.clone();
+        wrong_claims[1] += Fr::from(1u64);
+        let mut transcript = ProofTranscript::new(b"test_transcript");
+        assert!(proof
+            .verify(&relations, &wrong_claims, NUM_VARS, &mut transcript)
+            .is_err());
+    }
+}
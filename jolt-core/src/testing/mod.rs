@@ -0,0 +1,362 @@
+//! Adversarial tests of verifiers, for maintainers, auditors, and integrators checking their own
+//! guests.
+//!
+//! [`fuzz_verifier`] takes a valid proof, applies structured [`Mutation`]s to it, and reports any
+//! mutant that the verifier accepts. Mutations are applied to the proof's JSON encoding (see
+//! [`json`](crate::utils::json)), where field and group elements are hex strings and structs
+//! keep their field names, so any type with that encoding can be fuzzed, e.g. a
+//! [`JoltHyperKZGProof`](crate::jolt::vm::rv32i_vm::JoltHyperKZGProof) along with its
+//! commitments:
+//!
+//! ```ignore
+//! let report = fuzz_verifier::<Fr, _, _>(&proof, 100, 0, |mutant: JoltHyperKZGProof| {
+//!     RV32IJoltVM::verify(preprocessing.clone(), mutant.proof, mutant.commitments, None)
+//! });
+//! report.assert_sound();
+//! ```
+
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::field::JoltField;
+
+/// A structured change to a proof, which a sound verifier must reject.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    /// Adds one to a field element.
+    FlipScalar,
+    /// Swaps two distinct group elements of the same type, e.g. two commitments.
+    SwapCommitments,
+    /// Drops the last round of a sumcheck proof.
+    TruncateSumcheck,
+}
+
+const MUTATIONS: [Mutation; 3] = [
+    Mutation::FlipScalar,
+    Mutation::SwapCommitments,
+    Mutation::TruncateSumcheck,
+];
+
+/// A mutated proof, identified by the [JSON pointers](https://www.rfc-editor.org/rfc/rfc6901)
+/// of the values that were changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mutant {
+    pub mutation: Mutation,
+    pub locations: Vec<String>,
+}
+
+impl fmt::Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at {}", self.mutation, self.locations.join(", "))
+    }
+}
+
+/// The outcome of [`fuzz_verifier`].
+#[derive(Clone, Debug, Default)]
+pub struct FuzzReport {
+    /// Mutants rejected by the verifier, or that failed to decode.
+    pub rejected: usize,
+    /// Mutants the verifier panicked on. These count as rejected, but a verifier should return
+    /// an error instead.
+    pub panicked: Vec<Mutant>,
+    /// Mutants the verifier accepted, each of which is a soundness bug.
+    pub accepted: Vec<Mutant>,
+}
+
+impl FuzzReport {
+    /// The number of mutants tried.
+    pub fn num_mutants(&self) -> usize {
+        self.rejected + self.panicked.len() + self.accepted.len()
+    }
+
+    /// Panics, listing the accepted mutants, if there are any.
+    pub fn assert_sound(&self) {
+        assert!(
+            self.accepted.is_empty(),
+            "verifier accepted {} of {} mutants:\n{}",
+            self.accepted.len(),
+            self.num_mutants(),
+            self.accepted
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
+
+/// Applies `num_mutants` random mutations to `proof`, cycling through the kinds of [`Mutation`],
+/// and passes each mutant to `verify`. Mutations are drawn from `seed`, so a report can be
+/// reproduced. `F` is the field of the proof's scalars, which tells them apart from group
+/// elements.
+///
+/// Kinds of mutation that `proof` has nothing to apply to (e.g. no sumchecks) are skipped. Panics
+/// if `verify` rejects the unmutated proof, since every mutant would then be rejected trivially.
+pub fn fuzz_verifier<F, T, E>(
+    proof: &T,
+    num_mutants: usize,
+    seed: u64,
+    verify: impl Fn(T) -> Result<(), E>,
+) -> FuzzReport
+where
+    F: JoltField,
+    T: Serialize + DeserializeOwned,
+    E: fmt::Debug,
+{
+    let original = serde_json::to_value(proof).expect("proof should encode to JSON");
+    let decode = |value: Value| serde_json::from_value::<T>(value);
+    if let Err(err) = verify(decode(original.clone()).unwrap()) {
+        panic!("verifier rejected the unmutated proof: {err:?}");
+    }
+
+    let targets = Targets::new::<F>(&original);
+    let mutations: Vec<Mutation> = MUTATIONS
+        .into_iter()
+        .filter(|&mutation| targets.applicable(mutation))
+        .collect();
+    let mut report = FuzzReport::default();
+    if mutations.is_empty() {
+        return report;
+    }
+
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    for i in 0..num_mutants {
+        let mut value = original.clone();
+        let mutant = targets.mutate::<F>(mutations[i % mutations.len()], &mut value, &mut rng);
+        let Ok(mutated) = decode(value) else {
+            report.rejected += 1;
+            continue;
+        };
+        match catch_unwind(AssertUnwindSafe(|| verify(mutated))) {
+            Ok(Ok(())) => report.accepted.push(mutant),
+            Ok(Err(_)) => report.rejected += 1,
+            Err(_) => report.panicked.push(mutant),
+        }
+    }
+    report
+}
+
+/// The values of a proof's JSON that mutations apply to, as JSON pointers.
+#[derive(Default)]
+struct Targets {
+    scalars: Vec<String>,
+    /// Group elements, grouped by the length of their encoding.
+    group_elements: Vec<(usize, Vec<String>)>,
+    sumchecks: Vec<String>,
+}
+
+impl Targets {
+    fn new<F: JoltField>(value: &Value) -> Self {
+        let mut targets = Self::default();
+        targets.collect::<F>(value, String::new());
+        // Swapping equal elements doesn't change the proof
+        targets.group_elements.retain(|(_, elements)| {
+            elements
+                .iter()
+                .any(|pointer| value.pointer(pointer) != value.pointer(&elements[0]))
+        });
+        targets
+    }
+
+    fn collect<F: JoltField>(&mut self, value: &Value, pointer: String) {
+        match value {
+            Value::String(string) => match decode_hex(string) {
+                Some(bytes) if decode_scalar::<F>(&bytes).is_some() => self.scalars.push(pointer),
+                Some(bytes) => match self
+                    .group_elements
+                    .iter_mut()
+                    .find(|(len, _)| *len == bytes.len())
+                {
+                    Some((_, elements)) => elements.push(pointer),
+                    None => self.group_elements.push((bytes.len(), vec![pointer])),
+                },
+                None => {}
+            },
+            Value::Array(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    self.collect::<F>(value, format!("{pointer}/{i}"));
+                }
+            }
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    let pointer =
+                        format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+                    if key == "compressed_polys" && value.as_array().is_some_and(|a| !a.is_empty())
+                    {
+                        self.sumchecks.push(pointer.clone());
+                    }
+                    self.collect::<F>(value, pointer);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn applicable(&self, mutation: Mutation) -> bool {
+        match mutation {
+            Mutation::FlipScalar => !self.scalars.is_empty(),
+            Mutation::SwapCommitments => !self.group_elements.is_empty(),
+            Mutation::TruncateSumcheck => !self.sumchecks.is_empty(),
+        }
+    }
+
+    fn mutate<F: JoltField>(
+        &self,
+        mutation: Mutation,
+        value: &mut Value,
+        rng: &mut ChaCha20Rng,
+    ) -> Mutant {
+        let locations = match mutation {
+            Mutation::FlipScalar => {
+                let pointer = choose(&self.scalars, rng);
+                let scalar = value.pointer_mut(pointer).unwrap();
+                let bytes = decode_hex(scalar.as_str().unwrap()).unwrap();
+                let flipped = decode_scalar::<F>(&bytes).unwrap() + F::one();
+                let mut bytes = vec![];
+                flipped.serialize_uncompressed(&mut bytes).unwrap();
+                *scalar = Value::String(format!("0x{}", hex::encode(bytes)));
+                vec![pointer.clone()]
+            }
+            Mutation::SwapCommitments => {
+                let (_, elements) =
+                    &self.group_elements[rng.next_u64() as usize % self.group_elements.len()];
+                let a = choose(elements, rng);
+                // Every group has two distinct elements, see `Targets::new`
+                let others: Vec<&String> = elements
+                    .iter()
+                    .filter(|b| value.pointer(b) != value.pointer(a))
+                    .collect();
+                let b = others[rng.next_u64() as usize % others.len()];
+                let a_value = value.pointer(a).unwrap().clone();
+                let b_value = std::mem::replace(value.pointer_mut(b).unwrap(), a_value);
+                *value.pointer_mut(a).unwrap() = b_value;
+                vec![a.clone(), b.clone()]
+            }
+            Mutation::TruncateSumcheck => {
+                let pointer = choose(&self.sumchecks, rng);
+                let rounds = value.pointer_mut(pointer).unwrap().as_array_mut().unwrap();
+                rounds.pop();
+                vec![pointer.clone()]
+            }
+        };
+        Mutant {
+            mutation,
+            locations,
+        }
+    }
+}
+
+fn choose<'a>(pointers: &'a [String], rng: &mut ChaCha20Rng) -> &'a String {
+    &pointers[rng.next_u64() as usize % pointers.len()]
+}
+
+fn decode_hex(string: &str) -> Option<Vec<u8>> {
+    hex::decode(string.strip_prefix("0x")?).ok()
+}
+
+fn decode_scalar<F: JoltField>(bytes: &[u8]) -> Option<F> {
+    if bytes.len() != F::zero().uncompressed_size() {
+        return None;
+    }
+    F::deserialize_uncompressed(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json::Hex;
+    use ark_bn254::{Fr, G1Affine, G1Projective};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use serde::Deserialize;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Round {
+        #[serde_as(as = "Vec<Hex>")]
+        coeffs: Vec<Fr>,
+    }
+
+    #[serde_as]
+    #[derive(Clone, Serialize, Deserialize)]
+    struct ToyProof {
+        #[serde_as(as = "Vec<Hex>")]
+        commitments: Vec<G1Affine>,
+        compressed_polys: Vec<Round>,
+        #[serde_as(as = "Hex")]
+        claim: Fr,
+    }
+
+    fn proof() -> ToyProof {
+        let g = G1Affine::generator();
+        ToyProof {
+            commitments: vec![g, (G1Projective::from(g) * Fr::from(2u64)).into_affine(), g],
+            compressed_polys: vec![
+                Round {
+                    coeffs: vec![Fr::from(1u64), Fr::from(2u64)],
+                };
+                3
+            ],
+            claim: Fr::from(5u64),
+        }
+    }
+
+    fn encode(proof: &ToyProof) -> Value {
+        serde_json::to_value(proof).unwrap()
+    }
+
+    #[test]
+    fn strict_verifier_rejects_every_mutant() {
+        let proof = proof();
+        let expected = encode(&proof);
+        let report = fuzz_verifier::<Fr, _, _>(&proof, 30, 0, |mutant: ToyProof| {
+            if mutant.compressed_polys.len() < 3 {
+                panic!("too few rounds");
+            }
+            (encode(&mutant) == expected).then_some(()).ok_or(())
+        });
+        report.assert_sound();
+        assert_eq!(report.num_mutants(), 30);
+        // One in three mutants is a truncated sumcheck
+        assert_eq!(report.panicked.len(), 10);
+        assert!(report
+            .panicked
+            .iter()
+            .all(|mutant| mutant.mutation == Mutation::TruncateSumcheck
+                && mutant.locations == ["/compressed_polys"]));
+    }
+
+    #[test]
+    fn reports_accepted_mutants() {
+        // Ignores the claim and the order of the commitments
+        let proof = proof();
+        let report = fuzz_verifier::<Fr, _, _>(&proof, 300, 1, |mutant: ToyProof| {
+            let ok = mutant.compressed_polys.len() == 3
+                && mutant
+                    .compressed_polys
+                    .iter()
+                    .all(|round| round.coeffs == proof.compressed_polys[0].coeffs);
+            ok.then_some(()).ok_or("rejected")
+        });
+        assert_eq!(report.num_mutants(), 300);
+        assert!(report
+            .accepted
+            .iter()
+            .any(|mutant| mutant.locations == ["/claim"]));
+        assert!(report
+            .accepted
+            .iter()
+            .any(|mutant| mutant.mutation == Mutation::SwapCommitments
+                && mutant.locations.len() == 2));
+        assert!(report
+            .accepted
+            .iter()
+            .all(|mutant| mutant.mutation != Mutation::TruncateSumcheck
+                && !mutant.locations[0].starts_with("/compressed_polys")));
+    }
+}
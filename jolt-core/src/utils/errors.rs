@@ -1,3 +1,4 @@
+use common::rv_trace::{GuestFault, GuestPanic};
 use core::fmt::Debug;
 use thiserror::Error;
 
@@ -12,8 +13,136 @@ pub enum ProofVerifyError {
     InternalError,
     #[error("Compressed group element failed to decompress: {0:?}")]
     DecompressionError([u8; 32]),
+    #[error("Final memory does not match the claimed program I/O")]
+    OutputMismatch,
     #[error("R1CS proof verification failed: {0}")]
     SpartanError(String),
     #[error("Length Error: SRS Length: {0}, Key Length: {0}")]
     KeyLengthError(usize, usize),
+    #[error("Proof deserialization failed: {0}")]
+    DeserializationError(ark_serialize::SerializationError),
+}
+
+#[derive(Error, Debug)]
+pub enum PreprocessingError {
+    #[error("Not a Jolt preprocessing file")]
+    InvalidMagic,
+    #[error("Preprocessing metadata is corrupted (config digest mismatch)")]
+    CorruptedMetadata,
+    #[error(
+        "Incompatible preprocessing: generated with {parameter} `{found}`, expected `{expected}`"
+    )]
+    Incompatible {
+        parameter: &'static str,
+        expected: String,
+        found: String,
+    },
+    #[error("Preprocessing I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Preprocessing (de)serialization failed: {0}")]
+    Serialization(#[from] ark_serialize::SerializationError),
+}
+
+/// Errors returned when loading a commitment key with
+/// [`CheckedKey::load_checked`](crate::poly::commitment::key_file::CheckedKey::load_checked).
+#[derive(Error, Debug)]
+pub enum KeyError {
+    #[error("Not a Jolt key file")]
+    InvalidMagic,
+    #[error("Key file holds a `{found}`, expected a `{expected}`")]
+    WrongKind { expected: String, found: String },
+    #[error("Key file is corrupted (digest mismatch)")]
+    DigestMismatch,
+    #[error("Malformed key: {0}")]
+    Malformed(&'static str),
+    #[error("Key I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Key (de)serialization failed: {0}")]
+    Serialization(#[from] ark_serialize::SerializationError),
+}
+
+/// Errors returned when decoding a document with [`from_json`](crate::utils::json::from_json).
+#[derive(Error, Debug)]
+pub enum JsonError {
+    #[error("Unsupported JSON schema version {found}, expected {expected}")]
+    UnsupportedSchemaVersion { expected: u32, found: u32 },
+    #[error("JSON (de)serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Errors returned by [`Jolt::prove`](crate::jolt::vm::Jolt::prove) and
+/// [`Jolt::verify`](crate::jolt::vm::Jolt::verify).
+///
+/// [`JoltError::is_soundness_failure`] distinguishes proofs that were rejected by the verifier
+/// from errors caused by the program, its inputs, or the prover/verifier configuration.
+#[derive(Error, Debug)]
+pub enum JoltError {
+    #[error(
+        "Trace length {trace_length} exceeds the maximum of {max_trace_length} supported by the preprocessing"
+    )]
+    TraceTooLong {
+        trace_length: usize,
+        max_trace_length: usize,
+    },
+    #[error("{0}")]
+    GuestPanic(#[from] GuestPanic),
+    #[error("Guest accessed address {address:#X}, outside of its configured address space (memory_size = {memory_size} bytes)")]
+    MemoryOutOfBounds { address: u64, memory_size: u64 },
+    #[error("Guest stack overflow at PC {pc:#X}: stack pointer {sp:#X} is below the bottom of the stack ({stack_bottom:#X}); increase stack_size")]
+    StackOverflow { pc: u64, sp: u64, stack_bottom: u64 },
+    #[error("Program input is {size} bytes, but max_input_size is {max_size} bytes")]
+    InvalidInputSize { size: usize, max_size: u64 },
+    #[error("Program output is {size} bytes, but max_output_size is {max_size} bytes")]
+    InvalidOutputSize { size: usize, max_size: u64 },
+    #[error("Blobs take {size} bytes, but max_blob_size is {max_size} bytes")]
+    InvalidBlobSize { size: u64, max_size: u64 },
+    #[error("The data of the program's blobs must be attached to its program I/O to verify it")]
+    MissingBlobs,
+    #[error("SRS supports polynomials of size at most {available}, but {required} is required")]
+    SrsTooSmall { required: usize, available: usize },
+    #[error("Opening proof verification failed: {0}")]
+    Pcs(ProofVerifyError),
+    #[error("Proving was cancelled")]
+    Cancelled,
+    #[error("Transcript mismatch: proof was generated for {0}")]
+    TranscriptMismatch(String),
+    #[error(transparent)]
+    Verification(#[from] ProofVerifyError),
+    #[error(transparent)]
+    Preprocessing(#[from] PreprocessingError),
+}
+
+impl From<GuestFault> for JoltError {
+    fn from(fault: GuestFault) -> Self {
+        match fault {
+            GuestFault::MemoryOutOfBounds {
+                address,
+                memory_size,
+                ..
+            } => Self::MemoryOutOfBounds {
+                address,
+                memory_size,
+            },
+            GuestFault::StackOverflow {
+                pc,
+                sp,
+                stack_bottom,
+            } => Self::StackOverflow {
+                pc,
+                sp,
+                stack_bottom,
+            },
+        }
+    }
+}
+
+impl JoltError {
+    /// Returns whether the error indicates an invalid proof, as opposed to a problem with the
+    /// program, its inputs, or the configuration that can be fixed by the caller.
+    pub fn is_soundness_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Pcs(_) | Self::TranscriptMismatch(_) | Self::Verification(_)
+        )
+    }
 }
@@ -0,0 +1,164 @@
+//! JSON encoding of verifier keys and proofs, for tooling outside of Rust (e.g. in Python or
+//! TypeScript) that shouldn't have to reimplement arkworks' canonical serialization.
+//!
+//! [`to_json`] wraps a value in a document along with [`SCHEMA_VERSION`]:
+//!
+//! ```text
+//! { "schema_version": 1, "data": { ... } }
+//! ```
+//!
+//! Structs are JSON objects whose keys are their field names, and sequences are arrays. Field
+//! and group elements, as well as commitments, are encoded by [`Hex`] as 0x-prefixed hex strings
+//! of their uncompressed arkworks encoding, which is:
+//! - for a prime field element, its canonical (i.e. not Montgomery) representative as a
+//!   little-endian integer, e.g. 32 bytes for BN254;
+//! - for an extension field element, its coefficients in order, e.g. `c0` then `c1` in Fq2;
+//! - for an affine point, `x` then `y`, with the point at infinity encoded as (0, 0) with bit 6 of
+//!   the last byte set.
+//!
+//! The schema version is bumped whenever a change to any of the encoded types changes the JSON.
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use super::errors::JsonError;
+
+/// Version of the JSON encoding of the types in this crate, stored in every document.
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct Document<'a, T> {
+    schema_version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct OwnedDocument {
+    schema_version: u32,
+    data: serde_json::Value,
+}
+
+/// Encodes `value` as a JSON document of the current [`SCHEMA_VERSION`].
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, JsonError> {
+    Ok(serde_json::to_string(&Document {
+        schema_version: SCHEMA_VERSION,
+        data: value,
+    })?)
+}
+
+/// Decodes a JSON document written by [`to_json`], which must be of the current
+/// [`SCHEMA_VERSION`].
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, JsonError> {
+    let document: OwnedDocument = serde_json::from_str(json)?;
+    if document.schema_version != SCHEMA_VERSION {
+        return Err(JsonError::UnsupportedSchemaVersion {
+            expected: SCHEMA_VERSION,
+            found: document.schema_version,
+        });
+    }
+    Ok(serde_json::from_value(document.data)?)
+}
+
+/// `serde_with` adapter encoding arkworks types as hex strings (see the
+/// [module documentation](self)), e.g. `#[serde_as(as = "Vec<Hex>")]` for a `Vec<F>`.
+pub struct Hex;
+
+impl<T: CanonicalSerialize> SerializeAs<T> for Hex {
+    fn serialize_as<S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        let mut bytes = Vec::with_capacity(value.uncompressed_size());
+        value
+            .serialize_uncompressed(&mut bytes)
+            .map_err(S::Error::custom)?;
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+impl<'de, T: CanonicalDeserialize> DeserializeAs<'de, T> for Hex {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        use serde::de::Error;
+
+        let string = String::deserialize(deserializer)?;
+        let digits = string
+            .strip_prefix("0x")
+            .ok_or_else(|| D::Error::custom("expected a 0x-prefixed hex string"))?;
+        let bytes = hex::decode(digits).map_err(D::Error::custom)?;
+        let mut reader = bytes.as_slice();
+        let value = T::deserialize_uncompressed(&mut reader).map_err(D::Error::custom)?;
+        if !reader.is_empty() {
+            return Err(D::Error::custom(format!(
+                "{} trailing bytes after encoded value",
+                reader.len()
+            )));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Affine, G2Affine};
+    use ark_ec::AffineRepr;
+    use ark_std::{test_rng, UniformRand};
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Elements {
+        #[serde_as(as = "Vec<Hex>")]
+        scalars: Vec<Fr>,
+        #[serde_as(as = "Hex")]
+        g1: G1Affine,
+        #[serde_as(as = "[Hex; 2]")]
+        g2: [G2Affine; 2],
+    }
+
+    #[test]
+    fn elements_round_trip() {
+        let mut rng = test_rng();
+        let elements = Elements {
+            scalars: vec![Fr::from(1u64), Fr::rand(&mut rng)],
+            g1: G1Affine::zero(),
+            g2: [G2Affine::generator(), G2Affine::rand(&mut rng)],
+        };
+        let json = to_json(&elements).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        // Little-endian: 1 is a single byte followed by zeroes
+        let one = format!("0x01{}", "00".repeat(31));
+        assert_eq!(value["data"]["scalars"][0], one.as_str());
+        assert_eq!(value["data"]["g2"][0].as_str().unwrap().len(), 2 + 2 * 128);
+
+        assert_eq!(from_json::<Elements>(&json).unwrap(), elements);
+    }
+
+    #[test]
+    fn rejects_other_schema_versions_and_malformed_elements() {
+        let json = to_json(&7u64).unwrap();
+        let newer = json.replace(
+            &format!("\"schema_version\":{SCHEMA_VERSION}"),
+            &format!("\"schema_version\":{}", SCHEMA_VERSION + 1),
+        );
+        assert!(matches!(
+            from_json::<u64>(&newer),
+            Err(JsonError::UnsupportedSchemaVersion { .. })
+        ));
+
+        #[serde_as]
+        #[derive(Serialize, Deserialize)]
+        struct Scalar(#[serde_as(as = "Hex")] Fr);
+        let json = to_json(&Scalar(Fr::from(7u64))).unwrap();
+        for malformed in [
+            json.replace("0x07", "07"),
+            json.replace("0x07", "0x0700"),
+            json.replace("0x07", "0xzz"),
+        ] {
+            assert!(from_json::<Scalar>(&malformed).is_err());
+        }
+        assert!(from_json::<Scalar>(&json).is_ok());
+    }
+}
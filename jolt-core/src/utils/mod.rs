@@ -7,10 +7,12 @@ use rayon::prelude::*;
 pub mod errors;
 pub mod gaussian_elimination;
 pub mod instruction_utils;
+pub mod json;
 pub mod math;
 pub mod profiling;
 pub mod sol_types;
 pub mod thread;
+pub mod throttle;
 pub mod transcript;
 
 /// Converts an integer value to a bitvector (all values {0,1}) of field elements.
@@ -1,9 +1,12 @@
-use ark_bn254::Bn254;
+use ark_bn254::{Bn254, Fr};
 use ark_ff::BigInteger;
 use ark_ff::PrimeField;
 
 use crate::field::JoltField;
-use crate::poly::commitment::hyperkzg::{HyperKZG, HyperKZGProof, HyperKZGVerifierKey};
+use crate::jolt::vm::rv32i_vm::RV32IJoltProof;
+use crate::poly::commitment::hyperkzg::{
+    HyperKZG, HyperKZGOpening, HyperKZGProof, HyperKZGVerifierKey,
+};
 use crate::r1cs::inputs::JoltR1CSInputs;
 use crate::r1cs::spartan::UniformSpartanProof;
 use crate::subprotocols::grand_product::BatchedGrandProductLayerProof;
@@ -23,6 +26,15 @@ sol!(struct HyperKZGProofSol {
     uint256[] v_y;
 });
 
+// A single opening in a batch submitted to `HyperKZG.aggregate_verify`
+sol!(struct HyperKZGOpeningSol {
+    uint256 commitment_x;
+    uint256 commitment_y;
+    uint256[] point;
+    uint256 claim;
+    HyperKZGProofSol proof;
+});
+
 sol!(struct VK {
     uint256 VK_g1_x;
     uint256 VK_g1_y;
@@ -60,6 +72,26 @@ sol!(struct GrandProductProof {
     }
 );
 
+// The public part of a Jolt proof: the guest and verifier key it was generated for (see
+// `JoltPreprocessing::digest`), which a contract can check against a registered one, the
+// guest's I/O, the cycle limit it was halted at (if any), the application namespace bound into
+// its transcript (if any) and the hashes of the blobs it was given
+sol!(
+    struct JoltProofHeader {
+        bytes32 preprocessingDigest;
+        uint256 traceLength;
+        bytes inputs;
+        bytes outputs;
+        bool panic;
+        bool exceededMaxCycles;
+        uint64 maxCycles; // 0 unless exceededMaxCycles
+        bool hasNamespace;
+        string namespaceAppId; // Empty unless hasNamespace
+        uint64 namespaceVersion; // 0 unless hasNamespace
+        bytes32[] blobHashes;
+    }
+);
+
 impl Into<HyperKZGProofSol> for &HyperKZGProof<Bn254> {
     fn into(self) -> HyperKZGProofSol {
         let mut com = vec![];
@@ -102,6 +134,19 @@ impl Into<HyperKZGProofSol> for &HyperKZGProof<Bn254> {
     }
 }
 
+impl Into<HyperKZGOpeningSol> for &HyperKZGOpening<'_, Bn254> {
+    fn into(self) -> HyperKZGOpeningSol {
+        let commitment = self.commitment.0;
+        HyperKZGOpeningSol {
+            commitment_x: U256::from_be_slice(&commitment.x.into_bigint().to_bytes_be()),
+            commitment_y: U256::from_be_slice(&commitment.y.into_bigint().to_bytes_be()),
+            point: self.point.iter().map(|i| into_uint256(*i)).collect(),
+            claim: into_uint256(self.eval),
+            proof: self.proof.into(),
+        }
+    }
+}
+
 impl Into<VK> for &HyperKZGVerifierKey<Bn254> {
     fn into(self) -> VK {
         let g1 = self.kzg_vk.g1;
@@ -177,6 +222,37 @@ impl Into<SpartanProof>
     }
 }
 
+impl Into<JoltProofHeader> for &RV32IJoltProof<Fr, HyperKZG<Bn254>> {
+    fn into(self) -> JoltProofHeader {
+        JoltProofHeader {
+            preprocessingDigest: self.preprocessing_digest.into(),
+            traceLength: U256::from(self.trace_length),
+            inputs: self.program_io.inputs.clone().into(),
+            outputs: self.program_io.outputs.clone().into(),
+            panic: self.program_io.panic,
+            exceededMaxCycles: self.program_io.exceeded_max_cycles.is_some(),
+            maxCycles: self.program_io.exceeded_max_cycles.unwrap_or(0),
+            hasNamespace: self.namespace.is_some(),
+            namespaceAppId: self
+                .namespace
+                .as_ref()
+                .map(|namespace| namespace.app_id.clone())
+                .unwrap_or_default(),
+            namespaceVersion: self
+                .namespace
+                .as_ref()
+                .map_or(0, |namespace| namespace.version),
+            blobHashes: self
+                .program_io
+                .blobs
+                .hashes()
+                .iter()
+                .map(|hash| (*hash).into())
+                .collect(),
+        }
+    }
+}
+
 impl<F: JoltField> Into<GKRLayer> for BatchedGrandProductLayerProof<F> {
     fn into(self) -> GKRLayer {
         let left = self.left_claims.into_iter().map(into_uint256).collect();
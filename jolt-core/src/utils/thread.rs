@@ -11,6 +11,43 @@ where
     rayon::spawn(move || drop(data));
 }
 
+/// Splits `threads` between two tasks in proportion to their weights, giving each at least one.
+fn split_threads(threads: usize, weight_a: usize, weight_b: usize) -> (usize, usize) {
+    let total_weight = (weight_a as u128 + weight_b as u128).max(1);
+    let threads_a =
+        ((threads as u128 * weight_a as u128 + total_weight / 2) / total_weight) as usize;
+    let threads_a = threads_a.clamp(1, threads - 1);
+    (threads_a, threads - threads_a)
+}
+
+/// Like [`rayon::join`], but runs `a` and `b` on disjoint slices of the current pool's threads,
+/// sized in proportion to `weight_a` and `weight_b` (e.g. the total MSM size of two independent
+/// commitment batches). This keeps a small task from repeatedly stealing threads from a large
+/// one, so both finish at about the same time. Falls back to running the tasks one after the
+/// other if there is only a single thread.
+pub fn join_weighted<A, B, RA, RB>(weight_a: usize, a: A, weight_b: usize, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    let threads = rayon::current_num_threads();
+    if threads < 2 {
+        return (a(), b());
+    }
+    let (threads_a, threads_b) = split_threads(threads, weight_a, weight_b);
+    let pool_a = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads_a)
+        .build()
+        .unwrap();
+    let pool_b = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads_b)
+        .build()
+        .unwrap();
+    rayon::join(|| pool_a.install(a), || pool_b.install(b))
+}
+
 pub fn allocate_vec_in_background<T: Clone + Send + 'static>(
     value: T,
     size: usize,
@@ -135,3 +172,25 @@ where
     let (res_a, (res_b, res_c)) = rayon::join(oper_a, || rayon::join(oper_b, oper_c));
     (res_a, res_b, res_c)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_join() {
+        assert_eq!(split_threads(8, 3, 1), (6, 2));
+        assert_eq!(split_threads(8, 1, 0), (7, 1));
+        assert_eq!(split_threads(8, 0, 0), (1, 7));
+        assert_eq!(split_threads(2, 1 << 40, 1), (1, 1));
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let (a, b) = pool.install(|| {
+            join_weighted(3, rayon::current_num_threads, 1, rayon::current_num_threads)
+        });
+        assert_eq!((a, b), (3, 1));
+    }
+}
@@ -0,0 +1,158 @@
+//! Throttled proving for shared or thermally constrained machines (laptops, CI runners).
+//!
+//! By default the prover saturates every core for the duration of the proof. A
+//! [`ThrottleConfig`] caps sustained CPU utilization in two ways: by running the prover on a
+//! dedicated rayon pool with fewer threads, and by duty-cycling that pool, i.e. periodically
+//! parking all of its threads so that each one is busy for at most `duty_cycle` of every
+//! `period`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThrottleConfig {
+    /// Number of prover threads. Defaults to the number of available cores.
+    pub max_threads: Option<usize>,
+    /// Fraction of each `period` during which prover threads may run, in `(0, 1]`.
+    pub duty_cycle: f64,
+    pub period: Duration,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            max_threads: None,
+            duty_cycle: 1.0,
+            period: Duration::from_millis(100),
+        }
+    }
+}
+
+impl ThrottleConfig {
+    /// Limits sustained CPU utilization to `utilization` (in `(0, 1]`) of every core.
+    pub fn max_utilization(utilization: f64) -> Self {
+        assert!(
+            utilization > 0.0 && utilization <= 1.0,
+            "CPU utilization must be in (0, 1], got {utilization}"
+        );
+        Self {
+            duty_cycle: utilization,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+
+    pub fn with_period(mut self, period: Duration) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn num_threads(&self) -> usize {
+        let available = available_threads();
+        self.max_threads.unwrap_or(available).clamp(1, available)
+    }
+
+    /// Expected average CPU utilization across the whole machine, in `(0, 1]`.
+    pub fn cpu_utilization(&self) -> f64 {
+        self.num_threads() as f64 / available_threads() as f64 * self.duty_cycle
+    }
+
+    /// Estimated factor by which throttling increases proving time, assuming the prover
+    /// otherwise scales linearly with the number of cores.
+    pub fn estimated_slowdown(&self) -> f64 {
+        1.0 / self.cpu_utilization()
+    }
+
+    /// Runs `op` (typically [`Jolt::prove`](crate::jolt::vm::Jolt::prove)) under this
+    /// throttling configuration, logging the estimated time impact before starting. Callers
+    /// that want to report it themselves can use [`Self::cpu_utilization`] and
+    /// [`Self::estimated_slowdown`].
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        assert!(
+            self.duty_cycle > 0.0 && self.duty_cycle <= 1.0,
+            "duty cycle must be in (0, 1], got {}",
+            self.duty_cycle
+        );
+        tracing::info!(
+            cpu_utilization = self.cpu_utilization(),
+            threads = self.num_threads(),
+            duty_cycle = self.duty_cycle,
+            estimated_slowdown = self.estimated_slowdown(),
+            "Throttling prover to {:.0}% CPU utilization; expect proving to take ~{:.1}x longer",
+            self.cpu_utilization() * 100.0,
+            self.estimated_slowdown()
+        );
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads())
+            .build()
+            .unwrap();
+        if self.duty_cycle >= 1.0 {
+            return pool.install(op);
+        }
+
+        let busy = self.period.mul_f64(self.duty_cycle);
+        let idle = self.period - busy;
+        let done = AtomicBool::new(false);
+        thread::scope(|s| {
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    thread::sleep(busy);
+                    // Blocks until every pool thread has picked up (and finished) the sleep
+                    pool.broadcast(|_| thread::sleep(idle));
+                }
+            });
+            let result = pool.install(op);
+            done.store(true, Ordering::Relaxed);
+            result
+        })
+    }
+}
+
+fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    #[test]
+    fn estimated_slowdown() {
+        assert_eq!(ThrottleConfig::default().estimated_slowdown(), 1.0);
+        assert_eq!(
+            ThrottleConfig::max_utilization(0.5).estimated_slowdown(),
+            2.0
+        );
+
+        let config = ThrottleConfig::max_utilization(0.5).with_max_threads(1);
+        assert_eq!(config.num_threads(), 1);
+        assert_eq!(
+            config.estimated_slowdown(),
+            2.0 * available_threads() as f64
+        );
+    }
+
+    #[test]
+    fn throttled_install() {
+        let config = ThrottleConfig::max_utilization(0.5)
+            .with_max_threads(2)
+            .with_period(Duration::from_millis(10));
+        let (sum, num_threads) = config.install(|| {
+            let sum: u64 = (0..1_000_000u64).into_par_iter().sum();
+            (sum, rayon::current_num_threads())
+        });
+        assert_eq!(sum, 499_999_500_000);
+        assert_eq!(num_threads, config.num_threads());
+    }
+}
@@ -1,7 +1,32 @@
+use crate::curve::JoltCurve;
 use crate::field::JoltField;
 use ark_ec::{AffineRepr, CurveGroup};
-use ark_serialize::CanonicalSerialize;
+use ark_ff::Zero;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
+use std::fmt;
+use std::io::{Read, Write};
+
+/// Number of elements per digest when [`TranscriptVersion::V2`] absorbs a vector. Vectors with
+/// fewer elements are absorbed one element at a time by every version.
+pub const ABSORB_CHUNK_SIZE: usize = 1 << 10;
+
+/// How a [`ProofTranscript`] absorbs vectors. The prover and verifier must use the same version.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TranscriptVersion {
+    /// Absorbs every element of a vector in its own round, as the Solidity verifier does.
+    #[default]
+    V1,
+    /// Absorbs vectors of at least [`ABSORB_CHUNK_SIZE`] elements as the Keccak digests of
+    /// chunks of that many elements, followed by the vector's length. The digests of a slice
+    /// are computed in parallel, so absorbing a long vector costs a round per chunk instead of
+    /// per element. Shorter vectors are absorbed as in [`TranscriptVersion::V1`].
+    V2,
+}
 
 /// Represents the current state of the protocol's Fiat-Shamir transcript.
 #[derive(Clone)]
@@ -10,6 +35,7 @@ pub struct ProofTranscript {
     pub state: [u8; 32],
     /// We append an ordinal to each invocation of the hash
     n_rounds: u32,
+    version: TranscriptVersion,
     #[cfg(test)]
     /// A complete history of the transcript's `state`; used for testing.
     state_history: Vec<[u8; 32]>,
@@ -36,6 +62,7 @@ impl ProofTranscript {
         Self {
             state: out.into(),
             n_rounds: 0,
+            version: TranscriptVersion::V1,
             #[cfg(test)]
             state_history: vec![out.into()],
             #[cfg(test)]
@@ -43,6 +70,16 @@ impl ProofTranscript {
         }
     }
 
+    /// Absorbs vectors according to `version` from now on.
+    pub fn with_version(mut self, version: TranscriptVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn version(&self) -> TranscriptVersion {
+        self.version
+    }
+
     #[cfg(test)]
     /// Compare this transcript to `other` and panic if/when they deviate.
     /// Typically used to compare the verifier's transcript to the prover's.
@@ -61,6 +98,27 @@ impl ProofTranscript {
             .chain_update(&packed)
     }
 
+    /// Derives a transcript for a sub-protocol (e.g. a proof stage) that runs independently of
+    /// other sub-protocols forked from the same state. The fork is bound back into this
+    /// transcript with [`Self::join`].
+    pub fn fork(&self, label: &'static [u8]) -> Self {
+        let mut fork = self.clone();
+        #[cfg(test)]
+        {
+            // The expected history only covers this transcript, not its forks; a deviation
+            // within a fork is instead detected when it is joined.
+            fork.expected_state_history = None;
+        }
+        fork.append_message(label);
+        fork
+    }
+
+    /// Binds the final state of a transcript returned by [`Self::fork`] into this one. Forks
+    /// must be joined in the same order by the prover and verifier.
+    pub fn join(&mut self, fork: &Self) {
+        self.append_bytes(&fork.state);
+    }
+
     pub fn append_message(&mut self, msg: &'static [u8]) {
         // We require all messages to fit into one evm word and then right pad them
         // right padding matches the format of the strings when cast to bytes 32 in solidity
@@ -96,49 +154,103 @@ impl ProofTranscript {
 
     pub fn append_scalar<F: JoltField>(&mut self, scalar: &F) {
         let mut buf = vec![];
-        scalar.serialize_uncompressed(&mut buf).unwrap();
-        // Serialize uncompressed gives the scalar in LE byte order which is not
-        // a natural representation in the EVM for scalar math so we reverse
-        // to get an EVM compatible version.
-        buf = buf.into_iter().rev().collect();
+        scalar_bytes(scalar, &mut buf);
         self.append_bytes(&buf);
     }
 
     pub fn append_scalars<F: JoltField>(&mut self, scalars: &[F]) {
+        self.append_vector(scalars, scalar_bytes);
+    }
+
+    /// Like [`Self::append_scalars`], but takes the scalars from an iterator, so they needn't
+    /// be collected first. Absorbs exactly what [`Self::append_scalars`] would absorb given the
+    /// same scalars as a slice, hashing chunks as they arrive instead of in parallel.
+    pub fn append_scalars_iter<F: JoltField>(&mut self, scalars: impl IntoIterator<Item = F>) {
         self.append_message(b"begin_append_vector");
-        for item in scalars.iter() {
-            self.append_scalar(item);
+        let mut scalars = scalars.into_iter();
+        if self.version == TranscriptVersion::V1 {
+            for item in scalars {
+                self.append_scalar(&item);
+            }
+            self.append_message(b"end_append_vector");
+            return;
+        }
+
+        let first_chunk: Vec<F> = scalars.by_ref().take(ABSORB_CHUNK_SIZE).collect();
+        if first_chunk.len() < ABSORB_CHUNK_SIZE {
+            for item in first_chunk.iter() {
+                self.append_scalar(item);
+            }
+            self.append_message(b"end_append_vector");
+            return;
+        }
+
+        let mut chunk = Keccak256::new();
+        let mut bytes = vec![];
+        for item in first_chunk.iter() {
+            scalar_bytes(item, &mut bytes);
         }
+        chunk.update(&bytes);
+        let mut len = first_chunk.len();
+        drop(first_chunk);
+        for item in scalars {
+            if len % ABSORB_CHUNK_SIZE == 0 {
+                let digest: [u8; 32] = std::mem::take(&mut chunk).finalize().into();
+                self.append_bytes(&digest);
+            }
+            bytes.clear();
+            scalar_bytes(&item, &mut bytes);
+            chunk.update(&bytes);
+            len += 1;
+        }
+        let digest: [u8; 32] = chunk.finalize().into();
+        self.append_bytes(&digest);
+        self.append_u64(len as u64);
         self.append_message(b"end_append_vector");
     }
 
     pub fn append_point<G: CurveGroup>(&mut self, point: &G) {
-        // If we add the point at infinity then we hash over a region of zeros
-        if point.is_zero() {
-            self.append_bytes(&[0_u8; 64]);
-            return;
-        }
+        let mut buf = vec![];
+        point_bytes(point, &mut buf);
+        self.append_bytes(&buf);
+    }
 
-        let aff = point.into_affine();
-        let mut x_bytes = vec![];
-        let mut y_bytes = vec![];
-        // The native serialize for the points are le encoded in x,y format and simply reversing
-        // can lead to errors so we extract the affine coordinates and the encode them be before writing
-        let x = aff.x().unwrap();
-        x.serialize_compressed(&mut x_bytes).unwrap();
-        x_bytes = x_bytes.into_iter().rev().collect();
-        let y = aff.y().unwrap();
-        y.serialize_compressed(&mut y_bytes).unwrap();
-        y_bytes = y_bytes.into_iter().rev().collect();
-
-        let hasher = self.hasher().chain_update(x_bytes).chain_update(y_bytes);
-        self.update_state(hasher.finalize().into());
+    /// Like [`Self::append_point`], for a point of any [`JoltCurve`] backend.
+    pub fn append_curve_point<G: JoltCurve>(&mut self, point: &G) {
+        let mut buf = vec![];
+        point.transcript_bytes(&mut buf);
+        self.append_bytes(&buf);
     }
 
     pub fn append_points<G: CurveGroup>(&mut self, points: &[G]) {
+        self.append_vector(points, point_bytes);
+    }
+
+    /// Absorbs `items`, encoding each with `encode`, according to [`Self::version`].
+    fn append_vector<T: Sync>(&mut self, items: &[T], encode: fn(&T, &mut Vec<u8>)) {
         self.append_message(b"begin_append_vector");
-        for item in points.iter() {
-            self.append_point(item);
+        if self.version == TranscriptVersion::V1 || items.len() < ABSORB_CHUNK_SIZE {
+            let mut buf = vec![];
+            for item in items.iter() {
+                buf.clear();
+                encode(item, &mut buf);
+                self.append_bytes(&buf);
+            }
+        } else {
+            let digests: Vec<[u8; 32]> = items
+                .par_chunks(ABSORB_CHUNK_SIZE)
+                .map(|chunk| {
+                    let mut bytes = vec![];
+                    for item in chunk {
+                        encode(item, &mut bytes);
+                    }
+                    Keccak256::digest(&bytes).into()
+                })
+                .collect();
+            for digest in digests.iter() {
+                self.append_bytes(digest);
+            }
+            self.append_u64(items.len() as u64);
         }
         self.append_message(b"end_append_vector");
     }
@@ -209,6 +321,181 @@ impl ProofTranscript {
     }
 }
 
+/// Appends `scalar` in big-endian, the natural representation for scalar math in the EVM.
+fn scalar_bytes<F: JoltField>(scalar: &F, buf: &mut Vec<u8>) {
+    let start = buf.len();
+    // Serialize uncompressed gives the scalar in LE byte order, so we reverse it
+    scalar.serialize_uncompressed(&mut *buf).unwrap();
+    buf[start..].reverse();
+}
+
+/// Appends the affine coordinates of `point` in big-endian, or zeros as large as the
+/// coordinates of any other point if it is the point at infinity.
+pub(crate) fn point_bytes<G: CurveGroup>(point: &G, buf: &mut Vec<u8>) {
+    if point.is_zero() {
+        let coordinate_size = G::BaseField::zero().compressed_size();
+        buf.resize(buf.len() + 2 * coordinate_size, 0);
+        return;
+    }
+
+    // The native serialize for the points are le encoded in x,y format and simply reversing
+    // can lead to errors so we extract the affine coordinates and the encode them be before writing
+    let aff = point.into_affine();
+    for coordinate in [aff.x().unwrap(), aff.y().unwrap()] {
+        let start = buf.len();
+        coordinate.serialize_compressed(&mut *buf).unwrap();
+        buf[start..].reverse();
+    }
+}
+
 pub trait AppendToTranscript {
     fn append_to_transcript(&self, transcript: &mut ProofTranscript);
 }
+
+/// Domain separator for the proofs of one application. Absorbing it at the start of the
+/// transcript means that a proof generated for one application (or version of it) is rejected
+/// by the verifier of another, even if both prove the same guest.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct TranscriptNamespace {
+    pub app_id: String,
+    pub version: u64,
+}
+
+impl CanonicalSerialize for TranscriptVersion {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let version: u8 = match self {
+            TranscriptVersion::V1 => 1,
+            TranscriptVersion::V2 => 2,
+        };
+        version.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        0u8.serialized_size(compress)
+    }
+}
+
+impl Valid for TranscriptVersion {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for TranscriptVersion {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(reader, compress, validate)? {
+            1 => Ok(TranscriptVersion::V1),
+            2 => Ok(TranscriptVersion::V2),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+impl TranscriptNamespace {
+    pub fn new(app_id: impl Into<String>, version: u64) -> Self {
+        Self {
+            app_id: app_id.into(),
+            version,
+        }
+    }
+}
+
+impl AppendToTranscript for TranscriptNamespace {
+    fn append_to_transcript(&self, transcript: &mut ProofTranscript) {
+        transcript.append_message(b"app namespace");
+        transcript.append_bytes(self.app_id.as_bytes());
+        transcript.append_u64(self.version);
+    }
+}
+
+impl fmt::Display for TranscriptNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` version {}", self.app_id, self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective};
+    use ark_std::UniformRand;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn transcript(version: TranscriptVersion) -> ProofTranscript {
+        ProofTranscript::new(b"test").with_version(version)
+    }
+
+    #[test]
+    fn short_vectors_are_absorbed_the_same_by_every_version() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let scalars: Vec<Fr> = (0..ABSORB_CHUNK_SIZE - 1)
+            .map(|_| Fr::rand(&mut rng))
+            .collect();
+        let points: Vec<G1Projective> = (0..8).map(|_| G1Projective::rand(&mut rng)).collect();
+
+        let mut states = vec![];
+        for version in [TranscriptVersion::V1, TranscriptVersion::V2] {
+            let mut t = transcript(version);
+            t.append_scalars(&scalars);
+            t.append_points(&points);
+            states.push(t.state);
+
+            let mut t = transcript(version);
+            t.append_scalars_iter(scalars.iter().copied());
+            t.append_points(&points);
+            states.push(t.state);
+        }
+        assert!(states.iter().all(|state| *state == states[0]));
+    }
+
+    #[test]
+    fn long_vectors_are_chunked_in_v2() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        for len in [
+            ABSORB_CHUNK_SIZE,
+            2 * ABSORB_CHUNK_SIZE,
+            3 * ABSORB_CHUNK_SIZE + 5,
+        ] {
+            let scalars: Vec<Fr> = (0..len).map(|_| Fr::rand(&mut rng)).collect();
+
+            let mut v1 = transcript(TranscriptVersion::V1);
+            v1.append_scalars(&scalars);
+            let mut v1_iter = transcript(TranscriptVersion::V1);
+            v1_iter.append_scalars_iter(scalars.iter().copied());
+            assert_eq!(v1.state, v1_iter.state);
+
+            let mut v2 = transcript(TranscriptVersion::V2);
+            v2.append_scalars(&scalars);
+            let mut v2_iter = transcript(TranscriptVersion::V2);
+            v2_iter.append_scalars_iter(scalars.iter().copied());
+            assert_eq!(v2.state, v2_iter.state);
+            assert_ne!(v1.state, v2.state);
+            // A round per chunk, plus the length and the two delimiters
+            assert_eq!(v2.n_rounds as usize, len.div_ceil(ABSORB_CHUNK_SIZE) + 3);
+
+            // The length is bound, so dropping the last element changes the state
+            let mut truncated = transcript(TranscriptVersion::V2);
+            truncated.append_scalars(&scalars[..len - 1]);
+            assert_ne!(v2.state, truncated.state);
+        }
+    }
+}
@@ -1 +0,0 @@
-echo 0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000026e000000000000000000000000000000000000000000000000000000000000028a000000000000000000000000000000000000000000000000000000000000000e00b5fe282653c469feb3b2d21103c83a814754c7a6a11c4b1a0784e5442ac27f714ca7c09913350af2731332af4d65668b05b2342a43d5c2eac5ccbe8ae3608fe0e25512b55d1d4966dd2183e2e3fce96a74617562ab86c42abe5f3f0a3e409750000000000000000000000000000000000000000000000000000000000000b2000000000000000000000000000000000000000000000000000000000000013e00000000000000000000000000000000000000000000000000000000000001f20000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000028000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000380000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000004800000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000058000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000680000000000000000000000000000000000000000000000000000000000000070000000000000000000000000000000000000000000000000000000000000007800000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000088000000000000000000000000000000000000000000000000000000000000009000000000000000000000000000000000000000000000000000000000000000980000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000000000000000000000000000000000002b6bd65ece201f51f35bda36e2cb6c91b8eb3b038371f3bf4d8e249a458839541c66d16d6da71fa3a1b11c7af6956775b68ae099986af49da010d0261509a99b000000000000000000000000000000000000000000000000000000000000000327f341855f26639ddffda61b8edcc6bcacf8aa859c8bc8dd16c5141f25c342a42105a1bf17d06695e72eaa2288f342602513745d5eca1d411bcef892922498901d904971dd8778cc9f23c746a7d6fcf326aaecc9e11c00413285112616384c27000000000000000000000000000000000000000000000000000000000000000328cdf1e1d4b435b3f59b1a5aa02a9c582ca61ed5c4497d6d2c15e2d7fd55565c19b7c2b69cbc7b300b340ece4f2c0e8fc0f154c4793ee7d119923381720f582e0091b4e740701ae9dd03892febcd9e36d294f6d23cc3f9d5f0020e264f8ae89c0000000000000000000000000000000000000000000000000000000000000003202d646c9935e01ccc29097bde5634681abbc5a0f90c039097b506d712f8831a25e15f413b904120b782b8b04d4f5f7330f21034dabb0b693b722887f68643a520a58bbc7273988911e94d5de1a0d8454972d60e00cec9f6142d3b4ee04b7dd000000000000000000000000000000000000000000000000000000000000000032052c7dba4d347f22997cc4020acb7d589a019f01f1b8074c81dbe46956edd140b802e956f88813dfb684fa994f1019493f6fda82eeb35e720f7c5d265a43bd80dfe3812abe6cfbd7423f7f1821f3ec48aef32e1112dc68dc03837294e9067c800000000000000000000000000000000000000000000000000000000000000032928645c46d1d66a42b4c56fe0870fe7aa1b97e20dd6a6c16c298a3f06fc05681e0789b2a071378faa423e39e6b2c1f3ecf839a1cc1bd175907b7ddfccb20189225f58ac3c2302f308aec25a0cbff9220b192cd533a502f0054e1a2932defb66000000000000000000000000000000000000000000000000000000000000000319f07d5df927fde6a553c0e5fa16e5b94f0eb3bab391d4c093b321ceb910234306515b7146e445b0f62e6d612644673b2f594a4efa09a704ccdfc4c87b239929300aee43fc9ad4e353f2aae9e67cd91e4e9589f09a779a583c2b72839052234400000000000000000000000000000000000000000000000000000000000000030ed84520ee93faf0f2153ad8d457a3cbcc0cbad48d4d8b3fbf983d46a71f7c832bd7a492c5964fa55d435360090815f1e3234f9c05b73a059a93ba729397b02b009b2fc1f724fb71f9debf590bfcb7e00cb54e4ee1be8dd7e79a01cd574b9ce3000000000000000000000000000000000000000000000000000000000000000310ce882cdfd43b2d2ce8f14d2bf6c29dd6019d4396c999e4297eeccd5bd3c5620e5c72ec95b12f76cd07fc827f37a373c0a9a53a3c31df0ccb98dcf8db0b9b951a02a3eab0fef1f193abc3d64cfcded10e52240139a38a4174da6748169dca3900000000000000000000000000000000000000000000000000000000000000031e173ac785db2c117884c8077cef0b9087144ba563a80943043ff109acb6ee65130ce246932640c52a85d3ec84dd4bc40e3946a562f93edfb208e18690b6957c151406e7b5b8b1944c7eb88e0f87cef6bcf4779c103476af32ae4cdc51cceed500000000000000000000000000000000000000000000000000000000000000030ac90f0c239312bc983fd80e93ad2e2f575a9cd1e582553232f237d5b6fad37a17cd98db6ea75f7c2f41d3eb16082b20bfe906259bc7a636729fb4322afef832158d9aa2c57d2b425c299aafbc3640b295ba1180d0e83ec0236b38246164085300000000000000000000000000000000000000000000000000000000000000030bb7b352232e3822418fbd297cf7b7cd8fa6f704097619588d3f9936fc7bb3751d5c1bb2eed290d942f5f7b19e6b059bb7233f88f09d2cc6d6914c9db40d32d2045013d523d630995965e0ba43e24b52772610f6443cced852f79bb7d7e586110000000000000000000000000000000000000000000000000000000000000003112d20317eb776da20f05345654f53fa46973ed92caff4abeb7bd1bbda7d406d21fe3219c303d174058d04bf57c99c0df2dea0c6dcc421282133ece9af9d44f915de43a704d1a10cfb2e934d17f200d8aedd6a5c57fbfbdcafa89e32dd0ded6c00000000000000000000000000000000000000000000000000000000000000030f667232222a209b6f5c343862a5901afab54b8157a72ae0b7a8766074a0a3ab0f99c211e44b8154c1038735d0be246751e99f3ec6a70857a70c24a916f699131f54b890c7bde33a413a329d2d78998ef29ebecb1dc1d05c494081f4086d551800000000000000000000000000000000000000000000000000000000000000031039a71fbba99a2f2d6bbd97281dedf43911a628b4be3689f797fe65bb287871217754e3832c2bf731a44c18edf22fb4eb8218801375bac981f5171152ff57c5135840d24d72ece3f5bce737b8eecb2648d3657c9e9d79bfa829ac7d882b826a00000000000000000000000000000000000000000000000000000000000000030cdf6d42945175466e2d77cf4e6a0d3f6f39b9417140ad141761db6726ebd4a41f1dd953ba8861cf6c01ec189cbed18c290663faeb915ea192237e7005e57f0522db8381c3faf8d7a4575f1c557f4252af893527ab0a44b50306cae4a9430a34000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000110000000000000000000000000000000000000000000000000000000000000220000000000000000000000000000000000000000000000000000000000000028000000000000000000000000000000000000000000000000000000000000002e0000000000000000000000000000000000000000000000000000000000000034000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000046000000000000000000000000000000000000000000000000000000000000004c00000000000000000000000000000000000000000000000000000000000000520000000000000000000000000000000000000000000000000000000000000058000000000000000000000000000000000000000000000000000000000000005e0000000000000000000000000000000000000000000000000000000000000064000000000000000000000000000000000000000000000000000000000000006a00000000000000000000000000000000000000000000000000000000000000700000000000000000000000000000000000000000000000000000000000000076000000000000000000000000000000000000000000000000000000000000007c000000000000000000000000000000000000000000000000000000000000008200000000000000000000000000000000000000000000000000000000000000002101c941762138205cbd70bafac7fcd938ecab393b196946592b9577d93686150259ac4860b1c43f3f1235959cd87544b07b2cfb7bed6ee46b4ea0415aaf7cf9c00000000000000000000000000000000000000000000000000000000000000022cafe91001cd82e75fd74ec2d64a89bcfec83fe0fe7ece693a54f861323a15dc1f57ee30f9ea37d2cbf0e68a8b8a66e4f412edfe9f15e4f6db3c3da9905b56de000000000000000000000000000000000000000000000000000000000000000203a2114c8ff73d3a5c6bf691697ecc365fe93e0eb5e5c7844b40258f83c3b50820612fec7f72e4d250656692f31f04af751236e510bd2dcaf72546df8fbb313b0000000000000000000000000000000000000000000000000000000000000002196220987611947ac67cbf14d6062e502e0fee43fd58abacbff8f50375e78f9f1488bec67934cd1ef74fa0364699b043dfd4351e0afc8c90dc6368055fa0c30b00000000000000000000000000000000000000000000000000000000000000022a9f82078eb44bbfb2e1ebb8e314e4a49a51e738963e0260e0a1078fe7e1ddfd17128a1a46d87193d0b6f40195ae7349de7fa2143fb4273a59f9a20f7cb09422000000000000000000000000000000000000000000000000000000000000000222516818ee8c9591d409fcda38d2d02ec51399720d4564463453b40b77437bd6145f4de4f3d145a94a6e8f333cfde3231e282c06ab4f3a9e32c5e6341ef908ac000000000000000000000000000000000000000000000000000000000000000203ab8c39c921cf34325d2b37e7d9e686b1410f068952bd769b80bfd0144265e92e2c5b310355065e93902050e93c3d72a4090844f16ce4f4cf023a0776bd3fdc000000000000000000000000000000000000000000000000000000000000000203258f8d9a3725deaec8de142e4a62238c4a5bd133296bf116af7bc94e0fb2f013315ca7f29943731d38a9e8c51c2533758b9a7a9a9829c843e61d542dd9d2a6000000000000000000000000000000000000000000000000000000000000000201340d7a6084aad679f07837ab5610839ba873cf37c9bb8766a5b4bcd2db63450a02eb02b0e8081b338f918b805b7687516474190def832216606ec6b5b677ee0000000000000000000000000000000000000000000000000000000000000002237bbbd96f79d718b390fe5959d96ce7b95103871469fa798f8f02410008896b13ae2196e33f7c1aeb208371c3a596b1a28727565c207ca203532849951a0b5f00000000000000000000000000000000000000000000000000000000000000022b25e1be34d531b3462e5489f2a63dd9661f0e0e57b8d91d85d1559c1218691d09aed77541a0850daf83dd93b22390277122994e86faef5c7d6484d9b1cd3605000000000000000000000000000000000000000000000000000000000000000203fe30d7d869544de146371183d8281d8a4cc9f5a6ec3e043c7d1ba4399194e0018240b3d4431f35c66cf6615db4b3fd623c7b0112eb9eb8fb06934ed49487db000000000000000000000000000000000000000000000000000000000000000226a9b2f5ff710766f9af533305a8201167cdc6c02337b18c40979431c918d0f803962ae20f9d69baa8ffd21440c6ba2f4ca76a2458a9e089aa39c77f83f9a9200000000000000000000000000000000000000000000000000000000000000002190d22a3a42d5877d264cbe1fe27f80d97c96871eeb0dc2f4c564ad9ffa85dc1154c9ce5c15499e43cb109865811f3f38ce76a1d5f1492ce8fe9bc4e5e1ee31e0000000000000000000000000000000000000000000000000000000000000002267aade2927e15dc20b6ec5436aba93cea294a31f18e08801aeec211d0c8b08e0bc27cc52eed966dcb2e737bbb7cddfab32907311180fcbda6bdec79a7df0fc400000000000000000000000000000000000000000000000000000000000000022a8cefaf6fb2ab7a42facf617587315b3df58cfc04c284c6694df00b3c5d76291bed76dcdcea35441c1ae85a14c14ae3c74a31683ef97b642b39f736441054df00000000000000000000000000000000000000000000000000000000000000022b10a7f4b8deed004196db853b179cc926dc45edf0acf1847d14cd0b7d2990b72e2a890be9c458450fc64a9b48aec84c074e3495dfea2d637d8022df4ae275a500000000000000000000000000000000000000000000000000000000000000592f5d83fe181b6b305a82b5082dbcd70a8efffd34fa4a42281a6a3f99476173822f5d83fe181b6b305a82b5082dbcd70a8efffd34fa4a42281a6a3f99476173822f5d83fe181b6b305a82b5082dbcd70a8efffd34fa4a42281a6a3f994761738229316add216e5f3b56a2069d814e2561493a75d214bbe8a7bb4cf01927acf8321a103e508bcc5a34b432ee1b31549071410d92574b1c3c4deac0612b9ee9bd3f0f42ca3040decf7db344dbf7bd13c9698a7eb4785a31126006ea52a39b3e057126256707484acdcbe198b0c25eeb5fee228e5c3aa403618270500491004f618d097bac9123d8f7d88250287f48842bef8618d172693deb02716f57d3b9311be812271503c573aa677f016e2ee66fb2b76c26836199594fd740649a945eb130090a76a12397d3df06e47f70b71e504b39eb581b01f0eef7fb6edc724fd6390d532479fae2caaa08ac28011d55e31656a9194c5beaa7142b4c526e62498704dc2603bbcf97597d91ad4b0b876c6b15eda6d77b4e09d0f25f2a96893ee9de8bf8462c52b342fa52840c12240b005d6db955c354c869deda1ef19e8941fad0be49842f3e5ee67e040fdde221ec79f4475dfb543c12a640752eb28466850a2d2a1bf800000000000000000000000000000000000000000000000000000000000000001210ff254d73d60357940ccf9b2cf4828ec7b87faa03a548c1604d72bcf3414322a201f8ae580df1dfdcc33261598ead060f215f1ec8319baa401340f5da6c4926504f4e471a248d953192f368e5c0aedfc8ea29c9fd0089e1fff70fa2e78c800acdcfc619ac1599ad64c62cfdc16af5f1fbecb8bebb4cb125f1e5603372492600000000000000000000000000000000000000000000000000000000000000002d1c21f2bbc76b594ed0188023d9959889230474b3137b46b71bc7f33c43dd210df65b3f71041173558b36e888eeaa80f102a03818b9e96d6c290d082dc482061e202c03fadef46d6316d531cb6de389fd536dfbff3c39b69b1903ce2db7605626aa54c6f48c3badfa4d7d78e6abb75f43f75591b72b44d29f6b95d1144f44eb2e98dbd0480422cf33fbd7f066053c835a196e8f84d4ccff0ef44ffb2c7407b41c2e290f2b37ab33c532d19d73a8895760a039a51bc2d2ead3020db7c269f9991c8d9d93bc672c0a41fe27dbcc47d50d160c16c597c5633f18494b7d2ada037a0937498c2b03c195435c20593c9b97af7ef0497704f9a5ea605c62d3ce5e3dc121b5456a0e7c0b898477913fb996629172d91aa8c9dd37da1b5d7dd9426fe5dc28062db1f48ea04b7a9557dee4bc80ef5bbe4f8bd1372d2e81c0792cfabee0731af88bc489b3845cacff4d8720695a429a3cc829ed8ccc8c93d241430a51c8ce1631c8663100f69ab3f5efca1b32e6d21b262a4c905ac932fbeb78cd5425ef551eacc0439c2d9eeb239f224c7c2d0ff046c3cad9238ee572e4ef1d740861128812ea4c3abdb336ba2198e1ba6f355069aeaafa8e5cd57cfb64071c64bfa46d982cac87461bb8814426912275f9686dfafba8e89e0255312cd7a3edc486801f86255e25f69f21298b9efc6ad1f0862033fd1eebeb82c99ae6065311415b3da76b2602893d7a850e74bc06825022306cfc02a000b51ec05aebc74a14689bad1cde1f97dffaaa438eaa98e9621693c65277a9c4a9171307fe6c1b04356c1e5e653d0750385ed428f33f4aa955b0b6895a8e327dffbb72541d5a888494a647566bcb122468d86d6290a522a0de0022fed47bf78a8071e7812ea055ab80b9f138b0d208964ec96595903d6473b438a51c537f3b441b6af27ec44f621e35f72e9626e80b31efa463e22ad7af4b776cb46035c3e8a4bb980a7981901e5f4eb4a08a313b19795903227198f5a19ac99825ccd77d10ca3a7533250000df6354c2d50693b1000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f3e93b7378633a8084963130457d667ede5c8482f402230a995d5f3924e863503b4c87bb3d5d93924237ea4b3d0c0058c30806e44e803be33ed01cc54230cc208a3d500e68506c1b6698608f11627267273a6f2f0de807312319c766d48fd331d4740314d19117a3cf21a996c20e9214766366c4119deb210d7b43ca10100090000000000000000000000000000000000000000000000000000000000000000164aaf847c7e9ad192fd1f9419b509eb99bff35e92710d44ad44df027ccd3ce200000000000000000000000000000000000000000000000000000000000000000b258c3db17963b2440ba69e5df5706da358d56c45c759512873d66916e568010000000000000000000000000000000000000000000000000000000000000000242a2d75f6d09e9b7de71e343e4c451a6f4be101598ff2c20c9594692658dd382e58a75a822f9d4e340d57f200005878068f21a3f53119ecba9a9a44b684bda4106aaee1f52ed2514666c05b3266b2e603d3538389b9174a7f70dab57133fca700000000000000000000000000000000000000000000000000000000000000001c3b042b2923fb5c5084714029b9692f87da245accaf1b9702db1bd6e7e20c9a00000000000000000000000000000000000000000000000000000000000000000080c84d3157c965a07530553184def03bd5b8975d84d96ae5c23317636880bc068015111a27a65629735073095db2512c1390112d874c06c416e956b39bb3c8000000000000000000000000000000000000000000000000000000000000000022bbf763a666867f424eefc3b616ca7dd56633c497b3804fa140378c701d9b0f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d62cc04bfe4f893bec447e0edfe94b036821faefd86af7a2811d3d970d5153e2d3c0045a60ca38466f6c307ff7660cd28d63384ea0a761f70069c5450264f6f2d841fcec63318a00bc8b42c6f49602e0b3b50548c69924dfaa87d6e846a574f201e00e39c0a0b38ffa3b14e623412ef121f1c95b3121f3159994fc975420eec2b523f86ff270b288d4137c6c14c87a9fb4d105b4e514c165e303d4036a9fcbe27c775924539f6c58a54f40b2f93035d6f825ab412dd1eedc25ffe95b1c475a704f7946c4c2766ff414cbfbd530c726fd482390c27614fa9b3e5d67efc91fba02eb187dd3c5795cadf1c0d7f4c63b9cc42b5736ee6163d3742f0af66dd6dc5822f10fc61cd8716a15be763bda5030581f821508f6218cd8b8837ed2c45ddcf631bbaa85a3c23ac2c5d455c3b1556c82461058340cf4d1036d04b0482e96209aa21b5456a0e7c0b898477913fb996629172d91aa8c9dd37da1b5d7dd9426fe5dc2eb9c24467e472e04f51344421af318a15f7135ffa14619d2224364cebfe56a700000000000000000000000000000000000000000000000000000000000000001a07c2cc91e4172bd402cf8f63ba6a6a075591cf6e230ef1a673146f26c551b22c494612234b8846a6406c7cdb1dad0d81cffd7f83f425b77acb675f578b121e0510a25d85f426ce4771bca9a80c789ff698067d4bf35db6e79d04dfdc840a9100000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000002c000000000000000000000000000000000000000000000000000000000000003a000000000000000000000000000000000000000000000000000000000000004e0000000000000000000000000000000000000000000000000000000000000062000000000000000000000000000000000000000000000000000000000000000102b9e8983ba35acb02b879383d8735819cdc5e1bf1fdd9cafe3340f475361d5ce13962d747f0910066e534fcd2c75bac3c1318c007d6add9c22e1deb6f038cd590c496a4751b753787a5c912ad2ee561bb8d3620279a5e456c74680447e0e3c94237b1c70649548fe0b2457cabfd5a699a7e209d3a604e05b6307961ec5b11aba29f905c36f6ecaa1cc93614533ab5f184df113c9c0049cf51340aa99aa59559e1abc4c58c0304096e9fc24056e47c59cb832c08e098db10e4968823259de2c81246d75e9e66e874cf0bf8f1073ccce0ad32cff4d008275c19dc84740b32c85940c5a3cb36732e25c589c7e412dd325a173c5124b71ee06b32a8ade06ed4436a2162b8361e85c6fd51648dc84b07d5cf22c31e028ec27190f15382877dc910f502033ff37be98a18a08d59c84db26e4957056c40bfa6bf7620cdc775fd75efb7d16bbc0b50303009f493e7403edba4afcd9695156f75ded03d7a22e452d9fa3020267df54d8cb79f517392894337e87b1ee1c2a6673489fa202faebc2b6a24b8a136760251dec078e309f94ffd5d98fa867bade09ab7d84004d05566de7481ac429bfcac4dcb4882e8334ecbf3727774c173767c85a68cc4b7fcd34a4461ef7c02e7e8c5d03f3d155a14dea18052a788bd45f3396b770fa8c6e0b34c8cd48dbb0158311a99bb4d4fadb581b1a0154ec0cc1d5d0c8701633e238de6005c282b6b00000000000000000000000000000000000000000000000000000000000000006088c834909c69f01e1d92220841253090e34db27cdf6ed24f4e38fc51a5015282bdde77b8b1bf751f245ddecec2acaeae33cae8a2b8782209522e35ab9bdb32508f0640af73147ccb5bd43e3a97cd5749d13d15e841b6547e8494c8dc4c3077624d4d1c5461b30ff5666c1e2b1911073711ca0deed238420a977672752511c7228d7fb3dae7553e1180b1684f9eb0881a5d65226aedeb83e02f87fbee8c3141e1bb1e9cf930a354724e389cf04bd0ccc7155b1b26b9d883046b0724eef04adb3000000000000000000000000000000000000000000000000000000000000000914a68b8f2d1654e9c702abb162d175a49611b8327e470281b03d2e5350a2d1b52b762fa12ecd6bbf4aba6bfe46f93cb08da8c3690a718fc69cc5947e139161530a54201362c5fa8e6eacc7620b667f68b6a3c2f5330cef0226e23d3a9b5c6f7305dede899ba9f6afbc6b9dc637d20c63a4e3e3030c4a043f8cba771f2bab539821138539e08ecf52e60d993240a39b61917ea4694e21359d449556d6eeb6fd3818d6a815c69e54017cba6e3bef8c97083cd089035e425bccec8aa17f1406259a177212017d023dcfe87b18317c2a84ad2639719747e854dbce0076232c97fcf924eafaff048d6d378dafb5ba452ecb6729c4e2212ac7f9999bf6ecd09770872e1665fb7d3583e6678fa13095b08982b8df72d60f029b6ee0ed9e853db3eb5ae50000000000000000000000000000000000000000000000000000000000000009075b8a19649451e91f2b91c60150f2904574742ce6b95e3b4094d076ac7134b90c302f8defdedae14c0715bd94f676509cf0f7f8f96398dc3ab0b88551c5d5a22ffc985867e7a7b185231d532803eafc395f75c606ab254ce83fa2f0536909741456bf43250566400754ace6c2ec2b975e5422417c76c3262b43982a75f9f86f1c2118a278ef4fd7d74776d5db2bdd7048ffde5fb3eb52df0f7d69c0e8d71f311a179eb3c6a6a61b3d7987dd508d877cc08ccf4f7c79914ed09ff76c0f958b890a43fb48d26c654935bfce4546c0b15a16f54112b9e6fb8355f5ae58a1d39a5c17a655853517af9b8d4dd2ee334d214bfdf032a061e461efe012802abc76ed9a04617afaf8466603d8d8763429de91804925596978bb16b0b8bbf1b9af2727ad0000000000000000000000000000000000000000000000000000000000000009104c619f988572a01a043a3952bb7d5c75ccc57fbb8b37a3f93726f534183aa91fefa2e64ecc634296ccaf178bcee20da99caf1e81be14c2ea003a78e60963012410a1ea8b9c3bc76cc9cb62f9a5af0cd43d5db82a4ea8fdcca98d871f3a2a08235a6934d80bb552cf0948ed94b86c2da52d5d94a6b9a3c842db883cc36eef7405fbf801c748539f9d4539eb76f837b0497d0815578bbf1f2211e666bdc789ea16e427fca9e9f64aae9e25d61d4ba575343e19b36022b94cbf423f9d269ace410d7bdd42975b29a3a72832ec98d9648a5ba5685c0c5920c76635d7206197888d16b02a9ad5d97ee755fdc79be51d6814c4cf30280ca0a25ba1c9e3cdbc4b8a3b0e1a42e18fe712f64b65d343873ea119226052fbf19de324a872bc551b7892ce0c6d870dc69c0608cedfe01803a981fe369717c5aaf8427cb8d5e43524271c280efe316f3ee379b928741a079d8414bb72873897eeb6dfc64f379f5bc0b1ed0600000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000120000000000000000000000000000000000000000000000000000000000000000422e6025b7fc1b40a2f224f14d260c4479785bd76f2ca500a4ae917278d66d10129d8a9e7b418e1f3ffe8ff294b524741950b38a6861eb57b19be9c08b390eda005eca5e5905cf46e1e650528266a246c6a33242dfb60bbc8b1f6da10e703faa61f7c40568011b8489cb0c6ceb1533bd55d06fe4e8a3721e279975478088ade7300000000000000000000000000000000000000000000000000000000000000041e9e2fd5fcd95314214210258340897f9b58f0689e81c655ba90c404acdcc9ce00639a5fb527b7597244b1a40e1d6f879a2ef5c9b2fabeb4753acf2f0674699d0217afde4c5abae97f36974b0551324a3f18b0294292c653d4c2348ebab4e44f1e3cfe52fd50ddeba459b2d681de027d50de9d91fc1e5ef929c9b767ce0aafdd00000000000000000000000000000000000000000000000000000000000000b22044c3d96e524e64f44d685da890d3472b067af4574ea05b4802b67be437145d2d22a0bc9ee20866882ce79ae6ec42154c439ecfddf8cf680503d9f699b2a0bd2044c3d96e524e64f44d685da890d3472b067af4574ea05b4802b67be437145d2d22a0bc9ee20866882ce79ae6ec42154c439ecfddf8cf680503d9f699b2a0bd2044c3d96e524e64f44d685da890d3472b067af4574ea05b4802b67be437145d2d22a0bc9ee20866882ce79ae6ec42154c439ecfddf8cf680503d9f699b2a0bd00e50f74c03c376fe1d0f0f3678ffad42091d6cfb97fd6cc16920dfccc26dc460e9cb61ab81526e7462a5d8562da9a331be359a2e4ecf1986bbd65e873b258592d62815c894199d4af061d151d37fdc62ac00d16ac05c3c5fa180883f94441a70fa033a22026d388c6e4955a30e9fecfd2330d7176cba753ed76bc26bebfd6ea284fb4ecfaa4cac4811eec90f9996699690493cddba5fbee4c528775b4397bd90203715517e3e7eba4a2fbbbeb1547a0844b0997674fc2d24eb87d97450b330029ce5517aa432243fbba3c25afabd15bec4e84925d5810819f27934cfda0aa1a240932d400127a2c68d25f33d4aa58bb4d69d2b78358bd70376a110e6d7513aa2ed8c499d772d64f47386045ba5d2ba075c2b5168593fe025cb279402bc844ac0c464966c4b471640b82b331b0c88539dca4e26399368d79bf7ccbb08a3b5b522836354b454274df14a599705a06094d6dd23959f69813408570e11dcac2f2ad0f6ccc4c9b13aee2db4b2e1fd8c21343548b33bfe2c68dad1222c3e64a42dd5f18be29053d41c4aff021f80a07fb9e50f0561ae740d216f29156274f151f0c240312c0e6e9525fc69b8808d1210e4e7ed8cb3e57d7224aacb5fff0c4eb73396910a14593f8d0eaccccc7c3b7f6b6ab4d1d754b84edad06ed1f44c4c5eff7eefd0e75864db3c0c2b60cdb06ae8d8115862cb61cac682ebf9002b2234f9ed31e53042ac69dcdbf50a09cdee8628797e00d5bd4be5b4ebe2c4ab126a47770d8cc91289932430f799ebf569a3c786e50cf3533529272401f35302196bfc3a3717feb0a0bbd74fd5bb315c33ed2d60d8cddd7e3118e3cf582d71431a090e2df4b83a72c0e4c9cfb74837dbae662fb8b09ed9034dcc89a542d97ab6c6c482fcbfe77cc24bc41b01f22e3be456a456586cf6154e113681e77f2a8f15e9ff87d8855452d08b5325ff1ea7a650a0ae38cb70387354fee0b03ee59dc922cf0d18e81f2a49f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d68afce7d0ac16e7144be8b65408ea4da0780c2f236ce445f89f678da140c4001a567c8470e627a991da5e084dc79e6bb6a59c1726a83d7aa55f5a6b146fbb219728a217a1206891fccc02ec0dc33ef8cef28245ee0a75a26fafb9fdcc36512e3ab8946ecf260a359522ced5b6a60f1ad1f61b51169e461e1a32ef4a58edbe1b032ec93af2997609dfa7b813cd79a60b48ba60dbd8a8e756b9d188241831d01b8c661951f27dd566d8096e955541eb239dc200ce01f28f92e183e57aed0891189d611d14fdf669eba720476c21fabdd667330997d51b7ec80fc47aa3f7efa919ab1f84b61a763a2e8de5fe87a086f8460a6bda53ac6dee11f454705d8fa63f000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f7dc3b42516d3afa15cf127a31357378de10e65cbb1d1f9e5aeeb42777a61441991ed233eb5013cbe34ea8e2efa764c8e07a072da3180f9793d98e2c733d28c1f39c61a333b77136a0cdc020acfb717f4ce93f7d056978d120ccd1d16b184c6106d878b47f72335b95c6f3ccd9cb748b36c557ad53063ba177020a09476af731befe687192e0fed489c294231ca21689739a4c78f9228c9bc1e0927d9458ce511abb925e86fed3779cd4ffa9899ab6ece0c3492614a074906e1a4a5645d44df05550195857705f1282d55f25ab780e0bd83eb6d391300f4ad5080679be4176a2592ea5b73d8d0271cb684f9763a4812222f4849cdf4a48edf85ed262b50f12f2db6e8cef36e2af827d7e36e75fd1af87b321398d4f1a7173616d92a3d7889c7205055a94c6f4104a6780be04fddd2964605b3413a4bdc699c439b9760cc7f3d066ba58e97141c579e65d99decf4fc7c1c01658bf4b90798f8dfaafe57307c282d34c6d2b294e768e26a3dd0e15ddb96d110c69fe6a528b2b1c376bc32deb98c267f7796f58af8a3c90e5ab8dba4d92ef35ccdd4dc0a25054f85ede96da8415d2f0ecc978121d9dc80b65940ea236e143bb66e45365e39bf9a847f8fd8d2c5df19b6c28dc42e797642960ebf27694e00ff2099b036aaa7a521ee63bb881ca5912c333da2a07190de8b9112aeee0d911cfee70b145ad910e9cd6bfff6984fcccc155f9bf83f7d84dae78cd0e7592e811f9c80f0472c2b91cec3b98a5506b7cb6b2216434af1da8e35919630becb9a3edb77f9e90f0de5b3840eb1b7a8df02a5b82e57f1e935bb35326f16e331cf4a7da45a4b427dcd827698f54fddd373c76c7a2bfcb1c3063654467518ff64a6f7fb6f1efe838f064d970e40667a7f6e9e77fd229339a1ccfe106b5b113e4fd1b68bb10b1be531cdb8d2b7a7258d174cd7857c04c0172c60316efaaff52149f7e456c6c15b0e15e1e1eec01f6d146babc373520ca423bc45cc9f8f84e10c6127818b33b0f227993013f00fcaed82386ac1319412ca0043d674c10e9e858d98280e22ccc6498fa26dd08a566696d8587c15ba7e16c84b64b95cc18d0e894307d03c15e83528d7a59f66567681d88bf81370e43606db0363d9c154e578f82f35b4c351e0e2e38d5162635bcc99526b5542e21c1b207c02862068853eebee0ae30e766351d82a57ae9e5ad2aa9d67d780812656680e2c6f33a0d761f922ceb0a38527b530aadb0fac3dfd017f2c1130a0b02795ec194dd9102ba53fb8d9e55b2cf50788279b37c125da6712f4e0d3093ca33d156b08b6a7c02de5d580a2c55b8daa2b06e129c19df6daaea5a616daa5aede32a1fe1642412d25b216bfb0cb1723eb6831636de88a20a1d16330f3918fe4f442155a02835274abf11c6e1cb5b0baff917121b2ff271db0d4a165fcc46eb6a64caea607f1cc3eeb22451d9c82a462fb08ba7cc36bd6304eed60a1f5a8611c5af9e2251996fb227a88a1057fe77bdde2694c754dc9ef4e90a81b8b114eadfd980655b72b97cb630b19c0c8a77ac919532b46ee562d49355bc5a367aee9cc41aee1ea0f1f98d402a03b07f13ffa85de4f02fc91289d8ab7f06962bbf7e1d7a727993c9100de734f099c83a0def5b0a491437148a1c90b464aa5b420e2c268eb2ad8e30f157a45ad8296bbb5d88fb2bbcedfaeb1b27824c518270e1708b58b81ddcb2a8c06f03bf87f37117661366d9790808ca78c77a4e79d59f0d883dff488807706ca2c39d4d3594d6386689e5081acc51f91669e63e60cb8187e4dea10e201f4f9572d8dcec38dfa125467ebe5d81c775e715551da76ca46c3fd54d840cf2b3c707d22e2eed95799ba2e3d077122050b9f8ae35d39036a12a61bb18d09d62874c96e2e48113a3a66c479cd124cbae4b959d28e1cd8cd8a7c0e1a3457b3057f4446d92bcbc36cb210838cc7ae70181d434438c4207f324edad9133a24da77b5107641088c8cddd487f2464d131f1eac234b2b895fff5e49453ef29d4c602a091767f2006a21c3a5dcdb44704b4df0b624f0f2c8daf1c11df89ca9cb73cf178a93ad19000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000011820a923787611eeea2665137519542c6949db5fe974f92132cfc097ec1a73e11cb530ec53c61f6965125c25ac0e7c8a06ade1e5d8accf58c33496e88c4fd421aef917b3d1814d2c6fc00141ff20d624c7ac8aef8e88482276a12a423dd964d01d314c33e1141ab613968309431f89bd11c0f369aaf88cc838f967269cd84b411596ef0cf3cf6fdb4d2bae3da749ed7ff215f08e5926a927bc3f7ed58fa5e4e15c95e41acaa9e623539a30507191c07ba66de192411e4b79ceed406b19033de090e08d9cf8efc02e09008810e593453562a0711f356d48da6ea4229849a35b41670e32a4c46236ed309f5d7590efa7d5fc14a0ce5a080aa22557075bf2aab58000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000da75ca30aeb804bc73dc090f035bb59559069d4c5731e147e48275a320258461ef247a75b2d983790338d5595c44ada182f3589b6756d7751a910e941ea8aad0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000014020e7c93de34529c926eae3a6c4c418b3e1c8fddc35abc793a0e9cefb125021298988608edc4556bf75e0d55cca5d19fb1c6ed74963c0c530e4acf4e0933df0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000006d5187c582f35c2333a8985cc25a70433836c47c6e703abda54e2e4f4bb2e5c2d89b6248d61e272e0579b4c5e8134ef1b5f1066e5e54497679fcdd5f7ad9b8d03eab93769c3e64b1ec84b16c700065a285d1ef0373a200694706f7467cb764a219384f74c105e265e44209621c7b940de57d921a3f2dcfc9958c8d83a2ad2352422776c7712e1639a526e5f128725a981cfaa4c71a95d1029fc84456f97955f14b1d342c402ee60300373ebb02e07f6f3c3002c74ab11102e12e1b43bce494d0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000029b11106e78680b53e2d45d92677bac07ecdc717f6b6075a446ee5d73fa5626617bb9f5e8027135406154005cc6f0bf0ea0a784125252ad64ac236510174c17f000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001abf4ac9d61b10091a91d3ea0b90914b648d8f53aedf47a90ee01ccd0fde050c2e921af4a8d573a351fe2fdb802ffb376a1045330994a992477709079e0c642628d70c5984ada40c3a295f717f89c46f2db5970b3db40e512c6bd7df008657b008288bbabcf175f5d78c76100739c0918eb2de35e9fd8b48df827b31aabe8dd0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002d66d0ff4d033a9c8f99988ce2b5958e4559b2e44b22f5110e2f80ff2f9eeb911e17fea21f5ae401379bdaeff784d05137d73c9ae1a7074fa1b395b87a12a6520000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001d12768da2a1e7c28b34755c79e3c07e2f3e49fbf97978752b62cad12a48c6510f9ec77931c79a7cc55bed7a92d510d2c5e3cf050e85d449db351fcac1b67c792832279f1e15b15e9883ca9016dbad680843becf1784ed369213e00054db9ace0fddd9706861b51629a93fe128f39c3cf2c37ed2c1111166aba712e0002e8dba29c3688bf652506a9ff31a91e9340398f5cbe9638c074ef23a8e2bf595a7b0651c4ad8318a86513666e6430360c0697ccba8ab205fe419a533b6424f82f979cc2819d965bc3571ab9c42421a985e3a2c5e0f5abbbd1136d33263d24b187ca7a60af5df83f97b690a0876b1ffcac975c84db2e8427b80b5872c8f10a79d10f3dd0cbe2ec547ffd5dae022d63ce8029881a4f19be1975aaa3760239709d7d09abc18c35c6522753bd87e910a201b5c95d4d9d975a139f2fe55203f031796cf34132a4b518898aea629d5c70b59bb530c1f44a884934434bff9b039c4f69bdcaa252eb0c3723497606b6cbe430aa846ae2bb4b111cdf9ca722300d65c05e34429391875e914d2e3c0483c0b063f6491331dfb7f3e7c2239d5eddf8085453915abf3020b33fac779293f88bf5acd6bf90455e1fa6f37ac0691488cfa5e67e5a79120029ae80d5b5cf3e0be09e53a7b0a7d8a5bcdfbb285f021400f8b58e45be2642d0b95dd9c9d5fb59f793fc94a53515bf277e3be44b561c6270ec898bf1b6f740e24f153a9f29b5f9772c1ddacf51af3c8fd7875a7bba88e6b9bf9258ca83dbff7278dbb66ca8b38e98a8369d6983d3e8635d55f8500fbe514f15ad9e968c7c03d2333b8a996b067c10f23c6b1edfd06d7a531f03323af82cd95bb33f65ea95d0400b1b3d98de4d8c66d5622603000df5bd280b89869ab8c2ee5fe62f14ceb9f46155f9bf83f7d84dae78cd0e7592e811f9c80f0472c2b91cec3b98a5506b7cb6b2216434af1da8e35919630becb9a3edb77f9e90f0de5b3840eb1b7a8df02a5b825d3e69d86300488f43dd07f13bc367599e6e44825cce85b91936dc97ff3de85015bb27fb8af82dff7fbcbcc00e371e51fa5e52f4a24456fa8de973f95e3580000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000225764532ae65f9011f43f091ff6cf8b6ed3415f9a5a71c7e692740015e0e3db2bc3cb034fcca08492d9ab8150b8ce0235ed4c7dd6351a0ad051cfb5a0506d141ffaff3515c8186db090971f5ef19503b470cdb2051dd714497ee155a07f78df0f8267a0ab6f0e89471820bae373febacdc8448c809bbd3356b0331e459348eb0395c1750c2fbf56edcdea22ea34fe4fc21423eb29e52f418242eef9f6fcb82a0cdf70735248ed025cb29c85e90811b1740160bbdcfc123eccfff665f730a7ab
\ No newline at end of file
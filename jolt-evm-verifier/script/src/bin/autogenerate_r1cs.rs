@@ -1,71 +1,22 @@
-use alloy_primitives::hex;
-use jolt_core::field::JoltField;
-use jolt_core::r1cs::key::SparseConstraints;
-use jolt_core::r1cs::key::SparseEqualityItem;
+use script::codegen::{regenerate_r1cs_matrix, rv32i_spartan_key};
 
-// This code is provided as an example of how to turn the sparse constraints into solidity, and was the base
-// for the code in R1CSMatrix, however because the key is not expected to change often full auto-generation is not
-// provided and some tweaking is needed to produce sol from this.
-#[allow(dead_code)]
-fn partial_autogenerate<F: JoltField>(
-    constraints: &SparseConstraints<F>,
-    non_uni: Option<&SparseEqualityItem<F>>,
-) {
-    let bytes = |from: F| -> Vec<u8> {
-        let mut buf = vec![];
-        from.serialize_uncompressed(&mut buf).unwrap();
-        buf.into_iter().rev().collect()
-    };
-
-    print!("Fr running = Fr.wrap(0);\nFr rv = Fr.wrap(0);\n");
-
-    for (row, col, coeff) in constraints.vars.iter() {
-        println!(
-            "rv += Fr.wrap(0x{})*row[{:?}]*col[{:?}];",
-            hex::encode(bytes(*coeff)),
-            row,
-            col
-        );
-    }
-
-    print!("running += rv*eq_rx_ry_step;\n// then we do constant col\nFr rc = Fr.wrap(0);");
-
-    for (row, coeff) in constraints.consts.iter() {
-        println!(
-            "rc += Fr.wrap(0x{})*row[{:?}];",
-            hex::encode(bytes(*coeff)),
-            row
-        );
-    }
-    println!("running += rc*col_eq_constant;");
-
-    if non_uni.is_some() {
-        println!("Fr rnu = Fr.wrap(0);");
-
-        for (col, offset, coeff) in non_uni.unwrap().offset_vars.iter() {
-            if *offset {
-                println!(
-                    "rnu += Fr.wrap(0x{})*col[{:?}]*eq_step_offset_1;",
-                    hex::encode(bytes(*coeff)),
-                    col
-                );
-            } else {
-                println!(
-                    "rnu += Fr.wrap(0x{})*col[{:?}]*eq_rx_ry_step;",
-                    hex::encode(bytes(*coeff)),
-                    col
-                );
-            }
-        }
-        println!(
-            "rnu += col_eq_const*Fr.wrap(0x{});",
-            hex::encode(bytes(non_uni.unwrap().constant))
-        );
-        println!("running += rnu * row_constr_eq_non_uni;");
-    }
-    println!("return(running);");
-}
+const USAGE: &str = "usage: autogenerate_r1cs [R1CSMatrix.sol path]";
 
 fn main() {
-    // TODO - Have example code here rather than in the verifier
+    // Rewrites the A, B and C functions of R1CSMatrix.sol from the constraints of RV32I programs
+    // with the default memory layout. Run it from jolt-evm-verifier whenever they change.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() > 1 {
+        eprintln!("{USAGE}");
+        std::process::exit(1);
+    }
+    let path = args
+        .first()
+        .map_or("src/subprotocols/R1CSMatrix.sol", String::as_str);
+
+    let source = std::fs::read_to_string(path).expect("failed to read R1CSMatrix.sol");
+    // A step's constraints don't depend on the number of steps
+    let key = rv32i_spartan_key(1);
+    std::fs::write(path, regenerate_r1cs_matrix(&source, &key))
+        .expect("failed to write R1CSMatrix.sol");
 }
@@ -0,0 +1,293 @@
+use std::path::PathBuf;
+
+use alloy_primitives::{hex, U256};
+use alloy_sol_types::{sol, SolType};
+
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::UniformRand;
+use jolt_core::poly::commitment::commitment_scheme::{BatchType, CommitmentScheme};
+use jolt_core::poly::commitment::hyperkzg::*;
+use jolt_core::poly::commitment::zeromorph::*;
+use jolt_core::poly::dense_mlpoly::DensePolynomial;
+use jolt_core::utils::transcript::ProofTranscript;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use sha2::{Digest, Sha256};
+
+use jolt_core::utils::sol_types::{HyperKZGProofSol, VK};
+
+const USAGE: &str = "usage: batch_opening_example [--ell <log2 of polynomial size>] \
+    [--num-polys <n>] [--seed <u64>] [--scheme hyperkzg|zeromorph] [--out <file>]";
+
+const TRANSCRIPT_LABEL: &[u8] = b"TestEval";
+
+sol!(struct BatchedExample {
+    VK vk;
+    HyperKZGProofSol proof;
+    uint256[] commitments;
+    uint256[] point;
+    uint256[] claims;
+});
+
+// There is no Solidity Zeromorph verifier yet, so this encoding only follows the conventions of
+// the HyperKZG one: G1 points are (x, y) pairs, and G2 points are (x.c0, x.c1, y.c0, y.c1).
+sol!(struct ZeromorphVK {
+    uint256 g1_x;
+    uint256 g1_y;
+    uint256[] g2;
+    uint256[] beta_g2;
+    uint256[] tau_N_max_sub_2_N;
+});
+
+sol!(struct ZeromorphProofSol {
+    uint256[] pi;
+    uint256[] q_hat_com;
+    uint256[] q_k_com; // G1 points represented pairwise
+});
+
+sol!(struct ZeromorphBatchedExample {
+    ZeromorphVK vk;
+    ZeromorphProofSol proof;
+    uint256[] commitments;
+    uint256[] point;
+    uint256[] claims;
+});
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scheme {
+    HyperKZG,
+    Zeromorph,
+}
+
+impl Scheme {
+    fn name(self) -> &'static str {
+        match self {
+            Scheme::HyperKZG => "hyperkzg",
+            Scheme::Zeromorph => "zeromorph",
+        }
+    }
+}
+
+struct Args {
+    ell: usize,
+    num_polys: usize,
+    seed: u64,
+    scheme: Scheme,
+    out: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut ell = 12;
+        let mut num_polys = 8;
+        let mut seed = None;
+        let mut scheme = Scheme::HyperKZG;
+        let mut out = None;
+
+        let mut args = std::env::args().skip(1);
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for {flag}"))?;
+            let invalid = |_| format!("invalid value for {flag}: {value}");
+            match flag.as_str() {
+                "--ell" => ell = value.parse().map_err(invalid)?,
+                "--num-polys" => num_polys = value.parse().map_err(invalid)?,
+                "--seed" => seed = Some(value.parse().map_err(invalid)?),
+                "--scheme" => {
+                    scheme = match value.as_str() {
+                        "hyperkzg" => Scheme::HyperKZG,
+                        "zeromorph" => Scheme::Zeromorph,
+                        _ => return Err(format!("unknown scheme: {value}")),
+                    }
+                }
+                "--out" => out = Some(PathBuf::from(&value)),
+                _ => return Err(format!("unknown argument: {flag}")),
+            }
+        }
+        if ell == 0 || ell > 24 {
+            return Err(format!("--ell must be between 1 and 24, got {ell}"));
+        }
+        if num_polys == 0 {
+            return Err("--num-polys must be at least 1".to_string());
+        }
+
+        Ok(Self {
+            ell,
+            num_polys,
+            // Defaults to the seed of the fixture this generator replaced
+            seed: seed.unwrap_or(ell as u64),
+            scheme,
+            out,
+        })
+    }
+}
+
+/// `num_polys` random polynomials of `2^ell` evaluations, and their evaluations at a random
+/// point.
+fn random_openings(
+    rng: &mut ChaCha20Rng,
+    ell: usize,
+    num_polys: usize,
+) -> (Vec<DensePolynomial<Fr>>, Vec<Fr>, Vec<Fr>) {
+    let point: Vec<Fr> = (0..ell).map(|_| Fr::rand(rng)).collect();
+    let polys: Vec<DensePolynomial<Fr>> = (0..num_polys)
+        .map(|_| DensePolynomial::new((0..1 << ell).map(|_| Fr::rand(rng)).collect()))
+        .collect();
+    let evals = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+    (polys, point, evals)
+}
+
+fn to_uint256<F: PrimeField>(element: &F) -> U256 {
+    U256::from_be_slice(&element.into_bigint().to_bytes_be())
+}
+
+fn encode_g1(points: &[G1Affine]) -> Vec<U256> {
+    points
+        .iter()
+        .flat_map(|point| [to_uint256(&point.x), to_uint256(&point.y)])
+        .collect()
+}
+
+fn encode_g2(point: &G2Affine) -> Vec<U256> {
+    vec![
+        to_uint256(&point.x.c0),
+        to_uint256(&point.x.c1),
+        to_uint256(&point.y.c0),
+        to_uint256(&point.y.c1),
+    ]
+}
+
+/// Proves the batched opening of `polys` with `PCS`, and checks it with the Rust verifier
+/// before it is handed to the Solidity tests.
+fn batch_prove<PCS: CommitmentScheme<Field = Fr>>(
+    setup: &PCS::Setup,
+    polys: &[DensePolynomial<Fr>],
+    commitments: &[PCS::Commitment],
+    point: &[Fr],
+    evals: &[Fr],
+) -> PCS::BatchedProof {
+    let polys: Vec<_> = polys.iter().collect();
+    let proof = PCS::batch_prove(
+        setup,
+        &polys,
+        point,
+        evals,
+        BatchType::Big,
+        &mut ProofTranscript::new(TRANSCRIPT_LABEL),
+    );
+    let commitments: Vec<_> = commitments.iter().collect();
+    PCS::batch_verify(
+        &proof,
+        setup,
+        point,
+        evals,
+        &commitments,
+        &mut ProofTranscript::new(TRANSCRIPT_LABEL),
+    )
+    .expect("the generated batch opening doesn't verify");
+    proof
+}
+
+/// Returns the ABI encoding of the example and the name of its Solidity struct.
+fn hyperkzg_example(args: &Args, rng: &mut ChaCha20Rng) -> (Vec<u8>, &'static str) {
+    let n = 1 << args.ell;
+    let srs = HyperKZGSRS::setup(rng, n);
+    let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+    let (polys, point, evals) = random_openings(rng, args.ell, args.num_polys);
+    let commitments: Vec<HyperKZGCommitment<Bn254>> = polys
+        .iter()
+        .map(|poly| HyperKZG::commit(&pk, poly).unwrap())
+        .collect();
+    let proof = batch_prove::<HyperKZG<Bn254>>(&(pk, vk), &polys, &commitments, &point, &evals);
+
+    let commitments: Vec<G1Affine> = commitments.iter().map(|c| c.0).collect();
+    let example = BatchedExample {
+        vk: (&vk).into(),
+        proof: (&proof).into(),
+        commitments: encode_g1(&commitments),
+        point: point.iter().map(to_uint256).collect(),
+        claims: evals.iter().map(to_uint256).collect(),
+    };
+    (BatchedExample::abi_encode(&example), "BatchedExample")
+}
+
+/// Returns the ABI encoding of the example and the name of its Solidity struct.
+fn zeromorph_example(args: &Args, rng: &mut ChaCha20Rng) -> (Vec<u8>, &'static str) {
+    let n = 1 << args.ell;
+    let srs = ZeromorphSRS::setup(rng, n);
+    let (pk, vk): (ZeromorphProverKey<Bn254>, ZeromorphVerifierKey<Bn254>) = srs.trim(n);
+
+    let (polys, point, evals) = random_openings(rng, args.ell, args.num_polys);
+    let commitments: Vec<ZeromorphCommitment<Bn254>> = polys
+        .iter()
+        .map(|poly| Zeromorph::commit(&pk, poly).unwrap())
+        .collect();
+    let proof = batch_prove::<Zeromorph<Bn254>>(&(pk, vk), &polys, &commitments, &point, &evals);
+
+    let commitments: Vec<G1Affine> = commitments.iter().map(|c| c.0).collect();
+    let example = ZeromorphBatchedExample {
+        vk: ZeromorphVK {
+            g1_x: to_uint256(&vk.kzg_vk.g1.x),
+            g1_y: to_uint256(&vk.kzg_vk.g1.y),
+            g2: encode_g2(&vk.kzg_vk.g2),
+            beta_g2: encode_g2(&vk.kzg_vk.beta_g2),
+            tau_N_max_sub_2_N: encode_g2(&vk.tau_N_max_sub_2_N),
+        },
+        proof: ZeromorphProofSol {
+            pi: encode_g1(&[proof.pi]),
+            q_hat_com: encode_g1(&[proof.q_hat_com]),
+            q_k_com: encode_g1(&proof.q_k_com),
+        },
+        commitments: encode_g1(&commitments),
+        point: point.iter().map(to_uint256).collect(),
+        claims: evals.iter().map(to_uint256).collect(),
+    };
+    (
+        ZeromorphBatchedExample::abi_encode(&example),
+        "ZeromorphBatchedExample",
+    )
+}
+
+fn main() {
+    // Generates a batched opening of `--num-polys` random polynomials of 2^`--ell` elements at
+    // one random point, checks it with the Rust verifier, and outputs it ABI-encoded for the
+    // Solidity tests. The ABI encoding is hex encoded, and printed (for forge's ffi) or written
+    // to `--out`, in which case its metadata is written to a JSON sidecar next to it.
+    let args = Args::parse().unwrap_or_else(|error| {
+        eprintln!("{error}\n{USAGE}");
+        std::process::exit(1);
+    });
+
+    let mut rng = ChaCha20Rng::seed_from_u64(args.seed);
+    let (abi, abi_struct) = match args.scheme {
+        Scheme::HyperKZG => hyperkzg_example(&args, &mut rng),
+        Scheme::Zeromorph => zeromorph_example(&args, &mut rng),
+    };
+
+    let Some(out) = &args.out else {
+        print!("{}", hex::encode(&abi));
+        return;
+    };
+    std::fs::write(out, hex::encode(&abi)).expect("failed to write the example");
+
+    let metadata = serde_json::json!({
+        "scheme": args.scheme.name(),
+        "ell": args.ell,
+        "num_polys": args.num_polys,
+        "seed": args.seed,
+        "transcript_label": std::str::from_utf8(TRANSCRIPT_LABEL).unwrap(),
+        "abi_struct": abi_struct,
+        "abi_length": abi.len(),
+        "abi_sha256": hex::encode(Sha256::digest(&abi)),
+    });
+    let mut sidecar = out.clone().into_os_string();
+    sidecar.push(".json");
+    std::fs::write(
+        &sidecar,
+        serde_json::to_string_pretty(&metadata).unwrap() + "\n",
+    )
+    .expect("failed to write the metadata");
+}
@@ -0,0 +1,30 @@
+use ark_bn254::Bn254;
+use ark_serialize::CanonicalDeserialize;
+use jolt_core::poly::commitment::hyperkzg::HyperKZGVerifierKey;
+
+use script::codegen::hyperkzg_verifier_contract;
+
+const USAGE: &str =
+    "usage: generate_hyperkzg_verifier <verifier key file> <contract name> [HyperKZG.sol import path]";
+
+fn main() {
+    // Reads a compressed, canonically serialized `HyperKZGVerifierKey<Bn254>` and prints a
+    // deployable verifier contract with the key baked in.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("{USAGE}");
+        std::process::exit(1);
+    }
+    let hyperkzg_import = args
+        .get(2)
+        .map_or("../subprotocols/HyperKZG.sol", String::as_str);
+
+    let bytes = std::fs::read(&args[0]).expect("failed to read verifier key");
+    let vk = HyperKZGVerifierKey::<Bn254>::deserialize_compressed(bytes.as_slice())
+        .expect("not a serialized HyperKZG verifier key");
+
+    print!(
+        "{}",
+        hyperkzg_verifier_contract(&vk, &args[1], hyperkzg_import)
+    );
+}
@@ -0,0 +1,73 @@
+use alloy_primitives::hex;
+use alloy_sol_types::{sol, SolType};
+
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_std::UniformRand;
+use jolt_core::poly::commitment::hyperkzg::*;
+use jolt_core::poly::dense_mlpoly::DensePolynomial;
+use jolt_core::utils::transcript::ProofTranscript;
+use rand_core::SeedableRng;
+
+use jolt_core::utils::sol_types::{HyperKZGOpeningSol, VK};
+
+fn main() {
+    // Four independent openings of 2^12 element polynomials, as if settling the final
+    // opening proofs of four Jolt proofs in one transaction. Each opening has its own
+    // transcript, which the solidity test recreates.
+    let ell = 12;
+    let num_openings = 4;
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+
+    let n = 1 << ell; // n = 2^ell
+
+    let srs = HyperKZGSRS::setup(&mut rng, n);
+    let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+    let mut commitments = vec![];
+    let mut points = vec![];
+    let mut evals = vec![];
+    let mut proofs = vec![];
+    for _ in 0..num_openings {
+        let poly = DensePolynomial::new(
+            (0..n)
+                .map(|_| <Bn254 as Pairing>::ScalarField::rand(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        let point = (0..ell)
+            .map(|_| <Bn254 as Pairing>::ScalarField::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let eval = poly.evaluate(&point);
+        let mut prover_transcript = ProofTranscript::new(b"TestEval");
+        proofs.push(HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap());
+        commitments.push(HyperKZG::commit(&pk, &poly).unwrap());
+        points.push(point);
+        evals.push(eval);
+    }
+
+    let openings: Vec<HyperKZGOpening<Bn254>> = (0..num_openings)
+        .map(|i| HyperKZGOpening {
+            commitment: &commitments[i],
+            point: &points[i],
+            eval: evals[i],
+            proof: &proofs[i],
+            transcript: ProofTranscript::new(b"TestEval"),
+        })
+        .collect();
+    let openings_sol: Vec<HyperKZGOpeningSol> =
+        openings.iter().map(|opening| opening.into()).collect();
+    // Sanity check the fixture before handing it to the solidity test
+    HyperKZG::aggregate_verify(&vk, openings).unwrap();
+
+    sol!(struct AggregateExample {
+        VK vk;
+        HyperKZGOpeningSol[] openings;
+    });
+
+    let example = AggregateExample {
+        vk: (&vk).into(),
+        openings: openings_sol,
+    };
+
+    print!("{}", hex::encode(AggregateExample::abi_encode(&example)));
+}
@@ -0,0 +1,55 @@
+use alloy_primitives::{hex, Bytes, U256};
+use alloy_sol_types::{sol, SolType};
+
+use ark_bn254::{Bn254, Fr};
+use ark_std::UniformRand;
+use jolt_core::poly::commitment::hyperkzg::*;
+use jolt_core::poly::dense_mlpoly::DensePolynomial;
+use jolt_core::utils::transcript::ProofTranscript;
+use rand_core::SeedableRng;
+
+use jolt_core::utils::sol_types::{into_uint256, VK};
+use script::calldata::{compress_g1, encode_hyperkzg_proof};
+
+fn main() {
+    // The same opening as hyperkzg_example, with the commitment and proof in the compact
+    // calldata encoding taken by HyperKZG.verify_compressed.
+    let ell = 12;
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+
+    let n = 1 << ell; // n = 2^ell
+
+    let srs = HyperKZGSRS::setup(&mut rng, n);
+    let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+    let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>());
+    let point = (0..ell).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let eval = poly.evaluate(&point);
+
+    let c = HyperKZG::commit(&pk, &poly).unwrap();
+
+    let mut prover_transcript = ProofTranscript::new(b"TestEval");
+    let proof: HyperKZGProof<Bn254> =
+        HyperKZG::open(&pk, &poly, &point, &eval, &mut prover_transcript).unwrap();
+
+    let mut verifier_tr = ProofTranscript::new(b"TestEval");
+    assert!(HyperKZG::verify(&vk, &c, &point, &eval, &proof, &mut verifier_tr).is_ok());
+
+    sol!(struct CompressedExample {
+        VK vk;
+        uint256 commitment;
+        uint256[] point;
+        uint256 claim;
+        bytes proof;
+    });
+
+    let example = CompressedExample {
+        vk: (&vk).into(),
+        commitment: U256::from_be_bytes(compress_g1(&c.0)),
+        point: point.iter().map(|i| into_uint256(*i)).collect(),
+        claim: into_uint256(eval),
+        proof: Bytes::from(encode_hyperkzg_proof(&proof)),
+    };
+
+    print!("{}", hex::encode(CompressedExample::abi_encode(&example)));
+}
@@ -0,0 +1,51 @@
+use alloy_primitives::{hex, U256};
+use alloy_sol_types::{sol, SolType};
+use ark_bn254::Fr;
+use ark_std::UniformRand;
+use jolt_core::utils::sol_types::into_uint256;
+use rand_core::SeedableRng;
+
+use script::codegen::rv32i_spartan_key;
+
+fn main() {
+    // Evaluates the R1CS matrices and the witness MLE of a key for 16 steps at a random point,
+    // for checking R1CSMatrix.sol against the Rust verifier's evaluations.
+    let key = rv32i_spartan_key(1 << 4);
+    let row_bits = key.num_rows_total().trailing_zeros() as usize;
+    let col_bits = key.num_cols_total().trailing_zeros() as usize;
+
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+    let r: Vec<Fr> = (0..row_bits + col_bits)
+        .map(|_| Fr::rand(&mut rng))
+        .collect();
+    let segment_evals: Vec<Fr> = (0..key.uniform_r1cs.num_vars)
+        .map(|_| Fr::rand(&mut rng))
+        .collect();
+
+    let z = key.evaluate_z_mle(&segment_evals, &r[row_bits..]);
+    let (a, b, c) = key.evaluate_r1cs_matrix_mles(&r);
+
+    sol!(struct R1CSExample {
+        uint256[] r;
+        uint256 rowBits;
+        uint256 colBits;
+        uint256[] segmentEvals;
+        uint256 z;
+        uint256 a;
+        uint256 b;
+        uint256 c;
+    });
+
+    let example = R1CSExample {
+        r: r.into_iter().map(into_uint256).collect(),
+        rowBits: U256::from(row_bits),
+        colBits: U256::from(col_bits),
+        segmentEvals: segment_evals.into_iter().map(into_uint256).collect(),
+        z: into_uint256(z),
+        a: into_uint256(a),
+        b: into_uint256(b),
+        c: into_uint256(c),
+    };
+
+    print!("{}", hex::encode(R1CSExample::abi_encode(&example)));
+}
@@ -0,0 +1,68 @@
+use alloy_primitives::{hex, U256};
+use alloy_sol_types::{sol, SolType};
+use ark_bn254::{Bn254, Fr};
+use jolt_core::host;
+use jolt_core::jolt::vm::rv32i_vm::{RV32IJoltVM, C, M};
+use jolt_core::jolt::vm::Jolt;
+use jolt_core::poly::commitment::hyperkzg::{
+    HyperKZG, HyperKZGProverKey, HyperKZGSRS, HyperKZGVerifierKey,
+};
+use jolt_core::poly::opening_proof::ProverOpeningAccumulator;
+use jolt_core::r1cs::inputs::JoltR1CSInputs;
+use jolt_core::r1cs::spartan::UniformSpartanProof;
+use jolt_core::utils::sol_types::{SpartanProof, VK};
+use jolt_core::utils::transcript::ProofTranscript;
+use rand_core::SeedableRng;
+
+fn main() {
+    // Proves the R1CS of the Fibonacci example's execution with a fresh transcript, whose
+    // constraints are those R1CSMatrix.sol is generated from. SpartanVerifier doesn't check the
+    // openings of the witness yet, so only its key is output, for deploying the verifier.
+    let mut program = host::Program::new("fibonacci-guest");
+    program.set_input(&9u32);
+    let (bytecode, memory_init) = program.decode();
+    let (io_device, trace) = program.trace();
+    let trace_length = trace.len();
+
+    let (polynomials, builder) = <RV32IJoltVM as Jolt<Fr, HyperKZG<Bn254>, C, M>>::witness(
+        bytecode,
+        memory_init,
+        &io_device,
+        trace,
+        trace_length,
+    );
+    let key = UniformSpartanProof::<C, JoltR1CSInputs, Fr>::setup(
+        &builder,
+        trace_length.next_power_of_two(),
+    );
+    let mut transcript = ProofTranscript::new(b"test_transcript");
+    let proof = UniformSpartanProof::<C, JoltR1CSInputs, Fr>::prove::<HyperKZG<Bn254>>(
+        &builder,
+        &key,
+        &polynomials,
+        trace_length,
+        &mut ProverOpeningAccumulator::new(),
+        &mut transcript,
+    )
+    .unwrap();
+
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+    let (_, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) =
+        HyperKZGSRS::setup(&mut rng, 2).trim(2);
+
+    sol!(struct SpartanExample {
+        SpartanProof proof;
+        VK vk;
+        uint256 logRows;
+        uint256 logCols;
+    });
+
+    let example = SpartanExample {
+        proof: (&proof).into(),
+        vk: (&vk).into(),
+        logRows: U256::from(key.num_rows_total().trailing_zeros()),
+        logCols: U256::from(key.num_cols_total().trailing_zeros()),
+    };
+
+    print!("{}", hex::encode(SpartanExample::abi_encode(&example)));
+}
@@ -0,0 +1,266 @@
+//! Packing of proofs into EIP-4844 blobs, for rollups that post proofs as blob data instead of
+//! calldata.
+//!
+//! A blob is [`FIELD_ELEMENTS_PER_BLOB`] elements of the BLS12-381 scalar field, each written as
+//! a 32-byte big-endian word. [`encode_blobs`] prefixes the payload with its length as a
+//! big-endian `u64` and packs it 31 bytes per word, leaving the top byte of every word zero so
+//! that each word is a canonical field element:
+//!
+//! ```text
+//! word i = [ 0x00 | bytes 31 * i .. 31 * (i + 1) of (length || payload), zero-padded ]
+//! ```
+//!
+//! Each blob is committed to with the KZG setup of the Ethereum ceremony ([`BlobSetup`]), and
+//! the transaction carries the commitments' versioned hashes. A contract sees only those
+//! hashes (through the `BLOBHASH` opcode), so [`BlobSubmission::onchain_payload`] is the small
+//! calldata left to send alongside: the versioned hashes and the payload's length.
+
+use alloy_primitives::{FixedBytes, U256};
+use alloy_sol_types::SolValue;
+use ark_bls12_381::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use sha2::{Digest, Sha256};
+
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+/// Payload bytes per field element: all but the top byte, which is left zero.
+pub const USABLE_BYTES_PER_FIELD_ELEMENT: usize = BYTES_PER_FIELD_ELEMENT - 1;
+pub const USABLE_BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * USABLE_BYTES_PER_FIELD_ELEMENT;
+/// Version byte of a versioned hash of a KZG commitment (EIP-4844).
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+/// Size of the length prefix written by [`encode_blobs`].
+const LENGTH_PREFIX_SIZE: usize = 8;
+
+pub type Blob = Box<[u8; BYTES_PER_BLOB]>;
+
+/// Packs `payload`, prefixed with its length, into as many blobs as it takes.
+pub fn encode_blobs(payload: &[u8]) -> Vec<Blob> {
+    let prefixed: Vec<u8> = (payload.len() as u64)
+        .to_be_bytes()
+        .into_iter()
+        .chain(payload.iter().copied())
+        .collect();
+    prefixed
+        .chunks(USABLE_BYTES_PER_BLOB)
+        .map(|data| {
+            let mut blob: Blob = vec![0u8; BYTES_PER_BLOB].try_into().unwrap();
+            for (word, bytes) in blob
+                .chunks_exact_mut(BYTES_PER_FIELD_ELEMENT)
+                .zip(data.chunks(USABLE_BYTES_PER_FIELD_ELEMENT))
+            {
+                word[1..1 + bytes.len()].copy_from_slice(bytes);
+            }
+            blob
+        })
+        .collect()
+}
+
+/// Recovers the payload written by [`encode_blobs`]. Returns `None` if a word's top byte is
+/// set, or the length prefix doesn't fit in the blobs.
+pub fn decode_blobs(blobs: &[Blob]) -> Option<Vec<u8>> {
+    let mut prefixed = Vec::with_capacity(blobs.len() * USABLE_BYTES_PER_BLOB);
+    for word in blobs
+        .iter()
+        .flat_map(|blob| blob.chunks_exact(BYTES_PER_FIELD_ELEMENT))
+    {
+        if word[0] != 0 {
+            return None;
+        }
+        prefixed.extend_from_slice(&word[1..]);
+    }
+    let length = u64::from_be_bytes(prefixed.get(..LENGTH_PREFIX_SIZE)?.try_into().unwrap());
+    let end = LENGTH_PREFIX_SIZE.checked_add(usize::try_from(length).ok()?)?;
+    Some(prefixed.get(LENGTH_PREFIX_SIZE..end)?.to_vec())
+}
+
+/// The G1 points of the EIP-4844 KZG setup in Lagrange form, in the bit-reversed order that
+/// blob elements are evaluations in.
+pub struct BlobSetup {
+    g1_lagrange: Vec<G1Affine>,
+}
+
+impl BlobSetup {
+    /// Reads the `trusted_setup.txt` format used by the consensus specs and `c-kzg-4844`: the
+    /// number of G1 points, the number of G2 points, then the G1 Lagrange points as hex-encoded
+    /// compressed points, one per line (anything after them is ignored). Returns `None` if the
+    /// setup is malformed or isn't for blobs of [`FIELD_ELEMENTS_PER_BLOB`] elements.
+    pub fn from_trusted_setup(text: &str) -> Option<Self> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let num_g1: usize = lines.next()?.parse().ok()?;
+        let _num_g2: usize = lines.next()?.parse().ok()?;
+        if num_g1 != FIELD_ELEMENTS_PER_BLOB {
+            return None;
+        }
+        let g1_lagrange = lines
+            .take(num_g1)
+            .map(|line| {
+                let bytes = alloy_primitives::hex::decode(line).ok()?;
+                G1Affine::deserialize_compressed(bytes.as_slice()).ok()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (g1_lagrange.len() == num_g1).then_some(Self { g1_lagrange })
+    }
+
+    /// The KZG commitment to `blob`, compressed as in a blob transaction.
+    pub fn commit(&self, blob: &Blob) -> [u8; 48] {
+        let scalars: Vec<Fr> = blob
+            .chunks_exact(BYTES_PER_FIELD_ELEMENT)
+            .map(Fr::from_be_bytes_mod_order)
+            .collect();
+        let commitment = G1Projective::msm(&self.g1_lagrange, &scalars)
+            .unwrap()
+            .into_affine();
+        let mut bytes = [0u8; 48];
+        commitment.serialize_compressed(&mut bytes[..]).unwrap();
+        bytes
+    }
+}
+
+/// The versioned hash of a KZG commitment: its SHA-256 digest, with the first byte replaced by
+/// [`VERSIONED_HASH_VERSION_KZG`].
+pub fn kzg_to_versioned_hash(commitment: &[u8; 48]) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// A payload packed into blobs, with what a blob transaction carrying it needs.
+pub struct BlobSubmission {
+    pub blobs: Vec<Blob>,
+    pub commitments: Vec<[u8; 48]>,
+    pub versioned_hashes: Vec<[u8; 32]>,
+    /// Length of the payload in bytes.
+    pub length: usize,
+}
+
+impl BlobSubmission {
+    /// Packs `payload` into blobs and commits to them.
+    pub fn new(setup: &BlobSetup, payload: &[u8]) -> Self {
+        let blobs = encode_blobs(payload);
+        let commitments: Vec<_> = blobs.iter().map(|blob| setup.commit(blob)).collect();
+        let versioned_hashes = commitments.iter().map(kzg_to_versioned_hash).collect();
+        Self {
+            blobs,
+            commitments,
+            versioned_hashes,
+            length: payload.len(),
+        }
+    }
+
+    /// Packs the compressed serialization of `proof` (e.g. a Jolt proof and its commitments)
+    /// into blobs.
+    pub fn for_proof<T: CanonicalSerialize>(setup: &BlobSetup, proof: &T) -> Self {
+        let mut payload = Vec::with_capacity(proof.compressed_size());
+        proof.serialize_compressed(&mut payload).unwrap();
+        Self::new(setup, &payload)
+    }
+
+    /// The calldata sent alongside the blobs: the ABI encoding of
+    /// `(bytes32[] versioned_hashes, uint256 length)`, which a contract checks against
+    /// `blobhash(i)`.
+    pub fn onchain_payload(&self) -> Vec<u8> {
+        let hashes: Vec<FixedBytes<32>> = self
+            .versioned_hashes
+            .iter()
+            .map(|hash| FixedBytes::from(*hash))
+            .collect();
+        (hashes, U256::from(self.length)).abi_encode_params()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::hex;
+    use ark_ec::AffineRepr;
+    use ark_std::UniformRand;
+    use rand_core::SeedableRng;
+
+    use super::*;
+
+    /// A setup in the `trusted_setup.txt` format with random points, which commits like the
+    /// real one but with an unknown (and untrusted) secret.
+    fn random_setup_text() -> String {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        let mut text = format!("{FIELD_ELEMENTS_PER_BLOB}\n65\n");
+        for _ in 0..FIELD_ELEMENTS_PER_BLOB {
+            let mut bytes = vec![];
+            G1Affine::rand(&mut rng)
+                .serialize_compressed(&mut bytes)
+                .unwrap();
+            text.push_str(&hex::encode(bytes));
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn round_trip() {
+        for length in [
+            0,
+            1,
+            USABLE_BYTES_PER_BLOB - LENGTH_PREFIX_SIZE,
+            USABLE_BYTES_PER_BLOB - LENGTH_PREFIX_SIZE + 1,
+            3 * USABLE_BYTES_PER_BLOB,
+        ] {
+            let payload: Vec<u8> = (0..length).map(|i| (i * 7 + 3) as u8).collect();
+            let blobs = encode_blobs(&payload);
+            assert_eq!(
+                blobs.len(),
+                (length + LENGTH_PREFIX_SIZE).div_ceil(USABLE_BYTES_PER_BLOB)
+            );
+            // Every word is a canonical field element
+            for word in blobs.iter().flat_map(|blob| blob.chunks_exact(32)) {
+                assert_eq!(word[0], 0);
+            }
+            assert_eq!(decode_blobs(&blobs), Some(payload));
+        }
+
+        let mut blobs = encode_blobs(&[1, 2, 3]);
+        blobs[0][32] = 1;
+        assert_eq!(decode_blobs(&blobs), None);
+        // A length past the end of the blobs
+        let mut blobs = encode_blobs(&[1, 2, 3]);
+        blobs[0][1] = 1;
+        assert_eq!(decode_blobs(&blobs), None);
+    }
+
+    #[test]
+    fn commitments_and_versioned_hashes() {
+        let text = random_setup_text();
+        let setup = BlobSetup::from_trusted_setup(&text).unwrap();
+        assert!(BlobSetup::from_trusted_setup(&text[..text.len() / 2]).is_none());
+
+        // A blob with a single nonzero element commits to the matching setup point
+        let mut blob: Blob = vec![0u8; BYTES_PER_BLOB].try_into().unwrap();
+        blob[5 * 32 + 31] = 1;
+        let mut expected = [0u8; 48];
+        setup.g1_lagrange[5]
+            .serialize_compressed(&mut expected[..])
+            .unwrap();
+        assert_eq!(setup.commit(&blob), expected);
+
+        let mut identity = [0u8; 48];
+        G1Affine::zero()
+            .serialize_compressed(&mut identity[..])
+            .unwrap();
+        let empty: Blob = vec![0u8; BYTES_PER_BLOB].try_into().unwrap();
+        assert_eq!(setup.commit(&empty), identity);
+
+        let submission = BlobSubmission::new(&setup, &vec![0xab; USABLE_BYTES_PER_BLOB]);
+        assert_eq!(submission.blobs.len(), 2);
+        assert_ne!(submission.commitments[0], submission.commitments[1]);
+        for (commitment, hash) in submission
+            .commitments
+            .iter()
+            .zip(&submission.versioned_hashes)
+        {
+            assert_eq!(hash[0], VERSIONED_HASH_VERSION_KZG);
+            assert_eq!(hash[1..], Sha256::digest(commitment)[1..]);
+        }
+        // Offset and length of the array, the payload length, then one word per blob
+        assert_eq!(submission.onchain_payload().len(), 32 * (3 + 2));
+    }
+}
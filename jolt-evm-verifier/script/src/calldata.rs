@@ -0,0 +1,233 @@
+//! Compact calldata encoding of HyperKZG openings, decoded on-chain by `HyperKZGCalldata.sol`.
+//!
+//! The ABI encoding of a `HyperKZGProofSol` spends two words on every G1 point and a word on
+//! the offset and length of each of its five arrays. The compact encoding instead packs:
+//!
+//! ```text
+//! [ ell: u8 | com: (ell - 1) points | w: 3 points | v_ypos: ell scalars | v_yneg: ell scalars | v_y[0] ]
+//! ```
+//!
+//! where every point is compressed to one big-endian word (`x`, with the parity of `y` in the top
+//! bit and a point-at-infinity flag in the bit below) and every scalar is one big-endian word.
+//! `v_y[1..]` is omitted: the verifier's consistency check determines `v_y[i + 1]` from
+//! `v_ypos[i]`, `v_yneg[i]`, the opening point and the challenge `r`, so the decoder recomputes
+//! it, and only the last check (against the claimed evaluation) still constrains the prover.
+//!
+//! Decompressing a point costs a `modexp` on-chain, so this trades execution gas for calldata
+//! gas. It pays off where calldata dominates the cost of a transaction, e.g. on rollups.
+
+use alloy_primitives::U256;
+use ark_bn254::{Bn254, Fq, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, BigInteger, Field, One, PrimeField};
+use jolt_core::poly::commitment::hyperkzg::HyperKZGProof;
+use jolt_core::utils::transcript::ProofTranscript;
+
+const Y_ODD_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+
+/// Encodes `proof` of an opening at a point of `proof.v[0].len()` variables.
+pub fn encode_hyperkzg_proof(proof: &HyperKZGProof<Bn254>) -> Vec<u8> {
+    let ell = proof.v[0].len();
+    assert!(
+        ell > 0 && ell <= u8::MAX as usize,
+        "unsupported number of variables"
+    );
+    assert_eq!(proof.com.len(), ell - 1);
+    assert_eq!(proof.w.len(), 3);
+
+    let mut bytes = Vec::with_capacity(1 + 32 * (3 * ell + 3));
+    bytes.push(ell as u8);
+    for point in proof.com.iter().chain(&proof.w) {
+        bytes.extend_from_slice(&compress_g1(point));
+    }
+    for scalar in proof.v[0].iter().chain(&proof.v[1]).chain(&proof.v[2][..1]) {
+        bytes.extend_from_slice(&scalar_bytes(scalar));
+    }
+    bytes
+}
+
+/// Decodes a proof encoded by [`encode_hyperkzg_proof`] for an opening at `point`, recomputing
+/// the omitted evaluations. `transcript` must be in the state `HyperKZG::verify` would be called
+/// with; it is not modified. Returns `None` if `bytes` is not a valid encoding.
+pub fn decode_hyperkzg_proof(
+    bytes: &[u8],
+    point: &[Fr],
+    transcript: &ProofTranscript,
+) -> Option<HyperKZGProof<Bn254>> {
+    let ell = point.len();
+    if ell == 0 || bytes.len() != 1 + 32 * (3 * ell + 3) || bytes[0] as usize != ell {
+        return None;
+    }
+    let mut words = bytes[1..]
+        .chunks_exact(32)
+        .map(|word| word.try_into().unwrap());
+
+    let com = (&mut words)
+        .take(ell - 1)
+        .map(decompress_g1)
+        .collect::<Option<Vec<_>>>()?;
+    let w = (&mut words)
+        .take(3)
+        .map(decompress_g1)
+        .collect::<Option<Vec<_>>>()?;
+    let ypos = (&mut words)
+        .take(ell)
+        .map(read_scalar)
+        .collect::<Option<Vec<_>>>()?;
+    let yneg = (&mut words)
+        .take(ell)
+        .map(read_scalar)
+        .collect::<Option<Vec<_>>>()?;
+    let mut y = vec![read_scalar(words.next()?)?];
+
+    // The challenge HyperKZG::verify draws right after absorbing `com`.
+    let mut transcript = transcript.clone();
+    transcript.append_points(&com.iter().map(|g| g.into_group()).collect::<Vec<_>>());
+    let r: Fr = transcript.challenge_scalar();
+    let two_r_inverse = (r + r).inverse()?;
+    for i in 0..ell - 1 {
+        let x = point[ell - i - 1];
+        y.push(
+            (r * (Fr::one() - x) * (ypos[i] + yneg[i]) + x * (ypos[i] - yneg[i])) * two_r_inverse,
+        );
+    }
+
+    Some(HyperKZGProof {
+        com,
+        w,
+        v: vec![ypos, yneg, y],
+    })
+}
+
+/// The calldata gas of `bytes` (EIP-2028): 4 per zero byte and 16 per non-zero byte.
+pub fn calldata_gas(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .map(|&byte| if byte == 0 { 4 } else { 16 })
+        .sum()
+}
+
+/// `x` in big-endian, with the parity of `y` and the point-at-infinity flag in the top two bits,
+/// which are free since the base field modulus is below 2^254.
+pub fn compress_g1(point: &G1Affine) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    if point.is_zero() {
+        word[0] = INFINITY_FLAG;
+        return word;
+    }
+    word.copy_from_slice(&point.x.into_bigint().to_bytes_be());
+    if point.y.into_bigint().is_odd() {
+        word[0] |= Y_ODD_FLAG;
+    }
+    word
+}
+
+pub fn decompress_g1(word: &[u8; 32]) -> Option<G1Affine> {
+    let flags = word[0] & (Y_ODD_FLAG | INFINITY_FLAG);
+    let mut x = *word;
+    x[0] &= !(Y_ODD_FLAG | INFINITY_FLAG);
+    if flags & INFINITY_FLAG != 0 {
+        return (flags == INFINITY_FLAG && x == [0; 32]).then(G1Affine::zero);
+    }
+
+    let x = Fq::from_bigint(BigInt::new(U256::from_be_bytes(x).into_limbs()))?;
+    let mut y = (x.square() * x + Fq::from(3u64)).sqrt()?;
+    if y.into_bigint().is_odd() != (flags & Y_ODD_FLAG != 0) {
+        y = -y;
+    }
+    // The BN254 G1 cofactor is 1, so every point on the curve is in the group.
+    Some(G1Affine::new_unchecked(x, y))
+}
+
+/// A scalar as a big-endian word.
+pub fn scalar_bytes(scalar: &Fr) -> [u8; 32] {
+    scalar.into_bigint().to_bytes_be().try_into().unwrap()
+}
+
+fn read_scalar(word: &[u8; 32]) -> Option<Fr> {
+    Fr::from_bigint(BigInt::new(U256::from_be_bytes(*word).into_limbs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::SolValue;
+    use ark_std::UniformRand;
+    use jolt_core::poly::commitment::hyperkzg::{
+        HyperKZG, HyperKZGProverKey, HyperKZGSRS, HyperKZGVerifierKey,
+    };
+    use jolt_core::poly::dense_mlpoly::DensePolynomial;
+    use jolt_core::utils::sol_types::HyperKZGProofSol;
+    use rand_core::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn compressed_points() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..16 {
+            let point = G1Affine::rand(&mut rng);
+            assert_eq!(decompress_g1(&compress_g1(&point)), Some(point));
+        }
+        assert_eq!(
+            decompress_g1(&compress_g1(&G1Affine::zero())),
+            Some(G1Affine::zero())
+        );
+
+        let point = G1Affine::rand(&mut rng);
+        let mut tampered = compress_g1(&point);
+        tampered[31] ^= 1;
+        assert_ne!(decompress_g1(&tampered), Some(point));
+        tampered[0] |= INFINITY_FLAG;
+        assert_eq!(decompress_g1(&tampered), None);
+    }
+
+    #[test]
+    fn round_trip_and_gas() {
+        for ell in [4, 12, 16] {
+            let n = 1 << ell;
+            let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+            let srs = HyperKZGSRS::setup(&mut rng, n);
+            let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+            let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect());
+            let point: Vec<_> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+            let eval = poly.evaluate(&point);
+            let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+            let transcript = ProofTranscript::new(b"TestEval");
+            let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut transcript.clone()).unwrap();
+
+            let encoded = encode_hyperkzg_proof(&proof);
+            let decoded = decode_hyperkzg_proof(&encoded, &point, &transcript).unwrap();
+            assert_eq!(decoded.com, proof.com);
+            assert_eq!(decoded.w, proof.w);
+            assert_eq!(decoded.v, proof.v);
+            HyperKZG::verify(
+                &vk,
+                &commitment,
+                &point,
+                &eval,
+                &decoded,
+                &mut transcript.clone(),
+            )
+            .unwrap();
+
+            // The commitment and proof, as passed to HyperKZG.verify and HyperKZG.verify_compressed.
+            let proof_sol: HyperKZGProofSol = (&proof).into();
+            let x = U256::from_be_slice(&commitment.0.x.into_bigint().to_bytes_be());
+            let y = U256::from_be_slice(&commitment.0.y.into_bigint().to_bytes_be());
+            let abi = (x, y, proof_sol).abi_encode_params();
+            let compact = (
+                U256::from_be_bytes(compress_g1(&commitment.0)),
+                alloy_primitives::Bytes::from(encoded),
+            )
+                .abi_encode_params();
+
+            let (abi_gas, compact_gas) = (calldata_gas(&abi), calldata_gas(&compact));
+            assert!(
+                compact_gas * 10 <= abi_gas * 6,
+                "ell = {ell}: {compact_gas} calldata gas compressed, {abi_gas} uncompressed"
+            );
+        }
+    }
+}
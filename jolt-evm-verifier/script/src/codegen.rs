@@ -0,0 +1,451 @@
+//! Generates the Solidity that depends on keys and constraints fixed by the Rust prover.
+//!
+//! `HyperKZG.sol` reads its verifier key from immutables, which a deriving contract has to set
+//! in its constructor. [`hyperkzg_verifier_contract`] writes that contract for a given key, so
+//! the key's field elements never have to be copied into Solidity by hand. For a Jolt program
+//! the key is the second half of the preprocessing's generators, `&preprocessing.generators.1`.
+//!
+//! `R1CSMatrix.sol` evaluates Jolt's R1CS matrices from the constraints of a single step, which
+//! are spelled out in its `A`, `B` and `C` functions. [`regenerate_r1cs_matrix`] rewrites them
+//! from a Spartan key, and has to be rerun (through `autogenerate_r1cs`) whenever the
+//! constraints change.
+
+use std::fmt::Write;
+
+use alloy_primitives::U256;
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInteger, One, PrimeField, Zero};
+use common::constants::RAM_START_ADDRESS;
+use common::rv_trace::{MemoryConfig, MemoryLayout};
+use jolt_core::jolt::vm::rv32i_vm::C;
+use jolt_core::poly::commitment::hyperkzg::HyperKZGVerifierKey;
+use jolt_core::r1cs::constraints::{JoltRV32IMConstraints, R1CSConstraints};
+use jolt_core::r1cs::inputs::JoltR1CSInputs;
+use jolt_core::r1cs::key::{SparseConstraints, SparseEqualityItem, UniformSpartanKey};
+use jolt_core::utils::sol_types::VK;
+
+/// Returns the source of a contract `contract_name` deriving from `HyperKZG`, which is imported
+/// from `hyperkzg_import`, a path relative to the generated file (e.g.
+/// `../subprotocols/HyperKZG.sol` for a contract written to `src/generated/`).
+pub fn hyperkzg_verifier_contract(
+    vk: &HyperKZGVerifierKey<Bn254>,
+    contract_name: &str,
+    hyperkzg_import: &str,
+) -> String {
+    assert!(
+        is_identifier(contract_name),
+        "{contract_name:?} is not a valid contract name"
+    );
+    let vk: VK = vk.into();
+    let constants = [
+        ("VK_g1_x", vk.VK_g1_x),
+        ("VK_g1_y", vk.VK_g1_y),
+        ("VK_g2_x_c0", vk.VK_g2[0]),
+        ("VK_g2_x_c1", vk.VK_g2[1]),
+        ("VK_g2_y_c0", vk.VK_g2[2]),
+        ("VK_g2_y_c1", vk.VK_g2[3]),
+        ("VK_beta_g2_x_c0", vk.VK_beta_g2[0]),
+        ("VK_beta_g2_x_c1", vk.VK_beta_g2[1]),
+        ("VK_beta_g2_y_c0", vk.VK_beta_g2[2]),
+        ("VK_beta_g2_y_c1", vk.VK_beta_g2[3]),
+    ];
+
+    let mut source = String::new();
+    writeln!(source, "// SPDX-License-Identifier: MIT").unwrap();
+    writeln!(
+        source,
+        "// Generated by jolt-evm-verifier's generate_hyperkzg_verifier. Do not edit."
+    )
+    .unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "pragma solidity ^0.8.21;").unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "import {{HyperKZG}} from \"{hyperkzg_import}\";").unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "contract {contract_name} is HyperKZG {{").unwrap();
+    writeln!(source, "    constructor() {{").unwrap();
+    for (name, value) in constants {
+        writeln!(source, "        {name} = {};", uint256_literal(value)).unwrap();
+    }
+    writeln!(source, "    }}").unwrap();
+    writeln!(source, "}}").unwrap();
+    source
+}
+
+/// Returns the Spartan key of RV32I programs with the default memory layout, for traces padded
+/// to `padded_trace_length` steps. The constraints of a step, which `R1CSMatrix.sol` is generated
+/// from, don't depend on the trace length, but do depend on the memory layout through the start
+/// of the RAM witness.
+pub fn rv32i_spartan_key(padded_trace_length: usize) -> UniformSpartanKey<C, JoltR1CSInputs, Fr> {
+    let memory_layout = MemoryLayout::new(&MemoryConfig::default());
+    let builder = <JoltRV32IMConstraints as R1CSConstraints<C, Fr>>::construct_constraints(
+        padded_trace_length,
+        RAM_START_ADDRESS - memory_layout.ram_witness_offset,
+    );
+    UniformSpartanKey::from_builder(&builder)
+}
+
+/// Opens the part of `R1CSMatrix.sol` written by [`regenerate_r1cs_matrix`].
+pub const R1CS_MATRIX_BEGIN: &str = "    // BEGIN GENERATED BY autogenerate_r1cs";
+/// Closes the part of `R1CSMatrix.sol` written by [`regenerate_r1cs_matrix`].
+pub const R1CS_MATRIX_END: &str = "    // END GENERATED BY autogenerate_r1cs";
+
+/// Replaces the generated part of `R1CSMatrix.sol`, between [`R1CS_MATRIX_BEGIN`] and
+/// [`R1CS_MATRIX_END`], with the sizes and the `A`, `B` and `C` functions for `key`.
+pub fn regenerate_r1cs_matrix(
+    source: &str,
+    key: &UniformSpartanKey<C, JoltR1CSInputs, Fr>,
+) -> String {
+    let begin = source
+        .find(R1CS_MATRIX_BEGIN)
+        .expect("R1CSMatrix.sol has no generated section");
+    let end = source[begin..]
+        .find(R1CS_MATRIX_END)
+        .expect("R1CSMatrix.sol's generated section is not closed")
+        + begin;
+    format!(
+        "{}{}{}",
+        &source[..begin],
+        r1cs_matrix_functions(key),
+        &source[end..]
+    )
+}
+
+/// Returns the generated part of `R1CSMatrix.sol` for `key`, including [`R1CS_MATRIX_BEGIN`] but
+/// not [`R1CS_MATRIX_END`].
+fn r1cs_matrix_functions(key: &UniformSpartanKey<C, JoltR1CSInputs, Fr>) -> String {
+    let uniform = &key.uniform_r1cs;
+    let offset_constraints = &key.offset_eq_r1cs.constraints;
+    let row_bits = (uniform.num_rows + 1).next_power_of_two().trailing_zeros();
+    let col_bits = uniform.num_vars.next_power_of_two().trailing_zeros();
+    // The offset constraints take the rows after the uniform ones, and are evaluated on the same
+    // (padded) row vector
+    assert!(
+        uniform.num_rows + offset_constraints.len() <= 1 << row_bits,
+        "the offset constraints don't fit in the step's rows"
+    );
+
+    let mut source = String::new();
+    writeln!(source, "{R1CS_MATRIX_BEGIN}").unwrap();
+    writeln!(
+        source,
+        "    // Generated from Jolt's constraints by jolt-evm-verifier's autogenerate_r1cs. Do not edit."
+    )
+    .unwrap();
+    writeln!(source).unwrap();
+    writeln!(
+        source,
+        "    // The log of the number of columns of a step's variables, and of the (padded) rows of its constraints"
+    )
+    .unwrap();
+    writeln!(source, "    uint256 constant COL_BITS = {col_bits};").unwrap();
+    writeln!(source, "    uint256 constant ROW_BITS = {row_bits};").unwrap();
+    writeln!(source, "    // The number of variables of a step").unwrap();
+    writeln!(
+        source,
+        "    uint256 constant SEGMENT_LENGTH = {};",
+        uniform.num_vars
+    )
+    .unwrap();
+
+    let eqs: Vec<_> = offset_constraints.iter().map(|c| &c.eq).collect();
+    let conditions: Vec<_> = offset_constraints.iter().map(|c| &c.condition).collect();
+    for (name, constraints, offset) in [
+        ("A", &uniform.a, Some(eqs.as_slice())),
+        ("B", &uniform.b, Some(conditions.as_slice())),
+        ("C", &uniform.c, None),
+    ] {
+        writeln!(source).unwrap();
+        write_matrix_function(&mut source, name, constraints, offset, uniform.num_rows);
+    }
+    source
+}
+
+/// Writes the function evaluating one of the matrices, from the uniform `constraints` and the
+/// `offset` constraint items the matrix holds, if any.
+fn write_matrix_function(
+    source: &mut String,
+    name: &str,
+    constraints: &SparseConstraints<Fr>,
+    offset: Option<&[&SparseEqualityItem<Fr>]>,
+    num_rows: usize,
+) {
+    let mut body = String::new();
+    let mut uses_eq_rx_ry_step = false;
+    let mut uses_col_eq_constant = false;
+    let mut uses_eq_step_offset_1 = false;
+    writeln!(body, "        Fr running = Fr.wrap(0);").unwrap();
+
+    // Terms with a zero coefficient don't contribute anything
+    let vars: Vec<_> = constraints
+        .vars
+        .iter()
+        .filter(|(_, _, coeff)| !coeff.is_zero())
+        .collect();
+    let consts: Vec<_> = constraints
+        .consts
+        .iter()
+        .filter(|(_, coeff)| !coeff.is_zero())
+        .collect();
+    let offset_vars = |item: &SparseEqualityItem<Fr>, step: bool| -> Vec<(usize, Fr)> {
+        item.offset_vars
+            .iter()
+            .filter(|(_, offset, coeff)| *offset == step && !coeff.is_zero())
+            .map(|(col, _, coeff)| (*col, *coeff))
+            .collect()
+    };
+
+    if !vars.is_empty() {
+        uses_eq_rx_ry_step = true;
+        writeln!(body, "        Fr rv = Fr.wrap(0);").unwrap();
+        for (row, col, coeff) in vars {
+            writeln!(
+                body,
+                "        rv = rv + {}row[{row}] * col[{col}];",
+                coefficient_factor(*coeff)
+            )
+            .unwrap();
+        }
+        writeln!(body, "        running = running + rv * eq_rx_ry_step;").unwrap();
+    }
+
+    if !consts.is_empty() {
+        uses_col_eq_constant = true;
+        writeln!(body, "        // then we do constant col").unwrap();
+        writeln!(body, "        Fr rc = Fr.wrap(0);").unwrap();
+        for (row, coeff) in consts {
+            writeln!(
+                body,
+                "        rc = rc + {}row[{row}];",
+                coefficient_factor(*coeff)
+            )
+            .unwrap();
+        }
+        writeln!(body, "        running = running + rc * col_eq_constant;").unwrap();
+    }
+
+    let offset_items: Vec<_> = offset
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            !offset_vars(item, false).is_empty()
+                || !offset_vars(item, true).is_empty()
+                || !item.constant.is_zero()
+        })
+        .collect();
+    if !offset_items.is_empty() {
+        writeln!(
+            body,
+            "        // then the offset constraints, which take the rows after the uniform ones"
+        )
+        .unwrap();
+        writeln!(body, "        Fr ro;").unwrap();
+        if offset_items
+            .iter()
+            .any(|(_, item)| !offset_vars(item, true).is_empty())
+        {
+            writeln!(body, "        Fr rn;").unwrap();
+        }
+        for (i, item) in offset_items {
+            // The vars of this step go in ro and those of the next in rn
+            let mut steps = vec![];
+            for (var, step) in [("ro", false), ("rn", true)] {
+                let terms = offset_vars(item, step);
+                if terms.is_empty() {
+                    continue;
+                }
+                writeln!(body, "        {var} = Fr.wrap(0);").unwrap();
+                for (col, coeff) in terms {
+                    writeln!(
+                        body,
+                        "        {var} = {var} + {}col[{col}];",
+                        coefficient_factor(coeff)
+                    )
+                    .unwrap();
+                }
+                if step {
+                    uses_eq_step_offset_1 = true;
+                    steps.push(format!("{var} * eq_step_offset_1"));
+                } else {
+                    uses_eq_rx_ry_step = true;
+                    steps.push(format!("{var} * eq_rx_ry_step"));
+                }
+            }
+            if steps.is_empty() {
+                writeln!(body, "        ro = Fr.wrap(0);").unwrap();
+            } else {
+                writeln!(body, "        ro = {};", steps.join(" + ")).unwrap();
+            }
+            if !item.constant.is_zero() {
+                uses_col_eq_constant = true;
+                writeln!(
+                    body,
+                    "        ro = ro + {}col_eq_constant;",
+                    coefficient_factor(item.constant)
+                )
+                .unwrap();
+            }
+            writeln!(
+                body,
+                "        running = running + ro * row[{}];",
+                num_rows + i
+            )
+            .unwrap();
+        }
+    }
+    writeln!(body).unwrap();
+    writeln!(body, "        return (running);").unwrap();
+
+    let parameter = |name: &str, used: bool| {
+        if used {
+            format!("Fr {name}")
+        } else {
+            format!("Fr /* {name} */")
+        }
+    };
+    let mut parameters = vec![
+        "Fr[] memory row".to_string(),
+        "Fr[] memory col".to_string(),
+        parameter("eq_rx_ry_step", uses_eq_rx_ry_step),
+        parameter("col_eq_constant", uses_col_eq_constant),
+    ];
+    if offset.is_some() {
+        parameters.push(parameter("eq_step_offset_1", uses_eq_step_offset_1));
+    }
+
+    writeln!(
+        source,
+        "    /// Computes the sparse MLE evaluation of {name} at the step matrix of the r1cs"
+    )
+    .unwrap();
+    writeln!(source, "    /// @param row The relevant row values").unwrap();
+    writeln!(source, "    /// @param col The relevant col values").unwrap();
+    writeln!(
+        source,
+        "    /// @param eq_rx_ry_step The const for the var steps"
+    )
+    .unwrap();
+    writeln!(
+        source,
+        "    /// @param col_eq_constant The const for the constant evals"
+    )
+    .unwrap();
+    if offset.is_some() {
+        writeln!(
+            source,
+            "    /// @param eq_step_offset_1 The const for the vars of the next step"
+        )
+        .unwrap();
+    }
+    let signature = format!("    function {name}({})", parameters.join(", "));
+    // Wraps the signature the way forge fmt does once it's longer than a line
+    if signature.len() + " internal pure returns (Fr) {".len() <= 120 {
+        writeln!(source, "{signature} internal pure returns (Fr) {{").unwrap();
+    } else {
+        writeln!(source, "{signature}").unwrap();
+        writeln!(source, "        internal").unwrap();
+        writeln!(source, "        pure").unwrap();
+        writeln!(source, "        returns (Fr)").unwrap();
+        writeln!(source, "    {{").unwrap();
+    }
+    source.push_str(&body);
+    writeln!(source, "    }}").unwrap();
+}
+
+/// Returns `coeff * `, or nothing for a coefficient of one.
+fn coefficient_factor(coeff: Fr) -> String {
+    if coeff.is_one() {
+        return String::new();
+    }
+    let coeff = U256::from_be_slice(&coeff.into_bigint().to_bytes_be());
+    format!("Fr.wrap(0x{coeff:x}) * ")
+}
+
+fn uint256_literal(value: U256) -> String {
+    format!(
+        "0x{}",
+        alloy_primitives::hex::encode(value.to_be_bytes::<32>())
+    )
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+    use jolt_core::poly::commitment::hyperkzg::{HyperKZG, HyperKZGProverKey, HyperKZGSRS};
+    use jolt_core::poly::dense_mlpoly::DensePolynomial;
+    use jolt_core::utils::sol_types::HyperKZGProofSol;
+    use jolt_core::utils::transcript::ProofTranscript;
+    use rand_core::SeedableRng;
+    use std::path::Path;
+
+    use super::*;
+    use crate::testing::{new_transcript, to_uint256, verifyCall, Deployed, HyperKZGProof};
+
+    /// Fails when Jolt's constraints have changed since `R1CSMatrix.sol` was last generated.
+    #[test]
+    fn r1cs_matrix_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/subprotocols/R1CSMatrix.sol");
+        let source = std::fs::read_to_string(path).unwrap();
+        assert!(
+            regenerate_r1cs_matrix(&source, &rv32i_spartan_key(1)) == source,
+            "R1CSMatrix.sol is out of date, rerun autogenerate_r1cs"
+        );
+    }
+
+    #[test]
+    fn is_identifier_cases() {
+        assert!(is_identifier("JoltVerifier"));
+        assert!(is_identifier("_verifier2"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("2Verifier"));
+        assert!(!is_identifier("Jolt Verifier"));
+    }
+
+    #[test]
+    fn generated_verifier_in_revm() {
+        let ell = 8;
+        let n = 1 << ell;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(ell as u64);
+        let srs = HyperKZGSRS::setup(&mut rng, n);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) = srs.trim(n);
+
+        let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect());
+        let point: Vec<_> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+        let eval = poly.evaluate(&point);
+        let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+        let mut transcript = ProofTranscript::new(b"TestEval");
+        let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut transcript).unwrap();
+
+        let mut verifier = Deployed::hyperkzg_verifier(&vk, "GeneratedHyperKZGVerifier");
+
+        let proof: HyperKZGProofSol = (&proof).into();
+        let mut call = verifyCall {
+            c_x: to_uint256(&commitment.0.x),
+            c_y: to_uint256(&commitment.0.y),
+            point: point.iter().map(to_uint256).collect(),
+            p_of_x: to_uint256(&eval),
+            pi: HyperKZGProof {
+                com: proof.com,
+                w: proof.w,
+                v_ypos: proof.v_ypos,
+                v_yneg: proof.v_yneg,
+                v_y: proof.v_y,
+            },
+            transcript: new_transcript(b"TestEval", 3),
+        };
+        assert_eq!(verifier.call(&call).map(|r| r._0), Some(true));
+
+        call.p_of_x = to_uint256(&(eval + Fr::from(1u64)));
+        assert_ne!(verifier.call(&call).map(|r| r._0), Some(true));
+    }
+}
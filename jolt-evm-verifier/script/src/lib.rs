@@ -0,0 +1,5 @@
+pub mod blob;
+pub mod calldata;
+pub mod codegen;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,264 @@
+//! Runs the Solidity verifiers in revm, so that tests can check them against the Rust prover
+//! and verifier directly instead of through Forge's ffi.
+//!
+//! Compiling needs `forge` on the `PATH`. The generated contracts are written to `src/generated/`
+//! and compiled with the rest of the Forge project.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use alloy_primitives::{keccak256, Address, Bytes, FixedBytes, U256};
+use alloy_sol_types::{sol, SolCall};
+use ark_bn254::Bn254;
+use ark_ff::{BigInteger, PrimeField};
+use jolt_core::poly::commitment::hyperkzg::HyperKZGVerifierKey;
+use revm::db::InMemoryDB;
+use revm::primitives::{ExecutionResult, Output, TxKind};
+use revm::Evm;
+
+use crate::codegen::hyperkzg_verifier_contract;
+
+sol! {
+    struct HyperKZGProof {
+        uint256[] com;
+        uint256[] w;
+        uint256[] v_ypos;
+        uint256[] v_yneg;
+        uint256[] v_y;
+    }
+
+    struct Transcript {
+        bytes32[] region;
+    }
+
+    function verify(
+        uint256 c_x,
+        uint256 c_y,
+        uint256[] point,
+        uint256 p_of_x,
+        HyperKZGProof pi,
+        Transcript transcript
+    ) external view returns (bool);
+
+    function verify_compressed(
+        uint256 commitment,
+        uint256[] point,
+        uint256 p_of_x,
+        bytes proof,
+        Transcript transcript
+    ) external view returns (bool);
+}
+
+/// Serializes `forge build`s, which would otherwise race on the build cache when tests run in
+/// parallel.
+static FORGE: Mutex<()> = Mutex::new(());
+
+fn evm_verifier_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .into()
+}
+
+/// Writes `source` to `src/generated/` and compiles it with forge, returning the contract's
+/// creation bytecode.
+pub fn compile(contract_name: &str, source: &str) -> Vec<u8> {
+    let _guard = FORGE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let root = evm_verifier_dir();
+    let generated = root.join("src/generated");
+    std::fs::create_dir_all(&generated).unwrap();
+    std::fs::write(generated.join(format!("{contract_name}.sol")), source).unwrap();
+
+    let status = Command::new("forge")
+        .arg("build")
+        .current_dir(&root)
+        .status()
+        .expect("forge must be installed to compile the generated verifier");
+    assert!(status.success(), "forge build failed");
+
+    let artifact = root.join(format!("out/{contract_name}.sol/{contract_name}.json"));
+    let artifact: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(artifact).unwrap()).unwrap();
+    let bytecode = artifact["bytecode"]["object"].as_str().unwrap();
+    alloy_primitives::hex::decode(bytecode).unwrap()
+}
+
+/// A contract deployed in an in-memory EVM.
+pub struct Deployed {
+    evm: Evm<'static, (), InMemoryDB>,
+    address: Address,
+}
+
+impl Deployed {
+    pub fn new(bytecode: Vec<u8>) -> Self {
+        let mut evm = Evm::builder().with_db(InMemoryDB::default()).build();
+        evm.tx_mut().transact_to = TxKind::Create;
+        evm.tx_mut().data = Bytes::from(bytecode);
+        let address = match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => address,
+            result => panic!("deployment failed: {result:?}"),
+        };
+        Self { evm, address }
+    }
+
+    /// Generates, compiles and deploys a `HyperKZG` verifier with `vk` baked in.
+    pub fn hyperkzg_verifier(vk: &HyperKZGVerifierKey<Bn254>, contract_name: &str) -> Self {
+        let source = hyperkzg_verifier_contract(vk, contract_name, "../subprotocols/HyperKZG.sol");
+        Self::new(compile(contract_name, &source))
+    }
+
+    /// Calls the contract without committing any state change. Returns `None` if the call
+    /// reverts.
+    pub fn call<C: SolCall>(&mut self, call: &C) -> Option<C::Return> {
+        self.evm.tx_mut().transact_to = TxKind::Call(self.address);
+        self.evm.tx_mut().data = Bytes::from(call.abi_encode());
+        match self.evm.transact().unwrap().result {
+            ExecutionResult::Success {
+                output: Output::Call(output),
+                ..
+            } => Some(C::abi_decode_returns(&output, true).unwrap()),
+            _ => None,
+        }
+    }
+}
+
+/// The in-memory state of `FiatShamirTranscript.new_transcript(label, max_size)`.
+pub fn new_transcript(label: &[u8], max_size: usize) -> Transcript {
+    let mut name = [0u8; 32];
+    name[..label.len()].copy_from_slice(label);
+    let mut region = vec![FixedBytes::ZERO; max_size + 2];
+    region[0] = keccak256(name);
+    Transcript { region }
+}
+
+pub fn to_uint256<F: PrimeField>(element: &F) -> U256 {
+    U256::from_be_slice(&element.into_bigint().to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+    use jolt_core::poly::commitment::hyperkzg::{HyperKZG, HyperKZGProverKey, HyperKZGSRS};
+    use jolt_core::poly::dense_mlpoly::DensePolynomial;
+    use jolt_core::utils::sol_types::HyperKZGProofSol;
+    use jolt_core::utils::transcript::ProofTranscript;
+    use rand_core::SeedableRng;
+
+    use super::*;
+    use crate::calldata::{compress_g1, encode_hyperkzg_proof};
+
+    const MIN_ELL: usize = 4;
+    const MAX_ELL: usize = 20;
+    const SEED: u64 = 0;
+
+    /// Checks that the Solidity verifier agrees with the Rust one on random openings of every
+    /// size, in both the ABI and the compact calldata encodings. Set `HYPERKZG_EVM_SEED` to run
+    /// with other randomness.
+    #[test]
+    fn hyperkzg_openings_in_revm() {
+        let seed = match std::env::var("HYPERKZG_EVM_SEED") {
+            Ok(seed) => seed.parse().expect("HYPERKZG_EVM_SEED must be a u64"),
+            Err(_) => SEED,
+        };
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(seed);
+
+        // The verifier key does not depend on the size of the polynomial, so one deployed
+        // verifier checks openings of every size.
+        let srs = HyperKZGSRS::setup(&mut rng, 1 << MAX_ELL);
+        let (pk, vk): (HyperKZGProverKey<Bn254>, HyperKZGVerifierKey<Bn254>) =
+            srs.trim(1 << MAX_ELL);
+        let mut verifier = Deployed::hyperkzg_verifier(&vk, "RandomizedHyperKZGVerifier");
+
+        for ell in MIN_ELL..=MAX_ELL {
+            let n = 1 << ell;
+            let poly = DensePolynomial::new((0..n).map(|_| Fr::rand(&mut rng)).collect());
+            let point: Vec<_> = (0..ell).map(|_| Fr::rand(&mut rng)).collect();
+            let eval = poly.evaluate(&point);
+            let commitment = HyperKZG::commit(&pk, &poly).unwrap();
+            let transcript = ProofTranscript::new(b"TestEval");
+            let proof = HyperKZG::open(&pk, &poly, &point, &eval, &mut transcript.clone()).unwrap();
+            let wrong_eval = eval + Fr::from(1u64);
+
+            assert!(HyperKZG::verify(
+                &vk,
+                &commitment,
+                &point,
+                &eval,
+                &proof,
+                &mut transcript.clone()
+            )
+            .is_ok());
+            assert!(HyperKZG::verify(
+                &vk,
+                &commitment,
+                &point,
+                &wrong_eval,
+                &proof,
+                &mut transcript.clone()
+            )
+            .is_err());
+
+            let proof_sol: HyperKZGProofSol = (&proof).into();
+            let mut call = verifyCall {
+                c_x: to_uint256(&commitment.0.x),
+                c_y: to_uint256(&commitment.0.y),
+                point: point.iter().map(to_uint256).collect(),
+                p_of_x: to_uint256(&eval),
+                pi: HyperKZGProof {
+                    com: proof_sol.com,
+                    w: proof_sol.w,
+                    v_ypos: proof_sol.v_ypos,
+                    v_yneg: proof_sol.v_yneg,
+                    v_y: proof_sol.v_y,
+                },
+                transcript: new_transcript(b"TestEval", 3),
+            };
+            assert_eq!(
+                verifier.call(&call).map(|r| r._0),
+                Some(true),
+                "ell = {ell}"
+            );
+
+            call.p_of_x = to_uint256(&wrong_eval);
+            assert_ne!(
+                verifier.call(&call).map(|r| r._0),
+                Some(true),
+                "ell = {ell}"
+            );
+            call.p_of_x = to_uint256(&eval);
+            call.pi.v_ypos[0] = to_uint256(&(proof.v[0][0] + Fr::from(1u64)));
+            assert_ne!(
+                verifier.call(&call).map(|r| r._0),
+                Some(true),
+                "ell = {ell}"
+            );
+
+            let mut call = verify_compressedCall {
+                commitment: U256::from_be_bytes(compress_g1(&commitment.0)),
+                point: call.point,
+                p_of_x: to_uint256(&eval),
+                proof: Bytes::from(encode_hyperkzg_proof(&proof)),
+                transcript: new_transcript(b"TestEval", 3),
+            };
+            assert_eq!(
+                verifier.call(&call).map(|r| r._0),
+                Some(true),
+                "ell = {ell}"
+            );
+
+            call.p_of_x = to_uint256(&wrong_eval);
+            assert_ne!(
+                verifier.call(&call).map(|r| r._0),
+                Some(true),
+                "ell = {ell}"
+            );
+        }
+    }
+}
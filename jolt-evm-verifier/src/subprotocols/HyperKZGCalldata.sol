@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity >=0.8.21;
+
+import {Transcript, FiatShamirTranscript} from "./FiatShamirTranscript.sol";
+import {MODULUS, Fr, FrLib} from "./Fr.sol";
+import {HyperKZGProof} from "./HyperKZG.sol";
+
+// Order of the base field of BN254, whose elements are the coordinates of G1 points
+uint256 constant BASE_MODULUS = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+// Flags in the top two bits of a compressed point, which are free as BASE_MODULUS < 2^254
+uint256 constant Y_ODD_FLAG = 1 << 255;
+uint256 constant INFINITY_FLAG = 1 << 254;
+
+// Decodes the compact HyperKZG proof encoding written by script/src/calldata.rs:
+// [ell: 1 byte][com: ell - 1 points][w: 3 points][v_ypos: ell scalars][v_yneg: ell scalars][v_y[0]]
+// where each point is compressed to one big-endian word (x, with the parity of y in the top bit and
+// the point at infinity flagged in the bit below) and each scalar is one big-endian word.
+library HyperKZGCalldata {
+    using FiatShamirTranscript for Transcript;
+    using FrLib for Fr;
+
+    /// Decodes a proof of an opening at point, recomputing v_y[1..] from the consistency checks of
+    /// HyperKZG.verify, which determine v_y[i + 1] from v_ypos[i], v_yneg[i], the point and the
+    /// challenge r. Only the last check, against the claimed evaluation, still constrains the prover.
+    /// @param data The encoded proof
+    /// @param point The point which is opened
+    /// @param transcript The transcript in the state verify will be called with, which is not modified
+    function decode_proof(bytes calldata data, uint256[] memory point, Transcript memory transcript)
+        internal
+        view
+        returns (HyperKZGProof memory pi)
+    {
+        uint256 ell = point.length;
+        require(ell > 0 && data.length == 1 + 32 * (3 * ell + 3) && uint8(data[0]) == ell, "bad encoding");
+        uint256 offset = 1;
+
+        pi.com = new uint256[](2 * (ell - 1));
+        for (uint256 i = 0; i < pi.com.length; i += 2) {
+            (pi.com[i], pi.com[i + 1]) = decompress_g1(read_word(data, offset));
+            offset += 32;
+        }
+        pi.w = new uint256[](6);
+        for (uint256 i = 0; i < 6; i += 2) {
+            (pi.w[i], pi.w[i + 1]) = decompress_g1(read_word(data, offset));
+            offset += 32;
+        }
+        pi.v_ypos = new uint256[](ell);
+        pi.v_yneg = new uint256[](ell);
+        pi.v_y = new uint256[](ell);
+        for (uint256 i = 0; i < ell; i++) {
+            pi.v_ypos[i] = read_scalar(data, offset + 32 * i);
+            pi.v_yneg[i] = read_scalar(data, offset + 32 * (ell + i));
+        }
+        pi.v_y[0] = read_scalar(data, offset + 64 * ell);
+
+        // The challenge verify draws right after absorbing com, on a copy of the transcript
+        Transcript memory copy = Transcript(new bytes32[](transcript.region.length));
+        for (uint256 i = 0; i < transcript.region.length; i++) {
+            copy.region[i] = transcript.region[i];
+        }
+        copy.append_points(pi.com);
+        Fr r = Fr.wrap(copy.challenge_scalar(MODULUS));
+        Fr two_r_inverse = (Fr.wrap(2) * r).invert();
+        for (uint256 i = 0; i + 1 < ell; i++) {
+            Fr x = FrLib.from(point[ell - i - 1]);
+            Fr ypos = Fr.wrap(pi.v_ypos[i]);
+            Fr yneg = Fr.wrap(pi.v_yneg[i]);
+            pi.v_y[i + 1] = ((r * (Fr.wrap(1) - x) * (ypos + yneg) + x * (ypos - yneg)) * two_r_inverse).unwrap();
+        }
+    }
+
+    /// Decompresses a G1 point by solving y^2 = x^3 + 3, returning (0, 0) for the point at infinity as the
+    /// precompiles expect
+    /// @param compressed The x coordinate with the flags in its top two bits
+    function decompress_g1(uint256 compressed) internal view returns (uint256 x, uint256 y) {
+        if (compressed & INFINITY_FLAG != 0) {
+            require(compressed == INFINITY_FLAG, "bad point");
+            return (0, 0);
+        }
+        x = compressed & ~(Y_ODD_FLAG | INFINITY_FLAG);
+        require(x < BASE_MODULUS, "bad point");
+        uint256 rhs = addmod(mulmod(mulmod(x, x, BASE_MODULUS), x, BASE_MODULUS), 3, BASE_MODULUS);
+        // BASE_MODULUS = 3 mod 4, so a square root of rhs, if there is one, is rhs^((BASE_MODULUS + 1) / 4)
+        y = base_field_pow(rhs, (BASE_MODULUS + 1) / 4);
+        require(mulmod(y, y, BASE_MODULUS) == rhs, "bad point");
+        if ((y & 1 == 1) != (compressed & Y_ODD_FLAG != 0)) {
+            y = BASE_MODULUS - y;
+        }
+    }
+
+    function read_word(bytes calldata data, uint256 offset) private pure returns (uint256) {
+        return uint256(bytes32(data[offset:offset + 32]));
+    }
+
+    function read_scalar(bytes calldata data, uint256 offset) private pure returns (uint256 scalar) {
+        scalar = read_word(data, offset);
+        require(scalar < MODULUS, "bad scalar");
+    }
+
+    function base_field_pow(uint256 base, uint256 exponent) private view returns (uint256 result) {
+        // Call the modexp precompile
+        assembly ("memory-safe") {
+            let free := mload(0x40)
+            mstore(free, 0x20)
+            mstore(add(free, 0x20), 0x20)
+            mstore(add(free, 0x40), 0x20)
+            mstore(add(free, 0x60), base)
+            mstore(add(free, 0x80), exponent)
+            mstore(add(free, 0xa0), BASE_MODULUS)
+            if iszero(staticcall(gas(), 0x05, free, 0xc0, 0x00, 0x20)) { revert(0, 0) }
+            result := mload(0x00)
+        }
+    }
+}
@@ -8,24 +8,19 @@ import "forge-std/console.sol";
 library R1CSMatrix {
     using FrLib for Fr;
 
-    // These constants being changed may break the logic in some areas as the assumption of their size or bit composition is built in
-    uint256 constant COL_BITS = 7;
-    uint256 constant ROW_BITS = 7;
-    uint256 constant SEGMENT_LENGTH = 89;
-
     /// Evaluates the z mle for the outer sumcheck
     /// @param r The r we are evaluating on
-    /// @param segmentEvals The segment evals from the proof, should be length 89
+    /// @param segmentEvals The segment evals from the proof, should be length SEGMENT_LENGTH
     function eval_z_mle(Fr[] memory r, uint256[] memory segmentEvals) internal pure returns (Fr) {
         require(segmentEvals.length == SEGMENT_LENGTH, "Incorrect segment length");
-        // We calculate the evals based on a length 7 slice
-        Fr[] memory r_var_eqs = eq_poly_evals(r, 1, ROW_BITS);
+        // We calculate the evals based on a length COL_BITS slice
+        Fr[] memory r_var_eqs = eq_poly_evals(r, 1, COL_BITS);
         Fr sum = Fr.wrap(0);
         for (uint256 i = 0; i < SEGMENT_LENGTH; i++) {
             sum = sum + r_var_eqs[i] * FrLib.from(segmentEvals[i]);
         }
         // In the rust we do let const_poly = SparsePolynomial::new(self.num_vars_total().log_2(), vec![(F::one(), 0)]); let eval_const = const_poly.evaluate(r_rest);
-        // But because we presume self.num_vars_total().log_2() = 7 and the vec only has one element we just have to run compute chis on the bit vec of zero and r
+        // But because the vec only has one element we just have to run compute chis on the bit vec of zero and r
         // Which simplifies to this for loop:
         Fr prod = Fr.wrap(1);
         for (uint256 i = 0; i < r.length - 1; i++) {
@@ -37,43 +32,39 @@ library R1CSMatrix {
     }
 
     /// Evaluates our stepwise low memory representation of the r1cs using a constant fixed matrix of constraints.
-    /// NOTE - Changing the R1CS in jolt's rust will break this function and new constant and offset calculations must
-    ///        be calculated for the A, B and C functions
-    /// @param r Our typechecked field element array which is log the size of the jolt trace
+    /// NOTE - The A, B and C functions are generated from jolt's constraints by autogenerate_r1cs, which must be
+    ///        rerun whenever the R1CS in jolt's rust changes
+    /// @param r Our typechecked field element array, the row point followed by the col point
     /// @param row_bits The log of the number of rows of our global r1cs
     /// @param col_bits The log of the number of cols of our global r1cs
-    // @param total_cols The total number of columns in our r1cs used for our col bit vector.
-    function evaluate_r1cs_matrix_mles(Fr[] memory r, uint256 row_bits, uint256 col_bits, uint256)
+    function evaluate_r1cs_matrix_mles(Fr[] memory r, uint256 row_bits, uint256 col_bits)
         internal
         pure
         returns (Fr, Fr, Fr)
     {
-        uint256 constraint_row_bits = ROW_BITS;
-        uint256 constraint_col_bits = COL_BITS;
-        //uint256 step_bits = row_bits - constraint_row_bits;
+        // The row bits are those of the constraint within a step then those of the step, and the col bits are the
+        // constant col bit, those of the variable within a step and then those of the step
+        require(row_bits >= ROW_BITS && r.length == row_bits + col_bits, "Incorrect r length");
+        uint256 step_bits = row_bits - ROW_BITS;
+        require(col_bits == 1 + COL_BITS + step_bits, "Mismatched row and col steps");
+        uint256 col_step_start = row_bits + 1 + COL_BITS;
 
-        // Do an eq pol eval on the parts of r which are not in the first 7 bits of the row and col sub vector
-        Fr eq_rx_ry_step = eq_poly_evaluate(
-            r,
-            constraint_row_bits,
-            row_bits - constraint_row_bits,
-            r,
-            row_bits + constraint_col_bits + 1,
-            col_bits - constraint_col_bits
-        );
-        Fr[] memory eq_poly_row = eq_poly_evals(r, 0, constraint_row_bits);
-        // Creates a 256 field element allocation but doesn't need it?
-        Fr[] memory eq_poly_col = eq_poly_evals(r, row_bits, constraint_col_bits + 1);
-
-        // Does the eval of the constraint row const via a bit vec
-        Fr col_eq_constant = Fr.wrap(0);
-        // Fr eq_plus_one_eval = eq_plus_one(r, constraint_row_bits, r, row_bits + constraint_col_bits + 1, step_bits);
+        // Do an eq pol eval on the step parts of the row and col sub vectors
+        Fr eq_rx_ry_step = eq_poly_evaluate(r, ROW_BITS, step_bits, r, col_step_start, step_bits);
+        // And the same for a col step which is the one after the row step, for the offset constraints
+        Fr eq_step_offset_1 = eq_plus_one(r, ROW_BITS, r, col_step_start, step_bits);
+        Fr[] memory eq_poly_row = eq_poly_evals(r, 0, ROW_BITS);
+        Fr[] memory eq_poly_col = eq_poly_evals(r, row_bits, COL_BITS + 1);
 
-        // Fr non_uni_constraint_index = Fr.wrap(0);
+        // Does the eval of the constant col, which is the first col of the second half, via its bit vec
+        Fr col_eq_constant = r[row_bits];
+        for (uint256 i = row_bits + 1; i < r.length; i++) {
+            col_eq_constant = col_eq_constant * (Fr.wrap(1) - r[i]);
+        }
 
         return (
-            A(eq_poly_row, eq_poly_col, eq_rx_ry_step, col_eq_constant),
-            B(eq_poly_row, eq_poly_col, eq_rx_ry_step, col_eq_constant),
+            A(eq_poly_row, eq_poly_col, eq_rx_ry_step, col_eq_constant, eq_step_offset_1),
+            B(eq_poly_row, eq_poly_col, eq_rx_ry_step, col_eq_constant, eq_step_offset_1),
             C(eq_poly_row, eq_poly_col, eq_rx_ry_step, col_eq_constant)
         );
     }
@@ -134,297 +125,415 @@ library R1CSMatrix {
         return evals;
     }
 
+    // The same as rust's eq_plus_one(x, y, length), which is one on bit vecs where y is x plus one
     function eq_plus_one(Fr[] memory x, uint256 x_start, Fr[] memory y, uint256 y_start, uint256 length)
         internal
         pure
         returns (Fr)
     {
-        // Firstly we evaluate the upper bit products
-        Fr running_upper_product = Fr.wrap(1);
+        // The bits are counted from the least significant, which is the last element. Firstly we evaluate the
+        // products of the eq terms of the bits above each bit.
         Fr[] memory upper = new Fr[](length);
-        for (uint256 i = length - 1;; i = i - 1) {
-            Fr new_prod =
-                x[x_start + i] * y[y_start + i] * (Fr.wrap(1) - x[x_start + i]) * (Fr.wrap(1) - y[y_start + i]);
-            running_upper_product = running_upper_product * new_prod;
-            upper[i] = running_upper_product;
-            if (i == 0) {
-                break;
-            }
+        Fr running_upper_product = Fr.wrap(1);
+        for (uint256 i = length; i > 0; i--) {
+            upper[i - 1] = running_upper_product;
+            Fr x_bit = x[x_start + length - i];
+            Fr y_bit = y[y_start + length - i];
+            running_upper_product =
+                running_upper_product * (x_bit * y_bit + (Fr.wrap(1) - x_bit) * (Fr.wrap(1) - y_bit));
         }
 
-        // Now we can compute the lower bit product and current bit product by just working in order
+        // Now we can compute the lower bit product and current bit product by just working in order, as x is
+        // ones and y is zeros below the bit where y is one and x is zero
         Fr running_lower_product = Fr.wrap(1);
         Fr sum = Fr.wrap(0);
         for (uint256 i = 0; i < length; i++) {
-            Fr new_prod = x[x_start + length - 1 - i] * (Fr.wrap(1) - y[y_start + length - 1 - i]);
-            running_lower_product = running_lower_product * new_prod;
-            Fr current_bit = y[y_start + length - 1 - i] * (Fr.wrap(1) - x[x_start + length - 1 - i]);
-            sum = sum + running_lower_product * upper[i] * current_bit;
+            Fr x_bit = x[x_start + length - 1 - i];
+            Fr y_bit = y[y_start + length - 1 - i];
+            sum = sum + running_lower_product * (Fr.wrap(1) - x_bit) * y_bit * upper[i];
+            running_lower_product = running_lower_product * x_bit * (Fr.wrap(1) - y_bit);
         }
 
         return (sum);
     }
 
-    /// Computes the sparse MLE evaluation at the step matrix of the r1cs#
+    // BEGIN GENERATED BY autogenerate_r1cs
+    // Generated from Jolt's constraints by jolt-evm-verifier's autogenerate_r1cs. Do not edit.
+
+    // The log of the number of columns of a step's variables, and of the (padded) rows of its constraints
+    uint256 constant COL_BITS = 7;
+    uint256 constant ROW_BITS = 7;
+    // The number of variables of a step
+    uint256 constant SEGMENT_LENGTH = 96;
+
+    /// Computes the sparse MLE evaluation of A at the step matrix of the r1cs
     /// @param row The relevant row values
-    /// @param col The relevant row values
+    /// @param col The relevant col values
     /// @param eq_rx_ry_step The const for the var steps
     /// @param col_eq_constant The const for the constant evals
-    function A(Fr[] memory row, Fr[] memory col, Fr eq_rx_ry_step, Fr col_eq_constant) internal pure returns (Fr) {
+    /// @param eq_step_offset_1 The const for the vars of the next step
+    function A(Fr[] memory row, Fr[] memory col, Fr eq_rx_ry_step, Fr col_eq_constant, Fr eq_step_offset_1)
+        internal
+        pure
+        returns (Fr)
+    {
         Fr running = Fr.wrap(0);
         Fr rv = Fr.wrap(0);
-        rv = rv + row[0] * col[34];
-        rv = rv + row[1] * col[35];
-        rv = rv + row[2] * col[36];
-        rv = rv + row[3] * col[37];
-        rv = rv + row[4] * col[38];
-        rv = rv + row[5] * col[39];
-        rv = rv + row[6] * col[40];
-        rv = rv + row[7] * col[41];
-        rv = rv + row[8] * col[42];
-        rv = rv + row[9] * col[43];
-        rv = rv + row[10] * col[44];
-        rv = rv + row[11] * col[45];
-        rv = rv + row[12] * col[46];
-        rv = rv + row[13] * col[47];
-        rv = rv + row[14] * col[48];
-        rv = rv + row[15] * col[49];
-        rv = rv + row[16] * col[50];
-        rv = rv + row[17] * col[51];
-        rv = rv + row[18] * col[52];
-        rv = rv + row[19] * col[53];
-        rv = rv + row[20] * col[54];
-        rv = rv + row[21] * col[55];
-        rv = rv + row[22] * col[56];
-        rv = rv + row[23] * col[57];
-        rv = rv + row[24] * col[58];
-        rv = rv + row[25] * col[59];
-        rv = rv + row[26] * col[60];
-        rv = rv + row[27] * col[61];
-        rv = rv + row[28] * col[62];
-        rv = rv + row[29] * col[63];
-        rv = rv + row[30] * col[64];
-        rv = rv + row[31] * col[65];
-        rv = rv + row[32] * col[66];
-        rv = rv + row[33] * col[67];
-        rv = rv + row[34] * col[68];
-        rv = rv + row[35] * col[69];
-        rv = rv + row[36] * col[70];
-        rv = rv + row[37] * col[71];
-        rv = rv + row[38] * col[0];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[38] * col[1];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[39] * col[3];
-        rv = rv + Fr.wrap(0x2000000000) * row[39] * col[34];
-        rv = rv + Fr.wrap(0x1000000000) * row[39] * col[35];
-        rv = rv + Fr.wrap(0x0800000000) * row[39] * col[36];
-        rv = rv + Fr.wrap(0x0400000000) * row[39] * col[37];
-        rv = rv + Fr.wrap(0x0200000000) * row[39] * col[38];
-        rv = rv + Fr.wrap(0x0100000000) * row[39] * col[39];
-        rv = rv + Fr.wrap(0x0080000000) * row[39] * col[40];
-        rv = rv + Fr.wrap(0x0040000000) * row[39] * col[41];
-        rv = rv + Fr.wrap(0x0020000000) * row[39] * col[42];
-        rv = rv + Fr.wrap(0x0010000000) * row[39] * col[43];
-        rv = rv + Fr.wrap(0x0008000000) * row[39] * col[44];
-        rv = rv + Fr.wrap(0x0004000000) * row[39] * col[45];
-        rv = rv + Fr.wrap(0x0002000000) * row[39] * col[46];
-        rv = rv + Fr.wrap(0x0001000000) * row[39] * col[47];
-        rv = rv + Fr.wrap(0x0000800000) * row[39] * col[48];
-        rv = rv + Fr.wrap(0x0000400000) * row[39] * col[49];
-        rv = rv + Fr.wrap(0x0000200000) * row[39] * col[50];
-        rv = rv + Fr.wrap(0x0000100000) * row[39] * col[51];
-        rv = rv + Fr.wrap(0x0000080000) * row[39] * col[52];
-        rv = rv + Fr.wrap(0x0000040000) * row[39] * col[53];
-        rv = rv + Fr.wrap(0x0000020000) * row[39] * col[54];
-        rv = rv + Fr.wrap(0x0000010000) * row[39] * col[55];
-        rv = rv + Fr.wrap(0x0000008000) * row[39] * col[56];
-        rv = rv + Fr.wrap(0x0000004000) * row[39] * col[57];
-        rv = rv + Fr.wrap(0x0000002000) * row[39] * col[58];
-        rv = rv + Fr.wrap(0x0000001000) * row[39] * col[59];
-        rv = rv + Fr.wrap(0x0000000800) * row[39] * col[60];
-        rv = rv + Fr.wrap(0x0000000400) * row[39] * col[61];
-        rv = rv + Fr.wrap(0x0000000200) * row[39] * col[62];
-        rv = rv + Fr.wrap(0x0000000100) * row[39] * col[63];
-        rv = rv + Fr.wrap(0x0000000080) * row[39] * col[64];
-        rv = rv + Fr.wrap(0x0000000040) * row[39] * col[65];
-        rv = rv + Fr.wrap(0x0000000020) * row[39] * col[66];
-        rv = rv + Fr.wrap(0x0000000010) * row[39] * col[67];
-        rv = rv + Fr.wrap(0x0000000008) * row[39] * col[68];
-        rv = rv + Fr.wrap(0x0000000004) * row[39] * col[69];
-        rv = rv + Fr.wrap(0x0000000002) * row[39] * col[70];
-        rv = rv + row[39] * col[71];
-        rv = rv + row[40] * col[34];
-        rv = rv + row[41] * col[35];
+        rv = rv + row[0] * col[47];
+        rv = rv + row[1] * col[48];
+        rv = rv + row[2] * col[49];
+        rv = rv + row[3] * col[50];
+        rv = rv + row[4] * col[51];
+        rv = rv + row[5] * col[52];
+        rv = rv + row[6] * col[53];
+        rv = rv + row[7] * col[54];
+        rv = rv + row[8] * col[55];
+        rv = rv + row[9] * col[56];
+        rv = rv + row[10] * col[57];
+        rv = rv + row[11] * col[58];
+        rv = rv + row[12] * col[59];
+        rv = rv + row[13] * col[60];
+        rv = rv + row[14] * col[61];
+        rv = rv + row[15] * col[62];
+        rv = rv + row[16] * col[63];
+        rv = rv + row[17] * col[64];
+        rv = rv + row[18] * col[65];
+        rv = rv + row[19] * col[66];
+        rv = rv + row[20] * col[67];
+        rv = rv + row[21] * col[68];
+        rv = rv + row[22] * col[69];
+        rv = rv + row[23] * col[70];
+        rv = rv + row[24] * col[71];
+        rv = rv + row[25] * col[72];
+        rv = rv + row[26] * col[73];
+        rv = rv + row[27] * col[74];
+        rv = rv + row[28] * col[75];
+        rv = rv + row[29] * col[76];
+        rv = rv + row[30] * col[77];
+        rv = rv + row[31] * col[78];
+        rv = rv + row[32] * col[79];
+        rv = rv + row[33] * col[80];
+        rv = rv + row[34] * col[81];
+        rv = rv + row[35] * col[82];
+        rv = rv + row[36] * col[35];
+        rv = rv + row[37] * col[36];
+        rv = rv + row[38] * col[37];
+        rv = rv + row[39] * col[38];
+        rv = rv + row[40] * col[39];
+        rv = rv + row[41] * col[40];
         rv = rv + row[42] * col[41];
-        rv = rv + row[43] * col[36];
-        rv = rv + row[43] * col[37];
-        rv = rv + row[44] * col[36];
-        rv = rv + row[45] * col[36];
-        rv = rv + row[46] * col[36];
-        rv = rv + row[47] * col[36];
-        rv = rv + row[48] * col[17];
-        rv = rv + Fr.wrap(0x0000000100) * row[48] * col[18];
-        rv = rv + Fr.wrap(0x0000010000) * row[48] * col[19];
-        rv = rv + Fr.wrap(0x0001000000) * row[48] * col[20];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[48] * col[75];
-        rv = rv + row[49] * col[37];
-        rv = rv + Fr.wrap(0x0000000000000000000000000000000000000000000000000001000000000000) * row[50] * col[29];
-        rv = rv + Fr.wrap(0x0100000000) * row[50] * col[30];
-        rv = rv + Fr.wrap(0x0000010000) * row[50] * col[31];
-        rv = rv + row[50] * col[32];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[50] * col[76];
-        rv = rv + row[51] * col[45];
-        rv = rv + row[52] * col[46];
-        rv = rv + row[53] * col[36];
+        rv = rv + row[43] * col[42];
+        rv = rv + row[44] * col[43];
+        rv = rv + row[45] * col[44];
+        rv = rv + row[46] * col[45];
+        rv = rv + row[47] * col[46];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[48] * col[2];
+        rv = rv + Fr.wrap(0x800000000000) * row[48] * col[35];
+        rv = rv + Fr.wrap(0x400000000000) * row[48] * col[36];
+        rv = rv + Fr.wrap(0x200000000000) * row[48] * col[37];
+        rv = rv + Fr.wrap(0x100000000000) * row[48] * col[38];
+        rv = rv + Fr.wrap(0x80000000000) * row[48] * col[39];
+        rv = rv + Fr.wrap(0x40000000000) * row[48] * col[40];
+        rv = rv + Fr.wrap(0x20000000000) * row[48] * col[41];
+        rv = rv + Fr.wrap(0x10000000000) * row[48] * col[42];
+        rv = rv + Fr.wrap(0x8000000000) * row[48] * col[43];
+        rv = rv + Fr.wrap(0x4000000000) * row[48] * col[44];
+        rv = rv + Fr.wrap(0x2000000000) * row[48] * col[45];
+        rv = rv + Fr.wrap(0x1000000000) * row[48] * col[46];
+        rv = rv + Fr.wrap(0x800000000) * row[48] * col[47];
+        rv = rv + Fr.wrap(0x400000000) * row[48] * col[48];
+        rv = rv + Fr.wrap(0x200000000) * row[48] * col[49];
+        rv = rv + Fr.wrap(0x100000000) * row[48] * col[50];
+        rv = rv + Fr.wrap(0x80000000) * row[48] * col[51];
+        rv = rv + Fr.wrap(0x40000000) * row[48] * col[52];
+        rv = rv + Fr.wrap(0x20000000) * row[48] * col[53];
+        rv = rv + Fr.wrap(0x10000000) * row[48] * col[54];
+        rv = rv + Fr.wrap(0x8000000) * row[48] * col[55];
+        rv = rv + Fr.wrap(0x4000000) * row[48] * col[56];
+        rv = rv + Fr.wrap(0x2000000) * row[48] * col[57];
+        rv = rv + Fr.wrap(0x1000000) * row[48] * col[58];
+        rv = rv + Fr.wrap(0x800000) * row[48] * col[59];
+        rv = rv + Fr.wrap(0x400000) * row[48] * col[60];
+        rv = rv + Fr.wrap(0x200000) * row[48] * col[61];
+        rv = rv + Fr.wrap(0x100000) * row[48] * col[62];
+        rv = rv + Fr.wrap(0x80000) * row[48] * col[63];
+        rv = rv + Fr.wrap(0x40000) * row[48] * col[64];
+        rv = rv + Fr.wrap(0x20000) * row[48] * col[65];
+        rv = rv + Fr.wrap(0x10000) * row[48] * col[66];
+        rv = rv + Fr.wrap(0x8000) * row[48] * col[67];
+        rv = rv + Fr.wrap(0x4000) * row[48] * col[68];
+        rv = rv + Fr.wrap(0x2000) * row[48] * col[69];
+        rv = rv + Fr.wrap(0x1000) * row[48] * col[70];
+        rv = rv + Fr.wrap(0x800) * row[48] * col[71];
+        rv = rv + Fr.wrap(0x400) * row[48] * col[72];
+        rv = rv + Fr.wrap(0x200) * row[48] * col[73];
+        rv = rv + Fr.wrap(0x100) * row[48] * col[74];
+        rv = rv + Fr.wrap(0x80) * row[48] * col[75];
+        rv = rv + Fr.wrap(0x40) * row[48] * col[76];
+        rv = rv + Fr.wrap(0x20) * row[48] * col[77];
+        rv = rv + Fr.wrap(0x10) * row[48] * col[78];
+        rv = rv + Fr.wrap(0x8) * row[48] * col[79];
+        rv = rv + Fr.wrap(0x4) * row[48] * col[80];
+        rv = rv + Fr.wrap(0x2) * row[48] * col[81];
+        rv = rv + row[48] * col[82];
+        rv = rv + row[49] * col[35];
+        rv = rv + row[50] * col[36];
+        rv = rv + row[51] * col[42];
+        rv = rv + row[52] * col[37];
+        rv = rv + row[52] * col[38];
+        rv = rv + row[53] * col[37];
         rv = rv + row[54] * col[37];
-        rv = rv + Fr.wrap(0x0001000000) * row[55] * col[21];
-        rv = rv + Fr.wrap(0x0000010000) * row[55] * col[22];
-        rv = rv + Fr.wrap(0x0000000100) * row[55] * col[23];
-        rv = rv + row[55] * col[24];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[55] * col[77];
-        rv = rv + Fr.wrap(0x0001000000) * row[56] * col[25];
-        rv = rv + Fr.wrap(0x0000010000) * row[56] * col[26];
-        rv = rv + Fr.wrap(0x0000000100) * row[56] * col[27];
-        rv = rv + row[56] * col[28];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[56] * col[78];
-        rv = rv + row[57] * col[42];
-        rv = rv + row[58] * col[42];
-        rv = rv + row[59] * col[61];
-        rv = rv + row[59] * col[62];
-        rv = rv + row[59] * col[63];
-        rv = rv + row[60] * col[42];
-        rv = rv + row[61] * col[61];
-        rv = rv + row[61] * col[62];
-        rv = rv + row[61] * col[63];
-        rv = rv + row[62] * col[42];
-        rv = rv + row[63] * col[61];
-        rv = rv + row[63] * col[62];
-        rv = rv + row[63] * col[63];
-        rv = rv + row[64] * col[42];
-        rv = rv + row[65] * col[61];
-        rv = rv + row[65] * col[62];
-        rv = rv + row[65] * col[63];
-        rv = rv + row[66] * col[42];
-        rv = rv + row[67] * col[6];
-        rv = rv + row[68] * col[83];
-        rv = rv + row[69] * col[6];
-        rv = rv + row[70] * col[84];
-        rv = rv + row[71] * col[39];
-        rv = rv + row[72] * col[38];
-        rv = rv + row[73] * col[85];
+        rv = rv + row[55] * col[37];
+        rv = rv + row[56] * col[37];
+        rv = rv + row[57] * col[38];
+        rv = rv + row[58] * col[47];
+        rv = rv + row[59] * col[48];
+        rv = rv + row[60] * col[83];
+        rv = rv + row[61] * col[67];
+        rv = rv + row[61] * col[68];
+        rv = rv + row[61] * col[69];
+        rv = rv + row[62] * col[66];
+        rv = rv + row[62] * col[71];
+        rv = rv + row[63] * col[37];
+        rv = rv + row[64] * col[38];
+        rv = rv + row[65] * col[45];
+        rv = rv + row[66] * col[43];
+        rv = rv + row[67] * col[43];
+        rv = rv + row[68] * col[63];
+        rv = rv + row[68] * col[64];
+        rv = rv + row[68] * col[65];
+        rv = rv + row[69] * col[43];
+        rv = rv + row[70] * col[63];
+        rv = rv + row[70] * col[64];
+        rv = rv + row[70] * col[65];
+        rv = rv + row[71] * col[43];
+        rv = rv + row[72] * col[63];
+        rv = rv + row[72] * col[64];
+        rv = rv + row[72] * col[65];
+        rv = rv + row[73] * col[43];
+        rv = rv + row[74] * col[63];
+        rv = rv + row[74] * col[64];
+        rv = rv + row[74] * col[65];
+        rv = rv + row[75] * col[43];
+        rv = rv + row[76] * col[5];
+        rv = rv + row[77] * col[91];
+        rv = rv + row[78] * col[5];
+        rv = rv + row[79] * col[92];
+        rv = rv + row[80] * col[39];
+        rv = rv + row[81] * col[40];
+        rv = rv + row[82] * col[94];
+        rv = rv + row[83] * col[46];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[84] * col[34];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[85] * col[34];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[86] * col[34];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[87] * col[34];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[88] * col[34];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[89] * col[34];
         running = running + rv * eq_rx_ry_step;
         // then we do constant col
         Fr rc = Fr.wrap(0);
+        rc = rc + row[84];
+        rc = rc + row[85];
+        rc = rc + row[86];
+        rc = rc + row[87];
+        rc = rc + row[88];
+        rc = rc + row[89];
         running = running + rc * col_eq_constant;
+        // then the offset constraints, which take the rows after the uniform ones
+        Fr ro;
+        Fr rn;
+        ro = Fr.wrap(0);
+        ro = ro + col[95];
+        rn = Fr.wrap(0);
+        rn = rn + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffd) * col[1];
+        ro = ro * eq_rx_ry_step + rn * eq_step_offset_1;
+        ro = ro + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f59370000001) * col_eq_constant;
+        running = running + ro * row[90];
+        ro = Fr.wrap(0);
+        ro = ro + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * col[0];
+        rn = Fr.wrap(0);
+        rn = rn + col[0];
+        ro = ro * eq_rx_ry_step + rn * eq_step_offset_1;
+        ro = ro + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * col_eq_constant;
+        running = running + ro * row[91];
+        ro = Fr.wrap(0);
+        ro = ro + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * col[33];
+        ro = ro + col[34];
+        ro = ro + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * col[46];
+        rn = Fr.wrap(0);
+        rn = rn + col[33];
+        ro = ro * eq_rx_ry_step + rn * eq_step_offset_1;
+        running = running + ro * row[92];
+
         return (running);
     }
 
-    /// Computes the sparse MLE evaluation at the step matrix of the r1cs#
+    /// Computes the sparse MLE evaluation of B at the step matrix of the r1cs
     /// @param row The relevant row values
-    /// @param col The relevant row values
+    /// @param col The relevant col values
     /// @param eq_rx_ry_step The const for the var steps
     /// @param col_eq_constant The const for the constant evals
-    function B(Fr[] memory row, Fr[] memory col, Fr eq_rx_ry_step, Fr col_eq_constant) internal pure returns (Fr) {
+    /// @param eq_step_offset_1 The const for the vars of the next step
+    function B(Fr[] memory row, Fr[] memory col, Fr eq_rx_ry_step, Fr col_eq_constant, Fr eq_step_offset_1)
+        internal
+        pure
+        returns (Fr)
+    {
         Fr running = Fr.wrap(0);
         Fr rv = Fr.wrap(0);
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[0] * col[34];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[1] * col[35];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[2] * col[36];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[3] * col[37];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[4] * col[38];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[5] * col[39];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[6] * col[40];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[7] * col[41];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[8] * col[42];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[9] * col[43];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[10] * col[44];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[11] * col[45];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[12] * col[46];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[13] * col[47];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[14] * col[48];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[15] * col[49];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[16] * col[50];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[17] * col[51];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[18] * col[52];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[19] * col[53];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[20] * col[54];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[21] * col[55];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[22] * col[56];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[23] * col[57];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[24] * col[58];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[25] * col[59];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[26] * col[60];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[27] * col[61];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[28] * col[62];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[29] * col[63];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[30] * col[64];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[31] * col[65];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[32] * col[66];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[33] * col[67];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[34] * col[68];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[35] * col[69];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[36] * col[70];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[37] * col[71];
-        rv = rv + Fr.wrap(0x0000000004) * row[40] * col[0];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[40] * col[9];
-        rv = rv + row[41] * col[7];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[41] * col[10];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[43] * col[8];
-        rv = rv + row[43] * col[9];
-        rv = rv + row[43] * col[74];
-        rv = rv + row[44] * col[12];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[44] * col[17];
-        rv = rv + row[45] * col[13];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[45] * col[18];
-        rv = rv + row[46] * col[14];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[46] * col[19];
-        rv = rv + row[47] * col[15];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[47] * col[20];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[49] * col[33];
-        rv = rv + row[49] * col[75];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[51] * col[72];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[51] * col[73];
-        rv = rv + row[51] * col[76];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[52] * col[72];
-        rv = rv + row[52] * col[73];
-        rv = rv + row[52] * col[76];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[53] * col[75];
-        rv = rv + row[53] * col[76];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[54] * col[10];
-        rv = rv + row[54] * col[76];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[57] * col[72];
-        rv = rv + row[57] * col[77];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[58] * col[73];
-        rv = rv + row[58] * col[78];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[59] * col[25];
-        rv = rv + row[59] * col[28];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[60] * col[21];
-        rv = rv + row[60] * col[29];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[60] * col[79];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[61] * col[26];
-        rv = rv + row[61] * col[28];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[62] * col[22];
-        rv = rv + row[62] * col[30];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[62] * col[80];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[63] * col[27];
-        rv = rv + row[63] * col[28];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[64] * col[23];
-        rv = rv + row[64] * col[31];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[64] * col[81];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[66] * col[24];
-        rv = rv + row[66] * col[32];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[66] * col[82];
-        rv = rv + row[67] * col[40];
-        rv = rv + row[68] * col[16];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[68] * col[33];
-        rv = rv + row[69] * col[38];
-        rv = rv + row[70] * col[0];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[70] * col[16];
-        rv = rv + row[71] * col[33];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffd) * row[72] * col[0];
-        rv = rv + row[72] * col[33];
-        rv = rv + Fr.wrap(0x0000000004) * row[73] * col[0];
-        rv = rv + row[73] * col[74];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[73] * col[86];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[0] * col[47];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[1] * col[48];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[2] * col[49];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[3] * col[50];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[4] * col[51];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[5] * col[52];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[6] * col[53];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[7] * col[54];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[8] * col[55];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[9] * col[56];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[10] * col[57];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[11] * col[58];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[12] * col[59];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[13] * col[60];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[14] * col[61];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[15] * col[62];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[16] * col[63];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[17] * col[64];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[18] * col[65];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[19] * col[66];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[20] * col[67];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[21] * col[68];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[22] * col[69];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[23] * col[70];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[24] * col[71];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[25] * col[72];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[26] * col[73];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[27] * col[74];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[28] * col[75];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[29] * col[76];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[30] * col[77];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[31] * col[78];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[32] * col[79];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[33] * col[80];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[34] * col[81];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[35] * col[82];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[36] * col[35];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[37] * col[36];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[38] * col[37];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[39] * col[38];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[40] * col[39];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[41] * col[40];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[42] * col[41];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[43] * col[42];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[44] * col[43];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[45] * col[44];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[46] * col[45];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[47] * col[46];
+        rv = rv + Fr.wrap(0x4) * row[49] * col[1];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[49] * col[8];
+        rv = rv + row[50] * col[6];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[50] * col[9];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[52] * col[7];
+        rv = rv + row[52] * col[8];
+        rv = rv + row[52] * col[85];
+        rv = rv + row[53] * col[11];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[53] * col[16];
+        rv = rv + row[54] * col[12];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[54] * col[17];
+        rv = rv + row[55] * col[13];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[55] * col[18];
+        rv = rv + row[56] * col[14];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[56] * col[19];
+        rv = rv + row[57] * col[16];
+        rv = rv + Fr.wrap(0x100) * row[57] * col[17];
+        rv = rv + Fr.wrap(0x10000) * row[57] * col[18];
+        rv = rv + Fr.wrap(0x1000000) * row[57] * col[19];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[57] * col[24];
+        rv = rv + Fr.wrap(0x1000000000000) * row[58] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[58] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[58] * col[22];
+        rv = rv + row[58] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[58] * col[83];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[58] * col[84];
+        rv = rv + Fr.wrap(0x1000000000000) * row[59] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[59] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[59] * col[22];
+        rv = rv + row[59] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[59] * col[83];
+        rv = rv + row[59] * col[84];
+        rv = rv + row[60] * col[84];
+        rv = rv + Fr.wrap(0x1000000000000) * row[61] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[61] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[61] * col[22];
+        rv = rv + row[61] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[61] * col[86];
+        rv = rv + Fr.wrap(0x1000000000000) * row[62] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[62] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[62] * col[22];
+        rv = rv + row[62] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[62] * col[83];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[63] * col[16];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[63] * col[17];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efff0001) * row[63] * col[18];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593ef000001) * row[63] * col[19];
+        rv = rv + Fr.wrap(0x1000000000000) * row[63] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[63] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[63] * col[22];
+        rv = rv + row[63] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[64] * col[9];
+        rv = rv + Fr.wrap(0x1000000000000) * row[64] * col[20];
+        rv = rv + Fr.wrap(0x100000000) * row[64] * col[21];
+        rv = rv + Fr.wrap(0x10000) * row[64] * col[22];
+        rv = rv + row[64] * col[23];
+        rv = rv + row[65] * col[24];
+        rv = rv + Fr.wrap(0x1000000) * row[66] * col[25];
+        rv = rv + Fr.wrap(0x10000) * row[66] * col[26];
+        rv = rv + Fr.wrap(0x100) * row[66] * col[27];
+        rv = rv + row[66] * col[28];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[66] * col[83];
+        rv = rv + Fr.wrap(0x1000000) * row[67] * col[29];
+        rv = rv + Fr.wrap(0x10000) * row[67] * col[30];
+        rv = rv + Fr.wrap(0x100) * row[67] * col[31];
+        rv = rv + row[67] * col[32];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[67] * col[84];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[68] * col[29];
+        rv = rv + row[68] * col[32];
+        rv = rv + row[69] * col[20];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[69] * col[25];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[69] * col[87];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[70] * col[30];
+        rv = rv + row[70] * col[32];
+        rv = rv + row[71] * col[21];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[71] * col[26];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[71] * col[88];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[72] * col[31];
+        rv = rv + row[72] * col[32];
+        rv = rv + row[73] * col[22];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[73] * col[27];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[73] * col[89];
+        rv = rv + row[75] * col[23];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593efffff01) * row[75] * col[28];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[75] * col[90];
+        rv = rv + row[76] * col[41];
+        rv = rv + row[77] * col[15];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[77] * col[24];
+        rv = rv + row[78] * col[39];
+        rv = rv + Fr.wrap(0x4) * row[79] * col[1];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[79] * col[15];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffd) * row[80] * col[1];
+        rv = rv + row[80] * col[24];
+        rv = rv + Fr.wrap(0x4) * row[80] * col[46];
+        rv = rv + row[81] * col[24];
+        rv = rv + Fr.wrap(0x4) * row[82] * col[1];
+        rv = rv + row[82] * col[85];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[82] * col[93];
+        rv = rv + row[83] * col[34];
+        rv = rv + row[84] * col[1];
+        rv = rv + row[85] * col[2];
+        rv = rv + row[86] * col[5];
+        rv = rv + row[87] * col[3];
+        rv = rv + row[88] * col[4];
+        rv = rv + row[89] * col[6];
         running = running + rv * eq_rx_ry_step;
         // then we do constant col
         Fr rc = Fr.wrap(0);
@@ -468,56 +577,81 @@ library R1CSMatrix {
         rc = rc + row[37];
         rc = rc + row[38];
         rc = rc + row[39];
-        rc = rc + Fr.wrap(0x007ffffffc) * row[40];
-        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f592f0000001) * row[42];
-        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f59370004001) * row[43];
+        rc = rc + row[40];
+        rc = rc + row[41];
+        rc = rc + row[42];
+        rc = rc + row[43];
+        rc = rc + row[44];
+        rc = rc + row[45];
+        rc = rc + row[46];
+        rc = rc + row[47];
         rc = rc + row[48];
-        rc = rc + row[50];
-        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f592f0000001) * row[52];
-        rc = rc + row[55];
-        rc = rc + row[56];
-        rc = rc + Fr.wrap(0x007ffffffc) * row[70];
-        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f59370000001) * row[72];
-        rc = rc + Fr.wrap(0x0080000000) * row[73];
+        rc = rc + Fr.wrap(0x7ffffffc) * row[49];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f592f0000001) * row[51];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f59370004001) * row[52];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f592f0000001) * row[59];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[65];
+        rc = rc + Fr.wrap(0x80000000) * row[79];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f59370000001) * row[80];
+        rc = rc + Fr.wrap(0x80000000) * row[82];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[83];
         running = running + rc * col_eq_constant;
+        // then the offset constraints, which take the rows after the uniform ones
+        Fr ro;
+        Fr rn;
+        rn = Fr.wrap(0);
+        rn = rn + col[34];
+        ro = rn * eq_step_offset_1;
+        running = running + ro * row[90];
+        ro = Fr.wrap(0);
+        ro = ro + col[44];
+        ro = ro * eq_rx_ry_step;
+        running = running + ro * row[91];
+        ro = Fr.wrap(0);
+        ro = ro + col_eq_constant;
+        running = running + ro * row[92];
+
         return (running);
     }
 
-    /// Computes the sparse MLE evaluation at the step matrix of the r1cs#
+    /// Computes the sparse MLE evaluation of C at the step matrix of the r1cs
     /// @param row The relevant row values
-    /// @param col The relevant row values
+    /// @param col The relevant col values
     /// @param eq_rx_ry_step The const for the var steps
     /// @param col_eq_constant The const for the constant evals
     function C(Fr[] memory row, Fr[] memory col, Fr eq_rx_ry_step, Fr col_eq_constant) internal pure returns (Fr) {
         Fr running = Fr.wrap(0);
         Fr rv = Fr.wrap(0);
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[40] * col[9];
-        rv = rv + row[40] * col[72];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[41] * col[10];
-        rv = rv + row[41] * col[73];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[42] * col[7];
-        rv = rv + row[42] * col[74];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[59] * col[25];
-        rv = rv + row[59] * col[79];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[61] * col[26];
-        rv = rv + row[61] * col[80];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[63] * col[27];
-        rv = rv + row[63] * col[81];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[65] * col[28];
-        rv = rv + row[65] * col[82];
-        rv = rv + row[67] * col[83];
-        rv = rv + row[69] * col[84];
-        rv = rv + row[71] * col[85];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffd) * row[72] * col[0];
-        rv = rv + row[72] * col[86];
-        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[73] * col[86];
-        rv = rv + row[73] * col[87];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[49] * col[8];
+        rv = rv + row[49] * col[83];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[50] * col[9];
+        rv = rv + row[50] * col[84];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[51] * col[6];
+        rv = rv + row[51] * col[85];
+        rv = rv + row[60] * col[86];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[68] * col[29];
+        rv = rv + row[68] * col[87];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[70] * col[30];
+        rv = rv + row[70] * col[88];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[72] * col[31];
+        rv = rv + row[72] * col[89];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[74] * col[32];
+        rv = rv + row[74] * col[90];
+        rv = rv + row[76] * col[91];
+        rv = rv + row[78] * col[92];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593effffffd) * row[80] * col[1];
+        rv = rv + Fr.wrap(0x4) * row[80] * col[46];
+        rv = rv + row[80] * col[93];
+        rv = rv + row[81] * col[94];
+        rv = rv + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000) * row[82] * col[93];
+        rv = rv + row[82] * col[95];
         running = running + rv * eq_rx_ry_step;
         // then we do constant col
         Fr rc = Fr.wrap(0);
-        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f5936ffffffd) * row[72];
+        rc = rc + Fr.wrap(0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f5936ffffffd) * row[80];
         running = running + rc * col_eq_constant;
 
         return (running);
     }
+    // END GENERATED BY autogenerate_r1cs
 }
@@ -29,14 +29,12 @@ contract SpartanVerifier is HyperKZG {
     /// @param transcript The running fiat shamir transcript
     /// @param log_rows The log of the rows of our witness
     /// @param log_cols The log of the col of our witness
-    // @param total_rows The total rows, to be used in the computation of the abc mle
     function verifySpartanR1CS(
         SpartanProof memory proof,
         uint256[] memory, /* witness_segment_commitments */
         Transcript memory transcript,
         uint256 log_rows,
-        uint256 log_cols,
-        uint256
+        uint256 log_cols
     ) public pure returns (bool) {
         // Load a random tau
         Fr[] memory tau = new Fr[](log_rows);
@@ -81,8 +79,8 @@ contract SpartanVerifier is HyperKZG {
         // Validate the the inner sumcheck
         (Fr claim_inner, Fr[] memory r_y) =
             SumcheckVerifier.verify_sumcheck(transcript, proof.inner, claim_inner_join, log_cols, 2);
-        // The n prefix is key.uniform_r1cs.num_vars.next_power_of_two().log_2() + 1; and in our system it's initialized to 8
-        uint256 n_prefix = 8;
+        // The n prefix is key.uniform_r1cs.num_vars.next_power_of_two().log_2() + 1
+        uint256 n_prefix = R1CSMatrix.COL_BITS + 1;
 
         // Do the z mle
         Fr eval_Z = R1CSMatrix.eval_z_mle(r_y, proof.claimedEvals);
@@ -97,12 +95,7 @@ contract SpartanVerifier is HyperKZG {
         }
 
         // Evaluate the second MLE
-        //(Fr aEval, Fr bEval, Fr cEval) = R1CSMatrix.evaluate_r1cs_matrix_mles(r, proof.log_rows, proof.log_cols, proof.total_cols);
-        // TODO - (aleph) These values are hardcoded to make a single test pass, and must be replaced with the final version of
-        // R1CSMatrix.evaluate_r1cs_matrix_mles once the second sumcheck refactoring is done.
-        Fr aEval = Fr.wrap(0x0168ec8c28141fc3422b0ccee2fb350301b7a30900232c5c16ea8aaa5e48b63d);
-        Fr bEval = Fr.wrap(0x219d6c166058578e4e54e1819527d89d7f66d417c41c44733322d8f6204b581d);
-        Fr cEval = Fr.wrap(0x0b627be010723de7db4cac462721244d1f7e1dd84f8f60bc8334d3b86d67ee26);
+        (Fr aEval, Fr bEval, Fr cEval) = R1CSMatrix.evaluate_r1cs_matrix_mles(r, log_rows, log_cols);
 
         Fr expected_left = aEval + r_inner_sumcheck_RLC * bEval + r_inner_sumcheck_RLC * r_inner_sumcheck_RLC * cEval;
         require(claim_inner == expected_left * eval_Z, "SpartanError::InvalidInnerSumcheckClaim");
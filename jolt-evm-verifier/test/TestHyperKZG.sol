@@ -1,7 +1,7 @@
 pragma solidity ^0.8.21;
 
 import {TestBase} from "./base/TestBase.sol";
-import {HyperKZG, HyperKZGProof} from "../src/subprotocols/HyperKZG.sol";
+import {HyperKZG, HyperKZGProof, HyperKZGOpening} from "../src/subprotocols/HyperKZG.sol";
 import {FiatShamirTranscript, Transcript} from "../src/subprotocols/FiatShamirTranscript.sol";
 
 import "forge-std/console.sol";
@@ -47,6 +47,19 @@ contract TestHyperKZG is TestBase {
         uint256[] claims;
     }
 
+    struct CompressedExample {
+        VK vk;
+        uint256 commitment;
+        uint256[] point;
+        uint256 claim;
+        bytes proof;
+    }
+
+    struct AggregateExample {
+        VK vk;
+        HyperKZGOpening[] openings;
+    }
+
     function testHyperKZGPasses() public {
         // Invoke the rust to get a non trivial example proof
         string[] memory cmds = new string[](1);
@@ -64,19 +77,77 @@ contract TestHyperKZG is TestBase {
         require(passes, "does not verify a valid proof");
     }
 
+    function getBatchedExample(uint256 ell, uint256 numPolys) internal returns (BatchedExample memory) {
+        string[] memory cmds = new string[](5);
+        cmds[0] = "./script/target/release/batch_opening_example";
+        cmds[1] = "--ell";
+        cmds[2] = vm.toString(ell);
+        cmds[3] = "--num-polys";
+        cmds[4] = vm.toString(numPolys);
+        bytes memory result = vm.ffi(cmds);
+        return abi.decode(result, (BatchedExample));
+    }
+
     function testHyperKZGBatchPasses() public {
-        // Invoke the rust to get a non trivial example proof
+        // Invoke the rust to get batched openings of several shapes: (ell, number of polynomials)
+        uint256[2][5] memory shapes =
+            [[uint256(4), 1], [uint256(4), 5], [uint256(9), 2], [uint256(12), 8], [uint256(15), 3]];
+        for (uint256 i = 0; i < shapes.length; i++) {
+            BatchedExample memory data = getBatchedExample(shapes[i][0], shapes[i][1]);
+            // Now deploy a verifier with the key inited
+            HyperKZG verifier = new DeployableHyperKZG(data.vk);
+            // We build a transcript in memory
+            Transcript memory transcript = FiatShamirTranscript.new_transcript("TestEval", 3);
+            // We call into the verifier contract
+            bool passes = verifier.batch_verify(data.commitments, data.point, data.claims, data.proof, transcript);
+            require(passes, "does not verify a valid proof");
+        }
+    }
+
+    function testHyperKZGCompressedPasses() public {
+        // Invoke the rust to get a non trivial example proof in the compact encoding
         string[] memory cmds = new string[](1);
-        cmds[0] = "./script/target/release/hyperkzg_batch_example";
+        cmds[0] = "./script/target/release/hyperkzg_calldata_example";
         bytes memory result = vm.ffi(cmds);
-        BatchedExample memory data = abi.decode(result, (BatchedExample));
-        // Now deploy a verifier with the key inited
+        CompressedExample memory data = abi.decode(result, (CompressedExample));
         HyperKZG verifier = new DeployableHyperKZG(data.vk);
-        // We build a transcript in memory
-        bytes32 start_string = "TestEval";
-        Transcript memory transcript = FiatShamirTranscript.new_transcript(start_string, 3);
-        // We call into the verifier contract
-        bool passes = verifier.batch_verify(data.commitments, data.point, data.claims, data.proof, transcript);
+        bool passes = verifier.verify_compressed(
+            data.commitment, data.point, data.claim, data.proof, FiatShamirTranscript.new_transcript("TestEval", 3)
+        );
         require(passes, "does not verify a valid proof");
+
+        // Flipping the parity of a point in com decompresses to its negation, which changes r, so the
+        // recomputed evaluations no longer match the claim
+        data.proof[1] ^= 0x80;
+        vm.expectRevert("bad construction");
+        verifier.verify_compressed(
+            data.commitment, data.point, data.claim, data.proof, FiatShamirTranscript.new_transcript("TestEval", 3)
+        );
+    }
+
+    function aggregateTranscripts(uint256 count) internal pure returns (Transcript[] memory transcripts) {
+        transcripts = new Transcript[](count);
+        for (uint256 i = 0; i < count; i++) {
+            transcripts[i] = FiatShamirTranscript.new_transcript("TestEval", 3);
+        }
+    }
+
+    function testHyperKZGAggregatePasses() public {
+        // Invoke the rust to get several independent example proofs
+        string[] memory cmds = new string[](1);
+        cmds[0] = "./script/target/release/hyperkzg_aggregate_example";
+        bytes memory result = vm.ffi(cmds);
+        AggregateExample memory data = abi.decode(result, (AggregateExample));
+        HyperKZG verifier = new DeployableHyperKZG(data.vk);
+        bool passes = verifier.aggregate_verify(data.openings, aggregateTranscripts(data.openings.length));
+        require(passes, "does not verify valid proofs");
+
+        // Swapping two commitments keeps every opening well formed, but must fail the aggregated pairing
+        (data.openings[0].commitment_x, data.openings[1].commitment_x) =
+            (data.openings[1].commitment_x, data.openings[0].commitment_x);
+        (data.openings[0].commitment_y, data.openings[1].commitment_y) =
+            (data.openings[1].commitment_y, data.openings[0].commitment_y);
+        passes = verifier.aggregate_verify(data.openings, aggregateTranscripts(data.openings.length));
+        require(!passes, "verifies an invalid batch");
     }
 }
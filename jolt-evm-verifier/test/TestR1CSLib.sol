@@ -9,9 +9,9 @@ import "forge-std/console.sol";
 contract TestR1CSLib is TestBase {
     using FrLib for Fr;
 
-    // Here we are not calling out directly to rust because these functions are sometimes internal
-    // instead we load examples from the sha2 test chain and compare them. Integration tests are
-    // implied by integration tests of the full spartan R1CS test.
+    // The eq poly functions are checked against examples loaded from the sha2 test chain. The matrices change with
+    // jolt's constraints, so they are checked against evaluations from rust at a random point instead. Integration
+    // tests are implied by integration tests of the full spartan R1CS test.
     function testEQPolyEvaluate() public pure {
         // Define two test vectors
         uint256[] memory r_u = new uint256[](19);
@@ -87,155 +87,53 @@ contract TestR1CSLib is TestBase {
         }
     }
 
-    function testZMLE() public pure {
-        Fr[] memory r_in = new Fr[](27);
-        r_in[0] = Fr.wrap(0x20909cbd347ced3edbf95822bd20a6be344e15333b586a12bea1ea5aaf53465c);
-        r_in[1] = Fr.wrap(0x1a412ccd4128e55b2211d6806789e95aab168d51d7fcc7dc4cc0934aa4edb20a);
-        r_in[2] = Fr.wrap(0x273c5731390a9888e745b03b59bde1b7b3b016b43e6d295499b411b039265f15);
-        r_in[3] = Fr.wrap(0x175f00c72dfe10faa3df4e8eccc1f58f9a56d5c07536dba3d4c3f4be50b43762);
-        r_in[4] = Fr.wrap(0x021901894954aa44e586e9aa485722d55733246a7061423578c5f2989b2f3d43);
-        r_in[5] = Fr.wrap(0x079a2eef89e9f1fc22a9c6d4ecb58a71bf204960ccf743e01dfd1cfa8e5d3e5f);
-        r_in[6] = Fr.wrap(0x1a4605b45dea02d0b5480c1cd6a7e3f99972acb2c45b411678dcaf8000c0ea22);
-        r_in[7] = Fr.wrap(0x172786c28a36d0860b8a51db383202ff41ccd0b113bcfbea65605e1b5cb8db06);
-        r_in[8] = Fr.wrap(0x2aead1e7bef7c86fe2a0447ba88c7ce2e81946b4950db8079dcdfcc25e41196e);
-        r_in[9] = Fr.wrap(0x001f7e910037fe49e8361205fe98f41dff75f581631086ba45a9610f2173d49a);
-        r_in[10] = Fr.wrap(0x19ad735180364239f5b6d1e3308c8f2f0077457322995df2c3bced2e8a88df01);
-        r_in[11] = Fr.wrap(0x01755ffb6980d9ad6159a6e59681fdea6d9fa0a1be4db1c9a06c15c434254e2b);
-        r_in[12] = Fr.wrap(0x1007f00fde893346a335a3cc6e612844705066835706d25aa11ae69a231dfff1);
-        r_in[13] = Fr.wrap(0x2ea3a6d45fea6d714f478058bb29e2391ac136d2a65734dc812f39ad48f1afa8);
-        r_in[14] = Fr.wrap(0x01ff6285b83bc2e8a0dba5bedd3ea93aff159e173ccf7e8b437c4e5f1f613feb);
-        r_in[15] = Fr.wrap(0x1682e014bc2127005f07dd8fb9326e98d171002d8c9b559ab53d29b53d4a592d);
-        r_in[16] = Fr.wrap(0x1c38d441a2c63bd270e52f1b3543fb8897fda922d80cded53d267ae3bebcef87);
-        r_in[17] = Fr.wrap(0x23a9b40df842a954333ad538e06cf83f724425499a4e6436988d1d899692e1fa);
-        r_in[18] = Fr.wrap(0x0527b4f204d84a7c88e0c20bce9809c334e03b839030417499d5690df6b62f26);
-        r_in[19] = Fr.wrap(0x009274689310fb1e734a75cd25ed4b8b02f8191e62c6c236cfc790fbe18b375c);
-        r_in[20] = Fr.wrap(0x1c2faa00c278bbfb5663896a8ca0fd188fde25bdffd252a4728ce9466d4e81ae);
-        r_in[21] = Fr.wrap(0x2c07c965d33abcea6b09f143073d409f74f02fe4c78bf23b98d0075ff6de2a03);
-        r_in[22] = Fr.wrap(0x03112cda07738865cf624737270b01fa410829b347c783faac10e08ba6484c2e);
-        r_in[23] = Fr.wrap(0x204860062d77c232bc251627c596069ca7c8805f92df8442de6686ad75a98419);
-        r_in[24] = Fr.wrap(0x0e9744aee889be91866898070cdcd652cdb0604ee1fe4aa472da529dffd358c1);
-        r_in[25] = Fr.wrap(0x1d50f4d5aa8ba2504a37d91be2d5be9dbdf1a86a172217be0a38396fadaa8b97);
-        r_in[26] = Fr.wrap(0x1ce22bfe53a23fe2de22cdaa5f0e867ac23c22e18e0f0dbe08afdfbea2063627);
+    struct R1CSExample {
+        uint256[] r;
+        uint256 rowBits;
+        uint256 colBits;
+        uint256[] segmentEvals;
+        uint256 z;
+        uint256 a;
+        uint256 b;
+        uint256 c;
+    }
 
-        uint256[] memory seg_evals = new uint256[](89);
-        seg_evals[0] = 0x076fe98ad018b3794a884cfd025d27d057b19729c419bae12b45f02800e054e6;
-        seg_evals[1] = 0x076fe98ad018b3794a884cfd025d27d057b19729c419bae12b45f02800e054e6;
-        seg_evals[2] = 0x076fe98ad018b3794a884cfd025d27d057b19729c419bae12b45f02800e054e6;
-        seg_evals[3] = 0x0865894547c275eb09607560cca971ca69c0fb35e55fefbd90949e6f0af5cad3;
-        seg_evals[4] = 0x26da40253fcbb48323e6018af942baa02e13b2f6b8e68ba48706408ee89b4f65;
-        seg_evals[5] = 0x1d160375307716f740252a6ec6bc2279806689f5a9479896552b13244660ebca;
-        seg_evals[6] = 0x0076f940bedaa556aa5c5c851ca22fdcd813342f01ad74c36aa7b599ba2ddc1c;
-        seg_evals[7] = 0x2f39ac1746a8679d688e84042df3b4b5e05a65e21cd81aea161d4df6781234f3;
-        seg_evals[8] = 0x2479112c01b994bc06f42a2f8db657925b03e1649358c66515767d2cd09bc949;
-        seg_evals[9] = 0x2c0511ef7443409cf1e6bf01c364a8455c2338b6c10d43a8862e65fbb12612b7;
-        seg_evals[10] = 0x29ed18bf9c4012f8802f378a6bc30cfc0f53ae16373a255655849f13ec83fdef;
-        seg_evals[11] = 0x2059dddafb60e751b752de7e982953268d4c6b8c577e8174aa44a0065d6052df;
-        seg_evals[12] = 0x0175d48c37744cd6ea88ff0c67404870e3d9ae02497deedfb06f618f2e6d7ac8;
-        seg_evals[13] = 0x2dcba45d0a053484df2d3a065ddc657a6c670a806723d5bdcdd2321bc52b3600;
-        seg_evals[14] = 0x2e511ebb86eb88dd390634579991bca0c53d3a1ef3786533178503e0a101857b;
-        seg_evals[15] = 0x08e0a5ace908835417087a36104b1fd49055e76e42c8ce310667e6290c54da97;
-        seg_evals[16] = 0x033090674e422528a4c18af89ad8bda31570a80d200b9441b509c89daf4f0e51;
-        seg_evals[17] = 0x04e45f2449fc605d88bd8896985984a845c65744099f845d97d98aa90f3ec49a;
-        seg_evals[18] = 0x229dced2135be94d8e93265901e4959bcf88d542fd2f4e172fe6551c716e7bd9;
-        seg_evals[19] = 0x26d3e69b2defa56d377d01d987c975ae8a714ab0c9efe3c502ffa2dbaae32d52;
-        seg_evals[20] = 0x0b9888c9fc3e0eb7af18c5450d8c3176e63830a62d678b08584af0fba7d9df42;
-        seg_evals[21] = 0x2793a7ebd08f33788f2a96af451ec6cb670998ba9c314eb108cc9307e43048c6;
-        seg_evals[22] = 0x001e09a1c44c087771db50a23e28c238829576a99030627a39f95718f8e018f8;
-        seg_evals[23] = 0x172f353cdd6a1c06f68cc5b790634edb01bbdab3b02d5ec344e1027341b55fdb;
-        seg_evals[24] = 0x139a87f9fd03cb56bb73940fb5b84a67930dd9a253b282a142186e04660e5d69;
-        seg_evals[25] = 0x267de61abd829d163731a12b98d23569298050def395adefb71837c42f982ae7;
-        seg_evals[26] = 0x208529e691613612de66c93f560e735d43949fad32fe634df2b77507e5e26ebc;
-        seg_evals[27] = 0x017d0ae0ce37ec7a557021435013cbb815e207590d1db03668707f464dea258f;
-        seg_evals[28] = 0x098f5a1886ac6643035e77447f98a7bad23b29239c3340eff4aec307e7b5d286;
-        seg_evals[29] = 0x0efd71b3b8900c8168f923ae699f3ca9f709c2503d995c5e49b260e12a2be6f7;
-        seg_evals[30] = 0x01e88c0124956c91c1a80fc6fed493c6423c9cba1ea932d975b7ece99ac0a93a;
-        seg_evals[31] = 0x0deae7eb8c381d4228c4dc348ed94e8d1186e45a5575c4bb1c8b755a3f3a7a5a;
-        seg_evals[32] = 0x03f2e99744d4689851baea44ec8aa8892d4b282415b020d633aaf7c46a9bfdda;
-        seg_evals[33] = 0x108aa714ce86e32ad3eef509147896b85dce222bbcf7bb2cd9179a6c0268d77c;
-        seg_evals[34] = 0x05e9798569091b63cbe36ecfe45ecfc3fe3eb139179c1929b9d10747299fa997;
-        seg_evals[35] = 0x16c993e7f3d20640f756f06c911a681d82ee66f80f16925851448cef07ecbb6d;
-        seg_evals[36] = 0x02ca3fc35b98200b3bbe956d401b61b2769d0c7826c590a77aa812cddcaca2f2;
-        seg_evals[37] = 0x24c93afc84014f4cf6a21977ab6f1b299a0c85d8d0116e7142884087c0de5eb8;
-        seg_evals[38] = 0x10ef69902884841ac5b21b5dcd560f6a934e93be2cfca6ff097b339d59f22439;
-        seg_evals[39] = 0x1568ddd16a730062da476be15f31245a920c9b8a27e26190214f9f02e97400f1;
-        seg_evals[40] = 0x0908c9e241e983e3b78914e4006930ffb40e4fafd69de517c787997cb6208b20;
-        seg_evals[41] = 0x1703e0af67d3aa7e74ca5c9c14a0e0ab3f06d31996ff87e4962314c4324a2620;
-        seg_evals[42] = 0x065e20ff81a396cf2e8471ab351b66752f52484592cd546efe984208d8e8888c;
-        seg_evals[43] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[44] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[45] = 0x2638b080f7a55186ed3f950ab7b99c9d337a2a3a71ea08907912174643f184cc;
-        seg_evals[46] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[47] = 0x06bc6902243a4ab734f0d90814e291daaf1d09aaaa6b864cabb7471a6cbd3b1e;
-        seg_evals[48] = 0x235cef3fb075a3c0d99267a3e0df482f6ce809a6da39b87e4a28fae363e0272d;
-        seg_evals[49] = 0x116218c0b4d5cfad08aa6b633b584e2eda0b0e2a1a1b4e36460a21ee91b29d89;
-        seg_evals[50] = 0x0635b5deb92743b6987f192ea43d3e83bab1ed7ce3f73a036d9c9a59d5b5df7c;
-        seg_evals[51] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[52] = 0x19ea7f9ec49a6bd35f1a8bea20e15e79d1760d065fa285c0536679051d249cb9;
-        seg_evals[53] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[54] = 0x0773454261d7bfce3ac709cc266bdfde848197cdb33d3f54fc2d3ff6aab08575;
-        seg_evals[55] = 0x0a617b7bbaa89527a668ecc05684da1aa8505faa2f054a715a5916668352517f;
-        seg_evals[56] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[57] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[58] = 0x22f9facf63fc080a624de234c7a253799c7972a6652b8b064166ef0dc876e57e;
-        seg_evals[59] = 0x1871b5f92d00035a89e0e2a2c28b4f237576b1820d6afca9c9718f228daac9f5;
-        seg_evals[60] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[61] = 0x2a0a72baa06f739bceab5e57e310e13037602fa3aa3a93cb7d66c7d8d544053d;
-        seg_evals[62] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[63] = 0x1c9c4ac990d045289754cc86464341c8e87114758f1c23c5ef9d57fc87e0828d;
-        seg_evals[64] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[65] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[66] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[67] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[68] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[69] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[70] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[71] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[72] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[73] = 0x241ed2a5a4a205ce15b327ca0463eea11b9cbaf8e4ad5ef642c528de1cb2ce2c;
-        seg_evals[74] = 0x2e35e1780074616c93fd74d1840e8b48a06ab7c14f2f21c52011a5906c974c90;
-        seg_evals[75] = 0x155381f7cc161a16344248171a14564f82b32d2de2706241b70a85bee1d31fb4;
-        seg_evals[76] = 0x24da713339b3a4d16d76e110a5b0a85f63c426d40ade98cdabb33a0eacce777e;
-        seg_evals[77] = 0x0ca63820d965bb7ff42b2931f6fa67ee691537748fb12052a852f2f7431697e9;
-        seg_evals[78] = 0x2b857c4823c6f68e2dfcc1a564b3660475c1e3975ab92d6edc8804d03994d955;
-        seg_evals[79] = 0x2d6859c241f1b212e897f06b1e0d6c2fd5809e8afcdd19087fab5251d6cb14db;
-        seg_evals[80] = 0x172cb8e09a7cecb3fe164ddecb4bf23f564c2dc366263af374417ff37e177171;
-        seg_evals[81] = 0x1133fcac6e5b85b0a54b75f28888303370607c91a58ef051afe0bd373461b546;
-        seg_evals[82] = 0x22902c198c63dc41d4a513ad040ee0eb6ae1cc85f967adcb697bbd098c696c1a;
-        seg_evals[83] = 0x098f5a1886ac6643035e77447f98a7bad23b29239c3340eff4aec307e7b5d286;
-        seg_evals[84] = 0x1f4684940bd22a9bc82d1558d9d646d8a93359ba7233b322d84cce91d47a8f80;
-        seg_evals[85] = 0x0000000000000000000000000000000000000000000000000000000000000000;
-        seg_evals[86] = 0x1a3d3e0599b17dc28eab6b0198e7793fc3ed87528bb38275ed45e7a1396facb6;
-        seg_evals[87] = 0x17222098f602275b6180bc9b40e97add4202581b1c5fa53e29d302fbafd6409e;
-        seg_evals[88] = 0x1f4e7bc99a64de122b8ddd34a184af220150a4d457bacdf35726b658b594f374;
+    function getR1CSExample() internal returns (R1CSExample memory, Fr[] memory) {
+        string[] memory cmds = new string[](1);
+        cmds[0] = "./script/target/release/r1cs_example";
+        bytes memory result = vm.ffi(cmds);
+        (R1CSExample memory example) = abi.decode(result, (R1CSExample));
 
-        // Run the z mle verifier
-        Fr result = R1CSMatrix.eval_z_mle(r_in, seg_evals);
-        assertEq(result.unwrap(), 0x180c4c6329f2bc0803559dfe3246824f020c0c07b8c9db6bad6ab41a1222322d);
+        uint256[] memory r_u = example.r;
+        Fr[] memory r;
+        assembly {
+            r := r_u
+        }
+        return (example, r);
     }
 
-    /*
-    // Checks our autogen matches the various sparse matrices we expect it to
-    function testAUniform() public view {
-        Fr eq_rx_ry_step = Fr.wrap(0x1fa9a6f677c445c8aa4e4f32c1d9bd075629e69d51076f2de41dd7f11cbf192e);
-        Fr col_eq_constant = Fr.wrap(0x220112b31b8e2239f76ed791d3c9fb8b3bbd397b3d66074feffcb3515158ded3);
-        (Fr[] memory row, Fr[] memory col) = load();
-        assertEq(R1CSMatrix.A(row, col, eq_rx_ry_step, col_eq_constant).unwrap(), 0x20366d6b722ff62698e30a8d1c49556d4a1c62374a84f9a144b3bd3f368ee492);
-    }
+    function testZMLE() public {
+        (R1CSExample memory example, Fr[] memory r) = getR1CSExample();
+        // The z mle is evaluated on the col part of r
+        Fr[] memory r_y = new Fr[](example.colBits);
+        for (uint256 i = 0; i < r_y.length; i++) {
+            r_y[i] = r[example.rowBits + i];
+        }
 
-    function testBUniform() public view {
-        Fr eq_rx_ry_step = Fr.wrap(0x1fa9a6f677c445c8aa4e4f32c1d9bd075629e69d51076f2de41dd7f11cbf192e);
-        Fr col_eq_constant = Fr.wrap(0x220112b31b8e2239f76ed791d3c9fb8b3bbd397b3d66074feffcb3515158ded3);
-        (Fr[] memory row, Fr[] memory col) = load();
-        assertEq(R1CSMatrix.B(row, col, eq_rx_ry_step, col_eq_constant).unwrap(), 0x1c1858aca1068a7e6028ff6fa183d9f076a9ed5de1ec108d322f0902a0f1ead9);
+        // Run the z mle verifier
+        Fr result = R1CSMatrix.eval_z_mle(r_y, example.segmentEvals);
+        assertEq(result.unwrap(), example.z);
     }
 
-    function testCUniform() public view {
-        Fr eq_rx_ry_step = Fr.wrap(0x1fa9a6f677c445c8aa4e4f32c1d9bd075629e69d51076f2de41dd7f11cbf192e);
-        Fr col_eq_constant = Fr.wrap(0x220112b31b8e2239f76ed791d3c9fb8b3bbd397b3d66074feffcb3515158ded3);
-        (Fr[] memory row, Fr[] memory col) = load();
-        assertEq(R1CSMatrix.C(row, col, eq_rx_ry_step, col_eq_constant).unwrap(), 0x06599664d2bf6a58c553705d3eaf0da7027e226b3e7590d42c62a5374657187a);
+    // Checks our autogen matches the sparse matrices of the rust key, including the constant col and the offset
+    // constraints
+    function testMatrixMLEs() public {
+        (R1CSExample memory example, Fr[] memory r) = getR1CSExample();
+        (Fr a, Fr b, Fr c) = R1CSMatrix.evaluate_r1cs_matrix_mles(r, example.rowBits, example.colBits);
+        assertEq(a.unwrap(), example.a);
+        assertEq(b.unwrap(), example.b);
+        assertEq(c.unwrap(), example.c);
     }
-    */
 
     function load() internal pure returns (Fr[] memory, Fr[] memory) {
         uint256[] memory r_x = new uint256[](128);
@@ -36,24 +36,23 @@ contract TestSpartanVerifier is TestBase {
     struct SpartanExample {
         SpartanProof proof;
         VK vk;
-        uint256[] commitments;
+        uint256 logRows;
+        uint256 logCols;
     }
 
     function testSpartanVerifier() public {
-        // We use internal state of a jolt transcript at the point of verifying
-        bytes32[] memory internal_data = new bytes32[](5);
-        internal_data[0] = 0xc60338a7fc9488db12fd3c81b6719b62a2038420ecb8556bbf256a0b242d15b2;
-        internal_data[1] = bytes32(uint256(12932));
-        Transcript memory transcript = Transcript(internal_data);
-
-        string[] memory cmds = new string[](2);
-        cmds[0] = "sh";
-        cmds[1] = "script/spartan_example.sh";
+        // Inits the transcript with the same string label as the rust code
+        Transcript memory transcript = FiatShamirTranscript.new_transcript("test_transcript", 4);
+
+        string[] memory cmds = new string[](1);
+        cmds[0] = "./script/target/release/spartan_example";
         bytes memory result = vm.ffi(cmds);
-        (SpartanExample memory decodedProof) = abi.decode(result, (SpartanExample));
+        (SpartanExample memory example) = abi.decode(result, (SpartanExample));
 
-        DeployableSpartan spartan = new DeployableSpartan(decodedProof.vk);
+        DeployableSpartan spartan = new DeployableSpartan(example.vk);
 
-        assert(spartan.verifySpartanR1CS(decodedProof.proof, decodedProof.commitments, transcript, 16, 17, 131072));
+        // The example doesn't include the witness commitments, which aren't checked yet
+        uint256[] memory commitments = new uint256[](0);
+        assert(spartan.verifySpartanR1CS(example.proof, commitments, transcript, example.logRows, example.logCols));
     }
 }
@@ -0,0 +1,96 @@
+#![cfg_attr(feature = "guest", no_std)]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use jolt::disclosure::{Journal, JournalCommitment};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[jolt::provable]
+fn sha256(input: &[u8]) -> [u8; 32] {
+    Sha256::digest(input).into()
+}
+
+#[jolt::provable]
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    Keccak256::digest(input).into()
+}
+
+/// Checks that `leaf` is the `index`th leaf of the SHA-256 Merkle tree with root `root`, where
+/// `path` is the concatenation of the leaf's siblings from the bottom of the tree up and each
+/// node is the hash of its two children.
+#[jolt::provable]
+fn merkle_verify(root: [u8; 32], leaf: [u8; 32], index: u32, path: &[u8]) -> bool {
+    if path.len() % 32 != 0 || path.len() / 32 > 32 {
+        return false;
+    }
+    let mut node = leaf;
+    for (depth, sibling) in path.chunks_exact(32).enumerate() {
+        let mut hasher = Sha256::new();
+        if (index >> depth) & 1 == 0 {
+            hasher.update(node);
+            hasher.update(sibling);
+        } else {
+            hasher.update(sibling);
+            hasher.update(node);
+        }
+        node = hasher.finalize().into();
+    }
+    node == root
+}
+
+#[derive(Deserialize)]
+struct Transfer {
+    amount: u64,
+}
+
+#[derive(Deserialize)]
+struct Batch {
+    transfers: Vec<Transfer>,
+}
+
+/// Parses a JSON batch of transfers, `{"transfers": [{"to": "...", "amount": 5}, ...]}`, and
+/// returns their total amount, or `None` if it is malformed or the total overflows. Other fields
+/// of a transfer are ignored.
+#[jolt::provable(stack_size = 65536)]
+fn json_total(json: &str) -> Option<u64> {
+    let batch: Batch = serde_json::from_str(json).ok()?;
+    batch
+        .transfers
+        .iter()
+        .try_fold(0u64, |total, transfer| total.checked_add(transfer.amount))
+}
+
+/// Checks an Ed25519 signature of `message`, rejecting non-canonical encodings and small-order
+/// keys as `verify_strict` does. `signature` is 64 bytes.
+#[jolt::provable(stack_size = 65536)]
+fn ed25519_verify(public_key: [u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(public_key) = VerifyingKey::from_bytes(&public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return false;
+    };
+    public_key.verify_strict(message, &signature).is_ok()
+}
+
+/// The journal of a solvency receipt: whether `balance` is at least 1000, the balance and the
+/// owner, in that order. The host rebuilds it natively to disclose some of its fields.
+pub fn receipt(seed: [u8; 32], balance: u64, owner: [u8; 20]) -> Journal {
+    let mut journal = Journal::new(seed);
+    journal.push(&(balance >= 1000));
+    journal.push(&balance);
+    journal.push(&owner);
+    journal
+}
+
+/// Commits to the [`receipt`] for `balance` and `owner`, without making either public.
+#[jolt::provable]
+fn solvent(seed: [u8; 32], balance: u64, owner: [u8; 20]) -> JournalCommitment {
+    receipt(seed, balance, owner).commitment()
+}
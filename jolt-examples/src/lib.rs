@@ -0,0 +1,75 @@
+//! Example guests (in `guest/`) with the host-side helpers that build their inputs and expected
+//! outputs. The integration tests in `tests/` prove and verify each of them end to end, and
+//! `jolt-core`'s benchmark harness traces the same guests (`--name merkle-verify`, etc.).
+
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+
+pub use guest;
+
+/// A SHA-256 Merkle tree with the layout `guest::merkle_verify` expects: each node is the hash
+/// of the concatenation of its two children.
+pub struct MerkleTree {
+    /// From the leaves up to the root.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// `leaves` must be a nonzero power of two in number.
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        assert!(leaves.len().is_power_of_two());
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| hash_pair(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn leaf(&self, index: usize) -> [u8; 32] {
+        self.layers[0][index]
+    }
+
+    /// The siblings of the `index`th leaf from the bottom of the tree up, concatenated.
+    pub fn path(&self, index: usize) -> Vec<u8> {
+        let layers = &self.layers[..self.layers.len() - 1];
+        layers
+            .iter()
+            .enumerate()
+            .flat_map(|(depth, layer)| layer[(index >> depth) ^ 1])
+            .collect()
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// A batch of `num_transfers` transfers as `guest::json_total` expects it, and their total.
+pub fn transfer_batch(num_transfers: u64) -> (String, u64) {
+    let transfers: Vec<String> = (0..num_transfers)
+        .map(|i| format!(r#"{{"to": "account-{i}", "amount": {}}}"#, 100 + i))
+        .collect();
+    let json = format!(r#"{{"transfers": [{}]}}"#, transfers.join(", "));
+    let total = (0..num_transfers).map(|i| 100 + i).sum();
+    (json, total)
+}
+
+/// Signs `message` with a fixed key, returning the public key and the signature.
+pub fn sign(message: &[u8]) -> ([u8; 32], [u8; 64]) {
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let signature = key.sign(message);
+    (key.verifying_key().to_bytes(), signature.to_bytes())
+}
@@ -0,0 +1,89 @@
+use jolt_examples::{guest, sign, transfer_batch, MerkleTree};
+use sha2::{Digest, Sha256};
+
+#[test]
+fn sha256() {
+    let (prove, verify) = guest::build_sha256();
+    let input = [5u8; 256];
+    let (output, proof) = prove(&input);
+    assert_eq!(output, <[u8; 32]>::from(Sha256::digest(input)));
+    assert!(verify(proof));
+}
+
+#[test]
+fn keccak256() {
+    let (prove, verify) = guest::build_keccak256();
+    let (output, proof) = prove(b"");
+    // The Keccak-256 of the empty string, as used by Ethereum
+    assert_eq!(
+        output,
+        [
+            0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+            0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+            0x5d, 0x85, 0xa4, 0x70
+        ]
+    );
+    assert!(verify(proof));
+}
+
+#[test]
+fn merkle_verify() {
+    let leaves = (0..16u8).map(|i| [i; 32]).collect();
+    let tree = MerkleTree::new(leaves);
+    let (prove, verify) = guest::build_merkle_verify();
+
+    let (output, proof) = prove(tree.root(), tree.leaf(11), 11, &tree.path(11));
+    assert!(output);
+    assert!(verify(proof));
+
+    // The path of another leaf does not open this one
+    let (output, proof) = prove(tree.root(), tree.leaf(11), 11, &tree.path(10));
+    assert!(!output);
+    assert!(verify(proof));
+}
+
+#[test]
+fn json_total() {
+    let (json, total) = transfer_batch(20);
+    let (prove, verify) = guest::build_json_total();
+
+    let (output, proof) = prove(&json);
+    assert_eq!(output, Some(total));
+    assert!(verify(proof));
+
+    let (output, proof) = prove(r#"{"transfers": [{"amount": -1}]}"#);
+    assert_eq!(output, None);
+    assert!(verify(proof));
+}
+
+#[test]
+fn ed25519_verify() {
+    let message = b"transfer 100 to account-7";
+    let (public_key, signature) = sign(message);
+    let (prove, verify) = guest::build_ed25519_verify();
+
+    let (output, proof) = prove(public_key, message, &signature);
+    assert!(output);
+    assert!(verify(proof));
+
+    let (output, proof) = prove(public_key, b"transfer 999 to account-7", &signature);
+    assert!(!output);
+    assert!(verify(proof));
+}
+
+#[test]
+fn solvent_disclosure() {
+    let (seed, balance, owner) = ([3u8; 32], 2500, [9u8; 20]);
+    let (prove, verify) = guest::build_solvent();
+    let (commitment, proof) = prove(seed, balance, owner);
+    assert!(verify(proof));
+
+    let disclosure = guest::receipt(seed, balance, owner).disclose(&[0]);
+    disclosure.verify(&commitment).unwrap();
+    assert_eq!(disclosure.field::<bool>(0), Ok(true));
+    assert!(disclosure.field::<u64>(1).is_err());
+
+    // A receipt for another balance doesn't open against the proven commitment
+    let other = guest::receipt(seed, 500, owner).disclose(&[0]);
+    assert!(other.verify(&commitment).is_err());
+}
@@ -0,0 +1,119 @@
+//! Exponentials and logarithms.
+//!
+//! Both reduce to base 2, where the integer part of the exponent (or of the logarithm) is a
+//! shift, and the fractional part is interpolated from a 256-entry table.
+
+use crate::tables::{EXP2, LOG2};
+use crate::Fixed;
+
+/// log2(e), in Q2.30.
+const LOG2_E: i64 = 1_549_082_005;
+/// ln(2), in Q1.31.
+const LN_2: i64 = 1_488_522_236;
+
+impl Fixed {
+    /// 2^`self`, with a relative error below 2^-15. Saturates to [`Fixed::MAX`] for
+    /// `self >= 15`, and underflows to zero below -16.
+    pub fn exp2(self) -> Self {
+        let int = self.0 >> Self::FRAC_BITS;
+        let index = ((self.0 >> 8) & 0xFF) as usize;
+        let weight = self.0 & 0xFF;
+        let (low, high) = (EXP2[index], EXP2[index + 1]);
+        // In [1, 2)
+        let mantissa = low + (((high - low) * weight) >> 8);
+
+        if int >= 15 {
+            Self::MAX
+        } else if int >= 0 {
+            Self(mantissa << int)
+        } else {
+            Self(mantissa >> (-int).min(31))
+        }
+    }
+
+    /// e^`self`, with a relative error below 2^-14. Saturates to [`Fixed::MAX`] above
+    /// ln(32768) ≈ 10.4.
+    pub fn exp(self) -> Self {
+        Self(((self.0 as i64 * LOG2_E) >> 30) as i32).exp2()
+    }
+
+    /// Base-2 logarithm of `self`, within 2^-15 of the exact value.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not positive.
+    pub fn log2(self) -> Self {
+        assert!(self.0 > 0, "logarithm of a non-positive number");
+        let leading_zeros = self.0.leading_zeros();
+        // self = (1 + fraction) * 2^(15 - leading_zeros), with fraction in Q0.32
+        let fraction = ((self.0 as u32) << leading_zeros) << 1;
+        let index = (fraction >> 24) as usize;
+        let weight = ((fraction >> 8) & 0xFFFF) as i32;
+        let (low, high) = (LOG2[index], LOG2[index + 1]);
+        let log2_mantissa = low + (((high - low) * weight) >> 16);
+        Self(((15 - leading_zeros as i32) << Self::FRAC_BITS) + log2_mantissa)
+    }
+
+    /// Natural logarithm of `self`, within 2^-15 of the exact value.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not positive.
+    pub fn ln(self) -> Self {
+        Self(((self.log2().0 as i64 * LN_2) >> 31) as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        for i in 0..=256 {
+            let x = i as f64 / 256.0;
+            assert_eq!(Fixed(EXP2[i]), Fixed::from_f64(x.exp2()));
+            assert_eq!(Fixed(LOG2[i]), Fixed::from_f64((1.0 + x).log2()));
+        }
+    }
+
+    #[test]
+    fn exp() {
+        for bits in (-16 << 16..(10 << 16) + 25_000).step_by(331) {
+            let x = Fixed(bits);
+            let expected = x.to_f64().exp();
+            let error = (x.exp().to_f64() - expected).abs();
+            assert!(error <= expected / 16384.0 + 2.0 / 65536.0, "exp({x})");
+
+            let expected = x.to_f64().exp2().min(Fixed::MAX.to_f64());
+            let error = (x.exp2().to_f64() - expected).abs();
+            assert!(error <= expected / 32768.0 + 1.0 / 65536.0, "exp2({x})");
+        }
+        assert_eq!(Fixed::ZERO.exp(), Fixed::ONE);
+        assert_eq!(Fixed::from_int(11).exp(), Fixed::MAX);
+        assert_eq!(Fixed::from_int(-30).exp(), Fixed::ZERO);
+        assert_eq!(Fixed::MIN.exp2(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn ln() {
+        for bits in (1..i32::MAX).step_by(65_537) {
+            let x = Fixed(bits);
+            let (log2, ln) = (x.to_f64().log2(), x.to_f64().ln());
+            assert!(
+                (x.log2().to_f64() - log2).abs() < 1.0 / 32768.0,
+                "log2({x})"
+            );
+            assert!((x.ln().to_f64() - ln).abs() < 1.0 / 32768.0, "ln({x})");
+        }
+        assert_eq!(Fixed::ONE.ln(), Fixed::ZERO);
+        assert_eq!(Fixed::from_int(1024).log2(), Fixed::from_int(10));
+        assert_eq!(Fixed::EPSILON.log2(), Fixed::from_int(-16));
+    }
+
+    #[test]
+    #[should_panic(expected = "non-positive")]
+    fn ln_of_zero() {
+        Fixed::ZERO.ln();
+    }
+}
@@ -0,0 +1,247 @@
+//! Q16.16 fixed-point numbers.
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A signed Q16.16 fixed-point number: an `i32` holding the value times 2^16, covering
+/// `[-32768, 32768)` in steps of 2^-16.
+///
+/// Like integer arithmetic in release builds, operations wrap around on overflow rather than
+/// saturating, except where documented otherwise.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(pub(crate) i32);
+
+impl Fixed {
+    pub const FRAC_BITS: u32 = 16;
+
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 16);
+    pub const HALF: Self = Self(1 << 15);
+    pub const MIN: Self = Self(i32::MIN);
+    pub const MAX: Self = Self(i32::MAX);
+    /// The smallest positive value, 2^-16.
+    pub const EPSILON: Self = Self(1);
+
+    pub const PI: Self = Self(205_887);
+    pub const FRAC_PI_2: Self = Self(102_944);
+    pub const TAU: Self = Self(411_775);
+    pub const E: Self = Self(178_145);
+    pub const LN_2: Self = Self(45_426);
+
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    pub const fn from_int(n: i32) -> Self {
+        Self(n << Self::FRAC_BITS)
+    }
+
+    /// `num / den`, rounded towards zero.
+    pub const fn from_ratio(num: i32, den: i32) -> Self {
+        Self((((num as i64) << Self::FRAC_BITS) / den as i64) as i32)
+    }
+
+    /// Largest integer less than or equal to `self`.
+    pub const fn floor(self) -> i32 {
+        self.0 >> Self::FRAC_BITS
+    }
+
+    /// Nearest integer to `self`, rounding half-way cases up.
+    pub const fn round(self) -> i32 {
+        self.0.wrapping_add(Self::HALF.0) >> Self::FRAC_BITS
+    }
+
+    /// `self - self.floor()`, in `[0, 1)`.
+    pub const fn fract(self) -> Self {
+        Self(self.0 & (Self::ONE.0 - 1))
+    }
+
+    pub const fn abs(self) -> Self {
+        Self(self.0.wrapping_abs())
+    }
+
+    /// Converts from `f64`, rounding to the nearest representable value and saturating out of
+    /// range. Guests have no FPU, so this is meant for the host side.
+    pub fn from_f64(value: f64) -> Self {
+        let scaled = value * Self::ONE.0 as f64;
+        let rounded = if scaled < 0.0 {
+            scaled - 0.5
+        } else {
+            scaled + 0.5
+        };
+        // `as` rounds towards zero and saturates
+        Self(rounded as i32)
+    }
+
+    /// Converts to `f64`. Guests have no FPU, so this is meant for the host side.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE.0 as f64
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.wrapping_neg())
+    }
+}
+
+/// Rounds towards negative infinity.
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(((self.0 as i64 * rhs.0 as i64) >> Self::FRAC_BITS) as i32)
+    }
+}
+
+/// Scales by an integer, which is a single multiplication.
+impl Mul<i32> for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self {
+        Self(self.0.wrapping_mul(rhs))
+    }
+}
+
+/// Rounds towards zero. This is a 64-bit division, which RV32IM implements in software: where
+/// possible, multiply by a precomputed reciprocal instead.
+///
+/// # Panics
+///
+/// If `rhs` is zero.
+impl Div for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self((((self.0 as i64) << Self::FRAC_BITS) / rhs.0 as i64) as i32)
+    }
+}
+
+/// Rounds towards zero.
+///
+/// # Panics
+///
+/// If `rhs` is zero.
+impl Div<i32> for Fixed {
+    type Output = Self;
+
+    fn div(self, rhs: i32) -> Self {
+        Self(self.0.wrapping_div(rhs))
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl From<i16> for Fixed {
+    fn from(n: i16) -> Self {
+        Self::from_int(n as i32)
+    }
+}
+
+/// Prints five decimal places, without going through floating point.
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let int = abs >> Self::FRAC_BITS;
+        // At most 99998, since 2^-16 is coarser than 10^-5
+        let frac = ((abs & 0xFFFF) as u64 * 100_000 + (1 << 15)) >> Self::FRAC_BITS;
+        write!(f, "{sign}{int}.{frac:05}")
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixed({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn constants() {
+        for (constant, value) in [
+            (Fixed::PI, core::f64::consts::PI),
+            (Fixed::FRAC_PI_2, core::f64::consts::FRAC_PI_2),
+            (Fixed::TAU, core::f64::consts::TAU),
+            (Fixed::E, core::f64::consts::E),
+            (Fixed::LN_2, core::f64::consts::LN_2),
+        ] {
+            assert_eq!(constant, Fixed::from_f64(value));
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Fixed::from_f64(3.25);
+        let b = Fixed::from_f64(-1.5);
+        assert_eq!(a + b, Fixed::from_f64(1.75));
+        assert_eq!(a - b, Fixed::from_f64(4.75));
+        assert_eq!(a * b, Fixed::from_f64(-4.875));
+        assert_eq!(a / b, Fixed::from_ratio(-13, 6));
+        assert_eq!(a * 4, Fixed::from_int(13));
+        assert_eq!(a / 2, Fixed::from_f64(1.625));
+        assert_eq!(-a, Fixed::from_f64(-3.25));
+        assert_eq!(b.abs(), Fixed::from_f64(1.5));
+        assert_eq!(Fixed::MAX + Fixed::EPSILON, Fixed::MIN);
+    }
+
+    #[test]
+    fn rounding() {
+        let x = Fixed::from_f64(-2.5);
+        assert_eq!(x.floor(), -3);
+        assert_eq!(x.round(), -2);
+        assert_eq!(x.fract(), Fixed::HALF);
+        assert_eq!(Fixed::from_f64(2.5).round(), 3);
+        assert_eq!(Fixed::from_int(-7).floor(), -7);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Fixed::PI.to_string(), "3.14159");
+        assert_eq!((-Fixed::HALF).to_string(), "-0.50000");
+        assert_eq!(Fixed::from_bits(0xFFFF).to_string(), "0.99998");
+        assert_eq!(std::format!("{:?}", Fixed::ONE), "Fixed(1.00000)");
+    }
+}
@@ -0,0 +1,189 @@
+//! Exact integer geometry.
+//!
+//! Points have `i32` coordinates, and products are computed in `i64`. Results are exact as long
+//! as coordinates lie in `[-2^30, 2^30)`, so that coordinate differences fit in an `i32`.
+
+use core::cmp::Ordering;
+use core::ops::{Add, Sub};
+
+use crate::sqrt::isqrt_u64;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(self, other: Self) -> i64 {
+        self.x as i64 * other.x as i64 + self.y as i64 * other.y as i64
+    }
+
+    /// The z component of the cross product, i.e. twice the signed area of the triangle
+    /// `(0, self, other)`. Positive if `other` is counterclockwise from `self`.
+    pub fn cross(self, other: Self) -> i64 {
+        self.x as i64 * other.y as i64 - self.y as i64 * other.x as i64
+    }
+
+    pub fn distance_squared(self, other: Self) -> u64 {
+        let delta = self - other;
+        delta.dot(delta) as u64
+    }
+
+    /// Euclidean distance to `other`, rounded down.
+    pub fn distance(self, other: Self) -> u64 {
+        isqrt_u64(self.distance_squared(other))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) as u64 + self.y.abs_diff(other.y) as u64
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x.wrapping_sub(rhs.x), self.y.wrapping_sub(rhs.y))
+    }
+}
+
+/// Whether `a -> b -> c` turns counterclockwise ([`Ordering::Greater`]), clockwise
+/// ([`Ordering::Less`]), or the points are collinear.
+pub fn orientation(a: Point, b: Point, c: Point) -> Ordering {
+    (b - a).cross(c - a).cmp(&0)
+}
+
+/// Whether the closed segments `[a, b]` and `[c, d]` have a point in common.
+pub fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let (abc, abd) = (orientation(a, b, c), orientation(a, b, d));
+    let (cda, cdb) = (orientation(c, d, a), orientation(c, d, b));
+    if abc != abd && cda != cdb {
+        return true;
+    }
+    // Collinear endpoints only touch the other segment if they lie within its bounding box
+    let within = |p: Point, q: Point, r: Point| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+    (abc.is_eq() && within(a, b, c))
+        || (abd.is_eq() && within(a, b, d))
+        || (cda.is_eq() && within(c, d, a))
+        || (cdb.is_eq() && within(c, d, b))
+}
+
+/// Twice the signed area of a simple polygon, positive if its vertices run counterclockwise.
+/// Exact for polygons whose area fits in an `i64`.
+pub fn polygon_area2(vertices: &[Point]) -> i64 {
+    let Some(&last) = vertices.last() else {
+        return 0;
+    };
+    let mut previous = last;
+    let mut area = 0i64;
+    for &vertex in vertices {
+        area = area.wrapping_add(previous.cross(vertex));
+        previous = vertex;
+    }
+    area
+}
+
+/// Whether `point` lies inside or on the boundary of a simple polygon, by its winding number.
+pub fn polygon_contains(vertices: &[Point], point: Point) -> bool {
+    let Some(&last) = vertices.last() else {
+        return false;
+    };
+    let mut previous = last;
+    let mut winding = 0i32;
+    for &vertex in vertices {
+        let side = orientation(previous, vertex, point);
+        if side.is_eq() && segments_intersect(previous, vertex, point, point) {
+            return true;
+        }
+        if previous.y <= point.y {
+            if vertex.y > point.y && side.is_gt() {
+                winding += 1;
+            }
+        } else if vertex.y <= point.y && side.is_lt() {
+            winding -= 1;
+        }
+        previous = vertex;
+    }
+    winding != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Point; 4] = [
+        Point::new(0, 0),
+        Point::new(4, 0),
+        Point::new(4, 4),
+        Point::new(0, 4),
+    ];
+
+    #[test]
+    fn products_and_distances() {
+        let (a, b) = (Point::new(3, -4), Point::new(-1, 2));
+        assert_eq!(a.dot(b), -11);
+        assert_eq!(a.cross(b), 2);
+        assert_eq!(a.distance_squared(b), 52);
+        assert_eq!(a.distance(b), 7);
+        assert_eq!(a.manhattan_distance(b), 10);
+
+        let far = Point::new(-(1 << 30), -(1 << 30));
+        let near = Point::new((1 << 30) - 1, (1 << 30) - 1);
+        let side = (1u64 << 31) - 1;
+        assert_eq!(far.distance_squared(near), 2 * side * side);
+        assert_eq!(far.cross(near), 0);
+    }
+
+    #[test]
+    fn orientations() {
+        let (a, b) = (Point::new(0, 0), Point::new(2, 2));
+        assert_eq!(orientation(a, b, Point::new(0, 1)), Ordering::Greater);
+        assert_eq!(orientation(a, b, Point::new(1, 0)), Ordering::Less);
+        assert_eq!(orientation(a, b, Point::new(5, 5)), Ordering::Equal);
+    }
+
+    #[test]
+    fn intersections() {
+        let p = Point::new;
+        assert!(segments_intersect(p(0, 0), p(4, 4), p(0, 4), p(4, 0)));
+        assert!(!segments_intersect(p(0, 0), p(1, 1), p(0, 4), p(4, 0)));
+        // Touching at an endpoint
+        assert!(segments_intersect(p(0, 0), p(2, 2), p(2, 2), p(3, 0)));
+        // Collinear, overlapping and disjoint
+        assert!(segments_intersect(p(0, 0), p(2, 0), p(1, 0), p(3, 0)));
+        assert!(!segments_intersect(p(0, 0), p(1, 0), p(2, 0), p(3, 0)));
+    }
+
+    #[test]
+    fn polygons() {
+        assert_eq!(polygon_area2(&SQUARE), 32);
+        let mut clockwise = SQUARE;
+        clockwise.reverse();
+        assert_eq!(polygon_area2(&clockwise), -32);
+        assert_eq!(polygon_area2(&[]), 0);
+
+        for polygon in [SQUARE, clockwise] {
+            assert!(polygon_contains(&polygon, Point::new(2, 2)));
+            assert!(polygon_contains(&polygon, Point::new(4, 1)));
+            assert!(polygon_contains(&polygon, Point::new(0, 0)));
+            assert!(!polygon_contains(&polygon, Point::new(5, 2)));
+            assert!(!polygon_contains(&polygon, Point::new(2, -1)));
+        }
+        assert!(!polygon_contains(&[], Point::new(0, 0)));
+    }
+}
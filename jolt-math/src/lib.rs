@@ -0,0 +1,63 @@
+//! Fixed-point and integer math for Jolt guests.
+//!
+//! Guests run on RV32IM, which has no FPU, so `f32`/`f64` arithmetic is emulated in software by
+//! compiler-rt, and a numeric kernel ported as-is spends most of its trace in soft-float
+//! routines. This crate provides the usual elementary functions on a Q16.16 [`Fixed`] type, and
+//! exact integer geometry, implemented in ways that are cheap in Jolt:
+//!
+//! - Elementary functions interpolate small tables (loads cost a single cycle) rather than
+//!   evaluating long polynomials.
+//! - `MULH` and the division instructions expand into sequences of 4 to 9 Jolt cycles, so they
+//!   are used sparingly. In particular, `Fixed / Fixed` needs a 64-bit division, which is done
+//!   in software: multiply by a precomputed reciprocal where possible.
+//! - Code is branch-free where practical, so costs don't depend on the input.
+//!
+//! ```
+//! use jolt_math::Fixed;
+//!
+//! let angle = Fixed::from_ratio(1, 3);
+//! let (sin, cos) = angle.sin_cos();
+//! let one = sin * sin + cos * cos;
+//! assert!((one - Fixed::ONE).abs() <= Fixed::from_bits(4));
+//! ```
+//!
+//! # Cycle costs
+//!
+//! Jolt cycles per call of a release build, counting `MULH`, `DIV`, etc. as their virtual
+//! sequence lengths (as reported by `ProgramSummary::profile`), including the call's return.
+//!
+//! | Function                                        | Cycles                    |
+//! |-------------------------------------------------|---------------------------|
+//! | `Fixed * Fixed`                                 | 12                        |
+//! | `Fixed / Fixed`                                 | 71 – 227                  |
+//! | [`Fixed::sin`], [`Fixed::cos`]                  | 46                        |
+//! | [`Fixed::sin_cos`]                              | 75                        |
+//! | [`Fixed::exp2`]                                 | 18 – 21                   |
+//! | [`Fixed::exp`]                                  | 31 – 34                   |
+//! | [`Fixed::log2`]                                 | 51                        |
+//! | [`Fixed::ln`]                                   | 64                        |
+//! | [`Fixed::sqrt`]                                 | 234                       |
+//! | [`isqrt`]                                       | 161                       |
+//! | [`isqrt_u64`]                                   | 718                       |
+//! | [`Point::cross`](geometry::Point::cross)        | 21                        |
+//! | [`Point::distance_squared`](geometry::Point::distance_squared) | 23         |
+//! | [`Point::distance`](geometry::Point::distance)  | 740                       |
+//! | [`orientation`](geometry::orientation)          | 30                        |
+//! | [`segments_intersect`](geometry::segments_intersect) | 131 – 137            |
+//! | [`polygon_area2`](geometry::polygon_area2)      | 9 + 30 per vertex         |
+//! | [`polygon_contains`](geometry::polygon_contains) | about 40 + 38 per vertex |
+//!
+//! For comparison, a single soft-float `f64` multiplication or addition costs on the order of a
+//! hundred cycles.
+
+#![cfg_attr(not(test), no_std)]
+
+mod exp;
+mod fixed;
+pub mod geometry;
+mod sqrt;
+mod tables;
+pub mod trig;
+
+pub use fixed::Fixed;
+pub use sqrt::{isqrt, isqrt_u64};
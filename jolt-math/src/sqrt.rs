@@ -0,0 +1,105 @@
+//! Square roots.
+//!
+//! Computed digit by digit, which needs only shifts, additions and comparisons. The loops run a
+//! fixed number of iterations, so the cycle count barely depends on the input.
+
+use crate::Fixed;
+
+/// Largest `r` such that `r * r <= n`.
+pub fn isqrt(n: u32) -> u32 {
+    isqrt_rem(n).0
+}
+
+/// [`isqrt`], along with the remainder `n - r * r`.
+fn isqrt_rem(n: u32) -> (u32, u32) {
+    let (mut remainder, mut root) = (n, 0u32);
+    let mut bit = 1u32 << 30;
+    while bit != 0 {
+        let candidate = root + bit;
+        root >>= 1;
+        if remainder >= candidate {
+            remainder -= candidate;
+            root += bit;
+        }
+        bit >>= 2;
+    }
+    (root, remainder)
+}
+
+/// Largest `r` such that `r * r <= n`.
+pub fn isqrt_u64(n: u64) -> u64 {
+    let (mut remainder, mut root) = (n, 0u64);
+    let mut bit = 1u64 << 62;
+    while bit != 0 {
+        let candidate = root + bit;
+        root >>= 1;
+        if remainder >= candidate {
+            remainder -= candidate;
+            root += bit;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+impl Fixed {
+    /// Square root of `self`, rounded down to a multiple of 2^-16.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is negative.
+    pub fn sqrt(self) -> Self {
+        assert!(self.0 >= 0, "square root of a negative number");
+        // The raw value of sqrt(x) is sqrt(raw(x) * 2^16): the square root of raw(x), extended
+        // by the 8 digits contributed by the 16 zero bits. The remainder stays below
+        // 2 * root + 1 < 2^25, so this doesn't need 64-bit arithmetic.
+        let (mut root, mut remainder) = isqrt_rem(self.0 as u32);
+        for _ in 0..Self::FRAC_BITS / 2 {
+            let candidate = (root << 2) + 1;
+            root <<= 1;
+            remainder <<= 2;
+            if remainder >= candidate {
+                remainder -= candidate;
+                root += 1;
+            }
+        }
+        Self(root as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_square_roots() {
+        for n in (0..u32::MAX)
+            .step_by(9_973)
+            .chain([u32::MAX, 1 << 30, (1 << 30) - 1])
+        {
+            let root = isqrt(n) as u64;
+            assert!(root * root <= n as u64 && (root + 1) * (root + 1) > n as u64);
+            assert_eq!(isqrt_u64(n as u64), root);
+        }
+        for n in [u64::MAX, 1 << 62, (1 << 62) - 1, 0x0123_4567_89AB_CDEF] {
+            let root = isqrt_u64(n) as u128;
+            assert!(root * root <= n as u128 && (root + 1) * (root + 1) > n as u128);
+        }
+    }
+
+    #[test]
+    fn fixed_square_roots() {
+        for bits in (0..i32::MAX).step_by(65_537).chain([i32::MAX, 1]) {
+            let root = Fixed(bits).sqrt();
+            assert_eq!(root.0 as u64, isqrt_u64((bits as u64) << 16));
+        }
+        assert_eq!(Fixed::from_int(2).sqrt(), Fixed(92_681));
+        assert_eq!(Fixed::from_int(16).sqrt(), Fixed::from_int(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn sqrt_of_negative() {
+        (-Fixed::ONE).sqrt();
+    }
+}
@@ -0,0 +1,106 @@
+//! Sine and cosine.
+//!
+//! An angle is converted to a fraction of a full turn held in a `u32`, so that reducing it
+//! modulo 2π is just integer wraparound. The top two bits of the turn select the quadrant, and
+//! the rest index a quarter-wave table with linear interpolation. There are no data-dependent
+//! branches, so every call costs the same number of cycles.
+
+use crate::tables::SIN;
+use crate::Fixed;
+
+/// 2^32 / 2π, in Q16.16: multiplying radians in Q16.16 by this gives turns in Q0.32.
+const TURNS_PER_RADIAN: i64 = 683_565_276;
+
+/// A quarter turn, in Q0.32.
+pub const QUARTER_TURN: u32 = 1 << 30;
+
+/// Converts an angle in radians to a fraction of a full turn, in Q0.32.
+pub fn radians_to_turns(radians: Fixed) -> u32 {
+    ((radians.0 as i64 * TURNS_PER_RADIAN) >> Fixed::FRAC_BITS) as u32
+}
+
+/// Sine of an angle given as a fraction of a full turn, in Q0.32.
+pub fn sin_turns(turns: u32) -> Fixed {
+    let quadrant = turns >> 30;
+    // Odd quadrants run through the quarter wave backwards, i.e. at QUARTER_TURN - phase
+    let mirror = (quadrant & 1).wrapping_neg();
+    let phase = turns & (QUARTER_TURN - 1);
+    let phase = (phase ^ mirror)
+        .wrapping_sub(mirror)
+        .wrapping_add(mirror & QUARTER_TURN);
+
+    let index = (phase >> 22) as usize;
+    let weight = ((phase >> 6) & 0xFFFF) as i32;
+    let (low, high) = (SIN[index], SIN[index + 1]);
+    let value = low + (((high - low) * weight) >> 16);
+
+    // The second half of the turn is negative
+    let negate = ((quadrant >> 1) as i32).wrapping_neg();
+    Fixed((value ^ negate) - negate)
+}
+
+/// Cosine of an angle given as a fraction of a full turn, in Q0.32.
+pub fn cos_turns(turns: u32) -> Fixed {
+    sin_turns(turns.wrapping_add(QUARTER_TURN))
+}
+
+impl Fixed {
+    /// Sine of `self` radians, within 2^-15 of the exact value.
+    pub fn sin(self) -> Self {
+        sin_turns(radians_to_turns(self))
+    }
+
+    /// Cosine of `self` radians, within 2^-15 of the exact value.
+    pub fn cos(self) -> Self {
+        cos_turns(radians_to_turns(self))
+    }
+
+    /// Both the sine and cosine of `self` radians, sharing the angle reduction.
+    pub fn sin_cos(self) -> (Self, Self) {
+        let turns = radians_to_turns(self);
+        (sin_turns(turns), cos_turns(turns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1.0 / 32768.0;
+
+    #[test]
+    fn sine_table() {
+        for (i, &value) in SIN[..257].iter().enumerate() {
+            let angle = i as f64 * core::f64::consts::FRAC_PI_2 / 256.0;
+            assert_eq!(Fixed(value), Fixed::from_f64(angle.sin()));
+        }
+        assert_eq!(SIN[257], SIN[255]);
+    }
+
+    #[test]
+    fn sin_cos() {
+        for bits in (-40 << 16..40 << 16).step_by(997) {
+            let x = Fixed(bits);
+            let (sin, cos) = x.sin_cos();
+            assert!(
+                (sin.to_f64() - x.to_f64().sin()).abs() < TOLERANCE,
+                "sin({x})"
+            );
+            assert!(
+                (cos.to_f64() - x.to_f64().cos()).abs() < TOLERANCE,
+                "cos({x})"
+            );
+            assert_eq!(sin, x.sin());
+            assert_eq!(cos, x.cos());
+        }
+    }
+
+    #[test]
+    fn quadrant_boundaries() {
+        for (quarter_turns, sin) in [(0, 0), (1, 1), (2, 0), (3, -1), (4, 0)] {
+            let turns = (quarter_turns as u32).wrapping_mul(QUARTER_TURN);
+            assert_eq!(sin_turns(turns), Fixed::from_int(sin));
+        }
+        assert_eq!(cos_turns(0), Fixed::ONE);
+    }
+}
@@ -1,15 +1,14 @@
-#![feature(proc_macro_tracked_env)]
-
 extern crate proc_macro;
 
 use core::panic;
 
-use common::{attributes::parse_attributes, rv_trace::MemoryLayout};
+use common::{attributes::parse_attributes, constants::PANIC_ECALL, rv_trace::MemoryLayout};
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
 use std::sync::Once;
-use syn::{parse_macro_input, AttributeArgs, Ident, ItemFn, PatType, ReturnType, Type};
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_macro_input, AttributeArgs, Ident, ItemFn, Lifetime, PatType, ReturnType, Type};
 
 static WASM_IMPORTS_INIT: Once = Once::new();
 
@@ -35,10 +34,34 @@ pub fn provable(attr: TokenStream, item: TokenStream) -> TokenStream {
     token_stream
 }
 
+/// Gives every elided lifetime in a type the lifetime `'a`, so that the type can be a field of
+/// the generated `Inputs<'a>`.
+struct ElidedLifetimes {
+    found: bool,
+}
+
+impl VisitMut for ElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(Lifetime::new("'a", Span::call_site()));
+            self.found = true;
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = Lifetime::new("'a", Span::call_site());
+            self.found = true;
+        }
+    }
+}
+
 struct MacroBuilder {
     attr: AttributeArgs,
     func: ItemFn,
     std: bool,
+    zbb: bool,
     func_args: Vec<(Ident, Box<Type>)>,
 }
 
@@ -49,11 +72,16 @@ impl MacroBuilder {
         let std = true;
         #[cfg(not(feature = "guest-std"))]
         let std = false;
+        #[cfg(feature = "guest-zbb")]
+        let zbb = true;
+        #[cfg(not(feature = "guest-zbb"))]
+        let zbb = false;
 
         Self {
             attr,
             func,
             std,
+            zbb,
             func_args,
         }
     }
@@ -62,8 +90,11 @@ impl MacroBuilder {
         let build_fn = self.make_build_fn();
         let execute_fn = self.make_execute_function();
         let analyze_fn = self.make_analyze_function();
+        let sample_fn = self.make_sample_function();
+        let check_replay_fn = self.make_check_replay_function();
         let preprocess_fn = self.make_preprocess_func();
         let prove_fn = self.make_prove_func();
+        let io_module = self.make_io_module();
 
         let main_fn = if let Some(func) = self.get_func_selector() {
             if *self.get_func_name() == func {
@@ -79,8 +110,11 @@ impl MacroBuilder {
             #build_fn
             #execute_fn
             #analyze_fn
+            #sample_fn
+            #check_replay_fn
             #preprocess_fn
             #prove_fn
+            #io_module
             #main_fn
         }
         .into()
@@ -110,7 +144,7 @@ impl MacroBuilder {
                 let preprocessing = std::sync::Arc::new(preprocessing);
 
                 let program_cp = program.clone();
-                let preprocessing_cp = preprocessing.clone();
+                let verifier = jolt::RV32IVerifier::<jolt::F, jolt::PCS>::new((*preprocessing).clone());
 
                 let prove_closure = move |#inputs| {
                     let program = (*program).clone();
@@ -121,8 +155,20 @@ impl MacroBuilder {
 
                 let verify_closure = move |proof: jolt::JoltHyperKZGProof| {
                     let program = (*program_cp).clone();
-                    let preprocessing = (*preprocessing_cp).clone();
-                    RV32IJoltVM::verify(preprocessing, proof.proof, proof.commitments, None).is_ok()
+                    #[cfg(debug_assertions)]
+                    let program_io = proof.proof.program_io.clone();
+                    let result = verifier.verify(proof.proof, proof.commitments);
+
+                    // In dev builds, explain rejections caused by I/O the guest doesn't produce
+                    #[cfg(debug_assertions)]
+                    if result.is_err() {
+                        let diff = program.diff_final_state(&program_io);
+                        if !diff.is_empty() {
+                            eprintln!("{diff}");
+                        }
+                    }
+
+                    result.is_ok()
                 };
 
                 (prove_closure, verify_closure)
@@ -149,6 +195,7 @@ impl MacroBuilder {
         let guest_name = self.get_guest_name();
         let imports = self.make_imports();
         let set_std = self.make_set_std();
+        let set_zbb = self.make_set_zbb();
 
         let fn_name = self.get_func_name();
         let fn_name_str = fn_name.to_string();
@@ -169,6 +216,7 @@ impl MacroBuilder {
                 let mut program = Program::new(#guest_name);
                 program.set_func(#fn_name_str);
                 #set_std
+                #set_zbb
                 #set_mem_size
                 #(#set_program_args;)*
 
@@ -177,11 +225,95 @@ impl MacroBuilder {
         }
     }
 
+    fn make_sample_function(&self) -> TokenStream2 {
+        let set_mem_size = self.make_set_linker_parameters();
+        let guest_name = self.get_guest_name();
+        let imports = self.make_imports();
+        let set_std = self.make_set_std();
+        let set_zbb = self.make_set_zbb();
+
+        let fn_name = self.get_func_name();
+        let fn_name_str = fn_name.to_string();
+        let sample_fn_name = Ident::new(&format!("sample_{}", fn_name), fn_name.span());
+        let inputs = &self.func.sig.inputs;
+        let set_program_args = self.func_args.iter().map(|(name, _)| {
+            quote! {
+                program.set_input(&#name);
+            }
+        });
+
+        quote! {
+             #[cfg(not(target_arch = "wasm32"))]
+             #[cfg(not(feature = "guest"))]
+             pub fn #sample_fn_name(
+                sampling: jolt::host::Sampling,
+                #inputs
+             ) -> jolt::host::analyze::SampledSummary {
+                #imports
+
+                let mut program = Program::new(#guest_name);
+                program.set_func(#fn_name_str);
+                #set_std
+                #set_zbb
+                #set_mem_size
+                #(#set_program_args;)*
+
+                program.trace_sampled(sampling)
+             }
+        }
+    }
+
+    fn make_check_replay_function(&self) -> TokenStream2 {
+        let set_mem_size = self.make_set_linker_parameters();
+        let guest_name = self.get_guest_name();
+        let imports = self.make_imports();
+        let set_std = self.make_set_std();
+        let set_zbb = self.make_set_zbb();
+
+        let fn_name = self.get_func_name();
+        let fn_name_str = fn_name.to_string();
+        let check_replay_fn_name = Ident::new(&format!("check_replay_{}", fn_name), fn_name.span());
+        let inputs = &self.func.sig.inputs;
+        let set_program_args = self.func_args.iter().map(|(name, _)| {
+            quote! {
+                program.set_input(&#name);
+            }
+        });
+
+        quote! {
+             #[cfg(not(target_arch = "wasm32"))]
+             #[cfg(not(feature = "guest"))]
+             pub fn #check_replay_fn_name(
+                #inputs
+             ) -> Result<u64, Box<jolt::host::ReplayDivergence>> {
+                #imports
+
+                let mut program = Program::new(#guest_name);
+                program.set_func(#fn_name_str);
+                #set_std
+                #set_zbb
+                #set_mem_size
+                #(#set_program_args;)*
+
+                program.check_replay()
+             }
+        }
+    }
+
     fn make_preprocess_func(&self) -> TokenStream2 {
         let set_mem_size = self.make_set_linker_parameters();
         let guest_name = self.get_guest_name();
         let imports = self.make_imports();
         let set_std = self.make_set_std();
+        let set_zbb = self.make_set_zbb();
+        let max_memory_address = self.get_max_memory_address();
+
+        let set_max_cycles = match parse_attributes(&self.attr).max_cycles {
+            Some(value) => quote! {
+                let preprocessing = preprocessing.with_max_cycles(#value);
+            },
+            None => quote! {},
+        };
 
         let fn_name = self.get_func_name();
         let fn_name_str = fn_name.to_string();
@@ -197,18 +329,19 @@ impl MacroBuilder {
                 let mut program = Program::new(#guest_name);
                 program.set_func(#fn_name_str);
                 #set_std
+                #set_zbb
                 #set_mem_size
                 let (bytecode, memory_init) = program.decode();
 
-                // TODO(moodlezoup): Feed in size parameters via macro
                 let preprocessing: JoltPreprocessing<4, jolt::F, jolt::PCS> =
                     RV32IJoltVM::preprocess(
                         bytecode,
                         memory_init,
                         1 << 20,
-                        1 << 20,
+                        #max_memory_address,
                         1 << 24
                     );
+                #set_max_cycles
 
                 (program, preprocessing)
             }
@@ -222,9 +355,26 @@ impl MacroBuilder {
             ReturnType::Default => quote! {
                 let ret_val = ();
             },
-            ReturnType::Type(_, ty) => quote! {
-                let ret_val = jolt::postcard::from_bytes::<#ty>(&output_bytes).unwrap();
-            },
+            ReturnType::Type(_, ty) => {
+                let output_view = self.make_output_view();
+                quote! {
+                    let ret_val = #output_view.deserialize::<#ty>().unwrap();
+                }
+            }
+        };
+
+        // A guest halted for exceeding `max_cycles` has no return value
+        let handle_return = if parse_attributes(&self.attr).max_cycles.is_some() {
+            quote! {
+                let ret_val = if proof.proof.program_io.exceeded_max_cycles.is_some() {
+                    None
+                } else {
+                    #handle_return
+                    Some(ret_val)
+                };
+            }
+        } else {
+            handle_return
         };
 
         let set_program_args = self.func_args.iter().map(|(name, _)| {
@@ -237,48 +387,187 @@ impl MacroBuilder {
         let inputs = &self.func.sig.inputs;
         let imports = self.make_imports();
 
+        let input_names = self.func_args.iter().map(|(name, _)| name);
         let prove_fn_name = syn::Ident::new(&format!("prove_{}", fn_name), fn_name.span());
+        let prove_raw_fn_name = syn::Ident::new(&format!("prove_{}_raw", fn_name), fn_name.span());
         quote! {
+            /// Proves the function without deserializing its return value. The outputs can be
+            /// read in place from the returned proof with `jolt::OutputView`. Unlike the
+            /// `prove_` function, errors are returned rather than panicking.
             #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
-            pub fn #prove_fn_name(
+            pub fn #prove_raw_fn_name(
                 mut program: jolt::host::Program,
                 preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS>,
                 #inputs
-            ) -> #prove_output_ty {
+            ) -> Result<jolt::JoltHyperKZGProof, jolt::JoltError> {
                 #imports
 
                 #(#set_program_args;)*
 
-                let (io_device, trace) = program.trace();
-
-                let output_bytes = io_device.outputs.clone();
+                let (io_device, trace) = program.try_trace()?;
 
                 let (jolt_proof, jolt_commitments, _) = RV32IJoltVM::prove(
                     io_device,
                     trace,
                     preprocessing,
-                );
+                )?;
 
-                #handle_return
-
-                let proof = jolt::JoltHyperKZGProof {
+                Ok(jolt::JoltHyperKZGProof {
                     proof: jolt_proof,
                     commitments: jolt_commitments,
-                };
+                })
+            }
+
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub fn #prove_fn_name(
+                program: jolt::host::Program,
+                preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS>,
+                #inputs
+            ) -> #prove_output_ty {
+                let proof = #prove_raw_fn_name(program, preprocessing, #(#input_names),*)
+                    .unwrap_or_else(|e| panic!("Proving failed: {e}"));
+
+                #handle_return
 
                 (ret_val, proof)
             }
         }
     }
 
+    /// A module `{fn}_io`, with typed `Inputs` and `Outputs` and a `prove` function taking them,
+    /// e.g. `guest::fib_io::prove(program, preprocessing, fib_io::Inputs { n })`. It isn't named
+    /// after the function itself, which often shares its name with a crate the guest uses (e.g.
+    /// `sha2`).
+    fn make_io_module(&self) -> TokenStream2 {
+        let fn_name = self.get_func_name();
+        let fn_name_str = fn_name.to_string();
+        let io_mod_name = Ident::new(&format!("{}_io", fn_name), fn_name.span());
+        let max_input_size = parse_attributes(&self.attr).max_input_size;
+        let prove_raw_fn_name = Ident::new(&format!("prove_{}_raw", fn_name), fn_name.span());
+
+        let input_names: Vec<_> = self.func_args.iter().map(|(name, _)| name).collect();
+        let mut lifetimes = ElidedLifetimes { found: false };
+        let input_types: Vec<Type> = self
+            .func_args
+            .iter()
+            .map(|(_, ty)| {
+                let mut ty = (**ty).clone();
+                lifetimes.visit_type_mut(&mut ty);
+                ty
+            })
+            .collect();
+        let generics = if lifetimes.found {
+            quote! { <'a> }
+        } else {
+            quote! {}
+        };
+        let output_ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+
+        let output_view = self.make_output_view();
+        let decode_output = quote! {
+            #output_view
+                .deserialize::<Outputs>()
+                .expect("proven outputs should decode")
+        };
+        // A guest halted for exceeding `max_cycles` has no return value
+        let (prove_output_ty, decode_output) = if parse_attributes(&self.attr).max_cycles.is_some()
+        {
+            (
+                quote! { Option<Outputs> },
+                quote! {
+                    if proof.proof.program_io.exceeded_max_cycles.is_some() {
+                        None
+                    } else {
+                        Some(#decode_output)
+                    }
+                },
+            )
+        } else {
+            (quote! { Outputs }, decode_output)
+        };
+
+        quote! {
+            #[cfg(all(not(target_arch = "wasm32"), not(feature = "guest")))]
+            pub mod #io_mod_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[doc = concat!("The arguments of `", #fn_name_str, "`.")]
+                pub struct Inputs #generics {
+                    #(pub #input_names: #input_types,)*
+                }
+
+                #[doc = concat!("The return value of `", #fn_name_str, "`.")]
+                pub type Outputs = #output_ty;
+
+                impl #generics Inputs #generics {
+                    /// Serializes the inputs as the guest reads them, checking that they fit in
+                    /// its `max_input_size`.
+                    pub fn encode(&self) -> Result<Vec<u8>, jolt::JoltError> {
+                        let bytes = <[Vec<u8>]>::concat(&[
+                            #(jolt::postcard::to_stdvec(&self.#input_names)
+                                .expect("inputs should serialize")),*
+                        ]);
+                        if bytes.len() as u64 > #max_input_size {
+                            return Err(jolt::JoltError::InvalidInputSize {
+                                size: bytes.len(),
+                                max_size: #max_input_size,
+                            });
+                        }
+                        Ok(bytes)
+                    }
+                }
+
+                #[doc = concat!("Proves `", #fn_name_str, "` on `inputs`, returning its outputs along with the proof.")]
+                /// Inputs that don't fit in the guest's `max_input_size` are rejected before
+                /// the guest runs.
+                pub fn prove #generics (
+                    program: jolt::host::Program,
+                    preprocessing: jolt::JoltPreprocessing<4, jolt::F, jolt::PCS>,
+                    inputs: Inputs #generics,
+                ) -> Result<(#prove_output_ty, jolt::JoltHyperKZGProof), jolt::JoltError> {
+                    inputs.encode()?;
+                    let Inputs { #(#input_names),* } = inputs;
+                    let proof = super::#prove_raw_fn_name(program, preprocessing, #(#input_names),*)?;
+                    let outputs = #decode_output;
+                    Ok((outputs, proof))
+                }
+            }
+        }
+    }
+
     fn make_main_func(&self) -> TokenStream2 {
         let attributes = parse_attributes(&self.attr);
-        let memory_layout =
-            MemoryLayout::new(attributes.max_input_size, attributes.max_output_size);
+        let memory_layout = MemoryLayout::new(&attributes.memory_config());
         let input_start = memory_layout.input_start;
         let output_start = memory_layout.output_start;
         let max_input_len = attributes.max_input_size as usize;
         let max_output_len = attributes.max_output_size as usize;
+        let blob_start = memory_layout.blob_start;
+        let max_blob_len = attributes.max_blob_size as usize;
+
+        // Without checkpoints, the return value starts at the beginning of the outputs
+        let (init_checkpoint_journal, journal_size) = if attributes.checkpoints {
+            (
+                quote! {
+                    unsafe {
+                        jolt::init_checkpoint_journal(#output_start as *mut u8, #max_output_len);
+                    }
+                },
+                quote! { jolt::checkpoint_journal_size() },
+            )
+        } else {
+            (quote! {}, quote! { 0 })
+        };
+
+        let init_blobs = quote! {
+            unsafe {
+                jolt::init_blobs(#blob_start as *const u8, #max_blob_len);
+            }
+        };
 
         let get_input_slice = quote! {
             let input_ptr = #input_start as *const u8;
@@ -309,12 +598,16 @@ impl MacroBuilder {
                     core::slice::from_raw_parts_mut(output_ptr, #max_output_len)
                 };
 
-                jolt::postcard::to_slice::<#ty>(&to_return, output_slice).unwrap();
+                let journal_size = #journal_size;
+                jolt::postcard::to_slice::<#ty>(&to_return, &mut output_slice[journal_size..])
+                    .expect("return value exceeds max_output_size");
             },
         };
 
         let panic_fn = self.make_panic(memory_layout.panic);
+        let set_panic_hook = self.make_panic_hook();
         let declare_alloc = self.make_allocator();
+        let report_heap_usage = self.make_report_heap_usage();
 
         quote! {
             #[cfg(feature = "guest")]
@@ -336,20 +629,38 @@ impl MacroBuilder {
             #[no_mangle]
             pub extern "C" fn main() {
                 let mut offset = 0;
+                #set_panic_hook
+                #init_checkpoint_journal
+                #init_blobs
                 #get_input_slice
                 #(#args_fetch;)*
                 #check_input_len
                 #block
                 #handle_return
+                #report_heap_usage
             }
 
             #panic_fn
         }
     }
 
+    /// An `OutputView` over the outputs of `proof`, skipping the checkpoint journal if the
+    /// function has one.
+    fn make_output_view(&self) -> TokenStream2 {
+        if parse_attributes(&self.attr).checkpoints {
+            quote! {
+                jolt::OutputView::with_checkpoints(&proof)
+                    .expect("the checkpoint journal should be complete")
+            }
+        } else {
+            quote! { jolt::OutputView::new(&proof) }
+        }
+    }
+
     fn make_panic(&self, panic_address: u64) -> TokenStream2 {
         if self.std {
             quote! {
+                // The message and location are reported by the hook installed in `main`
                 #[cfg(feature = "guest")]
                 #[no_mangle]
                 pub extern "C" fn jolt_panic() {
@@ -367,7 +678,8 @@ impl MacroBuilder {
 
                 #[cfg(feature = "guest")]
                 #[panic_handler]
-                fn panic(_info: &PanicInfo) -> ! {
+                fn panic(info: &PanicInfo) -> ! {
+                    jolt::report_panic(#PANIC_ECALL, info.message(), info.location());
                     unsafe {
                         core::ptr::write_volatile(#panic_address as *mut u8, 1);
                     }
@@ -378,6 +690,24 @@ impl MacroBuilder {
         }
     }
 
+    fn make_panic_hook(&self) -> TokenStream2 {
+        if self.std {
+            quote! {
+                std::panic::set_hook(Box::new(|info| {
+                    let payload = info.payload();
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                        .unwrap_or("Box<dyn Any>");
+                    jolt::report_panic(#PANIC_ECALL, message, info.location());
+                }));
+            }
+        } else {
+            quote! {}
+        }
+    }
+
     fn make_allocator(&self) -> TokenStream2 {
         if self.std {
             quote! {}
@@ -390,6 +720,17 @@ impl MacroBuilder {
         }
     }
 
+    fn make_report_heap_usage(&self) -> TokenStream2 {
+        if self.std {
+            // `std` guests don't use the jolt-sdk allocator
+            quote! {}
+        } else {
+            quote! {
+                jolt::report_heap_usage(0);
+            }
+        }
+    }
+
     fn make_imports(&self) -> TokenStream2 {
         quote! {
             #[cfg(not(feature = "guest"))]
@@ -466,6 +807,17 @@ impl MacroBuilder {
             program.set_max_output_size(#value);
         });
 
+        let value = attributes.max_blob_size;
+        code.push(quote! {
+            program.set_max_blob_size(#value);
+        });
+
+        if let Some(value) = attributes.max_cycles {
+            code.push(quote! {
+                program.set_max_cycles(#value);
+            });
+        }
+
         quote! {
             #(#code;)*
         }
@@ -483,14 +835,31 @@ impl MacroBuilder {
         }
     }
 
+    fn make_set_zbb(&self) -> TokenStream2 {
+        if self.zbb {
+            quote! {
+                program.set_zbb(true);
+            }
+        } else {
+            quote! {
+                program.set_zbb(false);
+            }
+        }
+    }
+
     fn get_prove_output_type(&self) -> TokenStream2 {
-        match &self.func.sig.output {
-            ReturnType::Default => quote! {
-                ((), jolt::JoltHyperKZGProof)
-            },
-            ReturnType::Type(_, ty) => quote! {
+        let ty = match &self.func.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => quote! { #ty },
+        };
+        if parse_attributes(&self.attr).max_cycles.is_some() {
+            quote! {
+                (Option<#ty>, jolt::JoltHyperKZGProof)
+            }
+        } else {
+            quote! {
                 (#ty, jolt::JoltHyperKZGProof)
-            },
+            }
         }
     }
 
@@ -516,11 +885,20 @@ impl MacroBuilder {
     }
 
     fn get_guest_name(&self) -> String {
-        proc_macro::tracked_env::var("CARGO_PKG_NAME").unwrap()
+        std::env::var("CARGO_PKG_NAME").unwrap()
     }
 
+    /// Untracked, but every function of a guest is built in its own target directory, so a
+    /// change of `JOLT_FUNC_NAME` never reuses a stale expansion.
     fn get_func_selector(&self) -> Option<String> {
-        proc_macro::tracked_env::var("JOLT_FUNC_NAME").ok()
+        std::env::var("JOLT_FUNC_NAME").ok()
+    }
+
+    /// The memory-checking polynomials must cover the guest's entire configured address
+    /// space, not just the addresses touched by a particular execution.
+    fn get_max_memory_address(&self) -> usize {
+        let memory_config = parse_attributes(&self.attr).memory_config();
+        MemoryLayout::new(&memory_config).max_witness_size() as usize
     }
 
     fn has_wasm_attr(&self) -> bool {
@@ -530,6 +908,7 @@ impl MacroBuilder {
     fn make_wasm_function(&self) -> TokenStream2 {
         let fn_name = self.get_func_name();
         let verify_wasm_fn_name = Ident::new(&format!("verify_{}", fn_name), fn_name.span());
+        let max_memory_address = self.get_max_memory_address();
 
         quote! {
             #[wasm_bindgen]
@@ -544,7 +923,7 @@ impl MacroBuilder {
                     decoded_preprocessing_data.bytecode,
                     decoded_preprocessing_data.memory_init,
                     1 << 20,
-                    1 << 20,
+                    #max_memory_address,
                     1 << 24,
                 );
 
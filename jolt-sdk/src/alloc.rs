@@ -1,5 +1,18 @@
+//! Guest heap allocator.
+//!
+//! A bump allocator over the heap, which runs from the end of the stack to the end of the
+//! guest's RAM. It never frees individual allocations; memory is only reclaimed wholesale, by
+//! leaving a [`crate::arena::scope`]. The peak usage is reported to the host with an environment call when the guest returns, and appears in
+//! `ProgramSummary::heap`. An allocation that doesn't fit is reported the same way before
+//! failing, so that the host returns it as a `GuestPanic` with `out_of_memory` set, rather than
+//! just seeing a failed execution.
+
 use core::alloc::{GlobalAlloc, Layout};
 
+/// Value of `a7` for the environment call that reports heap usage. Must match
+/// `common::constants::HEAP_ECALL`.
+pub const HEAP_ECALL: u32 = 0x4A4F_4C48;
+
 pub struct BumpAllocator;
 
 unsafe impl GlobalAlloc for BumpAllocator {
@@ -12,26 +25,109 @@ unsafe impl GlobalAlloc for BumpAllocator {
 
 extern "C" {
     static _HEAP_PTR: u8;
+    static _HEAP_END: u8;
 }
 
 static mut ALLOC_NEXT: usize = 0;
+static mut ALLOC_PEAK: usize = 0;
+static mut ALLOC_COUNT: usize = 0;
 
+/// Returns a null pointer if the allocation doesn't fit in the heap, after reporting it to the
+/// host.
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_alloc(size: usize, align: usize) -> *mut u8 {
     let mut next = unsafe { ALLOC_NEXT };
 
     if next == 0 {
-        next = unsafe { (&_HEAP_PTR) as *const u8 as usize };
+        next = heap_start();
     }
 
     next = align_up(next, align);
+    match next.checked_add(size) {
+        Some(end) if end <= heap_end() => {
+            unsafe {
+                ALLOC_NEXT = end;
+                ALLOC_PEAK = ALLOC_PEAK.max(end);
+                ALLOC_COUNT += 1;
+            }
+            next as *mut u8
+        }
+        _ => {
+            report_heap_usage(size);
+            core::ptr::null_mut()
+        }
+    }
+}
+
+/// Bytes currently allocated, including alignment padding.
+pub fn heap_used() -> usize {
+    match unsafe { ALLOC_NEXT } {
+        0 => 0,
+        next => next - heap_start(),
+    }
+}
+
+/// Most bytes allocated at once, including alignment padding. Differs from [`heap_used`] once
+/// an arena scope has been left.
+pub fn heap_peak() -> usize {
+    match unsafe { ALLOC_PEAK } {
+        0 => 0,
+        peak => peak - heap_start(),
+    }
+}
+
+/// Number of allocations so far.
+pub fn heap_allocations() -> usize {
+    unsafe { ALLOC_COUNT }
+}
+
+/// Size of the heap.
+pub fn heap_capacity() -> usize {
+    heap_end() - heap_start()
+}
+
+/// Reports the heap usage to the host, along with the size of an allocation that failed, if
+/// nonzero. Called by the `main` generated by `#[jolt::provable]` when the guest returns.
+#[doc(hidden)]
+pub fn report_heap_usage(failed_size: usize) {
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        core::arch::asm!(
+            "ecall",
+            in("a7") HEAP_ECALL,
+            in("a0") heap_peak(),
+            in("a1") heap_allocations(),
+            in("a2") heap_capacity(),
+            in("a3") failed_size,
+        );
+    }
+    #[cfg(not(target_arch = "riscv32"))]
+    let _ = failed_size;
+}
 
-    let ptr = next as *mut u8;
-    next += size;
+/// The position of the allocator, to be restored with [`reset_heap`].
+pub(crate) fn heap_mark() -> usize {
+    unsafe { ALLOC_NEXT }
+}
+
+/// Frees everything allocated since `mark` was taken with [`heap_mark`].
+///
+/// # Safety
+/// None of those allocations may be used afterwards.
+pub(crate) unsafe fn reset_heap(mark: usize) {
+    unsafe { ALLOC_NEXT = mark };
+}
+
+// Taking the address of a `static mut` only became safe in newer toolchains.
+#[allow(unused_unsafe)]
+fn heap_start() -> usize {
+    unsafe { core::ptr::addr_of!(_HEAP_PTR) as usize }
+}
 
-    unsafe { ALLOC_NEXT = next };
-    ptr
+#[allow(unused_unsafe)]
+fn heap_end() -> usize {
+    unsafe { core::ptr::addr_of!(_HEAP_END) as usize }
 }
 
 fn align_up(addr: usize, align: usize) -> usize {
@@ -0,0 +1,32 @@
+//! Arena allocation for guests whose allocations come in phases.
+//!
+//! The guest allocator never frees individual allocations, so a long-running guest that
+//! allocates scratch space in every phase (e.g. for every block it processes) eventually runs
+//! out of heap, even though it only ever needs one phase's worth. [`scope`] reclaims everything
+//! allocated within it when it returns, at no cost per allocation.
+//!
+//! Guests built with `guest-std` use the allocator of the standard library, which frees memory
+//! itself; [`scope`] only runs its closure there.
+
+use crate::alloc::{heap_mark, reset_heap};
+
+/// Runs `f`, then frees everything allocated while it ran. Scopes can be nested, in which case
+/// leaving the inner scope only frees what was allocated within it.
+///
+/// ```ignore
+/// for block in blocks {
+///     let root = unsafe { jolt::arena::scope(|| apply(&mut state, &block)) };
+///     jolt::commit_checkpoint(root);
+/// }
+/// ```
+///
+/// # Safety
+/// Nothing allocated within the scope may be used after it returns. In particular, `f`'s result
+/// must not own or borrow heap memory allocated by `f`, and `f` must not grow (and so
+/// reallocate) collections that outlive the scope.
+pub unsafe fn scope<R>(f: impl FnOnce() -> R) -> R {
+    let mark = heap_mark();
+    let result = f();
+    unsafe { reset_heap(mark) };
+    result
+}
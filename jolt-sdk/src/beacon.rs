@@ -0,0 +1,239 @@
+//! Verifiable randomness from a [drand](https://drand.love) beacon.
+//!
+//! Guests that need unbiasable public randomness (lotteries, jury selection, ...) can take a
+//! beacon round as input and check its signature in-guest before using it:
+//!
+//! ```ignore
+//! const CHAIN: BeaconChain = BeaconChain::new(PUBLIC_KEY, GENESIS_TIME, PERIOD);
+//!
+//! #[jolt::provable]
+//! fn draw(round: BeaconRound, num_tickets: u32) -> (u64, u32) {
+//!     let randomness = CHAIN.verify(&round).expect("invalid beacon round");
+//!     (round.round, u32::from_le_bytes(randomness[..4].try_into().unwrap()) % num_tickets)
+//! }
+//! ```
+//!
+//! Because the chain's public key is part of the guest's bytecode, a valid proof shows that the
+//! output was derived from a round actually signed by that chain. The verifier should still
+//! check that the proven round number (e.g. returned as an output, as above) is the one it
+//! expects, e.g. using [`BeaconChain::round_at`], since any past round would verify.
+//!
+//! Supports chains using the `bls-unchained-g1-rfc9380` scheme (such as drand's "quicknet"),
+//! whose signatures are in G1 and public keys in G2. The chain parameters can be read from
+//! `https://api.drand.sh/<chain hash>/info`, and rounds from
+//! `https://api.drand.sh/<chain hash>/public/<round>`.
+//!
+//! Jolt does not have a BLS12-381 precompile yet, so signature verification (a hash-to-curve
+//! and two pairings) runs in software and dominates the cycle count of small guests.
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Domain separation tag of the `bls-unchained-g1-rfc9380` scheme.
+pub const BEACON_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+pub const BEACON_SIGNATURE_SIZE: usize = 48;
+pub const BEACON_PUBLIC_KEY_SIZE: usize = 96;
+
+/// Public parameters of a drand chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BeaconChain {
+    /// Compressed G2 public key.
+    pub public_key: [u8; BEACON_PUBLIC_KEY_SIZE],
+    /// Unix time (in seconds) of round 1.
+    pub genesis_time: u64,
+    /// Seconds between rounds.
+    pub period: u64,
+}
+
+/// A single round of beacon output, as published by the chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconRound {
+    pub round: u64,
+    /// Compressed G1 signature over the round number.
+    #[serde(with = "byte_array")]
+    pub signature: [u8; BEACON_SIGNATURE_SIZE],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BeaconError {
+    InvalidPublicKey,
+    InvalidSignature,
+    /// The signature is well-formed but was not produced by the chain for this round.
+    SignatureMismatch,
+}
+
+impl BeaconChain {
+    pub const fn new(
+        public_key: [u8; BEACON_PUBLIC_KEY_SIZE],
+        genesis_time: u64,
+        period: u64,
+    ) -> Self {
+        Self {
+            public_key,
+            genesis_time,
+            period,
+        }
+    }
+
+    /// The latest round published at `unix_time`, or 0 if it precedes the chain's genesis.
+    pub fn round_at(&self, unix_time: u64) -> u64 {
+        match unix_time.checked_sub(self.genesis_time) {
+            Some(elapsed) => elapsed / self.period + 1,
+            None => 0,
+        }
+    }
+
+    /// Checks the round's signature against the chain's public key, and returns its randomness.
+    pub fn verify(&self, round: &BeaconRound) -> Result<[u8; 32], BeaconError> {
+        let public_key = Option::<G2Affine>::from(G2Affine::from_compressed(&self.public_key))
+            .ok_or(BeaconError::InvalidPublicKey)?;
+        let signature = Option::<G1Affine>::from(G1Affine::from_compressed(&round.signature))
+            .ok_or(BeaconError::InvalidSignature)?;
+
+        // e(signature, g2) == e(H(m), public_key)
+        let message = G1Affine::from(round_message_point(round.round));
+        if pairing(&signature, &G2Affine::generator()) != pairing(&message, &public_key) {
+            return Err(BeaconError::SignatureMismatch);
+        }
+        Ok(round.randomness())
+    }
+}
+
+impl BeaconRound {
+    /// Parses a round as published by the drand HTTP API (hex-encoded signature).
+    pub fn from_hex(round: u64, signature: &str) -> Option<Self> {
+        let signature = signature.as_bytes();
+        if signature.len() != 2 * BEACON_SIGNATURE_SIZE {
+            return None;
+        }
+        let mut bytes = [0u8; BEACON_SIGNATURE_SIZE];
+        for (byte, digits) in bytes.iter_mut().zip(signature.chunks_exact(2)) {
+            *byte = (hex_digit(digits[0])? << 4) | hex_digit(digits[1])?;
+        }
+        Some(Self {
+            round,
+            signature: bytes,
+        })
+    }
+
+    /// The round's randomness, `sha256(signature)`. Only meaningful once the round has been
+    /// checked with [`BeaconChain::verify`].
+    pub fn randomness(&self) -> [u8; 32] {
+        Sha256::digest(self.signature).into()
+    }
+}
+
+/// The point signed by the chain for `round`: `H(sha256(round as big-endian u64))`.
+fn round_message_point(round: u64) -> G1Projective {
+    let digest = Sha256::digest(round.to_be_bytes());
+    <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(digest, BEACON_DST)
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// (De)serializes fixed-size byte arrays longer than serde's built-in limit of 32, without
+/// requiring an allocator.
+mod byte_array {
+    use core::fmt;
+    use serde::de::{Error, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        bytes: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        struct ByteArrayVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for ByteArrayVisitor<N> {
+            type Value = [u8; N];
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "{N} bytes")
+            }
+
+            fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+                bytes
+                    .try_into()
+                    .map_err(|_| E::invalid_length(bytes.len(), &self))
+            }
+        }
+
+        deserializer.deserialize_bytes(ByteArrayVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "host"))]
+mod tests {
+    use super::*;
+    use bls12_381::{G2Projective, Scalar};
+
+    fn test_chain_and_round(round: u64) -> (BeaconChain, BeaconRound) {
+        let secret_key = Scalar::from(0x1234_5678_9abc_def0u64);
+        let public_key = G2Affine::from(G2Projective::generator() * secret_key);
+        let signature = G1Affine::from(round_message_point(round) * secret_key);
+        (
+            BeaconChain::new(public_key.to_compressed(), 1_692_803_367, 3),
+            BeaconRound {
+                round,
+                signature: signature.to_compressed(),
+            },
+        )
+    }
+
+    #[test]
+    fn verify_round() {
+        let (chain, round) = test_chain_and_round(1000);
+        assert_eq!(chain.verify(&round), Ok(round.randomness()));
+
+        let wrong_round = BeaconRound {
+            round: 1001,
+            ..round
+        };
+        assert_eq!(
+            chain.verify(&wrong_round),
+            Err(BeaconError::SignatureMismatch)
+        );
+
+        let mut malformed = round;
+        malformed.signature[BEACON_SIGNATURE_SIZE - 1] ^= 1;
+        assert_ne!(chain.verify(&malformed), Ok(round.randomness()));
+    }
+
+    #[test]
+    fn round_serialization() {
+        let (_, round) = test_chain_and_round(7);
+        let bytes = postcard::to_stdvec(&round).unwrap();
+        assert_eq!(postcard::from_bytes::<BeaconRound>(&bytes).unwrap(), round);
+
+        let hex: String = round
+            .signature
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        assert_eq!(BeaconRound::from_hex(7, &hex), Some(round));
+        assert_eq!(BeaconRound::from_hex(7, &hex[2..]), None);
+    }
+
+    #[test]
+    fn round_at() {
+        let (chain, _) = test_chain_and_round(1);
+        assert_eq!(chain.round_at(chain.genesis_time - 1), 0);
+        assert_eq!(chain.round_at(chain.genesis_time), 1);
+        assert_eq!(chain.round_at(chain.genesis_time + 7), 3);
+    }
+}
@@ -0,0 +1,67 @@
+//! Read-only blobs.
+//!
+//! The host can register large byte blobs with `Program::add_blob`, e.g. a state snapshot or a
+//! lookup table, which are loaded into a dedicated region after the guest's RAM. The guest reads
+//! them with [`read_blob`], without deserializing them from its inputs. Only the blobs' hashes
+//! are part of the program's public I/O, so the proof doesn't grow with them, but the verifier
+//! needs their data (see `common::rv_trace::Blobs`, which also describes the region's layout).
+//!
+//! The region is read-only: a guest that writes to it can't be traced.
+
+const BLOB_DIRECTORY_ENTRY_SIZE: usize = core::mem::size_of::<u32>();
+
+struct BlobRegion {
+    start: *const u8,
+    capacity: usize,
+}
+
+static mut BLOBS: BlobRegion = BlobRegion {
+    start: core::ptr::null(),
+    capacity: 0,
+};
+
+/// Locates the blob region. Called by the `#[jolt::provable]` entry point before the guest
+/// function body runs.
+#[doc(hidden)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn init_blobs(blob_start: *const u8, max_blob_len: usize) {
+    // Without room for the directory, the region is empty
+    if max_blob_len >= BLOB_DIRECTORY_ENTRY_SIZE {
+        BLOBS = BlobRegion {
+            start: blob_start,
+            capacity: max_blob_len,
+        };
+    }
+}
+
+/// The blob with id `id`, as returned by `Program::add_blob`, or `None` if there is no such
+/// blob. Outside of the guest (e.g. when the function is executed natively on the host) there
+/// are no blobs.
+pub fn read_blob(id: usize) -> Option<&'static [u8]> {
+    let region = unsafe { &*core::ptr::addr_of!(BLOBS) };
+    if region.start.is_null() {
+        return None;
+    }
+
+    let count = unsafe { read_entry(region.start, 0) };
+    if id >= count {
+        return None;
+    }
+    let offset = (0..id).fold((count + 1) * BLOB_DIRECTORY_ENTRY_SIZE, |offset, i| {
+        offset + unsafe { read_entry(region.start, i + 1) }
+    });
+    let len = unsafe { read_entry(region.start, id + 1) };
+    assert!(
+        offset + len <= region.capacity,
+        "blob directory exceeds max_blob_size"
+    );
+    Some(unsafe { core::slice::from_raw_parts(region.start.add(offset), len) })
+}
+
+unsafe fn read_entry(start: *const u8, index: usize) -> usize {
+    let mut bytes = [0u8; BLOB_DIRECTORY_ENTRY_SIZE];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = core::ptr::read_volatile(start.add(index * BLOB_DIRECTORY_ENTRY_SIZE + i));
+    }
+    u32::from_le_bytes(bytes) as usize
+}
@@ -0,0 +1,165 @@
+//! Checkpoint journal.
+//!
+//! A guest whose function is marked `#[jolt::provable(checkpoints)]` can call
+//! [`commit_checkpoint`] to publish a 32-byte digest of some intermediate state (e.g. the state
+//! root after each block in a multi-block guest). Checkpoints are written to the start of the
+//! program's public outputs, ahead of the serialized return value:
+//!
+//! ```text
+//! [ count: u32 LE | digest_0 | digest_1 | ... | digest_{count-1} | postcard(return value) ]
+//! ```
+//!
+//! Since the outputs are part of the proven program I/O, anyone verifying the proof can
+//! reference the journal. Each checkpoint is written as soon as it is committed, so the journal
+//! is retained even if the guest later panics. Without the `checkpoints` attribute there is no
+//! journal, and the outputs hold only the serialized return value.
+
+pub const CHECKPOINT_DIGEST_SIZE: usize = 32;
+const CHECKPOINT_COUNT_SIZE: usize = core::mem::size_of::<u32>();
+
+struct CheckpointJournal {
+    start: *mut u8,
+    capacity: usize,
+    len: usize,
+}
+
+static mut JOURNAL: CheckpointJournal = CheckpointJournal {
+    start: core::ptr::null_mut(),
+    capacity: 0,
+    len: 0,
+};
+
+/// Sets up an empty journal at the start of the output region. Called by the
+/// `#[jolt::provable(checkpoints)]` entry point before the guest function body runs.
+#[doc(hidden)]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn init_checkpoint_journal(output_start: *mut u8, max_output_len: usize) {
+    assert!(
+        max_output_len >= CHECKPOINT_COUNT_SIZE,
+        "max_output_size is too small for the checkpoint journal"
+    );
+    JOURNAL = CheckpointJournal {
+        start: output_start,
+        capacity: max_output_len,
+        len: 0,
+    };
+    write_count(output_start, 0);
+}
+
+/// Appends `digest` to the checkpoint journal in the program's public outputs.
+///
+/// Panics if the journal would no longer fit in `max_output_size`, or if the guest function
+/// isn't marked `#[jolt::provable(checkpoints)]`. Outside of the guest (e.g. when the function
+/// is executed natively on the host) this is a no-op.
+pub fn commit_checkpoint(digest: [u8; CHECKPOINT_DIGEST_SIZE]) {
+    let journal = unsafe { &mut *core::ptr::addr_of_mut!(JOURNAL) };
+    if journal.start.is_null() {
+        #[cfg(target_arch = "riscv32")]
+        panic!("commit_checkpoint requires #[jolt::provable(checkpoints)]");
+        #[cfg(not(target_arch = "riscv32"))]
+        return;
+    }
+
+    let offset = CHECKPOINT_COUNT_SIZE + journal.len * CHECKPOINT_DIGEST_SIZE;
+    assert!(
+        offset + CHECKPOINT_DIGEST_SIZE <= journal.capacity,
+        "checkpoint journal exceeds max_output_size"
+    );
+    for (i, byte) in digest.iter().enumerate() {
+        unsafe { core::ptr::write_volatile(journal.start.add(offset + i), *byte) };
+    }
+    journal.len += 1;
+    unsafe { write_count(journal.start, journal.len as u32) };
+}
+
+/// Number of output bytes occupied by the journal; the return value is serialized after them.
+#[doc(hidden)]
+pub fn checkpoint_journal_size() -> usize {
+    let journal = unsafe { &*core::ptr::addr_of!(JOURNAL) };
+    CHECKPOINT_COUNT_SIZE + journal.len * CHECKPOINT_DIGEST_SIZE
+}
+
+unsafe fn write_count(start: *mut u8, count: u32) {
+    for (i, byte) in count.to_le_bytes().iter().enumerate() {
+        core::ptr::write_volatile(start.add(i), *byte);
+    }
+}
+
+/// Splits the public outputs of a program with checkpoints into its checkpoint journal and the
+/// bytes of the serialized return value.
+///
+/// The emulator does not record trailing output bytes that were never written, so a missing
+/// count is read as zero checkpoints. Returns `None` if the journal is truncated.
+#[cfg(feature = "host")]
+pub fn decode_checkpoint_journal(
+    outputs: &[u8],
+) -> Option<(Vec<[u8; CHECKPOINT_DIGEST_SIZE]>, &[u8])> {
+    let (digests, return_value) = split_checkpoint_journal(outputs)?;
+    let digests = digests
+        .chunks_exact(CHECKPOINT_DIGEST_SIZE)
+        .map(|digest| digest.try_into().unwrap())
+        .collect();
+    Some((digests, return_value))
+}
+
+/// Like [`decode_checkpoint_journal`], but returns the concatenated digests without copying them.
+#[cfg(feature = "host")]
+pub(crate) fn split_checkpoint_journal(outputs: &[u8]) -> Option<(&[u8], &[u8])> {
+    let mut count_bytes = [0u8; CHECKPOINT_COUNT_SIZE];
+    let count_len = outputs.len().min(CHECKPOINT_COUNT_SIZE);
+    count_bytes[..count_len].copy_from_slice(&outputs[..count_len]);
+    let count = u32::from_le_bytes(count_bytes) as usize;
+
+    let journal = &outputs[count_len..];
+    let journal_len = count.checked_mul(CHECKPOINT_DIGEST_SIZE)?;
+    if journal.len() < journal_len {
+        return None;
+    }
+    Some(journal.split_at(journal_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "host")]
+    #[test]
+    fn decode_journal() {
+        let digests = [[1u8; CHECKPOINT_DIGEST_SIZE], [2u8; CHECKPOINT_DIGEST_SIZE]];
+        let mut outputs = 2u32.to_le_bytes().to_vec();
+        outputs.extend(digests.concat());
+        outputs.extend_from_slice(b"ret");
+
+        let (decoded, return_value) = decode_checkpoint_journal(&outputs).unwrap();
+        assert_eq!(decoded, digests);
+        assert_eq!(return_value, b"ret");
+
+        // Trailing bytes that were never written, including the count, are not recorded
+        let (decoded, return_value) = decode_checkpoint_journal(&[]).unwrap();
+        assert!(decoded.is_empty());
+        assert!(return_value.is_empty());
+
+        // Truncated journal
+        assert!(decode_checkpoint_journal(&outputs[..CHECKPOINT_COUNT_SIZE + 40]).is_none());
+        let huge_count = u32::MAX.to_le_bytes();
+        assert!(decode_checkpoint_journal(&huge_count).is_none());
+    }
+
+    // The only test that touches the journal, which is global
+    #[test]
+    #[should_panic(expected = "checkpoint journal exceeds max_output_size")]
+    fn journal_overflow() {
+        // Room for the count and one and a half digests
+        let mut outputs = [0u8; CHECKPOINT_COUNT_SIZE + CHECKPOINT_DIGEST_SIZE * 3 / 2];
+        unsafe { init_checkpoint_journal(outputs.as_mut_ptr(), outputs.len()) };
+
+        commit_checkpoint([1u8; CHECKPOINT_DIGEST_SIZE]);
+        assert_eq!(outputs[..CHECKPOINT_COUNT_SIZE], 1u32.to_le_bytes());
+        assert_eq!(
+            checkpoint_journal_size(),
+            CHECKPOINT_COUNT_SIZE + CHECKPOINT_DIGEST_SIZE
+        );
+
+        commit_checkpoint([2u8; CHECKPOINT_DIGEST_SIZE]);
+    }
+}
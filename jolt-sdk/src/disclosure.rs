@@ -0,0 +1,326 @@
+//! Selective disclosure of a guest's outputs to parties that only see its commitment.
+//!
+//! Instead of returning every output field publicly, a guest can collect its fields in a
+//! [`Journal`] and return only the journal's [`JournalCommitment`], a Merkle root over salted
+//! hashes of the fields. Whoever holds the full journal (typically the host, which rebuilds it
+//! by running the same code natively) can later produce a [`Disclosure`] of any subset of the
+//! fields, which anyone can check against the proven commitment without learning the other
+//! fields:
+//!
+//! ```ignore
+//! // guest
+//! pub fn receipt(seed: [u8; 32], balance: u64, owner: [u8; 20]) -> Journal {
+//!     let mut journal = Journal::new(seed);
+//!     journal.push(&(balance >= 1000));
+//!     journal.push(&balance);
+//!     journal.push(&owner);
+//!     journal
+//! }
+//!
+//! #[jolt::provable]
+//! fn solvent(seed: [u8; 32], balance: u64, owner: [u8; 20]) -> JournalCommitment {
+//!     receipt(seed, balance, owner).commitment()
+//! }
+//!
+//! // host
+//! let (commitment, proof) = prove_solvent(seed, balance, owner);
+//! let disclosure = guest::receipt(seed, balance, owner).disclose(&[0]);
+//!
+//! // third party, given a commitment it trusts
+//! disclosure.verify(&commitment)?;
+//! let solvent: bool = disclosure.field(0)?;
+//! ```
+//!
+//! # Scope
+//!
+//! Undisclosed fields are only hidden from *commitment-only observers*: parties that get the
+//! proven commitment from someone who verified the proof (e.g. a contract recording the output
+//! of an on-chain verifier), and never see the proof itself. From them, each field is hidden by
+//! a salt derived from the journal's seed, so it can't be recovered by hashing guesses.
+//!
+//! The seed is a guest input, like the fields it salts, and Jolt has no private inputs: proofs
+//! are not zero-knowledge and include the guest's inputs in `program_io.inputs`. Anyone holding
+//! the proof therefore learns the seed and the fields' inputs, and can recompute every field.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const SALT_DOMAIN: &[u8] = b"jolt-journal-salt";
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+/// Stands in for the missing leaves of a journal whose field count isn't a power of two. Not the
+/// hash of any leaf or node.
+const EMPTY_LEAF: [u8; 32] = [0; 32];
+
+/// An ordered list of output fields, each postcard-serialized.
+#[derive(Clone, Debug)]
+pub struct Journal {
+    seed: [u8; 32],
+    fields: Vec<Vec<u8>>,
+}
+
+/// Commits to every field of a [`Journal`]. Returned by the guest as its public output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalCommitment {
+    pub root: [u8; 32],
+    pub num_fields: u32,
+}
+
+/// A subset of a journal's fields, with the salts and Merkle paths that open them against the
+/// journal's commitment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disclosure {
+    /// Sorted by index.
+    pub fields: Vec<DisclosedField>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisclosedField {
+    pub index: u32,
+    pub salt: [u8; 32],
+    /// The postcard-serialized field.
+    pub bytes: Vec<u8>,
+    /// Sibling hashes from the leaf up to the root.
+    pub path: Vec<[u8; 32]>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisclosureError {
+    /// A field's index is past the end of the journal, or not after the previous field's.
+    InvalidIndex(u32),
+    /// A field doesn't open to the commitment's root.
+    InvalidOpening(u32),
+    NotDisclosed(u32),
+    Deserialization(u32),
+}
+
+impl Journal {
+    /// `seed` salts every field, and must be unpredictable to whoever sees disclosures for the
+    /// undisclosed fields to stay hidden. It is still part of the proof's public inputs (see the
+    /// [module docs](self)).
+    pub fn new(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Appends `value` as the next field and returns its index.
+    pub fn push<T: Serialize>(&mut self, value: &T) -> u32 {
+        let bytes = postcard::to_extend(value, Vec::new()).expect("serialization failed");
+        self.fields.push(bytes);
+        self.fields.len() as u32 - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn commitment(&self) -> JournalCommitment {
+        let layers = self.merkle_layers();
+        JournalCommitment {
+            root: layers.last().map_or(EMPTY_LEAF, |root| root[0]),
+            num_fields: self.fields.len() as u32,
+        }
+    }
+
+    /// Discloses the fields at `indices`. Panics if an index is out of bounds.
+    pub fn disclose(&self, indices: &[u32]) -> Disclosure {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let layers = self.merkle_layers();
+        let fields = indices
+            .into_iter()
+            .map(|index| {
+                let path = layers[..layers.len() - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(level, layer)| layer[((index as usize) >> level) ^ 1])
+                    .collect();
+                DisclosedField {
+                    index,
+                    salt: self.salt(index),
+                    bytes: self.fields[index as usize].clone(),
+                    path,
+                }
+            })
+            .collect();
+        Disclosure { fields }
+    }
+
+    fn salt(&self, index: u32) -> [u8; 32] {
+        Sha256::new()
+            .chain_update(SALT_DOMAIN)
+            .chain_update(self.seed)
+            .chain_update(index.to_le_bytes())
+            .finalize()
+            .into()
+    }
+
+    /// Every layer of the tree, from the (padded) leaves up to the root. Empty for an empty
+    /// journal.
+    fn merkle_layers(&self) -> Vec<Vec<[u8; 32]>> {
+        if self.fields.is_empty() {
+            return Vec::new();
+        }
+        let mut leaves: Vec<_> = (0..self.fields.len())
+            .map(|i| leaf_hash(&self.salt(i as u32), &self.fields[i]))
+            .collect();
+        leaves.resize(self.fields.len().next_power_of_two(), EMPTY_LEAF);
+
+        let mut layers = alloc::vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        layers
+    }
+}
+
+impl Disclosure {
+    /// Checks every disclosed field against `commitment`.
+    pub fn verify(&self, commitment: &JournalCommitment) -> Result<(), DisclosureError> {
+        let depth = (commitment.num_fields as usize)
+            .next_power_of_two()
+            .trailing_zeros() as usize;
+        let mut next_index = 0;
+        for field in &self.fields {
+            if field.index < next_index || field.index >= commitment.num_fields {
+                return Err(DisclosureError::InvalidIndex(field.index));
+            }
+            next_index = field.index + 1;
+
+            if field.path.len() != depth {
+                return Err(DisclosureError::InvalidOpening(field.index));
+            }
+            let root = field.path.iter().enumerate().fold(
+                leaf_hash(&field.salt, &field.bytes),
+                |hash, (level, sibling)| match (field.index >> level) & 1 {
+                    0 => node_hash(&hash, sibling),
+                    _ => node_hash(sibling, &hash),
+                },
+            );
+            if root != commitment.root {
+                return Err(DisclosureError::InvalidOpening(field.index));
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserializes the field at `index`. Only meaningful once the disclosure has been checked
+    /// with [`Self::verify`].
+    pub fn field<T: DeserializeOwned>(&self, index: u32) -> Result<T, DisclosureError> {
+        let field = self
+            .fields
+            .binary_search_by_key(&index, |field| field.index)
+            .map(|i| &self.fields[i])
+            .map_err(|_| DisclosureError::NotDisclosed(index))?;
+        postcard::from_bytes(&field.bytes).map_err(|_| DisclosureError::Deserialization(index))
+    }
+}
+
+fn leaf_hash(salt: &[u8; 32], bytes: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([LEAF_TAG])
+        .chain_update(salt)
+        .chain_update(bytes)
+        .finalize()
+        .into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([NODE_TAG])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[cfg(all(test, feature = "host"))]
+mod tests {
+    use super::*;
+
+    fn test_journal(num_fields: u32) -> Journal {
+        let mut journal = Journal::new([7; 32]);
+        for i in 0..num_fields {
+            journal.push(&(i, u64::from(i) * 1000));
+        }
+        journal
+    }
+
+    #[test]
+    fn disclose_subset() {
+        for num_fields in [1, 2, 5, 8] {
+            let journal = test_journal(num_fields);
+            let commitment = journal.commitment();
+            let disclosure = journal.disclose(&[num_fields - 1, 0]);
+
+            disclosure.verify(&commitment).unwrap();
+            assert_eq!(disclosure.field::<(u32, u64)>(0), Ok((0, 0)));
+            assert_eq!(
+                disclosure.field::<(u32, u64)>(num_fields - 1),
+                Ok((num_fields - 1, u64::from(num_fields - 1) * 1000))
+            );
+            if num_fields > 2 {
+                assert_eq!(
+                    disclosure.field::<(u32, u64)>(1),
+                    Err(DisclosureError::NotDisclosed(1))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_disclosure() {
+        let journal = test_journal(5);
+        let commitment = journal.commitment();
+        let disclosure = journal.disclose(&[2, 3]);
+
+        let mut tampered = disclosure.clone();
+        tampered.fields[0].bytes[0] ^= 1;
+        assert_eq!(
+            tampered.verify(&commitment),
+            Err(DisclosureError::InvalidOpening(2))
+        );
+
+        let mut tampered = disclosure.clone();
+        tampered.fields[1].index = 1;
+        assert_eq!(
+            tampered.verify(&commitment),
+            Err(DisclosureError::InvalidIndex(1))
+        );
+
+        let other_seed = Journal {
+            seed: [8; 32],
+            ..journal.clone()
+        };
+        assert_eq!(
+            disclosure.verify(&other_seed.commitment()),
+            Err(DisclosureError::InvalidOpening(2))
+        );
+
+        let fewer_fields = JournalCommitment {
+            num_fields: 4,
+            ..commitment
+        };
+        assert_eq!(
+            disclosure.verify(&fewer_fields),
+            Err(DisclosureError::InvalidOpening(2))
+        );
+    }
+}
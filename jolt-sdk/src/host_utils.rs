@@ -10,7 +10,23 @@ pub use jolt_core::host;
 pub use jolt_core::jolt::instruction;
 pub use jolt_core::jolt::vm::{
     bytecode::BytecodeRow,
-    rv32i_vm::{JoltHyperKZGProof, RV32IJoltProof, RV32IJoltVM, PCS, RV32I, Serializable},
+    preprocessing::ProgramId,
+    progress::{CancellationToken, ProverEvent, ProverObserver},
+    rv32i_vm::{
+        JoltHyperKZGProof, RV32IJoltProof, RV32IJoltVM, RV32IVerifier, Serializable, PCS, RV32I,
+    },
+    scheduler::{ProofProfile, ProverMetrics, SchedulerConfig},
     Jolt, JoltCommitments, JoltPreprocessing, JoltProof,
 };
+pub use jolt_core::subprotocols::grand_product::GrandProductConfig;
+pub use jolt_core::testing;
+pub use jolt_core::utils::{
+    errors::JoltError,
+    json,
+    throttle::ThrottleConfig,
+    transcript::{TranscriptNamespace, TranscriptVersion},
+};
 pub use tracer;
+
+const _: () = assert!(crate::log::LOG_ECALL == common::constants::LOG_ECALL);
+const _: () = assert!(crate::alloc::HEAP_ECALL == common::constants::HEAP_ECALL);
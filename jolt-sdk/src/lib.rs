@@ -9,6 +9,32 @@ pub use postcard;
 pub mod host_utils;
 #[cfg(feature = "host")]
 pub use host_utils::*;
+#[cfg(feature = "host")]
+pub mod output;
+#[cfg(feature = "host")]
+pub use output::OutputView;
 
 pub mod alloc;
 pub use alloc::*;
+
+pub mod arena;
+
+pub mod blob;
+pub use blob::*;
+
+pub mod checkpoint;
+pub use checkpoint::*;
+
+pub mod panic;
+pub use panic::*;
+
+pub mod log;
+
+#[cfg(feature = "beacon")]
+pub mod beacon;
+
+#[cfg(feature = "disclosure")]
+pub mod disclosure;
+
+#[cfg(feature = "state")]
+pub mod state;
@@ -0,0 +1,61 @@
+//! Guest debug log.
+//!
+//! [`print!`](crate::print) and [`println!`](crate::println) write to a log that the host
+//! collects while tracing the guest, and prints once tracing finishes (see
+//! `Program::trace`). The log is not part of the program I/O, so it is neither committed to nor
+//! visible to the verifier. Writing to the log costs no cycles beyond formatting the message.
+//!
+//! ```ignore
+//! jolt::println!("state after round {}: {:?}", round, state);
+//! ```
+
+use core::fmt::{self, Write};
+
+/// Value of `a7` for the environment call that appends to the log. Must match
+/// `common::constants::LOG_ECALL`.
+pub const LOG_ECALL: u32 = 0x4A4F_4C4C;
+
+struct Log;
+
+impl Write for Log {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        #[cfg(target_arch = "riscv32")]
+        unsafe {
+            core::arch::asm!(
+                "ecall",
+                in("a7") LOG_ECALL,
+                in("a0") s.as_ptr(),
+                in("a1") s.len(),
+            );
+        }
+        #[cfg(all(not(target_arch = "riscv32"), feature = "host"))]
+        std::print!("{s}");
+        #[cfg(all(not(target_arch = "riscv32"), not(feature = "host")))]
+        let _ = s;
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn write_log(args: fmt::Arguments) {
+    let _ = Log.write_fmt(args);
+}
+
+/// Like `std::print!`, but writes to the guest's debug log.
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::log::write_log(format_args!($($arg)*))
+    };
+}
+
+/// Like `std::println!`, but writes to the guest's debug log.
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::log::write_log(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::log::write_log(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
@@ -0,0 +1,110 @@
+//! Zero-copy access to a proven program's outputs.
+//!
+//! The generated `prove_{fn}` stubs deserialize the guest's return value into an owned type.
+//! For large outputs (images, serialized trees, ...) that copy can be avoided by calling
+//! `prove_{fn}_raw` instead, which returns only the proof, and then reading the outputs
+//! through an [`OutputView`] that borrows the output tape recorded in the proof's program I/O:
+//!
+//! ```ignore
+//! let proof = guest::prove_render_raw(program, preprocessing, scene)?;
+//! let image: &[u8] = OutputView::new(&proof).deserialize().unwrap();
+//! ```
+//!
+//! The outputs of a function marked `#[jolt::provable(checkpoints)]` start with its checkpoint
+//! journal, so they are read with [`OutputView::with_checkpoints`] instead.
+
+use serde::Deserialize;
+
+use crate::checkpoint::CHECKPOINT_DIGEST_SIZE;
+use crate::JoltHyperKZGProof;
+
+/// A borrowed view over the outputs of a proven program, valid for as long as the proof.
+#[derive(Clone, Copy, Debug)]
+pub struct OutputView<'a> {
+    checkpoints: &'a [u8],
+    return_bytes: &'a [u8],
+}
+
+impl<'a> OutputView<'a> {
+    /// A view over the outputs of a function without checkpoints.
+    pub fn new(proof: &'a JoltHyperKZGProof) -> Self {
+        Self::from_outputs(&proof.proof.program_io.outputs)
+    }
+
+    pub fn from_outputs(outputs: &'a [u8]) -> Self {
+        Self {
+            checkpoints: &[],
+            return_bytes: outputs,
+        }
+    }
+
+    /// A view over the outputs of a function marked `#[jolt::provable(checkpoints)]`. Returns
+    /// `None` if the checkpoint journal at the start of the outputs is truncated.
+    pub fn with_checkpoints(proof: &'a JoltHyperKZGProof) -> Option<Self> {
+        Self::from_outputs_with_checkpoints(&proof.proof.program_io.outputs)
+    }
+
+    pub fn from_outputs_with_checkpoints(outputs: &'a [u8]) -> Option<Self> {
+        let (checkpoints, return_bytes) = crate::checkpoint::split_checkpoint_journal(outputs)?;
+        Some(Self {
+            checkpoints,
+            return_bytes,
+        })
+    }
+
+    /// Digests committed with [`commit_checkpoint`](crate::commit_checkpoint), in order. Always
+    /// empty for a view created without checkpoints.
+    pub fn checkpoints(&self) -> impl ExactSizeIterator<Item = &'a [u8; CHECKPOINT_DIGEST_SIZE]> {
+        self.checkpoints
+            .chunks_exact(CHECKPOINT_DIGEST_SIZE)
+            .map(|digest| digest.try_into().unwrap())
+    }
+
+    /// The postcard-serialized return value.
+    pub fn return_bytes(&self) -> &'a [u8] {
+        self.return_bytes
+    }
+
+    /// Deserializes the return value. Borrowed types such as `&[u8]` and `&str` point directly
+    /// into the proof's output tape rather than being copied.
+    pub fn deserialize<T: Deserialize<'a>>(&self) -> postcard::Result<T> {
+        postcard::from_bytes(self.return_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_outputs() {
+        let digest = [7u8; CHECKPOINT_DIGEST_SIZE];
+        let mut outputs = 1u32.to_le_bytes().to_vec();
+        outputs.extend_from_slice(&digest);
+        outputs.extend(postcard::to_stdvec(&b"hello".as_slice()).unwrap());
+
+        let view = OutputView::from_outputs_with_checkpoints(&outputs).unwrap();
+        assert_eq!(view.checkpoints().collect::<Vec<_>>(), vec![&digest]);
+
+        let bytes: &[u8] = view.deserialize().unwrap();
+        assert_eq!(bytes, b"hello");
+        assert_eq!(
+            bytes.as_ptr_range(),
+            outputs[outputs.len() - 5..].as_ptr_range()
+        );
+
+        // Truncated journal
+        assert!(OutputView::from_outputs_with_checkpoints(&outputs[..8]).is_none());
+    }
+
+    #[test]
+    fn outputs_without_checkpoints() {
+        let outputs = postcard::to_stdvec(&b"hello".as_slice()).unwrap();
+
+        let view = OutputView::from_outputs(&outputs);
+        assert_eq!(view.checkpoints().len(), 0);
+        assert_eq!(view.return_bytes(), outputs.as_slice());
+        let bytes: &[u8] = view.deserialize().unwrap();
+        assert_eq!(bytes, b"hello");
+    }
+}
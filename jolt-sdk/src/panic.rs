@@ -0,0 +1,61 @@
+//! Guest panic reporting.
+//!
+//! Before setting the panic bit, the panic handler generated by `#[jolt::provable]` passes the
+//! panic message and location to the host with an environment call. The host then returns them
+//! as an error (see `Program::try_trace`) rather than just seeing a failed execution. Messages
+//! are truncated to [`PANIC_MESSAGE_BUFFER_SIZE`] bytes.
+
+use core::fmt::{self, Write};
+use core::panic::Location;
+
+pub const PANIC_MESSAGE_BUFFER_SIZE: usize = 1024;
+
+struct MessageBuffer {
+    bytes: [u8; PANIC_MESSAGE_BUFFER_SIZE],
+    len: usize,
+}
+
+impl Write for MessageBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = s.len().min(PANIC_MESSAGE_BUFFER_SIZE - self.len);
+        self.bytes[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        Ok(())
+    }
+}
+
+// Static rather than on the stack, since the panic may be a stack overflow. The guest halts after
+// reporting a panic, so it is only written once.
+static mut MESSAGE: MessageBuffer = MessageBuffer {
+    bytes: [0; PANIC_MESSAGE_BUFFER_SIZE],
+    len: 0,
+};
+
+/// Reports a panic to the host with the environment call numbered `ecall`. Called by the
+/// `#[jolt::provable]` panic handler.
+#[doc(hidden)]
+pub fn report_panic(ecall: u32, message: impl fmt::Display, location: Option<&Location>) {
+    let buffer = unsafe { &mut *core::ptr::addr_of_mut!(MESSAGE) };
+    buffer.len = 0;
+    let _ = write!(buffer, "{message}");
+    let message = &buffer.bytes[..buffer.len];
+    let (file, line, column) = location.map_or(("<unknown>", 0, 0), |location| {
+        (location.file(), location.line(), location.column())
+    });
+
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        core::arch::asm!(
+            "ecall",
+            in("a7") ecall,
+            in("a0") message.as_ptr(),
+            in("a1") message.len(),
+            in("a2") file.as_ptr(),
+            in("a3") file.len(),
+            in("a4") line,
+            in("a5") column,
+        );
+    }
+    #[cfg(not(target_arch = "riscv32"))]
+    let _ = (ecall, message, file, line, column);
+}
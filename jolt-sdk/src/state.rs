@@ -0,0 +1,452 @@
+//! Persistent guest state, authenticated with a sparse Merkle tree.
+//!
+//! A guest whose state survives across proofs (e.g. a rollup's accounts) doesn't take the whole
+//! state as input. It takes the state's root and [`StateWitness`]es for the keys it accesses,
+//! and opens a [`StateStore`], which checks the witnesses against the root. The guest then reads
+//! and writes the witnessed keys, and returns the store's [`StateTransition`], so that the proof
+//! exposes the old and new roots as public outputs. The host keeps the full state in a
+//! [`StateTree`], which produces the witnesses, and applies the guest's writes to it to get the
+//! state the next proof starts from:
+//!
+//! ```ignore
+//! // guest
+//! pub fn transfer(store: &mut StateStore, from: StateKey, to: StateKey, amount: u64) {
+//!     let balance = |store: &StateStore, key| -> u64 {
+//!         store.get(&key).unwrap().map_or(0, |bytes| postcard::from_bytes(bytes).unwrap())
+//!     };
+//!     let (from_balance, to_balance) = (balance(store, from), balance(store, to));
+//!     assert!(from_balance >= amount);
+//!     store.insert(from, postcard::to_allocvec(&(from_balance - amount)).unwrap()).unwrap();
+//!     store.insert(to, postcard::to_allocvec(&(to_balance + amount)).unwrap()).unwrap();
+//! }
+//!
+//! #[jolt::provable]
+//! fn step(root: StateRoot, witnesses: Vec<StateWitness>, from: StateKey, to: StateKey, amount: u64) -> StateTransition {
+//!     let mut store = StateStore::open(root, witnesses).unwrap();
+//!     transfer(&mut store, from, to, amount);
+//!     store.transition()
+//! }
+//!
+//! // host
+//! let witnesses = tree.witnesses(&[from, to]);
+//! let (transition, proof) = prove_step(tree.root(), witnesses.clone(), from, to, 10);
+//! let mut store = StateStore::open(tree.root(), witnesses).unwrap();
+//! guest::transfer(&mut store, from, to, 10);
+//! tree.apply(&store);
+//! assert_eq!(tree.root(), transition.new_root);
+//! ```
+//!
+//! The tree has a leaf for each of the 2^256 keys, so every witnessed key costs 256 hashes to
+//! check when the store is opened, and every write costs another 256. Application keys of other
+//! types are mapped to keys with [`state_key`]. Empty subtrees hash to zero, so witnesses only
+//! carry the siblings of a path that aren't empty.
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const STATE_TREE_DEPTH: usize = 256;
+const KEY_DOMAIN: &[u8] = b"jolt-state-key";
+const LEAF_TAG: u8 = 0;
+const NODE_TAG: u8 = 1;
+/// The hash of an empty subtree, at any height. Not the hash of any leaf or node.
+const EMPTY: [u8; 32] = [0; 32];
+
+pub type StateKey = [u8; 32];
+pub type StateRoot = [u8; 32];
+
+/// The root of a state with no keys.
+pub const EMPTY_STATE_ROOT: StateRoot = EMPTY;
+
+/// The value of a key in a state with root `root`, and the path that opens it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateWitness {
+    pub key: StateKey,
+    /// `None` if the key isn't in the state.
+    pub value: Option<Vec<u8>>,
+    /// Bit `h` is set if the sibling at height `h` (0 being the leaf's sibling) isn't empty.
+    pub nonempty_siblings: [u8; STATE_TREE_DEPTH / 8],
+    /// The siblings that aren't empty, from the leaf up to the root.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// The state roots a guest started from and ended with, returned as its public output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub old_root: StateRoot,
+    pub new_root: StateRoot,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateError {
+    /// A witness doesn't open to the state root.
+    InvalidWitness(StateKey),
+    /// A key was witnessed more than once.
+    DuplicateWitness(StateKey),
+    /// A key was accessed without being witnessed.
+    NotWitnessed(StateKey),
+}
+
+/// The witnessed keys of a state, with their current values.
+#[derive(Clone, Debug)]
+pub struct StateStore {
+    old_root: StateRoot,
+    root: StateRoot,
+    entries: BTreeMap<StateKey, Entry>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    value: Option<Vec<u8>>,
+    /// From the leaf up to the root.
+    siblings: Vec<[u8; 32]>,
+}
+
+impl StateStore {
+    /// Checks each of `witnesses` against `root`.
+    pub fn open(root: StateRoot, witnesses: Vec<StateWitness>) -> Result<Self, StateError> {
+        let mut entries = BTreeMap::new();
+        for witness in witnesses {
+            let siblings = expand_siblings(&witness)?;
+            let path = path_hashes(&witness.key, witness.value.as_deref(), &siblings);
+            if path[STATE_TREE_DEPTH] != root {
+                return Err(StateError::InvalidWitness(witness.key));
+            }
+            let entry = Entry {
+                value: witness.value,
+                siblings,
+            };
+            if entries.insert(witness.key, entry).is_some() {
+                return Err(StateError::DuplicateWitness(witness.key));
+            }
+        }
+        Ok(Self {
+            old_root: root,
+            root,
+            entries,
+        })
+    }
+
+    /// The value of `key`, or `None` if it isn't in the state.
+    pub fn get(&self, key: &StateKey) -> Result<Option<&[u8]>, StateError> {
+        self.entries
+            .get(key)
+            .map(|entry| entry.value.as_deref())
+            .ok_or(StateError::NotWitnessed(*key))
+    }
+
+    pub fn insert(&mut self, key: StateKey, value: Vec<u8>) -> Result<(), StateError> {
+        self.update(key, Some(value))
+    }
+
+    pub fn remove(&mut self, key: &StateKey) -> Result<(), StateError> {
+        self.update(*key, None)
+    }
+
+    /// The root of the state with the writes made so far.
+    pub fn root(&self) -> StateRoot {
+        self.root
+    }
+
+    pub fn transition(&self) -> StateTransition {
+        StateTransition {
+            old_root: self.old_root,
+            new_root: self.root,
+        }
+    }
+
+    /// The witnessed keys and their current values, in key order.
+    pub fn entries(&self) -> impl Iterator<Item = (&StateKey, Option<&[u8]>)> {
+        self.entries
+            .iter()
+            .map(|(key, entry)| (key, entry.value.as_deref()))
+    }
+
+    /// Sets the value of `key`, and the sibling that its new path is for every other witnessed
+    /// key: the node at the height where their paths split.
+    fn update(&mut self, key: StateKey, value: Option<Vec<u8>>) -> Result<(), StateError> {
+        let entry = self
+            .entries
+            .get_mut(&key)
+            .ok_or(StateError::NotWitnessed(key))?;
+        entry.value = value;
+        let path = path_hashes(&key, entry.value.as_deref(), &entry.siblings);
+        for (other_key, other) in self.entries.iter_mut() {
+            if let Some(height) = split_height(&key, other_key) {
+                other.siblings[height] = path[height];
+            }
+        }
+        self.root = path[STATE_TREE_DEPTH];
+        Ok(())
+    }
+}
+
+/// The full state, kept by the host.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTree {
+    leaves: BTreeMap<StateKey, Vec<u8>>,
+}
+
+impl StateTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &StateKey) -> Option<&[u8]> {
+        self.leaves.get(key).map(Vec::as_slice)
+    }
+
+    pub fn insert(&mut self, key: StateKey, value: Vec<u8>) {
+        self.leaves.insert(key, value);
+    }
+
+    pub fn remove(&mut self, key: &StateKey) {
+        self.leaves.remove(key);
+    }
+
+    /// Applies the values of `store`'s keys, e.g. after running the guest's state transition
+    /// natively.
+    pub fn apply(&mut self, store: &StateStore) {
+        for (key, value) in store.entries() {
+            match value {
+                Some(value) => self.insert(*key, value.to_vec()),
+                None => self.remove(key),
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn root(&self) -> StateRoot {
+        let leaves: Vec<_> = self.leaves.iter().collect();
+        subtree_hash(&leaves, 0)
+    }
+
+    /// Witnesses for `keys` against [`Self::root`].
+    pub fn witnesses(&self, keys: &[StateKey]) -> Vec<StateWitness> {
+        let leaves: Vec<_> = self.leaves.iter().collect();
+        keys.iter()
+            .map(|key| {
+                // Walk down from the root, hashing the subtree on the other side of each split
+                let mut siblings = [EMPTY; STATE_TREE_DEPTH];
+                let mut subtree = leaves.as_slice();
+                for bit_index in 0..STATE_TREE_DEPTH {
+                    let split = subtree.partition_point(|(k, _)| bit(k, bit_index) == 0);
+                    let (left, right) = subtree.split_at(split);
+                    let (own, other) = match bit(key, bit_index) {
+                        0 => (left, right),
+                        _ => (right, left),
+                    };
+                    siblings[STATE_TREE_DEPTH - 1 - bit_index] = subtree_hash(other, bit_index + 1);
+                    subtree = own;
+                }
+
+                let mut nonempty_siblings = [0u8; STATE_TREE_DEPTH / 8];
+                for (height, sibling) in siblings.iter().enumerate() {
+                    if *sibling != EMPTY {
+                        nonempty_siblings[height / 8] |= 1 << (height % 8);
+                    }
+                }
+                StateWitness {
+                    key: *key,
+                    value: self.leaves.get(key).cloned(),
+                    nonempty_siblings,
+                    siblings: siblings.into_iter().filter(|s| *s != EMPTY).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Maps an application key (e.g. an account address) to a key of the state tree.
+pub fn state_key(bytes: &[u8]) -> StateKey {
+    Sha256::new()
+        .chain_update(KEY_DOMAIN)
+        .chain_update(bytes)
+        .finalize()
+        .into()
+}
+
+fn expand_siblings(witness: &StateWitness) -> Result<Vec<[u8; 32]>, StateError> {
+    let mut nonempty = witness.siblings.iter();
+    let siblings = (0..STATE_TREE_DEPTH)
+        .map(
+            |height| match (witness.nonempty_siblings[height / 8] >> (height % 8)) & 1 {
+                0 => Some(EMPTY),
+                _ => nonempty.next().copied(),
+            },
+        )
+        .collect::<Option<Vec<_>>>()
+        .ok_or(StateError::InvalidWitness(witness.key))?;
+    if nonempty.next().is_some() {
+        return Err(StateError::InvalidWitness(witness.key));
+    }
+    Ok(siblings)
+}
+
+/// The hashes of the nodes on `key`'s path, from its leaf (height 0) up to the root.
+fn path_hashes(key: &StateKey, value: Option<&[u8]>, siblings: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    let mut path = Vec::with_capacity(STATE_TREE_DEPTH + 1);
+    path.push(value.map_or(EMPTY, |value| leaf_hash(key, value)));
+    for (height, sibling) in siblings.iter().enumerate() {
+        let node = path[height];
+        path.push(match bit(key, STATE_TREE_DEPTH - 1 - height) {
+            0 => node_hash(&node, sibling),
+            _ => node_hash(sibling, &node),
+        });
+    }
+    path
+}
+
+/// The height of the nodes at which the paths of two different keys split, i.e. at which each
+/// is the other's sibling.
+fn split_height(a: &StateKey, b: &StateKey) -> Option<usize> {
+    let bit_index = (0..STATE_TREE_DEPTH).find(|&i| bit(a, i) != bit(b, i))?;
+    Some(STATE_TREE_DEPTH - 1 - bit_index)
+}
+
+/// The hash of the subtree below the first `bit_index` bits shared by the keys of `leaves`.
+fn subtree_hash(leaves: &[(&StateKey, &Vec<u8>)], bit_index: usize) -> [u8; 32] {
+    match leaves {
+        [] => EMPTY,
+        [(key, value)] if bit_index == STATE_TREE_DEPTH => leaf_hash(key, value),
+        _ => {
+            let split = leaves.partition_point(|(k, _)| bit(k, bit_index) == 0);
+            let (left, right) = leaves.split_at(split);
+            node_hash(
+                &subtree_hash(left, bit_index + 1),
+                &subtree_hash(right, bit_index + 1),
+            )
+        }
+    }
+}
+
+/// Bit `i` of `key`, counting from the most significant bit of its first byte, which picks the
+/// root's child.
+fn bit(key: &StateKey, i: usize) -> u8 {
+    (key[i / 8] >> (7 - i % 8)) & 1
+}
+
+fn leaf_hash(key: &StateKey, value: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([LEAF_TAG])
+        .chain_update(key)
+        .chain_update(value)
+        .finalize()
+        .into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    if *left == EMPTY && *right == EMPTY {
+        return EMPTY;
+    }
+    Sha256::new()
+        .chain_update([NODE_TAG])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[cfg(all(test, feature = "host"))]
+mod tests {
+    use super::*;
+
+    fn test_tree(num_keys: u8) -> StateTree {
+        let mut tree = StateTree::new();
+        for i in 0..num_keys {
+            tree.insert(state_key(&[i]), alloc::vec![i; i as usize + 1]);
+        }
+        tree
+    }
+
+    #[test]
+    fn reads_and_writes() {
+        let mut tree = test_tree(10);
+        let old_root = tree.root();
+        let keys = [state_key(&[3]), state_key(&[7]), state_key(b"new")];
+        let mut store = StateStore::open(old_root, tree.witnesses(&keys)).unwrap();
+
+        assert_eq!(store.get(&keys[0]), Ok(Some(&[3u8; 4][..])));
+        assert_eq!(store.get(&keys[2]), Ok(None));
+        assert_eq!(
+            store.get(&state_key(&[4])),
+            Err(StateError::NotWitnessed(state_key(&[4])))
+        );
+
+        store.insert(keys[0], alloc::vec![30]).unwrap();
+        store.remove(&keys[1]).unwrap();
+        store.insert(keys[2], alloc::vec![1, 2, 3]).unwrap();
+        tree.apply(&store);
+        assert_eq!(
+            store.transition(),
+            StateTransition {
+                old_root,
+                new_root: tree.root()
+            }
+        );
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.get(&keys[1]), None);
+
+        // The new state is the one the next store opens
+        let store = StateStore::open(tree.root(), tree.witnesses(&keys)).unwrap();
+        assert_eq!(store.get(&keys[2]), Ok(Some(&[1u8, 2, 3][..])));
+    }
+
+    #[test]
+    fn empty_state() {
+        let mut tree = StateTree::new();
+        assert_eq!(tree.root(), EMPTY_STATE_ROOT);
+        let key = state_key(b"first");
+        let mut store = StateStore::open(EMPTY_STATE_ROOT, tree.witnesses(&[key])).unwrap();
+        store.insert(key, alloc::vec![1]).unwrap();
+        tree.apply(&store);
+        assert_eq!(store.root(), tree.root());
+
+        store.remove(&key).unwrap();
+        assert_eq!(store.root(), EMPTY_STATE_ROOT);
+    }
+
+    #[test]
+    fn invalid_witnesses() {
+        let tree = test_tree(5);
+        let root = tree.root();
+        let key = state_key(&[2]);
+
+        let mut witness = tree.witnesses(&[key]).remove(0);
+        witness.value = Some(alloc::vec![0]);
+        assert_eq!(
+            StateStore::open(root, alloc::vec![witness]).err(),
+            Some(StateError::InvalidWitness(key))
+        );
+
+        // Claiming a present key is absent
+        let mut witness = tree.witnesses(&[key]).remove(0);
+        witness.value = None;
+        assert_eq!(
+            StateStore::open(root, alloc::vec![witness]).err(),
+            Some(StateError::InvalidWitness(key))
+        );
+
+        let mut witness = tree.witnesses(&[key]).remove(0);
+        witness.siblings.pop();
+        assert_eq!(
+            StateStore::open(root, alloc::vec![witness]).err(),
+            Some(StateError::InvalidWitness(key))
+        );
+
+        assert_eq!(
+            StateStore::open(root, tree.witnesses(&[key, key])).err(),
+            Some(StateError::DuplicateWitness(key))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use ark_bn254::{Bn254, Fr};
+use eyre::{bail, eyre, Result};
+use jolt_core::host::Program;
+use jolt_core::jolt::vm::rv32i_vm::{RV32IJoltVM, C, M};
+use jolt_core::jolt::vm::Jolt;
+use jolt_core::poly::commitment::hyperkzg::HyperKZG;
+
+use crate::build_wasm::{extract_provable_functions, is_std};
+
+#[allow(clippy::upper_case_acronyms)]
+type PCS = HyperKZG<Bn254>;
+
+/// Traces the provable function `func` of the project in the current directory on `input`, and
+/// prints the range of values in each of its committed witness columns. `input` is the
+/// function's arguments serialized with `postcard`, in hex.
+pub fn audit_witness(func: Option<String>, input: Option<String>) -> Result<()> {
+    let functions = extract_provable_functions();
+    let function = match func {
+        Some(func) => functions
+            .into_iter()
+            .find(|function| function.func_name == func)
+            .ok_or_else(|| eyre!("no provable function named `{func}` in guest/src/lib.rs"))?,
+        None if functions.len() == 1 => functions.into_iter().next().unwrap(),
+        None => {
+            let names: Vec<_> = functions.iter().map(|f| f.func_name.as_str()).collect();
+            bail!(
+                "the guest has {} provable functions, choose one with --func: {}",
+                names.len(),
+                names.join(", ")
+            );
+        }
+    };
+
+    let attributes = &function.attributes;
+    let mut program = Program::new("guest");
+    program.set_func(&function.func_name);
+    program.set_std(is_std().unwrap_or(false));
+    program.set_memory_size(attributes.memory_size);
+    program.set_stack_size(attributes.stack_size);
+    program.set_max_input_size(attributes.max_input_size);
+    program.set_max_output_size(attributes.max_output_size);
+    if let Some(max_cycles) = attributes.max_cycles {
+        program.set_max_cycles(max_cycles);
+    }
+    if let Some(input) = input {
+        program.set_input_bytes(&decode_hex(&input)?);
+    }
+
+    let (bytecode, memory_init) = program.decode();
+    let (io_device, trace) = program.try_trace()?;
+    let audit = <RV32IJoltVM as Jolt<Fr, PCS, C, M>>::audit_witness(
+        bytecode,
+        memory_init,
+        &io_device,
+        trace,
+    );
+    print!("{audit}");
+    Ok(())
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.is_ascii() || hex.len() % 2 != 0 {
+        bail!("input must be an even number of hex digits");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| eyre!("input is not valid hex"))
+        })
+        .collect()
+}
@@ -19,7 +19,7 @@ struct DecodedData {
     memory_init: Vec<(u64, u8)>,
 }
 
-struct FunctionAttributes {
+pub struct FunctionAttributes {
     pub func_name: String,
     pub attributes: Attributes,
 }
@@ -52,7 +52,7 @@ fn preprocess_and_save(func_name: &str, attributes: &Attributes, is_std: bool) -
     Ok(())
 }
 
-fn extract_provable_functions() -> Vec<FunctionAttributes> {
+pub fn extract_provable_functions() -> Vec<FunctionAttributes> {
     let content = fs::read_to_string("guest/src/lib.rs").expect("Unable to read file");
     let syntax: syn::File = syn::parse_file(&content).expect("Unable to parse file");
 
@@ -99,7 +99,7 @@ fn get_project_name() -> Option<String> {
     })
 }
 
-fn is_std() -> Option<bool> {
+pub fn is_std() -> Option<bool> {
     let content = fs::read_to_string("guest/Cargo.toml").expect("Failed to read Cargo.toml");
     let doc = content
         .parse::<DocumentMut>()
@@ -1,3 +1,4 @@
+mod audit_witness;
 mod build_wasm;
 
 use std::{
@@ -10,8 +11,9 @@ use eyre::Result;
 use rand::prelude::SliceRandom;
 use sysinfo::System;
 
+use audit_witness::audit_witness;
 use build_wasm::{build_wasm, modify_cargo_toml};
-use jolt_core::host::toolchain;
+use jolt_core::host::{hermetic::HermeticToolchain, toolchain};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -31,17 +33,36 @@ enum Command {
         wasm: bool,
     },
     /// Installs the required RISC-V toolchains for Rust
-    InstallToolchain,
+    InstallToolchain {
+        /// Also installs the pinned toolchain used for hermetic guest builds
+        #[arg(long)]
+        hermetic: bool,
+    },
     /// Handles preprocessing and generates WASM compatible files
     BuildWasm,
+    /// Traces a provable function and reports the range of values in each committed witness
+    /// column, and which integer type each column would fit in
+    AuditWitness {
+        /// The provable function to trace; may be omitted if the guest only has one
+        #[arg(short, long)]
+        func: Option<String>,
+        /// The function's arguments, serialized with postcard, in hex
+        #[arg(short, long)]
+        input: Option<String>,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
     match cli.command {
         Command::New { name, wasm } => create_project(name, wasm),
-        Command::InstallToolchain => install_toolchain(),
+        Command::InstallToolchain { hermetic } => install_toolchain(hermetic),
         Command::BuildWasm => build_wasm(),
+        Command::AuditWitness { func, input } => {
+            if let Err(err) = audit_witness(func, input) {
+                panic!("witness audit failed: {}", err);
+            }
+        }
     }
 }
 
@@ -54,10 +75,15 @@ fn create_project(name: String, wasm: bool) {
     }
 }
 
-fn install_toolchain() {
+fn install_toolchain(hermetic: bool) {
     if let Err(err) = toolchain::install_toolchain() {
         panic!("toolchain install failed: {}", err);
     }
+    if hermetic {
+        if let Err(err) = HermeticToolchain::install() {
+            panic!("hermetic toolchain install failed: {}", err);
+        }
+    }
     display_welcome();
 }
 
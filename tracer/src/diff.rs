@@ -0,0 +1,434 @@
+//! Differential testing of the emulator against a reference RISC-V implementation.
+//!
+//! [`diff_test`] traces an ELF with the in-crate emulator, runs the same ELF under Spike, and
+//! compares the two instruction by instruction: the program counter, the destination register
+//! write, and the memory access of every retired instruction. The first divergence is reported
+//! along with the instructions that preceded it.
+//!
+//! Guests are bare-metal programs that read their inputs from, and write their outputs to,
+//! Jolt's I/O region below RAM, so the reference has to map that region as plain memory. Spike
+//! can (see [`Reference::Spike`]); QEMU can't, in either user mode (which only runs Linux
+//! binaries) or system mode (whose machines put devices there), so it isn't supported.
+//!
+//! Spike can't be given the program's inputs, so both sides run without inputs and guest reads
+//! of the input region observe zeros in either implementation. The reference emulator is
+//! stopped once every instruction in the emulator's trace has been compared, since guests
+//! terminate by spinning forever.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Lines},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+};
+
+use common::{
+    constants::RAM_START_ADDRESS,
+    rv_trace::{Blobs, MemoryConfig, MemoryLayout, MemoryState, RVTraceRow, RV32IM},
+};
+
+/// Number of preceding instructions included in a [`Divergence`].
+const CONTEXT_LENGTH: usize = 8;
+/// Amount of memory above the I/O region mapped into Spike.
+const REFERENCE_MEMORY_SIZE: u64 = 1 << 30;
+/// Upper bound on reference instructions (e.g. a boot ROM) skipped before the entry point.
+const MAX_PRELUDE_STEPS: usize = 1 << 10;
+
+/// A reference implementation to compare the emulator against, given by the path of its
+/// executable.
+#[derive(Clone, Debug)]
+pub enum Reference {
+    /// The Spike ISA simulator, run with `--log-commits` and the I/O region and RAM mapped as
+    /// one contiguous memory.
+    Spike(PathBuf),
+}
+
+impl Reference {
+    pub fn spike() -> Self {
+        Self::Spike(PathBuf::from("spike"))
+    }
+
+    fn spawn(&self, elf: &PathBuf) -> io::Result<Child> {
+        let mut command = match self {
+            Reference::Spike(spike) => {
+                // Map the Jolt I/O region and RAM as one contiguous memory.
+                let layout = MemoryLayout::new(&MemoryConfig::default());
+                let base = RAM_START_ADDRESS - layout.ram_witness_offset;
+                let size = layout.ram_witness_offset + REFERENCE_MEMORY_SIZE;
+                let mut command = Command::new(spike);
+                command
+                    .arg("--isa=rv32im")
+                    .arg("--log-commits")
+                    .arg(format!("-m{:#x}:{:#x}", base, size));
+                command
+            }
+        };
+        command
+            .arg(elf)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+    }
+}
+
+/// A memory access reported by the reference, with the stored value for writes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryAccess {
+    pub address: u64,
+    pub value: Option<u64>,
+}
+
+/// The architectural effects of one instruction retired by the reference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceStep {
+    pub pc: u64,
+    /// Integer registers written, as (register, value).
+    pub register_writes: Vec<(u64, u64)>,
+    pub memory_access: Option<MemoryAccess>,
+    /// The reference's log output for this instruction.
+    pub log: String,
+}
+
+/// The first instruction at which the emulator and the reference disagree.
+#[derive(Debug)]
+pub struct Divergence {
+    /// Index of the diverging instruction among retired instructions.
+    pub index: usize,
+    pub mismatch: String,
+    /// The emulator's trace row for the diverging instruction.
+    pub row: RVTraceRow,
+    pub reference: ReferenceStep,
+    /// Up to [`CONTEXT_LENGTH`] instructions retired immediately before the divergence.
+    pub context: Vec<RVTraceRow>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "divergence at instruction {} (pc {:#x}): {}",
+            self.index, self.row.instruction.address, self.mismatch
+        )?;
+        writeln!(f, "preceding instructions:")?;
+        for row in self.context.iter() {
+            writeln!(
+                f,
+                "  {:#010x} {:?} {:?}",
+                row.instruction.address, row.instruction.opcode, row.register_state
+            )?;
+        }
+        writeln!(f, "emulator:  {:?}", self.row)?;
+        write!(f, "reference: {}", self.reference.log)
+    }
+}
+
+#[derive(Debug)]
+pub enum DiffTestError {
+    /// The reference could not be launched or its output could not be read.
+    Io(io::Error),
+    /// The reference stopped producing instructions before the emulator's trace ended.
+    ReferenceEnded {
+        compared: usize,
+    },
+    Divergence(Box<Divergence>),
+}
+
+impl fmt::Display for DiffTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffTestError::Io(e) => write!(f, "failed to run reference: {}", e),
+            DiffTestError::ReferenceEnded { compared } => write!(
+                f,
+                "reference stopped after {} instructions, before the emulator did",
+                compared
+            ),
+            DiffTestError::Divergence(divergence) => write!(f, "{}", divergence),
+        }
+    }
+}
+
+impl std::error::Error for DiffTestError {}
+
+impl From<io::Error> for DiffTestError {
+    fn from(e: io::Error) -> Self {
+        DiffTestError::Io(e)
+    }
+}
+
+/// Runs `elf` under both the emulator and `reference`, comparing architectural state after
+/// every retired instruction. Returns the number of instructions compared.
+#[tracing::instrument(skip_all)]
+pub fn diff_test(elf: &PathBuf, reference: Reference) -> Result<usize, DiffTestError> {
+    let (rows, ..) = crate::trace(elf, &[], &Blobs::default(), &MemoryConfig::default());
+
+    let mut child = reference.spawn(elf)?;
+    let stderr = BufReader::new(child.stderr.take().unwrap());
+    let steps = SpikeLog {
+        lines: stderr.lines(),
+    };
+
+    let result = compare_trace(&rows, steps);
+    // The reference never halts on its own; stop it once the trace has been compared.
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+fn compare_trace(
+    rows: &[RVTraceRow],
+    steps: impl Iterator<Item = io::Result<ReferenceStep>>,
+) -> Result<usize, DiffTestError> {
+    let entry = match rows.first() {
+        Some(row) => row.instruction.address,
+        None => return Ok(0),
+    };
+    let mut steps = steps
+        .enumerate()
+        .skip_while(|(skipped, step)| {
+            *skipped < MAX_PRELUDE_STEPS && matches!(step, Ok(step) if step.pc != entry)
+        })
+        .map(|(_, step)| step);
+
+    for (index, row) in rows.iter().enumerate() {
+        let step = match steps.next() {
+            Some(step) => step?,
+            None => return Err(DiffTestError::ReferenceEnded { compared: index }),
+        };
+        if let Err(mismatch) = compare_step(row, &step) {
+            return Err(DiffTestError::Divergence(Box::new(Divergence {
+                index,
+                mismatch,
+                row: row.clone(),
+                reference: step,
+                context: rows[index.saturating_sub(CONTEXT_LENGTH)..index].to_vec(),
+            })));
+        }
+    }
+    Ok(rows.len())
+}
+
+fn compare_step(row: &RVTraceRow, step: &ReferenceStep) -> Result<(), String> {
+    let pc = row.instruction.address;
+    if step.pc != pc {
+        return Err(format!(
+            "reference executed pc {:#x}, emulator executed pc {:#x}",
+            step.pc, pc
+        ));
+    }
+
+    let rd = row.instruction.rd.filter(|rd| *rd != 0);
+    if let (Some(rd), Some(value)) = (rd, row.register_state.rd_post_val) {
+        let expected = step
+            .register_writes
+            .iter()
+            .find(|(register, _)| *register == rd)
+            .map(|(_, value)| *value);
+        match expected {
+            Some(expected) if expected == value => {}
+            Some(expected) => {
+                return Err(format!(
+                    "x{} is {:#x} in the reference but {:#x} in the emulator",
+                    rd, expected, value
+                ))
+            }
+            None => return Err(format!("reference did not write x{}", rd)),
+        }
+    }
+    if let Some((register, value)) = step
+        .register_writes
+        .iter()
+        .find(|(register, _)| *register != 0 && Some(*register) != rd)
+    {
+        return Err(format!(
+            "reference wrote x{} = {:#x}, which the emulator did not",
+            register, value
+        ));
+    }
+
+    if let Some(access) = &step.memory_access {
+        let mask = match row.instruction.opcode {
+            RV32IM::SB => 0xff,
+            RV32IM::SH => 0xffff,
+            _ => 0xffff_ffff,
+        };
+        let matches = match (&row.memory_state, access.value) {
+            (Some(MemoryState::Read { address, .. }), None) => *address == access.address,
+            (
+                Some(MemoryState::Write {
+                    address,
+                    post_value,
+                }),
+                Some(value),
+            ) => *address == access.address && post_value & mask == value & mask,
+            _ => false,
+        };
+        if !matches {
+            return Err(format!(
+                "reference accessed memory {:?}, emulator accessed {:?}",
+                access, row.memory_state
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn parse_hex(token: &str) -> Option<u64> {
+    u64::from_str_radix(token.trim_start_matches("0x"), 16).ok()
+}
+
+/// Parses Spike's `--log-commits` output, e.g.
+/// `core   0: 3 0x80000010 (0x0002a303) x6  0x00000000 mem 0x80002000`.
+struct SpikeLog<R: BufRead> {
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> Iterator for SpikeLog<R> {
+    type Item = io::Result<ReferenceStep>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(step) = parse_spike_commit(&line) {
+                return Some(Ok(step));
+            }
+        }
+    }
+}
+
+fn parse_spike_commit(line: &str) -> Option<ReferenceStep> {
+    let commit = line.trim_start().strip_prefix("core")?;
+    let (_, commit) = commit.split_once(':')?;
+    let mut tokens = commit.split_whitespace().peekable();
+    // Skip the privilege level, if present.
+    let pc = loop {
+        let token = tokens.next()?;
+        if token.starts_with("0x") {
+            break parse_hex(token)?;
+        }
+    };
+    // The encoded instruction, e.g. `(0x0002a303)`
+    tokens.next()?;
+
+    let mut step = ReferenceStep {
+        pc,
+        register_writes: Vec::new(),
+        memory_access: None,
+        log: line.to_string(),
+    };
+    while let Some(token) = tokens.next() {
+        if token == "mem" {
+            let address = parse_hex(tokens.next()?)?;
+            let value = match tokens.peek() {
+                Some(value) if value.starts_with("0x") => parse_hex(tokens.next()?),
+                _ => None,
+            };
+            step.memory_access = Some(MemoryAccess { address, value });
+        } else if let Some(register) = token.strip_prefix('x').and_then(|r| r.parse().ok()) {
+            step.register_writes
+                .push((register, parse_hex(tokens.next()?)?));
+        } else {
+            // CSR and floating-point writes are followed by their value.
+            tokens.next();
+        }
+    }
+    Some(step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rv_trace::{ELFInstruction, RegisterState};
+
+    fn row(address: u64, opcode: RV32IM, rd: Option<u64>, rd_post_val: Option<u64>) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address,
+                opcode,
+                rs1: None,
+                rs2: None,
+                rd,
+                imm: None,
+                virtual_sequence_remaining: None,
+            },
+            register_state: RegisterState {
+                rs1_val: None,
+                rs2_val: None,
+                rd_post_val,
+            },
+            memory_state: None,
+            advice_value: None,
+        }
+    }
+
+    #[test]
+    fn spike_commit_parsing() {
+        let step =
+            parse_spike_commit("core   0: 3 0x80000010 (0x0002a303) x6  0x0000002a mem 0x80002000")
+                .unwrap();
+        assert_eq!(step.pc, 0x80000010);
+        assert_eq!(step.register_writes, vec![(6, 0x2a)]);
+        assert_eq!(
+            step.memory_access,
+            Some(MemoryAccess {
+                address: 0x80002000,
+                value: None
+            })
+        );
+
+        let store =
+            parse_spike_commit("core   0: 3 0x8000000c (0x0062a023) mem 0x80002000 0x0000002a")
+                .unwrap();
+        assert!(store.register_writes.is_empty());
+        assert_eq!(store.memory_access.unwrap().value, Some(0x2a));
+
+        assert!(parse_spike_commit("bbl loader").is_none());
+    }
+
+    #[test]
+    fn first_divergence_is_reported() {
+        let rows = vec![
+            row(0x80000000, RV32IM::ADDI, Some(10), Some(1)),
+            row(0x80000004, RV32IM::ADDI, Some(10), Some(2)),
+            row(0x80000008, RV32IM::ADDI, Some(11), Some(3)),
+        ];
+        let step = |pc, register, value| -> io::Result<ReferenceStep> {
+            Ok(ReferenceStep {
+                pc,
+                register_writes: vec![(register, value)],
+                memory_access: None,
+                log: String::new(),
+            })
+        };
+
+        let matching = vec![
+            step(0x1000, 5, 0x80000000),
+            step(0x80000000, 10, 1),
+            step(0x80000004, 10, 2),
+            step(0x80000008, 11, 3),
+        ];
+        assert_eq!(compare_trace(&rows, matching.into_iter()).unwrap(), 3);
+
+        let diverging = vec![
+            step(0x80000000, 10, 1),
+            step(0x80000004, 10, 5),
+            step(0x80000008, 11, 3),
+        ];
+        match compare_trace(&rows, diverging.into_iter()) {
+            Err(DiffTestError::Divergence(divergence)) => {
+                assert_eq!(divergence.index, 1);
+                assert_eq!(divergence.context.len(), 1);
+            }
+            other => panic!("expected a divergence, got {:?}", other),
+        }
+
+        let truncated = vec![step(0x80000000, 10, 1)];
+        assert!(matches!(
+            compare_trace(&rows, truncated.into_iter()),
+            Err(DiffTestError::ReferenceEnded { compared: 1 })
+        ));
+    }
+}
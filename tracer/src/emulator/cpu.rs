@@ -6,12 +6,13 @@ use std::convert::TryInto;
 use std::rc::Rc;
 use std::str::FromStr;
 
-use crate::trace::Tracer;
+use crate::trace::{Ecall, Tracer};
+use common::constants::{HEAP_ECALL, LOG_ECALL, PANIC_ECALL};
 use common::rv_trace::*;
 
 use self::fnv::FnvHashMap;
 
-use super::mmu::{AddressingMode, Mmu};
+use super::mmu::{AddressingMode, Mmu, DRAM_BASE};
 use super::terminal::Terminal;
 
 const CSR_CAPACITY: usize = 4096;
@@ -58,6 +59,9 @@ const CSR_TIME_ADDRESS: u16 = 0xc01;
 const _CSR_INSERT_ADDRESS: u16 = 0xc02;
 const _CSR_MHARTID_ADDRESS: u16 = 0xf14;
 
+/// Longest string the host reads from guest memory on behalf of an environment call.
+const MAX_GUEST_STRING_SIZE: u64 = 4096;
+
 const MIP_MEIP: u64 = 0x800;
 pub const MIP_MTIP: u64 = 0x080;
 pub const MIP_MSIP: u64 = 0x008;
@@ -83,9 +87,27 @@ pub struct Cpu {
     _dump_flag: bool,
     decode_cache: DecodeCache,
     unsigned_data_mask: u64,
+    custom_instruction_handler: Option<CustomInstructionHandler>,
+    /// Lowest valid stack address, if known. The stack pointer dropping below it means the
+    /// guest has overflowed its configured `stack_size`.
+    stack_bottom: Option<u64>,
+    /// Panic reported by the guest with a [`PANIC_ECALL`].
+    guest_panic: Option<GuestPanic>,
+    /// Fault that stopped the guest. The guest has no trap handler, so it can't recover.
+    guest_fault: Option<GuestFault>,
+    /// Bytes the guest wrote to its debug log with [`LOG_ECALL`]s.
+    guest_log: Vec<u8>,
+    /// Heap usage last reported by the guest allocator with a [`HEAP_ECALL`].
+    heap_stats: Option<HeapStats>,
     pub tracer: Rc<Tracer>,
 }
 
+/// Executes an application-defined `custom-0` instruction. Given the decoded instruction (see
+/// [`ELFInstruction::custom_funct`]) and its source register values, returns the value written to
+/// `rd`, or `None` if the instruction is not recognized.
+pub type CustomInstructionHandler =
+    fn(instruction: &ELFInstruction, rs1_val: u64, rs2_val: u64) -> Option<u64>;
+
 #[derive(Clone)]
 pub enum Xlen {
     Bit32,
@@ -244,6 +266,12 @@ impl Cpu {
             _dump_flag: false,
             decode_cache: DecodeCache::new(),
             unsigned_data_mask: 0xffffffffffffffff,
+            custom_instruction_handler: None,
+            stack_bottom: None,
+            guest_panic: None,
+            guest_fault: None,
+            guest_log: Vec::new(),
+            heap_stats: None,
             tracer,
         };
         cpu.x[0xb] = 0x1020; // I don't know why but Linux boot seems to require this initialization
@@ -290,12 +318,36 @@ impl Cpu {
     }
 
     /// Runs program one cycle. Fetch, decode, and execution are completed in a cycle so far.
+    /// Does nothing once the guest has faulted (see [`Self::guest_fault`]).
     pub fn tick(&mut self) {
+        if self.guest_fault.is_some() {
+            return;
+        }
         let instruction_address = self.pc;
-        match self.tick_operate() {
+        let result = self.tick_operate();
+        if let Some(trap) = self.mmu.take_access_fault() {
+            self.guest_fault = Some(GuestFault::MemoryOutOfBounds {
+                pc: instruction_address,
+                address: trap.value,
+                memory_size: self.mmu.memory_capacity(),
+            });
+            return;
+        }
+        match result {
             Ok(()) => {}
             Err(e) => self.handle_exception(e, instruction_address),
         }
+        if let Some(stack_bottom) = self.stack_bottom {
+            let sp = self.x[2] as u64 & self.unsigned_data_mask;
+            if (DRAM_BASE..stack_bottom).contains(&sp) {
+                self.guest_fault = Some(GuestFault::StackOverflow {
+                    pc: instruction_address,
+                    sp,
+                    stack_bottom,
+                });
+                return;
+            }
+        }
         self.mmu.tick(&mut self.csr[CSR_MIP_ADDRESS as usize]);
         self.handle_interrupt(self.pc);
         self.clock = self.clock.wrapping_add(1);
@@ -332,6 +384,22 @@ impl Cpu {
         };
 
         match self.decode(word).cloned() {
+            // Environment calls request services from the host (e.g. reporting a panic). They're
+            // in the bytecode as no-ops (see `decode`), so they're traced as such, and before the
+            // host reads any guest memory on their behalf
+            Ok(inst) if inst.name == "ECALL" => {
+                self.tracer.start_instruction(ELFInstruction {
+                    address: instruction_address,
+                    opcode: RV32IM::UNIMPL,
+                    rs1: None,
+                    rs2: None,
+                    rd: None,
+                    imm: None,
+                    virtual_sequence_remaining: None,
+                });
+                self.tracer.end_instruction();
+                (inst.operation)(self, word, instruction_address)
+            }
             Ok(inst) => {
                 // setup trace
                 let trace_inst = inst.trace.unwrap()(&inst, &self.xlen, word, instruction_address);
@@ -1460,6 +1528,106 @@ impl Cpu {
     pub fn get_mut_terminal(&mut self) -> &mut Box<dyn Terminal> {
         self.mmu.get_mut_uart().get_mut_terminal()
     }
+
+    /// Sets the handler used to execute `custom-0` instructions. Without one, they raise an
+    /// illegal instruction exception.
+    pub fn set_custom_instruction_handler(&mut self, handler: CustomInstructionHandler) {
+        self.custom_instruction_handler = Some(handler);
+    }
+
+    /// The panic reported by the guest, if it panicked.
+    pub fn guest_panic(&self) -> Option<&GuestPanic> {
+        self.guest_panic.as_ref()
+    }
+
+    /// The fault that stopped the guest, if it accessed memory beyond the end of its RAM or
+    /// overflowed its stack.
+    pub fn guest_fault(&self) -> Option<&GuestFault> {
+        self.guest_fault.as_ref()
+    }
+
+    /// The guest's debug log, decoded as UTF-8 (lossily).
+    pub fn guest_log(&self) -> String {
+        String::from_utf8_lossy(&self.guest_log).into_owned()
+    }
+
+    /// The heap usage reported by the guest allocator, if the guest uses the jolt-sdk allocator
+    /// and returned or ran out of memory.
+    pub fn heap_stats(&self) -> Option<HeapStats> {
+        self.heap_stats
+    }
+
+    /// Handles a [`LOG_ECALL`], whose argument is the bytes to append to the log (`a0`:
+    /// pointer, `a1`: length).
+    fn append_guest_log(&mut self) -> Result<(), Trap> {
+        let bytes = self.read_guest_bytes(self.x[10], self.unsigned_data(self.x[11]))?;
+        self.guest_log.extend(bytes);
+        Ok(())
+    }
+
+    /// Handles a [`HEAP_ECALL`], whose arguments are the heap usage (`a0`: peak bytes allocated,
+    /// `a1`: allocations, `a2`: heap size) and the size of the allocation that failed (`a3`), or
+    /// 0. A failed allocation is recorded as a panic, whose message and location are filled in if
+    /// the guest goes on to report it.
+    fn report_heap_usage(&mut self) -> Result<(), Trap> {
+        let heap = HeapStats {
+            peak_bytes: self.unsigned_data(self.x[10]),
+            allocations: self.unsigned_data(self.x[11]),
+            capacity: self.unsigned_data(self.x[12]),
+        };
+        let failed_size = self.unsigned_data(self.x[13]);
+        if failed_size > 0 {
+            self.guest_panic = Some(GuestPanic {
+                message: format!("memory allocation of {failed_size} bytes failed"),
+                file: "<unknown>".to_string(),
+                line: 0,
+                column: 0,
+                out_of_memory: Some(OutOfMemory {
+                    size: failed_size,
+                    heap,
+                }),
+            });
+        }
+        self.heap_stats = Some(heap);
+        Ok(())
+    }
+
+    /// Handles a [`PANIC_ECALL`], whose arguments are the panic message (`a0`: pointer, `a1`:
+    /// length), file (`a2`: pointer, `a3`: length), line (`a4`), and column (`a5`).
+    fn report_guest_panic(&mut self) -> Result<(), Trap> {
+        let message = self.read_guest_string(self.x[10], self.x[11])?;
+        let file = self.read_guest_string(self.x[12], self.x[13])?;
+        self.guest_panic = Some(GuestPanic {
+            message,
+            file,
+            line: self.unsigned_data(self.x[14]) as u32,
+            column: self.unsigned_data(self.x[15]) as u32,
+            out_of_memory: self
+                .guest_panic
+                .take()
+                .and_then(|guest_panic| guest_panic.out_of_memory),
+        });
+        Ok(())
+    }
+
+    /// Reads a string from guest memory, truncated to [`MAX_GUEST_STRING_SIZE`] bytes.
+    fn read_guest_string(&mut self, pointer: i64, length: i64) -> Result<String, Trap> {
+        let length = self.unsigned_data(length).min(MAX_GUEST_STRING_SIZE);
+        let bytes = self.read_guest_bytes(pointer, length)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn read_guest_bytes(&mut self, pointer: i64, length: u64) -> Result<Vec<u8>, Trap> {
+        let pointer = self.unsigned_data(pointer);
+        (0..length)
+            .map(|offset| self.mmu.load(pointer.wrapping_add(offset)))
+            .collect()
+    }
+
+    /// Enables stack overflow detection for a stack occupying `[stack_bottom, stack_top)`.
+    pub fn set_stack_bounds(&mut self, stack_top: u64, stack_size: u64) {
+        self.stack_bottom = Some(stack_top.saturating_sub(stack_size));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1659,6 +1827,21 @@ fn dump_format_r(cpu: &mut Cpu, word: u32, _address: u64, evaluate: bool) -> Str
     s
 }
 
+// R-format without rs2, e.g. Zbb's CLZ
+fn dump_format_r_unary(cpu: &mut Cpu, word: u32, _address: u64, evaluate: bool) -> String {
+    let f = parse_format_r(word);
+    let mut s = String::new();
+    s += &format!("{}", get_register_name(f.rd));
+    if evaluate {
+        s += &format!(":{:x}", cpu.x[f.rd]);
+    }
+    s += &format!(",{}", get_register_name(f.rs1));
+    if evaluate {
+        s += &format!(":{:x}", cpu.x[f.rs1]);
+    }
+    s
+}
+
 // has rs3
 struct FormatR2 {
     rd: usize,
@@ -1903,6 +2086,41 @@ fn trace_u(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
     }
 }
 
+// Zbb instructions with a single source register (e.g. CLZ), whose rs2/imm field is part of
+// the opcode
+fn trace_unary(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    ELFInstruction {
+        opcode: RV32IM::from_str(inst.name).unwrap(),
+        address: normalize_u64(address, xlen),
+        imm: None,
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: None,
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+    }
+}
+
+// custom-0: R-type, with funct3 and funct7 packed into imm
+fn trace_custom(_inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    custom_instruction(xlen, word, address)
+}
+
+fn custom_instruction(xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
+    let f = parse_format_r(word);
+    let funct3 = (word >> 12) & 0x7;
+    let funct7 = word >> 25;
+    ELFInstruction {
+        opcode: RV32IM::CUSTOM_0,
+        address: normalize_u64(address, xlen),
+        imm: Some((funct7 << 3) | funct3),
+        rs1: Some(normalize_register(f.rs1)),
+        rs2: Some(normalize_register(f.rs2)),
+        rd: Some(normalize_register(f.rd)),
+        virtual_sequence_remaining: None,
+    }
+}
+
 // (UJ)
 fn trace_j(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstruction {
     let f = parse_format_j(word);
@@ -1917,7 +2135,7 @@ fn trace_j(inst: &Instruction, xlen: &Xlen, word: u32, address: u64) -> ELFInstr
     }
 }
 
-const INSTRUCTION_NUM: usize = 116;
+const INSTRUCTION_NUM: usize = 135;
 
 // @TODO: Reorder in often used order as
 pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
@@ -2533,6 +2751,17 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         data: 0x00000073,
         name: "ECALL",
         operation: |cpu, _word, address| {
+            cpu.tracer.ecall(Ecall {
+                address: cpu.unsigned_data(address as i64),
+                number: cpu.unsigned_data(cpu.x[17]),
+                args: std::array::from_fn(|i| cpu.unsigned_data(cpu.x[10 + i])),
+            });
+            match cpu.unsigned_data(cpu.x[17]) {
+                number if number == PANIC_ECALL as u64 => return cpu.report_guest_panic(),
+                number if number == LOG_ECALL as u64 => return cpu.append_guest_log(),
+                number if number == HEAP_ECALL as u64 => return cpu.report_heap_usage(),
+                _ => {}
+            }
             let exception_type = match cpu.privilege_mode {
                 PrivilegeMode::User => TrapType::EnvironmentCallFromUMode,
                 PrivilegeMode::Supervisor => TrapType::EnvironmentCallFromSMode,
@@ -3766,6 +3995,290 @@ pub const INSTRUCTIONS: [Instruction; INSTRUCTION_NUM] = [
         disassemble: dump_format_i,
         trace: Some(trace_i),
     },
+    Instruction {
+        mask: 0x0000007f,
+        data: 0x0000000b,
+        name: "CUSTOM_0",
+        operation: |cpu, word, address| {
+            let illegal_instruction = Trap {
+                trap_type: TrapType::IllegalInstruction,
+                value: word as u64,
+            };
+            let handler = match cpu.custom_instruction_handler {
+                Some(handler) => handler,
+                None => return Err(illegal_instruction),
+            };
+            let f = parse_format_r(word);
+            let instruction = custom_instruction(&cpu.xlen, word, address);
+            let rs1_val = cpu.unsigned_data(cpu.x[f.rs1]);
+            let rs2_val = cpu.unsigned_data(cpu.x[f.rs2]);
+            match handler(&instruction, rs1_val, rs2_val) {
+                Some(rd_val) => {
+                    cpu.x[f.rd] = cpu.sign_extend(rd_val as i64);
+                    Ok(())
+                }
+                None => Err(illegal_instruction),
+            }
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_custom),
+    },
+    // Zbb (basic bit-manipulation) extension
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40007033,
+        name: "ANDN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(cpu.x[f.rs1] & !cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40006033,
+        name: "ORN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(cpu.x[f.rs1] | !cpu.x[f.rs2]);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x40004033,
+        name: "XNOR",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.sign_extend(!(cpu.x[f.rs1] ^ cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60001013,
+        name: "CLZ",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).leading_zeros() as i64,
+                Xlen::Bit64 => cpu.x[f.rs1].leading_zeros() as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60101013,
+        name: "CTZ",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).trailing_zeros() as i64,
+                Xlen::Bit64 => cpu.x[f.rs1].trailing_zeros() as i64,
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60201013,
+        name: "CPOP",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.unsigned_data(cpu.x[f.rs1]).count_ones() as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a006033,
+        name: "MAX",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu
+                .sign_extend(cpu.x[f.rs1])
+                .max(cpu.sign_extend(cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a007033,
+        name: "MAXU",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let max = cpu
+                .unsigned_data(cpu.x[f.rs1])
+                .max(cpu.unsigned_data(cpu.x[f.rs2]));
+            cpu.x[f.rd] = cpu.sign_extend(max as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a004033,
+        name: "MIN",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu
+                .sign_extend(cpu.x[f.rs1])
+                .min(cpu.sign_extend(cpu.x[f.rs2]));
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x0a005033,
+        name: "MINU",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let min = cpu
+                .unsigned_data(cpu.x[f.rs1])
+                .min(cpu.unsigned_data(cpu.x[f.rs2]));
+            cpu.x[f.rd] = cpu.sign_extend(min as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60401013,
+        name: "SEXT.B",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] as i8 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x60501013,
+        name: "SEXT.H",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] as i16 as i64;
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x08004033,
+        name: "ZEXT.H",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = cpu.x[f.rs1] & 0xffff;
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x60001033,
+        name: "ROL",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => {
+                    (cpu.x[f.rs1] as u32).rotate_left(cpu.x[f.rs2] as u32 & 0x1f) as i32 as i64
+                }
+                Xlen::Bit64 => cpu.x[f.rs1].rotate_left(cpu.x[f.rs2] as u32 & 0x3f),
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfe00707f,
+        data: 0x60005033,
+        name: "ROR",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => {
+                    (cpu.x[f.rs1] as u32).rotate_right(cpu.x[f.rs2] as u32 & 0x1f) as i32 as i64
+                }
+                Xlen::Bit64 => cpu.x[f.rs1].rotate_right(cpu.x[f.rs2] as u32 & 0x3f),
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_r),
+    },
+    Instruction {
+        mask: 0xfc00707f,
+        data: 0x60005013,
+        name: "RORI",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => {
+                    (cpu.x[f.rs1] as u32).rotate_right((word >> 20) & 0x1f) as i32 as i64
+                }
+                Xlen::Bit64 => cpu.x[f.rs1].rotate_right((word >> 20) & 0x3f),
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r,
+        trace: Some(trace_i),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x28705013,
+        name: "ORC.B",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            let value = cpu.unsigned_data(cpu.x[f.rs1]);
+            let orc = (0..8).fold(0u64, |orc, i| match (value >> (8 * i)) & 0xff {
+                0 => orc,
+                _ => orc | (0xff << (8 * i)),
+            });
+            cpu.x[f.rd] = cpu.sign_extend(orc as i64);
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
+    Instruction {
+        mask: 0xfff0707f,
+        data: 0x69805013,
+        name: "REV8",
+        operation: |cpu, word, _address| {
+            let f = parse_format_r(word);
+            cpu.x[f.rd] = match cpu.xlen {
+                Xlen::Bit32 => (cpu.x[f.rs1] as u32).swap_bytes() as i32 as i64,
+                Xlen::Bit64 => cpu.x[f.rs1].swap_bytes(),
+            };
+            Ok(())
+        },
+        disassemble: dump_format_r_unary,
+        trace: Some(trace_unary),
+    },
 ];
 
 /// The number of results [`DecodeCache`](struct.DecodeCache.html) holds.
@@ -4056,6 +4569,59 @@ mod test_cpu {
         assert_eq!(8, cpu.read_register(8));
     }
 
+    #[test]
+    fn stack_overflow() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(4);
+        cpu.update_pc(DRAM_BASE);
+        cpu.set_stack_bounds(DRAM_BASE + 0x100, 0x8);
+        cpu.x[2] = (DRAM_BASE + 0x100) as i64;
+
+        // Write "addi sp, sp, -16" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0xff010113) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+
+        cpu.tick();
+        assert_eq!(
+            cpu.guest_fault(),
+            Some(&GuestFault::StackOverflow {
+                pc: DRAM_BASE,
+                sp: DRAM_BASE + 0xf0,
+                stack_bottom: DRAM_BASE + 0xf8,
+            })
+        );
+    }
+
+    #[test]
+    fn memory_out_of_bounds() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        cpu.x[1] = (DRAM_BASE + 0x100) as i64;
+
+        // Write "sw x1, 0(x1)" instruction
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x0010a023) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+
+        cpu.tick();
+        let fault = Some(GuestFault::MemoryOutOfBounds {
+            pc: DRAM_BASE,
+            address: DRAM_BASE + 0x100,
+            memory_size: 8,
+        });
+        assert_eq!(cpu.guest_fault(), fault.as_ref());
+
+        // The guest stays stopped
+        let pc = cpu.read_pc();
+        cpu.tick();
+        assert_eq!(cpu.read_pc(), pc);
+        assert_eq!(cpu.guest_fault(), fault.as_ref());
+    }
+
     #[test]
     fn tick_operate() {
         let mut cpu = create_cpu();
@@ -4080,6 +4646,301 @@ mod test_cpu {
         // @TODO: Test compressed instruction operation
     }
 
+    #[test]
+    fn custom_instruction() {
+        fn handler(instruction: &ELFInstruction, rs1_val: u64, rs2_val: u64) -> Option<u64> {
+            match instruction.custom_funct() {
+                Some((1, 2)) => Some(rs1_val.wrapping_mul(3) ^ rs2_val),
+                _ => None,
+            }
+        }
+
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(8);
+        cpu.update_pc(DRAM_BASE);
+        // custom-0 with funct7 = 2, rs2 = x7, rs1 = x6, funct3 = 1, rd = x5
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x0473128b) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        cpu.x[6] = 5;
+        cpu.x[7] = 1;
+
+        // Without a handler, custom instructions are illegal
+        match cpu.tick_operate() {
+            Ok(()) => panic!("Expected an illegal instruction exception"),
+            Err(e) => assert!(matches!(e.trap_type, TrapType::IllegalInstruction)),
+        };
+
+        cpu.set_custom_instruction_handler(handler);
+        cpu.update_pc(DRAM_BASE);
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert_eq!(14, cpu.read_register(5));
+
+        let rows = cpu.tracer.rows.borrow();
+        let row = rows.last().unwrap();
+        assert_eq!(row.instruction.opcode, RV32IM::CUSTOM_0);
+        assert_eq!(row.instruction.custom_funct(), Some((1, 2)));
+        assert_eq!(row.register_state.rd_post_val, Some(14));
+    }
+
+    #[test]
+    fn zbb_instructions() {
+        let mut cpu = create_cpu();
+        cpu.update_xlen(Xlen::Bit32);
+        cpu.get_mut_mmu().init_memory(8);
+
+        // (instruction with rd = x5, rs1 = x6, rs2 = x7, expected x5)
+        let cases = [
+            (0x407372b3, "ANDN", 0x0000_00f0),
+            (0x60031293, "CLZ", 8),
+            (0x60131293, "CTZ", 4),
+            (0x60231293, "CPOP", 12),
+            (0x0a7362b3, "MAX", 0x00ff_00f0),
+            (0x60835293, "RORI", 0xf000_ff00),
+            (0x28735293, "ORC.B", 0x00ff_00ff),
+            (0x69835293, "REV8", 0xf000_ff00),
+        ];
+        for (word, name, expected) in cases {
+            cpu.update_pc(DRAM_BASE);
+            match cpu.get_mut_mmu().store_word(DRAM_BASE, word) {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+            cpu.x[6] = 0x00ff_00f0;
+            cpu.x[7] = -0x100;
+
+            assert_eq!(cpu.decode(word).unwrap().name, name);
+            match cpu.tick_operate() {
+                Ok(()) => {}
+                Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+            };
+            assert_eq!(cpu.read_register(5) as u32, expected, "{name}");
+        }
+
+        // Instructions with a single source register are traced without rs2
+        let rows = cpu.tracer.rows.borrow();
+        let row = rows.last().unwrap();
+        assert_eq!(row.instruction.opcode, RV32IM::REV8);
+        assert_eq!(row.instruction.rs2, None);
+        assert_eq!(row.register_state.rs1_val, Some(0x00ff_00f0));
+        assert_eq!(row.register_state.rd_post_val, Some(0xf000_ff00));
+    }
+
+    #[test]
+    fn panic_ecall() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(64);
+        cpu.update_pc(DRAM_BASE);
+        match cpu.get_mut_mmu().store_word(DRAM_BASE, 0x00000073) {
+            Ok(()) => {}
+            Err(_e) => panic!("Failed to store"),
+        };
+        // Message "oops" followed by file "src/lib.rs"
+        let mmu = cpu.get_mut_mmu();
+        for (offset, byte) in b"oopssrc/lib.rs".iter().enumerate() {
+            if mmu.store(DRAM_BASE + 16 + offset as u64, *byte).is_err() {
+                panic!("Failed to store");
+            }
+        }
+        cpu.x[17] = PANIC_ECALL as i64;
+        cpu.x[10] = (DRAM_BASE + 16) as i64;
+        cpu.x[11] = 4;
+        cpu.x[12] = (DRAM_BASE + 20) as i64;
+        cpu.x[13] = 10;
+        cpu.x[14] = 7;
+        cpu.x[15] = 5;
+
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        assert_eq!(DRAM_BASE + 4, cpu.read_pc());
+        assert_eq!(
+            cpu.guest_panic(),
+            Some(&GuestPanic {
+                message: "oops".to_string(),
+                file: "src/lib.rs".to_string(),
+                line: 7,
+                column: 5,
+                out_of_memory: None,
+            })
+        );
+        // The environment call is traced as a no-op, without the message and file reads
+        let rows = cpu.tracer.rows.borrow();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].instruction.address, DRAM_BASE);
+        assert_eq!(rows[0].instruction.opcode, RV32IM::UNIMPL);
+        assert_eq!(rows[0].memory_state, None);
+    }
+
+    #[test]
+    fn log_ecall() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(64);
+        cpu.update_pc(DRAM_BASE);
+        for offset in [0, 4] {
+            match cpu.get_mut_mmu().store_word(DRAM_BASE + offset, 0x00000073) {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        let mmu = cpu.get_mut_mmu();
+        for (offset, byte) in b"x = 42\n".iter().enumerate() {
+            if mmu.store(DRAM_BASE + 16 + offset as u64, *byte).is_err() {
+                panic!("Failed to store");
+            }
+        }
+        cpu.x[17] = LOG_ECALL as i64;
+        cpu.x[10] = (DRAM_BASE + 16) as i64;
+        for length in [4, 3] {
+            cpu.x[11] = length;
+            match cpu.tick_operate() {
+                Ok(()) => {}
+                Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+            };
+            cpu.x[10] += length;
+        }
+        assert_eq!(DRAM_BASE + 8, cpu.read_pc());
+        assert_eq!(cpu.guest_log(), "x = 42\n");
+        assert_eq!(cpu.tracer.rows.borrow().len(), 2);
+    }
+
+    #[test]
+    fn heap_ecall() {
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(64);
+        cpu.update_pc(DRAM_BASE);
+        for offset in [0, 4, 8] {
+            match cpu.get_mut_mmu().store_word(DRAM_BASE + offset, 0x00000073) {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        let mmu = cpu.get_mut_mmu();
+        for (offset, byte) in b"oom".iter().enumerate() {
+            if mmu.store(DRAM_BASE + 16 + offset as u64, *byte).is_err() {
+                panic!("Failed to store");
+            }
+        }
+        let heap = HeapStats {
+            peak_bytes: 1000,
+            allocations: 12,
+            capacity: 1024,
+        };
+        cpu.x[17] = HEAP_ECALL as i64;
+        cpu.x[10] = heap.peak_bytes as i64;
+        cpu.x[11] = heap.allocations as i64;
+        cpu.x[12] = heap.capacity as i64;
+        cpu.x[13] = 0;
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        assert_eq!(cpu.heap_stats(), Some(heap));
+        assert!(cpu.guest_panic().is_none());
+
+        // An allocation of 64 bytes fails, then the panic handler reports it
+        cpu.x[13] = 64;
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        let out_of_memory = OutOfMemory { size: 64, heap };
+        assert_eq!(
+            cpu.guest_panic().unwrap().out_of_memory,
+            Some(out_of_memory)
+        );
+        cpu.x[17] = PANIC_ECALL as i64;
+        cpu.x[10] = (DRAM_BASE + 16) as i64;
+        cpu.x[11] = 3;
+        cpu.x[12] = (DRAM_BASE + 16) as i64;
+        cpu.x[13] = 0;
+        cpu.x[14] = 0;
+        cpu.x[15] = 0;
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        let guest_panic = cpu.guest_panic().unwrap();
+        assert_eq!(guest_panic.message, "oom");
+        assert_eq!(guest_panic.out_of_memory, Some(out_of_memory));
+        assert_eq!(cpu.tracer.rows.borrow().len(), 3);
+    }
+
+    #[test]
+    fn trace_observer() {
+        use crate::trace::TraceObserver;
+        use std::cell::RefCell;
+
+        #[derive(Default)]
+        struct Events {
+            instructions: Vec<RV32IM>,
+            memory: Vec<MemoryState>,
+            ecalls: Vec<Ecall>,
+        }
+
+        impl TraceObserver for Events {
+            fn on_instruction(&mut self, row: &RVTraceRow) {
+                self.instructions.push(row.instruction.opcode);
+            }
+
+            fn on_memory(&mut self, _instruction: &ELFInstruction, memory_state: &MemoryState) {
+                self.memory.push(memory_state.clone());
+            }
+
+            fn on_ecall(&mut self, ecall: &Ecall) {
+                self.ecalls.push(*ecall);
+            }
+        }
+
+        let mut cpu = create_cpu();
+        cpu.get_mut_mmu().init_memory(64);
+        cpu.update_pc(DRAM_BASE);
+        // lw x5, 0(x6); ecall
+        for (offset, word) in [(0, 0x00032283), (4, 0x00000073), (16, 0xdeadbeef)] {
+            match cpu.get_mut_mmu().store_word(DRAM_BASE + offset, word) {
+                Ok(()) => {}
+                Err(_e) => panic!("Failed to store"),
+            };
+        }
+        cpu.x[6] = (DRAM_BASE + 16) as i64;
+        cpu.x[17] = 1234;
+        cpu.x[10] = 7;
+        cpu.x[11] = 0;
+
+        let events = Rc::new(RefCell::new(Events::default()));
+        cpu.tracer.subscribe(Box::new(events.clone()));
+        match cpu.tick_operate() {
+            Ok(()) => {}
+            Err(_e) => panic!("tick_operate() unexpectedly did panic"),
+        };
+        // An unknown environment call traps, but is observed first
+        assert!(cpu.tick_operate().is_err());
+
+        let events = events.borrow();
+        assert_eq!(events.instructions, vec![RV32IM::LW, RV32IM::UNIMPL]);
+        assert_eq!(
+            events.memory,
+            vec![MemoryState::Read {
+                address: DRAM_BASE + 16,
+                value: 0xdeadbeef,
+            }]
+        );
+        assert_eq!(
+            events.ecalls,
+            vec![Ecall {
+                address: DRAM_BASE + 4,
+                number: 1234,
+                args: [7, 0, 0, 0, 0, 0],
+            }]
+        );
+    }
+
     #[test]
     fn fetch() {
         // .fetch() reads four bytes from the memory
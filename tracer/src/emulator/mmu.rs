@@ -9,7 +9,7 @@ extern crate fnv;
 use std::rc::Rc;
 
 use crate::trace::Tracer;
-use common::rv_trace::{JoltDevice, MemoryState};
+use common::rv_trace::{JoltDevice, MemoryConfig, MemoryState};
 
 use self::fnv::FnvHashMap;
 
@@ -112,7 +112,11 @@ impl Mmu {
             plic: Plic::new(),
             clint: Clint::new(),
             uart: Uart::new(terminal),
-            jolt_device: JoltDevice::new(0, 0),
+            jolt_device: JoltDevice::new(&MemoryConfig {
+                max_input_size: 0,
+                max_output_size: 0,
+                ..Default::default()
+            }),
             tracer,
             mstatus: 0,
             page_cache_enabled: false,
@@ -139,6 +143,18 @@ impl Mmu {
         self.memory.init(capacity);
     }
 
+    /// Size of the main memory, in bytes.
+    pub fn memory_capacity(&self) -> u64 {
+        self.memory.capacity
+    }
+
+    /// Returns (and clears) the first access beyond the end of the main memory since the last
+    /// call, as a load or store access fault whose value is the address. Such accesses are
+    /// skipped: loads read zero and stores are dropped.
+    pub fn take_access_fault(&mut self) -> Option<Trap> {
+        self.memory.take_access_fault()
+    }
+
     /// Initializes Virtio block disk. This method is expected to be called only once.
     ///
     /// # Arguments
@@ -552,6 +568,13 @@ impl Mmu {
                 panic!("Unknown memory mapping {:X}.", effective_address);
             }
         } else {
+            if self.jolt_device.memory_layout.is_blob(effective_address) {
+                panic!(
+                    "Guest wrote to address {:#X}, in its read-only blob region; if the heap \
+                     overflowed into it, increase `memory_size`",
+                    effective_address
+                );
+            }
             self.tracer.push_memory(MemoryState::Write {
                 address: effective_address,
                 post_value: value,
@@ -752,6 +775,50 @@ impl Mmu {
         Ok(valid)
     }
 
+    /// Reads a byte on behalf of a debugger, bypassing the tracer. Returns `None` if
+    /// the address is not backed by main memory or the Jolt I/O device.
+    ///
+    /// # Arguments
+    /// * `p_address` Physical address
+    pub fn peek(&mut self, p_address: u64) -> Option<u8> {
+        let effective_address = self.get_effective_address(p_address);
+        if effective_address >= DRAM_BASE {
+            match self.memory.validate_address(effective_address) {
+                true => Some(self.memory.read_byte(effective_address)),
+                false => None,
+            }
+        } else if self.jolt_device.is_input(effective_address) {
+            Some(self.jolt_device.load(effective_address))
+        } else if self.jolt_device.is_output(effective_address) {
+            let offset = effective_address - self.jolt_device.memory_layout.output_start;
+            Some(
+                self.jolt_device
+                    .outputs
+                    .get(offset as usize)
+                    .copied()
+                    .unwrap_or(0),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Writes a byte of main memory on behalf of a debugger, bypassing the tracer.
+    /// Returns `false` if the address is not backed by main memory.
+    ///
+    /// # Arguments
+    /// * `p_address` Physical address
+    /// * `value` data written
+    pub fn poke(&mut self, p_address: u64, value: u8) -> bool {
+        let effective_address = self.get_effective_address(p_address);
+        if effective_address >= DRAM_BASE && self.memory.validate_address(effective_address) {
+            self.memory.write_byte(effective_address, value);
+            true
+        } else {
+            false
+        }
+    }
+
     fn translate_address(
         &mut self,
         v_address: u64,
@@ -1018,6 +1085,9 @@ impl Mmu {
 /// using [`DRAM_BASE`](constant.DRAM_BASE.html) and accesses [`Memory`](../memory/struct.Memory.html).
 pub struct MemoryWrapper {
     memory: Memory,
+    capacity: u64,
+    /// The first access beyond the end of the RAM since the CPU last checked.
+    access_fault: Option<Trap>,
     tracer: Rc<Tracer>,
 }
 
@@ -1025,12 +1095,32 @@ impl MemoryWrapper {
     fn new(tracer: Rc<Tracer>) -> Self {
         MemoryWrapper {
             memory: Memory::new(),
+            capacity: 0,
+            access_fault: None,
             tracer,
         }
     }
 
     fn init(&mut self, capacity: u64) {
         self.memory.init(capacity);
+        // `Memory` allocates in 8-byte words
+        self.capacity = capacity.next_multiple_of(8);
+    }
+
+    /// Returns whether the access lies within the RAM. If it doesn't, records it as an access
+    /// fault for the CPU to pick up with [`Mmu::take_access_fault`], since the raw accessors
+    /// can't return a [`Trap`] themselves.
+    fn check_bounds(&mut self, p_address: u64, bytes: u64, trap_type: TrapType) -> bool {
+        if p_address - DRAM_BASE + bytes <= self.capacity {
+            return true;
+        }
+        if self.access_fault.is_none() {
+            self.access_fault = Some(Trap {
+                trap_type,
+                value: p_address,
+            });
+        }
+        false
     }
 
     pub fn read_byte(&mut self, p_address: u64) -> u8 {
@@ -1040,6 +1130,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 1, TrapType::LoadAccessFault) {
+            return 0;
+        }
         self.memory.read_byte(p_address - DRAM_BASE)
     }
 
@@ -1050,6 +1143,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 2, TrapType::LoadAccessFault) {
+            return 0;
+        }
         self.memory.read_halfword(p_address - DRAM_BASE)
     }
 
@@ -1060,6 +1156,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 4, TrapType::LoadAccessFault) {
+            return 0;
+        }
         self.memory.read_word(p_address - DRAM_BASE)
     }
 
@@ -1070,6 +1169,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 8, TrapType::LoadAccessFault) {
+            return 0;
+        }
         self.memory.read_doubleword(p_address - DRAM_BASE)
     }
 
@@ -1080,6 +1182,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 1, TrapType::StoreAccessFault) {
+            return;
+        }
         self.memory.write_byte(p_address - DRAM_BASE, value);
     }
 
@@ -1090,6 +1195,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 2, TrapType::StoreAccessFault) {
+            return;
+        }
         self.memory.write_halfword(p_address - DRAM_BASE, value);
     }
 
@@ -1100,6 +1208,9 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 4, TrapType::StoreAccessFault) {
+            return;
+        }
         self.memory.write_word(p_address - DRAM_BASE, value);
     }
 
@@ -1110,10 +1221,17 @@ impl MemoryWrapper {
             p_address
         );
 
+        if !self.check_bounds(p_address, 8, TrapType::StoreAccessFault) {
+            return;
+        }
         self.memory.write_doubleword(p_address - DRAM_BASE, value);
     }
 
     pub fn validate_address(&self, address: u64) -> bool {
         self.memory.validate_address(address - DRAM_BASE)
     }
+
+    fn take_access_fault(&mut self) -> Option<Trap> {
+        self.access_fault.take()
+    }
 }
@@ -1,6 +1,5 @@
 // @TODO: temporal
 const TEST_MEMORY_CAPACITY: u64 = 1024 * 512;
-const PROGRAM_MEMORY_CAPACITY: u64 = 1024 * 1024 * 128; // big enough to run Linux and xv6
 
 extern crate fnv;
 
@@ -196,7 +195,15 @@ impl Emulator {
             self.cpu.get_mut_mmu().init_memory(TEST_MEMORY_CAPACITY);
         } else {
             self.is_test = false;
-            self.cpu.get_mut_mmu().init_memory(PROGRAM_MEMORY_CAPACITY);
+            let memory_layout = self.cpu.get_mut_mmu().jolt_device.memory_layout.clone();
+            self.cpu
+                .get_mut_mmu()
+                .init_memory(memory_layout.memory_size + memory_layout.max_blob_size);
+            // Set by the guest linker script to the top of the stack
+            if let Some(stack_top) = self.symbol_map.get("_STACK_PTR") {
+                self.cpu
+                    .set_stack_bounds(*stack_top, memory_layout.stack_size);
+            }
         }
 
         for header in &program_data_section_headers {
@@ -0,0 +1,485 @@
+//! A GDB remote serial protocol stub for the guest emulator.
+//!
+//! [`GdbStub`] drives an [`Emulator`] on behalf of a debugger connected over TCP, so
+//! guest programs can be inspected with `riscv32-unknown-elf-gdb` or `gdb-multiarch`
+//! before they are traced and proven:
+//!
+//! ```text
+//! (gdb) target remote localhost:1234
+//! ```
+//!
+//! Supported are software and hardware breakpoints, single-step, continue (which can
+//! be interrupted with Ctrl-C), and register and memory reads and writes. The program
+//! is reported as exited once it spins on a single instruction, the same termination
+//! heuristic used by [`crate::trace`].
+
+use std::{
+    collections::HashSet,
+    fmt::Write as _,
+    io::{self, ErrorKind, Read, Write},
+    net::TcpStream,
+};
+
+use common::rv_trace::JoltDevice;
+
+use crate::emulator::Emulator;
+
+/// Number of instructions executed between checks for a debugger interrupt while continuing.
+const INTERRUPT_POLL_INTERVAL: usize = 1 << 14;
+/// GDB's register number for the program counter; 0-31 are the integer registers.
+const PC_REGISTER: usize = 32;
+const NUM_REGISTERS: usize = 33;
+const SIGINT: u8 = 2;
+const SIGTRAP: u8 = 5;
+const INTERRUPT: u8 = 0x03;
+
+const REGISTER_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "fp", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+enum StopReason {
+    Signal(u8),
+    Exited,
+}
+
+/// Serves a single GDB session for an emulator that has already been set up with a program.
+pub struct GdbStub {
+    emulator: Emulator,
+    breakpoints: HashSet<u64>,
+    exited: bool,
+}
+
+impl GdbStub {
+    pub fn new(emulator: Emulator) -> Self {
+        Self {
+            emulator,
+            breakpoints: HashSet::new(),
+            exited: false,
+        }
+    }
+
+    /// Handles packets from `stream` until the debugger detaches, kills the program, or
+    /// disconnects, and returns the final state of the I/O device.
+    pub fn serve(mut self, stream: TcpStream) -> io::Result<JoltDevice> {
+        let mut connection = Connection::new(stream);
+        while let Some(packet) = connection.read_packet()? {
+            match packet.as_bytes().first() {
+                Some(b'k') => break,
+                Some(b'D') => {
+                    connection.write_packet("OK")?;
+                    break;
+                }
+                _ => {}
+            }
+            let reply = self.handle(&packet, &mut connection)?;
+            connection.write_packet(&reply)?;
+        }
+        Ok(self
+            .emulator
+            .get_mut_cpu()
+            .get_mut_mmu()
+            .jolt_device
+            .clone())
+    }
+
+    /// Returns the reply to `packet`. An empty reply tells GDB the packet is unsupported.
+    fn handle(&mut self, packet: &str, connection: &mut Connection) -> io::Result<String> {
+        let (command, args) = match packet.char_indices().nth(1) {
+            Some((split, _)) => packet.split_at(split),
+            None => (packet, ""),
+        };
+        let reply = match command {
+            "?" => match self.exited {
+                true => self.stop_reply(StopReason::Exited),
+                false => self.stop_reply(StopReason::Signal(SIGTRAP)),
+            },
+            "g" => (0..NUM_REGISTERS)
+                .map(|register| encode_register(self.read_register(register)))
+                .collect(),
+            "G" => match decode_registers(args) {
+                Some(values) => {
+                    values
+                        .into_iter()
+                        .enumerate()
+                        .for_each(|(register, value)| self.write_register(register, value));
+                    "OK".to_string()
+                }
+                None => error(),
+            },
+            "p" => match usize::from_str_radix(args, 16) {
+                Ok(register) if register < NUM_REGISTERS => {
+                    encode_register(self.read_register(register))
+                }
+                _ => error(),
+            },
+            "P" => match args.split_once('=').and_then(|(register, value)| {
+                let register = usize::from_str_radix(register, 16).ok()?;
+                Some((register, decode_register(value)?))
+            }) {
+                Some((register, value)) if register < NUM_REGISTERS => {
+                    self.write_register(register, value);
+                    "OK".to_string()
+                }
+                _ => error(),
+            },
+            "m" => match parse_address_length(args) {
+                Some((address, length)) => self.read_memory(address, length),
+                None => error(),
+            },
+            "M" => match args
+                .split_once(':')
+                .and_then(|(range, data)| Some((parse_address_length(range)?, decode_hex(data)?)))
+            {
+                Some(((address, length), data)) if data.len() == length => {
+                    self.write_memory(address, &data)
+                }
+                _ => error(),
+            },
+            "c" | "s" => {
+                if let Ok(address) = u64::from_str_radix(args, 16) {
+                    self.emulator.get_mut_cpu().update_pc(address);
+                }
+                let reason = self.resume(command == "s", connection)?;
+                self.stop_reply(reason)
+            }
+            "Z" | "z" => match parse_breakpoint(args) {
+                Some(address) => {
+                    if command == "Z" {
+                        self.breakpoints.insert(address);
+                    } else {
+                        self.breakpoints.remove(&address);
+                    }
+                    "OK".to_string()
+                }
+                None => String::new(),
+            },
+            "H" => "OK".to_string(),
+            "q" => self.handle_query(args),
+            _ => String::new(),
+        };
+        Ok(reply)
+    }
+
+    fn handle_query(&self, query: &str) -> String {
+        if query.starts_with("Supported") {
+            "PacketSize=1000;qXfer:features:read+".to_string()
+        } else if query == "Attached" {
+            "1".to_string()
+        } else if let Some(range) = query.strip_prefix("Xfer:features:read:target.xml:") {
+            match parse_address_length(range) {
+                Some((offset, length)) => xfer_chunk(&target_xml(), offset as usize, length),
+                None => error(),
+            }
+        } else {
+            String::new()
+        }
+    }
+
+    fn stop_reply(&mut self, reason: StopReason) -> String {
+        match reason {
+            // Report a guest panic as a nonzero exit status.
+            StopReason::Exited => {
+                let panicked = self.emulator.get_mut_cpu().get_mut_mmu().jolt_device.panic;
+                format!("W{:02x}", panicked as u8)
+            }
+            StopReason::Signal(signal) => format!("S{:02x}", signal),
+        }
+    }
+
+    /// Executes a single instruction, noting whether the program has terminated.
+    fn step(&mut self) {
+        let pc = self.emulator.get_cpu().read_pc();
+        self.emulator.tick();
+        // The debugger has no use for trace rows, so don't let them accumulate.
+        self.emulator.get_mut_cpu().tracer.rows.borrow_mut().clear();
+        let cpu = self.emulator.get_cpu();
+        if cpu.read_pc() == pc || cpu.guest_fault().is_some() {
+            self.exited = true;
+        }
+    }
+
+    fn resume(&mut self, single_step: bool, connection: &mut Connection) -> io::Result<StopReason> {
+        if self.exited {
+            return Ok(StopReason::Exited);
+        }
+        let mut executed = 0;
+        loop {
+            self.step();
+            executed += 1;
+            if self.exited {
+                return Ok(StopReason::Exited);
+            }
+            if single_step
+                || self
+                    .breakpoints
+                    .contains(&self.emulator.get_cpu().read_pc())
+            {
+                return Ok(StopReason::Signal(SIGTRAP));
+            }
+            if executed % INTERRUPT_POLL_INTERVAL == 0 && connection.interrupted()? {
+                return Ok(StopReason::Signal(SIGINT));
+            }
+        }
+    }
+
+    fn read_register(&self, register: usize) -> u32 {
+        let cpu = self.emulator.get_cpu();
+        match register {
+            PC_REGISTER => cpu.read_pc() as u32,
+            _ => cpu.read_register(register as u8) as u32,
+        }
+    }
+
+    fn write_register(&mut self, register: usize, value: u32) {
+        let cpu = self.emulator.get_mut_cpu();
+        match register {
+            0 => {}
+            PC_REGISTER => cpu.update_pc(value as u64),
+            // 32-bit register values are kept sign-extended, as the CPU itself does.
+            _ => cpu.x[register] = value as i32 as i64,
+        }
+    }
+
+    /// Reads up to `length` bytes, stopping at the first unmapped address.
+    fn read_memory(&mut self, address: u64, length: usize) -> String {
+        let mmu = self.emulator.get_mut_cpu().get_mut_mmu();
+        let bytes: Vec<u8> = (0..length as u64)
+            .map_while(|offset| mmu.peek(address.wrapping_add(offset)))
+            .collect();
+        match bytes.is_empty() && length > 0 {
+            true => error(),
+            false => encode_hex(&bytes),
+        }
+    }
+
+    fn write_memory(&mut self, address: u64, data: &[u8]) -> String {
+        let mmu = self.emulator.get_mut_cpu().get_mut_mmu();
+        let written = data
+            .iter()
+            .enumerate()
+            .all(|(offset, byte)| mmu.poke(address.wrapping_add(offset as u64), *byte));
+        match written {
+            true => "OK".to_string(),
+            false => error(),
+        }
+    }
+}
+
+/// A TCP connection speaking the packet layer of the remote serial protocol.
+struct Connection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0u8];
+        match self.stream.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Reads the next `$packet#checksum`, acknowledging it. Returns `None` once the
+    /// debugger disconnects.
+    fn read_packet(&mut self) -> io::Result<Option<String>> {
+        loop {
+            // Skip acknowledgements and interrupts that arrive while idle.
+            match self.read_byte()? {
+                None => return Ok(None),
+                Some(b'$') => {}
+                Some(_) => continue,
+            }
+            self.buffer.clear();
+            loop {
+                match self.read_byte()? {
+                    None => return Ok(None),
+                    Some(b'#') => break,
+                    Some(byte) => self.buffer.push(byte),
+                }
+            }
+            let mut checksum = [0u8; 2];
+            self.stream.read_exact(&mut checksum)?;
+            let valid = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|checksum| u8::from_str_radix(checksum, 16).ok())
+                == Some(packet_checksum(&self.buffer));
+            if !valid {
+                self.stream.write_all(b"-")?;
+                continue;
+            }
+            self.stream.write_all(b"+")?;
+            return Ok(Some(String::from_utf8_lossy(&self.buffer).into_owned()));
+        }
+    }
+
+    fn write_packet(&mut self, data: &str) -> io::Result<()> {
+        self.stream.write_all(frame_packet(data).as_bytes())?;
+        self.stream.flush()
+    }
+
+    /// Checks, without blocking, whether the debugger has sent an interrupt (Ctrl-C).
+    fn interrupted(&mut self) -> io::Result<bool> {
+        self.stream.set_nonblocking(true)?;
+        let mut interrupted = false;
+        let mut byte = [0u8];
+        loop {
+            match self.stream.read(&mut byte) {
+                Ok(0) => break,
+                Ok(_) => interrupted |= byte[0] == INTERRUPT,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    self.stream.set_nonblocking(false)?;
+                    return Err(e);
+                }
+            }
+        }
+        self.stream.set_nonblocking(false)?;
+        Ok(interrupted)
+    }
+}
+
+fn packet_checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+fn frame_packet(data: &str) -> String {
+    format!("${}#{:02x}", data, packet_checksum(data.as_bytes()))
+}
+
+fn error() -> String {
+    "E01".to_string()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(hex, "{:02x}", byte).unwrap();
+    }
+    hex
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Registers are transferred in target (little-endian) byte order.
+fn encode_register(value: u32) -> String {
+    encode_hex(&value.to_le_bytes())
+}
+
+fn decode_register(hex: &str) -> Option<u32> {
+    let bytes: [u8; 4] = decode_hex(hex)?.try_into().ok()?;
+    Some(u32::from_le_bytes(bytes))
+}
+
+fn decode_registers(hex: &str) -> Option<Vec<u32>> {
+    if hex.len() != NUM_REGISTERS * 8 {
+        return None;
+    }
+    (0..NUM_REGISTERS)
+        .map(|register| decode_register(&hex[register * 8..(register + 1) * 8]))
+        .collect()
+}
+
+/// Parses `addr,length`, both in hex.
+fn parse_address_length(args: &str) -> Option<(u64, usize)> {
+    let (address, length) = args.split_once(',')?;
+    Some((
+        u64::from_str_radix(address, 16).ok()?,
+        usize::from_str_radix(length, 16).ok()?,
+    ))
+}
+
+/// Parses the `type,addr,kind` arguments of `Z`/`z`. Software and hardware execution
+/// breakpoints are treated the same; watchpoints are unsupported.
+fn parse_breakpoint(args: &str) -> Option<u64> {
+    let mut fields = args.split(',');
+    match fields.next()? {
+        "0" | "1" => u64::from_str_radix(fields.next()?, 16).ok(),
+        _ => None,
+    }
+}
+
+/// Describes the register file so GDB doesn't expect floating-point or CSR registers.
+fn target_xml() -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?>\
+         <!DOCTYPE target SYSTEM \"gdb-target.dtd\">\
+         <target version=\"1.0\">\
+         <architecture>riscv:rv32</architecture>\
+         <feature name=\"org.gnu.gdb.riscv.cpu\">",
+    );
+    for name in REGISTER_NAMES {
+        write!(xml, "<reg name=\"{}\" bitsize=\"32\" type=\"int\"/>", name).unwrap();
+    }
+    xml.push_str("<reg name=\"pc\" bitsize=\"32\" type=\"code_ptr\"/></feature></target>");
+    xml
+}
+
+/// Returns the requested window of a `qXfer` object: `m` if more data follows, `l` if
+/// this is the last chunk.
+fn xfer_chunk(object: &str, offset: usize, length: usize) -> String {
+    let start = offset.min(object.len());
+    let end = offset.saturating_add(length).min(object.len());
+    let prefix = if end == object.len() { 'l' } else { 'm' };
+    format!("{}{}", prefix, &object[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_framing() {
+        assert_eq!(frame_packet("OK"), "$OK#9a");
+        assert_eq!(frame_packet(""), "$#00");
+        assert_eq!(frame_packet("S05"), "$S05#b8");
+    }
+
+    #[test]
+    fn register_encoding() {
+        assert_eq!(encode_register(0x80000004), "04000080");
+        assert_eq!(decode_register("04000080"), Some(0x80000004));
+        assert_eq!(decode_register("0400"), None);
+
+        let registers: String = (0..NUM_REGISTERS as u32).map(encode_register).collect();
+        assert_eq!(
+            decode_registers(&registers),
+            Some((0..NUM_REGISTERS as u32).collect())
+        );
+    }
+
+    #[test]
+    fn argument_parsing() {
+        assert_eq!(parse_address_length("80000000,4"), Some((0x80000000, 4)));
+        assert_eq!(parse_breakpoint("0,80000010,4"), Some(0x80000010));
+        assert_eq!(parse_breakpoint("2,80000010,4"), None);
+        assert_eq!(decode_hex("deadbeef"), Some(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode_hex("abc"), None);
+    }
+
+    #[test]
+    fn target_description_chunks() {
+        let xml = target_xml();
+        let first = xfer_chunk(&xml, 0, 16);
+        assert_eq!(first, format!("m{}", &xml[..16]));
+        let last = xfer_chunk(&xml, 16, xml.len());
+        assert_eq!(last, format!("l{}", &xml[16..]));
+    }
+}
@@ -1,7 +1,14 @@
 #![allow(dead_code)]
 #![allow(clippy::legacy_numeric_constants)]
 
-use std::{fs::File, io::Read, path::PathBuf};
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{self, Read},
+    net::{TcpListener, ToSocketAddrs},
+    path::PathBuf,
+    rc::Rc,
+};
 
 use common::{self, constants::RAM_START_ADDRESS};
 use emulator::{
@@ -13,37 +20,173 @@ use emulator::{
 use object::{Object, ObjectSection, SectionKind};
 
 mod decode;
+mod diff;
 mod emulator;
+mod gdb;
+mod profile;
+mod replay;
 mod trace;
 
 pub use common::rv_trace::{
-    ELFInstruction, JoltDevice, MemoryState, RVTraceRow, RegisterState, RV32IM,
+    Blobs, ELFInstruction, GuestFault, GuestPanic, HeapStats, JoltDevice, MemoryConfig,
+    MemoryState, OutOfMemory, RVTraceRow, RegisterState, RV32IM,
 };
+pub use diff::{diff_test, DiffTestError, Divergence, MemoryAccess, Reference, ReferenceStep};
+pub use emulator::cpu::CustomInstructionHandler;
+pub use profile::{Profile, Profiler, SourceLines};
+pub use replay::{check_replay, NondeterminismSource, ReplayDivergence};
+pub use trace::{Ecall, Sampling, TraceObserver};
 
 use crate::decode::decode_raw;
+use crate::gdb::GdbStub;
 
+/// Runs the program with `blobs` loaded into its blob region, returning its trace, its final
+/// I/O, the panic it reported if it panicked, the fault that stopped it if it accessed memory
+/// beyond the end of its RAM or overflowed its stack, its debug log (see `jolt::println!`), and
+/// the heap usage reported by its allocator, if it uses the jolt-sdk allocator. The trace of a
+/// program that reported a panic cannot be proven, since the environment call used to report it
+/// is not traced, and neither can that of a program that faulted. If the program runs for more
+/// than `memory_config.max_cycles` instructions, it is halted there and the returned device
+/// records that it exceeded them.
 #[tracing::instrument(skip_all)]
 pub fn trace(
     elf: &PathBuf,
     inputs: &[u8],
-    input_size: u64,
-    output_size: u64,
-) -> (Vec<RVTraceRow>, JoltDevice) {
-    let term = DefaultTerminal::new();
-    let mut emulator = Emulator::new(Box::new(term));
-    emulator.update_xlen(get_xlen());
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+) -> (
+    Vec<RVTraceRow>,
+    JoltDevice,
+    Option<GuestPanic>,
+    Option<GuestFault>,
+    String,
+    Option<HeapStats>,
+) {
+    let emulator = setup_emulator(elf, inputs, blobs, memory_config);
+    run(emulator, memory_config.max_cycles)
+}
 
-    let mut jolt_device = JoltDevice::new(input_size, output_size);
-    jolt_device.inputs = inputs.to_vec();
-    emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
+/// Like [`trace`], but executes the program's `custom-0` instructions using `handler`.
+#[tracing::instrument(skip_all)]
+pub fn trace_with_custom_instructions(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    handler: CustomInstructionHandler,
+) -> (
+    Vec<RVTraceRow>,
+    JoltDevice,
+    Option<GuestPanic>,
+    Option<GuestFault>,
+    String,
+    Option<HeapStats>,
+) {
+    let mut emulator = setup_emulator(elf, inputs, blobs, memory_config);
+    emulator
+        .get_mut_cpu()
+        .set_custom_instruction_handler(handler);
+    run(emulator, memory_config.max_cycles)
+}
 
-    let mut elf_file = File::open(elf).unwrap();
+/// Like [`trace`], but notifies `observer` of every instruction retirement, memory access and
+/// environment call as the program runs, then returns it along with the trace.
+#[tracing::instrument(skip_all)]
+#[allow(clippy::type_complexity)]
+pub fn trace_with_observer<O: TraceObserver + 'static>(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    observer: O,
+) -> (
+    Vec<RVTraceRow>,
+    JoltDevice,
+    Option<GuestPanic>,
+    Option<GuestFault>,
+    String,
+    Option<HeapStats>,
+    O,
+) {
+    let emulator = setup_emulator(elf, inputs, blobs, memory_config);
+    let tracer = emulator.get_cpu().tracer.clone();
+    let observer = Rc::new(RefCell::new(observer));
+    tracer.subscribe(Box::new(observer.clone()));
+    let (rows, device, guest_panic, guest_fault, guest_log, heap_stats) =
+        run(emulator, memory_config.max_cycles);
+    drop(tracer.take_observers());
+    let observer = Rc::try_unwrap(observer)
+        .unwrap_or_else(|_| unreachable!("the tracer no longer holds the observer"))
+        .into_inner();
+    (
+        rows,
+        device,
+        guest_panic,
+        guest_fault,
+        guest_log,
+        heap_stats,
+        observer,
+    )
+}
 
-    let mut elf_contents = Vec::new();
-    elf_file.read_to_end(&mut elf_contents).unwrap();
+/// A trace recorded by [`trace_sampled`].
+pub struct SampledTrace {
+    /// The recorded instructions, in execution order.
+    pub rows: Vec<RVTraceRow>,
+    /// Number of instructions the program executed, recorded or not.
+    pub total_instructions: u64,
+    pub sampling: Sampling,
+}
 
-    emulator.setup_program(elf_contents);
+/// Like [`trace`], but only records the instructions selected by `sampling`. The program still
+/// runs to completion, but skipping most of the recording makes this much cheaper than [`trace`]
+/// when only statistics about the trace are needed.
+#[tracing::instrument(skip_all)]
+pub fn trace_sampled(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    sampling: Sampling,
+) -> (
+    SampledTrace,
+    JoltDevice,
+    Option<GuestPanic>,
+    Option<GuestFault>,
+    String,
+    Option<HeapStats>,
+) {
+    let emulator = setup_emulator(elf, inputs, blobs, memory_config);
+    let tracer = emulator.get_cpu().tracer.clone();
+    tracer.set_sampling(sampling);
+    let (rows, device, guest_panic, guest_fault, guest_log, heap_stats) =
+        run(emulator, memory_config.max_cycles);
+    let trace = SampledTrace {
+        rows,
+        total_instructions: tracer.num_executed(),
+        sampling,
+    };
+    (
+        trace,
+        device,
+        guest_panic,
+        guest_fault,
+        guest_log,
+        heap_stats,
+    )
+}
 
+fn run(
+    mut emulator: Emulator,
+    max_cycles: Option<u64>,
+) -> (
+    Vec<RVTraceRow>,
+    JoltDevice,
+    Option<GuestPanic>,
+    Option<GuestFault>,
+    String,
+    Option<HeapStats>,
+) {
     let mut prev_pc = 0;
     loop {
         let pc = emulator.get_cpu().read_pc();
@@ -52,10 +195,21 @@ pub fn trace(
         // This is a trick to see if the program has terminated by throwing itself
         // into an infinite loop. It seems to be a good heuristic for now but we
         // should eventually migrate to an explicit shutdown signal.
-        if prev_pc == pc {
+        if prev_pc == pc || emulator.get_cpu().guest_fault().is_some() {
             break;
         }
 
+        if let Some(max_cycles) = max_cycles {
+            if emulator.get_cpu().tracer.num_executed() >= max_cycles {
+                emulator
+                    .get_mut_cpu()
+                    .get_mut_mmu()
+                    .jolt_device
+                    .exceeded_max_cycles = Some(max_cycles);
+                break;
+            }
+        }
+
         prev_pc = pc;
     }
 
@@ -65,8 +219,78 @@ pub fn trace(
     drop(rows);
 
     let device = emulator.get_mut_cpu().get_mut_mmu().jolt_device.clone();
+    let guest_panic = emulator.get_cpu().guest_panic().cloned();
+    let guest_fault = emulator.get_cpu().guest_fault().copied();
+    let guest_log = emulator.get_cpu().guest_log();
+    let heap_stats = emulator.get_cpu().heap_stats();
+
+    (
+        output,
+        device,
+        guest_panic,
+        guest_fault,
+        guest_log,
+        heap_stats,
+    )
+}
+
+/// Loads the program and waits for a GDB client to connect on `address` (logging the bound
+/// address at `info` level), then runs it under the debugger's control. Returns the final I/O device state once the client
+/// detaches or kills the program.
+pub fn debug<A: ToSocketAddrs>(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    address: A,
+) -> io::Result<JoltDevice> {
+    let emulator = setup_emulator(elf, inputs, blobs, memory_config);
+
+    let listener = TcpListener::bind(address)?;
+    tracing::info!(
+        address = %listener.local_addr()?,
+        "Waiting for GDB to connect"
+    );
+    let (stream, _) = listener.accept()?;
 
-    (output, device)
+    GdbStub::new(emulator).serve(stream)
+}
+
+fn setup_emulator(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+) -> Emulator {
+    let term = DefaultTerminal::new();
+    let mut emulator = Emulator::new(Box::new(term));
+    emulator.update_xlen(get_xlen());
+
+    let blob_region = blobs.region();
+    assert!(
+        blob_region.len() as u64 <= memory_config.max_blob_size,
+        "Blobs take {} bytes, but max_blob_size is {} bytes",
+        blob_region.len(),
+        memory_config.max_blob_size
+    );
+    let mut jolt_device = JoltDevice::new(memory_config);
+    jolt_device.inputs = inputs.to_vec();
+    jolt_device.blobs = blobs.clone();
+    let blob_start = jolt_device.memory_layout.blob_start;
+    emulator.get_mut_cpu().get_mut_mmu().jolt_device = jolt_device;
+
+    let mut elf_file = File::open(elf).unwrap();
+
+    let mut elf_contents = Vec::new();
+    elf_file.read_to_end(&mut elf_contents).unwrap();
+
+    emulator.setup_program(elf_contents);
+    // RAM is allocated by `setup_program`
+    let mmu = emulator.get_mut_cpu().get_mut_mmu();
+    for (offset, byte) in blob_region.into_iter().enumerate() {
+        mmu.store_raw(blob_start + offset as u64, byte);
+    }
+    emulator
 }
 
 #[tracing::instrument(skip_all)]
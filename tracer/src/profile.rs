@@ -0,0 +1,313 @@
+//! Cycle profiling for guest programs.
+//!
+//! A [`Profiler`] consumes retired instructions (in execution order) and attributes
+//! their cycles to the enclosing function, to the source line they were compiled from,
+//! and to the call stack active at the time. Function boundaries come from the ELF
+//! symbol table; source lines come from DWARF debug info, if the guest was built with it.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use addr2line::gimli::{EndianRcSlice, RunTimeEndian};
+use common::rv_trace::{ELFInstruction, RV32IM};
+use object::{Object, ObjectSymbol, SymbolKind};
+
+const UNKNOWN_SYMBOL: &str = "[unknown]";
+
+/// Cycle counts aggregated by function, by source line, and by call stack.
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    pub total_cycles: usize,
+    /// (function name, cycles), sorted by descending cycle count.
+    pub functions: Vec<(String, usize)>,
+    /// ("file:line", cycles), sorted by descending cycle count. Empty if the ELF
+    /// contains no line information.
+    pub lines: Vec<(String, usize)>,
+    /// (call stack, outermost frame first, cycles spent with exactly that stack).
+    pub stacks: Vec<(Vec<String>, usize)>,
+}
+
+impl Profile {
+    /// Renders the call-stack profile in the "folded stacks" format consumed by
+    /// `flamegraph.pl` and `inferno-flamegraph`: one `frame;frame;frame cycles` per line.
+    pub fn folded(&self) -> String {
+        let mut folded = String::new();
+        for (stack, cycles) in self.stacks.iter() {
+            writeln!(folded, "{} {}", stack.join(";"), cycles).unwrap();
+        }
+        folded
+    }
+
+    pub fn write_folded<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.folded().as_bytes())
+    }
+}
+
+/// Maps instruction addresses to the source lines they were compiled from, using the ELF's
+/// DWARF debug info.
+pub struct SourceLines(addr2line::Context<EndianRcSlice<RunTimeEndian>>);
+
+impl SourceLines {
+    /// Returns `None` if the ELF cannot be parsed or has no debug info.
+    pub fn new(elf: &[u8]) -> Option<Self> {
+        let obj = object::File::parse(elf).ok()?;
+        Self::from_object(&obj)
+    }
+
+    fn from_object(obj: &object::File) -> Option<Self> {
+        addr2line::Context::new(obj).ok().map(Self)
+    }
+
+    /// The `file:line` (or just the file, if the line is unknown) of the instruction at
+    /// `address`.
+    pub fn find(&self, address: u64) -> Option<String> {
+        let location = self.0.find_location(address).ok()??;
+        match (location.file, location.line) {
+            (Some(file), Some(line)) => Some(format!("{}:{}", file, line)),
+            (Some(file), None) => Some(file.to_string()),
+            _ => None,
+        }
+    }
+}
+
+struct FunctionSymbol {
+    start: u64,
+    end: u64,
+    name: String,
+}
+
+enum ControlTransfer {
+    Call,
+    Return,
+}
+
+/// Accumulates per-function, per-line, and per-stack cycle counts over a trace.
+pub struct Profiler {
+    functions: Vec<FunctionSymbol>,
+    lines: Option<SourceLines>,
+    /// Shadow call stack of indices into `functions` (`functions.len()` denotes an
+    /// address not covered by any symbol).
+    stack: Vec<usize>,
+    pending_transfer: Option<ControlTransfer>,
+    total_cycles: usize,
+    function_cycles: HashMap<usize, usize>,
+    address_cycles: HashMap<u64, usize>,
+    stack_cycles: HashMap<Vec<usize>, usize>,
+}
+
+impl Profiler {
+    /// Fails if `elf` isn't a valid ELF file.
+    pub fn new(elf: &[u8]) -> Result<Self, object::Error> {
+        let obj = object::File::parse(elf)?;
+
+        let mut functions: Vec<FunctionSymbol> = obj
+            .symbols()
+            .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.size() > 0)
+            .filter_map(|symbol| {
+                let name = symbol.name().ok()?;
+                Some(FunctionSymbol {
+                    start: symbol.address(),
+                    end: symbol.address() + symbol.size(),
+                    name: addr2line::demangle_auto(Cow::from(name), None).into_owned(),
+                })
+            })
+            .collect();
+        functions.sort_by_key(|function| function.start);
+
+        let lines = SourceLines::from_object(&obj);
+
+        Ok(Self {
+            functions,
+            lines,
+            stack: Vec::new(),
+            pending_transfer: None,
+            total_cycles: 0,
+            function_cycles: HashMap::new(),
+            address_cycles: HashMap::new(),
+            stack_cycles: HashMap::new(),
+        })
+    }
+
+    /// Attributes `cycles` to the instruction at `instruction.address`. Instructions
+    /// must be recorded in the order they were executed so that calls and returns
+    /// can be reconstructed.
+    pub fn record(&mut self, instruction: &ELFInstruction, cycles: usize) {
+        let function = self.lookup(instruction.address);
+
+        match self.pending_transfer.take() {
+            Some(ControlTransfer::Call) => self.stack.push(function),
+            Some(ControlTransfer::Return) if self.stack.len() > 1 => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+        // Tail calls, fallthroughs between adjacent symbols, and returns from
+        // frames we never saw called all show up as a mismatch with the top frame.
+        match self.stack.last_mut() {
+            Some(top) => *top = function,
+            None => self.stack.push(function),
+        }
+
+        self.total_cycles += cycles;
+        *self.function_cycles.entry(function).or_default() += cycles;
+        *self.address_cycles.entry(instruction.address).or_default() += cycles;
+        match self.stack_cycles.get_mut(self.stack.as_slice()) {
+            Some(count) => *count += cycles,
+            None => {
+                self.stack_cycles.insert(self.stack.clone(), cycles);
+            }
+        }
+
+        self.pending_transfer = control_transfer(instruction);
+    }
+
+    pub fn finish(self) -> Profile {
+        let function_name = |index: usize| self.function_name(index);
+
+        let mut functions: Vec<_> = self
+            .function_cycles
+            .iter()
+            .map(|(index, cycles)| (function_name(*index), *cycles))
+            .collect();
+        functions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut line_cycles = HashMap::<String, usize>::new();
+        if let Some(source_lines) = &self.lines {
+            for (address, cycles) in self.address_cycles.iter() {
+                if let Some(location) = source_lines.find(*address) {
+                    *line_cycles.entry(location).or_default() += cycles;
+                }
+            }
+        }
+        let mut lines: Vec<_> = line_cycles.into_iter().collect();
+        lines.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut stacks: Vec<_> = self
+            .stack_cycles
+            .iter()
+            .map(|(stack, cycles)| {
+                let frames = stack.iter().map(|index| function_name(*index)).collect();
+                (frames, *cycles)
+            })
+            .collect();
+        stacks.sort();
+
+        Profile {
+            total_cycles: self.total_cycles,
+            functions,
+            lines,
+            stacks,
+        }
+    }
+
+    /// The functions on the call stack of the last recorded instruction, outermost first.
+    pub(crate) fn call_stack(&self) -> Vec<String> {
+        self.stack
+            .iter()
+            .map(|index| self.function_name(*index))
+            .collect()
+    }
+
+    /// The `file:line` of the instruction at `address`, if the ELF has debug info.
+    pub(crate) fn location(&self, address: u64) -> Option<String> {
+        self.lines.as_ref()?.find(address)
+    }
+
+    fn function_name(&self, index: usize) -> String {
+        match self.functions.get(index) {
+            Some(function) => function.name.clone(),
+            None => UNKNOWN_SYMBOL.to_string(),
+        }
+    }
+
+    fn lookup(&self, address: u64) -> usize {
+        let candidate = self
+            .functions
+            .partition_point(|function| function.start <= address);
+        if candidate > 0 && address < self.functions[candidate - 1].end {
+            candidate - 1
+        } else {
+            self.functions.len()
+        }
+    }
+}
+
+/// Classifies jumps using the standard RISC-V calling convention: a jump that links
+/// into `ra` is a call, and `jalr x0, 0(ra)` is a return.
+fn control_transfer(instruction: &ELFInstruction) -> Option<ControlTransfer> {
+    const RA: u64 = 1;
+    match (instruction.opcode, instruction.rd, instruction.rs1) {
+        (RV32IM::JAL | RV32IM::JALR, Some(RA), _) => Some(ControlTransfer::Call),
+        (RV32IM::JALR, Some(0), Some(RA)) => Some(ControlTransfer::Return),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiler(functions: &[(&str, u64, u64)]) -> Profiler {
+        Profiler {
+            functions: functions
+                .iter()
+                .map(|(name, start, end)| FunctionSymbol {
+                    start: *start,
+                    end: *end,
+                    name: name.to_string(),
+                })
+                .collect(),
+            lines: None,
+            stack: Vec::new(),
+            pending_transfer: None,
+            total_cycles: 0,
+            function_cycles: HashMap::new(),
+            address_cycles: HashMap::new(),
+            stack_cycles: HashMap::new(),
+        }
+    }
+
+    fn instruction(
+        address: u64,
+        opcode: RV32IM,
+        rd: Option<u64>,
+        rs1: Option<u64>,
+    ) -> ELFInstruction {
+        ELFInstruction {
+            address,
+            opcode,
+            rs1,
+            rs2: None,
+            rd,
+            imm: None,
+            virtual_sequence_remaining: None,
+        }
+    }
+
+    #[test]
+    fn call_and_return() {
+        let mut profiler = profiler(&[("main", 0x100, 0x200), ("helper", 0x200, 0x300)]);
+        profiler.record(&instruction(0x100, RV32IM::ADDI, Some(5), Some(0)), 1);
+        profiler.record(&instruction(0x104, RV32IM::JAL, Some(1), None), 1);
+        profiler.record(&instruction(0x200, RV32IM::MUL, Some(5), Some(5)), 4);
+        profiler.record(&instruction(0x204, RV32IM::JALR, Some(0), Some(1)), 1);
+        profiler.record(&instruction(0x108, RV32IM::ADDI, Some(5), Some(5)), 1);
+        profiler.record(&instruction(0x400, RV32IM::ADDI, Some(5), Some(5)), 1);
+
+        let profile = profiler.finish();
+        assert_eq!(profile.total_cycles, 9);
+        assert_eq!(
+            profile.functions,
+            vec![
+                ("helper".to_string(), 5),
+                ("main".to_string(), 3),
+                (UNKNOWN_SYMBOL.to_string(), 1)
+            ]
+        );
+        assert_eq!(profile.folded(), "[unknown] 1\nmain 3\nmain;helper 5\n");
+    }
+}
@@ -0,0 +1,446 @@
+//! Replay checking: runs a program twice on the same inputs and reports the first point at which
+//! the two executions differ.
+//!
+//! A guest's execution is a function of its inputs, except where the host supplies values
+//! during the run: the results of `custom-0` instructions, computed by the host's
+//! [`CustomInstructionHandler`], and the environment calls serviced by the host. (The emulator's
+//! clock advances with the instruction count, so it cannot differ between runs.) A handler that
+//! consults the wall clock, a random number generator, or the iteration order of a `HashMap`
+//! makes the guest nondeterministic, and proofs of its trace then fail intermittently, e.g. when
+//! the prover and the party checking its outputs trace the program separately.
+//!
+//! [`check_replay`] records the first run, then compares the second one against it as it
+//! executes: every instruction's registers and memory access, every environment call, and
+//! finally the program's outputs. The first difference is reported with the instruction count,
+//! the address, call stack and source line of the instruction, and which kind of value diverged.
+
+use std::{cell::RefCell, fmt, fs, path::PathBuf, rc::Rc};
+
+use common::rv_trace::{Blobs, MemoryConfig, RVTraceRow, RV32IM};
+
+use crate::{
+    emulator::cpu::CustomInstructionHandler,
+    profile::Profiler,
+    setup_emulator,
+    trace::{Ecall, Sampling, TraceObserver},
+};
+
+/// The kind of value that differed between two runs of a program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NondeterminismSource {
+    /// The result a `custom-0` instruction's host handler returned.
+    HostCall,
+    /// The number or arguments of an environment call.
+    Ecall,
+    /// Any other register value, memory access or control flow. Only reported if every value
+    /// supplied by the host matched, which points at the emulator itself.
+    Execution,
+    /// The program's outputs, panic or debug log.
+    Output,
+}
+
+/// The first difference between two runs of a program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayDivergence {
+    pub source: NondeterminismSource,
+    /// Number of instructions both runs executed before the divergence.
+    pub cycle: u64,
+    /// Address of the diverging instruction, unless the runs only differ in their outputs.
+    pub address: Option<u64>,
+    /// Functions on the call stack of the diverging instruction, outermost first.
+    pub call_stack: Vec<String>,
+    /// `file:line` of the diverging instruction, if the ELF has debug info.
+    pub location: Option<String>,
+    /// The diverging value in the first run.
+    pub first: String,
+    /// The diverging value in the second run.
+    pub second: String,
+}
+
+impl fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} diverged at cycle {}", self.source, self.cycle)?;
+        if let Some(address) = self.address {
+            write!(f, " (pc {:#x})", address)?;
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        writeln!(f)?;
+        if !self.call_stack.is_empty() {
+            writeln!(f, "call stack: {}", self.call_stack.join(" > "))?;
+        }
+        writeln!(f, "first run:  {}", self.first)?;
+        write!(f, "second run: {}", self.second)
+    }
+}
+
+/// Runs `elf` twice with `inputs` and `blobs`, executing `custom-0` instructions with
+/// `handler`, and compares the two runs. Returns the number of instructions executed, or the first
+/// divergence.
+#[tracing::instrument(skip_all)]
+pub fn check_replay(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    handler: Option<CustomInstructionHandler>,
+) -> Result<u64, Box<ReplayDivergence>> {
+    let elf_contents = fs::read(elf).unwrap();
+    let recording = Rc::new(RefCell::new(Recording::default()));
+    let first = run_observed(
+        elf,
+        inputs,
+        blobs,
+        memory_config,
+        handler,
+        recording.clone(),
+    );
+    let recording = Rc::try_unwrap(recording)
+        .unwrap_or_else(|_| unreachable!("the tracer no longer holds the observer"))
+        .into_inner();
+
+    let checker = Rc::new(RefCell::new(ReplayChecker::new(
+        recording,
+        Profiler::new(&elf_contents).ok(),
+    )));
+    let second = run_observed(elf, inputs, blobs, memory_config, handler, checker.clone());
+    let checker = Rc::try_unwrap(checker)
+        .unwrap_or_else(|_| unreachable!("the tracer no longer holds the observer"))
+        .into_inner();
+    checker.finish(&first, &second)
+}
+
+/// What a run produced besides its trace, compared once both runs have finished.
+struct RunOutput {
+    outputs: String,
+    cycles: u64,
+}
+
+fn run_observed<O: TraceObserver + 'static>(
+    elf: &PathBuf,
+    inputs: &[u8],
+    blobs: &Blobs,
+    memory_config: &MemoryConfig,
+    handler: Option<CustomInstructionHandler>,
+    observer: Rc<RefCell<O>>,
+) -> RunOutput {
+    let mut emulator = setup_emulator(elf, inputs, blobs, memory_config);
+    if let Some(handler) = handler {
+        emulator
+            .get_mut_cpu()
+            .set_custom_instruction_handler(handler);
+    }
+    let tracer = emulator.get_cpu().tracer.clone();
+    // The observer sees every row, so the tracer's own trace only needs to keep the first.
+    tracer.set_sampling(Sampling {
+        window: 1,
+        period: u64::MAX,
+    });
+    tracer.subscribe(Box::new(observer));
+    let (_, device, guest_panic, guest_fault, guest_log, _) =
+        crate::run(emulator, memory_config.max_cycles);
+    drop(tracer.take_observers());
+    RunOutput {
+        outputs: format!(
+            "outputs {:?}, panic {:?}, fault {:?}, log {:?}",
+            device.outputs, guest_panic, guest_fault, guest_log
+        ),
+        cycles: tracer.num_executed(),
+    }
+}
+
+/// Every instruction and environment call of a run, in execution order.
+#[derive(Default)]
+struct Recording {
+    rows: Vec<RVTraceRow>,
+    /// Environment calls, with the number of instructions executed before each.
+    ecalls: Vec<(u64, Ecall)>,
+}
+
+impl TraceObserver for Recording {
+    fn on_instruction(&mut self, row: &RVTraceRow) {
+        self.rows.push(row.clone());
+    }
+
+    fn on_ecall(&mut self, ecall: &Ecall) {
+        self.ecalls.push((self.rows.len() as u64, *ecall));
+    }
+}
+
+/// Compares a run against a [`Recording`] of an earlier one as it executes, keeping the first
+/// divergence.
+struct ReplayChecker {
+    recording: Recording,
+    /// Tracks the call stack, and resolves source lines, for the report.
+    profiler: Option<Profiler>,
+    cycle: u64,
+    next_ecall: usize,
+    divergence: Option<ReplayDivergence>,
+}
+
+impl ReplayChecker {
+    fn new(recording: Recording, profiler: Option<Profiler>) -> Self {
+        Self {
+            recording,
+            profiler,
+            cycle: 0,
+            next_ecall: 0,
+            divergence: None,
+        }
+    }
+
+    fn diverge(
+        &mut self,
+        source: NondeterminismSource,
+        address: Option<u64>,
+        first: String,
+        second: String,
+    ) {
+        let (call_stack, location) = match (&self.profiler, address) {
+            (Some(profiler), Some(address)) => (profiler.call_stack(), profiler.location(address)),
+            _ => (vec![], None),
+        };
+        self.divergence = Some(ReplayDivergence {
+            source,
+            cycle: self.cycle,
+            address,
+            call_stack,
+            location,
+            first,
+            second,
+        });
+    }
+
+    fn finish(
+        mut self,
+        first: &RunOutput,
+        second: &RunOutput,
+    ) -> Result<u64, Box<ReplayDivergence>> {
+        if self.divergence.is_none() {
+            if first.cycles != second.cycles {
+                // The second run stopped early; its last instruction matched the recording.
+                self.diverge(
+                    NondeterminismSource::Execution,
+                    None,
+                    format!("{} instructions", first.cycles),
+                    format!("{} instructions", second.cycles),
+                );
+            } else if let Some(&(_, ecall)) = self.recording.ecalls.get(self.next_ecall) {
+                let first = format!("{:?}", ecall);
+                self.diverge(
+                    NondeterminismSource::Ecall,
+                    Some(ecall.address),
+                    first,
+                    "no environment call".to_string(),
+                );
+            } else if first.outputs != second.outputs {
+                self.diverge(
+                    NondeterminismSource::Output,
+                    None,
+                    first.outputs.clone(),
+                    second.outputs.clone(),
+                );
+            }
+        }
+        match self.divergence {
+            Some(divergence) => Err(Box::new(divergence)),
+            None => Ok(second.cycles),
+        }
+    }
+}
+
+impl TraceObserver for ReplayChecker {
+    fn on_instruction(&mut self, row: &RVTraceRow) {
+        if self.divergence.is_some() {
+            return;
+        }
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.record(&row.instruction, 1);
+        }
+        match self.recording.rows.get(self.cycle as usize) {
+            Some(recorded) if recorded == row => {}
+            Some(recorded) => {
+                let source = if row.instruction.opcode == RV32IM::CUSTOM_0
+                    && recorded.instruction == row.instruction
+                    && recorded.register_state.rs1_val == row.register_state.rs1_val
+                    && recorded.register_state.rs2_val == row.register_state.rs2_val
+                {
+                    NondeterminismSource::HostCall
+                } else {
+                    NondeterminismSource::Execution
+                };
+                let (first, second) = (format!("{:?}", recorded), format!("{:?}", row));
+                self.diverge(source, Some(row.instruction.address), first, second);
+            }
+            None => {
+                let second = format!("{:?}", row);
+                self.diverge(
+                    NondeterminismSource::Execution,
+                    Some(row.instruction.address),
+                    "no further instructions".to_string(),
+                    second,
+                );
+            }
+        }
+        self.cycle += 1;
+    }
+
+    fn on_ecall(&mut self, ecall: &Ecall) {
+        if self.divergence.is_some() {
+            return;
+        }
+        let recorded = self.recording.ecalls.get(self.next_ecall).copied();
+        self.next_ecall += 1;
+        match recorded {
+            Some((cycle, recorded)) if cycle == self.cycle && recorded == *ecall => {}
+            recorded => {
+                let first = match recorded {
+                    Some((cycle, recorded)) => format!("{:?} at cycle {}", recorded, cycle),
+                    None => "no environment call".to_string(),
+                };
+                let second = format!("{:?}", ecall);
+                self.diverge(
+                    NondeterminismSource::Ecall,
+                    Some(ecall.address),
+                    first,
+                    second,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::rv_trace::{ELFInstruction, RegisterState};
+
+    fn row(opcode: RV32IM, rs1_val: u64, rd_post_val: u64) -> RVTraceRow {
+        RVTraceRow {
+            instruction: ELFInstruction {
+                address: 0x80000000,
+                opcode,
+                rs1: Some(11),
+                rs2: None,
+                rd: Some(10),
+                imm: None,
+                virtual_sequence_remaining: None,
+            },
+            register_state: RegisterState {
+                rs1_val: Some(rs1_val),
+                rs2_val: None,
+                rd_post_val: Some(rd_post_val),
+            },
+            memory_state: None,
+            advice_value: None,
+        }
+    }
+
+    fn output(cycles: u64) -> RunOutput {
+        RunOutput {
+            outputs: String::new(),
+            cycles,
+        }
+    }
+
+    fn replay(recording: Recording, rows: &[RVTraceRow], ecalls: &[(u64, Ecall)]) -> ReplayChecker {
+        let mut checker = ReplayChecker::new(recording, None);
+        let mut ecalls = ecalls.iter().peekable();
+        for (cycle, row) in rows.iter().enumerate() {
+            while let Some((_, ecall)) = ecalls.next_if(|(at, _)| *at == cycle as u64) {
+                checker.on_ecall(ecall);
+            }
+            checker.on_instruction(row);
+        }
+        for (_, ecall) in ecalls {
+            checker.on_ecall(ecall);
+        }
+        checker
+    }
+
+    #[test]
+    fn identical_runs() {
+        let rows = vec![row(RV32IM::ADDI, 1, 2), row(RV32IM::CUSTOM_0, 2, 7)];
+        let ecalls = vec![(
+            1,
+            Ecall {
+                address: 0x80000004,
+                number: 1,
+                args: [0; 6],
+            },
+        )];
+        let recording = Recording {
+            rows: rows.clone(),
+            ecalls: ecalls.clone(),
+        };
+        let checker = replay(recording, &rows, &ecalls);
+        assert_eq!(checker.finish(&output(2), &output(2)), Ok(2));
+    }
+
+    #[test]
+    fn host_call_divergence() {
+        let recording = Recording {
+            rows: vec![row(RV32IM::CUSTOM_0, 2, 7), row(RV32IM::ADDI, 7, 8)],
+            ecalls: vec![],
+        };
+        // The handler returned a different value; everything after it differs too
+        let rows = vec![row(RV32IM::CUSTOM_0, 2, 9), row(RV32IM::ADDI, 9, 10)];
+        let divergence = replay(recording, &rows, &[])
+            .finish(&output(2), &output(2))
+            .unwrap_err();
+        assert_eq!(divergence.source, NondeterminismSource::HostCall);
+        assert_eq!(divergence.cycle, 0);
+        assert_eq!(divergence.address, Some(0x80000000));
+
+        // Different operands mean the divergence happened earlier
+        let recording = Recording {
+            rows: vec![row(RV32IM::CUSTOM_0, 2, 7)],
+            ecalls: vec![],
+        };
+        let rows = vec![row(RV32IM::CUSTOM_0, 3, 7)];
+        let divergence = replay(recording, &rows, &[])
+            .finish(&output(1), &output(1))
+            .unwrap_err();
+        assert_eq!(divergence.source, NondeterminismSource::Execution);
+    }
+
+    #[test]
+    fn ecall_and_output_divergence() {
+        let rows = vec![row(RV32IM::ADDI, 1, 2)];
+        let ecall = Ecall {
+            address: 0x80000004,
+            number: 1,
+            args: [0; 6],
+        };
+        let recording = Recording {
+            rows: rows.clone(),
+            ecalls: vec![(1, ecall)],
+        };
+        let changed = Ecall {
+            args: [1, 0, 0, 0, 0, 0],
+            ..ecall
+        };
+        let divergence = replay(recording, &rows, &[(1, changed)])
+            .finish(&output(1), &output(1))
+            .unwrap_err();
+        assert_eq!(divergence.source, NondeterminismSource::Ecall);
+        assert_eq!(divergence.cycle, 1);
+
+        let recording = Recording {
+            rows: rows.clone(),
+            ecalls: vec![],
+        };
+        let divergence = replay(recording, &rows, &[])
+            .finish(
+                &output(1),
+                &RunOutput {
+                    outputs: "different".to_string(),
+                    cycles: 1,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(divergence.source, NondeterminismSource::Output);
+        assert_eq!(divergence.address, None);
+    }
+}
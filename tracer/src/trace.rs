@@ -1,12 +1,76 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 use common::rv_trace::{ELFInstruction, MemoryState, RVTraceRow, RegisterState};
 
 use crate::emulator::cpu::Xlen;
 
+/// Selects which instructions of a trace are recorded: of every `period` consecutive windows of
+/// `window` instructions, only the first. Both must be nonzero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sampling {
+    pub window: u64,
+    pub period: u64,
+}
+
+impl Sampling {
+    /// Whether the instruction at `index`, in execution order, is recorded.
+    pub fn records(&self, index: u64) -> bool {
+        (index / self.window) % self.period == 0
+    }
+}
+
+/// An environment call made by the guest. Environment calls are not part of the trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ecall {
+    /// Address of the `ecall` instruction.
+    pub address: u64,
+    /// The call number, from `a7`.
+    pub number: u64,
+    /// The arguments, from `a0` through `a5`.
+    pub args: [u64; 6],
+}
+
+/// Receives events from the emulator as it traces a program, e.g. to track taint or check
+/// invariants without modifying the emulator loop. Every method defaults to doing nothing.
+///
+/// Observers see every executed instruction, including those a [`Sampling`] leaves out of the
+/// trace.
+pub trait TraceObserver {
+    /// Called when an instruction retires, with its completed row.
+    fn on_instruction(&mut self, _row: &RVTraceRow) {}
+
+    /// Called when the instruction being executed accesses memory, before it retires.
+    fn on_memory(&mut self, _instruction: &ELFInstruction, _memory_state: &MemoryState) {}
+
+    /// Called when the guest makes an environment call, before the emulator handles it.
+    fn on_ecall(&mut self, _ecall: &Ecall) {}
+}
+
+/// Lets the caller keep a handle on an observer it has handed to a [`Tracer`].
+impl<O: TraceObserver> TraceObserver for Rc<RefCell<O>> {
+    fn on_instruction(&mut self, row: &RVTraceRow) {
+        self.borrow_mut().on_instruction(row)
+    }
+
+    fn on_memory(&mut self, instruction: &ELFInstruction, memory_state: &MemoryState) {
+        self.borrow_mut().on_memory(instruction, memory_state)
+    }
+
+    fn on_ecall(&mut self, ecall: &Ecall) {
+        self.borrow_mut().on_ecall(ecall)
+    }
+}
+
 pub struct Tracer {
     pub rows: RefCell<Vec<RVTraceRow>>,
     open: RefCell<bool>,
+    /// Whether the open row is only kept until the instruction retires, for the observers.
+    discard: Cell<bool>,
+    observers: RefCell<Vec<Box<dyn TraceObserver>>>,
+    /// Number of instructions executed so far, whether recorded or not.
+    executed: Cell<u64>,
+    sampling: Cell<Option<Sampling>>,
 }
 
 impl Tracer {
@@ -14,10 +78,45 @@ impl Tracer {
         Self {
             rows: RefCell::new(Vec::new()),
             open: RefCell::new(false),
+            discard: Cell::new(false),
+            observers: RefCell::new(Vec::new()),
+            executed: Cell::new(0),
+            sampling: Cell::new(None),
         }
     }
 
+    /// Only records the instructions selected by `sampling` from now on.
+    pub fn set_sampling(&self, sampling: Sampling) {
+        assert!(sampling.window > 0 && sampling.period > 0);
+        self.sampling.set(Some(sampling));
+    }
+
+    /// Notifies `observer` of the events of every instruction executed from now on.
+    pub fn subscribe(&self, observer: Box<dyn TraceObserver>) {
+        self.observers.try_borrow_mut().unwrap().push(observer);
+    }
+
+    /// Removes and returns every subscribed observer.
+    pub fn take_observers(&self) -> Vec<Box<dyn TraceObserver>> {
+        std::mem::take(&mut *self.observers.try_borrow_mut().unwrap())
+    }
+
+    pub fn num_executed(&self) -> u64 {
+        self.executed.get()
+    }
+
     pub fn start_instruction(&self, inst: ELFInstruction) {
+        let index = self.executed.get();
+        self.executed.set(index + 1);
+        let recorded = match self.sampling.get() {
+            Some(sampling) => sampling.records(index),
+            None => true,
+        };
+        if !recorded && self.observers.try_borrow().unwrap().is_empty() {
+            return;
+        }
+        self.discard.set(!recorded);
+
         let mut inst = inst;
         inst.address = inst.address as u32 as u64;
         *self.open.try_borrow_mut().unwrap() = true;
@@ -65,12 +164,35 @@ impl Tracer {
         }
 
         if let Some(row) = self.rows.try_borrow_mut().unwrap().last_mut() {
+            for observer in self.observers.try_borrow_mut().unwrap().iter_mut() {
+                observer.on_memory(&row.instruction, &memory_state);
+            }
             row.memory_state = Some(memory_state);
         }
     }
 
     pub fn end_instruction(&self) {
-        *self.open.try_borrow_mut().unwrap() = false;
+        let mut open = self.open.try_borrow_mut().unwrap();
+        if !*open {
+            return;
+        }
+        *open = false;
+
+        let mut rows = self.rows.try_borrow_mut().unwrap();
+        if let Some(row) = rows.last() {
+            for observer in self.observers.try_borrow_mut().unwrap().iter_mut() {
+                observer.on_instruction(row);
+            }
+        }
+        if self.discard.get() {
+            rows.pop();
+        }
+    }
+
+    pub fn ecall(&self, ecall: Ecall) {
+        for observer in self.observers.try_borrow_mut().unwrap().iter_mut() {
+            observer.on_ecall(&ecall);
+        }
     }
 }
 